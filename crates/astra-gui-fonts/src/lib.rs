@@ -32,7 +32,7 @@ pub mod inter {
 
 /// JetBrains Mono (variable font).
 ///
-/// Included for future use. Not enabled by default.
+/// Used for code content by `astra-gui-text`. Not enabled by default.
 ///
 /// Source files in repo:
 /// `particles/assets/fonts/jetbrainsmono/JetBrainsMono-VariableFont_wght.ttf`
@@ -0,0 +1,231 @@
+//! Badge component for interactive UI
+//!
+//! A small bubble showing a count, a short status text or just a dot, either
+//! on its own or pinned to a corner of another node, like an unread count on
+//! an inbox button.
+
+use astra_gui::{
+    Color, Component, Content, Corner, CornerShape, FontWeight, HorizontalAlign, Layout, Node,
+    Place, Size, Spacing, Stroke, Style, TextContent, Theme, UiContext, VerticalAlign, ZIndex,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a badge
+#[derive(Debug, Clone, WithBuilders)]
+pub struct BadgeStyle {
+    /// Background color of the bubble
+    pub color: Color,
+    /// Text color of the count or status
+    pub text_color: Color,
+    /// Ring around the bubble separating it from the node it is pinned to
+    pub stroke_color: Color,
+    /// Width of the ring; 0 for none
+    pub stroke_width: f32,
+    /// Font size of the count or status
+    pub font_size: f32,
+    /// Height of the bubble; single characters get a circle this wide
+    pub height: f32,
+    /// Horizontal padding around longer text
+    pub padding: f32,
+    /// Diameter of a dot badge
+    pub dot_size: f32,
+}
+
+impl BadgeStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            color: theme.danger,
+            text_color: theme.on_primary,
+            stroke_color: theme.surface,
+            stroke_width: 2.0,
+            font_size: 12.0,
+            height: 20.0,
+            padding: 6.0,
+            dot_size: 10.0,
+        }
+    }
+}
+
+impl Default for BadgeStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// What a badge shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BadgeContent {
+    Count(u32),
+    Text(String),
+    Dot,
+}
+
+/// A count, status or dot bubble, optionally pinned to another node
+///
+/// On its own a badge is a small pill in the layout. With `anchor` it wraps
+/// the given node and sits centered on one of its corners in the overlay
+/// layer, so it can overflow the node without taking up layout space. A
+/// count of zero hides the badge unless `show_zero` is set, and counts above
+/// `max` are shown as e.g. "99+".
+///
+/// # Example
+///
+/// ```ignore
+/// Badge::count(unread)
+///     .anchor(inbox_button, Corner::TopRight)
+///     .node(&mut ctx)
+/// ```
+pub struct Badge {
+    content: BadgeContent,
+    max: u32,
+    show_zero: bool,
+    anchor: Option<(Node, Corner)>,
+    style: Option<BadgeStyle>,
+}
+
+impl Badge {
+    /// Create a badge showing a count
+    pub fn count(count: u32) -> Self {
+        Self::with_content(BadgeContent::Count(count))
+    }
+
+    /// Create a badge showing a short status text
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::with_content(BadgeContent::Text(text.into()))
+    }
+
+    /// Create a badge that is just a dot
+    pub fn dot() -> Self {
+        Self::with_content(BadgeContent::Dot)
+    }
+
+    fn with_content(content: BadgeContent) -> Self {
+        Self {
+            content,
+            max: 99,
+            show_zero: false,
+            anchor: None,
+            style: None,
+        }
+    }
+
+    /// Set the largest count shown in full; larger counts show as "max+"
+    /// (default: 99)
+    pub fn max(mut self, max: u32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set whether a count of zero is shown (default: false)
+    pub fn show_zero(mut self, show_zero: bool) -> Self {
+        self.show_zero = show_zero;
+        self
+    }
+
+    /// Pin the badge to a corner of `node`
+    pub fn anchor(mut self, node: Node, corner: Corner) -> Self {
+        self.anchor = Some((node, corner));
+        self
+    }
+
+    /// Set a custom style for the badge
+    pub fn with_style(mut self, style: BadgeStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The bubble itself, or `None` when it is hidden
+    fn bubble(&self, style: &BadgeStyle) -> Option<Node> {
+        let label = match &self.content {
+            BadgeContent::Count(0) if !self.show_zero => return None,
+            BadgeContent::Count(count) if *count > self.max => format!("{}+", self.max),
+            BadgeContent::Count(count) => count.to_string(),
+            BadgeContent::Text(text) => text.clone(),
+            BadgeContent::Dot => String::new(),
+        };
+
+        let bubble = Node::new().with_style(Style {
+            fill_color: Some(style.color),
+            stroke: (style.stroke_width > 0.0)
+                .then(|| Stroke::new(Size::lpx(style.stroke_width), style.stroke_color)),
+            corner_shape: Some(CornerShape::Round(Size::lpx(style.height / 2.0))),
+            ..Default::default()
+        });
+        if self.content == BadgeContent::Dot {
+            return Some(
+                bubble
+                    .with_width(Size::lpx(style.dot_size))
+                    .with_height(Size::lpx(style.dot_size)),
+            );
+        }
+
+        // Single characters get a circle rather than a narrow pill
+        let width = if label.chars().count() <= 1 {
+            Size::lpx(style.height)
+        } else {
+            Size::FitContent
+        };
+        Some(
+            bubble
+                .with_width(width)
+                .with_height(Size::lpx(style.height))
+                .with_padding(Spacing::horizontal(Size::lpx(style.padding)))
+                .with_content(Content::Text(
+                    TextContent::new(label)
+                        .with_font_size(Size::lpx(style.font_size))
+                        .with_font_weight(FontWeight::Bold)
+                        .with_color(style.text_color)
+                        .with_h_align(HorizontalAlign::Center)
+                        .with_v_align(VerticalAlign::Center),
+                )),
+        )
+    }
+}
+
+impl Component for Badge {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| BadgeStyle::from_theme(ctx.theme()));
+        let bubble = self.bubble(&style);
+
+        // No generated ID: badges appear and disappear with their counts,
+        // which must not shift the IDs of widgets built after them
+        let Some((node, corner)) = self.anchor else {
+            return bubble.unwrap_or_else(|| {
+                Node::new()
+                    .with_width(Size::lpx(0.0))
+                    .with_height(Size::lpx(0.0))
+            });
+        };
+        let Some(bubble) = bubble else {
+            return node;
+        };
+
+        let (h_align, v_align) = match corner {
+            Corner::TopLeft => (HorizontalAlign::Left, VerticalAlign::Top),
+            Corner::TopRight => (HorizontalAlign::Right, VerticalAlign::Top),
+            Corner::BottomLeft => (HorizontalAlign::Left, VerticalAlign::Bottom),
+            Corner::BottomRight => (HorizontalAlign::Right, VerticalAlign::Bottom),
+        };
+        Node::new()
+            .with_layout_direction(Layout::Stack)
+            .with_children(vec![
+                node,
+                // Zero-sized anchor on the corner; centering the bubble in it
+                // puts the bubble's center on the corner
+                Node::new()
+                    .with_width(Size::lpx(0.0))
+                    .with_height(Size::lpx(0.0))
+                    .with_place(Place::Alignment { h_align, v_align })
+                    .with_z_index(ZIndex::OVERLAY)
+                    .with_layout_direction(Layout::Stack)
+                    .with_child(bubble.with_place(Place::Alignment {
+                        h_align: HorizontalAlign::Center,
+                        v_align: VerticalAlign::Center,
+                    })),
+            ])
+    }
+}
@@ -3,8 +3,8 @@
 //! Provides a clickable button widget with hover and press states.
 
 use astra_gui::{
-    catppuccin::mocha, Color, Component, Content, CornerShape, HorizontalAlign, Node, NodeId, Size,
-    Spacing, Stroke, Style, TextContent, Transition, UiContext, VerticalAlign,
+    Color, Component, Content, CornerShape, HorizontalAlign, Node, NodeId, Size, Spacing, Stroke,
+    Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
 };
 use astra_gui_macros::WithBuilders;
 
@@ -81,22 +81,23 @@ pub struct ButtonStyle {
     pub font_size: f32,
 }
 
-impl Default for ButtonStyle {
-    fn default() -> Self {
+impl ButtonStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
         Self {
             // Fill Colors
-            idle_color: mocha::BASE,
-            hover_color: mocha::MANTLE,
-            pressed_color: mocha::CRUST,
-            disabled_color: mocha::BASE.with_alpha(0.8),
+            idle_color: theme.surface,
+            hover_color: theme.surface_hover,
+            pressed_color: theme.surface_active,
+            disabled_color: theme.surface.with_alpha(0.8),
             // Stroke Colors
-            idle_stroke_color: mocha::SURFACE0,
-            hover_stroke_color: mocha::SURFACE0,
-            pressed_stroke_color: mocha::SURFACE0,
-            disabled_stroke_color: mocha::SURFACE0.with_alpha(0.8),
+            idle_stroke_color: theme.border,
+            hover_stroke_color: theme.border,
+            pressed_stroke_color: theme.border,
+            disabled_stroke_color: theme.border.with_alpha(0.8),
             // Text Colors
-            text_color: mocha::TEXT,
-            disabled_text_color: mocha::SUBTEXT1,
+            text_color: theme.text,
+            disabled_text_color: theme.text_disabled,
             // Others
             padding: Spacing::symmetric(Size::lpx(18.0), Size::lpx(10.0)),
             border_radius: 24.0,
@@ -105,6 +106,12 @@ impl Default for ButtonStyle {
     }
 }
 
+impl Default for ButtonStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A clickable button component
 ///
 /// # Example
@@ -117,7 +124,7 @@ impl Default for ButtonStyle {
 pub struct Button {
     label: String,
     disabled: bool,
-    style: ButtonStyle,
+    style: Option<ButtonStyle>,
     on_click: Option<Box<dyn FnMut()>>,
     on_hover: Option<Box<dyn FnMut()>>,
 }
@@ -128,7 +135,7 @@ impl Button {
         Button {
             label: label.into(),
             disabled: false,
-            style: ButtonStyle::default(),
+            style: None,
             on_click: None,
             on_hover: None,
         }
@@ -142,7 +149,7 @@ impl Button {
 
     /// Set a custom style for the button
     pub fn with_style(mut self, style: ButtonStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
@@ -168,6 +175,10 @@ impl Component for Button {
     fn node(mut self, ctx: &mut UiContext) -> Node {
         // Generate a unique ID for this button
         let id = ctx.generate_id("button");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ButtonStyle::from_theme(ctx.theme()));
 
         // Check for events from last frame and fire callbacks
         if !self.disabled {
@@ -188,46 +199,52 @@ impl Component for Button {
             .with_id(NodeId::new(&id))
             .with_width(Size::FitContent)
             .with_height(Size::FitContent)
-            .with_padding(self.style.padding)
+            .with_padding(style.padding)
             .with_shape(astra_gui::Shape::rect())
             .with_content(Content::Text(TextContent {
                 text: self.label,
-                font_size: Size::lpx(self.style.font_size),
-                color: self.style.text_color,
+                spans: Vec::new(),
+                font_size: Size::lpx(style.font_size),
+                color: style.text_color,
                 h_align: HorizontalAlign::Center,
                 v_align: VerticalAlign::Center,
                 wrap: astra_gui::Wrap::Word,
+                overflow: astra_gui::TextOverflow::Clip,
+                max_lines: None,
                 line_height_multiplier: 1.2,
                 font_weight: astra_gui::FontWeight::Normal,
                 font_style: astra_gui::FontStyle::Normal,
+                font_family: None,
+                font_features: Vec::new(),
+                font_width: astra_gui::FontWidth::Normal,
+                tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
+                outline: None,
+                inherit: astra_gui::TextInherit::NONE,
             }))
             // Declarative styles - no manual state tracking needed!
             .with_style(Style {
-                fill_color: Some(self.style.idle_color),
-                text_color: Some(self.style.text_color),
+                fill_color: Some(style.idle_color),
+                text_color: Some(style.text_color),
                 corner_shape: Some(CornerShape::Round(astra_gui::Size::Logical(
-                    self.style.border_radius,
+                    style.border_radius,
                 ))),
-                stroke: Some(Stroke::new(Size::lpx(1.0), self.style.idle_stroke_color)),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.idle_stroke_color)),
                 ..Default::default()
             })
             .with_hover_style(Style {
-                fill_color: Some(self.style.hover_color),
-                stroke: Some(Stroke::new(Size::lpx(1.0), self.style.hover_stroke_color)),
+                fill_color: Some(style.hover_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.hover_stroke_color)),
                 ..Default::default()
             })
             .with_active_style(Style {
-                fill_color: Some(self.style.pressed_color),
-                stroke: Some(Stroke::new(Size::lpx(2.0), self.style.pressed_stroke_color)),
+                fill_color: Some(style.pressed_color),
+                stroke: Some(Stroke::new(Size::lpx(2.0), style.pressed_stroke_color)),
                 ..Default::default()
             })
             .with_disabled_style(Style {
-                fill_color: Some(self.style.disabled_color),
-                text_color: Some(self.style.disabled_text_color),
-                stroke: Some(Stroke::new(
-                    Size::lpx(1.0),
-                    self.style.disabled_stroke_color,
-                )),
+                fill_color: Some(style.disabled_color),
+                text_color: Some(style.disabled_text_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.disabled_stroke_color)),
                 ..Default::default()
             })
             .with_disabled(self.disabled)
@@ -0,0 +1,140 @@
+//! Canvas component for custom drawing
+//!
+//! A framed area the app draws into with a `Painter`: lines, rects, circles,
+//! paths and text in coordinates local to the canvas. Plots and custom gizmos
+//! need no backend code, since the drawing becomes ordinary output shapes.
+
+use astra_gui::{
+    Color, Component, CornerShape, Node, NodeId, Painter, Size, Spacing, Stroke, Style, Theme,
+    UiContext,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a canvas
+#[derive(Debug, Clone, WithBuilders)]
+pub struct CanvasStyle {
+    /// Background color behind the drawing
+    pub background_color: Color,
+    /// Color of the border
+    pub stroke_color: Color,
+    /// Width of the border (0 for none)
+    pub stroke_width: f32,
+    /// Corner radius of the canvas
+    pub border_radius: f32,
+    /// Inset of the drawing area from the edges
+    pub padding: f32,
+}
+
+impl CanvasStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            background_color: theme.field,
+            stroke_color: theme.border,
+            stroke_width: 1.0,
+            border_radius: 8.0,
+            padding: 8.0,
+        }
+    }
+}
+
+impl Default for CanvasStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// An area drawn by the app
+///
+/// The paint function runs after layout, so it can size the drawing to the
+/// canvas with `Painter::size`. Drawing is clipped to the canvas. The canvas
+/// fills its parent unless given a size; with an ID it receives pointer
+/// events like any other node (`ctx.events_for(id)`), for interactive gizmos.
+///
+/// # Example
+///
+/// ```ignore
+/// let samples = state.samples.clone();
+/// Canvas::new(move |painter| {
+///     let (width, height) = painter.size();
+///     let step = width / (samples.len() - 1) as f32;
+///     painter.path(
+///         samples.iter().enumerate().map(|(i, y)| [i as f32 * step, (1.0 - y) * height]),
+///         2.0,
+///         mocha::BLUE,
+///     );
+/// })
+/// .height(Size::lpx(160.0))
+/// .node(&mut ctx)
+/// ```
+pub struct Canvas {
+    paint: Box<dyn Fn(&mut Painter)>,
+    width: Size,
+    height: Size,
+    id: Option<String>,
+    style: Option<CanvasStyle>,
+}
+
+impl Canvas {
+    /// Create a canvas drawn by `paint`
+    pub fn new(paint: impl Fn(&mut Painter) + 'static) -> Self {
+        Self {
+            paint: Box::new(paint),
+            width: Size::Fill,
+            height: Size::Fill,
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Set the width (default: fill the parent)
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height (default: fill the parent)
+    pub fn height(mut self, height: Size) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set an ID so the canvas receives pointer events
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the canvas
+    pub fn with_style(mut self, style: CanvasStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for Canvas {
+    fn node(self, ctx: &mut UiContext) -> Node {
+        let style = self
+            .style
+            .unwrap_or_else(|| CanvasStyle::from_theme(ctx.theme()));
+
+        // No generated ID: a canvas only needs one to be interacted with, and
+        // then the app names it to read its events
+        let mut node = Node::new()
+            .with_width(self.width)
+            .with_height(self.height)
+            .with_padding(Spacing::all(Size::lpx(style.padding)))
+            .with_style(Style {
+                fill_color: Some(style.background_color),
+                stroke: (style.stroke_width > 0.0)
+                    .then(|| Stroke::new(Size::lpx(style.stroke_width), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                ..Default::default()
+            });
+        if let Some(id) = self.id {
+            node = node.with_id(NodeId::new(id));
+        }
+
+        node.with_painter(self.paint)
+    }
+}
@@ -0,0 +1,339 @@
+//! Checkbox component for interactive UI
+//!
+//! Provides a labeled checkbox with an animated checkmark, an indeterminate
+//! ("some selected") state and keyboard toggling while focused.
+
+use std::f32::consts::FRAC_PI_4;
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, Key, Layout, MouseButton, NamedKey, Node, NodeId, Size,
+    Stroke, Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a checkbox
+#[derive(Debug, Clone, WithBuilders)]
+pub struct CheckboxStyle {
+    /// Box color when unchecked
+    pub unchecked_color: Color,
+    /// Box color when unchecked and hovered
+    pub hover_color: Color,
+    /// Box color when checked or indeterminate
+    pub checked_color: Color,
+    /// Color of the checkmark and indeterminate dash
+    pub check_color: Color,
+    /// Box outline color
+    pub stroke_color: Color,
+    /// Box outline color while focused
+    pub focus_stroke_color: Color,
+    /// Label text color
+    pub label_color: Color,
+    /// Label text color when disabled
+    pub disabled_label_color: Color,
+    /// Side length of the box
+    pub box_size: f32,
+    /// Corner radius of the box
+    pub corner_radius: f32,
+    /// Thickness of the checkmark strokes, relative to the box size
+    pub check_thickness: f32,
+    /// Gap between the box and the label
+    pub gap: f32,
+    /// Label font size
+    pub font_size: f32,
+}
+
+impl CheckboxStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            unchecked_color: theme.field,
+            hover_color: theme.field_hover,
+            checked_color: theme.primary,
+            check_color: theme.on_primary,
+            stroke_color: theme.border,
+            focus_stroke_color: theme.primary,
+            label_color: theme.text,
+            disabled_label_color: theme.text_disabled,
+            box_size: 22.0,
+            corner_radius: 5.0,
+            check_thickness: 0.14,
+            gap: 10.0,
+            font_size: 18.0,
+        }
+    }
+}
+
+impl Default for CheckboxStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// IDs of the clickable parts of the checkbox with the given `id`
+fn part_ids(id: &str) -> [String; 4] {
+    [
+        id.to_string(),
+        format!("{}_box", id),
+        format!("{}_check", id),
+        format!("{}_dash", id),
+    ]
+}
+
+/// Whether the checkbox with the given `id` was toggled last frame: clicked
+/// anywhere on its box or label, or Space/Enter pressed while its box is focused
+///
+/// Pairs with [`checkbox`] for the function-style API.
+pub fn checkbox_clicked(ctx: &UiContext, id: &str) -> bool {
+    let [_, box_id, ..] = part_ids(id);
+    let clicked = part_ids(id).iter().any(|part| ctx.was_clicked(part));
+    let input = ctx.input();
+    let key_pressed = ctx.is_focused(&box_id)
+        && (input.is_named_key_just_pressed(NamedKey::Space)
+            || input.is_named_key_just_pressed(NamedKey::Enter));
+    clicked || key_pressed
+}
+
+/// Function-style checkbox with an explicit `id`, using the theme's style
+///
+/// # Example
+///
+/// ```ignore
+/// if checkbox_clicked(ctx, "vsync") {
+///     settings.vsync = !settings.vsync;
+/// }
+/// let node = checkbox(ctx, "vsync", settings.vsync, "V-Sync");
+/// ```
+pub fn checkbox(ctx: &mut UiContext, id: &str, checked: bool, label: impl Into<String>) -> Node {
+    Checkbox::new(checked).with_id(id).label(label).node(ctx)
+}
+
+/// A checkbox component
+///
+/// # Example
+///
+/// ```ignore
+/// Checkbox::new(show_grid)
+///     .label("Show grid")
+///     .on_toggle(|checked| println!("Show grid: {}", checked))
+///     .node(&mut ctx)
+/// ```
+pub struct Checkbox {
+    checked: bool,
+    indeterminate: bool,
+    label: Option<String>,
+    id: Option<String>,
+    disabled: bool,
+    style: Option<CheckboxStyle>,
+    on_toggle: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Checkbox {
+    /// Create a new checkbox with the given initial value
+    pub fn new(checked: bool) -> Self {
+        Checkbox {
+            checked,
+            indeterminate: false,
+            label: None,
+            id: None,
+            disabled: false,
+            style: None,
+            on_toggle: None,
+        }
+    }
+
+    /// Show the indeterminate dash instead of the checkmark (e.g. for a
+    /// "select all" box over a partial selection); toggling it checks the box
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Set the label shown next to the box; clicking it toggles the checkbox
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one, to query it with
+    /// [`checkbox_clicked`]
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set whether the checkbox is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Set a custom style for the checkbox
+    pub fn with_style(mut self, style: CheckboxStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the checkbox is toggled
+    ///
+    /// The callback receives the new value: the opposite of the current one,
+    /// or `true` when the checkbox is indeterminate
+    pub fn on_toggle(mut self, f: impl FnMut(bool) + 'static) -> Self {
+        self.on_toggle = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for Checkbox {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("checkbox"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| CheckboxStyle::from_theme(ctx.theme()));
+        let [_, box_id, check_id, dash_id] = part_ids(&id);
+
+        // Check for toggles from last frame and fire callback; a click focuses
+        // the box for keyboard toggling until clicking elsewhere or Escape
+        if !self.disabled {
+            if checkbox_clicked(ctx, &id) {
+                if let Some(ref mut on_toggle) = self.on_toggle {
+                    on_toggle(self.indeterminate || !self.checked);
+                }
+            }
+            let parts = part_ids(&id);
+            let pressed_outside = ctx.input().is_button_just_pressed(MouseButton::Left)
+                && !parts.iter().any(|part| ctx.is_hovered(part));
+            let escape_pressed = ctx
+                .input()
+                .is_key_just_pressed(&Key::Named(NamedKey::Escape));
+            if parts.iter().any(|part| ctx.was_clicked(part)) {
+                ctx.set_focus(Some(&box_id));
+            } else if ctx.is_focused(&box_id) && (pressed_outside || escape_pressed) {
+                ctx.set_focus(None);
+            }
+        } else if ctx.is_focused(&box_id) {
+            ctx.set_focus(None);
+        }
+
+        let filled = self.checked || self.indeterminate;
+        let size = style.box_size;
+        let thickness = (size * style.check_thickness).max(1.0);
+        let bar = |length: f32, center: [f32; 2], rotation: f32| {
+            Node::new()
+                .with_width(Size::lpx(length + thickness))
+                .with_height(Size::lpx(thickness))
+                .with_translation(astra_gui::Translation::new(
+                    Size::lpx(center[0] * size - (length + thickness) / 2.0),
+                    Size::lpx(center[1] * size - thickness / 2.0),
+                ))
+                .with_rotation(rotation)
+                .with_style(Style {
+                    fill_color: Some(style.check_color),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(thickness / 2.0))),
+                    ..Default::default()
+                })
+        };
+        // Shows the mark it holds, dropping in as it fades in
+        let mark = |mark_id: &str, shown: bool, bars: Vec<Node>| {
+            Node::new()
+                .with_id(NodeId::new(mark_id))
+                .with_width(Size::Fill)
+                .with_height(Size::Fill)
+                .with_layout_direction(Layout::Stack)
+                .with_style(Style {
+                    opacity: Some(if shown { 1.0 } else { 0.0 }),
+                    translation_y: Some(Size::lpx(if shown { 0.0 } else { -size / 6.0 })),
+                    ..Default::default()
+                })
+                .with_transition(Transition::quick())
+                .with_children(bars)
+        };
+
+        // Checkmark: a short stroke down to the joint, a long one up from it
+        let check = mark(
+            &check_id,
+            self.checked && !self.indeterminate,
+            vec![
+                bar(size * 0.24, [0.325, 0.615], FRAC_PI_4),
+                bar(size * 0.495, [0.585, 0.525], -FRAC_PI_4),
+            ],
+        );
+        let dash = mark(
+            &dash_id,
+            self.indeterminate,
+            vec![bar(size * 0.5, [0.5, 0.5], 0.0)],
+        );
+
+        let check_box = Node::new()
+            .with_id(NodeId::new(&box_id))
+            .with_width(Size::lpx(size))
+            .with_height(Size::lpx(size))
+            .with_layout_direction(Layout::Stack)
+            .with_selected(filled)
+            .with_style(Style {
+                fill_color: Some(style.unchecked_color),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.stroke_color)),
+                opacity: Some(1.0),
+                ..Default::default()
+            })
+            .with_selected_style(Style {
+                fill_color: Some(style.checked_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.checked_color)),
+                ..Default::default()
+            })
+            .with_focused_style(Style {
+                stroke: Some(Stroke::new(Size::lpx(2.0), style.focus_stroke_color)),
+                ..Default::default()
+            })
+            .with_hover_style(if filled {
+                Style {
+                    opacity: Some(0.9),
+                    ..Default::default()
+                }
+            } else {
+                Style {
+                    fill_color: Some(style.hover_color),
+                    ..Default::default()
+                }
+            })
+            .with_active_style(Style {
+                opacity: Some(0.7),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                opacity: Some(0.5),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick())
+            .with_children(vec![check, dash]);
+
+        let mut children = vec![check_box];
+        if let Some(label) = self.label {
+            children.push(
+                Node::new().with_content(Content::Text(
+                    TextContent::new(label)
+                        .with_font_size(Size::lpx(style.font_size))
+                        .with_color(if self.disabled {
+                            style.disabled_label_color
+                        } else {
+                            style.label_color
+                        }),
+                )),
+            );
+        }
+
+        // Row holding the box and its label, clickable as a whole
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_layout_direction(Layout::Horizontal)
+            .with_v_align(VerticalAlign::Center)
+            .with_gap(Size::lpx(style.gap))
+            .with_disabled(self.disabled)
+            .with_children(children)
+    }
+}
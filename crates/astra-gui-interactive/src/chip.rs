@@ -0,0 +1,241 @@
+//! Chip component for interactive UI
+//!
+//! A compact tag, such as an applied filter or a recipient in a list, with an
+//! optional close button for removing it.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Size, Spacing, Stroke, Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a chip
+#[derive(Debug, Clone, WithBuilders)]
+pub struct ChipStyle {
+    /// Background color of the chip
+    pub color: Color,
+    /// Background color of the chip when hovered
+    pub hover_color: Color,
+    /// Border color of the chip
+    pub stroke_color: Color,
+    /// Label color
+    pub text_color: Color,
+    /// Color of the close button's cross
+    pub close_color: Color,
+    /// Background color of the close button when hovered
+    pub close_hover_color: Color,
+    /// Color of the close button's cross when hovered
+    pub close_hover_text_color: Color,
+    /// Font size of the label
+    pub font_size: f32,
+    /// Height of the chip
+    pub height: f32,
+    /// Horizontal padding inside the chip
+    pub padding: f32,
+    /// Space between the label and the close button
+    pub gap: f32,
+    /// Diameter of the close button
+    pub close_size: f32,
+}
+
+impl ChipStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            color: theme.surface,
+            hover_color: theme.surface_hover,
+            stroke_color: theme.border,
+            text_color: theme.text,
+            close_color: theme.text_muted,
+            close_hover_color: theme.danger,
+            close_hover_text_color: theme.on_primary,
+            font_size: 14.0,
+            height: 30.0,
+            padding: 12.0,
+            gap: 6.0,
+            close_size: 18.0,
+        }
+    }
+}
+
+impl Default for ChipStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A tag with an optional close button
+///
+/// The close button is shown once `on_remove` is set; clicking it calls the
+/// callback, and the app drops the chip from its list. Clicking the rest of
+/// the chip calls `on_click`. Chips in a list should get an ID from their
+/// item with `with_id`, so removing one does not hand its hover state to the
+/// next.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut removed = None;
+/// let chips = tags.iter().enumerate().map(|(index, tag)| {
+///     Chip::new(tag)
+///         .with_id(format!("tag_{}", tag))
+///         .on_remove(|| removed = Some(index))
+///         .node(&mut ctx)
+/// });
+/// ```
+pub struct Chip<'a> {
+    label: String,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<ChipStyle>,
+    on_click: Option<Box<dyn FnMut() + 'a>>,
+    on_remove: Option<Box<dyn FnMut() + 'a>>,
+}
+
+impl<'a> Chip<'a> {
+    /// Create a chip with the given label
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            disabled: false,
+            id: None,
+            style: None,
+            on_click: None,
+            on_remove: None,
+        }
+    }
+
+    /// Set whether the chip is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the chip
+    pub fn with_style(mut self, style: ChipStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the chip is clicked
+    pub fn on_click(mut self, f: impl FnMut() + 'a) -> Self {
+        self.on_click = Some(Box::new(f));
+        self
+    }
+
+    /// Show a close button and set a callback to be called when it is clicked
+    pub fn on_remove(mut self, f: impl FnMut() + 'a) -> Self {
+        self.on_remove = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for Chip<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self.id.take().unwrap_or_else(|| ctx.generate_id("chip"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ChipStyle::from_theme(ctx.theme()));
+        let remove_id = format!("{}_remove", id);
+
+        // The close button has its own ID, so its clicks never reach the chip
+        if !self.disabled {
+            if ctx.was_clicked(&remove_id) {
+                if let Some(ref mut on_remove) = self.on_remove {
+                    on_remove();
+                }
+            } else if ctx.was_clicked(&id) {
+                if let Some(ref mut on_click) = self.on_click {
+                    on_click();
+                }
+            }
+        }
+
+        let mut children = vec![Node::new()
+            .with_height(Size::Fill)
+            .with_content(Content::Text(
+                TextContent::new(self.label)
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(style.text_color)
+                    .with_v_align(VerticalAlign::Center),
+            ))];
+        if self.on_remove.is_some() {
+            children.push(
+                Node::new()
+                    .with_id(NodeId::new(&remove_id))
+                    .with_width(Size::lpx(style.close_size))
+                    .with_height(Size::lpx(style.close_size))
+                    .with_cursor(CursorIcon::Pointer)
+                    .with_content(Content::Text(
+                        TextContent::new("\u{00D7}")
+                            .with_font_size(Size::lpx(style.font_size))
+                            .with_color(style.close_color)
+                            .with_h_align(HorizontalAlign::Center)
+                            .with_v_align(VerticalAlign::Center),
+                    ))
+                    .with_style(Style {
+                        fill_color: Some(style.close_hover_color.with_alpha(0.0)),
+                        text_color: Some(style.close_color),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(style.close_size / 2.0))),
+                        ..Default::default()
+                    })
+                    .with_hover_style(Style {
+                        fill_color: Some(style.close_hover_color),
+                        text_color: Some(style.close_hover_text_color),
+                        ..Default::default()
+                    })
+                    .with_disabled(self.disabled)
+                    .with_transition(Transition::quick()),
+            );
+        }
+
+        // The close button sits closer to the edge than the label
+        let right_padding = if self.on_remove.is_some() {
+            (style.height - style.close_size) / 2.0
+        } else {
+            style.padding
+        };
+        let mut chip = Node::new()
+            .with_id(NodeId::new(&id))
+            .with_height(Size::lpx(style.height))
+            .with_padding(Spacing::trbl(
+                Size::lpx(0.0),
+                Size::lpx(right_padding),
+                Size::lpx(0.0),
+                Size::lpx(style.padding),
+            ))
+            .with_gap(Size::lpx(style.gap))
+            .with_layout_direction(Layout::Horizontal)
+            .with_v_align(VerticalAlign::Center)
+            .with_style(Style {
+                fill_color: Some(style.color),
+                opacity: Some(1.0),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.height / 2.0))),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                opacity: Some(0.5),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick())
+            .with_children(children);
+        if self.on_click.is_some() {
+            chip = chip
+                .with_cursor(CursorIcon::Pointer)
+                .with_hover_style(Style {
+                    fill_color: Some(style.hover_color),
+                    ..Default::default()
+                });
+        }
+        chip
+    }
+}
@@ -0,0 +1,708 @@
+//! Code editor component for interactive UI
+//!
+//! A multi-line editor for source code, built on [`TextEditor`] like the text
+//! area: monospace text colored by the context's [`Highlighter`], a gutter
+//! with line numbers, a highlight on the caret's line and on the bracket pair
+//! around the caret. Long lines either wrap or scroll horizontally.
+//!
+//! [`TextEditor`]: astra_gui::TextEditor
+//! [`Highlighter`]: astra_gui::Highlighter
+
+use crate::{ScrollArea, ScrollbarStyle};
+use astra_gui::{
+    matching_bracket, CodeContent, Color, Component, Content, CornerShape, CursorIcon,
+    HorizontalAlign, Layout, LineRect, MeasureTextRequest, Node, NodeId, Overflow, Point,
+    Selection, Size, Spacing, Stroke, Style, TextContent, TextEditStyle, TextSpan, Theme,
+    Translation, UiContext, VerticalAlign, Wrap, DEFAULT_CODE_FAMILY,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::{InteractionEvent, Key, MouseButton, NamedKey};
+use std::time::Duration;
+
+/// Visual styling for a code editor
+#[derive(Debug, Clone, WithBuilders)]
+pub struct CodeEditorStyle {
+    /// Background color of the code
+    pub color: Color,
+    /// Background color of the line number gutter
+    pub gutter_color: Color,
+    /// Stroke color when idle
+    pub stroke_color: Color,
+    /// Stroke color when focused
+    pub focused_stroke_color: Color,
+    /// Stroke width
+    pub stroke_width: f32,
+
+    /// Color of code the highlighter leaves uncolored
+    pub text_color: Color,
+    /// Color of the line numbers
+    pub line_number_color: Color,
+    /// Color of the caret's line number
+    pub current_line_number_color: Color,
+    /// Background of the caret's line while focused
+    pub current_line_color: Color,
+    /// Background of the bracket pair around the caret
+    pub bracket_color: Color,
+
+    /// Caret color
+    pub caret_color: Color,
+    /// Selection highlight color
+    pub selection_color: Color,
+    /// Time between caret blinks
+    pub blink_interval: Duration,
+
+    /// Font family of the code and line numbers
+    pub font_family: &'static str,
+    /// Font size
+    pub font_size: f32,
+    /// Line height as a multiple of the font size
+    pub line_height_multiplier: f32,
+    /// Padding around the code, and on either side of the line numbers
+    pub padding: f32,
+    /// Corner radius for rounded corners
+    pub border_radius: f32,
+    /// Width of the editor
+    pub width: f32,
+    /// Height of the editor; longer code scrolls
+    pub height: f32,
+    /// Style of the scrollbars
+    pub scrollbar: ScrollbarStyle,
+}
+
+impl CodeEditorStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            color: theme.field,
+            gutter_color: theme.surface,
+            stroke_color: theme.border,
+            focused_stroke_color: theme.primary,
+            stroke_width: 2.0,
+            text_color: theme.text,
+            line_number_color: theme.text_disabled,
+            current_line_number_color: theme.text,
+            current_line_color: theme.field_hover,
+            bracket_color: theme.primary.with_alpha(0.35),
+            caret_color: theme.text,
+            selection_color: theme.primary.with_alpha(0.3),
+            blink_interval: Duration::from_millis(530),
+            font_family: DEFAULT_CODE_FAMILY,
+            font_size: 15.0,
+            line_height_multiplier: 1.4,
+            padding: 8.0,
+            border_radius: 8.0,
+            width: 600.0,
+            height: 320.0,
+            scrollbar: ScrollbarStyle::from_theme(theme),
+        }
+    }
+}
+
+impl Default for CodeEditorStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// Called with the new code after each edit
+type ChangeFn<'a> = Box<dyn FnMut(&str) + 'a>;
+
+/// A multi-line editor for source code
+///
+/// The code is colored by the highlighter set with
+/// `UiContext::set_highlighter` (plain without one), picking the grammar by
+/// `language`. Enter inserts a newline; editing, undo and the Ctrl+C/X/V
+/// clipboard shortcuts work as in the text area. With `soft_wrap` long lines
+/// wrap at word boundaries, and each wrapped line keeps a single line number;
+/// otherwise the editor scrolls horizontally.
+///
+/// # Example
+///
+/// ```ignore
+/// CodeEditor::new(&mut source, "rust")
+///     .soft_wrap(wrap)
+///     .on_change(|code| println!("{} lines", code.lines().count()))
+///     .node(&mut ctx)
+/// ```
+pub struct CodeEditor<'a> {
+    value: &'a mut String,
+    language: String,
+    soft_wrap: bool,
+    line_numbers: bool,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<CodeEditorStyle>,
+    on_change: Option<ChangeFn<'a>>,
+}
+
+impl<'a> CodeEditor<'a> {
+    /// Create a code editor bound to a mutable string, highlighted as
+    /// `language` (a name or file extension)
+    pub fn new(value: &'a mut String, language: impl Into<String>) -> Self {
+        Self {
+            value,
+            language: language.into(),
+            soft_wrap: false,
+            line_numbers: true,
+            disabled: false,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Set whether long lines wrap instead of scrolling horizontally
+    /// (default: false)
+    pub fn soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    /// Set whether the line number gutter is shown (default: true)
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Set whether the code editor is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    ///
+    /// The editor state is stored under this ID, so a stable ID keeps the
+    /// caret and undo history when the widgets built before it change.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the code editor
+    pub fn with_style(mut self, style: CodeEditorStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the code changes
+    pub fn on_change(mut self, f: impl FnMut(&str) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for CodeEditor<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("code_editor"));
+        let scroll_id = format!("{}_scroll", id);
+        let text_id = format!("{}_text", id);
+        let mut style = self
+            .style
+            .take()
+            .unwrap_or_else(|| CodeEditorStyle::from_theme(ctx.theme()));
+        // Wrapping and caret positions follow the font size, so the text scale
+        // is applied here rather than by layout (the node opts out below)
+        style.font_size *= ctx.text_scale();
+
+        let pad = style.padding;
+        let line_height = style.font_size * style.line_height_multiplier;
+        let mut edit_style = TextEditStyle {
+            font_size: style.font_size,
+            line_height_multiplier: style.line_height_multiplier,
+            font_family: Some(style.font_family),
+            caret_color: style.caret_color,
+            selection_color: style.selection_color,
+            ..TextEditStyle::from_theme(ctx.theme())
+        };
+
+        let mut state = std::mem::take(ctx.memory().code_editor(&id));
+        if *self.value != state.synced {
+            state.editor.set_text(self.value);
+            state.synced.clone_from(self.value);
+        }
+        let selection_before = state.editor.selection();
+
+        // The gutter fits the largest line number, with room for two digits
+        let digits = (state.editor.buffer().line_count().max(1).ilog10() + 1).max(2);
+        let gutter_width = if self.line_numbers {
+            let digit_width = measure(ctx, &style, "0").unwrap_or(style.font_size * 0.6);
+            digits as f32 * digit_width + pad * 2.0
+        } else {
+            0.0
+        };
+        let code_width = (style.width - gutter_width - pad * 2.0).max(0.0);
+        if self.soft_wrap {
+            edit_style.wrap_width = Some(code_width);
+        }
+
+        // Scroll offsets are in layout pixels; the viewport's known logical
+        // height gives the conversion
+        let metrics = ctx.scroll_metrics(&scroll_id).unwrap_or_default();
+        let scroll = metrics.offset;
+        let layout_scale = if metrics.viewport.1 > 0.0 && style.height > 0.0 {
+            metrics.viewport.1 / style.height
+        } else {
+            ctx.scale_factor()
+        };
+
+        // Clicks place the caret and drags extend the selection from it
+        let mut pressed = false;
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let target = event.target.as_str();
+            if self.disabled || (target != id && target != scroll_id && target != text_id) {
+                continue;
+            }
+            let extend = match event.event {
+                InteractionEvent::Click {
+                    button: MouseButton::Left,
+                    ..
+                } => false,
+                InteractionEvent::DragStart {
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    state.drag_scroll = Some(scroll);
+                    false
+                }
+                InteractionEvent::DragMove { .. } if state.drag_scroll.is_some() => true,
+                InteractionEvent::DragEnd { .. } => {
+                    state.drag_scroll = None;
+                    continue;
+                }
+                _ => continue,
+            };
+            pressed |= !extend;
+
+            // Position in the code. The scroll container's own position is
+            // unscrolled and includes the gutter; the text node's drag origin
+            // was fixed when the drag started, so it only misses scrolling
+            // since then.
+            let (scrolled, gutter) = if target != text_id {
+                (scroll, gutter_width)
+            } else if let InteractionEvent::DragMove { .. } = event.event {
+                let start = state.drag_scroll.unwrap_or(scroll);
+                ((scroll.0 - start.0, scroll.1 - start.1), 0.0)
+            } else {
+                ((0.0, 0.0), 0.0)
+            };
+            let local = event.local_position;
+            let x = (local.x + scrolled.0) / event.zoom - gutter - pad;
+            let y = (local.y + scrolled.1) / event.zoom - pad;
+            if let Some(measurer) = ctx.measurer() {
+                let index = state
+                    .editor
+                    .caret_index_at(measurer, &edit_style, Point { x, y });
+                let anchor = if extend {
+                    state.editor.selection().anchor
+                } else {
+                    index
+                };
+                state.editor.set_selection(Selection::new(anchor, index));
+            }
+        }
+
+        if pressed {
+            ctx.set_focus(Some(&id));
+        }
+
+        // Unfocus on a press elsewhere or Escape
+        let input = ctx.input().clone();
+        let escape_pressed = input
+            .keys_just_pressed
+            .iter()
+            .any(|key| matches!(key, Key::Named(NamedKey::Escape)));
+        if ctx.is_focused(&id) && !pressed && (ctx.was_pressed_outside(&id) || escape_pressed) {
+            ctx.set_focus(None);
+        }
+        let focused = ctx.is_focused(&id) && !self.disabled;
+
+        let mut changed = false;
+        if focused {
+            let response = match ctx.measurer() {
+                Some(measurer) => state
+                    .editor
+                    .handle_input_wrapped(&input, measurer, &edit_style),
+                None => state.editor.handle_input(&input),
+            };
+            changed |= response.changed;
+
+            // Clipboard shortcuts, after Ctrl+A so select-all and copy can
+            // share a frame
+            let editor = &mut state.editor;
+            let shortcuts = input.keys_just_pressed.iter().filter_map(|key| match key {
+                Key::Character(ch) if input.ctrl_held => Some(ch.to_lowercase()),
+                _ => None,
+            });
+            for shortcut in shortcuts {
+                match shortcut.as_str() {
+                    "c" | "x" if !editor.selection().is_empty() => {
+                        ctx.copy_to_clipboard(editor.selected_text());
+                        if shortcut == "x" {
+                            changed |= editor.delete(false);
+                        }
+                    }
+                    "v" if !ctx.clipboard_text().is_empty() => {
+                        let text = ctx.clipboard_text().to_string();
+                        changed |= editor.insert(&text);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if changed {
+            *self.value = state.editor.text().into_owned();
+            state.synced.clone_from(self.value);
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(self.value);
+            }
+        }
+        let caret_moved = changed || state.editor.selection() != selection_before;
+        if caret_moved {
+            ctx.reset_cursor_blink(&id);
+        }
+
+        let caret_visible =
+            focused && ctx.update_cursor_blink(&id, style.blink_interval.as_millis() as u64);
+
+        let code = self.value.clone();
+        let caret = state.editor.selection().caret;
+        let mut brackets = Vec::new();
+        let (display, decorations) = match ctx.measurer() {
+            Some(measurer) => {
+                let display = edit_style.display_text(measurer, &code).into_owned();
+                let decorations = state
+                    .editor
+                    .decorations(measurer, &edit_style, caret_visible);
+
+                // The bracket before the caret wins over the one after it
+                if focused {
+                    let before = code[..caret]
+                        .chars()
+                        .next_back()
+                        .map(|ch| caret - ch.len_utf8());
+                    let pair = before.into_iter().chain([caret]).find_map(|index| {
+                        matching_bracket(&code, index).map(|other| [index, other])
+                    });
+                    brackets = pair
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|index| edit_style.char_rect(measurer, &code, index))
+                        .collect();
+                }
+
+                // Keep the caret in view after typing or moving it, padding
+                // included
+                if caret_moved {
+                    let rect = edit_style.caret_rect(measurer, &code, caret);
+                    let line_top = rect.y - (line_height - rect.height) * 0.5;
+                    let top = line_top * layout_scale;
+                    let bottom = (line_top + line_height + pad * 2.0) * layout_scale;
+                    let left = rect.x * layout_scale;
+                    let right = (rect.x + gutter_width + pad * 2.0) * layout_scale;
+                    let (current, viewport) = (metrics.target, metrics.viewport);
+                    let mut target = current;
+                    if top < current.1 {
+                        target.1 = top;
+                    } else if viewport.1 > 0.0 && bottom > current.1 + viewport.1 {
+                        target.1 = bottom - viewport.1;
+                    }
+                    if left < current.0 {
+                        target.0 = left;
+                    } else if viewport.0 > 0.0 && right > current.0 + viewport.0 {
+                        target.0 = right - viewport.0;
+                    }
+                    if target != current {
+                        ctx.scroll_to(&scroll_id, target);
+                    }
+                }
+
+                (display, Some(decorations))
+            }
+            None => (code.clone(), None),
+        };
+        let block_height = decorations.as_ref().map_or_else(
+            || display.split('\n').count() as f32 * line_height,
+            |decorations| decorations.block_height,
+        );
+        // Unwrapped code scrolls sideways, so the text is as wide as its
+        // longest line
+        let block_width = if self.soft_wrap {
+            code_width
+        } else {
+            measure(ctx, &style, &display)
+                .unwrap_or(0.0)
+                .max(code_width)
+        };
+
+        *ctx.memory().code_editor(&id) = state;
+
+        // Line of each laid-out row; wrapped rows share their line's number
+        let breaks = soft_breaks(&code, &display);
+        let row_lines = row_lines(&code, &breaks);
+        let caret_line = code[..caret].matches('\n').count();
+
+        let highlighted = ctx.highlight(CodeContent::new(code.clone(), self.language.as_str()));
+        let mut content = CodeContent::new(display, self.language.as_str())
+            .with_font_family(style.font_family)
+            .with_font_size(Size::lpx(style.font_size))
+            .with_color(style.text_color);
+        content.text = content
+            .text
+            .with_v_align(VerticalAlign::Top)
+            .with_line_height(style.line_height_multiplier);
+        content.text.spans = split_spans(highlighted.text.spans, &breaks);
+
+        let height = block_height.max(style.height - pad * 2.0) + pad * 2.0;
+        let mut code_column = vec![];
+        if focused {
+            let first = row_lines.iter().position(|&line| line == caret_line);
+            let rows = row_lines.iter().filter(|&&line| line == caret_line).count();
+            if let Some(first) = first {
+                code_column.push(
+                    Node::new()
+                        .with_width(Size::Fill)
+                        .with_height(Size::lpx(rows as f32 * line_height))
+                        .with_translation(Translation::y(Size::lpx(
+                            pad + first as f32 * line_height,
+                        )))
+                        .with_style(Style {
+                            fill_color: Some(style.current_line_color),
+                            ..Default::default()
+                        }),
+                );
+            }
+        }
+        for rect in brackets {
+            code_column.push(highlight_rect(&rect, pad, style.bracket_color));
+        }
+        let mut text = Node::new()
+            .with_id(NodeId::new(&text_id))
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(pad)))
+            .with_content(Content::Code(content))
+            .with_disabled(self.disabled);
+        if let Some(decorations) = decorations {
+            text = text.with_text_edit(decorations);
+        }
+        code_column.push(text);
+
+        let mut columns = vec![];
+        if self.line_numbers {
+            columns.push(gutter(
+                &row_lines,
+                caret_line,
+                focused,
+                &style,
+                gutter_width,
+                height,
+            ));
+        }
+        // The text fills at least the viewport, so it takes every click right
+        // of the gutter
+        columns.push(
+            Node::new()
+                .with_width(Size::lpx(block_width + pad * 2.0))
+                .with_height(Size::lpx(height))
+                .with_layout_direction(Layout::Stack)
+                .with_children(code_column),
+        );
+        let content = Node::new()
+            .with_id(NodeId::new(&scroll_id))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(columns);
+        let area = ScrollArea::new(content)
+            .width(Size::Fill)
+            .height(Size::Fill)
+            .horizontal(!self.soft_wrap)
+            .with_style(style.scrollbar.clone())
+            .node(ctx);
+
+        let stroke_color = if focused {
+            style.focused_stroke_color
+        } else {
+            style.stroke_color
+        };
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(style.width))
+            .with_height(Size::lpx(style.height))
+            .with_text_scale(1.0)
+            .with_layout_direction(Layout::Stack)
+            .with_overflow(Overflow::Hidden)
+            .with_cursor(CursorIcon::Text)
+            .with_style(Style {
+                fill_color: Some(style.color),
+                stroke: Some(Stroke::new(Size::lpx(style.stroke_width), stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                opacity: Some(1.0),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                opacity: Some(0.6),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_child(area)
+    }
+}
+
+/// Width of `text` in the code font, if there is a measurer
+fn measure(ctx: &mut UiContext, style: &CodeEditorStyle, text: &str) -> Option<f32> {
+    let measurer = ctx.measurer()?;
+    Some(
+        measurer
+            .measure_text(MeasureTextRequest {
+                text,
+                spans: &[],
+                font_size: style.font_size,
+                h_align: HorizontalAlign::Left,
+                v_align: VerticalAlign::Top,
+                family: Some(style.font_family),
+                max_width: None,
+                wrap: Wrap::None,
+                max_lines: None,
+                line_height_multiplier: style.line_height_multiplier,
+                font_weight: astra_gui::FontWeight::Normal,
+                font_style: astra_gui::FontStyle::Normal,
+                font_features: &[],
+                font_width: astra_gui::FontWidth::Normal,
+                tab_width: TextContent::DEFAULT_TAB_WIDTH,
+            })
+            .width,
+    )
+}
+
+/// Line numbers for each laid-out row, right-aligned in the gutter
+fn gutter(
+    row_lines: &[usize],
+    caret_line: usize,
+    focused: bool,
+    style: &CodeEditorStyle,
+    width: f32,
+    height: f32,
+) -> Node {
+    let spans = row_lines
+        .iter()
+        .enumerate()
+        .map(|(row, &line)| {
+            let first = row == 0 || row_lines[row - 1] != line;
+            let number = if first {
+                (line + 1).to_string()
+            } else {
+                String::new()
+            };
+            let color = if focused && line == caret_line {
+                style.current_line_number_color
+            } else {
+                style.line_number_color
+            };
+            let newline = if row + 1 < row_lines.len() { "\n" } else { "" };
+            TextSpan::new(number + newline).with_color(color)
+        })
+        .collect::<Vec<_>>();
+    let numbers = TextContent::rich(spans)
+        .with_font_family(style.font_family)
+        .with_font_size(Size::lpx(style.font_size))
+        .with_color(style.line_number_color)
+        .with_h_align(HorizontalAlign::Right)
+        .with_v_align(VerticalAlign::Top)
+        .with_wrap(Wrap::None)
+        .with_line_height(style.line_height_multiplier);
+
+    Node::new()
+        .with_width(Size::lpx(width))
+        .with_height(Size::lpx(height))
+        .with_padding(Spacing::all(Size::lpx(style.padding)))
+        .with_content(Content::Text(numbers))
+        .with_style(Style {
+            fill_color: Some(style.gutter_color),
+            ..Default::default()
+        })
+}
+
+/// A rect behind the char at `rect` in the code column
+fn highlight_rect(rect: &LineRect, pad: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::lpx(rect.width))
+        .with_height(Size::lpx(rect.height))
+        .with_translation(Translation::new(
+            Size::lpx(pad + rect.x),
+            Size::lpx(pad + rect.y),
+        ))
+        .with_style(Style {
+            fill_color: Some(color),
+            corner_shape: Some(CornerShape::Round(Size::lpx(3.0))),
+            ..Default::default()
+        })
+}
+
+/// Byte offsets in `code` where `display`, the code as laid out, has a line
+/// break that the code does not
+fn soft_breaks(code: &str, display: &str) -> Vec<usize> {
+    let mut breaks = Vec::new();
+    let mut chars = code.char_indices().peekable();
+    for ch in display.chars() {
+        match chars.peek() {
+            Some(&(_, next)) if next == ch => {
+                chars.next();
+            }
+            next => breaks.push(next.map_or(code.len(), |&(index, _)| index)),
+        }
+    }
+    breaks
+}
+
+/// Line index of each laid-out row of `code`, given its soft breaks
+fn row_lines(code: &str, breaks: &[usize]) -> Vec<usize> {
+    let mut rows = vec![0];
+    let mut breaks = breaks.iter().peekable();
+    let mut line = 0;
+    for (index, ch) in code.char_indices() {
+        while breaks.next_if(|&&pos| pos <= index).is_some() {
+            rows.push(line);
+        }
+        if ch == '\n' {
+            line += 1;
+            rows.push(line);
+        }
+    }
+    rows.extend(breaks.map(|_| line));
+    rows
+}
+
+/// Insert the soft line breaks at `breaks` into the highlighting spans
+fn split_spans(spans: Vec<TextSpan>, breaks: &[usize]) -> Vec<TextSpan> {
+    if breaks.is_empty() {
+        return spans;
+    }
+    let mut out = Vec::with_capacity(spans.len() + breaks.len() * 2);
+    let mut breaks = breaks.iter().copied().peekable();
+    let mut pos = 0;
+    for span in spans {
+        let end = pos + span.text.len();
+        let mut start = pos;
+        while let Some(at) = breaks.next_if(|&at| at < end) {
+            if at > start {
+                out.push(TextSpan {
+                    text: span.text[start - pos..at - pos].to_string(),
+                    ..span.clone()
+                });
+            }
+            out.push(TextSpan::new("\n"));
+            start = at;
+        }
+        out.push(TextSpan {
+            text: span.text[start - pos..].to_string(),
+            ..span
+        });
+        pos = end;
+    }
+    out
+}
@@ -1,14 +1,17 @@
 //! Collapsible container component for interactive UI
 //!
 //! Provides an expandable/collapsible container with a clickable header,
-//! similar to egui's collapsing header. Supports smooth animations and nesting.
+//! similar to egui's collapsing header. Supports smooth animations and nesting,
+//! and an [`Accordion`] of sections of which at most one is open.
 
+use std::cell::Cell;
 use std::f32::consts::PI;
+use std::rc::Rc;
 
 use astra_gui::{
-    catppuccin::mocha, Color, Component, Content, CornerShape, HorizontalAlign, Layout, Node,
-    NodeId, Orientation, Overflow, Shape, Size, Spacing, Stroke, Style, TextContent, Transition,
-    TriangleSpec, UiContext, VerticalAlign, ZIndex,
+    Color, Component, Content, CornerShape, HorizontalAlign, Layout, Node, NodeId, Orientation,
+    Overflow, Shape, Size, Spacing, Stroke, Style, TextContent, Theme, Transition, TriangleSpec,
+    UiContext, VerticalAlign, ZIndex,
 };
 use astra_gui_macros::WithBuilders;
 
@@ -72,20 +75,21 @@ pub struct CollapsibleStyle {
     pub corners: CornerShape,
 }
 
-impl Default for CollapsibleStyle {
-    fn default() -> Self {
+impl CollapsibleStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
         Self {
             // Header colors
-            header_idle_color: mocha::BASE,
-            header_hover_color: mocha::MANTLE,
-            header_active_color: mocha::CRUST,
-            header_disabled_color: mocha::BASE.with_alpha(0.8),
+            header_idle_color: theme.surface,
+            header_hover_color: theme.surface_hover,
+            header_active_color: theme.surface_active,
+            header_disabled_color: theme.surface.with_alpha(0.8),
 
             // Header stroke colors
-            header_stroke_idle_color: mocha::SURFACE0,
-            header_stroke_hover_color: mocha::SURFACE0,
-            header_stroke_active_color: mocha::SURFACE0,
-            header_stroke_disabled_color: mocha::SURFACE0.with_alpha(0.8),
+            header_stroke_idle_color: theme.border,
+            header_stroke_hover_color: theme.border,
+            header_stroke_active_color: theme.border,
+            header_stroke_disabled_color: theme.border.with_alpha(0.8),
 
             // Header stroke widths
             stroke_idle_width: 1.0,
@@ -94,14 +98,14 @@ impl Default for CollapsibleStyle {
             stroke_disabled_width: 1.0,
 
             // Indicator colors
-            indicator_color: mocha::SURFACE1,
-            indicator_disabled_color: mocha::SURFACE0.with_alpha(0.8),
+            indicator_color: theme.field_hover,
+            indicator_disabled_color: theme.border.with_alpha(0.8),
             indicator_size: 20.0,
             indicator_stroke_width: 1.0,
 
             // Text colors
-            title_color: mocha::TEXT,
-            title_disabled_color: mocha::SUBTEXT1,
+            title_color: theme.text,
+            title_disabled_color: theme.text_disabled,
             title_font_size: 24.0,
 
             // Layout
@@ -118,6 +122,12 @@ impl Default for CollapsibleStyle {
     }
 }
 
+impl Default for CollapsibleStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A collapsible container component
 ///
 /// # Example
@@ -137,7 +147,7 @@ pub struct Collapsible {
     title: String,
     expanded: bool,
     disabled: bool,
-    style: CollapsibleStyle,
+    style: Option<CollapsibleStyle>,
     children: Vec<Node>,
     on_toggle: Option<Box<dyn FnMut(bool)>>,
 }
@@ -149,7 +159,7 @@ impl Collapsible {
             title: title.into(),
             expanded,
             disabled: false,
-            style: CollapsibleStyle::default(),
+            style: None,
             children: Vec::new(),
             on_toggle: None,
         }
@@ -163,7 +173,7 @@ impl Collapsible {
 
     /// Set a custom style for the collapsible
     pub fn with_style(mut self, style: CollapsibleStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
@@ -192,6 +202,10 @@ impl Component for Collapsible {
     fn node(mut self, ctx: &mut UiContext) -> Node {
         // Generate unique IDs
         let id = ctx.generate_id("collapsible");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| CollapsibleStyle::from_theme(ctx.theme()));
         let header_id = format!("{}_header", id);
         let indicator_id = format!("{}_indicator", id);
         let content_id = format!("{}_content", id);
@@ -206,29 +220,26 @@ impl Component for Collapsible {
             }
         }
 
-        // Triangle indicator - changes orientation to show expanded/collapsed state
+        // Triangle indicator - turns from pointing right to pointing down as the
+        // content expands; the rotation is part of the style so it transitions
         let triangle = Node::new()
             .with_id(NodeId::new(&indicator_id))
-            .with_width(Size::lpx(self.style.indicator_size))
-            .with_height(Size::lpx(self.style.indicator_size))
+            .with_width(Size::lpx(style.indicator_size))
+            .with_height(Size::lpx(style.indicator_size))
             .with_shape(Shape::triangle_with_spec(TriangleSpec::Equilateral {
-                orientation: if self.expanded {
-                    Orientation::Down
-                } else {
-                    Orientation::Right
-                },
+                orientation: Orientation::Right,
             }))
-            .with_rotation(if self.expanded { PI / 2.0 } else { 0.0 })
             .with_style(Style {
-                fill_color: Some(self.style.header_idle_color),
+                rotation: Some(if self.expanded { PI / 2.0 } else { 0.0 }),
+                fill_color: Some(style.header_idle_color),
                 stroke: Some(Stroke::new(
-                    Size::lpx(self.style.indicator_stroke_width),
-                    self.style.indicator_color,
+                    Size::lpx(style.indicator_stroke_width),
+                    style.indicator_color,
                 )),
                 ..Default::default()
             })
             .with_disabled_style(Style {
-                fill_color: Some(self.style.header_disabled_color),
+                fill_color: Some(style.header_disabled_color),
                 ..Default::default()
             })
             .with_transition(Transition::quick());
@@ -239,14 +250,23 @@ impl Component for Collapsible {
             .with_height(Size::FitContent)
             .with_content(Content::Text(TextContent {
                 text: self.title,
-                font_size: Size::lpx(self.style.title_font_size),
-                color: self.style.title_color,
+                spans: Vec::new(),
+                font_size: Size::lpx(style.title_font_size),
+                color: style.title_color,
                 h_align: HorizontalAlign::Left,
                 v_align: VerticalAlign::Center,
                 wrap: astra_gui::Wrap::Word,
+                overflow: astra_gui::TextOverflow::Clip,
+                max_lines: None,
                 line_height_multiplier: 1.2,
                 font_weight: astra_gui::FontWeight::Normal,
                 font_style: astra_gui::FontStyle::Normal,
+                font_family: None,
+                font_features: Vec::new(),
+                font_width: astra_gui::FontWidth::Normal,
+                tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
+                outline: None,
+                inherit: astra_gui::TextInherit::NONE,
             }));
 
         // Clickable header with hover/active states
@@ -256,45 +276,45 @@ impl Component for Collapsible {
             .with_height(Size::FitContent)
             .with_layout_direction(Layout::Horizontal)
             .with_v_align(VerticalAlign::Center)
-            .with_gap(Size::lpx(self.style.header_gap))
-            .with_padding(self.style.header_padding)
+            .with_gap(Size::lpx(style.header_gap))
+            .with_padding(style.header_padding)
             .with_z_index(ZIndex(1))
             .with_style(Style {
-                fill_color: Some(self.style.header_idle_color),
-                text_color: Some(self.style.title_color),
-                corner_shape: Some(self.style.corners),
+                fill_color: Some(style.header_idle_color),
+                text_color: Some(style.title_color),
+                corner_shape: Some(style.corners),
                 stroke: Some(Stroke::new(
-                    Size::lpx(self.style.stroke_idle_width),
+                    Size::lpx(style.stroke_idle_width),
                     if self.expanded {
-                        self.style.header_stroke_active_color
+                        style.header_stroke_active_color
                     } else {
-                        self.style.header_stroke_idle_color
+                        style.header_stroke_idle_color
                     },
                 )),
                 ..Default::default()
             })
             .with_hover_style(Style {
-                fill_color: Some(self.style.header_hover_color),
+                fill_color: Some(style.header_hover_color),
                 stroke: Some(Stroke::new(
-                    Size::lpx(self.style.stroke_hover_width),
-                    self.style.header_stroke_hover_color,
+                    Size::lpx(style.stroke_hover_width),
+                    style.header_stroke_hover_color,
                 )),
                 ..Default::default()
             })
             .with_active_style(Style {
-                fill_color: Some(self.style.header_active_color),
+                fill_color: Some(style.header_active_color),
                 stroke: Some(Stroke::new(
-                    Size::lpx(self.style.stroke_active_width),
-                    self.style.header_stroke_active_color,
+                    Size::lpx(style.stroke_active_width),
+                    style.header_stroke_active_color,
                 )),
                 ..Default::default()
             })
             .with_disabled_style(Style {
-                fill_color: Some(self.style.header_disabled_color),
-                text_color: Some(self.style.title_disabled_color),
+                fill_color: Some(style.header_disabled_color),
+                text_color: Some(style.title_disabled_color),
                 stroke: Some(Stroke::new(
-                    Size::lpx(self.style.stroke_disabled_width),
-                    self.style.header_stroke_disabled_color,
+                    Size::lpx(style.stroke_disabled_width),
+                    style.header_stroke_disabled_color,
                 )),
                 ..Default::default()
             })
@@ -307,7 +327,7 @@ impl Component for Collapsible {
             .with_width(Size::Fill)
             .with_height(Size::FitContent)
             .with_layout_direction(Layout::Vertical)
-            .with_padding(self.style.content_padding)
+            .with_padding(style.content_padding)
             .with_children(self.children);
 
         // Wrapper with overflow clipping for smooth height animation
@@ -316,17 +336,17 @@ impl Component for Collapsible {
             .with_width(Size::Fill)
             .with_z_index(ZIndex(0)) // Below header's ZIndex(1)
             .with_padding(Spacing::top(Size::lpx(
-                self.style.header_padding.get_vertical()
-                    + self.style.title_font_size
-                    + self.style.content_padding.get_top(),
+                style.header_padding.get_vertical()
+                    + style.title_font_size * ctx.text_scale()
+                    + style.content_padding.get_top(),
             )))
             .with_style(Style {
-                fill_color: Some(self.style.header_idle_color),
+                fill_color: Some(style.header_idle_color),
                 stroke: Some(Stroke::new(
-                    Size::lpx(self.style.stroke_idle_width),
-                    self.style.header_stroke_active_color,
+                    Size::lpx(style.stroke_idle_width),
+                    style.header_stroke_active_color,
                 )),
-                corner_shape: Some(self.style.corners),
+                corner_shape: Some(style.corners),
                 ..Default::default()
             })
             .with_height(if self.expanded {
@@ -346,3 +366,95 @@ impl Component for Collapsible {
             .with_children(vec![header, content_wrapper])
     }
 }
+
+/// A group of collapsible sections of which at most one is expanded
+///
+/// Opening a section closes the one that was open. Like [`Collapsible`], the
+/// accordion is controlled: pass the open section and update it from
+/// `on_change`.
+///
+/// # Example
+///
+/// ```ignore
+/// Accordion::new(state.open_section)
+///     .section("General", general_nodes)
+///     .section("Advanced", advanced_nodes)
+///     .on_change(|open| state.open_section = open)
+///     .node(&mut ctx)
+/// ```
+pub struct Accordion {
+    open: Option<usize>,
+    sections: Vec<(String, Vec<Node>)>,
+    gap: f32,
+    style: Option<CollapsibleStyle>,
+    on_change: Option<Box<dyn FnMut(Option<usize>)>>,
+}
+
+impl Accordion {
+    /// Create an accordion with the section at index `open` expanded
+    pub fn new(open: Option<usize>) -> Self {
+        Self {
+            open,
+            sections: Vec::new(),
+            gap: 8.0,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Append a section with the given title and content
+    pub fn section(mut self, title: impl Into<String>, children: Vec<Node>) -> Self {
+        self.sections.push((title.into(), children));
+        self
+    }
+
+    /// Set the space between sections
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set a custom style for the sections
+    pub fn with_style(mut self, style: CollapsibleStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the newly open section (`None` when
+    /// the open section was collapsed)
+    pub fn on_change(mut self, f: impl FnMut(Option<usize>) + 'static) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for Accordion {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        // Sections report their toggle while being built; the change is
+        // forwarded once all of them are done
+        let changed: Rc<Cell<Option<Option<usize>>>> = Rc::default();
+        let mut sections = Vec::with_capacity(self.sections.len());
+        for (index, (title, children)) in self.sections.into_iter().enumerate() {
+            let changed = changed.clone();
+            let mut section = Collapsible::new(title, self.open == Some(index))
+                .children(children)
+                .on_toggle(move |expanded| changed.set(Some(expanded.then_some(index))));
+            if let Some(style) = &self.style {
+                section = section.with_style(style.clone());
+            }
+            sections.push(section.node(ctx));
+        }
+
+        if let Some(open) = changed.get() {
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(open);
+            }
+        }
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_layout_direction(Layout::Vertical)
+            .with_gap(Size::lpx(self.gap))
+            .with_children(sections)
+    }
+}
@@ -0,0 +1,183 @@
+//! Context menu component for interactive UI
+//!
+//! Opens a menu at the cursor when its target node is right-clicked, kept inside
+//! the window. Items share [`MenuItem`] with the menu bar, so they support
+//! separators, shortcut hints, submenus and keyboard navigation. The chosen
+//! item is reported back to the target as a `ContextMenuAction` event.
+
+use crate::menu::{choose, clicked_item, follow_hover, handle_keys, Menu, MenuPainter};
+use crate::{MenuItem, MenuStyle};
+use astra_gui::{
+    Component, HorizontalAlign, Layout, Node, NodeId, Place, Point, Size, TargetedEvent,
+    Translation, UiContext, VerticalAlign, ZIndex,
+};
+use astra_gui_wgpu::InteractionEvent;
+
+/// Wraps a target node and opens a menu at the cursor when it is right-clicked
+///
+/// Choosing an item runs its `on_select` callback and sends a
+/// `ContextMenuAction` event to the target, readable next frame with
+/// [`UiContext::context_menu_action`]. The action is the item's
+/// [`MenuItem::action`], or its label. The menu closes when an item is chosen,
+/// Escape is pressed or a mouse button is pressed outside it.
+///
+/// Right-clicks on identified nodes inside the target are delivered to those
+/// nodes instead, so give interactive children their own context menu.
+///
+/// # Example
+///
+/// ```ignore
+/// let file = ContextMenu::new(file_row)
+///     .item(MenuItem::new("Rename").shortcut("F2"))
+///     .item(MenuItem::new("Delete").action("delete"))
+///     .node(&mut ctx);
+///
+/// if ctx.context_menu_action("file_row") == Some("delete") {
+///     delete_file();
+/// }
+/// ```
+pub struct ContextMenu {
+    target: Node,
+    items: Vec<MenuItem>,
+    style: Option<MenuStyle>,
+}
+
+impl ContextMenu {
+    /// Create a context menu for the given target node
+    pub fn new(target: Node) -> Self {
+        Self {
+            target,
+            items: Vec::new(),
+            style: None,
+        }
+    }
+
+    /// Append an item
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Append a separator
+    pub fn separator(self) -> Self {
+        self.item(MenuItem::separator())
+    }
+
+    /// Append several items
+    pub fn items(mut self, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+
+    /// Set a custom style for the menu
+    pub fn with_style(mut self, style: MenuStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for ContextMenu {
+    fn node(self, ctx: &mut UiContext) -> Node {
+        // Respect an ID already set on the target, otherwise generate one
+        let target_id = match self.target.id() {
+            Some(id) => id.as_str().to_string(),
+            None => ctx.generate_id("context_menu"),
+        };
+        let id = format!("{}_context_menu", target_id);
+        let popup_id = format!("{}_popup_0", id);
+        let style = self
+            .style
+            .unwrap_or_else(|| MenuStyle::from_theme(ctx.theme()));
+        let mut menus = vec![Menu::new("").items(self.items)];
+        let mut state = ctx.memory().menu(&id).clone();
+
+        if state.is_open() && ctx.was_pressed_outside(&popup_id) {
+            state.close();
+        }
+
+        // A right-click on the target opens the menu at the cursor, shifted
+        // back inside the window where it would overflow
+        let request = ctx
+            .events()
+            .iter()
+            .rev()
+            .find_map(|event| match event.event {
+                InteractionEvent::ContextMenuRequested { position }
+                    if event.target.as_str() == target_id =>
+                {
+                    Some((position, event.local_position, event.zoom))
+                }
+                _ => None,
+            });
+        if let Some((position, local_position, zoom)) = request {
+            let painter = MenuPainter {
+                id: &id,
+                style: &style,
+                state: &state,
+            };
+            let scale = ctx.scale_factor() * zoom;
+            let width = painter.popup_width(ctx, &menus[0].items) * scale;
+            let height = painter.popup_height(&menus[0].items) * scale;
+            let (viewport_width, viewport_height) = ctx.viewport_size();
+            let x = position.x.min(viewport_width - width).max(0.0);
+            let y = position.y.min(viewport_height - height).max(0.0);
+
+            state.close();
+            state.open_path = vec![0];
+            state.offset = (
+                x - (position.x - local_position.x),
+                y - (position.y - local_position.y),
+            );
+        }
+
+        let mut action = None;
+        if state.is_open() {
+            follow_hover(ctx, &id, &mut menus, &mut state);
+            let mut chosen = clicked_item(ctx, &id);
+            if chosen.is_none() {
+                chosen = handle_keys(ctx, &mut menus, &mut state, false);
+            }
+            if let Some(path) = chosen {
+                action = choose(&mut menus, &mut state, path);
+            }
+        }
+        if let Some(action) = action {
+            ctx.send_event(TargetedEvent {
+                event: InteractionEvent::ContextMenuAction { action },
+                target: NodeId::new(&target_id),
+                local_position: Point::zero(),
+                zoom: 1.0,
+            });
+        }
+        *ctx.memory().menu(&id) = state.clone();
+
+        let mut children = vec![self.target.with_id(NodeId::new(&target_id))];
+        if state.is_open() {
+            let painter = MenuPainter {
+                id: &id,
+                style: &style,
+                state: &state,
+            };
+            let items = menus.pop().map(|menu| menu.items).unwrap_or_default();
+            // Zero-sized anchor at the target's top-left corner, so the menu
+            // overflows from there without affecting layout
+            children.push(
+                Node::new()
+                    .with_width(Size::lpx(0.0))
+                    .with_height(Size::lpx(0.0))
+                    .with_place(Place::Alignment {
+                        h_align: HorizontalAlign::Left,
+                        v_align: VerticalAlign::Top,
+                    })
+                    .with_z_index(ZIndex::OVERLAY)
+                    .with_child(painter.popup(ctx, items, vec![0]).with_translation(
+                        Translation::new(Size::ppx(state.offset.0), Size::ppx(state.offset.1)),
+                    )),
+            );
+        }
+
+        Node::new()
+            .with_layout_direction(Layout::Stack)
+            .with_children(children)
+    }
+}
@@ -4,8 +4,8 @@
 //! Users can drag left/right to adjust the value, or click to enter text input mode.
 
 use astra_gui::{
-    catppuccin::mocha, Color, Content, CornerShape, HorizontalAlign, Layout, Node, NodeId, Size,
-    Spacing, Stroke, Style, TextContent, Transition, UiContext, VerticalAlign,
+    Color, Content, CornerShape, HorizontalAlign, Layout, Node, NodeId, Size, Spacing, Stroke,
+    Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
 };
 use astra_gui_macros::WithBuilders;
 use astra_gui_wgpu::{InteractionEvent, Key, NamedKey};
@@ -60,28 +60,29 @@ pub struct DragValueStyle {
     pub text_input_style: TextInputStyle,
 }
 
-impl Default for DragValueStyle {
-    fn default() -> Self {
+impl DragValueStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
         let min_width = 80.0;
-        let mut text_input_style = TextInputStyle::default();
+        let mut text_input_style = TextInputStyle::from_theme(theme);
         text_input_style.text_align = HorizontalAlign::Center;
         text_input_style.width = min_width;
 
         Self {
-            idle_color: mocha::SURFACE0,
-            hover_color: mocha::SURFACE1,
-            active_color: mocha::SURFACE2,
-            disabled_color: mocha::SURFACE0.with_alpha(0.8),
+            idle_color: theme.field,
+            hover_color: theme.field_hover,
+            active_color: theme.field_active,
+            disabled_color: theme.field.with_alpha(0.8),
 
-            idle_border_color: mocha::LAVENDER,
-            hover_border_color: mocha::MAUVE,
-            active_border_color: mocha::LAVENDER,
-            disabled_border_color: mocha::SURFACE2,
+            idle_border_color: theme.primary,
+            hover_border_color: theme.secondary,
+            active_border_color: theme.primary,
+            disabled_border_color: theme.field_active,
 
             border_width: 2.0,
 
-            text_color: mocha::TEXT,
-            disabled_text_color: mocha::SUBTEXT0,
+            text_color: theme.text,
+            disabled_text_color: theme.text_muted,
 
             padding: Spacing::symmetric(Size::lpx(10.0), Size::lpx(8.0)),
             border_radius: 8.0,
@@ -94,6 +95,12 @@ impl Default for DragValueStyle {
     }
 }
 
+impl Default for DragValueStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 impl DragValueStyle {
     /// Set the minimum width
     pub fn with_min_width(mut self, min_width: f32) -> Self {
@@ -112,7 +119,7 @@ impl DragValueStyle {
 // - text_mode: bool (editing mode)
 
 /// Format a float value with the given precision
-fn format_value(value: f32, precision: usize) -> String {
+pub(crate) fn format_value(value: f32, precision: usize) -> String {
     if precision == 0 {
         format!("{:.0}", value)
     } else {
@@ -130,7 +137,7 @@ fn format_value(value: f32, precision: usize) -> String {
 }
 
 /// Parse a string to an f32 value
-fn parse_value(text: &str) -> Option<f32> {
+pub(crate) fn parse_value(text: &str) -> Option<f32> {
     text.trim().parse::<f32>().ok()
 }
 
@@ -151,7 +158,7 @@ pub struct DragValue<'a> {
     step: Option<f32>,
     speed: f32,
     disabled: bool,
-    style: DragValueStyle,
+    style: Option<DragValueStyle>,
     on_change: Option<Box<dyn FnMut(f32) + 'a>>,
 }
 
@@ -164,7 +171,7 @@ impl<'a> DragValue<'a> {
             step: None,
             speed: 0.1,
             disabled: false,
-            style: DragValueStyle::default(),
+            style: None,
             on_change: None,
         }
     }
@@ -195,7 +202,7 @@ impl<'a> DragValue<'a> {
 
     /// Set a custom style for the drag value
     pub fn with_style(mut self, style: DragValueStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
@@ -212,6 +219,13 @@ impl<'a> DragValue<'a> {
     pub fn build(mut self, ctx: &mut UiContext) -> Node {
         // Generate unique ID
         let id = ctx.generate_id("drag_value");
+        let mut style = self
+            .style
+            .take()
+            .unwrap_or_else(|| DragValueStyle::from_theme(ctx.theme()));
+        // Caret positions and the field height follow the font size, so the text
+        // scale is applied here rather than by layout (the node opts out below)
+        style.font_size *= ctx.text_scale();
         let hitbox_id = format!("{}_hitbox", id);
         let container_id = format!("{}_container", id);
 
@@ -564,12 +578,12 @@ impl<'a> DragValue<'a> {
                 cursor_pos,
                 selection,
                 self.disabled,
-                &self.style,
+                &style,
                 ctx,
             )
         } else {
             // Use drag display rendering
-            build_drag_display_node(&id, *self.value, self.disabled, &self.style)
+            build_drag_display_node(&id, *self.value, self.disabled, &style)
         }
     }
 }
@@ -642,14 +656,23 @@ fn build_editing_node(
             .with_height(Size::Fill)
             .with_content(Content::Text(TextContent {
                 text: text_buffer.to_string(),
+                spans: Vec::new(),
                 font_size: Size::lpx(style.font_size),
                 color: style.text_color,
                 h_align: HorizontalAlign::Center,
                 v_align: VerticalAlign::Center,
                 wrap: astra_gui::Wrap::None,
+                overflow: astra_gui::TextOverflow::Clip,
+                max_lines: None,
                 line_height_multiplier: 1.2,
                 font_weight: astra_gui::FontWeight::Normal,
                 font_style: astra_gui::FontStyle::Normal,
+                font_family: None,
+                font_features: Vec::new(),
+                font_width: astra_gui::FontWidth::Normal,
+                tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
+                outline: None,
+                inherit: astra_gui::TextInherit::NONE,
             })),
     );
 
@@ -682,6 +705,7 @@ fn build_editing_node(
         .with_id(node_id)
         .with_width(Size::lpx(style.min_width))
         .with_height(Size::lpx(style.font_size + style.padding.get_vertical()))
+        .with_text_scale(1.0)
         .with_padding(style.padding)
         .with_layout_direction(Layout::Stack)
         .with_overflow(astra_gui::Overflow::Hidden)
@@ -711,6 +735,7 @@ fn build_drag_display_node(id: &str, value: f32, disabled: bool, style: &DragVal
         .with_id(NodeId::new(&container_id))
         .with_width(Size::lpx(style.min_width))
         .with_height(Size::lpx(style.font_size + style.padding.get_vertical()))
+        .with_text_scale(1.0)
         .with_padding(style.padding)
         .with_layout_direction(Layout::Stack)
         .with_style(Style {
@@ -757,14 +782,23 @@ fn build_drag_display_node(id: &str, value: f32, disabled: bool, style: &DragVal
                 .with_height(Size::Fill)
                 .with_content(Content::Text(TextContent {
                     text: display_text,
+                    spans: Vec::new(),
                     font_size: Size::lpx(style.font_size),
                     color: style.text_color,
                     h_align: HorizontalAlign::Center,
                     v_align: VerticalAlign::Center,
                     wrap: astra_gui::Wrap::Word,
+                    overflow: astra_gui::TextOverflow::Clip,
+                    max_lines: None,
                     line_height_multiplier: 1.2,
                     font_weight: astra_gui::FontWeight::Normal,
                     font_style: astra_gui::FontStyle::Normal,
+                    font_family: None,
+                    font_features: Vec::new(),
+                    font_width: astra_gui::FontWidth::Normal,
+                    tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
+                    outline: None,
+                    inherit: astra_gui::TextInherit::NONE,
                 }))
                 .with_style(Style {
                     text_color: Some(style.text_color),
@@ -0,0 +1,245 @@
+//! Drop zone component for interactive UI
+//!
+//! An area that accepts files dragged in from outside the window, such as an
+//! upload target, highlighting while files are dragged over it.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, HorizontalAlign, Layout, Node, NodeId, Size, Spacing,
+    Stroke, Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+use std::path::{Path, PathBuf};
+
+/// Visual styling for a drop zone
+#[derive(Debug, Clone, WithBuilders)]
+pub struct DropZoneStyle {
+    /// Background color when no files are being dragged
+    pub color: Color,
+    /// Border color when no files are being dragged
+    pub stroke_color: Color,
+    /// Border color while files are dragged anywhere over the window
+    pub active_stroke_color: Color,
+    /// Background color while accepted files are over the zone
+    pub hover_color: Color,
+    /// Border color while accepted files are over the zone
+    pub hover_stroke_color: Color,
+    /// Border color while only files the zone does not accept are over it
+    pub reject_stroke_color: Color,
+    /// Label color
+    pub text_color: Color,
+    /// Label font size
+    pub font_size: f32,
+    /// Border width
+    pub stroke_width: f32,
+    /// Corner radius
+    pub border_radius: f32,
+    /// Padding around the content
+    pub padding: f32,
+}
+
+impl DropZoneStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            color: theme.surface,
+            stroke_color: theme.border,
+            active_stroke_color: theme.primary.with_alpha(0.5),
+            hover_color: theme.surface_hover,
+            hover_stroke_color: theme.primary,
+            reject_stroke_color: theme.danger,
+            text_color: theme.text_muted,
+            font_size: 16.0,
+            stroke_width: 2.0,
+            border_radius: 12.0,
+            padding: 24.0,
+        }
+    }
+}
+
+impl Default for DropZoneStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// Called with the accepted files dropped on the zone
+type DropFn<'a> = Box<dyn FnMut(&[PathBuf]) + 'a>;
+
+/// An area that files can be dropped on
+///
+/// Files dropped on the zone are passed to `on_drop`, filtered by the
+/// extensions given to `accept`. The border lights up while files are
+/// dragged anywhere over the window, and the zone highlights once they are
+/// over it (or shows the reject color if none of them are accepted).
+///
+/// The files go to the topmost node with an ID under the pointer, so the
+/// content should not contain widgets with IDs. Most platforms do not report
+/// the pointer while files are dragged in from outside, so there the zone may
+/// only show the window-wide border until the drop, which is delivered once
+/// the pointer position is known again.
+///
+/// # Example
+///
+/// ```ignore
+/// DropZone::new("Drop images here")
+///     .accept(&["png", "jpg"])
+///     .on_drop(|files| images.extend(files.iter().cloned()))
+///     .node(&mut ctx)
+/// ```
+pub struct DropZone<'a> {
+    label: String,
+    content: Option<Node>,
+    accept: Vec<String>,
+    width: Size,
+    height: Size,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<DropZoneStyle>,
+    on_drop: Option<DropFn<'a>>,
+}
+
+impl<'a> DropZone<'a> {
+    /// Create a drop zone showing the given label
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            content: None,
+            accept: Vec::new(),
+            width: Size::Fill,
+            height: Size::lpx(160.0),
+            disabled: false,
+            id: None,
+            style: None,
+            on_drop: None,
+        }
+    }
+
+    /// Show custom content instead of the label
+    pub fn content(mut self, content: Node) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Only accept files with these extensions, ignoring case (default: any
+    /// file)
+    pub fn accept(mut self, extensions: &[&str]) -> Self {
+        self.accept = extensions.iter().map(|ext| ext.to_lowercase()).collect();
+        self
+    }
+
+    /// Set the width of the zone (default: fill)
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height of the zone (default: 160 logical pixels)
+    pub fn height(mut self, height: Size) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set whether the drop zone is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the drop zone
+    pub fn with_style(mut self, style: DropZoneStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the accepted files when files are
+    /// dropped on the zone
+    pub fn on_drop(mut self, f: impl FnMut(&[PathBuf]) + 'a) -> Self {
+        self.on_drop = Some(Box::new(f));
+        self
+    }
+
+    /// Whether the zone takes files like `path`
+    fn accepts(&self, path: &Path) -> bool {
+        self.accept.is_empty()
+            || path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| self.accept.contains(&ext.to_lowercase()))
+    }
+}
+
+impl Component for DropZone<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("drop_zone"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| DropZoneStyle::from_theme(ctx.theme()));
+
+        if !self.disabled {
+            if let Some(files) = ctx.dropped_files(&id) {
+                let accepted: Vec<PathBuf> = files
+                    .iter()
+                    .filter(|path| self.accepts(path))
+                    .cloned()
+                    .collect();
+                if !accepted.is_empty() {
+                    if let Some(ref mut on_drop) = self.on_drop {
+                        on_drop(&accepted);
+                    }
+                }
+            }
+        }
+
+        let (fill, stroke) = match ctx.hovered_files(&id) {
+            _ if self.disabled => (style.color, style.stroke_color),
+            Some(files) if files.iter().any(|path| self.accepts(path)) => {
+                (style.hover_color, style.hover_stroke_color)
+            }
+            Some(_) => (style.color, style.reject_stroke_color),
+            None if ctx.is_file_drag_active() => (style.color, style.active_stroke_color),
+            None => (style.color, style.stroke_color),
+        };
+
+        let content = self.content.take().unwrap_or_else(|| {
+            Node::new().with_content(Content::Text(
+                TextContent::new(self.label)
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(style.text_color)
+                    .with_h_align(HorizontalAlign::Center),
+            ))
+        });
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(self.width)
+            .with_height(self.height)
+            .with_padding(Spacing::all(Size::lpx(style.padding)))
+            .with_layout_direction(Layout::Vertical)
+            .with_h_align(HorizontalAlign::Center)
+            .with_v_align(VerticalAlign::Center)
+            .with_style(Style {
+                fill_color: Some(fill),
+                stroke: Some(Stroke::new(Size::lpx(style.stroke_width), stroke)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                opacity: Some(1.0),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                opacity: Some(0.5),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick())
+            .with_child(content)
+    }
+}
@@ -0,0 +1,244 @@
+//! Frame time graph component for diagnosing stutters
+//!
+//! Plots the most recent frame times recorded by `UiContext` as a bar chart with
+//! horizontal budget lines (16.6ms for 60 FPS and 8.3ms for 120 FPS by default).
+//! No timing collection is needed on the app side: the context measures the time
+//! between consecutive `end_frame` calls.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, HorizontalAlign, Layout, Node, NodeId, Overflow, Place,
+    Size, Spacing, Style, TextContent, Theme, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a frame time graph
+#[derive(Debug, Clone, WithBuilders)]
+pub struct FrameTimeGraphStyle {
+    /// Background color of the plot area
+    pub background_color: Color,
+    /// Bar color for frames within the tightest budget
+    pub ok_color: Color,
+    /// Bar color for frames over the tightest budget but within the loosest
+    pub warn_color: Color,
+    /// Bar color for frames over every budget
+    pub over_color: Color,
+    /// Color of the budget lines
+    pub budget_line_color: Color,
+    /// Color of the readout text
+    pub label_color: Color,
+    /// Width of the plot area
+    pub width: f32,
+    /// Height of the plot area
+    pub height: f32,
+    /// Width of a single bar
+    pub bar_width: f32,
+    /// Gap between bars
+    pub bar_gap: f32,
+    /// Thickness of the budget lines
+    pub budget_line_width: f32,
+    /// Font size of the readout text
+    pub label_font_size: f32,
+    /// Corner radius of the plot area
+    pub border_radius: f32,
+}
+
+impl FrameTimeGraphStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            background_color: theme.overlay.with_alpha(0.85),
+            ok_color: theme.success,
+            warn_color: theme.warning,
+            over_color: theme.danger,
+            budget_line_color: theme.text_muted.with_alpha(0.6),
+            label_color: theme.text,
+            width: 240.0,
+            height: 64.0,
+            bar_width: 2.0,
+            bar_gap: 0.0,
+            budget_line_width: 1.0,
+            label_font_size: 11.0,
+            border_radius: 4.0,
+        }
+    }
+}
+
+impl Default for FrameTimeGraphStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A bar chart of recent frame times
+///
+/// Samples come from `UiContext::frame_times`, newest on the right. Bars are
+/// colored by the budgets they exceed.
+///
+/// # Example
+///
+/// ```ignore
+/// FrameTimeGraph::new()
+///     .with_max_ms(50.0)
+///     .with_budgets(vec![16.6, 8.3])
+///     .node(&mut ctx)
+/// ```
+pub struct FrameTimeGraph {
+    max_ms: f32,
+    budgets: Vec<f32>,
+    show_label: bool,
+    style: Option<FrameTimeGraphStyle>,
+}
+
+impl FrameTimeGraph {
+    /// Create a graph with a 33.3ms scale and 60/120 FPS budget lines
+    pub fn new() -> Self {
+        Self {
+            max_ms: 33.3,
+            budgets: vec![16.6, 8.3],
+            show_label: true,
+            style: None,
+        }
+    }
+
+    /// Set the frame time at the top of the graph (taller frames are clamped)
+    pub fn with_max_ms(mut self, max_ms: f32) -> Self {
+        self.max_ms = max_ms.max(f32::EPSILON);
+        self
+    }
+
+    /// Set the budget lines in milliseconds
+    pub fn with_budgets(mut self, budgets: Vec<f32>) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    /// Set whether the last/average frame time readout is shown
+    pub fn with_label(mut self, show_label: bool) -> Self {
+        self.show_label = show_label;
+        self
+    }
+
+    /// Set a custom style for the graph
+    pub fn with_style(mut self, style: FrameTimeGraphStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    fn bar_color(&self, ms: f32, style: &FrameTimeGraphStyle) -> Color {
+        let tightest = self.budgets.iter().copied().reduce(f32::min);
+        let loosest = self.budgets.iter().copied().reduce(f32::max);
+        match (tightest, loosest) {
+            (_, Some(loosest)) if ms > loosest => style.over_color,
+            (Some(tightest), _) if ms > tightest => style.warn_color,
+            _ => style.ok_color,
+        }
+    }
+
+    fn height_for(&self, ms: f32, style: &FrameTimeGraphStyle) -> f32 {
+        (ms / self.max_ms).clamp(0.0, 1.0) * style.height
+    }
+}
+
+impl Default for FrameTimeGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for FrameTimeGraph {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = ctx.generate_id("frame_time_graph");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| FrameTimeGraphStyle::from_theme(ctx.theme()));
+
+        // Only keep as many samples as there are bars that fit the plot
+        let slot = (style.bar_width + style.bar_gap).max(f32::EPSILON);
+        let capacity = ((style.width + style.bar_gap) / slot).floor() as usize;
+        let times = ctx.frame_times();
+        let samples: Vec<f32> = times
+            .iter()
+            .skip(times.len().saturating_sub(capacity))
+            .copied()
+            .collect();
+
+        let bars = samples.iter().map(|&ms| {
+            Node::new()
+                .with_width(Size::lpx(style.bar_width))
+                .with_height(Size::lpx(self.height_for(ms, &style)))
+                .with_style(Style {
+                    fill_color: Some(self.bar_color(ms, &style)),
+                    ..Default::default()
+                })
+        });
+
+        let mut graph = Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(style.width))
+            .with_height(Size::lpx(style.height))
+            .with_layout_direction(Layout::Stack)
+            .with_overflow(Overflow::Hidden)
+            .with_style(Style {
+                fill_color: Some(style.background_color),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                ..Default::default()
+            })
+            .with_child(
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_h_align(HorizontalAlign::Right)
+                    .with_v_align(VerticalAlign::Bottom)
+                    .with_gap(Size::lpx(style.bar_gap))
+                    .with_children(bars.collect()),
+            );
+
+        for &budget in &self.budgets {
+            if budget > self.max_ms {
+                continue;
+            }
+            let y = style.height - self.height_for(budget, &style) - style.budget_line_width;
+            graph = graph.with_child(
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::lpx(style.budget_line_width))
+                    .with_place(Place::Absolute {
+                        x: Size::lpx(0.0),
+                        y: Size::lpx(y.max(0.0)),
+                    })
+                    .with_style(Style {
+                        fill_color: Some(style.budget_line_color),
+                        ..Default::default()
+                    }),
+            );
+        }
+
+        if self.show_label {
+            if let Some(&last) = samples.last() {
+                let average = samples.iter().sum::<f32>() / samples.len() as f32;
+                graph = graph.with_child(
+                    Node::new()
+                        .with_padding(Spacing::all(Size::lpx(4.0)))
+                        .with_place(Place::Absolute {
+                            x: Size::lpx(0.0),
+                            y: Size::lpx(0.0),
+                        })
+                        .with_content(Content::Text(
+                            TextContent::new(format!(
+                                "{:.1} ms  avg {:.1} ms  {:.0} FPS",
+                                last,
+                                average,
+                                1000.0 / average.max(f32::EPSILON)
+                            ))
+                            .with_font_size(Size::lpx(style.label_font_size))
+                            .with_color(style.label_color),
+                        )),
+                );
+            }
+        }
+
+        graph
+    }
+}
@@ -0,0 +1,312 @@
+//! Gauge component for monitoring dashboards
+//!
+//! An arc meter showing a value within a range, with optional colored zones
+//! (e.g. green/yellow/red for normal, warning and critical levels). The value
+//! is shown by a needle or by filling the arc up to it, and changes animate
+//! smoothly, easing from the value shown to the new one.
+
+use std::f32::consts::PI;
+use std::ops::RangeInclusive;
+
+use astra_gui::{
+    Color, Component, HorizontalAlign, Node, NodeId, Size, TextContent, Theme, UiContext,
+    VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a gauge
+#[derive(Debug, Clone, WithBuilders)]
+pub struct GaugeStyle {
+    /// Color of the arc behind the zones and the value
+    pub track_color: Color,
+    /// Color of the filled arc where no zone covers the value
+    pub fill_color: Color,
+    /// Color of the needle and its hub
+    pub needle_color: Color,
+    /// Color of the value text
+    pub value_color: Color,
+    /// Color of the label and the min/max labels
+    pub label_color: Color,
+    /// Width and height of the gauge
+    pub size: f32,
+    /// Thickness of the arc
+    pub arc_width: f32,
+    /// Angle of the range's minimum, in radians clockwise from the right
+    pub start_angle: f32,
+    /// Angle the range covers, in radians clockwise
+    pub sweep_angle: f32,
+    /// Width of the needle
+    pub needle_width: f32,
+    /// Diameter of the needle's hub
+    pub hub_size: f32,
+    /// Font size of the value text
+    pub value_font_size: f32,
+    /// Font size of the label and the min/max labels
+    pub label_font_size: f32,
+    /// Seconds a value change takes to animate (0 to jump)
+    pub animation_duration: f32,
+}
+
+impl GaugeStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            track_color: theme.field,
+            fill_color: theme.primary,
+            needle_color: theme.text,
+            value_color: theme.text,
+            label_color: theme.text_muted,
+            size: 160.0,
+            arc_width: 12.0,
+            // From bottom left over the top to bottom right
+            start_angle: 0.75 * PI,
+            sweep_angle: 1.5 * PI,
+            needle_width: 3.0,
+            hub_size: 10.0,
+            value_font_size: 22.0,
+            label_font_size: 12.0,
+            animation_duration: 0.4,
+        }
+    }
+}
+
+impl Default for GaugeStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// How a gauge shows its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GaugeIndicator {
+    /// A needle from the center pointing at the value, over the zones
+    Needle,
+    /// The arc filled from the minimum up to the value, in the value's zone color
+    Fill,
+}
+
+/// An arc meter for a value within a range
+///
+/// Values outside the range are shown as text but pin the needle (or fill)
+/// to the end of the arc. Zones are drawn in the order they were added, so a
+/// later zone covers an earlier one where they overlap.
+///
+/// # Example
+///
+/// ```ignore
+/// Gauge::new(stats.cpu_temperature, 20.0..=100.0)
+///     .zone(20.0..=70.0, mocha::GREEN)
+///     .zone(70.0..=85.0, mocha::YELLOW)
+///     .zone(85.0..=100.0, mocha::RED)
+///     .label("CPU")
+///     .unit("°C")
+///     .node(&mut ctx)
+/// ```
+pub struct Gauge {
+    value: f32,
+    range: RangeInclusive<f32>,
+    zones: Vec<(RangeInclusive<f32>, Color)>,
+    indicator: GaugeIndicator,
+    label: Option<String>,
+    unit: String,
+    decimals: usize,
+    id: Option<String>,
+    style: Option<GaugeStyle>,
+}
+
+impl Gauge {
+    /// Create a gauge showing `value` within `range`
+    pub fn new(value: f32, range: RangeInclusive<f32>) -> Self {
+        Self {
+            value,
+            range,
+            zones: Vec::new(),
+            indicator: GaugeIndicator::Needle,
+            label: None,
+            unit: String::new(),
+            decimals: 0,
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Color the part of the arc covering `range`
+    pub fn zone(mut self, range: RangeInclusive<f32>, color: Color) -> Self {
+        self.zones.push((range, color));
+        self
+    }
+
+    /// Set how the value is shown (default: a needle)
+    pub fn indicator(mut self, indicator: GaugeIndicator) -> Self {
+        self.indicator = indicator;
+        self
+    }
+
+    /// Set a label shown under the value
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set a unit appended to the value text
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Set the number of decimals in the value text (default: 0)
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Set a custom ID for the gauge
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the gauge
+    pub fn with_style(mut self, style: GaugeStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for Gauge {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self.id.take().unwrap_or_else(|| ctx.generate_id("gauge"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| GaugeStyle::from_theme(ctx.theme()));
+
+        let now = ctx.time();
+        let state = ctx.memory().gauge(id.as_str());
+        state.retarget(self.value, now, style.animation_duration);
+        let shown = state.value_at(now, style.animation_duration);
+
+        let (min, max) = (*self.range.start(), *self.range.end());
+        let span = max - min;
+        let start = style.start_angle;
+        let sweep = style.sweep_angle;
+        let angle_of = move |value: f32| {
+            let fraction = if span > 0.0 {
+                ((value - min) / span).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            start + sweep * fraction
+        };
+
+        let value_text = format!("{:.*}{}", self.decimals, shown, self.unit);
+        let min_text = format!("{:.*}", self.decimals, min);
+        let max_text = format!("{:.*}", self.decimals, max);
+        let zone_color = self
+            .zones
+            .iter()
+            .rev()
+            .find(|(zone, _)| zone.contains(&shown.clamp(min, max)))
+            .map(|(_, color)| *color);
+        let indicator = self.indicator;
+        let zones = self.zones;
+        let label = self.label;
+
+        Node::new()
+            .with_id(NodeId::new(id))
+            .with_width(Size::lpx(style.size))
+            .with_height(Size::lpx(style.size))
+            .with_painter(move |painter| {
+                let center = [style.size * 0.5, style.size * 0.5];
+                let radius = (style.size - style.arc_width) * 0.5;
+                let text = |text: &str, size: f32, color: Color| {
+                    TextContent::new(text)
+                        .with_font_size(Size::lpx(size))
+                        .with_color(color)
+                        .with_h_align(HorizontalAlign::Center)
+                        .with_v_align(VerticalAlign::Center)
+                };
+
+                painter.arc(
+                    center,
+                    radius,
+                    start,
+                    start + sweep,
+                    style.arc_width,
+                    style.track_color,
+                );
+
+                // Zones are dimmed behind a filled arc so the fill stands out
+                let zone_alpha = match indicator {
+                    GaugeIndicator::Needle => 1.0,
+                    GaugeIndicator::Fill => 0.35,
+                };
+                for (zone, color) in &zones {
+                    painter.arc(
+                        center,
+                        radius,
+                        angle_of(*zone.start()),
+                        angle_of(*zone.end()),
+                        style.arc_width,
+                        color.with_alpha(color.a * zone_alpha),
+                    );
+                }
+
+                let value_angle = angle_of(shown);
+                let value_y = match indicator {
+                    GaugeIndicator::Needle => {
+                        let tip = radius - style.arc_width;
+                        painter.line(
+                            center,
+                            [
+                                center[0] + tip * value_angle.cos(),
+                                center[1] + tip * value_angle.sin(),
+                            ],
+                            style.needle_width,
+                            style.needle_color,
+                        );
+                        painter.circle(center, style.hub_size * 0.5, style.needle_color);
+                        // Below the hub, clear of the needle's sweep over the top
+                        center[1] + radius * 0.45
+                    }
+                    GaugeIndicator::Fill => {
+                        painter.arc(
+                            center,
+                            radius,
+                            start,
+                            value_angle,
+                            style.arc_width,
+                            zone_color.unwrap_or(style.fill_color),
+                        );
+                        center[1]
+                    }
+                };
+
+                painter.text(
+                    [center[0], value_y],
+                    text(&value_text, style.value_font_size, style.value_color),
+                );
+                if let Some(label) = &label {
+                    painter.text(
+                        [
+                            center[0],
+                            value_y + (style.value_font_size + style.label_font_size) * 0.6,
+                        ],
+                        text(label, style.label_font_size, style.label_color),
+                    );
+                }
+
+                // Min and max just inside the ends of the arc
+                let inset = radius - style.arc_width - style.label_font_size;
+                for (angle, bound) in [(start, &min_text), (start + sweep, &max_text)] {
+                    painter.text(
+                        [
+                            center[0] + inset * angle.cos(),
+                            center[1] + inset * angle.sin(),
+                        ],
+                        text(bound, style.label_font_size, style.label_color),
+                    );
+                }
+            })
+    }
+}
@@ -0,0 +1,467 @@
+//! Gradient editor component for interactive UI
+//!
+//! Edits a linear gradient in place: color stops sit as handles on a preview
+//! bar and are dragged along it, clicking the bar adds a stop, and the
+//! selected stop's color is edited with HSLA drag values. Every edit reports
+//! the resulting `Fill::LinearGradient`, ready to hand to a styled rect.
+
+use std::f32::consts::FRAC_PI_2;
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, Fill, InteractionEvent, Layout,
+    LinearGradient, MouseButton, Node, NodeId, Place, Size, Spacing, Stroke, Style, TextContent,
+    Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+use crate::inspector::{colors_differ, hex_code};
+use crate::{DragValue, DragValueStyle};
+
+/// Fewest stops a gradient editor leaves when removing stops
+const MIN_STOPS: usize = 2;
+
+/// Visual styling for a gradient editor
+#[derive(Debug, Clone, WithBuilders)]
+pub struct GradientEditorStyle {
+    /// Outline color of the preview bar and unselected stop handles
+    pub stroke_color: Color,
+    /// Outline color of the selected stop handle
+    pub selected_color: Color,
+    /// Color of the field labels
+    pub label_color: Color,
+    /// Color of the selected stop's hex code
+    pub value_color: Color,
+    /// Background color of the remove button
+    pub button_color: Color,
+    /// Background color of the hovered remove button
+    pub button_hover_color: Color,
+    /// Color of the remove button's text
+    pub button_text_color: Color,
+    /// Font size of labels, values and the remove button
+    pub font_size: f32,
+    /// Height of the preview bar
+    pub bar_height: f32,
+    /// Width of the stop handles
+    pub handle_width: f32,
+    /// How far the stop handles reach above and below the preview bar
+    pub handle_overhang: f32,
+    /// Width of the drag value editors
+    pub editor_width: f32,
+    /// Space between rows and between the fields of a row
+    pub gap: f32,
+    /// Corner radius of the preview bar, swatch and button
+    pub corner_radius: f32,
+}
+
+impl GradientEditorStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            stroke_color: theme.border,
+            selected_color: theme.primary,
+            label_color: theme.text_muted,
+            value_color: theme.text,
+            button_color: theme.field,
+            button_hover_color: theme.field_hover,
+            button_text_color: theme.text,
+            font_size: 14.0,
+            bar_height: 28.0,
+            handle_width: 12.0,
+            handle_overhang: 5.0,
+            editor_width: 64.0,
+            gap: 8.0,
+            corner_radius: 6.0,
+        }
+    }
+}
+
+impl Default for GradientEditorStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// An editor for the stops and angle of a linear gradient
+///
+/// Drag a stop's handle along the preview bar to move it, click the bar to
+/// add a stop with the color already shown there, and right-click a handle
+/// (or use the remove button) to delete a stop. At least two stops are
+/// always kept. The gradient is edited in place and its stops stay sorted.
+///
+/// # Example
+///
+/// ```ignore
+/// GradientEditor::new(&mut self.background)
+///     .on_change(|fill| card_style.fill = fill)
+///     .node(&mut ctx)
+/// ```
+pub struct GradientEditor<'a> {
+    gradient: &'a mut LinearGradient,
+    id: Option<String>,
+    style: Option<GradientEditorStyle>,
+    on_change: Option<Box<dyn FnMut(Fill) + 'a>>,
+}
+
+impl<'a> GradientEditor<'a> {
+    /// Create an editor for `gradient`
+    pub fn new(gradient: &'a mut LinearGradient) -> Self {
+        Self {
+            gradient,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the gradient editor
+    pub fn with_style(mut self, style: GradientEditorStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the edited gradient when it changes
+    pub fn on_change(mut self, f: impl FnMut(Fill) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+fn text(text: impl Into<String>, font_size: f32, color: Color) -> Node {
+    Node::new().with_content(Content::Text(
+        TextContent::new(text)
+            .with_font_size(Size::lpx(font_size))
+            .with_color(color)
+            .with_v_align(VerticalAlign::Center),
+    ))
+}
+
+/// Move the stop at `index` to `position`, keeping the stops sorted, and
+/// return its new index
+fn move_stop(stops: &mut Vec<(f32, Color)>, index: usize, position: f32) -> usize {
+    let (_, color) = stops.remove(index);
+    let position = position.clamp(0.0, 1.0);
+    let index = stops.partition_point(|(p, _)| *p <= position);
+    stops.insert(index, (position, color));
+    index
+}
+
+impl Component for GradientEditor<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("gradient_editor"));
+        let bar_id = format!("{}_bar", id);
+        let remove_id = format!("{}_remove", id);
+        let stop_id = |index: usize| format!("{}_stop_{}", id, index);
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| GradientEditorStyle::from_theme(ctx.theme()));
+        let theme = *ctx.theme();
+
+        let gradient = self.gradient;
+        let before = gradient.clone();
+        gradient.sort_stops();
+        let mut state = *ctx.memory().gradient_editor(&id);
+
+        // Width of the bar as of the last frame
+        let zoom = ctx
+            .events_for(&bar_id)
+            .last()
+            .map_or(ctx.scale_factor(), |event| event.zoom);
+        let width = ctx
+            .measured_size(&bar_id)
+            .map_or(240.0, |(width, _)| width / zoom)
+            .max(1.0);
+
+        // Select, drag and remove stops. The dragged stop is tracked by index
+        // in the state, since moving it past a neighbor renumbers the handles
+        // while the drag stays with the handle it started on. Releasing a drag
+        // also sends a click, which must not change the selection.
+        let drag_ended = |ctx: &UiContext, id: &str| {
+            ctx.events_for(id)
+                .any(|event| matches!(event.event, InteractionEvent::DragEnd { .. }))
+        };
+        let mut removed = None;
+        for index in 0..gradient.stops.len() {
+            let id = stop_id(index);
+            let dragged = drag_ended(ctx, &id);
+            for event in ctx.events_for(&id) {
+                match event.event {
+                    InteractionEvent::Click {
+                        button: MouseButton::Left,
+                        ..
+                    } if !dragged => state.selected = index,
+                    InteractionEvent::Click {
+                        button: MouseButton::Right,
+                        ..
+                    } => removed = Some(index),
+                    InteractionEvent::DragStart {
+                        button: MouseButton::Left,
+                        ..
+                    } => {
+                        state.selected = index;
+                        state.dragging = Some(index);
+                        state.drag_origin = gradient.stops[index].0;
+                        state.drag_offset = 0.0;
+                    }
+                    InteractionEvent::DragMove { delta, .. } => {
+                        if let Some(dragging) = state.dragging {
+                            state.drag_offset += delta.x / event.zoom / width;
+                            let moved = move_stop(
+                                &mut gradient.stops,
+                                dragging,
+                                state.drag_origin + state.drag_offset,
+                            );
+                            state.dragging = Some(moved);
+                            state.selected = moved;
+                        }
+                    }
+                    InteractionEvent::DragEnd { .. } => state.dragging = None,
+                    _ => {}
+                }
+            }
+        }
+
+        // Clicking the bar adds a stop with the color shown there. Releasing
+        // a drag also sends a click, which must not add one.
+        let dragged = drag_ended(ctx, &bar_id);
+        let added = ctx.events_for(&bar_id).find_map(|event| match event.event {
+            InteractionEvent::Click {
+                button: MouseButton::Left,
+                ..
+            } if !dragged => Some(event.local_position.x / event.zoom / width),
+            _ => None,
+        });
+        if let Some(position) = added {
+            let position = position.clamp(0.0, 1.0);
+            let color = gradient.color_at(position);
+            let index = gradient.stops.partition_point(|(p, _)| *p <= position);
+            gradient.stops.insert(index, (position, color));
+            state.selected = index;
+        }
+
+        let can_remove = gradient.stops.len() > MIN_STOPS;
+        if ctx.was_clicked(&remove_id) {
+            removed = Some(state.selected);
+        }
+        if let Some(index) = removed.filter(|_| can_remove && state.dragging.is_none()) {
+            if index < gradient.stops.len() {
+                gradient.stops.remove(index);
+                if state.selected > index || state.selected == gradient.stops.len() {
+                    state.selected = state.selected.saturating_sub(1);
+                }
+            }
+        }
+        state.selected = state.selected.min(gradient.stops.len().saturating_sub(1));
+        let can_remove = gradient.stops.len() > MIN_STOPS;
+
+        let mut drag_style = DragValueStyle::from_theme(&theme).with_min_width(style.editor_width);
+        drag_style.font_size = style.font_size;
+        drag_style.padding = Spacing::symmetric(Size::lpx(8.0), Size::lpx(4.0));
+        drag_style.text_input_style.font_size = style.font_size;
+        drag_style.text_input_style.padding = drag_style.padding;
+        let field = |name: &str, editor: Node| {
+            Node::new()
+                .with_gap(Size::lpx(style.gap / 2.0))
+                .with_layout_direction(Layout::Horizontal)
+                .with_v_align(VerticalAlign::Center)
+                .with_children(vec![text(name, style.font_size, style.label_color), editor])
+        };
+
+        // Position and color of the selected stop
+        let mut selected_fields = Vec::new();
+        let mut color_fields = Vec::new();
+        if let Some(&(position, color)) = gradient.stops.get(state.selected) {
+            // Follow edits made elsewhere, keeping the hue otherwise
+            let [h, s, l, a] = state.picker_hsla;
+            if colors_differ(Color::hsla(h, s, l, a), color) {
+                let [h, s, l] = color.to_hsl();
+                state.picker_hsla = [h, s, l, color.a];
+            }
+
+            let mut new_position = position;
+            let mut position_style = drag_style.clone();
+            position_style.precision = 2;
+            selected_fields.push(field(
+                "Position",
+                DragValue::new(&mut new_position)
+                    .range(0.0..=1.0)
+                    .speed(0.005)
+                    .with_style(position_style)
+                    .build(ctx),
+            ));
+
+            let mut hsla = state.picker_hsla;
+            let channels = [
+                ("H", 0.0..=360.0, 1.0, 0),
+                ("S", 0.0..=1.0, 0.005, 2),
+                ("L", 0.0..=1.0, 0.005, 2),
+                ("A", 0.0..=1.0, 0.005, 2),
+            ];
+            for (channel, (name, range, speed, precision)) in channels.into_iter().enumerate() {
+                let mut channel_style = drag_style.clone();
+                channel_style.precision = precision;
+                color_fields.push(field(
+                    name,
+                    DragValue::new(&mut hsla[channel])
+                        .range(range)
+                        .speed(speed)
+                        .with_style(channel_style)
+                        .build(ctx),
+                ));
+            }
+            if hsla != state.picker_hsla {
+                let [h, s, l, a] = hsla;
+                gradient.stops[state.selected].1 = Color::hsla(h, s, l, a);
+                state.picker_hsla = hsla;
+            }
+            if new_position != position {
+                state.selected = move_stop(&mut gradient.stops, state.selected, new_position);
+            }
+        }
+
+        let mut degrees = gradient.angle.to_degrees().rem_euclid(360.0);
+        let before_degrees = degrees;
+        let mut angle_style = drag_style.clone();
+        angle_style.precision = 0;
+        let angle_field = field(
+            "Angle",
+            DragValue::new(&mut degrees)
+                .range(0.0..=360.0)
+                .speed(1.0)
+                .with_style(angle_style)
+                .build(ctx),
+        );
+        if degrees != before_degrees {
+            gradient.angle = degrees.to_radians();
+        }
+        *ctx.memory().gradient_editor(&id) = state;
+
+        if *gradient != before {
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(Fill::LinearGradient(gradient.clone()));
+            }
+        }
+
+        // Stop handles, centered on their position and reaching past the bar
+        let handles = gradient
+            .stops
+            .iter()
+            .enumerate()
+            .map(|(index, (position, color))| {
+                let stroke = if index == state.selected {
+                    Stroke::new(Size::lpx(2.0), style.selected_color)
+                } else {
+                    Stroke::new(Size::lpx(1.5), style.stroke_color)
+                };
+                Node::new()
+                    .with_id(NodeId::new(stop_id(index)))
+                    .with_place(Place::Absolute {
+                        x: Size::lpx(position * width - style.handle_width * 0.5),
+                        y: Size::lpx(-style.handle_overhang),
+                    })
+                    .with_width(Size::lpx(style.handle_width))
+                    .with_height(Size::lpx(style.bar_height + style.handle_overhang * 2.0))
+                    .with_cursor(CursorIcon::EwResize)
+                    .with_style(Style {
+                        fill_color: Some(color.with_alpha(1.0)),
+                        stroke: Some(stroke),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(style.handle_width * 0.5))),
+                        ..Default::default()
+                    })
+            })
+            .collect();
+
+        // The preview always runs left to right so the handles line up
+        let preview = LinearGradient {
+            angle: FRAC_PI_2,
+            stops: gradient.stops.clone(),
+        };
+        let bar = Node::new()
+            .with_id(NodeId::new(&bar_id))
+            .with_measure_size(true)
+            .with_width(Size::Fill)
+            .with_height(Size::lpx(style.bar_height))
+            .with_layout_direction(Layout::Stack)
+            .with_cursor(CursorIcon::Crosshair)
+            .with_style(Style {
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                ..Style::linear_gradient(preview)
+            })
+            .with_children(handles);
+
+        let selected_color = gradient
+            .stops
+            .get(state.selected)
+            .map_or(Color::transparent(), |(_, color)| *color);
+        let swatch = Node::new()
+            .with_width(Size::lpx(style.font_size + 6.0))
+            .with_height(Size::lpx(style.font_size + 6.0))
+            .with_style(Style {
+                fill_color: Some(selected_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius / 2.0))),
+                ..Default::default()
+            });
+        let mut remove = Node::new()
+            .with_id(NodeId::new(&remove_id))
+            .with_padding(Spacing::symmetric(Size::lpx(10.0), Size::lpx(4.0)))
+            .with_content(Content::Text(
+                TextContent::new("Remove")
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(style.button_text_color),
+            ))
+            .with_style(Style {
+                fill_color: Some(style.button_color),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                ..Default::default()
+            })
+            .with_disabled(!can_remove)
+            .with_disabled_style(Style {
+                text_color: Some(theme.text_disabled),
+                ..Default::default()
+            })
+            .with_transition(Transition::quick());
+        if can_remove {
+            remove = remove
+                .with_cursor(CursorIcon::Pointer)
+                .with_hover_style(Style {
+                    fill_color: Some(style.button_hover_color),
+                    ..Default::default()
+                });
+        }
+
+        let mut stop_row = vec![
+            swatch,
+            text(hex_code(selected_color), style.font_size, style.value_color),
+        ];
+        stop_row.extend(selected_fields);
+        stop_row.extend([angle_field, remove]);
+
+        let row = |children: Vec<Node>| {
+            Node::new()
+                .with_width(Size::Fill)
+                .with_gap(Size::lpx(style.gap))
+                .with_layout_direction(Layout::Horizontal)
+                .with_v_align(VerticalAlign::Center)
+                .with_children(children)
+        };
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::Fill)
+            .with_padding(Spacing::vertical(Size::lpx(style.handle_overhang)))
+            .with_gap(Size::lpx(style.gap + style.handle_overhang))
+            .with_layout_direction(Layout::Vertical)
+            .with_children(vec![bar, row(stop_row), row(color_fields)])
+    }
+}
@@ -0,0 +1,581 @@
+//! Input masks for text inputs
+//!
+//! Masks restrict and format what can be typed into a `TextInput`. Pattern
+//! masks insert literal characters automatically (e.g. the parentheses and
+//! dash of a phone number) and keep the caret off them, currency masks only
+//! accept numbers while editing and format the value when the input loses
+//! focus.
+
+/// A single position in a mask pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaskToken {
+    /// Accepts an ASCII digit (`#`)
+    Digit,
+    /// Accepts an alphabetic character (`A`)
+    Letter,
+    /// Accepts an alphanumeric character (`*`)
+    Alphanumeric,
+    /// Inserted automatically, never typed
+    Literal(char),
+}
+
+impl MaskToken {
+    fn parse(ch: char) -> Self {
+        match ch {
+            '#' => MaskToken::Digit,
+            'A' => MaskToken::Letter,
+            '*' => MaskToken::Alphanumeric,
+            other => MaskToken::Literal(other),
+        }
+    }
+
+    fn accepts(&self, ch: char) -> bool {
+        match self {
+            MaskToken::Digit => ch.is_ascii_digit(),
+            MaskToken::Letter => ch.is_alphabetic(),
+            MaskToken::Alphanumeric => ch.is_alphanumeric(),
+            MaskToken::Literal(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MaskKind {
+    Pattern(Vec<MaskToken>),
+    Currency {
+        symbol: String,
+        decimals: usize,
+        thousands_separator: char,
+        decimal_separator: char,
+    },
+}
+
+/// Restricts and formats the text of a `TextInput`
+///
+/// # Example
+///
+/// ```ignore
+/// TextInput::new(&mut phone)
+///     .mask(InputMask::pattern("(###) ###-####"))
+///     .build(&mut ctx)
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputMask {
+    kind: MaskKind,
+}
+
+impl InputMask {
+    /// Create a pattern mask
+    ///
+    /// `#` accepts a digit, `A` a letter and `*` a letter or digit. Every
+    /// other character is a literal that is inserted automatically as the
+    /// user types and skipped by the caret, e.g. `"(###) ###-####"` or
+    /// `"####-##-##"`.
+    pub fn pattern(pattern: &str) -> Self {
+        Self {
+            kind: MaskKind::Pattern(pattern.chars().map(MaskToken::parse).collect()),
+        }
+    }
+
+    /// Create a currency mask with two decimals, `,` thousands and `.` decimal separators
+    ///
+    /// While focused the input only accepts digits and one decimal separator.
+    /// When it loses focus the value is formatted, e.g. `1234.5` becomes `$1,234.50`.
+    pub fn currency(symbol: impl Into<String>) -> Self {
+        Self {
+            kind: MaskKind::Currency {
+                symbol: symbol.into(),
+                decimals: 2,
+                thousands_separator: ',',
+                decimal_separator: '.',
+            },
+        }
+    }
+
+    /// Set the number of decimals for a currency mask
+    pub fn with_decimals(mut self, count: usize) -> Self {
+        if let MaskKind::Currency { decimals, .. } = &mut self.kind {
+            *decimals = count;
+        }
+        self
+    }
+
+    /// Set the thousands and decimal separators for a currency mask
+    pub fn with_separators(mut self, thousands: char, decimal: char) -> Self {
+        if let MaskKind::Currency {
+            thousands_separator,
+            decimal_separator,
+            ..
+        } = &mut self.kind
+        {
+            *thousands_separator = thousands;
+            *decimal_separator = decimal;
+        }
+        self
+    }
+
+    /// Get the user-entered characters without literals or currency formatting
+    ///
+    /// For `"(###) ###-####"` and `"(555) 123-4567"` this returns `"5551234567"`.
+    pub fn raw_value(&self, value: &str) -> String {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                extract(tokens, value).into_iter().map(|(_, c)| c).collect()
+            }
+            MaskKind::Currency { .. } => self.unformat(value),
+        }
+    }
+
+    /// Check whether every slot of a pattern mask is filled
+    ///
+    /// Currency masks are complete as soon as they contain a digit.
+    pub fn is_complete(&self, value: &str) -> bool {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let slots = tokens
+                    .iter()
+                    .filter(|t| !matches!(t, MaskToken::Literal(_)))
+                    .count();
+                extract(tokens, value).len() == slots
+            }
+            MaskKind::Currency { .. } => value.chars().any(|c| c.is_ascii_digit()),
+        }
+    }
+
+    /// Format a value for display while the input is not focused
+    ///
+    /// Pattern masks re-apply their literals, currency masks add the symbol,
+    /// thousands separators and pad the decimals.
+    pub fn format(&self, value: &str) -> String {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => conform(tokens, value),
+            MaskKind::Currency {
+                symbol,
+                decimals,
+                thousands_separator,
+                decimal_separator,
+            } => {
+                let raw = self.unformat(value);
+                if !raw.chars().any(|c| c.is_ascii_digit()) {
+                    return String::new();
+                }
+
+                let (int_part, frac_part) = match raw.split_once(*decimal_separator) {
+                    Some((int_part, frac_part)) => (int_part, frac_part),
+                    None => (raw.as_str(), ""),
+                };
+                let int_part = int_part.trim_start_matches('0');
+                let int_part = if int_part.is_empty() { "0" } else { int_part };
+
+                let mut formatted = symbol.clone();
+                let len = int_part.len();
+                for (i, c) in int_part.chars().enumerate() {
+                    if i > 0 && (len - i) % 3 == 0 {
+                        formatted.push(*thousands_separator);
+                    }
+                    formatted.push(c);
+                }
+
+                if *decimals > 0 {
+                    formatted.push(*decimal_separator);
+                    let mut frac: String = frac_part.chars().take(*decimals).collect();
+                    while frac.len() < *decimals {
+                        frac.push('0');
+                    }
+                    formatted.push_str(&frac);
+                }
+
+                formatted
+            }
+        }
+    }
+
+    /// Remove display formatting so the value can be edited
+    ///
+    /// Pattern masks keep their literals while editing, so for them this only
+    /// re-applies the pattern.
+    pub fn unformat(&self, value: &str) -> String {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => conform(tokens, value),
+            MaskKind::Currency {
+                decimal_separator, ..
+            } => {
+                let mut seen_separator = false;
+                value
+                    .chars()
+                    .filter(|&c| {
+                        if c == *decimal_separator && !seen_separator {
+                            seen_separator = true;
+                            true
+                        } else {
+                            c.is_ascii_digit()
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Insert a typed character at `cursor`, returning the new cursor
+    ///
+    /// Returns `None` if the mask rejects the character.
+    pub(crate) fn insert(&self, value: &mut String, cursor: usize, ch: char) -> Option<usize> {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let mut raw: Vec<char> =
+                    extract(tokens, value).into_iter().map(|(_, c)| c).collect();
+                let index = raw_index_at(tokens, value, cursor);
+                let slots: Vec<&MaskToken> = tokens
+                    .iter()
+                    .filter(|t| !matches!(t, MaskToken::Literal(_)))
+                    .collect();
+                let slot = slots.get(index)?;
+
+                if !slot.accepts(ch) {
+                    // Typing a literal the mask inserts anyway is not an error
+                    return tokens.contains(&MaskToken::Literal(ch)).then_some(cursor);
+                }
+
+                // The characters after the caret shift one slot to the right,
+                // so they need a free slot and must fit their new ones
+                if raw.len() >= slots.len() {
+                    return None;
+                }
+                raw.insert(index, ch);
+                if !raw.iter().zip(&slots).all(|(c, slot)| slot.accepts(*c)) {
+                    return None;
+                }
+                *value = apply_pattern(tokens, &raw);
+                Some(caret_for(tokens, value, index + 1))
+            }
+            MaskKind::Currency {
+                decimals,
+                decimal_separator,
+                ..
+            } => {
+                let separator_pos = value.find(*decimal_separator);
+                let accepted = if ch.is_ascii_digit() {
+                    // Limit the number of decimals typed after the separator
+                    match separator_pos {
+                        Some(pos) if cursor > pos => {
+                            value[pos + decimal_separator.len_utf8()..].len() < *decimals
+                        }
+                        _ => true,
+                    }
+                } else if ch == *decimal_separator || ch == '.' {
+                    *decimals > 0 && separator_pos.is_none()
+                } else {
+                    false
+                };
+
+                if !accepted {
+                    return None;
+                }
+
+                let ch = if ch.is_ascii_digit() {
+                    ch
+                } else {
+                    *decimal_separator
+                };
+                value.insert(cursor, ch);
+                Some(cursor + ch.len_utf8())
+            }
+        }
+    }
+
+    /// Remove the text between `start` and `end`, returning the new cursor
+    pub(crate) fn remove(&self, value: &mut String, start: usize, end: usize) -> usize {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let mut raw: Vec<char> =
+                    extract(tokens, value).into_iter().map(|(_, c)| c).collect();
+                let raw_start = raw_index_at(tokens, value, start);
+                let raw_end = raw_index_at(tokens, value, end).max(raw_start);
+                raw.drain(raw_start..raw_end);
+                *value = apply_pattern(tokens, &raw);
+                caret_for(tokens, value, raw_start)
+            }
+            MaskKind::Currency { .. } => {
+                value.replace_range(start..end, "");
+                start
+            }
+        }
+    }
+
+    /// Delete the character before `cursor`, skipping literals
+    pub(crate) fn backspace(&self, value: &mut String, cursor: usize) -> usize {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let index = raw_index_at(tokens, value, cursor);
+                if index == 0 {
+                    return cursor.min(caret_for(tokens, value, 0));
+                }
+                let start = caret_for(tokens, value, index - 1);
+                self.remove(value, start, cursor)
+            }
+            MaskKind::Currency { .. } => {
+                let start = prev_char_boundary(value, cursor);
+                self.remove(value, start, cursor)
+            }
+        }
+    }
+
+    /// Delete the character after `cursor`, skipping literals
+    pub(crate) fn delete(&self, value: &mut String, cursor: usize) -> usize {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let index = raw_index_at(tokens, value, cursor);
+                let start = caret_for(tokens, value, index);
+                let end = caret_for(tokens, value, index + 1);
+                self.remove(value, start, end)
+            }
+            MaskKind::Currency { .. } => {
+                let end = next_char_boundary(value, cursor);
+                self.remove(value, cursor, end)
+            }
+        }
+    }
+
+    /// Move the caret one editable position to the left
+    pub(crate) fn prev_caret(&self, value: &str, cursor: usize) -> usize {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let index = raw_index_at(tokens, value, cursor);
+                caret_for(tokens, value, index.saturating_sub(1)).min(cursor)
+            }
+            MaskKind::Currency { .. } => prev_char_boundary(value, cursor),
+        }
+    }
+
+    /// Move the caret one editable position to the right
+    pub(crate) fn next_caret(&self, value: &str, cursor: usize) -> usize {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => {
+                let index = raw_index_at(tokens, value, cursor);
+                caret_for(tokens, value, index + 1).max(cursor)
+            }
+            MaskKind::Currency { .. } => next_char_boundary(value, cursor),
+        }
+    }
+
+    /// Get the first editable caret position
+    pub(crate) fn first_caret(&self, value: &str) -> usize {
+        match &self.kind {
+            MaskKind::Pattern(tokens) => caret_for(tokens, value, 0),
+            MaskKind::Currency { .. } => 0,
+        }
+    }
+}
+
+/// Match `value` against the slots of the pattern
+///
+/// Returns the byte position and character of every accepted character.
+/// Characters that don't fit the next slot (literals, invalid input) are
+/// skipped, so this also works for values that were set from outside.
+fn extract(tokens: &[MaskToken], value: &str) -> Vec<(usize, char)> {
+    let mut slots = tokens
+        .iter()
+        .filter(|t| !matches!(t, MaskToken::Literal(_)))
+        .peekable();
+    let mut accepted = Vec::new();
+    for (pos, ch) in value.char_indices() {
+        let Some(slot) = slots.peek() else {
+            break;
+        };
+        if slot.accepts(ch) {
+            accepted.push((pos, ch));
+            slots.next();
+        }
+    }
+    accepted
+}
+
+/// Number of accepted characters before byte position `cursor`
+fn raw_index_at(tokens: &[MaskToken], value: &str, cursor: usize) -> usize {
+    extract(tokens, value)
+        .iter()
+        .take_while(|(pos, _)| *pos < cursor)
+        .count()
+}
+
+/// Build the displayed text for the raw characters
+///
+/// Literals are emitted up to the next empty slot, so separators appear as
+/// soon as the slot before them is filled.
+fn apply_pattern(tokens: &[MaskToken], raw: &[char]) -> String {
+    let mut formatted = String::new();
+    if raw.is_empty() {
+        return formatted;
+    }
+
+    let mut raw = raw.iter();
+    for token in tokens {
+        match token {
+            MaskToken::Literal(c) => formatted.push(*c),
+            _ => match raw.next() {
+                Some(c) => formatted.push(*c),
+                None => break,
+            },
+        }
+    }
+    formatted
+}
+
+/// Byte position of the `index`-th slot in a conformed value
+///
+/// This is right after the previous accepted character and the literals
+/// following it, or the end of the value if the slot is not filled yet.
+fn caret_for(tokens: &[MaskToken], value: &str, index: usize) -> usize {
+    let mut filled = 0;
+    for ((pos, _), token) in value.char_indices().zip(tokens) {
+        if !matches!(token, MaskToken::Literal(_)) {
+            if filled == index {
+                return pos;
+            }
+            filled += 1;
+        }
+    }
+    value.len()
+}
+
+/// Re-apply the pattern to whatever accepted characters `value` contains
+fn conform(tokens: &[MaskToken], value: &str) -> String {
+    let raw: Vec<char> = extract(tokens, value).into_iter().map(|(_, c)| c).collect();
+    apply_pattern(tokens, &raw)
+}
+
+fn prev_char_boundary(value: &str, cursor: usize) -> usize {
+    value[..cursor]
+        .char_indices()
+        .next_back()
+        .map(|(pos, _)| pos)
+        .unwrap_or(0)
+}
+
+fn next_char_boundary(value: &str, cursor: usize) -> usize {
+    value[cursor..]
+        .chars()
+        .next()
+        .map(|c| cursor + c.len_utf8())
+        .unwrap_or(value.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Type `text` at the end of `value`, skipping rejected characters
+    fn type_text(mask: &InputMask, value: &mut String, text: &str) {
+        for ch in text.chars() {
+            let end = value.len();
+            mask.insert(value, end, ch);
+        }
+    }
+
+    #[test]
+    fn test_pattern_inserts_literals_while_typing() {
+        let mask = InputMask::pattern("(###) ###-####");
+        let mut value = String::new();
+
+        type_text(&mask, &mut value, "555");
+        assert_eq!(value, "(555) ");
+        type_text(&mask, &mut value, "x1234567");
+        assert_eq!(value, "(555) 123-4567");
+        assert!(mask.is_complete(&value));
+
+        // Extra digits have no free slot
+        assert_eq!(mask.insert(&mut value, 14, '8'), None);
+        assert_eq!(value, "(555) 123-4567");
+    }
+
+    #[test]
+    fn test_pattern_extracts_and_formats_raw_values() {
+        let mask = InputMask::pattern("(###) ###-####");
+        assert_eq!(mask.raw_value("(555) 123-4567"), "5551234567");
+        assert_eq!(mask.raw_value("555-12"), "55512");
+        assert_eq!(mask.format("5551234567"), "(555) 123-4567");
+        assert_eq!(mask.format("55512"), "(555) 12");
+        assert_eq!(mask.format(""), "");
+        assert!(!mask.is_complete("(555) 12"));
+    }
+
+    #[test]
+    fn test_pattern_insert_keeps_shifted_characters_in_fitting_slots() {
+        let mask = InputMask::pattern("AA##");
+        let mut value = String::new();
+        type_text(&mask, &mut value, "ab1");
+        assert_eq!(value, "ab1");
+
+        // Inserting before `b` would shift it into a digit slot
+        assert_eq!(mask.insert(&mut value, 0, 'c'), None);
+        assert_eq!(mask.insert(&mut value, 0, '2'), None);
+        assert_eq!(value, "ab1");
+
+        // Inserting before `1` shifts it into another digit slot
+        assert_eq!(mask.insert(&mut value, 2, '0'), Some(3));
+        assert_eq!(value, "ab01");
+        assert_eq!(mask.raw_value(&value), "ab01");
+
+        // A full field rejects inserts in the middle instead of dropping the end
+        let mask = InputMask::pattern("####");
+        let mut value = "1234".to_string();
+        assert_eq!(mask.insert(&mut value, 1, '9'), None);
+        assert_eq!(value, "1234");
+    }
+
+    #[test]
+    fn test_pattern_insert_skips_literals() {
+        let mask = InputMask::pattern("##-##");
+        let mut value = "12-".to_string();
+
+        // Typing the literal itself keeps the caret where it is
+        assert_eq!(mask.insert(&mut value, 3, '-'), Some(3));
+        assert_eq!(mask.insert(&mut value, 3, '3'), Some(4));
+        assert_eq!(value, "12-3");
+
+        // Inserting in the middle moves later characters across the literal
+        assert_eq!(mask.insert(&mut value, 1, '9'), Some(3));
+        assert_eq!(value, "19-23");
+    }
+
+    #[test]
+    fn test_currency_formats_and_unformats() {
+        let mask = InputMask::currency("$");
+        assert_eq!(mask.format("1234.5"), "$1,234.50");
+        assert_eq!(mask.format("0001234567"), "$1,234,567.00");
+        assert_eq!(mask.format(".5"), "$0.50");
+        assert_eq!(mask.format(""), "");
+        assert_eq!(mask.unformat("$1,234.50"), "1234.50");
+        assert_eq!(mask.raw_value("$1,234.50"), "1234.50");
+
+        let mask = InputMask::currency("€")
+            .with_separators('.', ',')
+            .with_decimals(0);
+        assert_eq!(mask.format("1234567"), "€1.234.567");
+    }
+
+    #[test]
+    fn test_currency_insert_limits_decimals() {
+        let mask = InputMask::currency("$");
+        let mut value = String::new();
+        type_text(&mask, &mut value, "12a.3.45");
+        assert_eq!(value, "12.34");
+
+        // Digits before the separator are not limited
+        assert_eq!(mask.insert(&mut value, 0, '9'), Some(1));
+        assert_eq!(value, "912.34");
+    }
+
+    #[test]
+    fn test_currency_insert_with_multi_byte_decimal_separator() {
+        let mask = InputMask::currency("$").with_separators('.', '٫');
+        let mut value = String::new();
+
+        // `.` types the configured separator
+        type_text(&mask, &mut value, "12.5");
+        assert_eq!(value, "12٫5");
+        type_text(&mask, &mut value, "06");
+        assert_eq!(value, "12٫50");
+        assert_eq!(mask.format(&value), "$12٫50");
+    }
+}
@@ -0,0 +1,497 @@
+//! Property inspector component for interactive UI
+//!
+//! Renders a list of labeled properties as editor rows, picking the widget
+//! from each value's type: booleans get a toggle, floats a drag value, colors
+//! a swatch that opens an HSLA picker, and strings a text input. Meant for
+//! editor tooling where values of mixed types are tweaked side by side.
+
+use std::cell::Cell;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Size, Spacing, Stroke, Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+use crate::{DragValue, DragValueStyle, TextInput, TextInputStyle, Toggle, ToggleStyle};
+
+/// Visual styling for a property inspector
+#[derive(Debug, Clone, WithBuilders)]
+pub struct InspectorStyle {
+    /// Color of the property labels
+    pub label_color: Color,
+    /// Color of a color property's hex code
+    pub value_color: Color,
+    /// Outline color of the color swatches
+    pub swatch_stroke_color: Color,
+    /// Background color of a hovered color property
+    pub swatch_hover_color: Color,
+    /// Background color of an open color picker
+    pub picker_color: Color,
+    /// Font size of labels and values
+    pub font_size: f32,
+    /// Width of the label column
+    pub label_width: f32,
+    /// Width of the drag value and text input editors
+    pub editor_width: f32,
+    /// Side length of the color swatches
+    pub swatch_size: f32,
+    /// Vertical space between rows
+    pub row_gap: f32,
+    /// Corner radius of swatches and the picker
+    pub corner_radius: f32,
+    /// Styling of the boolean toggles
+    pub toggle: ToggleStyle,
+}
+
+impl InspectorStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            label_color: theme.text_muted,
+            value_color: theme.text,
+            swatch_stroke_color: theme.border,
+            swatch_hover_color: theme.surface_hover,
+            picker_color: theme.surface,
+            font_size: 14.0,
+            label_width: 120.0,
+            editor_width: 160.0,
+            swatch_size: 20.0,
+            row_gap: 8.0,
+            corner_radius: 6.0,
+            toggle: ToggleStyle {
+                track_width: 38.0,
+                track_height: 22.0,
+                knob_width: 18.0,
+                ..ToggleStyle::from_theme(theme)
+            },
+        }
+    }
+}
+
+impl Default for InspectorStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A value edited by an [`Inspector`] row
+pub enum PropertyValue<'a> {
+    /// Edited with a toggle
+    Bool(&'a mut bool),
+    /// Edited with a drag value
+    Float(&'a mut f32),
+    /// Edited with a swatch that opens an HSLA picker
+    Color(&'a mut Color),
+    /// Edited with a text input
+    Text(&'a mut String),
+}
+
+/// A labeled value shown as one row of an [`Inspector`]
+pub struct Property<'a> {
+    label: String,
+    value: PropertyValue<'a>,
+    range: Option<RangeInclusive<f32>>,
+    speed: Option<f32>,
+    disabled: bool,
+}
+
+impl<'a> Property<'a> {
+    /// Create a property editing `value`
+    pub fn new(label: impl Into<String>, value: PropertyValue<'a>) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            range: None,
+            speed: None,
+            disabled: false,
+        }
+    }
+
+    /// A boolean property, edited with a toggle
+    pub fn bool(label: impl Into<String>, value: &'a mut bool) -> Self {
+        Self::new(label, PropertyValue::Bool(value))
+    }
+
+    /// A float property, edited with a drag value
+    pub fn float(label: impl Into<String>, value: &'a mut f32) -> Self {
+        Self::new(label, PropertyValue::Float(value))
+    }
+
+    /// A color property, edited with a swatch and HSLA picker
+    pub fn color(label: impl Into<String>, value: &'a mut Color) -> Self {
+        Self::new(label, PropertyValue::Color(value))
+    }
+
+    /// A string property, edited with a text input
+    pub fn text(label: impl Into<String>, value: &'a mut String) -> Self {
+        Self::new(label, PropertyValue::Text(value))
+    }
+
+    /// Set the valid range of a float property
+    pub fn range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Set the drag speed of a float property
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Set whether the property is read-only
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Called with the label of the property that changed
+type ChangeFn<'a> = Box<dyn FnMut(&str) + 'a>;
+
+/// Labeled editor rows for a list of properties of mixed types
+///
+/// The values are borrowed mutably and edited in place; `on_change` reports
+/// the label of each property that changed this frame.
+///
+/// # Example
+///
+/// ```ignore
+/// Inspector::new(vec![
+///     Property::bool("Visible", &mut light.visible),
+///     Property::float("Intensity", &mut light.intensity).range(0.0..=10.0),
+///     Property::color("Color", &mut light.color),
+///     Property::text("Name", &mut light.name),
+/// ])
+/// .on_change(|label| println!("{} changed", label))
+/// .node(&mut ctx)
+/// ```
+pub struct Inspector<'a> {
+    properties: Vec<Property<'a>>,
+    id: Option<String>,
+    style: Option<InspectorStyle>,
+    on_change: Option<ChangeFn<'a>>,
+}
+
+impl<'a> Inspector<'a> {
+    /// Create an inspector showing the given properties in order
+    pub fn new(properties: Vec<Property<'a>>) -> Self {
+        Self {
+            properties,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the inspector
+    pub fn with_style(mut self, style: InspectorStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the label of each changed property
+    pub fn on_change(mut self, f: impl FnMut(&str) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+/// Hex code of a color in sRGB, as `#RRGGBB` or `#RRGGBBAA` when translucent
+pub(crate) fn hex_code(color: Color) -> String {
+    let channel = |linear: f32| {
+        let c = linear.clamp(0.0, 1.0);
+        let srgb = if c <= 0.003_130_8 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (srgb * 255.0).round() as u8
+    };
+    let mut hex = format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    );
+    if color.a < 1.0 {
+        hex.push_str(&format!(
+            "{:02X}",
+            (color.a.clamp(0.0, 1.0) * 255.0).round() as u8
+        ));
+    }
+    hex
+}
+
+/// Check whether two colors differ by more than rounding in any channel
+pub(crate) fn colors_differ(a: Color, b: Color) -> bool {
+    [a.r - b.r, a.g - b.g, a.b - b.b, a.a - b.a]
+        .iter()
+        .any(|d| d.abs() > 1e-3)
+}
+
+fn text(text: impl Into<String>, font_size: f32, color: Color) -> Node {
+    Node::new().with_content(Content::Text(
+        TextContent::new(text)
+            .with_font_size(Size::lpx(font_size))
+            .with_color(color)
+            .with_v_align(VerticalAlign::Center),
+    ))
+}
+
+impl Component for Inspector<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("inspector"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| InspectorStyle::from_theme(ctx.theme()));
+        let theme = *ctx.theme();
+
+        let mut drag_style = DragValueStyle::from_theme(&theme).with_min_width(style.editor_width);
+        drag_style.font_size = style.font_size;
+        drag_style.padding = Spacing::symmetric(Size::lpx(8.0), Size::lpx(4.0));
+        drag_style.text_input_style.font_size = style.font_size;
+        drag_style.text_input_style.padding = drag_style.padding;
+        let mut text_style = TextInputStyle::from_theme(&theme);
+        text_style.width = style.editor_width;
+        text_style.font_size = style.font_size;
+        text_style.padding = Spacing::symmetric(Size::lpx(8.0), Size::lpx(4.0));
+
+        let properties = std::mem::take(&mut self.properties);
+        let mut state = *ctx.memory().inspector(&id);
+        if state
+            .open_picker
+            .is_some_and(|index| index >= properties.len())
+        {
+            state.open_picker = None;
+        }
+
+        let mut rows = Vec::new();
+        let mut changed = Vec::new();
+        for (index, property) in properties.into_iter().enumerate() {
+            let Property {
+                label,
+                value,
+                range,
+                speed,
+                disabled,
+            } = property;
+            let mut picker = None;
+
+            let (editor, did_change) = match value {
+                PropertyValue::Bool(value) => {
+                    // The toggle reports clicks through a 'static callback
+                    let toggled: Rc<Cell<Option<bool>>> = Rc::default();
+                    let sink = toggled.clone();
+                    let node = Toggle::new(*value)
+                        .disabled(disabled)
+                        .with_style(style.toggle.clone())
+                        .on_toggle(move |new_value| sink.set(Some(new_value)))
+                        .node(ctx);
+                    let did_change = toggled.get().is_some_and(|new_value| new_value != *value);
+                    if let Some(new_value) = toggled.get() {
+                        *value = new_value;
+                    }
+                    (node, did_change)
+                }
+                PropertyValue::Float(value) => {
+                    let before = *value;
+                    let mut drag_value = DragValue::new(&mut *value)
+                        .disabled(disabled)
+                        .with_style(drag_style.clone());
+                    if let Some(range) = range {
+                        drag_value = drag_value.range(range);
+                    }
+                    if let Some(speed) = speed {
+                        drag_value = drag_value.speed(speed);
+                    }
+                    let node = drag_value.build(ctx);
+                    (node, *value != before)
+                }
+                PropertyValue::Text(value) => {
+                    let before = value.clone();
+                    let node = TextInput::new(&mut *value)
+                        .with_id(format!("{}_text_{}", id, index))
+                        .disabled(disabled)
+                        .with_style(text_style.clone())
+                        .build(ctx);
+                    (node, *value != before)
+                }
+                PropertyValue::Color(value) => {
+                    let swatch_id = format!("{}_color_{}", id, index);
+                    if !disabled && ctx.was_clicked(&swatch_id) {
+                        state.open_picker = if state.open_picker == Some(index) {
+                            None
+                        } else {
+                            let [h, s, l] = value.to_hsl();
+                            state.picker_hsla = [h, s, l, value.a];
+                            Some(index)
+                        };
+                    }
+
+                    let mut did_change = false;
+                    if state.open_picker == Some(index) {
+                        // Follow edits made elsewhere, keeping the hue otherwise
+                        let [h, s, l, a] = state.picker_hsla;
+                        if colors_differ(Color::hsla(h, s, l, a), *value) {
+                            let [h, s, l] = value.to_hsl();
+                            state.picker_hsla = [h, s, l, value.a];
+                        }
+
+                        let mut hsla = state.picker_hsla;
+                        let fields = [
+                            ("H", 0.0..=360.0, 1.0, 0),
+                            ("S", 0.0..=1.0, 0.005, 2),
+                            ("L", 0.0..=1.0, 0.005, 2),
+                            ("A", 0.0..=1.0, 0.005, 2),
+                        ];
+                        let mut field_rows = Vec::new();
+                        for (channel, (name, range, speed, precision)) in
+                            fields.into_iter().enumerate()
+                        {
+                            // Fields line up with the editors above, after the channel letter
+                            let mut field_style = drag_style.clone().with_min_width(
+                                (style.editor_width - style.font_size - style.row_gap * 3.0)
+                                    .max(40.0),
+                            );
+                            field_style.precision = precision;
+                            field_rows.push(
+                                Node::new()
+                                    .with_gap(Size::lpx(style.row_gap))
+                                    .with_layout_direction(Layout::Horizontal)
+                                    .with_v_align(VerticalAlign::Center)
+                                    .with_children(vec![
+                                        text(name, style.font_size, style.label_color)
+                                            .with_width(Size::lpx(style.font_size)),
+                                        DragValue::new(&mut hsla[channel])
+                                            .range(range)
+                                            .speed(speed)
+                                            .with_style(field_style)
+                                            .build(ctx),
+                                    ]),
+                            );
+                        }
+                        if hsla != state.picker_hsla {
+                            let [h, s, l, a] = hsla;
+                            *value = Color::hsla(h, s, l, a);
+                            state.picker_hsla = hsla;
+                            did_change = true;
+                        }
+
+                        // Indented under the editors, past the label column
+                        picker = Some(
+                            Node::new()
+                                .with_width(Size::Fill)
+                                .with_gap(Size::lpx(style.row_gap))
+                                .with_layout_direction(Layout::Horizontal)
+                                .with_children(vec![
+                                    Node::new().with_width(Size::lpx(style.label_width)),
+                                    Node::new()
+                                        .with_padding(Spacing::all(Size::lpx(style.row_gap)))
+                                        .with_gap(Size::lpx(style.row_gap / 2.0))
+                                        .with_layout_direction(Layout::Vertical)
+                                        .with_style(Style {
+                                            fill_color: Some(style.picker_color),
+                                            corner_shape: Some(CornerShape::Round(Size::lpx(
+                                                style.corner_radius,
+                                            ))),
+                                            ..Default::default()
+                                        })
+                                        .with_children(field_rows),
+                                ]),
+                        );
+                    }
+
+                    let swatch = Node::new()
+                        .with_width(Size::lpx(style.swatch_size))
+                        .with_height(Size::lpx(style.swatch_size))
+                        .with_style(Style {
+                            fill_color: Some(*value),
+                            stroke: Some(Stroke::new(Size::lpx(1.0), style.swatch_stroke_color)),
+                            corner_shape: Some(CornerShape::Round(Size::lpx(
+                                style.corner_radius / 2.0,
+                            ))),
+                            ..Default::default()
+                        });
+                    let mut node = Node::new()
+                        .with_id(NodeId::new(&swatch_id))
+                        .with_padding(Spacing::all(Size::lpx(4.0)))
+                        .with_gap(Size::lpx(style.row_gap))
+                        .with_layout_direction(Layout::Horizontal)
+                        .with_v_align(VerticalAlign::Center)
+                        .with_style(Style {
+                            fill_color: Some(style.swatch_hover_color.with_alpha(0.0)),
+                            corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                            ..Default::default()
+                        })
+                        .with_children(vec![
+                            swatch,
+                            text(hex_code(*value), style.font_size, style.value_color),
+                        ])
+                        .with_disabled(disabled)
+                        .with_transition(Transition::quick());
+                    if !disabled {
+                        node = node
+                            .with_cursor(CursorIcon::Pointer)
+                            .with_hover_style(Style {
+                                fill_color: Some(style.swatch_hover_color),
+                                ..Default::default()
+                            });
+                    }
+                    (node, did_change)
+                }
+            };
+
+            if did_change {
+                changed.push(label.clone());
+            }
+            rows.push(
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_gap(Size::lpx(style.row_gap))
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_v_align(VerticalAlign::Center)
+                    .with_children(vec![
+                        Node::new()
+                            .with_width(Size::lpx(style.label_width))
+                            .with_content(Content::Text(
+                                TextContent::new(label)
+                                    .with_font_size(Size::lpx(style.font_size))
+                                    .with_color(style.label_color)
+                                    .with_h_align(HorizontalAlign::Left)
+                                    .with_v_align(VerticalAlign::Center),
+                            )),
+                        editor,
+                    ]),
+            );
+            rows.extend(picker);
+        }
+        *ctx.memory().inspector(&id) = state;
+
+        if let Some(ref mut on_change) = self.on_change {
+            for label in &changed {
+                on_change(label);
+            }
+        }
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::Fill)
+            .with_gap(Size::lpx(style.row_gap))
+            .with_layout_direction(Layout::Vertical)
+            .with_children(rows)
+    }
+}
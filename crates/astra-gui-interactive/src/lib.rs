@@ -3,20 +3,78 @@
 //! Interactive UI components library for astra-gui.
 //!
 //! This crate provides reusable interactive components like buttons, toggles,
-//! and sliders that work with the astra-gui framework's hybrid architecture.
+//! checkboxes, badges, chips, sliders, range sliders, number inputs, search inputs, plots, gauges, gradient editors, timelines, property inspectors, text areas, code editors, scrollbars, virtualized lists, reorderable lists, pagination, menus, context menus, modal dialogs, floating windows, drop zones and canvases that work with the astra-gui framework's hybrid architecture.
 
+mod badge;
 mod button;
+mod canvas;
+mod checkbox;
+mod chip;
+mod code_editor;
 mod collapsible;
+mod context_menu;
 mod drag_value;
+mod drop_zone;
+mod frame_time_graph;
+mod gauge;
+mod gradient_editor;
+mod input_mask;
+mod inspector;
+mod list_view;
+mod menu;
+mod modal;
+mod number_input;
+mod pagination;
+mod peek;
+mod plot;
+mod range_slider;
+mod reorderable_list;
+mod resizable;
+mod scrollbar;
+mod search_input;
 mod slider;
 mod slider_with_value;
+mod spinner;
+mod swipe_actions;
+mod text_area;
 mod text_input;
+mod timeline;
 mod toggle;
+mod window;
 
+pub use badge::*;
 pub use button::*;
+pub use canvas::*;
+pub use checkbox::*;
+pub use chip::*;
+pub use code_editor::*;
 pub use collapsible::*;
+pub use context_menu::*;
 pub use drag_value::*;
+pub use drop_zone::*;
+pub use frame_time_graph::*;
+pub use gauge::*;
+pub use gradient_editor::*;
+pub use input_mask::*;
+pub use inspector::*;
+pub use list_view::*;
+pub use menu::*;
+pub use modal::*;
+pub use number_input::*;
+pub use pagination::*;
+pub use peek::*;
+pub use plot::*;
+pub use range_slider::*;
+pub use reorderable_list::*;
+pub use resizable::*;
+pub use scrollbar::*;
+pub use search_input::*;
 pub use slider::*;
 pub use slider_with_value::*;
+pub use spinner::*;
+pub use swipe_actions::*;
+pub use text_area::*;
 pub use text_input::*;
+pub use timeline::*;
 pub use toggle::*;
+pub use window::*;
@@ -0,0 +1,223 @@
+//! Virtualized list component for interactive UI
+//!
+//! Only the rows that intersect the viewport (plus a few on either side) are
+//! built; the rest of the list is stood in for by two spacers. Row heights are
+//! measured once a row has been laid out and estimated until then, and the
+//! first visible row is kept in place when rows above it are inserted, removed
+//! or turn out taller or shorter than estimated.
+
+use crate::{ScrollArea, ScrollbarStyle};
+use astra_gui::{Component, Layout, Node, NodeId, Size, UiContext};
+
+/// Rows assumed to fill the viewport before its size is known
+const FIRST_FRAME_ROWS: f32 = 50.0;
+
+/// Builds the row at an index
+type RowFn<'a> = Box<dyn FnMut(&mut UiContext, usize) -> Node + 'a>;
+
+/// A scrolling list that only builds the rows it shows
+///
+/// Rows are built by the item-builder closure from their index, so lists of
+/// any length cost roughly the same per frame. Each row is identified by a
+/// key (the index by default); give rows stable keys with [`ListView::key`] so
+/// measured heights and the scroll position follow the items as they move.
+///
+/// Rows are built and dropped as they scroll in and out of view, which shifts
+/// the generated IDs of everything built after them. Give interactive widgets
+/// in rows explicit IDs derived from the key.
+///
+/// # Example
+///
+/// ```ignore
+/// ListView::new(messages.len(), |ctx, index| message_row(ctx, &messages[index]))
+///     .key(|index| messages[index].id)
+///     .estimated_row_height(48.0)
+///     .node(&mut ctx)
+/// ```
+pub struct ListView<'a> {
+    count: usize,
+    builder: RowFn<'a>,
+    key: Option<Box<dyn Fn(usize) -> u64 + 'a>>,
+    estimated_row_height: f32,
+    overscan: usize,
+    width: Size,
+    height: Size,
+    id: Option<String>,
+    style: Option<ScrollbarStyle>,
+}
+
+impl<'a> ListView<'a> {
+    /// Create a list of `count` rows, built on demand by `builder`
+    pub fn new(count: usize, builder: impl FnMut(&mut UiContext, usize) -> Node + 'a) -> Self {
+        Self {
+            count,
+            builder: Box::new(builder),
+            key: None,
+            estimated_row_height: 32.0,
+            overscan: 2,
+            width: Size::Fill,
+            height: Size::Fill,
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Set the key identifying the item at an index (default: the index)
+    ///
+    /// Keys must be unique within the list.
+    pub fn key(mut self, key: impl Fn(usize) -> u64 + 'a) -> Self {
+        self.key = Some(Box::new(key));
+        self
+    }
+
+    /// Set the height assumed for rows that have not been measured yet
+    /// (default: 32)
+    ///
+    /// Once some rows are measured, their average is used instead.
+    pub fn estimated_row_height(mut self, height: f32) -> Self {
+        self.estimated_row_height = height;
+        self
+    }
+
+    /// Set how many rows to build beyond each edge of the viewport (default: 2)
+    pub fn overscan(mut self, rows: usize) -> Self {
+        self.overscan = rows;
+        self
+    }
+
+    /// Set the width of the list (default: `Size::Fill`)
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height of the list (default: `Size::Fill`)
+    pub fn height(mut self, height: Size) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    ///
+    /// The ID names the scroll container, so it can be passed to
+    /// `UiContext::scroll_to` and `UiContext::scroll_metrics`.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the scrollbar
+    pub fn with_style(mut self, style: ScrollbarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for ListView<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("list_view"));
+        let row_id = |key: u64| format!("{}_row_{}", id, key);
+        let mut state = std::mem::take(ctx.memory().list_view(&id));
+
+        // Take in the heights of the rows laid out last frame
+        for key in &state.built {
+            if let Some((_, height)) = ctx.measured_size(&row_id(*key)) {
+                state.heights.insert(*key, height);
+            }
+        }
+
+        // Everything below is in layout pixels, like measurements and scroll
+        // offsets. Measured rows account for zoom, the estimate only for scale.
+        let estimate = if state.heights.is_empty() {
+            self.estimated_row_height * ctx.scale_factor()
+        } else {
+            state.heights.values().sum::<f32>() / state.heights.len() as f32
+        };
+        let height_of = |key: &u64| state.heights.get(key).copied().unwrap_or(estimate);
+        let keys: Vec<u64> = (0..self.count)
+            .map(|index| self.key.as_ref().map_or(index as u64, |key| key(index)))
+            .collect();
+        let mut tops = Vec::with_capacity(self.count + 1);
+        let mut top = 0.0;
+        for key in &keys {
+            tops.push(top);
+            top += height_of(key);
+        }
+        tops.push(top);
+
+        let metrics = ctx.scroll_metrics(&id);
+        let mut offset = metrics.map_or(0.0, |metrics| metrics.offset.1);
+        let viewport = match metrics.map_or(ctx.viewport_size().1, |metrics| metrics.viewport.1) {
+            viewport if viewport > 0.0 => viewport,
+            _ => estimate * FIRST_FRAME_ROWS,
+        };
+
+        // Anchor on the topmost row that was visible last frame and still
+        // exists. Whatever happened above it moved it; move the scroll position
+        // by the same amount so the visible rows stay put. At the very top
+        // nothing is anchored, so rows inserted there show up.
+        let mut built_top = state.built_top;
+        let anchor = state.built.iter().find_map(|key| {
+            let top = built_top;
+            built_top += height_of(key);
+            if built_top <= state.offset {
+                return None;
+            }
+            let index = keys.iter().position(|k| k == key)?;
+            Some(tops[index] - top)
+        });
+        if let Some(shift) = anchor.filter(|shift| state.offset > 0.0 && *shift != 0.0) {
+            ctx.shift_scroll(&id, (0.0, shift));
+            offset = (offset + shift).max(0.0);
+        }
+
+        // Rows from the one under the top edge to the one under the bottom edge
+        let first_visible = tops[1..].partition_point(|bottom| *bottom <= offset);
+        let last_visible = tops[..self.count].partition_point(|top| *top < offset + viewport);
+        let first = first_visible.saturating_sub(self.overscan);
+        let end = (last_visible + self.overscan).min(self.count).max(first);
+
+        state.built.clear();
+        state.built_top = tops[first];
+        state.offset = offset;
+
+        let mut rows = Vec::with_capacity(end.saturating_sub(first) + 2);
+        rows.push(spacer(tops[first]));
+        for (index, &key) in keys.iter().enumerate().take(end).skip(first) {
+            state.built.push(key);
+            rows.push(
+                Node::new()
+                    .with_id(NodeId::new(row_id(key)))
+                    .with_measure_size(true)
+                    .with_width(Size::Fill)
+                    .with_child((self.builder)(ctx, index)),
+            );
+        }
+        rows.push(spacer(tops[self.count] - tops[end]));
+
+        *ctx.memory().list_view(&id) = state;
+
+        let list = Node::new()
+            .with_id(NodeId::new(&id))
+            .with_layout_direction(Layout::Vertical)
+            .with_children(rows);
+        let mut area = ScrollArea::new(list)
+            .width(self.width)
+            .height(self.height)
+            .horizontal(false);
+        if let Some(style) = self.style {
+            area = area.with_style(style);
+        }
+        area.node(ctx)
+    }
+}
+
+/// Stands in for the rows that are not built
+fn spacer(height: f32) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_height(Size::ppx(height.max(0.0)))
+}
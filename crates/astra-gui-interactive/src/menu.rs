@@ -0,0 +1,740 @@
+//! Menu bar component for interactive UI
+//!
+//! Provides a horizontal bar of dropdown menus with nested submenus, separators and
+//! shortcut hints. Menus open on click, follow the pointer across the bar while open,
+//! can be navigated with the arrow keys and close when clicking anywhere else.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, HorizontalAlign, Layout, MeasureTextRequest, MenuState,
+    NamedKey, Node, NodeId, Place, Size, Spacing, Stroke, Style, TextContent, Theme, Transition,
+    Translation, UiContext, VerticalAlign, ZIndex,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::InteractionEvent;
+
+/// Visual styling for menus and the menu bar
+#[derive(Debug, Clone, WithBuilders)]
+pub struct MenuStyle {
+    /// Background color of the menu bar
+    pub bar_color: Color,
+    /// Background color of a hovered menu title
+    pub title_hover_color: Color,
+    /// Background color of the title of the open menu
+    pub title_open_color: Color,
+    /// Background color of a dropdown
+    pub popup_color: Color,
+    /// Outline color of a dropdown
+    pub popup_stroke_color: Color,
+    /// Background color of the highlighted item
+    pub highlight_color: Color,
+    /// Color of menu titles and item labels
+    pub text_color: Color,
+    /// Color of shortcut hints and submenu arrows
+    pub shortcut_color: Color,
+    /// Color of disabled item labels
+    pub disabled_text_color: Color,
+    /// Color of separator lines
+    pub separator_color: Color,
+    /// Font size of titles and items
+    pub font_size: f32,
+    /// Height of an item row
+    pub item_height: f32,
+    /// Horizontal padding of titles and item rows
+    pub item_padding: f32,
+    /// Padding between a dropdown's edge and its items
+    pub popup_padding: f32,
+    /// Narrowest a dropdown gets
+    pub min_popup_width: f32,
+    /// Corner radius of dropdowns, titles and highlighted items
+    pub corner_radius: f32,
+}
+
+impl MenuStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            bar_color: theme.surface,
+            title_hover_color: theme.surface_hover,
+            title_open_color: theme.surface_active,
+            popup_color: theme.overlay,
+            popup_stroke_color: theme.border,
+            highlight_color: theme.surface_hover,
+            text_color: theme.text,
+            shortcut_color: theme.text_muted,
+            disabled_text_color: theme.text_disabled,
+            separator_color: theme.border,
+            font_size: 16.0,
+            item_height: 28.0,
+            item_padding: 10.0,
+            popup_padding: 4.0,
+            min_popup_width: 160.0,
+            corner_radius: 6.0,
+        }
+    }
+}
+
+impl Default for MenuStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+enum MenuItemKind {
+    Action,
+    Separator,
+    Submenu(Vec<MenuItem>),
+}
+
+/// An entry in a [`Menu`]: an action, a separator or a submenu
+///
+/// # Example
+///
+/// ```ignore
+/// MenuItem::new("Save").shortcut("Ctrl+S").on_select(|| save())
+/// ```
+pub struct MenuItem {
+    label: String,
+    shortcut: Option<String>,
+    disabled: bool,
+    action: Option<String>,
+    kind: MenuItemKind,
+    on_select: Option<Box<dyn FnMut()>>,
+}
+
+impl MenuItem {
+    /// Create an item that runs its `on_select` callback when chosen
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            shortcut: None,
+            disabled: false,
+            action: None,
+            kind: MenuItemKind::Action,
+            on_select: None,
+        }
+    }
+
+    /// Create a separator line between groups of items
+    pub fn separator() -> Self {
+        Self {
+            kind: MenuItemKind::Separator,
+            ..Self::new("")
+        }
+    }
+
+    /// Create an item that opens a nested menu of `items`
+    pub fn submenu(label: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        Self {
+            kind: MenuItemKind::Submenu(items),
+            ..Self::new(label)
+        }
+    }
+
+    /// Show a shortcut hint (e.g. "Ctrl+S") at the end of the row
+    ///
+    /// The hint is only displayed; bind the shortcut itself in the app.
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Set whether the item is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Set the identifier reported when the item is chosen in a context menu
+    /// (defaults to the label)
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Set a callback to be called when the item is chosen
+    pub fn on_select(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Whether the item can be highlighted and chosen
+    fn is_selectable(&self) -> bool {
+        !self.disabled && !matches!(self.kind, MenuItemKind::Separator)
+    }
+}
+
+/// A titled dropdown in a [`MenuBar`]
+pub struct Menu {
+    title: String,
+    pub(crate) items: Vec<MenuItem>,
+}
+
+impl Menu {
+    /// Create an empty menu with the given title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Append an item
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Append a separator
+    pub fn separator(self) -> Self {
+        self.item(MenuItem::separator())
+    }
+
+    /// Append several items
+    pub fn items(mut self, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+}
+
+/// Items of the menu at `path` (menu index in the bar, then submenu item indices)
+fn items_at<'a>(menus: &'a mut [Menu], path: &[usize]) -> Option<&'a mut Vec<MenuItem>> {
+    let (first, rest) = path.split_first()?;
+    let mut items = &mut menus.get_mut(*first)?.items;
+    for &index in rest {
+        match &mut items.get_mut(index)?.kind {
+            MenuItemKind::Submenu(sub_items) => items = sub_items,
+            _ => return None,
+        }
+    }
+    Some(items)
+}
+
+/// ID of the item at `path` in the menu bar with the given ID
+fn item_id(menu_id: &str, path: &[usize]) -> String {
+    let path: Vec<String> = path.iter().map(|index| index.to_string()).collect();
+    format!("{}_item_{}", menu_id, path.join("_"))
+}
+
+/// Index of the next selectable item after `from` in direction `step`, wrapping around
+fn step_highlight(items: &[MenuItem], from: Option<usize>, step: isize) -> Option<usize> {
+    let len = items.len() as isize;
+    let mut index = from.map_or(if step > 0 { -1 } else { len }, |i| i as isize);
+    for _ in 0..len {
+        index = (index + step).rem_euclid(len);
+        if items[index as usize].is_selectable() {
+            return Some(index as usize);
+        }
+    }
+    None
+}
+
+/// Open the menu `step` places after the open one, wrapping around, with its
+/// first item highlighted
+fn open_adjacent_menu(menus: &[Menu], state: &mut MenuState, step: isize) {
+    let count = menus.len() as isize;
+    let index = (state.open_path[0] as isize + step).rem_euclid(count) as usize;
+    state.open_path = vec![index];
+    state.highlighted = step_highlight(&menus[index].items, None, 1);
+}
+
+/// Move the highlight onto the item under the pointer when it changes,
+/// opening submenus and closing those below the hovered level
+pub(crate) fn follow_hover(ctx: &UiContext, id: &str, menus: &mut [Menu], state: &mut MenuState) {
+    let mut hovered = None;
+    for depth in 1..=state.open_path.len() {
+        let Some(items) = items_at(menus, &state.open_path[..depth]) else {
+            break;
+        };
+        for (index, item) in items.iter().enumerate() {
+            let mut path = state.open_path[..depth].to_vec();
+            path.push(index);
+            let item_id = item_id(id, &path);
+            if item.is_selectable() && ctx.is_hovered(&item_id) {
+                let is_submenu = matches!(item.kind, MenuItemKind::Submenu(_));
+                hovered = Some((item_id, path, is_submenu));
+            }
+        }
+    }
+    let hovered_id = hovered.as_ref().map(|(item_id, ..)| item_id.clone());
+    if hovered_id != state.hovered_item {
+        if let Some((_, mut path, is_submenu)) = hovered {
+            if is_submenu {
+                state.open_path = path;
+                state.highlighted = None;
+            } else {
+                state.highlighted = path.pop();
+                state.open_path = path;
+            }
+        }
+        state.hovered_item = hovered_id;
+    }
+}
+
+/// Path of the item of the menus with the given ID clicked last frame, if any
+pub(crate) fn clicked_item(ctx: &UiContext, id: &str) -> Option<Vec<usize>> {
+    let item_prefix = format!("{}_item_", id);
+    ctx.events().iter().rev().find_map(|event| {
+        let path = event.target.as_str().strip_prefix(&item_prefix)?;
+        matches!(event.event, InteractionEvent::Click { .. })
+            .then(|| path.split('_').filter_map(|i| i.parse().ok()).collect())
+    })
+}
+
+/// Apply arrow keys, Enter/Space and Escape to the open menus, returning the
+/// path of an item chosen with Enter/Space
+///
+/// With `switch_menus`, Left/Right on the outermost menu move to the adjacent
+/// menu, as in a menu bar.
+pub(crate) fn handle_keys(
+    ctx: &UiContext,
+    menus: &mut [Menu],
+    state: &mut MenuState,
+    switch_menus: bool,
+) -> Option<Vec<usize>> {
+    let input = ctx.input();
+    let pressed = |key: NamedKey| input.is_named_key_just_pressed(key);
+    let items = items_at(menus, &state.open_path)?;
+    let highlighted = state.highlighted.and_then(|index| items.get(index));
+    let highlighted_submenu = match highlighted.map(|item| &item.kind) {
+        Some(MenuItemKind::Submenu(sub_items)) => Some(step_highlight(sub_items, None, 1)),
+        _ => None,
+    };
+
+    if pressed(NamedKey::ArrowDown) {
+        state.highlighted = step_highlight(items, state.highlighted, 1);
+    } else if pressed(NamedKey::ArrowUp) {
+        state.highlighted = step_highlight(items, state.highlighted, -1);
+    } else if pressed(NamedKey::ArrowRight) {
+        match highlighted_submenu {
+            Some(first) => {
+                state.open_path.extend(state.highlighted);
+                state.highlighted = first;
+            }
+            None if switch_menus => open_adjacent_menu(menus, state, 1),
+            None => {}
+        }
+    } else if pressed(NamedKey::ArrowLeft) {
+        if state.open_path.len() > 1 {
+            state.highlighted = state.open_path.pop();
+        } else if switch_menus {
+            open_adjacent_menu(menus, state, -1);
+        }
+    } else if pressed(NamedKey::Enter) || pressed(NamedKey::Space) {
+        let index = state.highlighted?;
+        match highlighted_submenu {
+            Some(first) => {
+                state.open_path.push(index);
+                state.highlighted = first;
+            }
+            None => {
+                let mut path = state.open_path.clone();
+                path.push(index);
+                return Some(path);
+            }
+        }
+    } else if pressed(NamedKey::Escape) {
+        if state.open_path.len() > 1 {
+            state.highlighted = state.open_path.pop();
+        } else {
+            state.close();
+        }
+    }
+    None
+}
+
+/// Choose the item at `path`: submenus open, actions run their callback and
+/// close the menus, returning the item's action identifier
+pub(crate) fn choose(
+    menus: &mut [Menu],
+    state: &mut MenuState,
+    mut path: Vec<usize>,
+) -> Option<String> {
+    let index = path.pop()?;
+    let item = items_at(menus, &path)?.get_mut(index)?;
+    if item.disabled {
+        return None;
+    }
+    match &item.kind {
+        MenuItemKind::Submenu(sub_items) => {
+            state.highlighted = step_highlight(sub_items, None, 1);
+            path.push(index);
+            state.open_path = path;
+            None
+        }
+        MenuItemKind::Action => {
+            if let Some(ref mut on_select) = item.on_select {
+                on_select();
+            }
+            state.close();
+            Some(item.action.clone().unwrap_or_else(|| item.label.clone()))
+        }
+        MenuItemKind::Separator => None,
+    }
+}
+
+/// A horizontal bar of dropdown menus
+///
+/// # Example
+///
+/// ```ignore
+/// MenuBar::new()
+///     .menu(
+///         Menu::new("File")
+///             .item(MenuItem::new("Open").shortcut("Ctrl+O").on_select(|| open()))
+///             .item(MenuItem::submenu("Recent", recent_items))
+///             .separator()
+///             .item(MenuItem::new("Quit").on_select(|| quit())),
+///     )
+///     .node(&mut ctx)
+/// ```
+pub struct MenuBar {
+    menus: Vec<Menu>,
+    id: Option<String>,
+    style: Option<MenuStyle>,
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MenuBar {
+    /// Create an empty menu bar
+    pub fn new() -> Self {
+        Self {
+            menus: Vec::new(),
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Append a menu
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the bar and its menus
+    pub fn with_style(mut self, style: MenuStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+/// Builds the dropdowns of a menu bar or context menu from its resolved state
+pub(crate) struct MenuPainter<'a> {
+    pub(crate) id: &'a str,
+    pub(crate) style: &'a MenuStyle,
+    pub(crate) state: &'a MenuState,
+}
+
+impl MenuPainter<'_> {
+    pub(crate) fn text(&self, text: impl Into<String>, color: Color) -> TextContent {
+        TextContent::new(text)
+            .with_font_size(Size::lpx(self.style.font_size))
+            .with_color(color)
+            .with_h_align(HorizontalAlign::Left)
+            .with_v_align(VerticalAlign::Center)
+    }
+
+    /// Width of the dropdown listing `items`, fitting the widest row
+    pub(crate) fn popup_width(&self, ctx: &mut UiContext, items: &[MenuItem]) -> f32 {
+        let style = self.style;
+        let mut measure = |text: &str| {
+            let content = TextContent::new(text).with_font_size(Size::lpx(style.font_size));
+            ctx.measure_text(MeasureTextRequest::from_text_content(&content))
+                .width
+        };
+        let widest = items
+            .iter()
+            .filter(|item| !matches!(item.kind, MenuItemKind::Separator))
+            .map(|item| {
+                let mut width = measure(&item.label);
+                if let Some(shortcut) = &item.shortcut {
+                    width += style.item_padding * 2.0 + measure(shortcut);
+                }
+                if let MenuItemKind::Submenu(_) = item.kind {
+                    width += style.item_padding * 2.0 + measure("›");
+                }
+                width
+            })
+            .fold(0.0, f32::max);
+        (widest + (style.item_padding + style.popup_padding) * 2.0).max(style.min_popup_width)
+    }
+
+    /// Height of the dropdown listing `items`
+    pub(crate) fn popup_height(&self, items: &[MenuItem]) -> f32 {
+        let style = self.style;
+        let rows: f32 = items
+            .iter()
+            .map(|item| match item.kind {
+                MenuItemKind::Separator => 1.0 + style.popup_padding * 2.0,
+                _ => style.item_height,
+            })
+            .sum();
+        rows + style.popup_padding * 2.0
+    }
+
+    /// Dropdown listing `items`, the menu at `path`
+    pub(crate) fn popup(
+        &self,
+        ctx: &mut UiContext,
+        items: Vec<MenuItem>,
+        path: Vec<usize>,
+    ) -> Node {
+        let style = self.style;
+        let width = self.popup_width(ctx, &items);
+        let depth = path.len();
+        let innermost = depth == self.state.open_path.len();
+        let path_ids: Vec<String> = path.iter().map(|index| index.to_string()).collect();
+
+        let mut rows = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            if let MenuItemKind::Separator = item.kind {
+                rows.push(
+                    Node::new()
+                        .with_width(Size::Fill)
+                        .with_height(Size::lpx(1.0))
+                        .with_margin(Spacing::vertical(Size::lpx(style.popup_padding)))
+                        .with_style(Style {
+                            fill_color: Some(style.separator_color),
+                            ..Default::default()
+                        }),
+                );
+                continue;
+            }
+
+            let mut item_path = path.clone();
+            item_path.push(index);
+            let submenu_open = self.state.open_path.get(depth) == Some(&index);
+            let highlighted = submenu_open || (innermost && self.state.highlighted == Some(index));
+            let label_color = if item.disabled {
+                style.disabled_text_color
+            } else {
+                style.text_color
+            };
+
+            let mut row = vec![Node::new()
+                .with_width(Size::Fill)
+                .with_height(Size::Fill)
+                .with_content(Content::Text(self.text(item.label, label_color)))];
+            if let Some(shortcut) = item.shortcut {
+                row.push(
+                    Node::new()
+                        .with_height(Size::Fill)
+                        .with_content(Content::Text(self.text(shortcut, style.shortcut_color))),
+                );
+            }
+
+            let mut children = Vec::new();
+            if let MenuItemKind::Submenu(sub_items) = item.kind {
+                row.push(
+                    Node::new()
+                        .with_height(Size::Fill)
+                        .with_content(Content::Text(self.text("›", style.shortcut_color))),
+                );
+                if submenu_open && !item.disabled {
+                    // Pinned to the row's top-right corner, lined up with its items
+                    children.push(
+                        Node::new()
+                            .with_width(Size::lpx(0.0))
+                            .with_height(Size::lpx(0.0))
+                            .with_place(Place::Alignment {
+                                h_align: HorizontalAlign::Right,
+                                v_align: VerticalAlign::Top,
+                            })
+                            .with_z_index(ZIndex(ZIndex::OVERLAY.0 + depth as i32))
+                            .with_child(
+                                self.popup(ctx, sub_items, item_path.clone())
+                                    .with_translation(Translation::new(
+                                        Size::lpx(style.popup_padding),
+                                        Size::lpx(-style.popup_padding),
+                                    )),
+                            ),
+                    );
+                }
+            }
+            children.insert(
+                0,
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_padding(Spacing::horizontal(Size::lpx(style.item_padding)))
+                    .with_gap(Size::lpx(style.item_padding * 2.0))
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_children(row),
+            );
+
+            rows.push(
+                Node::new()
+                    .with_id(NodeId::new(item_id(self.id, &item_path)))
+                    .with_width(Size::Fill)
+                    .with_height(Size::lpx(style.item_height))
+                    .with_layout_direction(Layout::Stack)
+                    .with_disabled(item.disabled)
+                    .with_style(Style {
+                        fill_color: Some(if highlighted {
+                            style.highlight_color
+                        } else {
+                            style.highlight_color.with_alpha(0.0)
+                        }),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(
+                            style.corner_radius - style.popup_padding / 2.0,
+                        ))),
+                        ..Default::default()
+                    })
+                    .with_transition(Transition::quick())
+                    .with_children(children),
+            );
+        }
+
+        Node::new()
+            .with_id(NodeId::new(format!(
+                "{}_popup_{}",
+                self.id,
+                path_ids.join("_")
+            )))
+            .with_width(Size::lpx(width))
+            .with_padding(Spacing::all(Size::lpx(style.popup_padding)))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(style.popup_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.popup_stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                ..Default::default()
+            })
+            .with_children(rows)
+    }
+}
+
+impl Component for MenuBar {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("menu_bar"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| MenuStyle::from_theme(ctx.theme()));
+        let title_id = |index: usize| format!("{}_title_{}", id, index);
+        let mut state = ctx.memory().menu(&id).clone();
+        if state.is_open() && items_at(&mut self.menus, &state.open_path).is_none() {
+            state.close();
+        }
+
+        // Titles toggle their menu; while one is open, hovering another switches to it
+        for index in 0..self.menus.len() {
+            if ctx.was_clicked(&title_id(index)) {
+                if state.open_path.first() == Some(&index) {
+                    state.close();
+                } else {
+                    state.open_path = vec![index];
+                    state.highlighted = None;
+                }
+            } else if state.open_path.first().is_some_and(|&open| open != index)
+                && ctx.is_hovered(&title_id(index))
+            {
+                state.open_path = vec![index];
+                state.highlighted = None;
+            }
+        }
+
+        // The highlight follows the pointer; clicked items are chosen (or open
+        // their submenu) and keys act on the innermost menu
+        follow_hover(ctx, &id, &mut self.menus, &mut state);
+        let mut chosen = clicked_item(ctx, &id);
+        if state.is_open() && chosen.is_none() {
+            chosen = handle_keys(ctx, &mut self.menus, &mut state, true);
+        }
+        if let Some(path) = chosen {
+            choose(&mut self.menus, &mut state, path);
+        }
+
+        if ctx.was_pressed_outside(&id) {
+            state.close();
+        }
+        *ctx.memory().menu(&id) = state.clone();
+
+        let painter = MenuPainter {
+            id: &id,
+            style: &style,
+            state: &state,
+        };
+        let mut titles = Vec::with_capacity(self.menus.len());
+        for (index, menu) in self.menus.into_iter().enumerate() {
+            let open = state.open_path.first() == Some(&index);
+            let mut children =
+                vec![Node::new()
+                    .with_content(Content::Text(painter.text(menu.title, style.text_color)))];
+            if open {
+                // Zero-sized anchor at the title's bottom-left corner, so the
+                // dropdown overflows below the bar without affecting layout
+                children.push(
+                    Node::new()
+                        .with_width(Size::lpx(0.0))
+                        .with_height(Size::lpx(0.0))
+                        .with_place(Place::Alignment {
+                            h_align: HorizontalAlign::Left,
+                            v_align: VerticalAlign::Bottom,
+                        })
+                        .with_z_index(ZIndex::OVERLAY)
+                        .with_child(
+                            // Left-aligned with the title, just below the bar
+                            painter
+                                .popup(ctx, menu.items, vec![index])
+                                .with_translation(Translation::new(
+                                    Size::lpx(-style.item_padding),
+                                    Size::lpx(style.popup_padding * 2.0),
+                                )),
+                        ),
+                );
+            }
+
+            titles.push(
+                Node::new()
+                    .with_id(NodeId::new(title_id(index)))
+                    .with_padding(Spacing::symmetric(
+                        Size::lpx(style.item_padding),
+                        Size::lpx(style.popup_padding),
+                    ))
+                    .with_layout_direction(Layout::Stack)
+                    .with_selected(open)
+                    .with_style(Style {
+                        fill_color: Some(style.title_hover_color.with_alpha(0.0)),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                        ..Default::default()
+                    })
+                    .with_hover_style(Style {
+                        fill_color: Some(style.title_hover_color),
+                        ..Default::default()
+                    })
+                    .with_selected_style(Style {
+                        fill_color: Some(style.title_open_color),
+                        ..Default::default()
+                    })
+                    .with_transition(Transition::quick())
+                    .with_children(children),
+            );
+        }
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(style.popup_padding)))
+            .with_layout_direction(Layout::Horizontal)
+            .with_v_align(VerticalAlign::Center)
+            .with_style(Style {
+                fill_color: Some(style.bar_color),
+                ..Default::default()
+            })
+            .with_children(titles)
+    }
+}
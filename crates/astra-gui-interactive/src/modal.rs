@@ -0,0 +1,368 @@
+//! Modal dialog component for interactive UI
+//!
+//! Shows a centered panel over a dimmed backdrop that blocks interaction with
+//! everything behind it. Focus is trapped inside the dialog while it is open:
+//! Tab cycles between its buttons, Escape cancels, and the previously focused
+//! widget is refocused when it closes.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, HorizontalAlign, Layout, NamedKey, Node, NodeId, Place,
+    Shape, Size, Spacing, Stroke, Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
+    ZIndex,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a modal dialog
+#[derive(Debug, Clone, WithBuilders)]
+pub struct ModalStyle {
+    /// Color of the backdrop dimming the content behind the dialog
+    pub backdrop_color: Color,
+    /// Background color of the dialog panel
+    pub panel_color: Color,
+    /// Outline color of the dialog panel
+    pub panel_stroke_color: Color,
+    /// Color of the title
+    pub title_color: Color,
+    /// Background color of the cancel button
+    pub button_color: Color,
+    /// Background color of a hovered cancel button
+    pub button_hover_color: Color,
+    /// Label color of the cancel button
+    pub button_text_color: Color,
+    /// Background color of the confirm button
+    pub confirm_color: Color,
+    /// Label color of the confirm button
+    pub confirm_text_color: Color,
+    /// Outline color of the focused button
+    pub focus_stroke_color: Color,
+    /// Font size of the title
+    pub title_font_size: f32,
+    /// Font size of the button labels
+    pub button_font_size: f32,
+    /// Width of the dialog panel
+    pub width: f32,
+    /// Internal padding of the dialog panel
+    pub padding: f32,
+    /// Space between the title, content and buttons
+    pub gap: f32,
+    /// Corner radius of the panel and buttons
+    pub corner_radius: f32,
+}
+
+impl ModalStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            backdrop_color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+            panel_color: theme.overlay,
+            panel_stroke_color: theme.border,
+            title_color: theme.text,
+            button_color: theme.surface,
+            button_hover_color: theme.surface_hover,
+            button_text_color: theme.text,
+            confirm_color: theme.primary,
+            confirm_text_color: theme.on_primary,
+            focus_stroke_color: theme.secondary,
+            title_font_size: 22.0,
+            button_font_size: 16.0,
+            width: 420.0,
+            padding: 20.0,
+            gap: 16.0,
+            corner_radius: 12.0,
+        }
+    }
+}
+
+impl Default for ModalStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A dialog over a backdrop that blocks the rest of the UI while open
+///
+/// The modal is controlled: pass whether it is open, and close it from the
+/// `on_confirm`/`on_cancel` callbacks. Build it every frame, also while closed,
+/// so it can hand focus back when it closes. Add it as the last child of a
+/// node with `Layout::Stack` (usually the root) so the backdrop covers the
+/// window.
+///
+/// # Example
+///
+/// ```ignore
+/// Modal::new(state.confirm_delete)
+///     .title("Delete file?")
+///     .content(description_node)
+///     .confirm_label("Delete")
+///     .on_confirm(|| delete_file())
+///     .on_cancel(|| state.confirm_delete = false)
+///     .node(&mut ctx)
+/// ```
+pub struct Modal {
+    open: bool,
+    title: Option<String>,
+    content: Option<Node>,
+    confirm_label: String,
+    cancel_label: String,
+    dismiss_on_backdrop: bool,
+    id: Option<String>,
+    style: Option<ModalStyle>,
+    on_confirm: Option<Box<dyn FnMut()>>,
+    on_cancel: Option<Box<dyn FnMut()>>,
+}
+
+impl Modal {
+    /// Create a modal that is shown while `open` is true
+    pub fn new(open: bool) -> Self {
+        Self {
+            open,
+            title: None,
+            content: None,
+            confirm_label: "OK".to_string(),
+            cancel_label: "Cancel".to_string(),
+            dismiss_on_backdrop: true,
+            id: None,
+            style: None,
+            on_confirm: None,
+            on_cancel: None,
+        }
+    }
+
+    /// Set the title shown at the top of the dialog
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the content shown between the title and the buttons
+    pub fn content(mut self, content: Node) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Set the label of the confirm button (default "OK")
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = label.into();
+        self
+    }
+
+    /// Set the label of the cancel button (default "Cancel")
+    pub fn cancel_label(mut self, label: impl Into<String>) -> Self {
+        self.cancel_label = label.into();
+        self
+    }
+
+    /// Set whether clicking the backdrop cancels the dialog (default true)
+    pub fn dismiss_on_backdrop(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_backdrop = dismiss;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the dialog
+    pub fn with_style(mut self, style: ModalStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the confirm button is chosen
+    pub fn on_confirm(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_confirm = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback to be called when the dialog is cancelled with the
+    /// cancel button, Escape or a click on the backdrop
+    pub fn on_cancel(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_cancel = Some(Box::new(f));
+        self
+    }
+}
+
+/// A dialog button with the given colors
+fn dialog_button(
+    id: &str,
+    label: String,
+    fill: Color,
+    hover_fill: Color,
+    text_color: Color,
+    style: &ModalStyle,
+) -> Node {
+    Node::new()
+        .with_id(NodeId::new(id))
+        .with_padding(Spacing::symmetric(
+            Size::lpx(style.padding * 0.8),
+            Size::lpx(style.padding * 0.4),
+        ))
+        .with_shape(Shape::rect())
+        .with_content(Content::Text(
+            TextContent::new(label)
+                .with_font_size(Size::lpx(style.button_font_size))
+                .with_color(text_color)
+                .with_h_align(HorizontalAlign::Center)
+                .with_v_align(VerticalAlign::Center),
+        ))
+        .with_style(Style {
+            fill_color: Some(fill),
+            stroke: Some(Stroke::new(
+                Size::lpx(2.0),
+                style.focus_stroke_color.with_alpha(0.0),
+            )),
+            corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius / 2.0))),
+            ..Default::default()
+        })
+        .with_hover_style(Style {
+            fill_color: Some(hover_fill),
+            ..Default::default()
+        })
+        .with_focused_style(Style {
+            stroke: Some(Stroke::new(Size::lpx(2.0), style.focus_stroke_color)),
+            ..Default::default()
+        })
+        .with_transition(Transition::quick())
+}
+
+impl Component for Modal {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self.id.take().unwrap_or_else(|| ctx.generate_id("modal"));
+        let confirm_id = format!("{}_confirm", id);
+        let cancel_id = format!("{}_cancel", id);
+        let mut state = ctx.memory().modal(&id).clone();
+
+        if !self.open {
+            // Hand focus back if it is still in the closed dialog
+            if state.was_open && (ctx.focused_widget().is_none() || ctx.is_focus_within(&id)) {
+                ctx.set_focus(state.restore_focus.as_ref().map(|id| id.as_str()));
+            }
+            *ctx.memory().modal(&id) = Default::default();
+            return Node::new();
+        }
+
+        // Focus moves into the dialog when it opens and is pulled back if it
+        // leaves; Tab cycles between the buttons
+        if !state.was_open {
+            state.restore_focus = ctx.focused_widget().cloned();
+            ctx.set_focus(Some(&confirm_id));
+        } else if !ctx.is_focus_within(&id) {
+            ctx.set_focus(Some(&confirm_id));
+        }
+        let input = ctx.input();
+        let tab_pressed = input.is_named_key_just_pressed(NamedKey::Tab);
+        let escape_pressed = input.is_named_key_just_pressed(NamedKey::Escape);
+        let activate_pressed = input.is_named_key_just_pressed(NamedKey::Enter)
+            || input.is_named_key_just_pressed(NamedKey::Space);
+        if tab_pressed {
+            // From elsewhere in the dialog, Tab enters at the first button and
+            // Shift+Tab at the last
+            let next = if ctx.is_focused(&cancel_id) {
+                &confirm_id
+            } else if ctx.is_focused(&confirm_id) || !ctx.shift_held() {
+                &cancel_id
+            } else {
+                &confirm_id
+            };
+            ctx.set_focus(Some(next));
+        }
+
+        let confirmed =
+            ctx.was_clicked(&confirm_id) || (activate_pressed && ctx.is_focused(&confirm_id));
+        let cancelled = ctx.was_clicked(&cancel_id)
+            || (activate_pressed && ctx.is_focused(&cancel_id))
+            || escape_pressed
+            || (self.dismiss_on_backdrop && ctx.was_clicked(&id));
+        if confirmed {
+            if let Some(ref mut on_confirm) = self.on_confirm {
+                on_confirm();
+            }
+        } else if cancelled {
+            if let Some(ref mut on_cancel) = self.on_cancel {
+                on_cancel();
+            }
+        }
+
+        state.was_open = true;
+        *ctx.memory().modal(&id) = state;
+
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ModalStyle::from_theme(ctx.theme()));
+        let mut children = Vec::new();
+        if let Some(title) = self.title {
+            children.push(
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_content(Content::Text(
+                        TextContent::new(title)
+                            .with_font_size(Size::lpx(style.title_font_size))
+                            .with_color(style.title_color)
+                            .with_h_align(HorizontalAlign::Left)
+                            .with_v_align(VerticalAlign::Center),
+                    )),
+            );
+        }
+        children.extend(self.content);
+        children.push(
+            Node::new()
+                .with_width(Size::Fill)
+                .with_layout_direction(Layout::Horizontal)
+                .with_gap(Size::lpx(style.gap / 2.0))
+                .with_children(vec![
+                    Node::new().with_width(Size::Fill),
+                    dialog_button(
+                        &cancel_id,
+                        self.cancel_label,
+                        style.button_color,
+                        style.button_hover_color,
+                        style.button_text_color,
+                        &style,
+                    ),
+                    dialog_button(
+                        &confirm_id,
+                        self.confirm_label,
+                        style.confirm_color,
+                        style.confirm_color.with_alpha(0.85),
+                        style.confirm_text_color,
+                        &style,
+                    ),
+                ]),
+        );
+
+        // The panel has an ID so clicks on it don't count as backdrop clicks
+        let panel = Node::new()
+            .with_id(NodeId::new(format!("{}_panel", id)))
+            .with_width(Size::lpx(style.width))
+            .with_place(Place::Alignment {
+                h_align: HorizontalAlign::Center,
+                v_align: VerticalAlign::Center,
+            })
+            .with_padding(Spacing::all(Size::lpx(style.padding)))
+            .with_gap(Size::lpx(style.gap))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(style.panel_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.panel_stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                ..Default::default()
+            })
+            .with_children(children);
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Stack)
+            .with_z_index(ZIndex::OVERLAY)
+            .with_input_barrier(true)
+            .with_style(Style {
+                fill_color: Some(style.backdrop_color),
+                ..Default::default()
+            })
+            .with_child(panel)
+    }
+}
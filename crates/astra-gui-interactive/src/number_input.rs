@@ -0,0 +1,495 @@
+//! Numeric stepper component for interactive UI
+//!
+//! A text field for a number with decrement and increment buttons on either
+//! side. The buttons and the Up/Down arrow keys step the value, Shift steps
+//! coarsely and Alt finely; typed values are parsed and clamped when Enter is
+//! pressed or the field loses focus.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Overflow, Point, Selection, Size, Spacing, Stroke, Style, TextContent, TextEditStyle,
+    TextEditor, Theme, Transition, UiContext, VerticalAlign, Wrap,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::{InteractionEvent, Key, MouseButton, NamedKey};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::drag_value::{format_value, parse_value};
+
+/// Step multiplier while Shift is held
+const COARSE_FACTOR: f32 = 10.0;
+
+/// Step multiplier while Alt is held
+const FINE_FACTOR: f32 = 0.1;
+
+/// Decimal places shown while editing, so no precision is lost on focus
+const EDIT_PRECISION: usize = 6;
+
+/// Visual styling for a number input
+#[derive(Debug, Clone, WithBuilders)]
+pub struct NumberInputStyle {
+    /// Background color when idle
+    pub idle_color: Color,
+    /// Background color when focused
+    pub focused_color: Color,
+    /// Background color when disabled
+    pub disabled_color: Color,
+
+    /// Stroke color when idle
+    pub idle_stroke_color: Color,
+    /// Stroke color when focused
+    pub focused_stroke_color: Color,
+    /// Stroke color when disabled
+    pub disabled_stroke_color: Color,
+    /// Stroke width
+    pub stroke_width: f32,
+
+    /// Text color
+    pub text_color: Color,
+    /// Text color when disabled
+    pub disabled_text_color: Color,
+    /// Caret color
+    pub caret_color: Color,
+    /// Selection highlight color
+    pub selection_color: Color,
+    /// Time between caret blinks
+    pub blink_interval: Duration,
+
+    /// Step button background color
+    pub button_color: Color,
+    /// Step button background color when hovered
+    pub button_hover_color: Color,
+    /// Step button background color when pressed
+    pub button_active_color: Color,
+    /// Step button label color
+    pub button_text_color: Color,
+
+    /// Internal padding of the text field
+    pub padding: Spacing,
+    /// Corner radius for rounded corners
+    pub border_radius: f32,
+    /// Font size
+    pub font_size: f32,
+    /// Width of the whole widget, buttons included
+    pub width: f32,
+    /// Width of each step button
+    pub button_width: f32,
+    /// Number of decimal places to show
+    pub precision: usize,
+}
+
+impl NumberInputStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            // Fill Colors
+            idle_color: theme.field,
+            focused_color: theme.field_hover,
+            disabled_color: theme.field.with_alpha(0.8),
+            // Stroke Colors
+            idle_stroke_color: theme.primary,
+            focused_stroke_color: theme.primary,
+            disabled_stroke_color: theme.field_active,
+            stroke_width: 2.0,
+            // Text Colors
+            text_color: theme.text,
+            disabled_text_color: theme.text_muted,
+            // Editing
+            caret_color: theme.text,
+            selection_color: theme.primary.with_alpha(0.3),
+            blink_interval: Duration::from_millis(530),
+            // Buttons
+            button_color: theme.field_hover,
+            button_hover_color: theme.field_active,
+            button_active_color: theme.primary.with_alpha(0.5),
+            button_text_color: theme.text,
+            // Other
+            padding: Spacing::symmetric(Size::lpx(8.0), Size::lpx(8.0)),
+            border_radius: 8.0,
+            font_size: 20.0,
+            width: 160.0,
+            button_width: 36.0,
+            precision: 2,
+        }
+    }
+}
+
+impl Default for NumberInputStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A numeric stepper (spin box)
+///
+/// Unlike [`DragValue`](crate::DragValue), which changes its value by
+/// dragging, the value is typed or stepped by a fixed amount: the step size,
+/// ten times it with Shift or a tenth of it with Alt. Stepped and typed values
+/// are clamped to the range. Escape discards a typed value.
+///
+/// # Example
+///
+/// ```ignore
+/// NumberInput::new(&mut quantity)
+///     .range(0.0..=99.0)
+///     .step(1.0)
+///     .on_change(|value| println!("Quantity: {}", value))
+///     .node(&mut ctx)
+/// ```
+pub struct NumberInput<'a> {
+    value: &'a mut f32,
+    range: Option<RangeInclusive<f32>>,
+    step: f32,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<NumberInputStyle>,
+    on_change: Option<Box<dyn FnMut(f32) + 'a>>,
+}
+
+impl<'a> NumberInput<'a> {
+    /// Create a new number input bound to a mutable float reference
+    pub fn new(value: &'a mut f32) -> Self {
+        Self {
+            value,
+            range: None,
+            step: 1.0,
+            disabled: false,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Set the valid range of values
+    pub fn range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Set the amount a button press or arrow key changes the value
+    /// (default: 1)
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set whether the number input is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the number input
+    pub fn with_style(mut self, style: NumberInputStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the value changes
+    pub fn on_change(mut self, f: impl FnMut(f32) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    fn clamp(&self, value: f32) -> f32 {
+        match &self.range {
+            Some(range) => value.clamp(*range.start(), *range.end()),
+            None => value,
+        }
+    }
+}
+
+impl Component for NumberInput<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("number_input"));
+        let field_id = format!("{}_field", id);
+        let decrement_id = format!("{}_decrement", id);
+        let increment_id = format!("{}_increment", id);
+        let mut style = self
+            .style
+            .take()
+            .unwrap_or_else(|| NumberInputStyle::from_theme(ctx.theme()));
+        // Caret positions and the field height follow the font size, so the text
+        // scale is applied here rather than by layout (the node opts out below)
+        style.font_size *= ctx.text_scale();
+        let edit_style = TextEditStyle {
+            font_size: style.font_size,
+            caret_color: style.caret_color,
+            selection_color: style.selection_color,
+            ..TextEditStyle::from_theme(ctx.theme())
+        };
+
+        let mut state = std::mem::take(ctx.memory().number_input(&id));
+        let input = ctx.input().clone();
+        let mut value = *self.value;
+
+        // Button clicks step the value; clicks on the field focus it
+        let mut steps = 0;
+        let mut field_click = None;
+        let mut focused_by_press = false;
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let target = event.target.as_str();
+            match event.event {
+                // Presses focus the widget before their click is reported
+                InteractionEvent::Focus if target == id || target == field_id => {
+                    focused_by_press = true;
+                }
+                InteractionEvent::Click {
+                    button: MouseButton::Left,
+                    ..
+                } if !self.disabled => {
+                    if target == decrement_id {
+                        steps -= 1;
+                    } else if target == increment_id {
+                        steps += 1;
+                    } else if target == field_id {
+                        field_click = Some(event.local_position.x / event.zoom);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if field_click.is_some() {
+            ctx.set_focus(Some(&id));
+        }
+        let escape_pressed = input
+            .keys_just_pressed
+            .iter()
+            .any(|key| matches!(key, Key::Named(NamedKey::Escape)));
+        if ctx.is_focused(&id)
+            && field_click.is_none()
+            && (ctx.was_pressed_outside(&id) || escape_pressed)
+        {
+            ctx.set_focus(None);
+        }
+        let focused = ctx.is_focused(&id) && !self.disabled;
+
+        // Only characters that can be part of a number are typed; the arrows
+        // step instead of moving the caret
+        let mut typed = input.clone();
+        typed
+            .characters_typed
+            .retain(|ch| ch.is_ascii_digit() || matches!(ch, '.' | '-' | '+' | 'e' | 'E'));
+        typed.keys_just_pressed.retain(|key| {
+            !matches!(
+                key,
+                Key::Named(NamedKey::ArrowUp) | Key::Named(NamedKey::ArrowDown)
+            )
+        });
+
+        // Pressing the field or typing starts editing, with the whole value
+        // selected so typing replaces it. Pressing a step button focuses the
+        // widget for the arrow keys without editing.
+        let field_pressed = focused_by_press && ctx.is_hovered(&field_id);
+        let start_editing =
+            field_click.is_some() || field_pressed || !typed.characters_typed.is_empty();
+        if focused && !state.editing && start_editing {
+            state.editor = TextEditor::new()
+                .with_multiline(false)
+                .with_text(&format_value(value, EDIT_PRECISION));
+            state.editor.select_all();
+            state.editing = true;
+            // Presses focus the widget before their click is reported
+            state.focus_click = field_pressed && field_click.is_none();
+        } else if state.focus_click && field_click.is_some() {
+            state.focus_click = false;
+        } else if let Some(x) = field_click.filter(|_| focused) {
+            // Later clicks place the caret in the centered text
+            if let Some(measurer) = ctx.measurer() {
+                let text = state.editor.text().into_owned();
+                let line_width = edit_style.caret_rect(measurer, &text, 0).line_width;
+                let field_width =
+                    style.width - 2.0 * style.button_width - style.padding.get_horizontal();
+                let point = Point {
+                    x: x - style.padding.get_left() - (field_width - line_width) * 0.5,
+                    y: 0.0,
+                };
+                let index = state.editor.caret_index_at(measurer, &edit_style, point);
+                state.editor.set_selection(Selection::collapsed(index));
+            }
+        }
+
+        let mut submitted = false;
+        if focused {
+            for key in &input.keys_just_pressed {
+                match key {
+                    Key::Named(NamedKey::ArrowUp) => steps += 1,
+                    Key::Named(NamedKey::ArrowDown) => steps -= 1,
+                    _ => {}
+                }
+            }
+            if state.editing {
+                let response = state.editor.handle_input(&typed);
+                if response.changed {
+                    ctx.reset_cursor_blink(&id);
+                }
+                submitted = response.submitted;
+            }
+        }
+
+        // Typed values apply before stepping from them, on Enter and on blur;
+        // Escape discards them. Unparsable text falls back to the last value.
+        let commit = state.editing && (steps != 0 || submitted || !focused);
+        if commit && !escape_pressed {
+            if let Some(typed) = parse_value(&state.editor.text()) {
+                value = self.clamp(typed);
+            }
+        }
+        if steps != 0 {
+            let factor = if input.shift_held {
+                COARSE_FACTOR
+            } else if input.alt_held {
+                FINE_FACTOR
+            } else {
+                1.0
+            };
+            value = self.clamp(value + steps as f32 * self.step * factor);
+        }
+        if submitted || !focused {
+            state.editing = false;
+        } else if commit {
+            state.editor.set_text(&format_value(value, EDIT_PRECISION));
+            ctx.reset_cursor_blink(&id);
+        }
+        if submitted {
+            ctx.set_focus(None);
+        }
+
+        if value != *self.value {
+            *self.value = value;
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(value);
+            }
+        }
+
+        let caret_visible =
+            state.editing && ctx.update_cursor_blink(&id, style.blink_interval.as_millis() as u64);
+        let (text, decorations) = if state.editing {
+            let text = state.editor.text().into_owned();
+            let decorations = ctx.measurer().map(|measurer| {
+                state
+                    .editor
+                    .decorations(measurer, &edit_style, caret_visible)
+            });
+            (text, decorations)
+        } else {
+            (format_value(value, style.precision), None)
+        };
+        let focused = focused && !submitted;
+        *ctx.memory().number_input(&id) = state;
+
+        let (at_min, at_max) = match &self.range {
+            Some(range) => (value <= *range.start(), value >= *range.end()),
+            None => (false, false),
+        };
+        let button = |id: &str, label: &str, enabled: bool| {
+            Node::new()
+                .with_id(NodeId::new(id))
+                .with_width(Size::lpx(style.button_width))
+                .with_height(Size::Fill)
+                .with_cursor(CursorIcon::Pointer)
+                .with_content(Content::Text(
+                    TextContent::new(label)
+                        .with_font_size(Size::lpx(style.font_size))
+                        .with_color(style.button_text_color)
+                        .with_h_align(HorizontalAlign::Center)
+                        .with_v_align(VerticalAlign::Center),
+                ))
+                .with_style(Style {
+                    fill_color: Some(style.button_color),
+                    text_color: Some(style.button_text_color),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                    ..Default::default()
+                })
+                .with_hover_style(Style {
+                    fill_color: Some(style.button_hover_color),
+                    ..Default::default()
+                })
+                .with_active_style(Style {
+                    fill_color: Some(style.button_active_color),
+                    ..Default::default()
+                })
+                .with_disabled_style(Style {
+                    text_color: Some(style.disabled_text_color),
+                    ..Default::default()
+                })
+                .with_disabled(self.disabled || !enabled)
+                .with_transition(Transition::quick())
+        };
+
+        let mut field = Node::new()
+            .with_id(NodeId::new(&field_id))
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(style.padding)
+            .with_cursor(CursorIcon::Text)
+            .with_content(Content::Text(
+                TextContent::new(text)
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(style.text_color)
+                    .with_h_align(HorizontalAlign::Center)
+                    .with_v_align(VerticalAlign::Center)
+                    .with_wrap(Wrap::None),
+            ))
+            .with_style(Style {
+                text_color: Some(style.text_color),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                text_color: Some(style.disabled_text_color),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick());
+        if let Some(decorations) = decorations {
+            field = field.with_text_edit(decorations);
+        }
+
+        let (fill_color, stroke_color) = if focused {
+            (style.focused_color, style.focused_stroke_color)
+        } else {
+            (style.idle_color, style.idle_stroke_color)
+        };
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(style.width))
+            .with_height(Size::lpx(style.font_size + style.padding.get_vertical()))
+            .with_text_scale(1.0)
+            .with_layout_direction(Layout::Horizontal)
+            .with_overflow(Overflow::Hidden)
+            .with_style(Style {
+                fill_color: Some(fill_color),
+                stroke: Some(Stroke::new(Size::lpx(style.stroke_width), stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                fill_color: Some(style.disabled_color),
+                stroke: Some(Stroke::new(
+                    Size::lpx(style.stroke_width),
+                    style.disabled_stroke_color,
+                )),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick())
+            .with_children(vec![
+                button(&decrement_id, "\u{2212}", !at_min),
+                field,
+                button(&increment_id, "+", !at_max),
+            ])
+    }
+}
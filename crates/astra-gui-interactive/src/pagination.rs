@@ -0,0 +1,397 @@
+//! Pagination component for interactive UI
+//!
+//! A row of page buttons between previous and next arrows, for paging through
+//! tables and long result lists. Long ranges keep the first and last pages and
+//! the pages around the current one, with ellipses for the pages in between.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Size, Style, TextContent, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a pagination control
+#[derive(Debug, Clone, WithBuilders)]
+pub struct PaginationStyle {
+    /// Background color of the page buttons when idle
+    pub button_color: Color,
+    /// Background color of the page buttons when hovered
+    pub hover_color: Color,
+    /// Background color of the page buttons when pressed
+    pub pressed_color: Color,
+    /// Background color of the current page
+    pub current_color: Color,
+    /// Label color of the page buttons
+    pub text_color: Color,
+    /// Label color of the current page
+    pub current_text_color: Color,
+    /// Label color of the arrows when there is no page to go to
+    pub disabled_text_color: Color,
+    /// Width and height of each button
+    pub button_size: f32,
+    /// Space between buttons
+    pub gap: f32,
+    /// Corner radius of the buttons
+    pub border_radius: f32,
+    /// Font size of the labels
+    pub font_size: f32,
+}
+
+impl PaginationStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            button_color: theme.surface,
+            hover_color: theme.surface_hover,
+            pressed_color: theme.surface_active,
+            current_color: theme.primary,
+            text_color: theme.text,
+            current_text_color: theme.on_primary,
+            disabled_text_color: theme.text_disabled,
+            button_size: 36.0,
+            gap: 4.0,
+            border_radius: 8.0,
+            font_size: 16.0,
+        }
+    }
+}
+
+impl Default for PaginationStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// An entry in the row of pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PageItem {
+    /// A button for the page with this (zero-based) index
+    Page(usize),
+    /// A gap standing in for two or more pages
+    Ellipsis,
+}
+
+/// The pages to show for `current` out of `count`
+///
+/// The row always has the same number of entries once pages are elided, so
+/// the buttons stay put while paging through the middle.
+fn page_items(current: usize, count: usize, siblings: usize, boundaries: usize) -> Vec<PageItem> {
+    // Boundary pages at both ends, the current page with its siblings, and
+    // an ellipsis on either side of those
+    let slots = 2 * boundaries + 2 * siblings + 3;
+    if count <= slots {
+        return (0..count).map(PageItem::Page).collect();
+    }
+
+    let start = (0..boundaries).map(PageItem::Page);
+    let end = (count - boundaries..count).map(PageItem::Page);
+    // Near an end, the window of pages grows into the slot of the ellipsis
+    // that is not needed there
+    let window = slots - boundaries - 1;
+    if current < boundaries + siblings + 2 {
+        (0..window)
+            .map(PageItem::Page)
+            .chain([PageItem::Ellipsis])
+            .chain(end)
+            .collect()
+    } else if current + siblings + 2 >= count - boundaries {
+        start
+            .chain([PageItem::Ellipsis])
+            .chain((count - window..count).map(PageItem::Page))
+            .collect()
+    } else {
+        start
+            .chain([PageItem::Ellipsis])
+            .chain((current - siblings..=current + siblings).map(PageItem::Page))
+            .chain([PageItem::Ellipsis])
+            .chain(end)
+            .collect()
+    }
+}
+
+/// Page navigation with previous/next arrows and numbered pages
+///
+/// Pages are zero-based; the buttons show them counting from 1. Clicking a
+/// page or an arrow updates the bound page and calls `on_change` with the
+/// new one. The current page is clamped to the page count.
+///
+/// # Example
+///
+/// ```ignore
+/// let page_count = rows.len().div_ceil(ROWS_PER_PAGE);
+/// Pagination::new(&mut page, page_count)
+///     .on_change(|page| println!("Showing page {}", page + 1))
+///     .node(&mut ctx)
+/// ```
+pub struct Pagination<'a> {
+    page: &'a mut usize,
+    count: usize,
+    siblings: usize,
+    boundaries: usize,
+    id: Option<String>,
+    style: Option<PaginationStyle>,
+    on_change: Option<Box<dyn FnMut(usize) + 'a>>,
+}
+
+impl<'a> Pagination<'a> {
+    /// Create a pagination control over `count` pages, bound to the current page
+    pub fn new(page: &'a mut usize, count: usize) -> Self {
+        Self {
+            page,
+            count,
+            siblings: 1,
+            boundaries: 1,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Set how many pages to show on either side of the current one
+    /// (default: 1)
+    pub fn siblings(mut self, siblings: usize) -> Self {
+        self.siblings = siblings;
+        self
+    }
+
+    /// Set how many pages to always show at the start and the end
+    /// (default: 1)
+    pub fn boundaries(mut self, boundaries: usize) -> Self {
+        self.boundaries = boundaries;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the pagination control
+    pub fn with_style(mut self, style: PaginationStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the page changes
+    pub fn on_change(mut self, f: impl FnMut(usize) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for Pagination<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("pagination"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| PaginationStyle::from_theme(ctx.theme()));
+        let prev_id = format!("{}_prev", id);
+        let next_id = format!("{}_next", id);
+        let page_id = |page: usize| format!("{}_page_{}", id, page);
+
+        let last = self.count.saturating_sub(1);
+        let mut page = (*self.page).min(last);
+        let items = page_items(page, self.count, self.siblings, self.boundaries);
+
+        // Clicks on last frame's buttons; the pages shown then are the ones
+        // shown now, unless the page changed from outside
+        if ctx.was_clicked(&prev_id) {
+            page = page.saturating_sub(1);
+        } else if ctx.was_clicked(&next_id) {
+            page = (page + 1).min(last);
+        } else if let Some(clicked) = items.iter().find_map(|item| match item {
+            PageItem::Page(page) if ctx.was_clicked(&page_id(*page)) => Some(*page),
+            _ => None,
+        }) {
+            page = clicked;
+        }
+
+        if page != *self.page {
+            *self.page = page;
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(page);
+            }
+        }
+
+        let label = |text: String, color: Color| {
+            Content::Text(
+                TextContent::new(text)
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(color)
+                    .with_h_align(HorizontalAlign::Center)
+                    .with_v_align(VerticalAlign::Center),
+            )
+        };
+        let button = |id: String, text: String, current: bool, enabled: bool| {
+            let (fill, text_color) = if current {
+                (style.current_color, style.current_text_color)
+            } else {
+                (style.button_color, style.text_color)
+            };
+            let mut node = Node::new()
+                .with_id(NodeId::new(id))
+                .with_width(Size::lpx(style.button_size))
+                .with_height(Size::lpx(style.button_size))
+                .with_content(label(text, text_color))
+                .with_style(Style {
+                    fill_color: Some(fill),
+                    text_color: Some(text_color),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                    ..Default::default()
+                })
+                .with_disabled_style(Style {
+                    text_color: Some(style.disabled_text_color),
+                    ..Default::default()
+                })
+                .with_disabled(!enabled)
+                .with_transition(Transition::quick());
+            // The current page is not a link
+            if !current {
+                node = node
+                    .with_cursor(CursorIcon::Pointer)
+                    .with_hover_style(Style {
+                        fill_color: Some(style.hover_color),
+                        ..Default::default()
+                    })
+                    .with_active_style(Style {
+                        fill_color: Some(style.pressed_color),
+                        ..Default::default()
+                    });
+            }
+            node
+        };
+
+        // Items are rebuilt for the page after any click, so the row matches it
+        let items = page_items(page, self.count, self.siblings, self.boundaries);
+        let mut children = Vec::with_capacity(items.len() + 2);
+        children.push(button(prev_id, "\u{2039}".to_string(), false, page > 0));
+        for item in items {
+            children.push(match item {
+                PageItem::Page(index) => {
+                    button(page_id(index), (index + 1).to_string(), index == page, true)
+                }
+                PageItem::Ellipsis => Node::new()
+                    .with_width(Size::lpx(style.button_size))
+                    .with_height(Size::lpx(style.button_size))
+                    .with_content(label("\u{2026}".to_string(), style.text_color)),
+            });
+        }
+        children.push(button(next_id, "\u{203A}".to_string(), false, page < last));
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_gap(Size::lpx(style.gap))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PageItem::{Ellipsis, Page};
+    use super::*;
+
+    #[test]
+    fn test_short_ranges_show_every_page() {
+        assert_eq!(page_items(0, 0, 1, 1), []);
+        assert_eq!(page_items(3, 7, 1, 1), (0..7).map(Page).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_window_follows_the_current_page() {
+        // Near the start the window takes the slot of the left ellipsis
+        assert_eq!(
+            page_items(1, 20, 1, 1),
+            [
+                Page(0),
+                Page(1),
+                Page(2),
+                Page(3),
+                Page(4),
+                Ellipsis,
+                Page(19)
+            ]
+        );
+        assert_eq!(
+            page_items(10, 20, 1, 1),
+            [
+                Page(0),
+                Ellipsis,
+                Page(9),
+                Page(10),
+                Page(11),
+                Ellipsis,
+                Page(19)
+            ]
+        );
+        assert_eq!(
+            page_items(18, 20, 1, 1),
+            [
+                Page(0),
+                Ellipsis,
+                Page(15),
+                Page(16),
+                Page(17),
+                Page(18),
+                Page(19)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zero_boundaries_drop_the_end_pages() {
+        assert_eq!(
+            page_items(0, 20, 1, 0),
+            [Page(0), Page(1), Page(2), Page(3), Ellipsis]
+        );
+        assert_eq!(
+            page_items(10, 20, 1, 0),
+            [Ellipsis, Page(9), Page(10), Page(11), Ellipsis]
+        );
+        assert_eq!(
+            page_items(19, 20, 1, 0),
+            [Ellipsis, Page(16), Page(17), Page(18), Page(19)]
+        );
+    }
+
+    #[test]
+    fn test_row_width_stays_constant_while_paging() {
+        for (siblings, boundaries) in [(0, 0), (1, 0), (1, 1), (2, 1), (1, 2)] {
+            let slots = 2 * boundaries + 2 * siblings + 3;
+            for count in slots + 1..slots + 8 {
+                for current in 0..count {
+                    let items = page_items(current, count, siblings, boundaries);
+                    assert_eq!(items.len(), slots, "{current} of {count}");
+                    assert!(items.contains(&Page(current)));
+
+                    // Pages ascend, and each ellipsis hides two or more of them
+                    let mut next = 0;
+                    let mut hidden = false;
+                    for item in items {
+                        let Page(page) = item else {
+                            hidden = true;
+                            continue;
+                        };
+                        if hidden {
+                            assert!(page >= next + 2);
+                        } else {
+                            assert_eq!(page, next);
+                        }
+                        next = page + 1;
+                        hidden = false;
+                    }
+                    if hidden {
+                        assert!(count >= next + 2);
+                    } else {
+                        assert_eq!(next, count);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Peek component for interactive UI
+//!
+//! Shows an anchored preview after the cursor rests on an anchor node for a
+//! short delay. The preview content is built lazily, only while the peek is
+//! open, which makes it suitable for link previews, thumbnail hovers and
+//! definition popovers.
+
+use astra_gui::{
+    Color, Component, CornerShape, HorizontalAlign, Layout, Node, NodeId, Place, Size, Spacing,
+    Stroke, Style, Theme, UiContext, VerticalAlign, ZIndex,
+};
+use astra_gui_macros::WithBuilders;
+use std::time::Duration;
+
+/// Visual styling for a peek preview
+#[derive(Debug, Clone, WithBuilders)]
+pub struct PeekStyle {
+    /// Background color of the preview container
+    pub background_color: Color,
+    /// Stroke color of the preview container
+    pub stroke_color: Color,
+    /// Stroke width of the preview container
+    pub stroke_width: f32,
+    /// Corner radius of the preview container
+    pub border_radius: f32,
+    /// Internal padding of the preview container
+    pub padding: Spacing,
+    /// Vertical distance between the anchor and the preview
+    pub offset: f32,
+}
+
+impl PeekStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            background_color: theme.overlay,
+            stroke_color: theme.border,
+            stroke_width: 1.0,
+            border_radius: 12.0,
+            padding: Spacing::all(Size::lpx(12.0)),
+            offset: 6.0,
+        }
+    }
+}
+
+impl Default for PeekStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// Lazily builds the preview content while a peek is open
+type PreviewFn = Box<dyn FnOnce(&mut UiContext) -> Node>;
+
+/// Wraps an anchor node and shows a preview below it after a hover delay
+///
+/// The preview closure is only called while the peek is open. The peek is
+/// dismissed automatically when the cursor leaves the anchor or a mouse
+/// button is pressed.
+///
+/// # Example
+///
+/// ```ignore
+/// Peek::new(link_node)
+///     .delay(Duration::from_millis(400))
+///     .preview(|ctx| definition_card(ctx))
+///     .node(&mut ctx)
+/// ```
+pub struct Peek {
+    anchor: Node,
+    delay: Duration,
+    style: Option<PeekStyle>,
+    preview: Option<PreviewFn>,
+    on_peek: Option<Box<dyn FnMut()>>,
+}
+
+impl Peek {
+    /// Create a new peek around the given anchor node
+    pub fn new(anchor: Node) -> Self {
+        Peek {
+            anchor,
+            delay: Duration::from_millis(500),
+            style: None,
+            preview: None,
+            on_peek: None,
+        }
+    }
+
+    /// Set how long the cursor must rest on the anchor before the peek opens
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set a custom style for the preview container
+    pub fn with_style(mut self, style: PeekStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set the closure that builds the preview content
+    ///
+    /// Called only on frames where the peek is open.
+    pub fn preview(mut self, f: impl FnOnce(&mut UiContext) -> Node + 'static) -> Self {
+        self.preview = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback to be called once when the peek is requested
+    ///
+    /// Use this to start loading expensive preview data.
+    pub fn on_peek(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_peek = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for Peek {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        // Respect an ID already set on the anchor, otherwise generate one
+        let anchor_id = match self.anchor.id() {
+            Some(id) => id.as_str().to_string(),
+            None => ctx.generate_id("peek"),
+        };
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| PeekStyle::from_theme(ctx.theme()));
+
+        if ctx.was_peek_requested(&anchor_id) {
+            if let Some(ref mut on_peek) = self.on_peek {
+                on_peek();
+            }
+        }
+
+        let anchor = self
+            .anchor
+            .with_id(NodeId::new(&anchor_id))
+            .with_peek_delay(self.delay);
+
+        let mut children = vec![anchor];
+
+        if ctx.is_peek_open(&anchor_id) {
+            if let Some(preview) = self.preview {
+                let content = preview(ctx);
+
+                // Zero-sized wrapper pinned to the anchor's bottom-left corner so
+                // the preview overflows below the anchor without affecting layout
+                children.push(
+                    Node::new()
+                        .with_width(Size::lpx(0.0))
+                        .with_height(Size::lpx(0.0))
+                        .with_place(Place::Alignment {
+                            h_align: HorizontalAlign::Left,
+                            v_align: VerticalAlign::Bottom,
+                        })
+                        .with_z_index(ZIndex::TOOLTIP)
+                        .with_child(
+                            Node::new()
+                                .with_margin(Spacing::top(Size::lpx(style.offset)))
+                                .with_padding(style.padding)
+                                .with_style(Style {
+                                    fill_color: Some(style.background_color),
+                                    stroke: Some(Stroke::new(
+                                        Size::lpx(style.stroke_width),
+                                        style.stroke_color,
+                                    )),
+                                    corner_shape: Some(CornerShape::Round(Size::lpx(
+                                        style.border_radius,
+                                    ))),
+                                    ..Default::default()
+                                })
+                                .with_child(content),
+                        ),
+                );
+            }
+        }
+
+        Node::new()
+            .with_layout_direction(Layout::Stack)
+            .with_children(children)
+    }
+}
@@ -0,0 +1,857 @@
+//! Plot component for line, bar and scatter charts
+//!
+//! Plots one or more data series over axes with "nice" tick values. The view
+//! fits the data until the user pans (drag) or zooms (mouse wheel, around the
+//! pointer); a right-click goes back to fitting the data. Hovering a point or
+//! bar shows its value in a tooltip.
+//!
+//! The chart is drawn with `Painter`s: the frame draws the grid, axes and tick
+//! labels, and the plot area inside it draws the series, clipped to the area.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, HorizontalAlign, InteractionEvent, Layout,
+    MeasureTextRequest, MouseButton, Node, NodeId, Overflow, Painter, Place, Rect, Size, Spacing,
+    Stroke, Style, TextContent, Theme, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Relative view change per pixel of wheel scrolling
+const ZOOM_SPEED: f32 = 0.005;
+/// Fraction of the data range added on each side when fitting the data
+const FIT_MARGIN: f32 = 0.05;
+/// Approximate distance between ticks, in logical pixels
+const X_TICK_SPACING: f32 = 80.0;
+const Y_TICK_SPACING: f32 = 40.0;
+/// Gap between tick labels and the plot area
+const LABEL_GAP: f32 = 6.0;
+
+/// Visual styling for a plot
+#[derive(Debug, Clone, WithBuilders)]
+pub struct PlotStyle {
+    /// Background color of the plot
+    pub background_color: Color,
+    /// Color of the plot's border
+    pub stroke_color: Color,
+    /// Width of the plot's border (0 for none)
+    pub stroke_width: f32,
+    /// Corner radius of the plot
+    pub border_radius: f32,
+    /// Inset of the axes and labels from the plot's edges
+    pub padding: f32,
+    /// Color of the axis lines
+    pub axis_color: Color,
+    /// Color of the grid lines at each tick
+    pub grid_color: Color,
+    /// Color of the tick labels
+    pub label_color: Color,
+    /// Font size of the tick labels, legend and tooltip
+    pub font_size: f32,
+    /// Colors given to series without their own, in order
+    pub series_colors: Vec<Color>,
+    /// Width of line series
+    pub line_width: f32,
+    /// Radius of scatter points
+    pub point_radius: f32,
+    /// Fraction of the space between bars that the bars fill
+    pub bar_width: f32,
+    /// Distance in logical pixels within which a point counts as hovered
+    pub hover_radius: f32,
+    /// Background color of the legend and tooltip
+    pub overlay_color: Color,
+    /// Text color of the legend and tooltip
+    pub overlay_text_color: Color,
+}
+
+impl PlotStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            background_color: theme.field,
+            stroke_color: theme.border,
+            stroke_width: 1.0,
+            border_radius: 8.0,
+            padding: 12.0,
+            axis_color: theme.text_muted,
+            grid_color: theme.border.with_alpha(0.5),
+            label_color: theme.text_muted,
+            font_size: 13.0,
+            series_colors: vec![
+                theme.primary,
+                theme.secondary,
+                theme.success,
+                theme.warning,
+                theme.danger,
+            ],
+            line_width: 2.0,
+            point_radius: 3.5,
+            bar_width: 0.8,
+            hover_radius: 12.0,
+            overlay_color: theme.overlay.with_alpha(0.92),
+            overlay_text_color: theme.text,
+        }
+    }
+
+    /// Color of the series at `index`, unless it has its own
+    fn series_color(&self, index: usize) -> Color {
+        if self.series_colors.is_empty() {
+            self.axis_color
+        } else {
+            self.series_colors[index % self.series_colors.len()]
+        }
+    }
+}
+
+impl Default for PlotStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// How a series is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    /// Points joined by a line, in order
+    Line,
+    /// A bar from zero to each point
+    Bar,
+    /// A dot at each point
+    Scatter,
+}
+
+/// A named set of points drawn in one style
+#[derive(Debug, Clone)]
+pub struct Series {
+    name: String,
+    kind: SeriesKind,
+    points: Vec<[f32; 2]>,
+    color: Option<Color>,
+}
+
+impl Series {
+    /// Create a series of the given kind from `[x, y]` points
+    pub fn new(
+        name: impl Into<String>,
+        kind: SeriesKind,
+        points: impl IntoIterator<Item = [f32; 2]>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            points: points.into_iter().collect(),
+            color: None,
+        }
+    }
+
+    /// Create a line series
+    pub fn line(name: impl Into<String>, points: impl IntoIterator<Item = [f32; 2]>) -> Self {
+        Self::new(name, SeriesKind::Line, points)
+    }
+
+    /// Create a bar series
+    pub fn bar(name: impl Into<String>, points: impl IntoIterator<Item = [f32; 2]>) -> Self {
+        Self::new(name, SeriesKind::Bar, points)
+    }
+
+    /// Create a scatter series
+    pub fn scatter(name: impl Into<String>, points: impl IntoIterator<Item = [f32; 2]>) -> Self {
+        Self::new(name, SeriesKind::Scatter, points)
+    }
+
+    /// Set the color instead of taking the next one from the style
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    fn finite_points(&self) -> impl Iterator<Item = [f32; 2]> + '_ {
+        self.points
+            .iter()
+            .copied()
+            .filter(|[x, y]| x.is_finite() && y.is_finite())
+    }
+}
+
+/// Visible data ranges
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct View {
+    x: (f32, f32),
+    y: (f32, f32),
+}
+
+/// Mapping between data coordinates and logical pixels in the plot area
+#[derive(Debug, Clone, Copy)]
+struct Projection {
+    view: View,
+    width: f32,
+    height: f32,
+}
+
+impl Projection {
+    fn to_screen(self, [x, y]: [f32; 2]) -> [f32; 2] {
+        [
+            (x - self.view.x.0) / (self.view.x.1 - self.view.x.0) * self.width,
+            (self.view.y.1 - y) / (self.view.y.1 - self.view.y.0) * self.height,
+        ]
+    }
+
+    fn to_data(self, [x, y]: [f32; 2]) -> [f32; 2] {
+        [
+            self.view.x.0 + x / self.width * (self.view.x.1 - self.view.x.0),
+            self.view.y.1 - y / self.height * (self.view.y.1 - self.view.y.0),
+        ]
+    }
+}
+
+/// Horizontal extent of the bar for `x`, in data units
+///
+/// Bars of all bar series share the space around their x value, side by
+/// side in series order.
+#[derive(Debug, Clone, Copy)]
+struct BarLayout {
+    slot: f32,
+    count: usize,
+}
+
+impl BarLayout {
+    fn new(series: &[Series], bar_width: f32) -> Self {
+        let mut xs: Vec<f32> = series
+            .iter()
+            .filter(|series| series.kind == SeriesKind::Bar)
+            .flat_map(|series| series.finite_points().map(|[x, _]| x))
+            .collect();
+        xs.sort_by(f32::total_cmp);
+        let spacing = xs
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|gap| *gap > f32::EPSILON)
+            .fold(f32::INFINITY, f32::min);
+        let spacing = if spacing.is_finite() { spacing } else { 1.0 };
+        Self {
+            slot: spacing * bar_width.clamp(0.0, 1.0),
+            count: series
+                .iter()
+                .filter(|series| series.kind == SeriesKind::Bar)
+                .count()
+                .max(1),
+        }
+    }
+
+    fn span(&self, x: f32, bar_index: usize) -> (f32, f32) {
+        let width = self.slot / self.count as f32;
+        let left = x - self.slot * 0.5 + width * bar_index as f32;
+        (left, left + width)
+    }
+}
+
+/// A hovered data point: series index and point index
+type Hit = (usize, usize);
+
+/// Round a tick step to 1, 2 or 5 times a power of ten
+fn nice_step(span: f32, target_count: f32) -> f32 {
+    let raw = span / target_count.max(1.0);
+    let magnitude = 10f32.powf(raw.log10().floor());
+    let normalized = raw / magnitude;
+    let nice = if normalized < 1.5 {
+        1.0
+    } else if normalized < 3.0 {
+        2.0
+    } else if normalized < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Tick values within `range` spaced about `spacing` pixels apart over `length`
+pub(crate) fn ticks(range: (f32, f32), length: f32, spacing: f32) -> (Vec<f32>, f32) {
+    let step = nice_step(range.1 - range.0, length / spacing);
+    if !step.is_finite() || step <= 0.0 {
+        return (Vec::new(), 1.0);
+    }
+    let first = (range.0 / step).ceil() as i64;
+    let last = (range.1 / step).floor() as i64;
+    let ticks = (first..=last.min(first + 100))
+        .map(|index| index as f32 * step)
+        .collect();
+    (ticks, step)
+}
+
+/// Format a value with as many decimals as a step of `step` needs
+pub(crate) fn format_tick(value: f32, step: f32) -> String {
+    let decimals = (-step.log10().floor()).clamp(0.0, 6.0) as usize;
+    // Avoid printing "-0" for values that are zero within rounding
+    let value = if value.abs() < step * 1e-3 {
+        0.0
+    } else {
+        value
+    };
+    format!("{:.*}", decimals, value)
+}
+
+/// A chart of line, bar and scatter series
+///
+/// Dragging pans the view and the mouse wheel zooms it around the pointer.
+/// The wheel is read from the input state rather than consumed, so a plot
+/// inside a scroll area also scrolls it.
+///
+/// # Example
+///
+/// ```ignore
+/// Plot::new()
+///     .series(Series::line("sin", (0..100).map(|i| [i as f32 * 0.1, (i as f32 * 0.1).sin()])))
+///     .series(Series::scatter("samples", samples.iter().copied()))
+///     .height(Size::lpx(300.0))
+///     .node(&mut ctx)
+/// ```
+pub struct Plot {
+    series: Vec<Series>,
+    x_range: Option<(f32, f32)>,
+    y_range: Option<(f32, f32)>,
+    legend: bool,
+    width: Size,
+    height: Size,
+    id: Option<String>,
+    style: Option<PlotStyle>,
+}
+
+impl Default for Plot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plot {
+    /// Create an empty plot with the theme's default style
+    pub fn new() -> Self {
+        Self {
+            series: Vec::new(),
+            x_range: None,
+            y_range: None,
+            legend: true,
+            width: Size::Fill,
+            height: Size::Fill,
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Add a series
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Show `min..=max` on the x axis instead of fitting the data
+    pub fn x_range(mut self, min: f32, max: f32) -> Self {
+        self.x_range = Some((min, max));
+        self
+    }
+
+    /// Show `min..=max` on the y axis instead of fitting the data
+    pub fn y_range(mut self, min: f32, max: f32) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+
+    /// Show or hide the legend of series names (default: shown)
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Set the width (default: fill the parent)
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height (default: fill the parent)
+    pub fn height(mut self, height: Size) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set a custom ID (otherwise auto-generated)
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the plot
+    pub fn with_style(mut self, style: PlotStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The view fitting all series, with a margin
+    ///
+    /// Bars always reach down (or up) to zero, so zero is kept in view when
+    /// there are bars.
+    fn fit(&self, bars: &BarLayout) -> View {
+        let mut x = (f32::INFINITY, f32::NEG_INFINITY);
+        let mut y = (f32::INFINITY, f32::NEG_INFINITY);
+        let mut bar_index = 0;
+        for series in &self.series {
+            for [px, py] in series.finite_points() {
+                let (left, right) = if series.kind == SeriesKind::Bar {
+                    bars.span(px, bar_index)
+                } else {
+                    (px, px)
+                };
+                x = (x.0.min(left), x.1.max(right));
+                y = (y.0.min(py), y.1.max(py));
+                if series.kind == SeriesKind::Bar {
+                    y = (y.0.min(0.0), y.1.max(0.0));
+                }
+            }
+            if series.kind == SeriesKind::Bar {
+                bar_index += 1;
+            }
+        }
+
+        let pad = |(min, max): (f32, f32)| {
+            if !min.is_finite() {
+                (0.0, 1.0)
+            } else if max - min <= f32::EPSILON {
+                let half = (min.abs() * 0.5).max(0.5);
+                (min - half, max + half)
+            } else {
+                let margin = (max - min) * FIT_MARGIN;
+                (min - margin, max + margin)
+            }
+        };
+        View {
+            x: self.x_range.unwrap_or_else(|| pad(x)),
+            y: self.y_range.unwrap_or_else(|| pad(y)),
+        }
+    }
+
+    /// The point or bar under `pointer`, if any
+    fn hit(
+        &self,
+        projection: &Projection,
+        bars: &BarLayout,
+        pointer: [f32; 2],
+        radius: f32,
+    ) -> Option<Hit> {
+        let data = projection.to_data(pointer);
+        let mut nearest: Option<(f32, Hit)> = None;
+        let mut bar_index = 0;
+        for (series_index, series) in self.series.iter().enumerate() {
+            for (point_index, point) in series.points.iter().copied().enumerate() {
+                if !point[0].is_finite() || !point[1].is_finite() {
+                    continue;
+                }
+                let distance = if series.kind == SeriesKind::Bar {
+                    let (left, right) = bars.span(point[0], bar_index);
+                    let (bottom, top) = (point[1].min(0.0), point[1].max(0.0));
+                    let inside =
+                        (left..=right).contains(&data[0]) && (bottom..=top).contains(&data[1]);
+                    if !inside {
+                        continue;
+                    }
+                    0.0
+                } else {
+                    let screen = projection.to_screen(point);
+                    let (dx, dy) = (screen[0] - pointer[0], screen[1] - pointer[1]);
+                    (dx * dx + dy * dy).sqrt()
+                };
+                if distance <= radius && nearest.is_none_or(|(best, _)| distance < best) {
+                    nearest = Some((distance, (series_index, point_index)));
+                }
+            }
+            if series.kind == SeriesKind::Bar {
+                bar_index += 1;
+            }
+        }
+        nearest.map(|(_, hit)| hit)
+    }
+}
+
+impl Component for Plot {
+    fn node(self, ctx: &mut UiContext) -> Node {
+        let id = self.id.clone().unwrap_or_else(|| ctx.generate_id("plot"));
+        let style = self
+            .style
+            .clone()
+            .unwrap_or_else(|| PlotStyle::from_theme(ctx.theme()));
+        let mut state = std::mem::take(ctx.memory().plot(&id));
+
+        let bars = BarLayout::new(&self.series, style.bar_width);
+        let fitted = self.fit(&bars);
+        let mut view = View {
+            x: state.x_range.unwrap_or(fitted.x),
+            y: state.y_range.unwrap_or(fitted.y),
+        };
+
+        let label = |text: String| {
+            TextContent::new(text)
+                .with_font_size(Size::lpx(style.font_size))
+                .with_color(style.label_color)
+        };
+        let text_width = |ctx: &mut UiContext, content: &TextContent| {
+            ctx.measure_text(MeasureTextRequest::from_text_content(content))
+                .width
+        };
+
+        // Plot area as of the last frame: the plot minus its padding and the
+        // room for tick labels. Ticks follow from it, and the left margin
+        // from the widest y tick label.
+        let zoom = ctx
+            .events_for(&id)
+            .last()
+            .map_or(ctx.scale_factor(), |event| event.zoom);
+        let (outer_width, outer_height) = ctx
+            .measured_size(&id)
+            .map_or((400.0, 300.0), |(width, height)| {
+                (width / zoom, height / zoom)
+            });
+        let top_margin = style.font_size * 0.5;
+        let bottom_margin = style.font_size * 1.5 + LABEL_GAP;
+        let area_height =
+            (outer_height - 2.0 * style.padding - top_margin - bottom_margin).max(1.0);
+        let (y_ticks, y_step) = ticks(view.y, area_height, Y_TICK_SPACING);
+        let y_labels: Vec<String> = y_ticks.iter().map(|y| format_tick(*y, y_step)).collect();
+        let left_margin = y_labels
+            .iter()
+            .map(|text| text_width(ctx, &label(text.clone())))
+            .fold(0.0, f32::max)
+            + LABEL_GAP;
+        let right_margin = style.font_size;
+        let area_width = (outer_width - 2.0 * style.padding - left_margin - right_margin).max(1.0);
+        let margins = Spacing {
+            top: Size::lpx(top_margin),
+            right: Size::lpx(right_margin),
+            bottom: Size::lpx(bottom_margin),
+            left: Size::lpx(left_margin),
+        };
+        let area_offset = [style.padding + left_margin, style.padding + top_margin];
+
+        // Pointer, pan and reset from last frame's events
+        let mut pointer = None;
+        let mut panned = false;
+        for event in ctx.events_for(&id) {
+            let local = [
+                event.local_position.x / event.zoom - area_offset[0],
+                event.local_position.y / event.zoom - area_offset[1],
+            ];
+            match &event.event {
+                InteractionEvent::Hover { .. } => pointer = Some(local),
+                InteractionEvent::DragMove { delta, .. } => {
+                    let dx = delta.x / event.zoom / area_width * (view.x.1 - view.x.0);
+                    let dy = delta.y / event.zoom / area_height * (view.y.1 - view.y.0);
+                    view.x = (view.x.0 - dx, view.x.1 - dx);
+                    view.y = (view.y.0 + dy, view.y.1 + dy);
+                    panned = true;
+                }
+                _ => {}
+            }
+        }
+        let in_area =
+            |[x, y]: [f32; 2]| x >= 0.0 && y >= 0.0 && x <= area_width && y <= area_height;
+        let pointer = pointer.filter(|pointer| in_area(*pointer));
+
+        // Zoom around the pointer, keeping the value under it in place
+        let wheel = ctx.input().scroll_delta.1;
+        let mut zoomed = false;
+        if let Some(pointer) = pointer.filter(|_| wheel != 0.0) {
+            let projection = Projection {
+                view,
+                width: area_width,
+                height: area_height,
+            };
+            let [x, y] = projection.to_data(pointer);
+            let factor = (-wheel * ZOOM_SPEED).exp();
+            view.x = (x + (view.x.0 - x) * factor, x + (view.x.1 - x) * factor);
+            view.y = (y + (view.y.0 - y) * factor, y + (view.y.1 - y) * factor);
+            zoomed = true;
+        }
+
+        if ctx.was_clicked_with(&id, MouseButton::Right) {
+            state.x_range = None;
+            state.y_range = None;
+            view = fitted;
+        } else if panned || zoomed {
+            state.x_range = Some(view.x);
+            state.y_range = Some(view.y);
+        }
+        *ctx.memory().plot(&id) = state;
+
+        let projection = Projection {
+            view,
+            width: area_width,
+            height: area_height,
+        };
+        let hovered =
+            pointer.and_then(|pointer| self.hit(&projection, &bars, pointer, style.hover_radius));
+
+        // Tooltip with the hovered value, beside the pointer and kept inside
+        // the plot
+        let tooltip = hovered
+            .zip(pointer)
+            .map(|((series_index, point_index), pointer)| {
+                let series = &self.series[series_index];
+                let [x, y] = series.points[point_index];
+                let (_, x_step) = ticks(view.x, area_width, X_TICK_SPACING);
+                let text = format!(
+                    "{}\nx: {}\ny: {}",
+                    series.name,
+                    format_tick(x, x_step / 10.0),
+                    format_tick(y, y_step / 10.0)
+                );
+                let content = TextContent::new(text)
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(style.overlay_text_color);
+                let width = text_width(ctx, &content) + 2.0 * style.padding * 0.5;
+                let mut left = area_offset[0] - style.padding + pointer[0] + style.hover_radius;
+                if left + width > area_offset[0] - style.padding + area_width {
+                    left -= width + 2.0 * style.hover_radius;
+                }
+                Node::new()
+                    .with_place(Place::Absolute {
+                        x: Size::lpx(left.max(0.0)),
+                        y: Size::lpx((area_offset[1] - style.padding + pointer[1]).max(0.0)),
+                    })
+                    .with_padding(Spacing::all(Size::lpx(style.padding * 0.5)))
+                    .with_style(Style {
+                        fill_color: Some(style.overlay_color),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(
+                            style.border_radius * 0.5,
+                        ))),
+                        ..Default::default()
+                    })
+                    .with_content(Content::Text(content))
+            });
+
+        let legend = (self.legend && self.series.iter().any(|series| !series.name.is_empty()))
+            .then(|| {
+                let entries = self
+                    .series
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, series)| !series.name.is_empty())
+                    .map(|(index, series)| {
+                        let color = series.color.unwrap_or_else(|| style.series_color(index));
+                        Node::new()
+                            .with_layout_direction(Layout::Horizontal)
+                            .with_gap(Size::lpx(style.font_size * 0.5))
+                            .with_v_align(VerticalAlign::Center)
+                            .with_children(vec![
+                                Node::new()
+                                    .with_width(Size::lpx(style.font_size * 0.75))
+                                    .with_height(Size::lpx(style.font_size * 0.75))
+                                    .with_style(Style {
+                                        fill_color: Some(color),
+                                        corner_shape: Some(CornerShape::Round(Size::lpx(
+                                            if series.kind == SeriesKind::Line {
+                                                style.font_size * 0.375
+                                            } else {
+                                                2.0
+                                            },
+                                        ))),
+                                        ..Default::default()
+                                    }),
+                                Node::new().with_content(Content::Text(
+                                    TextContent::new(series.name.clone())
+                                        .with_font_size(Size::lpx(style.font_size))
+                                        .with_color(style.overlay_text_color),
+                                )),
+                            ])
+                    })
+                    .collect();
+                Node::new()
+                    .with_place(Place::Alignment {
+                        h_align: HorizontalAlign::Right,
+                        v_align: VerticalAlign::Top,
+                    })
+                    .with_margin(Spacing {
+                        top: Size::lpx(top_margin + style.padding * 0.5),
+                        right: Size::lpx(right_margin + style.padding * 0.5),
+                        bottom: Size::lpx(0.0),
+                        left: Size::lpx(0.0),
+                    })
+                    .with_padding(Spacing::all(Size::lpx(style.padding * 0.5)))
+                    .with_gap(Size::lpx(style.font_size * 0.25))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_style(Style {
+                        fill_color: Some(style.overlay_color),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(
+                            style.border_radius * 0.5,
+                        ))),
+                        ..Default::default()
+                    })
+                    .with_children(entries)
+            });
+
+        // Grid, axes and tick labels around the plot area
+        let frame_style = style.clone();
+        let frame = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(margins)
+            .with_painter(move |painter| {
+                paint_frame(painter, &frame_style, view, &y_ticks, &y_labels)
+            });
+
+        // Series, clipped to the plot area
+        let series = self.series;
+        let series_style = style.clone();
+        let area = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_overflow(Overflow::Hidden)
+            .with_painter(move |painter| {
+                paint_series(painter, &series_style, view, &series, &bars, hovered)
+            });
+
+        let mut children = vec![frame.with_child(area)];
+        children.extend(legend);
+        children.extend(tooltip);
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(self.width)
+            .with_height(self.height)
+            .with_padding(Spacing::all(Size::lpx(style.padding)))
+            .with_layout_direction(Layout::Stack)
+            .with_measure_size(true)
+            .with_style(Style {
+                fill_color: Some(style.background_color),
+                stroke: (style.stroke_width > 0.0)
+                    .then(|| Stroke::new(Size::lpx(style.stroke_width), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                ..Default::default()
+            })
+            .with_children(children)
+    }
+}
+
+/// Draw the grid, axes and tick labels; the painter's area is the plot area
+fn paint_frame(
+    painter: &mut Painter,
+    style: &PlotStyle,
+    view: View,
+    y_ticks: &[f32],
+    y_labels: &[String],
+) {
+    let (width, height) = painter.size();
+    let projection = Projection {
+        view,
+        width,
+        height,
+    };
+    let label = |text: String, h_align, v_align| {
+        TextContent::new(text)
+            .with_font_size(Size::lpx(style.font_size))
+            .with_color(style.label_color)
+            .with_h_align(h_align)
+            .with_v_align(v_align)
+    };
+
+    for (y, text) in y_ticks.iter().zip(y_labels) {
+        let [_, top] = projection.to_screen([0.0, *y]);
+        painter.line([0.0, top], [width, top], 1.0, style.grid_color);
+        painter.text(
+            [-LABEL_GAP, top],
+            label(text.clone(), HorizontalAlign::Right, VerticalAlign::Center),
+        );
+    }
+    let (x_ticks, x_step) = ticks(view.x, width, X_TICK_SPACING);
+    for x in x_ticks {
+        let [left, _] = projection.to_screen([x, 0.0]);
+        painter.line([left, 0.0], [left, height], 1.0, style.grid_color);
+        painter.text(
+            [left, height + LABEL_GAP],
+            label(
+                format_tick(x, x_step),
+                HorizontalAlign::Center,
+                VerticalAlign::Top,
+            ),
+        );
+    }
+
+    // Axes along the zero lines when they are in view, the area's edges otherwise
+    let [zero_x, zero_y] = projection.to_screen([0.0, 0.0]);
+    let axis_x = zero_x.clamp(0.0, width);
+    let axis_y = zero_y.clamp(0.0, height);
+    painter.line([0.0, axis_y], [width, axis_y], 1.0, style.axis_color);
+    painter.line([axis_x, 0.0], [axis_x, height], 1.0, style.axis_color);
+}
+
+/// Draw the series; the painter's area is the plot area
+fn paint_series(
+    painter: &mut Painter,
+    style: &PlotStyle,
+    view: View,
+    series: &[Series],
+    bars: &BarLayout,
+    hovered: Option<Hit>,
+) {
+    let (width, height) = painter.size();
+    let projection = Projection {
+        view,
+        width,
+        height,
+    };
+    let mut bar_index = 0;
+    for (series_index, series) in series.iter().enumerate() {
+        let color = series
+            .color
+            .unwrap_or_else(|| style.series_color(series_index));
+        let hovered_point = hovered
+            .filter(|(index, _)| *index == series_index)
+            .map(|(_, point)| point);
+        match series.kind {
+            SeriesKind::Line => {
+                // Gaps in the data (non-finite values) break the line
+                let mut run = Vec::new();
+                for point in series.points.iter().chain([&[f32::NAN, f32::NAN]]) {
+                    if point[0].is_finite() && point[1].is_finite() {
+                        run.push(projection.to_screen(*point));
+                    } else if !run.is_empty() {
+                        if run.len() == 1 {
+                            painter.circle(run[0], style.line_width, color);
+                        }
+                        painter.path(run.drain(..), style.line_width, color);
+                    }
+                }
+            }
+            SeriesKind::Scatter => {
+                for point in series.finite_points() {
+                    painter.circle(projection.to_screen(point), style.point_radius, color);
+                }
+            }
+            SeriesKind::Bar => {
+                for (point_index, point) in series.points.iter().enumerate() {
+                    if !point[0].is_finite() || !point[1].is_finite() {
+                        continue;
+                    }
+                    let (left, right) = bars.span(point[0], bar_index);
+                    let [x0, y0] = projection.to_screen([left, point[1].max(0.0)]);
+                    let [x1, y1] = projection.to_screen([right, point[1].min(0.0)]);
+                    let rect = Rect::new([x0, y0], [x1, y1]);
+                    let fill = if hovered_point == Some(point_index) {
+                        color.with_alpha(1.0)
+                    } else {
+                        color.with_alpha(color.a * 0.8)
+                    };
+                    painter.rect(rect, fill);
+                }
+                bar_index += 1;
+            }
+        }
+
+        // Ring around the hovered point
+        if let Some(point_index) = hovered_point.filter(|_| series.kind != SeriesKind::Bar) {
+            let center = projection.to_screen(series.points[point_index]);
+            painter.circle(center, style.point_radius + 2.0, color);
+            painter.circle_stroke(
+                center,
+                style.point_radius + 4.0,
+                2.0,
+                style.overlay_text_color,
+            );
+        }
+    }
+}
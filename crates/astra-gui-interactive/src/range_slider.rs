@@ -0,0 +1,326 @@
+//! Range slider component for interactive UI
+//!
+//! A slider with two handles for picking a low and a high value within a
+//! range, such as a price filter. The track between the handles is filled.
+
+use astra_gui::{
+    Component, CornerShape, Layout, Node, NodeId, RangeSliderState, Size, Style, Transition,
+    Translation, UiContext,
+};
+use astra_gui_wgpu::InteractionEvent;
+use std::ops::RangeInclusive;
+
+use crate::SliderStyle;
+
+/// Handle index of the low end
+const LOW: usize = 0;
+/// Handle index of the high end
+const HIGH: usize = 1;
+
+/// A slider with two handles selecting a sub-range
+///
+/// A press moves the nearest handle to the pointer and dragging keeps moving
+/// it. Handles cannot pass each other: a handle stops `min_gap` short of the
+/// other one. When a drag starts on stacked handles, the direction it moves
+/// in picks the handle, so they can be pulled apart either way. Shares
+/// `SliderStyle` with `Slider`.
+///
+/// # Example
+///
+/// ```ignore
+/// RangeSlider::new(&mut min_price, &mut max_price, 0.0..=500.0)
+///     .step(5.0)
+///     .min_gap(10.0)
+///     .on_change(|low, high| println!("{}–{}", low, high))
+///     .node(&mut ctx)
+/// ```
+pub struct RangeSlider<'a> {
+    low: &'a mut f32,
+    high: &'a mut f32,
+    range: RangeInclusive<f32>,
+    step: Option<f32>,
+    min_gap: f32,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<SliderStyle>,
+    on_change: Option<Box<dyn FnMut(f32, f32) + 'a>>,
+}
+
+impl<'a> RangeSlider<'a> {
+    /// Create a range slider bound to the low and high values
+    pub fn new(low: &'a mut f32, high: &'a mut f32, range: RangeInclusive<f32>) -> Self {
+        Self {
+            low,
+            high,
+            range,
+            step: None,
+            min_gap: 0.0,
+            disabled: false,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Set the step size for value snapping
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Set the smallest allowed distance between the low and high values
+    /// (default: 0)
+    pub fn min_gap(mut self, min_gap: f32) -> Self {
+        self.min_gap = min_gap.max(0.0);
+        self
+    }
+
+    /// Set whether the range slider is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the range slider
+    pub fn with_style(mut self, style: SliderStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the low and high values when either
+    /// changes
+    pub fn on_change(mut self, f: impl FnMut(f32, f32) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Position of `value` along the range, from 0 to 1
+    fn fraction(&self, value: f32) -> f32 {
+        let range_size = self.range.end() - self.range.start();
+        if range_size > 0.0 {
+            ((value - self.range.start()) / range_size).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Value under the pointer, snapped to the step
+    fn value_at(&self, style: &SliderStyle, local_x: f32, zoom: f32) -> f32 {
+        // Thumb centers travel between half a track height from either end
+        let travel = style.track_width - style.track_height;
+        let fraction = if travel > 0.0 {
+            ((local_x / zoom - style.track_height / 2.0) / travel).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let range_size = self.range.end() - self.range.start();
+        let value = self.range.start() + range_size * fraction;
+        match self.step {
+            Some(step) if step > 0.0 => {
+                // Snap to range boundaries if we're very close
+                if fraction < 0.02 {
+                    *self.range.start()
+                } else if fraction > 0.98 {
+                    *self.range.end()
+                } else {
+                    let steps = ((value - self.range.start()) / step).round();
+                    (self.range.start() + steps * step)
+                        .clamp(*self.range.start(), *self.range.end())
+                }
+            }
+            _ => value,
+        }
+    }
+
+    /// The handle a press at `value` should grab
+    fn nearest_handle(&self, value: f32, low: f32, high: f32) -> usize {
+        let to_low = (value - low).abs();
+        let to_high = (value - high).abs();
+        if to_low < to_high {
+            LOW
+        } else if to_high < to_low {
+            HIGH
+        } else if value < low || high >= *self.range.end() {
+            // Stacked handles: take the one that can move toward the pointer
+            LOW
+        } else {
+            HIGH
+        }
+    }
+}
+
+impl Component for RangeSlider<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("range_slider"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| SliderStyle::from_theme(ctx.theme()));
+        let theme = *ctx.theme();
+        let hitbox_id = format!("{}_hitbox", id);
+
+        let start = *self.range.start();
+        let end = *self.range.end();
+        let mut low = self.low.clamp(start, end);
+        let mut high = self.high.clamp(low, end);
+
+        let mut state = *ctx.memory().range_slider(&id);
+        // Releasing a drag also clicks; that click must not move the other
+        // handle to the pointer
+        let dragging = state.active.is_some();
+        if !self.disabled {
+            for event in ctx.events() {
+                if event.target.as_str() != hitbox_id {
+                    continue;
+                }
+
+                let handle = match &event.event {
+                    InteractionEvent::Click { .. } if !dragging => {
+                        let value = self.value_at(&style, event.local_position.x, event.zoom);
+                        Some((self.nearest_handle(value, low, high), value))
+                    }
+                    InteractionEvent::DragStart { .. } => {
+                        let value = self.value_at(&style, event.local_position.x, event.zoom);
+                        let handle = self.nearest_handle(value, low, high);
+                        state.active = Some(handle);
+                        state.stacked = high - low <= self.min_gap + f32::EPSILON;
+                        Some((handle, value))
+                    }
+                    InteractionEvent::DragMove { .. } => {
+                        let value = self.value_at(&style, event.local_position.x, event.zoom);
+                        if state.stacked && value < low {
+                            state.active = Some(LOW);
+                            state.stacked = false;
+                        } else if state.stacked && value > high {
+                            state.active = Some(HIGH);
+                            state.stacked = false;
+                        }
+                        state.active.map(|handle| (handle, value))
+                    }
+                    InteractionEvent::DragEnd { .. } => {
+                        state = RangeSliderState::default();
+                        None
+                    }
+                    _ => None,
+                };
+
+                // Each handle stops short of the other
+                match handle {
+                    Some((LOW, value)) => low = value.min(high - self.min_gap).max(start),
+                    Some((_, value)) => high = value.max(low + self.min_gap).min(end),
+                    None => {}
+                }
+            }
+        }
+        if self.disabled {
+            state = RangeSliderState::default();
+        }
+        *ctx.memory().range_slider(&id) = state;
+
+        if (low - *self.low).abs() > f32::EPSILON || (high - *self.high).abs() > f32::EPSILON {
+            *self.low = low;
+            *self.high = high;
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(low, high);
+            }
+        }
+
+        // Thumb centers travel between half a track height from either end
+        let travel = style.track_width - style.track_height;
+        let thumb_inset = (style.track_height - style.thumb_size) / 2.0;
+        let low_x = travel * self.fraction(low);
+        let high_x = travel * self.fraction(high);
+
+        let thumb = |offset_x: f32| {
+            Node::new()
+                .with_width(Size::lpx(style.thumb_size))
+                .with_height(Size::lpx(style.thumb_size))
+                .with_translation(Translation::new(
+                    Size::lpx(offset_x + thumb_inset),
+                    Size::lpx(thumb_inset),
+                ))
+                .with_style(Style {
+                    fill_color: Some(style.thumb_color),
+                    opacity: Some(1.0),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(style.thumb_size / 2.0))),
+                    ..Default::default()
+                })
+                .with_hover_style(Style {
+                    fill_color: Some(style.thumb_hover_color),
+                    ..Default::default()
+                })
+                .with_active_style(Style {
+                    fill_color: Some(style.thumb_active_color),
+                    ..Default::default()
+                })
+                .with_disabled_style(Style {
+                    opacity: Some(0.0),
+                    ..Default::default()
+                })
+                .with_disabled(self.disabled)
+                .with_transition(Transition::quick())
+        };
+        // The dragged handle is drawn on top of the other one
+        let thumbs = if state.active == Some(LOW) {
+            [thumb(high_x), thumb(low_x)]
+        } else {
+            [thumb(low_x), thumb(high_x)]
+        };
+
+        let mut children = vec![
+            // Track background (unfilled)
+            Node::new()
+                .with_width(Size::lpx(style.track_width))
+                .with_height(Size::lpx(style.track_height))
+                .with_style(Style {
+                    fill_color: Some(style.track_color),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(style.track_height / 2.0))),
+                    ..Default::default()
+                })
+                .with_disabled(self.disabled)
+                .with_transition(Transition::quick()),
+            // Filled portion of the track between the handles
+            Node::new()
+                .with_width(Size::lpx(high_x - low_x + style.track_height))
+                .with_height(Size::lpx(style.track_height))
+                .with_translation(Translation::new(Size::lpx(low_x), Size::lpx(0.0)))
+                .with_style(Style {
+                    fill_color: Some(style.filled_color),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(style.track_height / 2.0))),
+                    ..Default::default()
+                })
+                .with_disabled_style(Style {
+                    fill_color: Some(theme.field_hover),
+                    ..Default::default()
+                })
+                .with_disabled(self.disabled)
+                .with_transition(Transition::quick()),
+        ];
+        children.extend(thumbs);
+        // Hitbox node
+        children.push(
+            Node::new()
+                .with_id(NodeId::new(&hitbox_id))
+                .with_width(Size::Fill)
+                .with_height(Size::Fill)
+                .with_disabled(self.disabled),
+        );
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(style.track_width))
+            .with_height(Size::lpx(style.thumb_size.max(style.track_height)))
+            .with_layout_direction(Layout::Stack)
+            .with_children(children)
+    }
+}
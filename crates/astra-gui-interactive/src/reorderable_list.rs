@@ -0,0 +1,327 @@
+//! Drag-to-reorder list component for interactive UI
+//!
+//! Rows are picked up by dragging them. While a row is dragged, a ghost of it
+//! follows the pointer and a placeholder opens at the position it would be
+//! dropped at, with the rows around it sliding apart. Dropping reports the
+//! move; the app reorders its items.
+
+use astra_gui::{
+    Color, Component, CornerShape, CursorIcon, DragGhost, Layout, Node, NodeId, Overflow, Size,
+    Spacing, Stroke, Style, Theme, UiContext,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::{InteractionEvent, MouseButton};
+use std::cell::Cell;
+
+/// Time for the placeholder to move from one slot to the next
+const PLACEHOLDER_SECONDS: f32 = 0.15;
+
+/// Visual styling for a reorderable list
+#[derive(Debug, Clone, WithBuilders)]
+pub struct ReorderableListStyle {
+    /// Space between rows
+    pub gap: f32,
+    /// Fill color of the placeholder marking the drop position
+    pub placeholder_color: Color,
+    /// Border color of the placeholder
+    pub placeholder_stroke_color: Color,
+    /// Background color behind the dragged row's ghost
+    pub ghost_color: Color,
+    /// Opacity of the ghost following the pointer
+    pub ghost_opacity: f32,
+    /// Corner radius of the placeholder and the ghost
+    pub border_radius: f32,
+}
+
+impl ReorderableListStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            gap: 4.0,
+            placeholder_color: theme.field,
+            placeholder_stroke_color: theme.primary,
+            ghost_color: theme.surface_active,
+            ghost_opacity: 0.8,
+            border_radius: 6.0,
+        }
+    }
+}
+
+impl Default for ReorderableListStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// Builds the row at an index
+type RowFn<'a> = Box<dyn FnMut(&mut UiContext, usize) -> Node + 'a>;
+
+/// Called with a row's old and new index after it is dropped
+type ReorderFn<'a> = Box<dyn FnMut(usize, usize) + 'a>;
+
+/// A vertical list whose rows can be dragged into a new order
+///
+/// Rows are built by the item-builder closure from their index. The list does
+/// not own the items: when a row is dropped in a new position, `on_reorder`
+/// receives its old index and its index after the move, matching
+/// `items.remove(from)` followed by `items.insert(to, item)`.
+///
+/// A drag picks up the innermost node with an ID under the pointer, so
+/// interactive widgets in rows keep working and rows are grabbed by their
+/// remaining area.
+///
+/// # Example
+///
+/// ```ignore
+/// let names = self.tasks.iter().map(|task| task.name.clone()).collect::<Vec<_>>();
+/// ReorderableList::new(names.len(), |_ctx, index| task_row(&names[index]))
+///     .on_reorder(|from, to| {
+///         let task = self.tasks.remove(from);
+///         self.tasks.insert(to, task);
+///     })
+///     .node(&mut ctx)
+/// ```
+pub struct ReorderableList<'a> {
+    count: usize,
+    builder: RowFn<'a>,
+    on_reorder: Option<ReorderFn<'a>>,
+    width: Size,
+    id: Option<String>,
+    style: Option<ReorderableListStyle>,
+}
+
+impl<'a> ReorderableList<'a> {
+    /// Create a list of `count` rows, built by `builder`
+    pub fn new(count: usize, builder: impl FnMut(&mut UiContext, usize) -> Node + 'a) -> Self {
+        Self {
+            count,
+            builder: Box::new(builder),
+            on_reorder: None,
+            width: Size::Fill,
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Set a callback called with `(from, to)` when a row is dropped in a new
+    /// position
+    pub fn on_reorder(mut self, f: impl FnMut(usize, usize) + 'a) -> Self {
+        self.on_reorder = Some(Box::new(f));
+        self
+    }
+
+    /// Set the width of the list (default: `Size::Fill`)
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the list
+    pub fn with_style(mut self, style: ReorderableListStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for ReorderableList<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("reorderable_list"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ReorderableListStyle::from_theme(ctx.theme()));
+        let row_id = |index: usize| format!("{}_row_{}", id, index);
+        let gap = style.gap * ctx.scale_factor();
+
+        let mut state = std::mem::take(ctx.memory().reorder(&id));
+        if state.dragging.is_some_and(|from| from >= self.count) {
+            state.dragging = None;
+        }
+        let was_dragging = state.dragging.is_some();
+        let mut dropped = None;
+
+        // Everything below is in layout pixels, like the measured row sizes
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let Some(index) = event
+                .target
+                .as_str()
+                .strip_prefix(&id)
+                .and_then(|rest| rest.strip_prefix("_row_"))
+                .and_then(|index| index.parse::<usize>().ok())
+                .filter(|index| *index < self.count)
+            else {
+                continue;
+            };
+
+            match &event.event {
+                InteractionEvent::DragStart {
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    // Rows are laid out as last frame measured them
+                    let mut top = 0.0;
+                    state.rows = (0..self.count)
+                        .map(|row| {
+                            let (width, height) =
+                                ctx.measured_size(&row_id(row)).unwrap_or_default();
+                            if row == index {
+                                state.width = width;
+                            }
+                            let extent = (top, height);
+                            top += height + gap;
+                            extent
+                        })
+                        .collect();
+                    state.dragging = Some(index);
+                    state.target = index;
+                    state.grab_y = event.local_position.y / event.zoom;
+                }
+                InteractionEvent::DragMove { .. } if state.dragging == Some(index) => {
+                    // Drop where the middle of the dragged row has passed the
+                    // middle of the rows it was dragged over
+                    let (top, height) = state.rows[index];
+                    let middle =
+                        top + event.local_position.y / event.zoom - state.grab_y + height * 0.5;
+                    state.target = state
+                        .rows
+                        .iter()
+                        .enumerate()
+                        .filter(|(row, (top, height))| *row != index && top + height * 0.5 < middle)
+                        .count();
+                }
+                InteractionEvent::DragEnd { .. } if state.dragging == Some(index) => {
+                    state.dragging = None;
+                    if state.target != index {
+                        dropped = Some((index, state.target));
+                        if let Some(ref mut on_reorder) = self.on_reorder {
+                            on_reorder(index, state.target);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Row order on screen: without the dragged row while dragging, and
+        // already in the new order on the frame of a drop, so nothing jumps
+        // when the app reorders its items for the next frame
+        let mut order: Vec<usize> = (0..self.count).collect();
+        if let Some(from) = state.dragging {
+            order.remove(from);
+        } else if let Some((from, to)) = dropped {
+            let row = order.remove(from);
+            order.insert(to, row);
+        }
+
+        // The slots before, between and after the rows hold the gaps between
+        // rows and, while dragging, the placeholder. The slot the drag started
+        // from is open right away; after that the open slot eases toward the
+        // target, so the rows slide apart rather than jump.
+        let now = ctx.time();
+        if let Some(from) = state.dragging {
+            if !was_dragging {
+                state.openness = vec![0.0; self.count];
+                state.openness[from] = 1.0;
+            }
+            let step = (now - state.last_time) as f32 / PLACEHOLDER_SECONDS;
+            for (slot, openness) in state.openness.iter_mut().enumerate() {
+                *openness = if slot == state.target {
+                    (*openness + step).min(1.0)
+                } else {
+                    (*openness - step).max(0.0)
+                };
+            }
+        } else {
+            state.openness.clear();
+        }
+        state.last_time = now;
+
+        let placeholder_height = state.dragging.map_or(0.0, |from| state.rows[from].1);
+        let slot = |slot: usize| {
+            let spacing = if slot == 0 || slot == order.len() {
+                0.0
+            } else {
+                gap
+            };
+            let openness = state.openness.get(slot).copied().unwrap_or(0.0);
+            let node = Node::new()
+                .with_width(Size::Fill)
+                .with_padding(Spacing::top(Size::ppx(spacing)))
+                .with_overflow(Overflow::Hidden);
+            if openness == 0.0 {
+                return node.with_height(Size::ppx(spacing));
+            }
+
+            let trailing = if slot < order.len() { gap } else { 0.0 };
+            let eased = openness * openness * (3.0 - 2.0 * openness);
+            node.with_height(Size::ppx(spacing + (placeholder_height + trailing) * eased))
+                .with_child(
+                    Node::new()
+                        .with_width(Size::Fill)
+                        .with_height(Size::ppx(placeholder_height))
+                        .with_style(Style {
+                            fill_color: Some(style.placeholder_color),
+                            stroke: Some(Stroke::new(
+                                Size::lpx(1.0),
+                                style.placeholder_stroke_color,
+                            )),
+                            corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                            ..Default::default()
+                        }),
+                )
+        };
+
+        let mut children = Vec::with_capacity(order.len() * 2 + 1);
+        for (position, index) in order.iter().enumerate() {
+            children.push(slot(position));
+            children.push(
+                Node::new()
+                    .with_id(NodeId::new(row_id(position)))
+                    .with_width(Size::Fill)
+                    .with_measure_size(true)
+                    .with_cursor(CursorIcon::Grab)
+                    .with_child((self.builder)(ctx, *index)),
+            );
+        }
+        children.push(slot(order.len()));
+
+        // The dragged row is built last, for the ghost; it takes the node out
+        // on the frame it is shown
+        if let Some(from) = state.dragging {
+            let (_, height) = state.rows[from];
+            let ghost = Cell::new(Some(
+                Node::new()
+                    .with_width(Size::ppx(state.width))
+                    .with_height(Size::ppx(height))
+                    .with_style(Style {
+                        fill_color: Some(style.ghost_color),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                        ..Default::default()
+                    })
+                    .with_child((self.builder)(ctx, from)),
+            ));
+            ctx.set_drag_ghost(
+                DragGhost::new(move || ghost.take().unwrap_or_default())
+                    .with_opacity(style.ghost_opacity),
+            );
+        }
+
+        *ctx.memory().reorder(&id) = state;
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(self.width)
+            .with_layout_direction(Layout::Vertical)
+            .with_children(children)
+    }
+}
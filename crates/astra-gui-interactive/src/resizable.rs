@@ -0,0 +1,412 @@
+//! Resizable wrapper component for interactive UI
+//!
+//! Attaches draggable edge and corner handles to any node, for user-resizable panels,
+//! crop rectangles and floating windows. Handles show a resize cursor while hovered and
+//! keep receiving drag events (and their cursor) when the pointer leaves them mid-drag.
+
+use astra_gui::{
+    Color, Component, CursorIcon, HorizontalAlign, Layout, Node, NodeId, Place, Size, Style, Theme,
+    Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::InteractionEvent;
+
+/// Visual styling for resize handles
+#[derive(Debug, Clone, WithBuilders)]
+pub struct ResizableStyle {
+    /// Thickness of the edge handles (grab area)
+    pub handle_thickness: f32,
+    /// Side length of the corner handles
+    pub corner_size: f32,
+    /// Handle color while idle
+    pub handle_color: Color,
+    /// Handle color while hovered
+    pub handle_hover_color: Color,
+    /// Handle color while dragged
+    pub handle_active_color: Color,
+}
+
+impl ResizableStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            handle_thickness: 6.0,
+            corner_size: 12.0,
+            handle_color: Color::transparent(),
+            handle_hover_color: theme.primary.with_alpha(0.5),
+            handle_active_color: theme.primary,
+        }
+    }
+}
+
+impl Default for ResizableStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// An edge or corner handle of a [`Resizable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeHandle {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl ResizeHandle {
+    /// All edge and corner handles
+    pub const ALL: [ResizeHandle; 8] = [
+        ResizeHandle::Left,
+        ResizeHandle::Right,
+        ResizeHandle::Top,
+        ResizeHandle::Bottom,
+        ResizeHandle::TopLeft,
+        ResizeHandle::TopRight,
+        ResizeHandle::BottomLeft,
+        ResizeHandle::BottomRight,
+    ];
+
+    /// Handles on the right and bottom edges, which never move the node's origin
+    pub const TRAILING: [ResizeHandle; 3] = [
+        ResizeHandle::Right,
+        ResizeHandle::Bottom,
+        ResizeHandle::BottomRight,
+    ];
+
+    /// Direction the handle grows the node in: -1 (left/up), 0 or 1 (right/down)
+    pub(crate) fn direction(self) -> (f32, f32) {
+        match self {
+            ResizeHandle::Left => (-1.0, 0.0),
+            ResizeHandle::Right => (1.0, 0.0),
+            ResizeHandle::Top => (0.0, -1.0),
+            ResizeHandle::Bottom => (0.0, 1.0),
+            ResizeHandle::TopLeft => (-1.0, -1.0),
+            ResizeHandle::TopRight => (1.0, -1.0),
+            ResizeHandle::BottomLeft => (-1.0, 1.0),
+            ResizeHandle::BottomRight => (1.0, 1.0),
+        }
+    }
+
+    fn cursor(self) -> CursorIcon {
+        match self {
+            ResizeHandle::Left | ResizeHandle::Right => CursorIcon::EwResize,
+            ResizeHandle::Top | ResizeHandle::Bottom => CursorIcon::NsResize,
+            ResizeHandle::TopLeft | ResizeHandle::BottomRight => CursorIcon::NwseResize,
+            ResizeHandle::TopRight | ResizeHandle::BottomLeft => CursorIcon::NeswResize,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ResizeHandle::Left => "left",
+            ResizeHandle::Right => "right",
+            ResizeHandle::Top => "top",
+            ResizeHandle::Bottom => "bottom",
+            ResizeHandle::TopLeft => "top_left",
+            ResizeHandle::TopRight => "top_right",
+            ResizeHandle::BottomLeft => "bottom_left",
+            ResizeHandle::BottomRight => "bottom_right",
+        }
+    }
+
+    fn place(self) -> Place {
+        let (h_align, v_align) = match self {
+            ResizeHandle::Left => (HorizontalAlign::Left, VerticalAlign::Center),
+            ResizeHandle::Right => (HorizontalAlign::Right, VerticalAlign::Center),
+            ResizeHandle::Top => (HorizontalAlign::Center, VerticalAlign::Top),
+            ResizeHandle::Bottom => (HorizontalAlign::Center, VerticalAlign::Bottom),
+            ResizeHandle::TopLeft => (HorizontalAlign::Left, VerticalAlign::Top),
+            ResizeHandle::TopRight => (HorizontalAlign::Right, VerticalAlign::Top),
+            ResizeHandle::BottomLeft => (HorizontalAlign::Left, VerticalAlign::Bottom),
+            ResizeHandle::BottomRight => (HorizontalAlign::Right, VerticalAlign::Bottom),
+        };
+        Place::Alignment { h_align, v_align }
+    }
+}
+
+/// Size change emitted while a handle is dragged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResizeEvent {
+    /// The handle being dragged
+    pub handle: ResizeHandle,
+    /// New width in logical pixels
+    pub width: f32,
+    /// New height in logical pixels
+    pub height: f32,
+    /// How far the top-left corner moved, in logical pixels
+    ///
+    /// Non-zero when dragging left or top handles. Apps positioning the node
+    /// themselves (floating windows, crop rects) add this to the position to
+    /// keep the opposite edge in place.
+    pub origin_delta: (f32, f32),
+}
+
+/// Wraps a node with draggable edge and corner resize handles
+///
+/// The size is owned by the app: pass the current size in and apply the
+/// sizes reported through `on_resize`. The wrapped content should use
+/// `Size::Fill` to follow the wrapper.
+///
+/// # Example
+///
+/// ```ignore
+/// Resizable::new(panel, self.panel_size.0, self.panel_size.1)
+///     .min_size(120.0, 80.0)
+///     .max_size(800.0, 600.0)
+///     .on_resize(|event| self.panel_size = (event.width, event.height))
+///     .node(&mut ctx)
+/// ```
+pub struct Resizable {
+    content: Node,
+    width: f32,
+    height: f32,
+    min_size: (f32, f32),
+    max_size: (f32, f32),
+    aspect_ratio: Option<f32>,
+    handles: Vec<ResizeHandle>,
+    disabled: bool,
+    style: Option<ResizableStyle>,
+    on_resize: Option<Box<dyn FnMut(ResizeEvent)>>,
+}
+
+impl Resizable {
+    /// Wrap `content` at the given size in logical pixels
+    pub fn new(content: Node, width: f32, height: f32) -> Self {
+        Self {
+            content,
+            width,
+            height,
+            min_size: (0.0, 0.0),
+            max_size: (f32::INFINITY, f32::INFINITY),
+            aspect_ratio: None,
+            handles: ResizeHandle::ALL.to_vec(),
+            disabled: false,
+            style: None,
+            on_resize: None,
+        }
+    }
+
+    /// Set the minimum size in logical pixels
+    pub fn min_size(mut self, width: f32, height: f32) -> Self {
+        self.min_size = (width.max(0.0), height.max(0.0));
+        self
+    }
+
+    /// Set the maximum size in logical pixels
+    pub fn max_size(mut self, width: f32, height: f32) -> Self {
+        self.max_size = (width, height);
+        self
+    }
+
+    /// Lock the aspect ratio (width / height) while resizing
+    pub fn aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = (ratio > 0.0).then_some(ratio);
+        self
+    }
+
+    /// Set which handles are shown (default: all edges and corners)
+    pub fn handles(mut self, handles: &[ResizeHandle]) -> Self {
+        self.handles = handles.to_vec();
+        self
+    }
+
+    /// Set whether resizing is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Set a custom style for the handles
+    pub fn with_style(mut self, style: ResizableStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the new size while a handle is dragged
+    pub fn on_resize(mut self, f: impl FnMut(ResizeEvent) + 'static) -> Self {
+        self.on_resize = Some(Box::new(f));
+        self
+    }
+
+    /// Size for a drag of `offset` logical pixels on `handle`, starting at `start`
+    fn constrained_size(
+        &self,
+        handle: ResizeHandle,
+        start: (f32, f32),
+        offset: (f32, f32),
+    ) -> (f32, f32) {
+        let (dir_x, dir_y) = handle.direction();
+        let mut width = start.0 + offset.0 * dir_x;
+        let mut height = start.1 + offset.1 * dir_y;
+        let (min_w, min_h) = self.min_size;
+        let (max_w, max_h) = self.max_size;
+
+        match self.aspect_ratio {
+            Some(ratio) => {
+                // Edges drive the other axis; corners follow the axis dragged further
+                let follow_width = match (dir_x != 0.0, dir_y != 0.0) {
+                    (true, false) => true,
+                    (false, true) => false,
+                    _ => (width - start.0).abs() >= ((height - start.1) * ratio).abs(),
+                };
+                if !follow_width {
+                    width = height * ratio;
+                }
+                let lower = min_w.max(min_h * ratio);
+                let upper = max_w.min(max_h * ratio).max(lower);
+                width = width.clamp(lower, upper);
+                height = width / ratio;
+            }
+            None => {
+                width = width.clamp(min_w, max_w.max(min_w));
+                height = height.clamp(min_h, max_h.max(min_h));
+            }
+        }
+        (width, height)
+    }
+
+    pub(crate) fn handle_node(
+        id: String,
+        handle: ResizeHandle,
+        style: &ResizableStyle,
+        dragging: bool,
+    ) -> Node {
+        let (width, height) = match handle {
+            ResizeHandle::Left | ResizeHandle::Right => {
+                (Size::lpx(style.handle_thickness), Size::Fill)
+            }
+            ResizeHandle::Top | ResizeHandle::Bottom => {
+                (Size::Fill, Size::lpx(style.handle_thickness))
+            }
+            _ => (Size::lpx(style.corner_size), Size::lpx(style.corner_size)),
+        };
+
+        Node::new()
+            .with_id(NodeId::new(id))
+            .with_width(width)
+            .with_height(height)
+            .with_place(handle.place())
+            .with_cursor(handle.cursor())
+            .with_style(Style {
+                fill_color: Some(if dragging {
+                    style.handle_active_color
+                } else {
+                    style.handle_color
+                }),
+                ..Default::default()
+            })
+            .with_hover_style(Style {
+                fill_color: Some(style.handle_hover_color),
+                ..Default::default()
+            })
+            .with_active_style(Style {
+                fill_color: Some(style.handle_active_color),
+                ..Default::default()
+            })
+            .with_transition(Transition::quick())
+    }
+}
+
+impl Component for Resizable {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = ctx.generate_id("resizable");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ResizableStyle::from_theme(ctx.theme()));
+        let scale = ctx.scale_factor();
+        let handle_prefix = format!("{}_handle_", id);
+
+        let state = *ctx.memory().resize(&id);
+        let mut start_size = state.start_size;
+        let mut drag_offset = state.drag_offset;
+        let mut dragging = state.dragging;
+        let mut dragged_handle = None;
+
+        if !self.disabled {
+            let events: Vec<_> = ctx.events().to_vec();
+            for event in &events {
+                let Some(handle) = event
+                    .target
+                    .as_str()
+                    .strip_prefix(&handle_prefix)
+                    .and_then(|name| ResizeHandle::ALL.into_iter().find(|h| h.name() == name))
+                else {
+                    continue;
+                };
+
+                match &event.event {
+                    InteractionEvent::DragStart { .. } => {
+                        start_size = (self.width, self.height);
+                        drag_offset = (0.0, 0.0);
+                        dragging = true;
+                    }
+                    InteractionEvent::DragMove { delta, .. } if dragging => {
+                        drag_offset.0 += delta.x / (scale * event.zoom);
+                        drag_offset.1 += delta.y / (scale * event.zoom);
+                        dragged_handle = Some(handle);
+                    }
+                    InteractionEvent::DragEnd { .. } => dragging = false,
+                    _ => {}
+                }
+            }
+        } else {
+            dragging = false;
+        }
+
+        if let Some(handle) = dragged_handle {
+            let (width, height) = self.constrained_size(handle, start_size, drag_offset);
+            if (width, height) != (self.width, self.height) {
+                let (dir_x, dir_y) = handle.direction();
+                let origin_delta = (
+                    if dir_x < 0.0 { self.width - width } else { 0.0 },
+                    if dir_y < 0.0 {
+                        self.height - height
+                    } else {
+                        0.0
+                    },
+                );
+                self.width = width;
+                self.height = height;
+                if let Some(ref mut on_resize) = self.on_resize {
+                    on_resize(ResizeEvent {
+                        handle,
+                        width,
+                        height,
+                        origin_delta,
+                    });
+                }
+            }
+        }
+
+        let state = ctx.memory().resize(&id);
+        state.start_size = start_size;
+        state.drag_offset = drag_offset;
+        state.dragging = dragging;
+
+        let mut node = Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(self.width))
+            .with_height(Size::lpx(self.height))
+            .with_layout_direction(Layout::Stack)
+            .with_child(self.content);
+
+        if !self.disabled {
+            // Corners come last so they sit above the edges they overlap
+            let mut handles = self.handles.clone();
+            handles.sort_by_key(|h| h.direction().0 != 0.0 && h.direction().1 != 0.0);
+            for handle in handles {
+                let handle_id = format!("{}{}", handle_prefix, handle.name());
+                let active = dragging && ctx.is_dragging(&handle_id);
+                node = node.with_child(Self::handle_node(handle_id, handle, &style, active));
+            }
+        }
+
+        node
+    }
+}
@@ -0,0 +1,390 @@
+//! Scrollbar component for interactive UI
+//!
+//! Draws a draggable thumb for an `Overflow::Scroll` container, sized and placed from its
+//! scroll offset and range. Clicking the track pages by one viewport, and the bar fades out
+//! after a period without scrolling, hovering or dragging. [`ScrollArea`] wraps a scroll
+//! container and overlays its scrollbars.
+
+use astra_gui::{
+    Color, Component, CornerShape, HorizontalAlign, Layout, Node, NodeId, Overflow, Place, Size,
+    Style, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::InteractionEvent;
+
+/// Visual styling for a scrollbar
+#[derive(Debug, Clone, WithBuilders)]
+pub struct ScrollbarStyle {
+    /// Thickness of the track and thumb
+    pub thickness: f32,
+    /// Shortest the thumb gets, however long the content
+    pub min_thumb_length: f32,
+    /// Color of the track behind the thumb
+    pub track_color: Color,
+    /// Color of the thumb
+    pub thumb_color: Color,
+    /// Color of the thumb when hovered
+    pub thumb_hover_color: Color,
+    /// Color of the thumb while dragged
+    pub thumb_active_color: Color,
+    /// Seconds without activity before an auto-hiding scrollbar fades out
+    pub auto_hide_delay: f32,
+}
+
+impl ScrollbarStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            thickness: 8.0,
+            min_thumb_length: 24.0,
+            track_color: theme.field.with_alpha(0.4),
+            thumb_color: theme.border,
+            thumb_hover_color: theme.text_muted,
+            thumb_active_color: theme.primary,
+            auto_hide_delay: 1.0,
+        }
+    }
+}
+
+impl Default for ScrollbarStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// The axis a [`Scrollbar`] scrolls along
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollbarAxis {
+    Vertical,
+    Horizontal,
+}
+
+impl ScrollbarAxis {
+    /// The component of a (horizontal, vertical) pair along this axis
+    fn of(self, pair: (f32, f32)) -> f32 {
+        match self {
+            ScrollbarAxis::Vertical => pair.1,
+            ScrollbarAxis::Horizontal => pair.0,
+        }
+    }
+
+    /// `pair` with the component along this axis replaced by `value`
+    fn with(self, pair: (f32, f32), value: f32) -> (f32, f32) {
+        match self {
+            ScrollbarAxis::Vertical => (pair.0, value),
+            ScrollbarAxis::Horizontal => (value, pair.1),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ScrollbarAxis::Vertical => "vertical",
+            ScrollbarAxis::Horizontal => "horizontal",
+        }
+    }
+}
+
+/// A scrollbar for the `Overflow::Scroll` node with a given ID
+///
+/// Reads the container's offset and range from last frame through
+/// `UiContext::scroll_metrics` and scrolls it with `UiContext::scroll_to`. The
+/// scrollbar spans its parent along its axis; place it over the container's
+/// edge (as [`ScrollArea`] does) so the track matches the viewport.
+///
+/// # Example
+///
+/// ```ignore
+/// Node::new()
+///     .with_layout_direction(Layout::Horizontal)
+///     .with_children(vec![
+///         list.with_id(NodeId::new("list")).with_overflow(Overflow::Scroll),
+///         Scrollbar::vertical("list").node(&mut ctx),
+///     ])
+/// ```
+pub struct Scrollbar {
+    target: String,
+    axis: ScrollbarAxis,
+    auto_hide: bool,
+    style: Option<ScrollbarStyle>,
+}
+
+impl Scrollbar {
+    /// Create a scrollbar along `axis` for the scroll container with ID `target`
+    pub fn new(target: impl Into<String>, axis: ScrollbarAxis) -> Self {
+        Self {
+            target: target.into(),
+            axis,
+            auto_hide: true,
+            style: None,
+        }
+    }
+
+    /// Create a vertical scrollbar for the scroll container with ID `target`
+    pub fn vertical(target: impl Into<String>) -> Self {
+        Self::new(target, ScrollbarAxis::Vertical)
+    }
+
+    /// Create a horizontal scrollbar for the scroll container with ID `target`
+    pub fn horizontal(target: impl Into<String>) -> Self {
+        Self::new(target, ScrollbarAxis::Horizontal)
+    }
+
+    /// Set whether the scrollbar fades out while idle (default: true)
+    pub fn auto_hide(mut self, auto_hide: bool) -> Self {
+        self.auto_hide = auto_hide;
+        self
+    }
+
+    /// Set a custom style for the scrollbar
+    pub fn with_style(mut self, style: ScrollbarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for Scrollbar {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let axis = self.axis;
+        let id = format!("{}_{}_scrollbar", self.target, axis.name());
+        let thumb_id = format!("{}_thumb", id);
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ScrollbarStyle::from_theme(ctx.theme()));
+        let scale = ctx.scale_factor();
+        let now = ctx.time();
+
+        // Everything below is in layout pixels, like the scroll metrics
+        let metrics = ctx.scroll_metrics(&self.target).unwrap_or_default();
+        let offset = axis.of(metrics.offset);
+        let max_scroll = axis.of(metrics.max_scroll);
+        let viewport = axis.of(metrics.viewport);
+        let thumb_fraction = if viewport > 0.0 {
+            axis.of(metrics.visible_fraction())
+                .max(style.min_thumb_length * scale / viewport)
+                .min(1.0)
+        } else {
+            1.0
+        };
+        let thumb_start = axis.of(metrics.progress()) * (1.0 - thumb_fraction);
+        let travel = viewport * (1.0 - thumb_fraction);
+
+        let mut state = *ctx.memory().scrollbar(&id);
+        let mut scroll_target = None;
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let base = scroll_target.unwrap_or(axis.of(metrics.target));
+            if event.target.as_str() == thumb_id {
+                match &event.event {
+                    InteractionEvent::DragStart { .. } => state.dragging = true,
+                    InteractionEvent::DragMove { delta, .. } if state.dragging && travel > 0.0 => {
+                        let moved = axis.of((delta.x, delta.y)) / event.zoom;
+                        scroll_target = Some(base + moved * max_scroll / travel);
+                    }
+                    InteractionEvent::DragEnd { .. } => state.dragging = false,
+                    _ => {}
+                }
+            } else if event.target.as_str() == id {
+                // Clicks on the track page toward the pointer
+                if let InteractionEvent::Click { .. } = event.event {
+                    let position = axis.of((event.local_position.x, event.local_position.y));
+                    if position < thumb_start * viewport {
+                        scroll_target = Some(base - viewport);
+                    } else if position > (thumb_start + thumb_fraction) * viewport {
+                        scroll_target = Some(base + viewport);
+                    }
+                }
+            }
+        }
+        if let Some(target) = scroll_target {
+            ctx.scroll_to(&self.target, axis.with(metrics.target, target));
+        }
+
+        let hovered = ctx.is_hovered(&id) || ctx.is_hovered(&thumb_id);
+        if hovered || state.dragging || offset != state.last_offset || scroll_target.is_some() {
+            state.last_activity = now;
+        }
+        state.last_offset = offset;
+        *ctx.memory().scrollbar(&id) = state;
+
+        let shown = max_scroll > 0.0
+            && (!self.auto_hide || now - state.last_activity < style.auto_hide_delay as f64);
+
+        let thickness = Size::lpx(style.thickness);
+        let along = |fraction: f32| Size::Relative(fraction);
+        let (width, height, layout) = match axis {
+            ScrollbarAxis::Vertical => (thickness, Size::Fill, Layout::Vertical),
+            ScrollbarAxis::Horizontal => (Size::Fill, thickness, Layout::Horizontal),
+        };
+        let sized = |node: Node, length: Size| match axis {
+            ScrollbarAxis::Vertical => node.with_width(Size::Fill).with_height(length),
+            ScrollbarAxis::Horizontal => node.with_width(length).with_height(Size::Fill),
+        };
+        let corner_shape = Some(CornerShape::Round(Size::lpx(style.thickness / 2.0)));
+
+        let thumb = sized(Node::new(), along(thumb_fraction))
+            .with_id(NodeId::new(&thumb_id))
+            .with_style(Style {
+                fill_color: Some(if state.dragging {
+                    style.thumb_active_color
+                } else {
+                    style.thumb_color
+                }),
+                corner_shape,
+                ..Default::default()
+            })
+            .with_hover_style(Style {
+                fill_color: Some(style.thumb_hover_color),
+                ..Default::default()
+            })
+            .with_active_style(Style {
+                fill_color: Some(style.thumb_active_color),
+                ..Default::default()
+            })
+            .with_transition(Transition::quick());
+
+        // Track: a spacer pushes the thumb to its position along the axis
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(width)
+            .with_height(height)
+            .with_layout_direction(layout)
+            .with_disabled(max_scroll <= 0.0)
+            .with_style(Style {
+                fill_color: Some(style.track_color),
+                corner_shape,
+                opacity: Some(if shown { 1.0 } else { 0.0 }),
+                ..Default::default()
+            })
+            .with_transition(Transition::quick())
+            .with_children(vec![sized(Node::new(), along(thumb_start)), thumb])
+    }
+}
+
+/// Wraps an `Overflow::Scroll` node and overlays scrollbars on its edges
+///
+/// The content fills the area and is made scrollable; it is given an ID if it
+/// has none. Each scrollbar only appears when the content overflows that axis.
+///
+/// # Example
+///
+/// ```ignore
+/// ScrollArea::new(list)
+///     .width(Size::lpx(400.0))
+///     .horizontal(false)
+///     .node(&mut ctx)
+/// ```
+pub struct ScrollArea {
+    content: Node,
+    width: Size,
+    height: Size,
+    vertical: bool,
+    horizontal: bool,
+    auto_hide: bool,
+    style: Option<ScrollbarStyle>,
+}
+
+impl ScrollArea {
+    /// Wrap `content`, which scrolls inside the area
+    pub fn new(content: Node) -> Self {
+        Self {
+            content,
+            width: Size::Fill,
+            height: Size::Fill,
+            vertical: true,
+            horizontal: true,
+            auto_hide: true,
+            style: None,
+        }
+    }
+
+    /// Set the width of the area (default: `Size::Fill`)
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height of the area (default: `Size::Fill`)
+    pub fn height(mut self, height: Size) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set whether to show a vertical scrollbar when the content overflows vertically
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Set whether to show a horizontal scrollbar when the content overflows horizontally
+    pub fn horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+
+    /// Set whether the scrollbars fade out while idle (default: true)
+    pub fn auto_hide(mut self, auto_hide: bool) -> Self {
+        self.auto_hide = auto_hide;
+        self
+    }
+
+    /// Set a custom style for the scrollbars
+    pub fn with_style(mut self, style: ScrollbarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for ScrollArea {
+    fn node(self, ctx: &mut UiContext) -> Node {
+        let id = ctx.generate_id("scroll_area");
+        let target = match self.content.id() {
+            Some(content_id) => content_id.as_str().to_string(),
+            None => format!("{}_content", id),
+        };
+        let style = self
+            .style
+            .unwrap_or_else(|| ScrollbarStyle::from_theme(ctx.theme()));
+
+        let mut node = Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(self.width)
+            .with_height(self.height)
+            .with_layout_direction(Layout::Stack)
+            .with_child(
+                self.content
+                    .with_id(NodeId::new(&target))
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_overflow(Overflow::Scroll),
+            );
+
+        let bars = [
+            (
+                self.vertical,
+                ScrollbarAxis::Vertical,
+                HorizontalAlign::Right,
+                VerticalAlign::Top,
+            ),
+            (
+                self.horizontal,
+                ScrollbarAxis::Horizontal,
+                HorizontalAlign::Left,
+                VerticalAlign::Bottom,
+            ),
+        ];
+        for (enabled, axis, h_align, v_align) in bars {
+            if enabled {
+                node = node.with_child(
+                    Scrollbar::new(&target, axis)
+                        .auto_hide(self.auto_hide)
+                        .with_style(style.clone())
+                        .node(ctx)
+                        .with_place(Place::Alignment { h_align, v_align }),
+                );
+            }
+        }
+
+        node
+    }
+}
@@ -0,0 +1,371 @@
+//! Search input component for interactive UI
+//!
+//! A text input with a popover of suggestions below it. The app filters the
+//! suggestions itself in a debounced callback, so expensive or remote searches
+//! run once typing pauses rather than on every keystroke. Suggestions are
+//! picked with the pointer or with the arrow keys and Enter.
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Place, Size, Spacing, Stroke, Style, TextContent, Theme, Translation, UiContext, VerticalAlign,
+    ZIndex,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::{Key, NamedKey};
+use std::time::Duration;
+
+use crate::{TextInput, TextInputStyle};
+
+/// Visual styling for a search input's suggestion popover
+#[derive(Debug, Clone, WithBuilders)]
+pub struct SearchInputStyle {
+    /// Background color of the popover
+    pub popover_color: Color,
+    /// Border color of the popover
+    pub popover_stroke_color: Color,
+    /// Background color of the highlighted suggestion
+    pub highlight_color: Color,
+    /// Suggestion text color
+    pub text_color: Color,
+    /// Suggestion font size
+    pub font_size: f32,
+    /// Padding around each suggestion
+    pub item_padding: Spacing,
+    /// Padding inside the popover
+    pub popover_padding: f32,
+    /// Space between the input and the popover
+    pub popover_offset: f32,
+    /// Corner radius of the popover and the highlight
+    pub border_radius: f32,
+}
+
+impl SearchInputStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            popover_color: theme.surface,
+            popover_stroke_color: theme.border,
+            highlight_color: theme.surface_hover,
+            text_color: theme.text,
+            font_size: 18.0,
+            item_padding: Spacing::symmetric(Size::lpx(10.0), Size::lpx(6.0)),
+            popover_padding: 4.0,
+            popover_offset: 4.0,
+            border_radius: 8.0,
+        }
+    }
+}
+
+impl Default for SearchInputStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// Called with the query, when it settles or is submitted
+type QueryFn<'a> = Box<dyn FnMut(&str) + 'a>;
+
+/// Called with the index of the chosen suggestion
+type SelectFn<'a> = Box<dyn FnMut(usize) + 'a>;
+
+/// A text input with filtered suggestions
+///
+/// The input does not filter anything itself: `on_filter` receives the query
+/// once it has stopped changing for the debounce time, and the app passes the
+/// matching suggestions back in on the next frame. Typing opens the popover;
+/// Up/Down move the highlight, Enter or a click picks the highlighted
+/// suggestion, and Escape or a press outside closes it. Picking a suggestion
+/// puts it in the input and calls `on_select` with its index.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut search = None;
+/// let node = SearchInput::new(&mut self.query, &self.matches)
+///     .placeholder("Search countries...")
+///     .on_filter(|query| search = Some(query.to_lowercase()))
+///     .node(&mut ctx);
+/// if let Some(query) = search {
+///     self.matches = countries().filter(|c| c.to_lowercase().contains(&query)).collect();
+/// }
+/// ```
+pub struct SearchInput<'a> {
+    value: &'a mut String,
+    suggestions: &'a [String],
+    placeholder: String,
+    debounce: Duration,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<SearchInputStyle>,
+    input_style: Option<TextInputStyle>,
+    on_filter: Option<QueryFn<'a>>,
+    on_select: Option<SelectFn<'a>>,
+    on_submit: Option<QueryFn<'a>>,
+}
+
+impl<'a> SearchInput<'a> {
+    /// Create a search input bound to the query, showing `suggestions`
+    pub fn new(value: &'a mut String, suggestions: &'a [String]) -> Self {
+        Self {
+            value,
+            suggestions,
+            placeholder: String::new(),
+            debounce: Duration::from_millis(200),
+            disabled: false,
+            id: None,
+            style: None,
+            input_style: None,
+            on_filter: None,
+            on_select: None,
+            on_submit: None,
+        }
+    }
+
+    /// Set the placeholder text shown when empty
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set how long the query must stay unchanged before `on_filter` runs
+    /// (default: 200 ms)
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set whether the search input is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the suggestion popover
+    pub fn with_style(mut self, style: SearchInputStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a custom style for the text input
+    pub fn input_style(mut self, style: TextInputStyle) -> Self {
+        self.input_style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called with the query once typing pauses
+    pub fn on_filter(mut self, f: impl FnMut(&str) + 'a) -> Self {
+        self.on_filter = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback to be called with the index of a picked suggestion
+    pub fn on_select(mut self, f: impl FnMut(usize) + 'a) -> Self {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback to be called when Enter is pressed without a
+    /// highlighted suggestion
+    pub fn on_submit(mut self, f: impl FnMut(&str) + 'a) -> Self {
+        self.on_submit = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for SearchInput<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("search_input"));
+        let input_id = format!("{}_input", id);
+        let suggestion_id = |index: usize| format!("{}_suggestion_{}", id, index);
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| SearchInputStyle::from_theme(ctx.theme()));
+        let input_style = self
+            .input_style
+            .take()
+            .unwrap_or_else(|| TextInputStyle::from_theme(ctx.theme()));
+        let count = self.suggestions.len();
+
+        let mut state = std::mem::take(ctx.memory().search_input(&id));
+        if state.highlighted.is_some_and(|index| index >= count) {
+            state.highlighted = None;
+        }
+
+        // The highlight follows the pointer onto a suggestion; clicks pick one
+        let mut chosen = None;
+        let mut hovered = None;
+        if state.open {
+            for index in 0..count {
+                if ctx.was_clicked(&suggestion_id(index)) {
+                    chosen = Some(index);
+                }
+                if ctx.is_hovered(&suggestion_id(index)) {
+                    hovered = Some(index);
+                }
+            }
+        }
+        if hovered.is_some() && hovered != state.hovered {
+            state.highlighted = hovered;
+        }
+        state.hovered = hovered;
+
+        let mut submitted = false;
+        if ctx.is_focused(&input_id) && !self.disabled {
+            for key in &ctx.input().keys_just_pressed {
+                match key {
+                    Key::Named(NamedKey::ArrowDown) if state.open && count > 0 => {
+                        state.highlighted =
+                            Some(state.highlighted.map_or(0, |index| (index + 1) % count));
+                    }
+                    Key::Named(NamedKey::ArrowDown) => state.open = true,
+                    Key::Named(NamedKey::ArrowUp) if state.open && count > 0 => {
+                        state.highlighted = Some(
+                            state
+                                .highlighted
+                                .map_or(count - 1, |index| (index + count - 1) % count),
+                        );
+                    }
+                    Key::Named(NamedKey::Enter) => match state.highlighted {
+                        Some(index) if state.open => chosen = Some(index),
+                        _ => submitted = true,
+                    },
+                    Key::Named(NamedKey::Escape) => state.open = false,
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(index) = chosen {
+            *self.value = self.suggestions[index].clone();
+            state.open = false;
+            state.highlighted = None;
+            state.pending = false;
+            // Back to typing, with the caret after the picked suggestion
+            let caret = ctx.memory().text_input(&input_id);
+            caret.cursor_pos = self.value.len();
+            caret.selection = None;
+            ctx.set_focus(Some(&input_id));
+            if let Some(ref mut on_select) = self.on_select {
+                on_select(index);
+            }
+        } else if submitted {
+            state.open = false;
+            if let Some(ref mut on_submit) = self.on_submit {
+                on_submit(self.value);
+            }
+        }
+
+        let before = self.value.clone();
+        let input = TextInput::new(&mut *self.value)
+            .with_id(&input_id)
+            .placeholder(self.placeholder)
+            .disabled(self.disabled)
+            .with_style(input_style.clone())
+            .build(ctx);
+
+        // Edits open the suggestions and restart the debounce
+        let now = ctx.time();
+        if *self.value != before {
+            state.open = true;
+            state.highlighted = None;
+            state.pending = true;
+            state.last_edit = now;
+        }
+        if state.pending && now - state.last_edit >= self.debounce.as_secs_f64() {
+            state.pending = false;
+            if let Some(ref mut on_filter) = self.on_filter {
+                on_filter(self.value);
+            }
+        }
+
+        if ctx.was_pressed_outside(&id) || self.disabled {
+            state.open = false;
+        }
+        let highlighted = state.highlighted;
+        let show = state.open && count > 0;
+        *ctx.memory().search_input(&id) = state;
+
+        let mut children = vec![input];
+        if show {
+            let rows = self
+                .suggestions
+                .iter()
+                .enumerate()
+                .map(|(index, suggestion)| {
+                    let fill = if highlighted == Some(index) {
+                        style.highlight_color
+                    } else {
+                        style.highlight_color.with_alpha(0.0)
+                    };
+                    Node::new()
+                        .with_id(NodeId::new(suggestion_id(index)))
+                        .with_width(Size::Fill)
+                        .with_padding(style.item_padding)
+                        .with_cursor(CursorIcon::Pointer)
+                        .with_content(Content::Text(
+                            TextContent::new(suggestion.clone())
+                                .with_font_size(Size::lpx(style.font_size))
+                                .with_color(style.text_color)
+                                .with_h_align(HorizontalAlign::Left)
+                                .with_v_align(VerticalAlign::Center),
+                        ))
+                        .with_style(Style {
+                            fill_color: Some(fill),
+                            corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                            ..Default::default()
+                        })
+                })
+                .collect();
+
+            // Zero-sized anchor at the input's bottom-left corner, so the
+            // popover overflows below it without affecting layout
+            children.push(
+                Node::new()
+                    .with_width(Size::lpx(0.0))
+                    .with_height(Size::lpx(0.0))
+                    .with_place(Place::Alignment {
+                        h_align: HorizontalAlign::Left,
+                        v_align: VerticalAlign::Bottom,
+                    })
+                    .with_z_index(ZIndex::OVERLAY)
+                    .with_child(
+                        Node::new()
+                            .with_width(Size::lpx(input_style.width))
+                            .with_padding(Spacing::all(Size::lpx(style.popover_padding)))
+                            .with_translation(Translation::new(
+                                Size::lpx(0.0),
+                                Size::lpx(style.popover_offset),
+                            ))
+                            .with_layout_direction(Layout::Vertical)
+                            .with_style(Style {
+                                fill_color: Some(style.popover_color),
+                                stroke: Some(Stroke::new(
+                                    Size::lpx(1.0),
+                                    style.popover_stroke_color,
+                                )),
+                                corner_shape: Some(CornerShape::Round(Size::lpx(
+                                    style.border_radius,
+                                ))),
+                                ..Default::default()
+                            })
+                            .with_children(rows),
+                    ),
+            );
+        }
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_layout_direction(Layout::Stack)
+            .with_children(children)
+    }
+}
@@ -3,8 +3,8 @@
 //! Provides a draggable slider for selecting values within a range.
 
 use astra_gui::{
-    catppuccin::mocha, Color, Component, CornerShape, Layout, Node, NodeId, Size, Style,
-    Transition, Translation, UiContext,
+    Color, Component, CornerShape, Layout, Node, NodeId, Size, Style, Theme, Transition,
+    Translation, UiContext,
 };
 use astra_gui_macros::WithBuilders;
 use astra_gui_wgpu::InteractionEvent;
@@ -31,14 +31,15 @@ pub struct SliderStyle {
     pub thumb_size: f32,
 }
 
-impl Default for SliderStyle {
-    fn default() -> Self {
+impl SliderStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
         Self {
-            track_color: mocha::SURFACE0,
-            filled_color: mocha::LAVENDER,
-            thumb_color: mocha::BASE,
-            thumb_hover_color: mocha::SURFACE0,
-            thumb_active_color: mocha::MAUVE.with_alpha(0.0),
+            track_color: theme.field,
+            filled_color: theme.primary,
+            thumb_color: theme.surface,
+            thumb_hover_color: theme.field,
+            thumb_active_color: theme.secondary.with_alpha(0.0),
             track_width: 200.0,
             track_height: 30.0,
             thumb_size: 26.0,
@@ -46,6 +47,12 @@ impl Default for SliderStyle {
     }
 }
 
+impl Default for SliderStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A slider component for selecting values within a range
 ///
 /// # Example
@@ -60,7 +67,7 @@ pub struct Slider {
     range: RangeInclusive<f32>,
     step: Option<f32>,
     disabled: bool,
-    style: SliderStyle,
+    style: Option<SliderStyle>,
     on_change: Option<Box<dyn FnMut(f32)>>,
 }
 
@@ -72,7 +79,7 @@ impl Slider {
             range,
             step: None,
             disabled: false,
-            style: SliderStyle::default(),
+            style: None,
             on_change: None,
         }
     }
@@ -91,7 +98,7 @@ impl Slider {
 
     /// Set a custom style for the slider
     pub fn with_style(mut self, style: SliderStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
@@ -102,10 +109,10 @@ impl Slider {
     }
 
     /// Calculate new value from local position
-    fn calculate_value_from_position(&self, local_x: f32, zoom: f32) -> f32 {
+    fn calculate_value_from_position(&self, style: &SliderStyle, local_x: f32, zoom: f32) -> f32 {
         let adjusted_x = local_x / zoom;
-        let usable_width = self.style.track_width - self.style.thumb_size;
-        let adjusted_x = (adjusted_x - self.style.thumb_size / 2.0).clamp(0.0, usable_width);
+        let usable_width = style.track_width - style.thumb_size;
+        let adjusted_x = (adjusted_x - style.thumb_size / 2.0).clamp(0.0, usable_width);
         let percentage = if usable_width > 0.0 {
             (adjusted_x / usable_width).clamp(0.0, 1.0)
         } else {
@@ -139,10 +146,16 @@ impl Component for Slider {
     fn node(mut self, ctx: &mut UiContext) -> Node {
         // Generate unique ID for the slider hitbox
         let id = ctx.generate_id("slider");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| SliderStyle::from_theme(ctx.theme()));
+        let theme = *ctx.theme();
         let hitbox_id = format!("{}_hitbox", id);
 
         // Check for drag events from last frame and fire callback
         if !self.disabled {
+            let mut drag_started = false;
             for event in ctx.events() {
                 if event.target.as_str() != hitbox_id {
                     continue;
@@ -152,18 +165,27 @@ impl Component for Slider {
                     InteractionEvent::Click { .. }
                     | InteractionEvent::DragStart { .. }
                     | InteractionEvent::DragMove { .. } => {
-                        let new_value =
-                            self.calculate_value_from_position(event.local_position.x, event.zoom);
+                        let new_value = self.calculate_value_from_position(
+                            &style,
+                            event.local_position.x,
+                            event.zoom,
+                        );
 
                         if (self.value - new_value).abs() > f32::EPSILON {
                             if let Some(ref mut on_change) = self.on_change {
                                 on_change(new_value);
                             }
                         }
+                        drag_started |= matches!(event.event, InteractionEvent::DragStart { .. });
                     }
                     _ => {}
                 }
             }
+
+            // Keep the drag going wherever the pointer goes until it is released
+            if drag_started {
+                ctx.capture_pointer(&hitbox_id);
+            }
         }
 
         // Calculate percentage (0.0 to 1.0)
@@ -175,33 +197,29 @@ impl Component for Slider {
         };
 
         // Calculate thumb position
-        let thumb_inset = (self.style.track_height - self.style.thumb_size) / 2.0;
-        let usable_width = self.style.track_width
-            - self.style.thumb_size
-            - (self.style.track_height - self.style.thumb_size) * 2.0;
-        let thumb_offset_x = (usable_width - (self.style.thumb_size - self.style.track_height))
-            * percentage
-            + thumb_inset;
+        let thumb_inset = (style.track_height - style.thumb_size) / 2.0;
+        let usable_width =
+            style.track_width - style.thumb_size - (style.track_height - style.thumb_size) * 2.0;
+        let thumb_offset_x =
+            (usable_width - (style.thumb_size - style.track_height)) * percentage + thumb_inset;
 
         // Calculate filled width
-        let filled_width = thumb_offset_x + self.style.track_height - thumb_inset;
+        let filled_width = thumb_offset_x + style.track_height - thumb_inset;
 
         // Create the slider node
         Node::new()
-            .with_width(Size::lpx(self.style.track_width))
-            .with_height(Size::lpx(
-                self.style.thumb_size.max(self.style.track_height),
-            ))
+            .with_width(Size::lpx(style.track_width))
+            .with_height(Size::lpx(style.thumb_size.max(style.track_height)))
             .with_layout_direction(Layout::Stack)
             .with_children(vec![
                 // Track background (unfilled)
                 Node::new()
-                    .with_width(Size::lpx(self.style.track_width))
-                    .with_height(Size::lpx(self.style.track_height))
+                    .with_width(Size::lpx(style.track_width))
+                    .with_height(Size::lpx(style.track_height))
                     .with_style(Style {
-                        fill_color: Some(self.style.track_color),
+                        fill_color: Some(style.track_color),
                         corner_shape: Some(CornerShape::Round(astra_gui::Size::Logical(
-                            self.style.track_height / 2.0,
+                            style.track_height / 2.0,
                         ))),
                         ..Default::default()
                     })
@@ -210,42 +228,42 @@ impl Component for Slider {
                 // Filled portion of track
                 Node::new()
                     .with_width(Size::lpx(filled_width))
-                    .with_height(Size::lpx(self.style.track_height))
+                    .with_height(Size::lpx(style.track_height))
                     .with_style(Style {
-                        fill_color: Some(self.style.filled_color),
+                        fill_color: Some(style.filled_color),
                         corner_shape: Some(CornerShape::Round(astra_gui::Size::Logical(
-                            self.style.track_height / 2.0,
+                            style.track_height / 2.0,
                         ))),
                         ..Default::default()
                     })
                     .with_disabled_style(Style {
-                        fill_color: Some(mocha::SURFACE1),
+                        fill_color: Some(theme.field_hover),
                         ..Default::default()
                     })
                     .with_disabled(self.disabled)
                     .with_transition(Transition::quick()),
                 // Thumb
                 Node::new()
-                    .with_width(Size::lpx(self.style.thumb_size))
-                    .with_height(Size::lpx(self.style.thumb_size))
+                    .with_width(Size::lpx(style.thumb_size))
+                    .with_height(Size::lpx(style.thumb_size))
                     .with_translation(Translation::new(
                         astra_gui::Size::Logical(thumb_offset_x),
                         astra_gui::Size::Logical(thumb_inset),
                     ))
                     .with_style(Style {
-                        fill_color: Some(self.style.thumb_color),
+                        fill_color: Some(style.thumb_color),
                         opacity: Some(1.0),
                         corner_shape: Some(CornerShape::Round(astra_gui::Size::Logical(
-                            self.style.thumb_size / 2.0,
+                            style.thumb_size / 2.0,
                         ))),
                         ..Default::default()
                     })
                     .with_hover_style(Style {
-                        fill_color: Some(self.style.thumb_hover_color),
+                        fill_color: Some(style.thumb_hover_color),
                         ..Default::default()
                     })
                     .with_active_style(Style {
-                        fill_color: Some(self.style.thumb_active_color),
+                        fill_color: Some(style.thumb_active_color),
                         ..Default::default()
                     })
                     .with_disabled_style(Style {
@@ -29,8 +29,8 @@ pub struct SliderWithValue<'a> {
     speed: f32,
     gap: f32,
     disabled: bool,
-    slider_style: SliderStyle,
-    value_style: DragValueStyle,
+    slider_style: Option<SliderStyle>,
+    value_style: Option<DragValueStyle>,
     on_change: Option<Box<dyn FnMut(f32) + 'a>>,
 }
 
@@ -48,8 +48,8 @@ impl<'a> SliderWithValue<'a> {
             speed: 0.1,
             gap: 8.0,
             disabled: false,
-            slider_style: SliderStyle::default(),
-            value_style: DragValueStyle::default(),
+            slider_style: None,
+            value_style: None,
             on_change: None,
         }
     }
@@ -80,20 +80,20 @@ impl<'a> SliderWithValue<'a> {
 
     /// Set a custom style for the slider
     pub fn with_slider_style(mut self, style: SliderStyle) -> Self {
-        self.slider_style = style;
+        self.slider_style = Some(style);
         self
     }
 
     /// Set a custom style for the drag value
     pub fn with_value_style(mut self, style: DragValueStyle) -> Self {
-        self.value_style = style;
+        self.value_style = Some(style);
         self
     }
 
     /// Set both slider and drag value styles at once
     pub fn with_styles(mut self, slider_style: SliderStyle, value_style: DragValueStyle) -> Self {
-        self.slider_style = slider_style;
-        self.value_style = value_style;
+        self.slider_style = Some(slider_style);
+        self.value_style = Some(value_style);
         self
     }
 
@@ -112,9 +112,11 @@ impl<'a> SliderWithValue<'a> {
         let original_value = *self.value;
 
         // Build slider component
-        let mut slider_builder = Slider::new(*self.value, self.range.clone())
-            .disabled(self.disabled)
-            .with_style(self.slider_style);
+        let mut slider_builder =
+            Slider::new(*self.value, self.range.clone()).disabled(self.disabled);
+        if let Some(style) = self.slider_style.take() {
+            slider_builder = slider_builder.with_style(style);
+        }
 
         if let Some(step) = self.step {
             slider_builder = slider_builder.step(step);
@@ -135,8 +137,10 @@ impl<'a> SliderWithValue<'a> {
         let mut drag_value_builder = DragValue::new(self.value)
             .range(self.range)
             .speed(self.speed)
-            .disabled(self.disabled)
-            .with_style(self.value_style);
+            .disabled(self.disabled);
+        if let Some(style) = self.value_style.take() {
+            drag_value_builder = drag_value_builder.with_style(style);
+        }
 
         if let Some(step) = self.step {
             drag_value_builder = drag_value_builder.step(step);
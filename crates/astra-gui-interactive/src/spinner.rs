@@ -0,0 +1,143 @@
+//! Spinner component for interactive UI
+//!
+//! A ring of dots fading out behind the leading one, rotating continuously.
+//! The rotation is a looping style animation, so the app keeps redrawing for
+//! as long as a spinner is shown and stops when it is removed.
+
+use std::f32::consts::TAU;
+
+use astra_gui::{
+    AnimatedProperty, Animation, Color, Component, CornerShape, Layout, Node, Size, Style, Theme,
+    Translation, UiContext,
+};
+use astra_gui_macros::WithBuilders;
+
+/// Visual styling for a spinner
+#[derive(Debug, Clone, WithBuilders)]
+pub struct SpinnerStyle {
+    /// Color of the leading dot; trailing dots fade out from it
+    pub color: Color,
+    /// Outer diameter of the spinner
+    pub size: f32,
+    /// Number of dots on the ring
+    pub dot_count: usize,
+    /// Diameter of each dot
+    pub dot_size: f32,
+    /// Opacity of the last trailing dot
+    pub min_opacity: f32,
+    /// Seconds per revolution
+    pub period: f32,
+}
+
+impl SpinnerStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            color: theme.primary,
+            size: 32.0,
+            dot_count: 8,
+            dot_size: 6.0,
+            min_opacity: 0.15,
+            period: 1.0,
+        }
+    }
+}
+
+impl Default for SpinnerStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A continuously rotating loading indicator
+///
+/// Takes up a fixed square, so it can stand in for content that is still
+/// loading and be swapped out once it arrives.
+///
+/// # Example
+///
+/// ```ignore
+/// match &state.thumbnail {
+///     Some(image) => image_node(image),
+///     None => Spinner::new().size(48.0).node(&mut ctx),
+/// }
+/// ```
+pub struct Spinner {
+    size: Option<f32>,
+    style: Option<SpinnerStyle>,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinner {
+    /// Create a spinner with the theme's default style
+    pub fn new() -> Self {
+        Self {
+            size: None,
+            style: None,
+        }
+    }
+
+    /// Set the outer diameter, scaling the dots along with it
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set a custom style for the spinner
+    pub fn with_style(mut self, style: SpinnerStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Component for Spinner {
+    fn node(self, ctx: &mut UiContext) -> Node {
+        let mut style = self
+            .style
+            .unwrap_or_else(|| SpinnerStyle::from_theme(ctx.theme()));
+        if let Some(size) = self.size {
+            style.dot_size *= size / style.size;
+            style.size = size;
+        }
+
+        // Dots clockwise from the top; each trails the one after it, so the
+        // last dot leads the rotation at full opacity
+        let count = style.dot_count.max(1);
+        let radius = (style.size - style.dot_size) / 2.0;
+        let dots = (0..count).map(|index| {
+            let fraction = (index + 1) as f32 / count as f32;
+            let angle = fraction * TAU;
+            Node::new()
+                .with_width(Size::lpx(style.dot_size))
+                .with_height(Size::lpx(style.dot_size))
+                .with_translation(Translation::new(
+                    Size::lpx(radius * (1.0 + angle.sin())),
+                    Size::lpx(radius * (1.0 - angle.cos())),
+                ))
+                .with_opacity(style.min_opacity + (1.0 - style.min_opacity) * fraction)
+                .with_style(Style {
+                    fill_color: Some(style.color),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(style.dot_size / 2.0))),
+                    ..Default::default()
+                })
+        });
+
+        // No generated ID: spinners come and go with loading state, which must
+        // not shift the IDs of widgets built after them. The animation is
+        // sampled from the frame clock, so it needs no per-node state.
+        Node::new()
+            .with_width(Size::lpx(style.size))
+            .with_height(Size::lpx(style.size))
+            .with_layout_direction(Layout::Stack)
+            .with_style(Style::animated(Animation::new(
+                AnimatedProperty::Rotation(0.0, TAU),
+                style.period,
+            )))
+            .with_children(dots.collect())
+    }
+}
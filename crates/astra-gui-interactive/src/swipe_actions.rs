@@ -0,0 +1,298 @@
+//! Swipe actions component for interactive UI
+//!
+//! Provides swipe-to-reveal action buttons for list rows, as found on touch platforms.
+//! Swiping a row horizontally reveals leading (left) or trailing (right) actions, snapping
+//! open or closed on release. Swiping past the full-swipe distance triggers the outermost
+//! action directly.
+
+use astra_gui::{
+    Color, Component, Content, HorizontalAlign, Layout, Node, NodeId, Overflow, Size, Style,
+    TextContent, Theme, Transition, UiContext, VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::InteractionEvent;
+
+/// Visual styling for swipe actions
+#[derive(Debug, Clone, WithBuilders)]
+pub struct SwipeActionsStyle {
+    /// Width of each revealed action button
+    pub action_width: f32,
+    /// Color of the action labels
+    pub text_color: Color,
+    /// Font size of the action labels
+    pub font_size: f32,
+    /// Background color of the row content, hiding the actions while closed
+    pub row_color: Color,
+    /// Swipe distance past which the outermost action is triggered on release
+    pub full_swipe_distance: f32,
+}
+
+impl SwipeActionsStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            action_width: 80.0,
+            text_color: theme.on_primary,
+            font_size: 16.0,
+            row_color: theme.surface,
+            full_swipe_distance: 220.0,
+        }
+    }
+}
+
+impl Default for SwipeActionsStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A single action revealed by swiping a row
+pub struct SwipeAction {
+    label: String,
+    color: Color,
+    on_trigger: Option<Box<dyn FnMut()>>,
+}
+
+impl SwipeAction {
+    /// Create a new action with the given label and background color
+    pub fn new(label: impl Into<String>, color: Color) -> Self {
+        Self {
+            label: label.into(),
+            color,
+            on_trigger: None,
+        }
+    }
+
+    /// Set a callback to be called when the action is clicked or fully swiped
+    pub fn on_trigger(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_trigger = Some(Box::new(f));
+        self
+    }
+
+    fn trigger(&mut self) {
+        if let Some(ref mut on_trigger) = self.on_trigger {
+            on_trigger();
+        }
+    }
+}
+
+/// Wraps a list row with swipe-to-reveal actions
+///
+/// Any node can be used as the row, so this works for plain lists as well as
+/// rows produced by virtualized lists. The open/closed state is kept in widget
+/// memory, keyed by the generated id.
+///
+/// # Example
+///
+/// ```ignore
+/// SwipeActions::new(row)
+///     .leading(vec![SwipeAction::new("Pin", mocha::YELLOW).on_trigger(|| pin())])
+///     .trailing(vec![
+///         SwipeAction::new("Archive", mocha::BLUE).on_trigger(|| archive()),
+///         SwipeAction::new("Delete", mocha::RED).on_trigger(|| delete()),
+///     ])
+///     .node(&mut ctx)
+/// ```
+pub struct SwipeActions {
+    content: Node,
+    leading: Vec<SwipeAction>,
+    trailing: Vec<SwipeAction>,
+    style: Option<SwipeActionsStyle>,
+}
+
+impl SwipeActions {
+    /// Create new swipe actions around the given row content
+    pub fn new(content: Node) -> Self {
+        Self {
+            content,
+            leading: Vec::new(),
+            trailing: Vec::new(),
+            style: None,
+        }
+    }
+
+    /// Set the actions revealed by swiping right, ordered from the leading edge inwards
+    pub fn leading(mut self, actions: Vec<SwipeAction>) -> Self {
+        self.leading = actions;
+        self
+    }
+
+    /// Set the actions revealed by swiping left, ordered from the inside outwards
+    ///
+    /// The last action is the one triggered by a full swipe.
+    pub fn trailing(mut self, actions: Vec<SwipeAction>) -> Self {
+        self.trailing = actions;
+        self
+    }
+
+    /// Set a custom style for the swipe actions
+    pub fn with_style(mut self, style: SwipeActionsStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    fn action_node(id: String, action: &SwipeAction, style: &SwipeActionsStyle) -> Node {
+        Node::new()
+            .with_id(NodeId::new(id))
+            .with_width(Size::lpx(style.action_width))
+            .with_height(Size::Fill)
+            .with_content(Content::Text(
+                TextContent::new(action.label.clone())
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(style.text_color)
+                    .with_h_align(HorizontalAlign::Center)
+                    .with_v_align(VerticalAlign::Center),
+            ))
+            .with_style(Style {
+                fill_color: Some(action.color),
+                ..Default::default()
+            })
+            .with_hover_style(Style {
+                opacity: Some(0.9),
+                ..Default::default()
+            })
+            .with_active_style(Style {
+                opacity: Some(0.7),
+                ..Default::default()
+            })
+            .with_transition(Transition::quick())
+    }
+}
+
+impl Component for SwipeActions {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = ctx.generate_id("swipe");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| SwipeActionsStyle::from_theme(ctx.theme()));
+        let row_id = format!("{}_row", id);
+
+        let scale = ctx.scale_factor();
+        let leading_width = self.leading.len() as f32 * style.action_width;
+        let trailing_width = self.trailing.len() as f32 * style.action_width;
+        // Allow dragging past the revealed actions up to the full-swipe distance
+        let max_offset = if self.leading.is_empty() {
+            0.0
+        } else {
+            style.full_swipe_distance.max(leading_width)
+        };
+        let min_offset = if self.trailing.is_empty() {
+            0.0
+        } else {
+            -style.full_swipe_distance.max(trailing_width)
+        };
+
+        let state = ctx.memory().swipe(&id);
+        let mut offset = state.offset;
+        let mut dragging = state.dragging;
+
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let target_str = event.target.as_str();
+
+            if target_str == row_id {
+                match &event.event {
+                    InteractionEvent::DragStart { .. } => dragging = true,
+                    InteractionEvent::DragMove { delta, .. } => {
+                        offset =
+                            (offset + delta.x / (scale * event.zoom)).clamp(min_offset, max_offset);
+                    }
+                    InteractionEvent::DragEnd { .. } => {
+                        dragging = false;
+                        if offset >= style.full_swipe_distance {
+                            if let Some(action) = self.leading.first_mut() {
+                                action.trigger();
+                            }
+                            offset = 0.0;
+                        } else if offset <= -style.full_swipe_distance {
+                            if let Some(action) = self.trailing.last_mut() {
+                                action.trigger();
+                            }
+                            offset = 0.0;
+                        } else {
+                            // Snap to the nearest of closed, leading open, or trailing open
+                            offset = [0.0, leading_width, -trailing_width]
+                                .into_iter()
+                                .min_by(|a, b| (offset - a).abs().total_cmp(&(offset - b).abs()))
+                                .unwrap_or(0.0);
+                        }
+                    }
+                    InteractionEvent::Click { .. } if !dragging => offset = 0.0,
+                    _ => {}
+                }
+            } else if let Some(index) = target_str
+                .strip_prefix(&id)
+                .and_then(|rest| rest.strip_prefix("_leading_"))
+                .and_then(|index| index.parse::<usize>().ok())
+            {
+                if matches!(event.event, InteractionEvent::Click { .. }) {
+                    if let Some(action) = self.leading.get_mut(index) {
+                        action.trigger();
+                    }
+                    offset = 0.0;
+                }
+            } else if let Some(index) = target_str
+                .strip_prefix(&id)
+                .and_then(|rest| rest.strip_prefix("_trailing_"))
+                .and_then(|index| index.parse::<usize>().ok())
+            {
+                if matches!(event.event, InteractionEvent::Click { .. }) {
+                    if let Some(action) = self.trailing.get_mut(index) {
+                        action.trigger();
+                    }
+                    offset = 0.0;
+                }
+            }
+        }
+
+        let state = ctx.memory().swipe(&id);
+        state.offset = offset;
+        state.dragging = dragging;
+
+        // Actions layer, revealed behind the row as it slides away
+        let mut actions = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Horizontal);
+        for (i, action) in self.leading.iter().enumerate() {
+            actions = actions.with_child(Self::action_node(
+                format!("{}_leading_{}", id, i),
+                action,
+                &style,
+            ));
+        }
+        actions = actions.with_child(Node::new().with_width(Size::Fill));
+        for (i, action) in self.trailing.iter().enumerate() {
+            actions = actions.with_child(Self::action_node(
+                format!("{}_trailing_{}", id, i),
+                action,
+                &style,
+            ));
+        }
+
+        let row = Node::new()
+            .with_id(NodeId::new(&row_id))
+            .with_width(Size::Fill)
+            .with_style(Style {
+                fill_color: Some(style.row_color),
+                translation_x: Some(Size::Logical(offset)),
+                ..Default::default()
+            })
+            // Follow the pointer exactly while dragging, animate when snapping
+            .with_transition(if dragging {
+                Transition::instant()
+            } else {
+                Transition::quick()
+            })
+            .with_child(self.content);
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::Fill)
+            .with_layout_direction(Layout::Stack)
+            .with_overflow(Overflow::Hidden)
+            .with_child(actions)
+            .with_child(row)
+    }
+}
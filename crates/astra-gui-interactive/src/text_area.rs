@@ -0,0 +1,480 @@
+//! Multi-line text area component for interactive UI
+//!
+//! An editable text field for longer text, built on [`TextEditor`]. Lines wrap
+//! at word boundaries to fit the field, and the field scrolls vertically to
+//! keep the caret in view. The mouse places the caret and drags out
+//! selections; Ctrl+C, Ctrl+X and Ctrl+V copy, cut and paste through the
+//! `UiContext` clipboard.
+//!
+//! [`TextEditor`]: astra_gui::TextEditor
+
+use crate::{ScrollArea, ScrollbarStyle};
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Overflow, Point, Selection, Size, Spacing, Stroke, Style, TextContent, TextEditStyle, Theme,
+    Transition, UiContext, VerticalAlign, Wrap,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::{InteractionEvent, Key, MouseButton, NamedKey};
+use std::time::Duration;
+
+/// Visual styling for a text area
+#[derive(Debug, Clone, WithBuilders)]
+pub struct TextAreaStyle {
+    /// Background color when idle
+    pub idle_color: Color,
+    /// Background color when focused
+    pub focused_color: Color,
+    /// Background color when disabled
+    pub disabled_color: Color,
+
+    /// Stroke color when idle
+    pub idle_stroke_color: Color,
+    /// Stroke color when focused
+    pub focused_stroke_color: Color,
+    /// Stroke color when disabled
+    pub disabled_stroke_color: Color,
+
+    // Stroke width
+    pub idle_stroke_width: f32,
+    pub focused_stroke_width: f32,
+
+    /// Text color
+    pub text_color: Color,
+    /// Placeholder text color
+    pub placeholder_text_color: Color,
+    /// Disabled text color
+    pub disabled_text_color: Color,
+
+    /// Caret color
+    pub caret_color: Color,
+    /// Selection highlight color
+    pub selection_color: Color,
+    /// Time between caret blinks
+    pub blink_interval: Duration,
+
+    /// Internal padding around the text
+    pub padding: Spacing,
+    /// Corner radius for rounded corners
+    pub border_radius: f32,
+    /// Font size
+    pub font_size: f32,
+    /// Line height as a multiple of the font size
+    pub line_height_multiplier: f32,
+    /// Width of the text area
+    pub width: f32,
+    /// Height of the text area; longer text scrolls
+    pub height: f32,
+    /// Style of the vertical scrollbar
+    pub scrollbar: ScrollbarStyle,
+}
+
+impl TextAreaStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            // Fill Colors
+            idle_color: theme.field,
+            focused_color: theme.field_hover,
+            disabled_color: theme.field.with_alpha(0.8),
+            // Stroke Colors
+            idle_stroke_color: theme.primary,
+            focused_stroke_color: theme.primary,
+            disabled_stroke_color: theme.field_active,
+            // Stroke Width
+            idle_stroke_width: 2.0,
+            focused_stroke_width: 3.0,
+            // Text Colors
+            text_color: theme.text,
+            placeholder_text_color: theme.text_muted,
+            disabled_text_color: theme.text_muted,
+            // Editing
+            caret_color: theme.text,
+            selection_color: theme.primary.with_alpha(0.3),
+            blink_interval: Duration::from_millis(530),
+            // Other
+            padding: Spacing::symmetric(Size::lpx(10.0), Size::lpx(8.0)),
+            border_radius: 8.0,
+            font_size: 18.0,
+            line_height_multiplier: 1.3,
+            width: 400.0,
+            height: 160.0,
+            scrollbar: ScrollbarStyle::from_theme(theme),
+        }
+    }
+}
+
+impl Default for TextAreaStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// Called with the new text after each edit
+type ChangeFn<'a> = Box<dyn FnMut(&str) + 'a>;
+
+/// A multi-line text field
+///
+/// Enter inserts a newline. Text, caret and selection are kept in widget
+/// memory between frames; changing the bound string from outside replaces the
+/// text and clears the undo history.
+///
+/// # Example
+///
+/// ```ignore
+/// TextArea::new(&mut notes)
+///     .placeholder("Notes...")
+///     .on_change(|text| println!("{} chars", text.chars().count()))
+///     .node(&mut ctx)
+/// ```
+pub struct TextArea<'a> {
+    value: &'a mut String,
+    placeholder: String,
+    disabled: bool,
+    id: Option<String>,
+    style: Option<TextAreaStyle>,
+    on_change: Option<ChangeFn<'a>>,
+}
+
+impl<'a> TextArea<'a> {
+    /// Create a new text area bound to a mutable string reference
+    pub fn new(value: &'a mut String) -> Self {
+        Self {
+            value,
+            placeholder: String::new(),
+            disabled: false,
+            id: None,
+            style: None,
+            on_change: None,
+        }
+    }
+
+    /// Set the placeholder text shown when empty
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set whether the text area is disabled
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    ///
+    /// The editor state is stored under this ID, so a stable ID keeps the
+    /// caret and undo history when the widgets built before it change.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the text area
+    pub fn with_style(mut self, style: TextAreaStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set a callback to be called when the text changes
+    pub fn on_change(mut self, f: impl FnMut(&str) + 'a) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+impl Component for TextArea<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("text_area"));
+        let scroll_id = format!("{}_scroll", id);
+        let text_id = format!("{}_text", id);
+        let mut style = self
+            .style
+            .take()
+            .unwrap_or_else(|| TextAreaStyle::from_theme(ctx.theme()));
+        // Wrapping and caret positions follow the font size, so the text scale
+        // is applied here rather than by layout (the node opts out below)
+        style.font_size *= ctx.text_scale();
+
+        let (pad_left, pad_top) = (style.padding.get_left(), style.padding.get_top());
+        let text_height = (style.height - style.padding.get_vertical()).max(0.0);
+        let edit_style = TextEditStyle {
+            font_size: style.font_size,
+            line_height_multiplier: style.line_height_multiplier,
+            caret_color: style.caret_color,
+            selection_color: style.selection_color,
+            wrap_width: Some((style.width - style.padding.get_horizontal()).max(0.0)),
+            ..TextEditStyle::from_theme(ctx.theme())
+        };
+
+        let mut state = std::mem::take(ctx.memory().text_area(&id));
+        if *self.value != state.synced {
+            state.editor.set_text(self.value);
+            state.synced.clone_from(self.value);
+        }
+        let selection_before = state.editor.selection();
+
+        // Scroll offsets are in layout pixels; the viewport's known logical
+        // height gives the conversion
+        let metrics = ctx.scroll_metrics(&scroll_id).unwrap_or_default();
+        let scroll = metrics.offset.1;
+        let layout_scale = if metrics.viewport.1 > 0.0 && style.height > 0.0 {
+            metrics.viewport.1 / style.height
+        } else {
+            ctx.scale_factor()
+        };
+
+        // Clicks place the caret and drags extend the selection from it
+        let mut pressed = false;
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let target = event.target.as_str();
+            if self.disabled || (target != id && target != scroll_id && target != text_id) {
+                continue;
+            }
+            let extend = match event.event {
+                InteractionEvent::Click {
+                    button: MouseButton::Left,
+                    ..
+                } => false,
+                InteractionEvent::DragStart {
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    state.drag_scroll = Some(scroll);
+                    false
+                }
+                InteractionEvent::DragMove { .. } if state.drag_scroll.is_some() => true,
+                InteractionEvent::DragEnd { .. } => {
+                    state.drag_scroll = None;
+                    continue;
+                }
+                _ => continue,
+            };
+            pressed |= !extend;
+
+            // Position in the text block. The scroll container's own position
+            // is unscrolled; the text node's drag origin was fixed when the
+            // drag started, so it only misses scrolling since then.
+            let scrolled = if target != text_id {
+                scroll
+            } else if let InteractionEvent::DragMove { .. } = event.event {
+                scroll - state.drag_scroll.unwrap_or(scroll)
+            } else {
+                0.0
+            };
+            let local = event.local_position;
+            let x = local.x / event.zoom - pad_left;
+            let y = (local.y + scrolled) / event.zoom - pad_top;
+            if let Some(measurer) = ctx.measurer() {
+                let index = state
+                    .editor
+                    .caret_index_at(measurer, &edit_style, Point { x, y });
+                let anchor = if extend {
+                    state.editor.selection().anchor
+                } else {
+                    index
+                };
+                state.editor.set_selection(Selection::new(anchor, index));
+            }
+        }
+
+        if pressed {
+            ctx.set_focus(Some(&id));
+        }
+
+        // Unfocus on a press elsewhere or Escape
+        let input = ctx.input().clone();
+        let escape_pressed = input
+            .keys_just_pressed
+            .iter()
+            .any(|key| matches!(key, Key::Named(NamedKey::Escape)));
+        if ctx.is_focused(&id) && !pressed && (ctx.was_pressed_outside(&id) || escape_pressed) {
+            ctx.set_focus(None);
+        }
+        let focused = ctx.is_focused(&id) && !self.disabled;
+
+        let mut changed = false;
+        if focused {
+            let response = match ctx.measurer() {
+                Some(measurer) => state
+                    .editor
+                    .handle_input_wrapped(&input, measurer, &edit_style),
+                None => state.editor.handle_input(&input),
+            };
+            changed |= response.changed;
+
+            // Clipboard shortcuts, after Ctrl+A so select-all and copy can
+            // share a frame
+            let editor = &mut state.editor;
+            let shortcuts = input.keys_just_pressed.iter().filter_map(|key| match key {
+                Key::Character(ch) if input.ctrl_held => Some(ch.to_lowercase()),
+                _ => None,
+            });
+            for shortcut in shortcuts {
+                match shortcut.as_str() {
+                    "c" | "x" if !editor.selection().is_empty() => {
+                        ctx.copy_to_clipboard(editor.selected_text());
+                        if shortcut == "x" {
+                            changed |= editor.delete(false);
+                        }
+                    }
+                    "v" if !ctx.clipboard_text().is_empty() => {
+                        let text = ctx.clipboard_text().to_string();
+                        changed |= editor.insert(&text);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if changed {
+            *self.value = state.editor.text().into_owned();
+            state.synced.clone_from(self.value);
+            if let Some(ref mut on_change) = self.on_change {
+                on_change(self.value);
+            }
+        }
+        let caret_moved = changed || state.editor.selection() != selection_before;
+        if caret_moved {
+            ctx.reset_cursor_blink(&id);
+        }
+
+        let caret_visible =
+            focused && ctx.update_cursor_blink(&id, style.blink_interval.as_millis() as u64);
+
+        // Show the placeholder while empty; the caret still sits at the start
+        let empty = self.value.is_empty();
+        let shown = if empty {
+            self.placeholder.as_str()
+        } else {
+            self.value.as_str()
+        };
+        let line_height = style.font_size * style.line_height_multiplier;
+        let (display_text, decorations) = match ctx.measurer() {
+            Some(measurer) => {
+                let display_text = edit_style.display_text(measurer, shown).into_owned();
+                let decorations = state
+                    .editor
+                    .decorations(measurer, &edit_style, caret_visible);
+
+                // Keep the caret in view after typing or moving it, padding
+                // included
+                if caret_moved {
+                    let text = state.editor.text();
+                    let caret =
+                        edit_style.caret_rect(measurer, &text, state.editor.selection().caret);
+                    let line_top = caret.y - (line_height - caret.height) * 0.5;
+                    let top = line_top * layout_scale;
+                    let bottom =
+                        (line_top + line_height + style.padding.get_vertical()) * layout_scale;
+                    let (current, viewport) = (metrics.target.1, metrics.viewport.1);
+                    if top < current {
+                        ctx.scroll_to(&scroll_id, (0.0, top));
+                    } else if viewport > 0.0 && bottom > current + viewport {
+                        ctx.scroll_to(&scroll_id, (0.0, bottom - viewport));
+                    }
+                }
+
+                (display_text, Some(decorations))
+            }
+            None => (shown.to_string(), None),
+        };
+        let block_height = decorations.as_ref().map_or_else(
+            || display_text.lines().count().max(1) as f32 * line_height,
+            |decorations| decorations.block_height,
+        );
+
+        *ctx.memory().text_area(&id) = state;
+
+        let text_color = if empty {
+            style.placeholder_text_color
+        } else {
+            style.text_color
+        };
+        let mut text = Node::new()
+            .with_id(NodeId::new(&text_id))
+            .with_width(Size::Fill)
+            .with_height(Size::lpx(
+                block_height.max(text_height) + style.padding.get_vertical(),
+            ))
+            .with_padding(style.padding)
+            .with_content(Content::Text(
+                TextContent::new(display_text)
+                    .with_font_size(Size::lpx(style.font_size))
+                    .with_color(text_color)
+                    .with_h_align(HorizontalAlign::Left)
+                    .with_v_align(VerticalAlign::Top)
+                    .with_wrap(Wrap::None)
+                    .with_line_height(style.line_height_multiplier),
+            ))
+            .with_style(Style {
+                text_color: Some(text_color),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                text_color: Some(style.disabled_text_color),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick());
+        if let Some(decorations) = decorations {
+            text = text.with_text_edit(decorations);
+        }
+
+        // The text node fills at least the viewport, so it takes every click
+        let content = Node::new()
+            .with_id(NodeId::new(&scroll_id))
+            .with_layout_direction(Layout::Vertical)
+            .with_child(text);
+        let area = ScrollArea::new(content)
+            .width(Size::Fill)
+            .height(Size::Fill)
+            .horizontal(false)
+            .with_style(style.scrollbar.clone())
+            .node(ctx);
+
+        let (fill_color, stroke_color, stroke_width) = if focused {
+            (
+                style.focused_color,
+                style.focused_stroke_color,
+                style.focused_stroke_width,
+            )
+        } else {
+            (
+                style.idle_color,
+                style.idle_stroke_color,
+                style.idle_stroke_width,
+            )
+        };
+
+        Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(style.width))
+            .with_height(Size::lpx(style.height))
+            .with_text_scale(1.0)
+            .with_layout_direction(Layout::Stack)
+            .with_overflow(Overflow::Hidden)
+            .with_cursor(CursorIcon::Text)
+            .with_style(Style {
+                fill_color: Some(fill_color),
+                stroke: Some(Stroke::new(Size::lpx(stroke_width), stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                ..Default::default()
+            })
+            .with_disabled_style(Style {
+                fill_color: Some(style.disabled_color),
+                stroke: Some(Stroke::new(
+                    Size::lpx(stroke_width),
+                    style.disabled_stroke_color,
+                )),
+                ..Default::default()
+            })
+            .with_disabled(self.disabled)
+            .with_transition(Transition::quick())
+            .with_child(area)
+    }
+}
@@ -3,14 +3,16 @@
 //! Provides an editable text input field with cursor, selection, and keyboard support.
 
 use astra_gui::{
-    catppuccin::mocha, Color, Content, CornerShape, HorizontalAlign, Layout, MeasureTextRequest,
-    Node, NodeId, Overflow, Rect, Shape, Size, Spacing, Stroke, Style, StyledRect, TextContent,
-    Transition, Translation, UiContext, VerticalAlign,
+    Color, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, MeasureTextRequest, Node,
+    NodeId, Overflow, Place, Point, Rect, Shape, Size, Spacing, Stroke, Style, StyledRect,
+    TextContent, TextEditStyle, Theme, Transition, Translation, UiContext, VerticalAlign,
 };
 use astra_gui_macros::WithBuilders;
 use astra_gui_wgpu::{InteractionEvent, Key, MouseButton, NamedKey};
 use std::time::Duration;
 
+use crate::InputMask;
+
 /// Cursor shape for text input
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CursorShape {
@@ -91,27 +93,28 @@ pub struct TextInputStyle {
     pub width: f32,
 }
 
-impl Default for TextInputStyle {
-    fn default() -> Self {
+impl TextInputStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
         Self {
             // Fill Colors
-            idle_color: mocha::SURFACE0,
-            focused_color: mocha::SURFACE1,
-            disabled_color: mocha::SURFACE0.with_alpha(0.8),
+            idle_color: theme.field,
+            focused_color: theme.field_hover,
+            disabled_color: theme.field.with_alpha(0.8),
             // Stroke Colors
-            idle_stroke_color: mocha::LAVENDER,
-            focused_stroke_color: mocha::LAVENDER,
-            disabled_stroke_color: mocha::SURFACE2,
+            idle_stroke_color: theme.primary,
+            focused_stroke_color: theme.primary,
+            disabled_stroke_color: theme.field_active,
             // Stroke Width
             idle_stroke_width: 2.0,
             focused_stroke_width: 3.0,
             disabled_stroke_width: 2.0,
             // Text Colors
-            text_color: mocha::TEXT,
-            placeholder_text_color: mocha::SUBTEXT0,
-            disabled_text_color: mocha::SUBTEXT0,
+            text_color: theme.text,
+            placeholder_text_color: theme.text_muted,
+            disabled_text_color: theme.text_muted,
             // Other
-            selection_color: mocha::LAVENDER.with_alpha(0.3),
+            selection_color: theme.primary.with_alpha(0.3),
             padding: Spacing::symmetric(Size::lpx(10.0), Size::lpx(8.0)),
             border_radius: 8.0,
             font_size: 20.0,
@@ -122,6 +125,12 @@ impl Default for TextInputStyle {
     }
 }
 
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// Internal state stored in WidgetMemory for text input
 #[derive(Debug, Clone, Default)]
 pub struct TextInputState {
@@ -146,7 +155,12 @@ pub struct TextInput<'a> {
     value: &'a mut String,
     placeholder: String,
     disabled: bool,
-    style: TextInputStyle,
+    id: Option<String>,
+    style: Option<TextInputStyle>,
+    mask: Option<InputMask>,
+    password: bool,
+    reveal_toggle: bool,
+    allow_copy: bool,
     on_change: Option<Box<dyn FnMut(&str) + 'a>>,
     on_submit: Option<Box<dyn FnMut(&str) + 'a>>,
 }
@@ -158,7 +172,12 @@ impl<'a> TextInput<'a> {
             value,
             placeholder: String::new(),
             disabled: false,
-            style: TextInputStyle::default(),
+            id: None,
+            style: None,
+            mask: None,
+            password: false,
+            reveal_toggle: false,
+            allow_copy: false,
             on_change: None,
             on_submit: None,
         }
@@ -176,9 +195,48 @@ impl<'a> TextInput<'a> {
         self
     }
 
+    /// Use an explicit ID instead of a generated one
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     /// Set a custom style for the text input
     pub fn with_style(mut self, style: TextInputStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
+        self
+    }
+
+    /// Restrict and format the input with a mask
+    ///
+    /// Pattern masks insert their literal characters as the user types and
+    /// the caret skips over them. Word-wise editing with Ctrl is disabled for
+    /// masked inputs.
+    pub fn mask(mut self, mask: InputMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Show a bullet for every character instead of the text
+    ///
+    /// Word-wise editing with Ctrl jumps to the start or end instead of word
+    /// boundaries, and copying or cutting the text is disabled unless
+    /// `allow_copy` is set. Pasting still works.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Show a "Show"/"Hide" button at the end of a password field that
+    /// reveals the text
+    pub fn reveal_toggle(mut self, reveal_toggle: bool) -> Self {
+        self.reveal_toggle = reveal_toggle;
+        self
+    }
+
+    /// Allow copying and cutting the text of a password field (default: false)
+    pub fn allow_copy(mut self, allow_copy: bool) -> Self {
+        self.allow_copy = allow_copy;
         self
     }
 
@@ -199,28 +257,51 @@ impl<'a> TextInput<'a> {
     /// Note: This is not implementing Component trait because we need lifetime 'a
     /// for the mutable reference to the value string.
     pub fn build(mut self, ctx: &mut UiContext) -> Node {
-        // Generate unique ID
-        let id = ctx.generate_id("text_input");
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("text_input"));
+        let mut style = self
+            .style
+            .take()
+            .unwrap_or_else(|| TextInputStyle::from_theme(ctx.theme()));
+        // Caret positions and the field height follow the font size, so the text
+        // scale is applied here rather than by layout (the node opts out below)
+        style.font_size *= ctx.text_scale();
         let hitbox_id = format!("{}_hitbox", id);
+        let reveal_id = format!("{}_reveal", id);
         let _node_id = NodeId::new(&id);
+        let show_toggle = self.password && self.reveal_toggle;
+        let toggle_width = if show_toggle {
+            reveal_toggle_width(&style)
+        } else {
+            0.0
+        };
 
         // Get or create state from widget memory
         let state = ctx.memory().text_input(&id);
         let mut cursor_pos = state.cursor_pos;
         let mut selection = state.selection;
+        let mut revealed = state.revealed;
 
         // Check if focused
         let is_focused = ctx.is_focused(&id);
 
-        // Handle click to focus
+        // Handle click to focus; the reveal toggle keeps the field focused
         let was_clicked = ctx.events().iter().any(|e| {
             matches!(e.event, InteractionEvent::Click { .. })
-                && (e.target.as_str() == id || e.target.as_str() == hitbox_id)
+                && (e.target.as_str() == id
+                    || e.target.as_str() == hitbox_id
+                    || e.target.as_str() == reveal_id)
         });
 
         if was_clicked && !self.disabled {
             ctx.set_focus(Some(&id));
         }
+        if show_toggle && !self.disabled && ctx.was_clicked(&reveal_id) {
+            revealed = !revealed;
+        }
+        let masked = self.password && !revealed;
 
         // Handle unfocus: clicking outside or pressing ESC
         let input = ctx.input().clone();
@@ -240,6 +321,43 @@ impl<'a> TextInput<'a> {
         // Process keyboard input if focused
         let mut value_changed = false;
 
+        // Masks edit the unformatted value and format it again on blur
+        if let Some(ref mask) = self.mask {
+            let formatted = if focused && !is_focused {
+                Some(mask.unformat(self.value))
+            } else if !focused && is_focused {
+                Some(mask.format(self.value))
+            } else {
+                None
+            };
+
+            if let Some(formatted) = formatted.filter(|f| f != self.value) {
+                *self.value = formatted;
+                value_changed = true;
+            }
+            if focused && !is_focused {
+                cursor_pos = self.value.len();
+                selection = None;
+            }
+        }
+
+        // Place the caret at the clicked character
+        if was_clicked && focused && !self.disabled {
+            let caret = if masked {
+                // Hit-test the bullets, then map their char index back to a
+                // byte offset in the value
+                let bullets = bullets(self.value);
+                click_caret(ctx, &id, &hitbox_id, &bullets, &style, toggle_width)
+                    .map(|caret| char_to_byte(self.value, bullets[..caret].chars().count()))
+            } else {
+                click_caret(ctx, &id, &hitbox_id, self.value, &style, toggle_width)
+            };
+            if let Some(caret) = caret {
+                cursor_pos = caret;
+                selection = None;
+            }
+        }
+
         if focused && !self.disabled {
             let shift_held = input.shift_held;
             let ctrl_held = input.ctrl_held;
@@ -260,17 +378,16 @@ impl<'a> TextInput<'a> {
                 // Delete selection if exists before inserting
                 if let Some((start, end)) = selection {
                     if start < end {
-                        self.value.replace_range(start..end, "");
-                        cursor_pos = start;
+                        cursor_pos = remove_range(self.value, self.mask.as_ref(), start, end);
                         selection = None;
                         value_changed = true;
                     }
                 }
 
                 // Insert character at cursor position
-                if cursor_pos <= self.value.len() {
-                    self.value.insert(cursor_pos, *ch);
-                    cursor_pos += ch.len_utf8();
+                if let Some(new_pos) = insert_char(self.value, self.mask.as_ref(), cursor_pos, *ch)
+                {
+                    cursor_pos = new_pos;
                     value_changed = true;
                     ctx.reset_cursor_blink(&id);
                 }
@@ -287,6 +404,51 @@ impl<'a> TextInput<'a> {
                             ctx.reset_cursor_blink(&id);
                         }
                     }
+                    // Ctrl/Cmd+C and Ctrl/Cmd+X: Copy or cut the selection,
+                    // unless it is a password that may not be copied
+                    Key::Character(ref ch) if (ch == "c" || ch == "x") && ctrl_held => {
+                        let Some((start, end)) = selection.filter(|(start, end)| start < end)
+                        else {
+                            continue;
+                        };
+                        if self.password && !self.allow_copy {
+                            continue;
+                        }
+                        ctx.copy_to_clipboard(&self.value[start..end]);
+                        if ch == "x" {
+                            cursor_pos = remove_range(self.value, self.mask.as_ref(), start, end);
+                            selection = None;
+                            value_changed = true;
+                            ctx.reset_cursor_blink(&id);
+                        }
+                    }
+                    // Ctrl/Cmd+V: Paste over the selection, dropping line breaks
+                    Key::Character(ref ch) if ch == "v" && ctrl_held => {
+                        let pasted: Vec<char> = ctx
+                            .clipboard_text()
+                            .chars()
+                            .filter(|ch| !ch.is_control())
+                            .collect();
+                        if pasted.is_empty() {
+                            continue;
+                        }
+                        if let Some((start, end)) = selection.take() {
+                            if start < end {
+                                cursor_pos =
+                                    remove_range(self.value, self.mask.as_ref(), start, end);
+                                value_changed = true;
+                            }
+                        }
+                        for ch in pasted {
+                            if let Some(new_pos) =
+                                insert_char(self.value, self.mask.as_ref(), cursor_pos, ch)
+                            {
+                                cursor_pos = new_pos;
+                                value_changed = true;
+                            }
+                        }
+                        ctx.reset_cursor_blink(&id);
+                    }
                     Key::Named(NamedKey::Enter) => {
                         if let Some(ref mut on_submit) = self.on_submit {
                             on_submit(self.value);
@@ -296,15 +458,21 @@ impl<'a> TextInput<'a> {
                         // Delete selection if exists
                         if let Some((start, end)) = selection {
                             if start < end {
-                                self.value.replace_range(start..end, "");
-                                cursor_pos = start;
+                                cursor_pos =
+                                    remove_range(self.value, self.mask.as_ref(), start, end);
                                 selection = None;
                                 value_changed = true;
                                 ctx.reset_cursor_blink(&id);
                             }
                         } else if cursor_pos > 0 && !self.value.is_empty() {
-                            if ctrl_held {
-                                let new_pos = find_prev_word_boundary(self.value, cursor_pos);
+                            if let Some(ref mask) = self.mask {
+                                cursor_pos = mask.backspace(self.value, cursor_pos);
+                            } else if ctrl_held {
+                                let new_pos = if self.password {
+                                    0
+                                } else {
+                                    find_prev_word_boundary(self.value, cursor_pos)
+                                };
                                 self.value.replace_range(new_pos..cursor_pos, "");
                                 cursor_pos = new_pos;
                             } else {
@@ -323,15 +491,21 @@ impl<'a> TextInput<'a> {
                         // Delete selection if exists
                         if let Some((start, end)) = selection {
                             if start < end {
-                                self.value.replace_range(start..end, "");
-                                cursor_pos = start;
+                                cursor_pos =
+                                    remove_range(self.value, self.mask.as_ref(), start, end);
                                 selection = None;
                                 value_changed = true;
                                 ctx.reset_cursor_blink(&id);
                             }
                         } else if cursor_pos < self.value.len() {
-                            if ctrl_held {
-                                let new_pos = find_next_word_boundary(self.value, cursor_pos);
+                            if let Some(ref mask) = self.mask {
+                                cursor_pos = mask.delete(self.value, cursor_pos);
+                            } else if ctrl_held {
+                                let new_pos = if self.password {
+                                    self.value.len()
+                                } else {
+                                    find_next_word_boundary(self.value, cursor_pos)
+                                };
                                 self.value.replace_range(cursor_pos..new_pos, "");
                             } else {
                                 self.value.remove(cursor_pos);
@@ -344,7 +518,11 @@ impl<'a> TextInput<'a> {
                         if cursor_pos > 0 {
                             let old_pos = cursor_pos;
 
-                            if ctrl_held {
+                            if let Some(ref mask) = self.mask {
+                                cursor_pos = mask.prev_caret(self.value, cursor_pos);
+                            } else if ctrl_held && self.password {
+                                cursor_pos = 0;
+                            } else if ctrl_held {
                                 cursor_pos = find_prev_word_boundary(self.value, cursor_pos);
                             } else {
                                 cursor_pos -= 1;
@@ -374,7 +552,11 @@ impl<'a> TextInput<'a> {
                         if cursor_pos < self.value.len() {
                             let old_pos = cursor_pos;
 
-                            if ctrl_held {
+                            if let Some(ref mask) = self.mask {
+                                cursor_pos = mask.next_caret(self.value, cursor_pos);
+                            } else if ctrl_held && self.password {
+                                cursor_pos = self.value.len();
+                            } else if ctrl_held {
                                 cursor_pos = find_next_word_boundary(self.value, cursor_pos);
                             } else {
                                 cursor_pos += 1;
@@ -404,13 +586,16 @@ impl<'a> TextInput<'a> {
                     }
                     Key::Named(NamedKey::Home) => {
                         let old_pos = cursor_pos;
-                        cursor_pos = 0;
+                        cursor_pos = self
+                            .mask
+                            .as_ref()
+                            .map_or(0, |mask| mask.first_caret(self.value));
 
                         if shift_held {
                             if let Some(anchor) = selection_anchor {
-                                selection = Some((0, anchor));
+                                selection = Some((cursor_pos, anchor));
                             } else {
-                                selection = Some((0, old_pos));
+                                selection = Some((cursor_pos, old_pos));
                             }
                         } else {
                             selection = None;
@@ -454,48 +639,178 @@ impl<'a> TextInput<'a> {
         let state = ctx.memory().text_input(&id);
         state.cursor_pos = cursor_pos;
         state.selection = selection;
+        state.revealed = revealed;
 
         // Update cursor blink
         let cursor_visible = if focused {
-            ctx.update_cursor_blink(
-                &id,
-                self.style.cursor_style.blink_interval.as_millis() as u64,
-            )
+            ctx.update_cursor_blink(&id, style.cursor_style.blink_interval.as_millis() as u64)
         } else {
             false
         };
 
+        // The caret and selection are drawn at char indices rather than byte
+        // offsets, which also carry over to the bullets of a masked field
+        let to_char = |pos: usize| self.value[..pos].chars().count();
+        let display = if masked {
+            bullets(self.value)
+        } else {
+            self.value.clone()
+        };
+
         // Build the node
+        let display = TextInputDisplay {
+            text: &display,
+            cursor_pos: to_char(cursor_pos),
+            selection: selection.map(|(start, end)| (to_char(start), to_char(end))),
+            cursor_visible,
+            revealed: show_toggle.then_some(revealed),
+        };
         build_text_input_node(
             &id,
-            self.value,
+            display,
             &self.placeholder,
             focused,
             self.disabled,
-            &self.style,
-            cursor_pos,
-            selection,
-            cursor_visible,
+            &style,
             ctx,
         )
     }
 }
 
+/// Byte offset in `value` of the caret closest to this frame's click on the
+/// field, if it has a measurer to hit-test the text with
+fn click_caret(
+    ctx: &mut UiContext,
+    id: &str,
+    hitbox_id: &str,
+    value: &str,
+    style: &TextInputStyle,
+    toggle_width: f32,
+) -> Option<usize> {
+    // The hitbox covers the text area; the field itself includes the padding
+    let click_x = ctx.events().iter().find_map(|event| {
+        if !matches!(event.event, InteractionEvent::Click { .. }) {
+            return None;
+        }
+        let x = event.local_position.x / event.zoom;
+        match event.target.as_str() {
+            target if target == hitbox_id => Some(x),
+            target if target == id => Some(x - style.padding.get_left()),
+            _ => None,
+        }
+    })?;
+
+    let edit_style = TextEditStyle {
+        font_size: style.font_size,
+        ..TextEditStyle::default()
+    };
+    let measurer = ctx.measurer()?;
+    let text_width = edit_style
+        .caret_rect(measurer, value, value.len())
+        .line_width;
+    let text_container_width = style.width - style.padding.get_horizontal() - toggle_width;
+    let text_start_x = match style.text_align {
+        HorizontalAlign::Left => 0.0,
+        HorizontalAlign::Center => (text_container_width - text_width) / 2.0,
+        HorizontalAlign::Right => text_container_width - text_width,
+    };
+
+    let point = Point {
+        x: click_x - text_start_x,
+        y: 0.0,
+    };
+    Some(edit_style.caret_index_at(measurer, value, point))
+}
+
+/// Width of a password field's reveal toggle
+fn reveal_toggle_width(style: &TextInputStyle) -> f32 {
+    style.font_size * 2.5
+}
+
+/// A bullet for every character of `value`
+fn bullets(value: &str) -> String {
+    "\u{2022}".repeat(value.chars().count())
+}
+
+/// Byte offset of the char at `index`, or the end of `value` past the last one
+fn char_to_byte(value: &str, index: usize) -> usize {
+    value
+        .char_indices()
+        .nth(index)
+        .map_or(value.len(), |(byte, _)| byte)
+}
+
+/// Remove `start..end` from `value` through the mask if there is one,
+/// returning the new caret position
+fn remove_range(value: &mut String, mask: Option<&InputMask>, start: usize, end: usize) -> usize {
+    match mask {
+        Some(mask) => mask.remove(value, start, end),
+        None => {
+            value.replace_range(start..end, "");
+            start
+        }
+    }
+}
+
+/// Insert `ch` at `pos` through the mask if there is one, returning the new
+/// caret position, or `None` if the character was rejected
+fn insert_char(
+    value: &mut String,
+    mask: Option<&InputMask>,
+    pos: usize,
+    ch: char,
+) -> Option<usize> {
+    match mask {
+        Some(mask) => mask.insert(value, pos, ch),
+        None if pos <= value.len() => {
+            value.insert(pos, ch);
+            Some(pos + ch.len_utf8())
+        }
+        None => None,
+    }
+}
+
 /// Build the visual node for a text input
+/// What a text input shows, and where its caret and selection are drawn
+#[derive(Clone, Copy)]
+struct TextInputDisplay<'a> {
+    /// The value, or bullets standing in for a masked password
+    text: &'a str,
+    /// Caret position, in chars of `text`
+    cursor_pos: usize,
+    /// Selected range, in chars of `text`
+    selection: Option<(usize, usize)>,
+    /// Whether the caret is in the visible half of its blink
+    cursor_visible: bool,
+    /// Whether the password is revealed, if the field has a reveal toggle
+    revealed: Option<bool>,
+}
+
 fn build_text_input_node(
     id: &str,
-    value: &str,
+    display: TextInputDisplay<'_>,
     placeholder: &str,
     focused: bool,
     disabled: bool,
     style: &TextInputStyle,
-    cursor_pos: usize,
-    selection: Option<(usize, usize)>,
-    cursor_visible: bool,
     ctx: &mut UiContext,
 ) -> Node {
+    let TextInputDisplay {
+        text: value,
+        cursor_pos,
+        selection,
+        cursor_visible,
+        revealed,
+    } = display;
     let node_id = NodeId::new(id);
     let hitbox_id = format!("{}_hitbox", id);
+    let toggle_width = revealed.map_or(0.0, |_| reveal_toggle_width(style));
+    // The text stops short of the reveal toggle
+    let text_width = if revealed.is_some() {
+        Size::lpx(style.width - style.padding.get_horizontal() - toggle_width)
+    } else {
+        Size::Fill
+    };
 
     // Determine what text to display
     let display_text = if value.is_empty() {
@@ -521,22 +836,27 @@ fn build_text_input_node(
                 measurer
                     .measure_text(MeasureTextRequest {
                         text: value,
+                        spans: &[],
                         font_size: style.font_size,
                         h_align: style.text_align,
                         v_align: VerticalAlign::Center,
                         family: None,
                         max_width: None,
                         wrap: astra_gui::Wrap::None,
+                        max_lines: None,
                         line_height_multiplier: 1.2,
                         font_weight: astra_gui::FontWeight::Normal,
                         font_style: astra_gui::FontStyle::Normal,
+                        font_features: &[],
+                        font_width: astra_gui::FontWidth::Normal,
+                        tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
                     })
                     .width
             } else {
                 0.0
             };
 
-            let text_container_width = style.width - style.padding.get_horizontal();
+            let text_container_width = style.width - style.padding.get_horizontal() - toggle_width;
             let text_start_x = match style.text_align {
                 HorizontalAlign::Left => 0.0,
                 HorizontalAlign::Center => (text_container_width - total_width) / 2.0,
@@ -549,15 +869,20 @@ fn build_text_input_node(
                     measurer
                         .measure_text(MeasureTextRequest {
                             text: &text_before_cursor,
+                            spans: &[],
                             font_size: style.font_size,
                             h_align: HorizontalAlign::Left,
                             v_align: VerticalAlign::Center,
                             family: None,
                             max_width: None,
                             wrap: astra_gui::Wrap::None,
+                            max_lines: None,
                             line_height_multiplier: 1.2,
                             font_weight: astra_gui::FontWeight::Normal,
                             font_style: astra_gui::FontStyle::Normal,
+                            font_features: &[],
+                            font_width: astra_gui::FontWidth::Normal,
+                            tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
                         })
                         .width
                 } else {
@@ -573,15 +898,20 @@ fn build_text_input_node(
                             measurer
                                 .measure_text(MeasureTextRequest {
                                     text: &text_before_selection,
+                                    spans: &[],
                                     font_size: style.font_size,
                                     h_align: HorizontalAlign::Left,
                                     v_align: VerticalAlign::Center,
                                     family: None,
                                     max_width: None,
                                     wrap: astra_gui::Wrap::None,
+                                    max_lines: None,
                                     line_height_multiplier: 1.2,
                                     font_weight: astra_gui::FontWeight::Normal,
                                     font_style: astra_gui::FontStyle::Normal,
+                                    font_features: &[],
+                                    font_width: astra_gui::FontWidth::Normal,
+                                    tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
                                 })
                                 .width
                         } else {
@@ -597,15 +927,20 @@ fn build_text_input_node(
                         measurer
                             .measure_text(MeasureTextRequest {
                                 text: &selected_text,
+                                spans: &[],
                                 font_size: style.font_size,
                                 h_align: HorizontalAlign::Left,
                                 v_align: VerticalAlign::Center,
                                 family: None,
                                 max_width: None,
                                 wrap: astra_gui::Wrap::None,
+                                max_lines: None,
                                 line_height_multiplier: 1.2,
                                 font_weight: astra_gui::FontWeight::Normal,
                                 font_style: astra_gui::FontStyle::Normal,
+                                font_features: &[],
+                                font_width: astra_gui::FontWidth::Normal,
+                                tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
                             })
                             .width
                     } else {
@@ -649,18 +984,27 @@ fn build_text_input_node(
     // Text content
     children.push(
         Node::new()
-            .with_width(Size::Fill)
+            .with_width(text_width)
             .with_height(Size::Fill)
             .with_content(Content::Text(TextContent {
                 text: display_text,
+                spans: Vec::new(),
                 font_size: Size::lpx(style.font_size),
                 color: text_color,
                 h_align: style.text_align,
                 v_align: VerticalAlign::Center,
                 wrap: astra_gui::Wrap::None,
+                overflow: astra_gui::TextOverflow::Clip,
+                max_lines: None,
                 line_height_multiplier: 1.2,
                 font_weight: astra_gui::FontWeight::Normal,
                 font_style: astra_gui::FontStyle::Normal,
+                font_family: None,
+                font_features: Vec::new(),
+                font_width: astra_gui::FontWidth::Normal,
+                tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
+                outline: None,
+                inherit: astra_gui::TextInherit::NONE,
             }))
             .with_style(Style {
                 text_color: Some(text_color),
@@ -685,15 +1029,20 @@ fn build_text_input_node(
                 .map(|m| {
                     m.measure_text(MeasureTextRequest {
                         text: &ch.to_string(),
+                        spans: &[],
                         font_size: style.font_size,
                         h_align: HorizontalAlign::Left,
                         v_align: VerticalAlign::Center,
                         family: None,
                         max_width: None,
                         wrap: astra_gui::Wrap::None,
+                        max_lines: None,
                         line_height_multiplier: 1.2,
                         font_weight: astra_gui::FontWeight::Normal,
                         font_style: astra_gui::FontStyle::Normal,
+                        font_features: &[],
+                        font_width: astra_gui::FontWidth::Normal,
+                        tab_width: astra_gui::TextContent::DEFAULT_TAB_WIDTH,
                     })
                     .width
                 })
@@ -755,11 +1104,42 @@ fn build_text_input_node(
     children.push(
         Node::new()
             .with_id(NodeId::new(&hitbox_id))
-            .with_width(Size::Fill)
+            .with_width(text_width)
             .with_height(Size::Fill)
             .with_disabled(disabled),
     );
 
+    if let Some(revealed) = revealed {
+        children.push(
+            Node::new()
+                .with_id(NodeId::new(format!("{}_reveal", id)))
+                .with_width(Size::lpx(toggle_width))
+                .with_height(Size::Fill)
+                .with_place(Place::Alignment {
+                    h_align: HorizontalAlign::Right,
+                    v_align: VerticalAlign::Center,
+                })
+                .with_cursor(CursorIcon::Pointer)
+                .with_content(Content::Text(
+                    TextContent::new(if revealed { "Hide" } else { "Show" })
+                        .with_font_size(Size::lpx(style.font_size * 0.7))
+                        .with_color(style.placeholder_text_color)
+                        .with_h_align(HorizontalAlign::Right)
+                        .with_v_align(VerticalAlign::Center),
+                ))
+                .with_style(Style {
+                    text_color: Some(style.placeholder_text_color),
+                    ..Default::default()
+                })
+                .with_hover_style(Style {
+                    text_color: Some(style.text_color),
+                    ..Default::default()
+                })
+                .with_disabled(disabled)
+                .with_transition(Transition::quick()),
+        );
+    }
+
     let fill_color = if focused {
         style.focused_color
     } else {
@@ -782,6 +1162,7 @@ fn build_text_input_node(
         .with_id(node_id)
         .with_width(Size::lpx(style.width))
         .with_height(Size::lpx(style.font_size + style.padding.get_vertical()))
+        .with_text_scale(1.0)
         .with_padding(style.padding)
         .with_layout_direction(Layout::Stack)
         .with_overflow(Overflow::Hidden)
@@ -872,3 +1253,64 @@ fn find_next_word_boundary(text: &str, pos: usize) -> usize {
 
     new_pos
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astra_gui::{ContentMeasurer, IntrinsicSize};
+
+    /// Measures every char as 10px wide
+    struct MonospaceMeasurer;
+
+    impl ContentMeasurer for MonospaceMeasurer {
+        fn measure_text(&mut self, request: MeasureTextRequest<'_>) -> IntrinsicSize {
+            IntrinsicSize::new(request.text.chars().count() as f32 * 10.0, 20.0)
+        }
+    }
+
+    /// Run a frame of a text input editing `value`, with the pointer at `x`
+    /// (relative to the start of the text) and `input` applied first
+    fn frame(
+        ctx: &mut UiContext,
+        value: &mut String,
+        x: f32,
+        input: impl FnOnce(&mut astra_gui::InputState),
+    ) {
+        let style = TextInputStyle::from_theme(ctx.theme());
+        ctx.input_mut().cursor_position =
+            Some(Point::new(style.padding.get_left() + x, style.font_size));
+        input(ctx.input_mut());
+        ctx.begin_frame();
+        let mut root = TextInput::new(value).with_id("field").build(ctx);
+        root.compute_layout(Rect::from_min_size([0.0, 0.0], [400.0, 100.0]));
+        ctx.end_frame(&mut root);
+        ctx.input_mut().begin_frame();
+    }
+
+    #[test]
+    fn test_click_places_caret_between_multibyte_chars() {
+        let mut ctx = UiContext::with_measurer(MonospaceMeasurer);
+        let mut value = String::from("h\u{e9}\u{1f600}llo");
+
+        // Click just past the emoji, then edit there
+        frame(&mut ctx, &mut value, 31.0, |input| {
+            input.press_button(MouseButton::Left)
+        });
+        frame(&mut ctx, &mut value, 31.0, |input| {
+            input.release_button(MouseButton::Left)
+        });
+        frame(&mut ctx, &mut value, 31.0, |input| {
+            input.type_character('x')
+        });
+        assert_eq!(value, "h\u{e9}\u{1f600}xllo");
+
+        // Backspace removes whole chars, the emoji included
+        for _ in 0..2 {
+            frame(&mut ctx, &mut value, 31.0, |input| {
+                input.press_key(Key::Named(NamedKey::Backspace), false, false)
+            });
+            ctx.input_mut().release_key(Key::Named(NamedKey::Backspace));
+        }
+        assert_eq!(value, "h\u{e9}llo");
+    }
+}
@@ -0,0 +1,634 @@
+//! Timeline component for animation and audio tools
+//!
+//! Tracks of keyframes under a time ruler. Clicking or dragging the ruler
+//! moves the playhead; keyframes are selected by clicking them (Shift adds to
+//! the selection) or by dragging a box around them, and dragging a selected
+//! keyframe moves the whole selection. The mouse wheel zooms the time axis
+//! around the pointer and Shift+wheel (or a horizontal wheel) pans it.
+//!
+//! The ruler and the tracks are drawn with `Painter`s; keyframes are nodes
+//! on top so they receive their own pointer events.
+
+use std::f32::consts::{FRAC_PI_4, SQRT_2};
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, InteractionEvent, Layout, MouseButton, Node, NodeId,
+    Overflow, Painter, Place, Rect, Size, Spacing, Stroke, Style, TextContent, Theme, UiContext,
+    VerticalAlign,
+};
+use astra_gui_macros::WithBuilders;
+
+use crate::plot::{format_tick, ticks};
+
+/// Relative zoom change per pixel of wheel scrolling
+const ZOOM_SPEED: f32 = 0.005;
+/// Approximate distance between ruler ticks, in logical pixels
+const TICK_SPACING: f32 = 80.0;
+/// Narrowest visible time range, relative to the timeline's duration
+const MIN_VIEW_FRACTION: f32 = 0.001;
+
+/// Visual styling for a timeline
+#[derive(Debug, Clone, WithBuilders)]
+pub struct TimelineStyle {
+    /// Background color of the tracks
+    pub background_color: Color,
+    /// Background color of every other track
+    pub alternate_track_color: Color,
+    /// Background color of the ruler and the track labels
+    pub header_color: Color,
+    /// Color of the border
+    pub stroke_color: Color,
+    /// Color of the ruler ticks and the grid lines under the tracks
+    pub tick_color: Color,
+    /// Color of the ruler's time labels
+    pub label_color: Color,
+    /// Color of the track names
+    pub track_label_color: Color,
+    /// Color of keyframes on tracks without their own color
+    pub keyframe_color: Color,
+    /// Color of selected keyframes
+    pub selected_color: Color,
+    /// Color of the playhead
+    pub playhead_color: Color,
+    /// Fill of the selection box
+    pub selection_fill_color: Color,
+    /// Outline of the selection box
+    pub selection_stroke_color: Color,
+    /// Font size of the ruler labels and track names
+    pub font_size: f32,
+    /// Height of the ruler
+    pub ruler_height: f32,
+    /// Height of each track
+    pub track_height: f32,
+    /// Width of the track name column
+    pub label_width: f32,
+    /// Width and height of a keyframe diamond
+    pub keyframe_size: f32,
+    /// Corner radius of the timeline
+    pub border_radius: f32,
+}
+
+impl TimelineStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            background_color: theme.field,
+            alternate_track_color: theme.surface.with_alpha(0.35),
+            header_color: theme.surface,
+            stroke_color: theme.border,
+            tick_color: theme.border,
+            label_color: theme.text_muted,
+            track_label_color: theme.text,
+            keyframe_color: theme.secondary,
+            selected_color: theme.primary,
+            playhead_color: theme.danger,
+            selection_fill_color: theme.primary.with_alpha(0.15),
+            selection_stroke_color: theme.primary,
+            font_size: 12.0,
+            ruler_height: 28.0,
+            track_height: 32.0,
+            label_width: 120.0,
+            keyframe_size: 14.0,
+            border_radius: 8.0,
+        }
+    }
+}
+
+impl Default for TimelineStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A named row of keyframe times on a timeline
+pub struct TimelineTrack<'a> {
+    name: String,
+    keys: &'a mut Vec<f32>,
+    color: Option<Color>,
+}
+
+impl<'a> TimelineTrack<'a> {
+    /// Create a track editing the keyframe times in `keys`
+    pub fn new(name: impl Into<String>, keys: &'a mut Vec<f32>) -> Self {
+        Self {
+            name: name.into(),
+            keys,
+            color: None,
+        }
+    }
+
+    /// Set the color of this track's keyframes
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Maps between times and logical pixels along the tracks
+#[derive(Clone, Copy)]
+struct TimeAxis {
+    view: (f32, f32),
+    width: f32,
+}
+
+impl TimeAxis {
+    fn pixels_per_second(&self) -> f32 {
+        self.width / (self.view.1 - self.view.0)
+    }
+
+    fn to_x(self, time: f32) -> f32 {
+        (time - self.view.0) * self.pixels_per_second()
+    }
+
+    fn to_time(self, x: f32) -> f32 {
+        self.view.0 + x / self.pixels_per_second()
+    }
+}
+
+/// Keyframe tracks under a time ruler with a playhead
+///
+/// Keyframe times are edited in place. Moving keyframes keeps their order in
+/// each track's `Vec`, even when one is dragged past another, so the
+/// selection stays valid; sort the times afterwards if the order matters.
+/// The selection can be read (and changed) through
+/// `ctx.memory().timeline(id).selected`.
+///
+/// # Example
+///
+/// ```ignore
+/// Timeline::new(&mut state.playhead, 10.0)
+///     .track(TimelineTrack::new("Position", &mut state.position_keys))
+///     .track(TimelineTrack::new("Opacity", &mut state.opacity_keys).color(mocha::PEACH))
+///     .snap(1.0 / 30.0)
+///     .with_id("timeline")
+///     .node(&mut ctx)
+/// ```
+pub struct Timeline<'a> {
+    playhead: &'a mut f32,
+    duration: f32,
+    tracks: Vec<TimelineTrack<'a>>,
+    snap: Option<f32>,
+    id: Option<String>,
+    style: Option<TimelineStyle>,
+}
+
+impl<'a> Timeline<'a> {
+    /// Create a timeline from 0 to `duration` seconds, editing the playhead time
+    pub fn new(playhead: &'a mut f32, duration: f32) -> Self {
+        Self {
+            playhead,
+            duration,
+            tracks: Vec::new(),
+            snap: None,
+            id: None,
+            style: None,
+        }
+    }
+
+    /// Add a track of keyframes
+    pub fn track(mut self, track: TimelineTrack<'a>) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    /// Snap the playhead and moved keyframes to multiples of `step` seconds
+    pub fn snap(mut self, step: f32) -> Self {
+        self.snap = (step > 0.0).then_some(step);
+        self
+    }
+
+    /// Set a custom ID (otherwise auto-generated)
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the timeline
+    pub fn with_style(mut self, style: TimelineStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Clamp a time to the timeline and snap it to the grid, if any
+    fn constrain(&self, time: f32) -> f32 {
+        let time = match self.snap {
+            Some(step) => (time / step).round() * step,
+            None => time,
+        };
+        time.clamp(0.0, self.duration)
+    }
+}
+
+impl Component for Timeline<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self
+            .id
+            .take()
+            .unwrap_or_else(|| ctx.generate_id("timeline"));
+        let ruler_id = format!("{}_ruler", id);
+        let tracks_id = format!("{}_tracks", id);
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| TimelineStyle::from_theme(ctx.theme()));
+        self.duration = self.duration.max(f32::EPSILON);
+        let mut state = std::mem::take(ctx.memory().timeline(&id));
+        let key_id = |track: usize, key: usize| format!("{}_key_{}_{}", id, track, key);
+
+        // Width of the tracks as of the last frame
+        let zoom = ctx
+            .events_for(&tracks_id)
+            .last()
+            .map_or(ctx.scale_factor(), |event| event.zoom);
+        let width = ctx
+            .measured_size(&tracks_id)
+            .map_or(400.0, |(width, _)| width / zoom)
+            .max(1.0);
+        let mut axis = TimeAxis {
+            view: state.view.unwrap_or((0.0, self.duration)),
+            width,
+        };
+
+        // Zoom around the pointer and pan with the wheel
+        let pointer = ctx
+            .events_for(&tracks_id)
+            .chain(ctx.events_for(&ruler_id))
+            .find_map(|event| match event.event {
+                InteractionEvent::Hover { .. } => Some(event.local_position.x / event.zoom),
+                _ => None,
+            });
+        let input = ctx.input();
+        let (wheel_x, wheel_y) = input.scroll_delta;
+        let pan = if input.shift_held {
+            wheel_x + wheel_y
+        } else {
+            wheel_x
+        };
+        let wheel_zoom = if input.shift_held { 0.0 } else { wheel_y };
+        if let Some(pointer) = pointer {
+            let mut view = axis.view;
+            if wheel_zoom != 0.0 {
+                let time = axis.to_time(pointer);
+                let factor = (-wheel_zoom * ZOOM_SPEED).exp();
+                let min_span = self.duration * MIN_VIEW_FRACTION;
+                let span = ((view.1 - view.0) * factor).clamp(min_span, self.duration);
+                let start = time - (time - view.0) * span / (view.1 - view.0);
+                view = (start, start + span);
+            }
+            if pan != 0.0 {
+                let shift = -pan / axis.pixels_per_second();
+                view = (view.0 + shift, view.1 + shift);
+            }
+            if view != axis.view {
+                // Keep the view within the timeline
+                let span = view.1 - view.0;
+                let start = view.0.clamp(0.0, self.duration - span);
+                axis.view = (start, start + span);
+                state.view = Some(axis.view);
+            }
+        }
+
+        // Scrub the playhead on the ruler
+        for event in ctx.events_for(&ruler_id) {
+            let x = event.local_position.x / event.zoom;
+            match event.event {
+                InteractionEvent::Click {
+                    button: MouseButton::Left,
+                    ..
+                }
+                | InteractionEvent::DragStart { .. }
+                | InteractionEvent::DragMove { .. } => {
+                    *self.playhead = self.constrain(axis.to_time(x));
+                }
+                _ => {}
+            }
+        }
+
+        // Select and drag keyframes. Releasing a drag also sends a click,
+        // which must not change the selection.
+        let shift_held = ctx.input().shift_held;
+        let drag_ended = |ctx: &UiContext, id: &str| {
+            ctx.events_for(id)
+                .any(|event| matches!(event.event, InteractionEvent::DragEnd { .. }))
+        };
+        for track in 0..self.tracks.len() {
+            for key in 0..self.tracks[track].keys.len() {
+                let id = key_id(track, key);
+                let dragged = drag_ended(ctx, &id);
+                for event in ctx.events_for(&id) {
+                    match event.event {
+                        InteractionEvent::Click {
+                            button: MouseButton::Left,
+                            ..
+                        } if !dragged => {
+                            if !shift_held {
+                                state.selected.clear();
+                                state.selected.push((track, key));
+                            } else if state.is_selected(track, key) {
+                                state.selected.retain(|selected| *selected != (track, key));
+                            } else {
+                                state.selected.push((track, key));
+                            }
+                        }
+                        InteractionEvent::DragStart { .. } => {
+                            if !state.is_selected(track, key) {
+                                if !shift_held {
+                                    state.selected.clear();
+                                }
+                                state.selected.push((track, key));
+                            }
+                            state.drag_origin = state
+                                .selected
+                                .iter()
+                                .filter_map(|&(t, k)| {
+                                    let time = *self.tracks.get(t)?.keys.get(k)?;
+                                    Some((t, k, time))
+                                })
+                                .collect();
+                            state.drag_offset = 0.0;
+                        }
+                        InteractionEvent::DragMove { delta, .. } => {
+                            state.drag_offset += delta.x / event.zoom / axis.pixels_per_second();
+                            for &(t, k, origin) in &state.drag_origin {
+                                let time = self.constrain(origin + state.drag_offset);
+                                self.tracks[t].keys[k] = time;
+                            }
+                        }
+                        InteractionEvent::DragEnd { .. } => state.drag_origin.clear(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Box selection on empty track space
+        let key_at = |tracks: &[TimelineTrack], (min, max): ([f32; 2], [f32; 2])| {
+            let mut inside = Vec::new();
+            for (t, track) in tracks.iter().enumerate() {
+                let y = (t as f32 + 0.5) * style.track_height;
+                if y < min[1] || y > max[1] {
+                    continue;
+                }
+                for (k, time) in track.keys.iter().enumerate() {
+                    let x = axis.to_x(*time);
+                    if x >= min[0] && x <= max[0] {
+                        inside.push((t, k));
+                    }
+                }
+            }
+            inside
+        };
+        let normalized = |(a, b): ([f32; 2], [f32; 2])| {
+            (
+                [a[0].min(b[0]), a[1].min(b[1])],
+                [a[0].max(b[0]), a[1].max(b[1])],
+            )
+        };
+        let dragged = drag_ended(ctx, &tracks_id);
+        for event in ctx.events_for(&tracks_id) {
+            let local = [
+                event.local_position.x / event.zoom,
+                event.local_position.y / event.zoom,
+            ];
+            match event.event {
+                InteractionEvent::Click {
+                    button: MouseButton::Left,
+                    ..
+                } if !shift_held && !dragged => state.selected.clear(),
+                InteractionEvent::DragStart {
+                    button: MouseButton::Left,
+                    ..
+                } => state.selection_box = Some((local, local)),
+                InteractionEvent::DragMove { .. } => {
+                    if let Some((start, _)) = state.selection_box {
+                        state.selection_box = Some((start, local));
+                    }
+                }
+                InteractionEvent::DragEnd { .. } => {
+                    if let Some((start, _)) = state.selection_box.take() {
+                        if !shift_held {
+                            state.selected.clear();
+                        }
+                        for hit in key_at(&self.tracks, normalized((start, local))) {
+                            if !state.selected.contains(&hit) {
+                                state.selected.push(hit);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        let boxed = state
+            .selection_box
+            .map(|selection_box| key_at(&self.tracks, normalized(selection_box)))
+            .unwrap_or_default();
+
+        // Forget selections of keyframes the app removed
+        let tracks = &self.tracks;
+        state
+            .selected
+            .retain(|&(t, k)| tracks.get(t).is_some_and(|track| k < track.keys.len()));
+
+        // Keyframe diamonds, centered on their time and track
+        let side = style.keyframe_size / SQRT_2;
+        let mut keyframes = Vec::new();
+        for (t, track) in self.tracks.iter().enumerate() {
+            for (k, time) in track.keys.iter().enumerate() {
+                let x = axis.to_x(*time);
+                if x < -style.keyframe_size || x > axis.width + style.keyframe_size {
+                    continue;
+                }
+                let selected = state.is_selected(t, k) || boxed.contains(&(t, k));
+                let color = if selected {
+                    style.selected_color
+                } else {
+                    track.color.unwrap_or(style.keyframe_color)
+                };
+                keyframes.push(
+                    Node::new()
+                        .with_id(NodeId::new(key_id(t, k)))
+                        .with_place(Place::Absolute {
+                            x: Size::lpx(x - side * 0.5),
+                            y: Size::lpx((t as f32 + 0.5) * style.track_height - side * 0.5),
+                        })
+                        .with_width(Size::lpx(side))
+                        .with_height(Size::lpx(side))
+                        .with_rotation(FRAC_PI_4)
+                        .with_style(Style {
+                            fill_color: Some(color),
+                            corner_shape: Some(CornerShape::Round(Size::lpx(2.0))),
+                            ..Default::default()
+                        }),
+                );
+            }
+        }
+        let selection_box = state.selection_box.map(normalized);
+        *ctx.memory().timeline(&id) = state;
+
+        let track_count = self.tracks.len();
+        let playhead = *self.playhead;
+        let (tick_times, step) = ticks(axis.view, axis.width, TICK_SPACING);
+        let tick_labels: Vec<String> = tick_times.iter().map(|t| format_tick(*t, step)).collect();
+
+        let ruler_style = style.clone();
+        let ruler_ticks = tick_times.clone();
+        let ruler = Node::new()
+            .with_id(NodeId::new(ruler_id))
+            .with_width(Size::Fill)
+            .with_height(Size::lpx(style.ruler_height))
+            .with_overflow(Overflow::Hidden)
+            .with_painter(move |painter| {
+                paint_ruler(
+                    painter,
+                    &ruler_style,
+                    axis,
+                    &ruler_ticks,
+                    &tick_labels,
+                    playhead,
+                )
+            });
+
+        let tracks_style = style.clone();
+        let tracks = Node::new()
+            .with_id(NodeId::new(tracks_id))
+            .with_measure_size(true)
+            .with_width(Size::Fill)
+            .with_height(Size::lpx(track_count as f32 * style.track_height))
+            .with_layout_direction(Layout::Stack)
+            .with_overflow(Overflow::Hidden)
+            .with_painter(move |painter| {
+                paint_tracks(
+                    painter,
+                    &tracks_style,
+                    axis,
+                    &tick_times,
+                    track_count,
+                    playhead,
+                    selection_box,
+                )
+            })
+            .with_children(keyframes);
+
+        let labels = self
+            .tracks
+            .iter()
+            .map(|track| {
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::lpx(style.track_height))
+                    .with_padding(Spacing::horizontal(Size::lpx(style.font_size)))
+                    .with_v_align(VerticalAlign::Center)
+                    .with_content(Content::Text(
+                        TextContent::new(track.name.clone())
+                            .with_font_size(Size::lpx(style.font_size))
+                            .with_color(style.track_label_color),
+                    ))
+            })
+            .collect();
+        let header = Style {
+            fill_color: Some(style.header_color),
+            ..Default::default()
+        };
+
+        Node::new()
+            .with_id(NodeId::new(id))
+            .with_width(Size::Fill)
+            .with_layout_direction(Layout::Vertical)
+            .with_overflow(Overflow::Hidden)
+            .with_style(Style {
+                fill_color: Some(style.background_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.border_radius))),
+                ..Default::default()
+            })
+            .with_children(vec![
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_style(header.clone())
+                    .with_children(vec![
+                        Node::new()
+                            .with_width(Size::lpx(style.label_width))
+                            .with_height(Size::lpx(style.ruler_height)),
+                        ruler,
+                    ]),
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_children(vec![
+                        Node::new()
+                            .with_width(Size::lpx(style.label_width))
+                            .with_layout_direction(Layout::Vertical)
+                            .with_style(header)
+                            .with_children(labels),
+                        tracks,
+                    ]),
+            ])
+    }
+}
+
+/// Ticks, time labels and the playhead handle
+fn paint_ruler(
+    painter: &mut Painter,
+    style: &TimelineStyle,
+    axis: TimeAxis,
+    ticks: &[f32],
+    labels: &[String],
+    playhead: f32,
+) {
+    let (_, height) = painter.size();
+    for (time, label) in ticks.iter().zip(labels) {
+        let x = axis.to_x(*time);
+        painter.line([x, height * 0.6], [x, height], 1.0, style.tick_color);
+        painter.text(
+            [x + 4.0, height * 0.15],
+            TextContent::new(label.clone())
+                .with_font_size(Size::lpx(style.font_size))
+                .with_color(style.label_color),
+        );
+    }
+
+    // A downward triangle over the playhead line below
+    let x = axis.to_x(playhead);
+    let half = style.font_size * 0.5;
+    painter.triangle(
+        [x - half, height - half * 1.5],
+        [x + half, height - half * 1.5],
+        [x, height],
+        style.playhead_color,
+    );
+}
+
+/// Track stripes, grid lines, the playhead line and the selection box
+fn paint_tracks(
+    painter: &mut Painter,
+    style: &TimelineStyle,
+    axis: TimeAxis,
+    ticks: &[f32],
+    track_count: usize,
+    playhead: f32,
+    selection_box: Option<([f32; 2], [f32; 2])>,
+) {
+    let (width, height) = painter.size();
+    for track in (1..track_count).step_by(2) {
+        let top = track as f32 * style.track_height;
+        painter.rect(
+            Rect::new([0.0, top], [width, top + style.track_height]),
+            style.alternate_track_color,
+        );
+    }
+    for time in ticks {
+        let x = axis.to_x(*time);
+        painter.line([x, 0.0], [x, height], 1.0, style.tick_color);
+    }
+
+    let x = axis.to_x(playhead);
+    painter.line([x, 0.0], [x, height], 2.0, style.playhead_color);
+
+    if let Some((min, max)) = selection_box {
+        let rect = Rect::new(min, max);
+        painter.rect(rect, style.selection_fill_color);
+        painter.rect_stroke(rect, 1.0, style.selection_stroke_color);
+    }
+}
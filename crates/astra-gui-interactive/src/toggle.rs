@@ -3,8 +3,8 @@
 //! Provides an iOS-style toggle switch with smooth animations.
 
 use astra_gui::{
-    catppuccin::mocha, Color, Component, CornerShape, Layout, Node, NodeId, Size, Spacing, Style,
-    Transition, UiContext,
+    Color, Component, CornerShape, Layout, Node, NodeId, Size, Spacing, Style, Theme, Transition,
+    UiContext,
 };
 use astra_gui_macros::WithBuilders;
 
@@ -27,12 +27,13 @@ pub struct ToggleStyle {
     pub knob_margin: f32,
 }
 
-impl Default for ToggleStyle {
-    fn default() -> Self {
+impl ToggleStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
         Self {
-            off_color: mocha::SURFACE0,
-            on_color: mocha::LAVENDER,
-            knob_color: mocha::BASE,
+            off_color: theme.field,
+            on_color: theme.primary,
+            knob_color: theme.surface,
             track_width: 50.0,
             track_height: 30.0,
             knob_width: 26.0,
@@ -41,6 +42,12 @@ impl Default for ToggleStyle {
     }
 }
 
+impl Default for ToggleStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A toggle switch component
 ///
 /// # Example
@@ -53,7 +60,7 @@ impl Default for ToggleStyle {
 pub struct Toggle {
     value: bool,
     disabled: bool,
-    style: ToggleStyle,
+    style: Option<ToggleStyle>,
     on_toggle: Option<Box<dyn FnMut(bool)>>,
 }
 
@@ -63,7 +70,7 @@ impl Toggle {
         Toggle {
             value,
             disabled: false,
-            style: ToggleStyle::default(),
+            style: None,
             on_toggle: None,
         }
     }
@@ -76,7 +83,7 @@ impl Toggle {
 
     /// Set a custom style for the toggle
     pub fn with_style(mut self, style: ToggleStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
@@ -93,6 +100,11 @@ impl Component for Toggle {
     fn node(mut self, ctx: &mut UiContext) -> Node {
         // Generate unique IDs for the toggle and its knob
         let id = ctx.generate_id("toggle");
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| ToggleStyle::from_theme(ctx.theme()));
+        let theme = *ctx.theme();
         let knob_id = format!("{}_knob", id);
 
         // Check for click events from last frame and fire callback
@@ -106,7 +118,7 @@ impl Component for Toggle {
         }
 
         let knob_offset_x = if self.value {
-            self.style.track_width - self.style.knob_width - self.style.knob_margin * 2.0
+            style.track_width - style.knob_width - style.knob_margin * 2.0
         } else {
             0.0
         };
@@ -114,24 +126,25 @@ impl Component for Toggle {
         // Track (background)
         Node::new()
             .with_id(NodeId::new(&id))
-            .with_width(Size::lpx(self.style.track_width))
-            .with_height(Size::lpx(self.style.track_height))
+            .with_width(Size::lpx(style.track_width))
+            .with_height(Size::lpx(style.track_height))
             .with_layout_direction(Layout::Horizontal)
-            .with_padding(Spacing::all(Size::lpx(self.style.knob_margin)))
+            .with_padding(Spacing::all(Size::lpx(style.knob_margin)))
+            .with_selected(self.value)
             .with_style(Style {
-                fill_color: Some(if self.value {
-                    self.style.on_color
-                } else {
-                    self.style.off_color
-                }),
+                fill_color: Some(style.off_color),
                 corner_shape: Some(CornerShape::Round(astra_gui::Size::Logical(
-                    self.style.track_height / 2.0,
+                    style.track_height / 2.0,
                 ))),
                 opacity: Some(1.0),
                 ..Default::default()
             })
+            .with_selected_style(Style {
+                fill_color: Some(style.on_color),
+                ..Default::default()
+            })
             .with_hover_style(Style {
-                fill_color: Some(mocha::SURFACE1),
+                fill_color: Some(theme.field_hover),
                 opacity: Some(0.9),
                 ..Default::default()
             })
@@ -140,7 +153,7 @@ impl Component for Toggle {
                 ..Default::default()
             })
             .with_disabled_style(Style {
-                fill_color: Some(mocha::SURFACE0),
+                fill_color: Some(theme.field),
                 opacity: Some(0.5),
                 ..Default::default()
             })
@@ -150,12 +163,12 @@ impl Component for Toggle {
                 // Knob (sliding circle with smooth offset animation)
                 Node::new()
                     .with_id(NodeId::new(&knob_id))
-                    .with_width(Size::lpx(self.style.knob_width))
+                    .with_width(Size::lpx(style.knob_width))
                     .with_height(Size::Fill)
                     .with_style(Style {
-                        fill_color: Some(self.style.knob_color),
+                        fill_color: Some(style.knob_color),
                         corner_shape: Some(CornerShape::Round(astra_gui::Size::Logical(
-                            self.style.knob_width / 2.0,
+                            style.knob_width / 2.0,
                         ))),
                         translation_x: Some(astra_gui::Size::Logical(knob_offset_x)),
                         ..Default::default()
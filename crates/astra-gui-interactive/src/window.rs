@@ -0,0 +1,437 @@
+//! Floating window component for interactive UI
+//!
+//! A panel with a title bar that can be dragged around its containing stack,
+//! resized from its edges and corners, collapsed to the title bar and closed.
+//! Pressing anywhere in a window raises it above the other windows. Position,
+//! size, collapse state and stacking order live in `WidgetMemory`, so only the
+//! content has to be rebuilt each frame.
+
+use std::f32::consts::PI;
+
+use astra_gui::{
+    Color, Component, Content, CornerShape, CursorIcon, HorizontalAlign, Layout, Node, NodeId,
+    Orientation, Overflow, Place, Shape, Size, Spacing, Stroke, Style, TextContent, Theme,
+    Transition, TriangleSpec, UiContext, VerticalAlign, ZIndex,
+};
+use astra_gui_macros::WithBuilders;
+use astra_gui_wgpu::InteractionEvent;
+
+use crate::{Resizable, ResizableStyle, ResizeHandle};
+
+/// Visual styling for a floating window
+#[derive(Debug, Clone, WithBuilders)]
+pub struct WindowStyle {
+    /// Background color of the window body
+    pub panel_color: Color,
+    /// Outline color of the window
+    pub stroke_color: Color,
+    /// Background color of the title bar
+    pub title_bar_color: Color,
+    /// Color of the title and the collapse indicator
+    pub title_color: Color,
+    /// Color of the close button's cross
+    pub close_color: Color,
+    /// Background color of a hovered close button
+    pub close_hover_color: Color,
+    /// Cross color of a hovered close button
+    pub close_hover_text_color: Color,
+    /// Font size of the title
+    pub title_font_size: f32,
+    /// Height of the title bar, which is all that shows while collapsed
+    pub title_bar_height: f32,
+    /// Side length of the collapse indicator
+    pub indicator_size: f32,
+    /// Internal padding of the window body
+    pub padding: f32,
+    /// Corner radius of the window
+    pub corner_radius: f32,
+    /// Styling of the edge and corner resize handles
+    pub resize_handles: ResizableStyle,
+}
+
+impl WindowStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            panel_color: theme.overlay,
+            stroke_color: theme.border,
+            title_bar_color: theme.surface,
+            title_color: theme.text,
+            close_color: theme.text_muted,
+            close_hover_color: theme.danger,
+            close_hover_text_color: theme.on_primary,
+            title_font_size: 15.0,
+            title_bar_height: 32.0,
+            indicator_size: 10.0,
+            padding: 12.0,
+            corner_radius: 8.0,
+            resize_handles: ResizableStyle::from_theme(theme),
+        }
+    }
+}
+
+impl Default for WindowStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A floating panel that can be moved, resized, collapsed, closed and raised
+///
+/// Add windows as children of a node with `Layout::Stack` (usually the root);
+/// the window places itself at its remembered position within that node. The
+/// default position and size only apply the first time a window is shown.
+/// Whether a window is open is owned by the app: stop building it once
+/// `on_close` reports that its close button was clicked.
+///
+/// # Example
+///
+/// ```ignore
+/// if self.inspector_open {
+///     children.push(
+///         Window::new("Inspector", inspector_content)
+///             .with_id("inspector")
+///             .default_position(40.0, 60.0)
+///             .default_size(280.0, 360.0)
+///             .on_close(|| self.inspector_open = false)
+///             .node(ctx),
+///     );
+/// }
+/// ```
+pub struct Window<'a> {
+    title: String,
+    content: Node,
+    default_position: (f32, f32),
+    default_size: (f32, f32),
+    min_size: (f32, f32),
+    max_size: (f32, f32),
+    resizable: bool,
+    collapsible: bool,
+    id: Option<String>,
+    style: Option<WindowStyle>,
+    on_close: Option<Box<dyn FnMut() + 'a>>,
+}
+
+impl<'a> Window<'a> {
+    /// Create a window with the given title and content
+    pub fn new(title: impl Into<String>, content: Node) -> Self {
+        Self {
+            title: title.into(),
+            content,
+            default_position: (40.0, 40.0),
+            default_size: (320.0, 240.0),
+            min_size: (160.0, 80.0),
+            max_size: (f32::INFINITY, f32::INFINITY),
+            resizable: true,
+            collapsible: true,
+            id: None,
+            style: None,
+            on_close: None,
+        }
+    }
+
+    /// Set where the window first appears, in logical pixels from the
+    /// containing stack's content origin
+    pub fn default_position(mut self, x: f32, y: f32) -> Self {
+        self.default_position = (x, y);
+        self
+    }
+
+    /// Set the size the window first appears with, in logical pixels
+    pub fn default_size(mut self, width: f32, height: f32) -> Self {
+        self.default_size = (width, height);
+        self
+    }
+
+    /// Set the minimum size when resizing, in logical pixels
+    pub fn min_size(mut self, width: f32, height: f32) -> Self {
+        self.min_size = (width.max(0.0), height.max(0.0));
+        self
+    }
+
+    /// Set the maximum size when resizing, in logical pixels
+    pub fn max_size(mut self, width: f32, height: f32) -> Self {
+        self.max_size = (width, height);
+        self
+    }
+
+    /// Set whether the edges and corners can be dragged to resize (default true)
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Set whether the title bar shows a collapse button (default true)
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    /// Use an explicit ID instead of a generated one
+    ///
+    /// Position, size and stacking order are remembered per ID, so windows
+    /// that come and go should have explicit IDs.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set a custom style for the window
+    pub fn with_style(mut self, style: WindowStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Show a close button calling `f` when clicked
+    pub fn on_close(mut self, f: impl FnMut() + 'a) -> Self {
+        self.on_close = Some(Box::new(f));
+        self
+    }
+
+    /// Size for a drag of `offset` logical pixels on `handle`, starting at `start`
+    fn resized(&self, handle: ResizeHandle, start: (f32, f32), offset: (f32, f32)) -> (f32, f32) {
+        let (dir_x, dir_y) = handle.direction();
+        let (min_w, min_h) = self.min_size;
+        let (max_w, max_h) = self.max_size;
+        (
+            (start.0 + offset.0 * dir_x).clamp(min_w, max_w.max(min_w)),
+            (start.1 + offset.1 * dir_y).clamp(min_h, max_h.max(min_h)),
+        )
+    }
+}
+
+impl Component for Window<'_> {
+    fn node(mut self, ctx: &mut UiContext) -> Node {
+        let id = self.id.take().unwrap_or_else(|| ctx.generate_id("window"));
+        let style = self
+            .style
+            .take()
+            .unwrap_or_else(|| WindowStyle::from_theme(ctx.theme()));
+        let scale = ctx.scale_factor();
+        let title_id = format!("{}_title", id);
+        let collapse_id = format!("{}_collapse", id);
+        let close_id = format!("{}_close", id);
+        let handle_prefix = format!("{}_resize_", id);
+
+        let mut state = *ctx
+            .memory()
+            .window(&id, self.default_position, self.default_size);
+
+        if ctx.was_pressed_inside(&id) {
+            ctx.memory().window_stack().raise(&id);
+        }
+        if self.collapsible && ctx.was_clicked(&collapse_id) {
+            state.collapsed = !state.collapsed;
+        }
+        if ctx.was_clicked(&close_id) {
+            if let Some(ref mut on_close) = self.on_close {
+                on_close();
+            }
+        }
+
+        // The title bar moves the window; handles resize it, moving the origin
+        // when dragged from the left or top
+        let mut dragged = None;
+        let events: Vec<_> = ctx.events().to_vec();
+        for event in &events {
+            let target = event.target.as_str();
+            let handle = target.strip_prefix(&handle_prefix).and_then(|name| {
+                ResizeHandle::ALL
+                    .into_iter()
+                    .find(|handle| handle.name() == name)
+            });
+            if target != title_id && handle.is_none() {
+                continue;
+            }
+            if handle.is_some() && (!self.resizable || state.collapsed) {
+                continue;
+            }
+
+            match &event.event {
+                InteractionEvent::DragStart { .. } => {
+                    state.drag_start = (state.position, state.size);
+                    state.drag_offset = (0.0, 0.0);
+                    state.dragging = true;
+                }
+                InteractionEvent::DragMove { delta, .. } if state.dragging => {
+                    state.drag_offset.0 += delta.x / (scale * event.zoom);
+                    state.drag_offset.1 += delta.y / (scale * event.zoom);
+                    dragged = Some(handle);
+                }
+                InteractionEvent::DragEnd { .. } => state.dragging = false,
+                _ => {}
+            }
+        }
+
+        let (start_position, start_size) = state.drag_start;
+        match dragged {
+            Some(None) => {
+                // Keep the title bar below the top edge so it can be grabbed again
+                state.position = (
+                    start_position.0 + state.drag_offset.0,
+                    (start_position.1 + state.drag_offset.1).max(0.0),
+                );
+            }
+            Some(Some(handle)) => {
+                let size = self.resized(handle, start_size, state.drag_offset);
+                let (dir_x, dir_y) = handle.direction();
+                if dir_x < 0.0 {
+                    state.position.0 = start_position.0 + start_size.0 - size.0;
+                }
+                if dir_y < 0.0 {
+                    state.position.1 = start_position.1 + start_size.1 - size.1;
+                }
+                state.size = size;
+            }
+            None => {}
+        }
+        *ctx.memory()
+            .window(&id, self.default_position, self.default_size) = state;
+
+        // Windows stack above regular content and below popups and tooltips
+        let depth = ctx.memory().window_stack().depth(&id);
+        let z_index =
+            ZIndex(ZIndex::DEFAULT.0 + 1 + depth.min(ZIndex::OVERLAY.0 as usize - 2) as i32);
+
+        let mut title_bar_children = Vec::new();
+        if self.collapsible {
+            // Points right while collapsed and down while expanded
+            title_bar_children.push(
+                Node::new()
+                    .with_id(NodeId::new(&collapse_id))
+                    .with_width(Size::lpx(style.indicator_size))
+                    .with_height(Size::lpx(style.indicator_size))
+                    .with_cursor(CursorIcon::Pointer)
+                    .with_shape(Shape::triangle_with_spec(TriangleSpec::Equilateral {
+                        orientation: Orientation::Right,
+                    }))
+                    .with_style(Style {
+                        rotation: Some(if state.collapsed { 0.0 } else { PI / 2.0 }),
+                        fill_color: Some(style.title_color),
+                        ..Default::default()
+                    })
+                    .with_transition(Transition::quick()),
+            );
+        }
+        title_bar_children.push(
+            Node::new()
+                .with_width(Size::Fill)
+                .with_height(Size::Fill)
+                .with_content(Content::Text(
+                    TextContent::new(self.title)
+                        .with_font_size(Size::lpx(style.title_font_size))
+                        .with_color(style.title_color)
+                        .with_h_align(HorizontalAlign::Left)
+                        .with_v_align(VerticalAlign::Center),
+                )),
+        );
+        if self.on_close.is_some() {
+            let close_size = style.title_bar_height * 0.65;
+            title_bar_children.push(
+                Node::new()
+                    .with_id(NodeId::new(&close_id))
+                    .with_width(Size::lpx(close_size))
+                    .with_height(Size::lpx(close_size))
+                    .with_cursor(CursorIcon::Pointer)
+                    .with_content(Content::Text(
+                        TextContent::new("\u{00D7}")
+                            .with_font_size(Size::lpx(style.title_font_size * 1.2))
+                            .with_color(style.close_color)
+                            .with_h_align(HorizontalAlign::Center)
+                            .with_v_align(VerticalAlign::Center),
+                    ))
+                    .with_style(Style {
+                        fill_color: Some(style.close_hover_color.with_alpha(0.0)),
+                        text_color: Some(style.close_color),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(close_size / 2.0))),
+                        ..Default::default()
+                    })
+                    .with_hover_style(Style {
+                        fill_color: Some(style.close_hover_color),
+                        text_color: Some(style.close_hover_text_color),
+                        ..Default::default()
+                    })
+                    .with_transition(Transition::quick()),
+            );
+        }
+
+        let moving = state.dragging && ctx.is_dragging(&title_id);
+        let title_bar = Node::new()
+            .with_id(NodeId::new(&title_id))
+            .with_width(Size::Fill)
+            .with_height(Size::lpx(style.title_bar_height))
+            .with_padding(Spacing::symmetric(Size::lpx(style.padding), Size::lpx(0.0)))
+            .with_gap(Size::lpx(style.padding * 0.75))
+            .with_layout_direction(Layout::Horizontal)
+            .with_v_align(VerticalAlign::Center)
+            .with_cursor(if moving {
+                CursorIcon::Grabbing
+            } else {
+                CursorIcon::Grab
+            })
+            .with_style(Style {
+                fill_color: Some(style.title_bar_color),
+                ..Default::default()
+            })
+            .with_children(title_bar_children);
+
+        let mut panel_children = vec![title_bar];
+        if !state.collapsed {
+            panel_children.push(
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_padding(Spacing::all(Size::lpx(style.padding)))
+                    .with_overflow(Overflow::Hidden)
+                    .with_child(self.content),
+            );
+        }
+        let panel = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Vertical)
+            .with_overflow(Overflow::Hidden)
+            .with_style(Style {
+                fill_color: Some(style.panel_color),
+                stroke: Some(Stroke::new(Size::lpx(1.0), style.stroke_color)),
+                corner_shape: Some(CornerShape::Round(Size::lpx(style.corner_radius))),
+                ..Default::default()
+            })
+            .with_children(panel_children);
+
+        let height = if state.collapsed {
+            style.title_bar_height
+        } else {
+            state.size.1
+        };
+        let mut node = Node::new()
+            .with_id(NodeId::new(&id))
+            .with_width(Size::lpx(state.size.0))
+            .with_height(Size::lpx(height))
+            .with_place(Place::Absolute {
+                x: Size::lpx(state.position.0),
+                y: Size::lpx(state.position.1),
+            })
+            .with_z_index(z_index)
+            .with_layout_direction(Layout::Stack)
+            .with_child(panel);
+
+        if self.resizable && !state.collapsed {
+            // Corners come last so they sit above the edges they overlap
+            let mut handles = ResizeHandle::ALL.to_vec();
+            handles.sort_by_key(|h| h.direction().0 != 0.0 && h.direction().1 != 0.0);
+            for handle in handles {
+                let handle_id = format!("{}{}", handle_prefix, handle.name());
+                let active = state.dragging && ctx.is_dragging(&handle_id);
+                node = node.with_child(Resizable::handle_node(
+                    handle_id,
+                    handle,
+                    &style.resize_handles,
+                    active,
+                ));
+            }
+        }
+
+        node
+    }
+}
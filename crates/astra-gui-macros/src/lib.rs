@@ -3,6 +3,8 @@
 //! Currently provided:
 //! - `#[derive(WithBuilders)]`: generates `with_<field>(...)` builder-style methods
 //!   for each named field in a struct.
+//! - `style! { ... }`: concise `Style` / `StyleRule` construction, re-exported
+//!   as `astra_gui::style!`.
 //!
 //! ## Field control
 //! You can exclude specific fields from builder generation using `#[with_builders(skip)]`
@@ -24,10 +26,47 @@
 //! // .with_debug_only(...) is NOT generated.
 //! ```
 
+mod style;
+
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields};
 
+/// Build a `Style` from `property: value` pairs
+///
+/// Numbers are accepted wherever a `Size` is expected and mean logical
+/// pixels. Properties: `fill`, `gradient`, `linear_gradient`, `nine_slice`, `stroke` (a
+/// `Stroke` or `(width, color)`), `outline`, `shadow`, `inset_shadow`, `tint`,
+/// `radius` (rounded corners) or `corner` (any `CornerShape`), `opacity`,
+/// `text`, `cursor`, `translate_x`, `translate_y`, `rotation`, `origin`,
+/// `elevation` and `animation` (may repeat).
+///
+/// With a `hover`, `active`, `focused`, `selected` or `disabled` block, a
+/// `padding` or a `transition`, the macro builds a `StyleRule` instead, for
+/// `Node::with_style_rule` or a `StyleSheet`. Inside `padding`, `all`,
+/// `symmetric`, `trbl`, `horizontal`, `vertical`, `top`, `right`, `bottom`
+/// and `left` take numbers too.
+///
+/// ```ignore
+/// use astra_gui::{catppuccin::mocha, style};
+///
+/// let card = style! { fill: mocha::SURFACE0, radius: 12, elevation: 2 };
+/// let button = style! {
+///     fill: mocha::SURFACE0,
+///     radius: 12,
+///     padding: all(8),
+///     hover: { fill: mocha::SURFACE1 },
+///     transition: Transition::quick(),
+/// };
+/// ```
+#[proc_macro]
+pub fn style(input: TokenStream) -> TokenStream {
+    let entries = parse_macro_input!(input as style::Entries);
+    style::expand(entries)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Derive that generates `with_<field>` builder methods for structs with named fields.
 ///
 /// Generated methods take `self` by value (builder style) and return `Self`.
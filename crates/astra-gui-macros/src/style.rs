@@ -0,0 +1,235 @@
+//! Parsing and expansion for `style! { ... }`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, token, Expr, Ident, Token};
+
+/// Interaction states that take a nested `{ ... }` block of style properties
+const STATES: [(&str, &str); 5] = [
+    ("hover", "with_hover_style"),
+    ("active", "with_active_style"),
+    ("focused", "with_focused_style"),
+    ("selected", "with_selected_style"),
+    ("disabled", "with_disabled_style"),
+];
+
+pub struct Entries(Vec<Entry>);
+
+struct Entry {
+    key: Ident,
+    value: Value,
+}
+
+enum Value {
+    Expr(Expr),
+    Block(Entries),
+}
+
+impl Parse for Entries {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut entries = Vec::new();
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+
+            // Only state keys take a nested block, so `fill: { ... }` stays a
+            // block expression
+            let is_state = STATES.iter().any(|(state, _)| key == state);
+            let value = if is_state && input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                Value::Block(content.parse()?)
+            } else {
+                Value::Expr(input.parse()?)
+            };
+            entries.push(Entry { key, value });
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self(entries))
+    }
+}
+
+/// Expand to a `Style`, or to a `StyleRule` when states, padding or a
+/// transition are given
+pub fn expand(entries: Entries) -> syn::Result<TokenStream> {
+    let mut base = Vec::new();
+    let mut rule = Vec::new();
+    let mut seen = Vec::new();
+
+    for entry in entries.0 {
+        let name = entry.key.to_string();
+        if seen.contains(&name) {
+            return Err(syn::Error::new(
+                entry.key.span(),
+                format!("duplicate style property `{name}`"),
+            ));
+        }
+
+        if let Some((_, method)) = STATES.iter().find(|(state, _)| *state == name) {
+            let Value::Block(block) = entry.value else {
+                return Err(syn::Error::new(
+                    entry.key.span(),
+                    format!("expected a block of style properties: `{name}: {{ ... }}`"),
+                ));
+            };
+            let method = Ident::new(method, entry.key.span());
+            let style = expand_style(block.0)?;
+            rule.push(quote! { .#method(#style) });
+            seen.push(name);
+            continue;
+        }
+
+        let Value::Expr(value) = &entry.value else {
+            unreachable!("only state keys parse as blocks");
+        };
+        match name.as_str() {
+            "padding" => rule.push(quote! {
+                .with_padding(::astra_gui::__private::spacing({
+                    #[allow(unused_imports)]
+                    use ::astra_gui::__private::padding::*;
+                    #value
+                }))
+            }),
+            "transition" => rule.push(quote! { .with_transition(#value) }),
+            _ => {
+                base.push(entry);
+                continue;
+            }
+        }
+        seen.push(name);
+    }
+
+    let base_empty = base.is_empty();
+    let style = expand_style(base)?;
+    if rule.is_empty() {
+        return Ok(style);
+    }
+    let with_style = (!base_empty).then(|| quote! { .with_style(#style) });
+    Ok(quote! {
+        ::astra_gui::StyleRule::new() #with_style #(#rule)*
+    })
+}
+
+/// Expand style properties to a `Style` struct literal
+fn expand_style(entries: Vec<Entry>) -> syn::Result<TokenStream> {
+    let mut fields = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    let mut elevation = None;
+    let mut animations = Vec::new();
+
+    for entry in &entries {
+        let name = entry.key.to_string();
+        let span = entry.key.span();
+        let value = match &entry.value {
+            Value::Expr(value) => value,
+            Value::Block(_) => {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{name}` blocks can't be nested inside another state"),
+                ))
+            }
+        };
+
+        let (field, expr) = match name.as_str() {
+            "fill" | "fill_color" => ("fill_color", quote! { #value }),
+            "gradient" => ("gradient", quote! { #value }),
+            "linear_gradient" => ("linear_gradient", quote! { #value }),
+            "nine_slice" => ("nine_slice", quote! { #value }),
+            "stroke" => ("stroke", stroke(value)),
+            "outline" => ("outline", quote! { #value }),
+            "shadow" => ("shadow", quote! { #value }),
+            "inset_shadow" => ("inset_shadow", quote! { #value }),
+            "tint" => ("tint", quote! { #value }),
+            "radius" => (
+                "corner_shape",
+                quote! { ::astra_gui::__private::corner_shape(#value) },
+            ),
+            "corner" | "corner_shape" => ("corner_shape", quote! { #value }),
+            "opacity" => ("opacity", quote! { #value }),
+            "text" | "text_color" => ("text_color", quote! { #value }),
+            "cursor" | "cursor_color" => ("cursor_color", quote! { #value }),
+            "translate_x" | "translation_x" => (
+                "translation_x",
+                quote! { ::astra_gui::__private::size(#value) },
+            ),
+            "translate_y" | "translation_y" => (
+                "translation_y",
+                quote! { ::astra_gui::__private::size(#value) },
+            ),
+            "rotation" => ("rotation", quote! { #value }),
+            "origin" | "transform_origin" => ("transform_origin", quote! { #value }),
+            "elevation" => {
+                if elevation.replace(value).is_some() {
+                    return Err(syn::Error::new(
+                        span,
+                        "duplicate style property `elevation`",
+                    ));
+                }
+                continue;
+            }
+            "animation" => {
+                animations.push(value);
+                continue;
+            }
+            "padding" | "transition" => {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{name}` is only allowed at the top level of `style!`"),
+                ))
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    span,
+                    format!("unknown style property `{name}`"),
+                ))
+            }
+        };
+
+        if seen.contains(&field) {
+            return Err(syn::Error::new(
+                span,
+                format!("`{name}` sets `{field}`, which is already set"),
+            ));
+        }
+        seen.push(field);
+
+        let field = Ident::new(field, span);
+        fields.push(quote! { #field: ::core::option::Option::Some(#expr), });
+    }
+
+    if !animations.is_empty() {
+        fields.push(quote! { animations: ::std::vec![#(#animations),*], });
+    }
+
+    let style = quote! {
+        ::astra_gui::Style {
+            #(#fields)*
+            ..::core::default::Default::default()
+        }
+    };
+    Ok(match elevation {
+        // Explicit properties win over the elevation's shadow and tint
+        Some(level) => quote! { ::astra_gui::Style::elevation(#level).merge(&#style) },
+        None => style,
+    })
+}
+
+/// `stroke: (width, color)` builds a `Stroke` from a size and a color;
+/// anything else is used as the `Stroke` itself
+fn stroke(value: &Expr) -> TokenStream {
+    match value {
+        Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+            let width = &tuple.elems[0];
+            let color = &tuple.elems[1];
+            quote! {
+                ::astra_gui::Stroke::new(::astra_gui::__private::size(#width), #color)
+            }
+        }
+        _ => quote! { #value },
+    }
+}
@@ -7,23 +7,54 @@
 //!
 //! # Current implementation
 //! The `cosmic` feature provides an implementation using `cosmic-text`.
-//! Fonts are provided by `astra-gui-fonts` (Inter by default).
+//! Fonts are provided by `astra-gui-fonts` (Inter by default). Characters the
+//! requested family lacks (CJK, symbols, emoji) are taken from a chain of
+//! fallback families, see [`Engine::set_fallback_families`].
 //!
 //! Renderers are expected to:
 //! 1. Call [`TextEngine::shape_line`] (or later multi-line APIs) for each `TextShape`.
 //! 2. Ensure glyphs are present in their atlas by calling [`TextEngine::rasterize_glyph`].
+//!    Color glyphs (emoji) come back as RGBA and need an atlas of their own, see [`GlyphFormat`].
 //! 3. Build quads from [`PositionedGlyph`] + atlas UVs and apply clipping via scissor.
 //!
+//! Glyphs can also be rasterized as size-independent distance fields
+//! ([`TextEngine::rasterize_glyph_sdf`]) for renderers that scale text freely.
+//!
 //! NOTE: This crate intentionally does not manage an atlas; that is backend-specific.
 
 #![deny(warnings)]
 
+pub mod sdf;
+
 use astra_gui::{
-    ContentMeasurer, HorizontalAlign, IntrinsicSize, MeasureTextRequest, Rect, VerticalAlign, Wrap,
+    CacheStats, ContentMeasurer, HorizontalAlign, IntrinsicSize, MeasureTextRequest, Rect,
+    TextOverflow, TextSpan, VerticalAlign, Wrap,
 };
 
 // Re-export for convenience
-pub use astra_gui::{FontStyle, FontWeight};
+pub use astra_gui::{FontFeature, FontStyle, FontWeight, FontWidth};
+
+/// Family used when a request doesn't name one (bundled with `astra-gui-fonts`)
+pub const DEFAULT_FAMILY: &str = "Inter";
+
+/// Fallback families an engine starts with, covering CJK, symbols and emoji on
+/// common Linux, macOS and Windows installs
+///
+/// Families that aren't installed are skipped.
+pub const DEFAULT_FALLBACK_FAMILIES: &[&str] = &[
+    "Noto Sans",
+    "Noto Sans CJK SC",
+    "Noto Sans Symbols",
+    "Noto Sans Symbols 2",
+    "Noto Color Emoji",
+    "DejaVu Sans",
+    "PingFang SC",
+    "Apple Symbols",
+    "Apple Color Emoji",
+    "Microsoft YaHei",
+    "Segoe UI Symbol",
+    "Segoe UI Emoji",
+];
 
 /// A stable identifier for a font face known to the text engine.
 ///
@@ -39,31 +70,101 @@ pub struct FontId(pub u64);
 /// - `font_id` is engine owned.
 /// - `glyph_id` is the font-specific glyph index (not Unicode scalar value).
 /// - `px_size` is the requested font size in pixels (rounded).
-/// - `subpixel_x_64` allows subpixel positioning when/if we choose to support it.
-///   For now we default to 0 in the cosmic implementation.
+/// - `subpixel_x_64` is the horizontal offset of the glyph within its pixel, in 1/64 px,
+///   quantized to [`SUBPIXEL_BINS`] bins (see [`GlyphKey::snap_x`]).
+/// - `weight` is the weight used during shaping (variable fonts rasterize with it).
+/// - `antialiasing` selects grayscale or LCD coverage.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct GlyphKey {
     pub font_id: FontId,
     pub glyph_id: u32,
     pub px_size: u16,
     pub subpixel_x_64: i16,
+    pub weight: u16,
+    pub antialiasing: GlyphAntialiasing,
 }
 
+/// Number of horizontal subpixel positions a glyph is rasterized at
+pub const SUBPIXEL_BINS: u8 = 4;
+
 impl GlyphKey {
-    pub fn new(font_id: FontId, glyph_id: u32, px_size: u16, subpixel_x_64: i16) -> Self {
+    /// Key for a grayscale glyph at a whole pixel position
+    pub fn new(font_id: FontId, glyph_id: u32, px_size: u16, weight: u16) -> Self {
         Self {
             font_id,
             glyph_id,
             px_size,
-            subpixel_x_64,
+            subpixel_x_64: 0,
+            weight,
+            antialiasing: GlyphAntialiasing::Grayscale,
+        }
+    }
+
+    /// Quantize a glyph origin `x_px` into a whole pixel and a subpixel bin
+    ///
+    /// Returns this key with `subpixel_x_64` set to the bin and the whole pixel x
+    /// the bitmap (with its bearing) should be placed at.
+    pub fn snap_x(self, x_px: f32) -> (Self, f32) {
+        let bins = SUBPIXEL_BINS as f32;
+        let binned = (x_px * bins).round();
+        let whole = (binned / bins).floor();
+        let bin = binned - whole * bins;
+        let subpixel_x_64 = (bin * 64.0 / bins) as i16;
+        (
+            Self {
+                subpixel_x_64,
+                ..self
+            },
+            whole,
+        )
+    }
+
+    /// Horizontal subpixel offset in pixels
+    pub fn subpixel_x(&self) -> f32 {
+        self.subpixel_x_64 as f32 / 64.0
+    }
+
+    /// This key with different antialiasing
+    pub fn with_antialiasing(self, antialiasing: GlyphAntialiasing) -> Self {
+        Self {
+            antialiasing,
+            ..self
         }
     }
 }
 
-/// CPU-side glyph coverage bitmap suitable for uploading into an `R8Unorm` atlas.
+/// How glyph edges are antialiased
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum GlyphAntialiasing {
+    /// One coverage value per pixel; looks right on any display and under rotation
+    #[default]
+    Grayscale,
+    /// Separate coverage for the red, green and blue stripes of horizontal-RGB LCD
+    /// panels, tripling horizontal resolution (produces [`GlyphFormat::Lcd`] bitmaps)
+    Lcd,
+}
+
+/// Pixel format of a rasterized glyph
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GlyphFormat {
+    /// One coverage byte per pixel, tinted with the text color (`R8Unorm` atlas)
+    Mask,
+    /// Four bytes per pixel of non-premultiplied sRGB RGBA, drawn as-is
+    /// (emoji, COLR and bitmap color fonts; `Rgba8UnormSrgb` atlas)
+    Color,
+    /// One signed distance byte per pixel, 128 on the outline (`R8Unorm` atlas,
+    /// sampled with linear filtering), see [`sdf`]
+    Sdf,
+    /// Four bytes per pixel: red, green and blue subpixel coverage plus unused
+    /// alpha, tinted with the text color; produced for [`GlyphAntialiasing::Lcd`]
+    Lcd,
+}
+
+/// CPU-side glyph bitmap suitable for uploading into an atlas of its `format`.
 #[derive(Clone, Debug)]
 pub struct GlyphBitmap {
     pub key: GlyphKey,
+    pub format: GlyphFormat,
     /// Bitmap dimensions in pixels: (width, height).
     pub size_px: [u32; 2],
     /// Glyph bearing in pixels (left, top) relative to the pen position.
@@ -74,7 +175,8 @@ pub struct GlyphBitmap {
     pub bearing_px: [i32; 2],
     /// Glyph advance in pixels to apply to the pen after drawing.
     pub advance_px: [f32; 2],
-    /// Row-major coverage bytes (0..=255), length = `width * height`.
+    /// Row-major pixel bytes: coverage (0..=255) with length `width * height`
+    /// for masks, RGBA with length `width * height * 4` for color and LCD glyphs.
     pub pixels: Vec<u8>,
 }
 
@@ -83,11 +185,40 @@ pub struct GlyphBitmap {
 /// Renderers typically use:
 /// - `x_px`, `y_px` + bitmap bearing to compute the quad in screen space
 /// - `key` to obtain atlas UVs for the glyph coverage bitmap
+///
+/// `x_px` is a whole pixel; the fractional pen position is `key.subpixel_x_64`,
+/// which the bitmap already includes. Renderers that place lines at fractional
+/// positions can re-quantize with [`GlyphKey::snap_x`].
 #[derive(Clone, Copy, Debug)]
 pub struct PositionedGlyph {
     pub key: GlyphKey,
     pub x_px: f32,
     pub y_px: f32,
+    /// Index of the rich text span this glyph belongs to (`None` for plain text)
+    pub span: Option<usize>,
+}
+
+/// A line drawn under part of a shaped line, in the same line-top-left space
+/// as [`PositionedGlyph`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Underline {
+    pub x_px: f32,
+    pub y_px: f32,
+    pub width_px: f32,
+    pub thickness_px: f32,
+    /// Index of the rich text span that is underlined
+    pub span: usize,
+}
+
+/// A place the caret can sit on a shaped line
+///
+/// `index` is a byte offset into the shaped text and `x_px` its position in
+/// the same line-top-left space as [`PositionedGlyph`]. Stops sit on cluster
+/// boundaries, so a ligature or combining sequence is skipped as a whole.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CaretStop {
+    pub index: usize,
+    pub x_px: f32,
 }
 
 /// Metric information for placing a shaped line inside a rectangular layout box.
@@ -103,7 +234,14 @@ pub struct LineMetrics {
 #[derive(Clone, Debug, Default)]
 pub struct ShapedLine {
     pub glyphs: Vec<PositionedGlyph>,
+    /// Underlines of rich text spans on this line
+    pub underlines: Vec<Underline>,
     pub metrics: LineMetrics,
+    /// Line-space x range over which the line fades from opaque to fully
+    /// transparent (`TextOverflow::Fade` on an overflowing line)
+    pub fade_out_px: Option<[f32; 2]>,
+    /// Caret positions on this line in text order; an empty line has one at its start
+    pub carets: Vec<CaretStop>,
 }
 
 /// Multi-line shaped text output
@@ -114,6 +252,101 @@ pub struct ShapedText {
     pub total_height: f32, // Sum of line heights
 }
 
+impl ShapedText {
+    /// Top-left corner of each line when the text is aligned in `rect`, the way
+    /// renderers place it (`v_align` moves the whole block, `h_align` each line)
+    pub fn line_origins(
+        &self,
+        rect: Rect,
+        h_align: HorizontalAlign,
+        v_align: VerticalAlign,
+    ) -> impl Iterator<Item = [f32; 2]> + '_ {
+        let mut y = align_origin(rect, 0.0, self.total_height, h_align, v_align)[1];
+        self.lines.iter().map(move |line| {
+            let origin = [
+                align_origin(rect, line.metrics.width_px, 0.0, h_align, v_align)[0],
+                y,
+            ];
+            y += line.metrics.height_px;
+            origin
+        })
+    }
+
+    /// Byte offset of the caret closest to `point`, with the text aligned in `rect`
+    ///
+    /// Points above or below the text pick a caret on the first or last line.
+    pub fn caret_index_at(
+        &self,
+        point: [f32; 2],
+        rect: Rect,
+        h_align: HorizontalAlign,
+        v_align: VerticalAlign,
+    ) -> usize {
+        let mut lines = self
+            .lines
+            .iter()
+            .zip(self.line_origins(rect, h_align, v_align));
+        let Some(mut nearest) = lines.next() else {
+            return 0;
+        };
+        // The line under the point, or the last one above it
+        for (line, origin) in lines {
+            if point[1] < origin[1] {
+                break;
+            }
+            nearest = (line, origin);
+        }
+
+        let (line, origin) = nearest;
+        line.carets
+            .iter()
+            .min_by(|a, b| {
+                let distance = |stop: &CaretStop| (origin[0] + stop.x_px - point[0]).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .map_or(0, |stop| stop.index)
+    }
+
+    /// Rect of the caret at byte offset `index`, with the text aligned in `rect`
+    ///
+    /// The rect has zero width and the height of its line; renderers widen it to
+    /// their caret width. An index inside a cluster gets the caret before it, and
+    /// an index where a wrapped line breaks gets the start of the next line.
+    pub fn caret_rect(
+        &self,
+        index: usize,
+        rect: Rect,
+        h_align: HorizontalAlign,
+        v_align: VerticalAlign,
+    ) -> Option<Rect> {
+        let mut lines = self
+            .lines
+            .iter()
+            .zip(self.line_origins(rect, h_align, v_align));
+        let mut nearest = lines.next()?;
+        // The last line starting at or before `index`
+        for (line, origin) in lines {
+            if line.carets.first().is_some_and(|stop| stop.index > index) {
+                break;
+            }
+            nearest = (line, origin);
+        }
+
+        let (line, origin) = nearest;
+        let stop = line
+            .carets
+            .iter()
+            .take_while(|stop| stop.index <= index)
+            .last()
+            .or(line.carets.first())?;
+        let x = origin[0] + stop.x_px;
+        Some(Rect::new(
+            [x, origin[1]],
+            [x, origin[1] + line.metrics.height_px],
+        ))
+    }
+}
+
 /// Input describing a single-line shaping request.
 ///
 /// This intentionally stays close to `astra-gui`'s current `TextShape` information.
@@ -126,33 +359,55 @@ pub struct ShapeLineRequest<'a> {
     pub font_px: f32,
     pub h_align: HorizontalAlign,
     pub v_align: VerticalAlign,
-    /// Optional family name (engine-defined meaning). For now, Inter is used by default.
+    /// Preferred family name; `None` uses [`DEFAULT_FAMILY`]. Characters it
+    /// has no glyphs for fall back to the engine's fallback families.
     pub family: Option<&'a str>,
     /// Font weight (100-900)
     pub font_weight: u16,
     /// Font style (normal or italic)
     pub font_style: FontStyle,
+    /// OpenType features applied on top of the font's defaults
+    pub font_features: &'a [FontFeature],
+    /// Font width, picking among the family's faces
+    pub font_width: FontWidth,
+    /// Distance between tab stops, in widths of a space
+    pub tab_width: u16,
 }
 
 /// Input describing a multi-line text shaping request.
 #[derive(Clone, Debug)]
 pub struct ShapeTextRequest<'a> {
     pub text: &'a str,
+    /// Styled runs making up `text`; empty for plain text. Glyphs report the
+    /// span they belong to so renderers can color them.
+    pub spans: &'a [TextSpan],
     /// Layout box (content rect) to align within.
     pub rect: Rect,
     pub font_px: f32,
     pub h_align: HorizontalAlign,
     pub v_align: VerticalAlign,
-    /// Optional family name (engine-defined meaning). For now, Inter is used by default.
+    /// Preferred family name; `None` uses [`DEFAULT_FAMILY`]. Characters it
+    /// has no glyphs for fall back to the engine's fallback families.
     pub family: Option<&'a str>,
     /// Text wrapping mode
     pub wrap: Wrap,
+    /// Handling of lines still wider than `rect` after wrapping
+    pub overflow: TextOverflow,
+    /// Stop after this many lines; an ellipsis overflow marks the cut on the
+    /// last kept line
+    pub max_lines: Option<usize>,
     /// Line height as a multiplier of font size
     pub line_height_multiplier: f32,
     /// Font weight (100-900)
     pub font_weight: u16,
     /// Font style (normal or italic)
     pub font_style: FontStyle,
+    /// OpenType features applied on top of the font's defaults
+    pub font_features: &'a [FontFeature],
+    /// Font width, picking among the family's faces
+    pub font_width: FontWidth,
+    /// Distance between tab stops, in widths of a space
+    pub tab_width: u16,
 }
 
 /// Output describing how to place a line in a rectangle.
@@ -178,6 +433,41 @@ pub trait TextEngine {
     ///
     /// Engines may internally cache bitmaps; callers should still keep an atlas cache.
     fn rasterize_glyph(&mut self, key: GlyphKey) -> Option<GlyphBitmap>;
+
+    /// Rasterize a size-independent signed distance field of the glyph
+    ///
+    /// The field is rasterized at [`sdf::SDF_REFERENCE_PX`] whatever `key.px_size`
+    /// is, so one atlas entry serves every size; renderers scale the quad by
+    /// `px_size / SDF_REFERENCE_PX`. Subpixel offset and antialiasing are ignored.
+    /// Color glyphs come back as color bitmaps at the reference size.
+    fn rasterize_glyph_sdf(&mut self, key: GlyphKey) -> Option<GlyphBitmap> {
+        let bitmap = self.rasterize_glyph(GlyphKey {
+            px_size: sdf::SDF_REFERENCE_PX,
+            subpixel_x_64: 0,
+            antialiasing: GlyphAntialiasing::Grayscale,
+            ..key
+        })?;
+        Some(sdf::glyph_to_sdf(bitmap))
+    }
+
+    /// Byte offset of the caret closest to `point` in the text `req` shapes
+    ///
+    /// See [`ShapedText::caret_index_at`]; callers that keep the shaped text
+    /// around should use that instead of shaping again.
+    fn caret_index_at(&mut self, req: ShapeTextRequest<'_>, point: [f32; 2]) -> usize {
+        let (rect, h_align, v_align) = (req.rect, req.h_align, req.v_align);
+        let (shaped, _) = self.shape_text(req);
+        shaped.caret_index_at(point, rect, h_align, v_align)
+    }
+
+    /// Rect of the caret at byte offset `index` in the text `req` shapes
+    ///
+    /// See [`ShapedText::caret_rect`].
+    fn caret_rect(&mut self, req: ShapeTextRequest<'_>, index: usize) -> Option<Rect> {
+        let (rect, h_align, v_align) = (req.rect, req.h_align, req.v_align);
+        let (shaped, _) = self.shape_text(req);
+        shaped.caret_rect(index, rect, h_align, v_align)
+    }
 }
 
 /// A convenient concrete engine selection.
@@ -196,6 +486,55 @@ impl Engine {
     pub fn new_default() -> Self {
         Self::Cosmic(cosmic::CosmicEngine::new_default())
     }
+
+    /// Builder form of [`Engine::set_fallback_families`]
+    pub fn with_fallback_families(
+        mut self,
+        families: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.set_fallback_families(families);
+        self
+    }
+
+    /// Replace the families tried, in order, for characters the requested
+    /// family has no glyphs for
+    ///
+    /// Fonts are resolved per run: each run of text uses the first family in
+    /// `requested, DEFAULT_FAMILY, fallbacks...` that covers it.
+    pub fn set_fallback_families(&mut self, families: impl IntoIterator<Item = impl Into<String>>) {
+        match self {
+            #[cfg(feature = "cosmic")]
+            Self::Cosmic(engine) => engine.set_fallback_families(families),
+        }
+    }
+
+    /// The current fallback families, in priority order
+    pub fn fallback_families(&self) -> &[String] {
+        match self {
+            #[cfg(feature = "cosmic")]
+            Self::Cosmic(engine) => engine.fallback_families(),
+        }
+    }
+
+    /// Clear the measurement cache (e.g. after the text scale changed)
+    pub fn clear_measurement_cache(&mut self) {
+        match self {
+            #[cfg(feature = "cosmic")]
+            Self::Cosmic(engine) => engine.clear_measurement_cache(),
+        }
+    }
+
+    /// A new engine with this engine's fonts and fallback families, and empty caches
+    ///
+    /// Fonts keep their ids, so glyphs shaped by the fork can be rasterized by
+    /// this engine. Used to shape text on another thread; fonts loaded into
+    /// either engine afterwards are not shared.
+    pub fn fork(&self) -> Self {
+        match self {
+            #[cfg(feature = "cosmic")]
+            Self::Cosmic(engine) => Self::Cosmic(engine.fork()),
+        }
+    }
 }
 
 impl TextEngine for Engine {
@@ -228,6 +567,13 @@ impl ContentMeasurer for Engine {
             Self::Cosmic(engine) => engine.measure_text(request),
         }
     }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        match self {
+            #[cfg(feature = "cosmic")]
+            Self::Cosmic(engine) => engine.cache_stats(),
+        }
+    }
 }
 
 /// Helper: compute alignment origin for a line box within a rect.
@@ -253,6 +599,24 @@ fn align_origin(
     [x, y]
 }
 
+/// Hash of everything in rich text spans that affects shaping (span
+/// boundaries and font overrides, but not colors or underlines)
+///
+/// Renderers include this in their shaping cache keys, so recoloring a span
+/// (e.g. on hover) doesn't reshape the text.
+pub fn span_layout_hash(spans: &[TextSpan]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for span in spans {
+        span.text.len().hash(&mut hasher);
+        span.font_weight.hash(&mut hasher);
+        span.font_style.hash(&mut hasher);
+        span.font_scale.map(f32::to_bits).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 #[cfg(feature = "cosmic")]
 pub mod cosmic {
     //! `cosmic-text` implementation of shaping and glyph rasterization.
@@ -264,43 +628,80 @@ pub mod cosmic {
     //! As this stabilizes, we can extend to multi-line shaping, wrapping, and richer font selection.
 
     use super::{
-        align_origin, FontId, FontStyle, GlyphBitmap, GlyphKey, LineMetrics, LinePlacement,
+        align_origin, span_layout_hash, CaretStop, FontFeature, FontId, FontStyle, FontWidth,
+        GlyphAntialiasing, GlyphBitmap, GlyphFormat, GlyphKey, LineMetrics, LinePlacement,
         PositionedGlyph, ShapeLineRequest, ShapeTextRequest, ShapedLine, ShapedText, TextEngine,
+        Underline, DEFAULT_FALLBACK_FAMILIES, DEFAULT_FAMILY,
     };
 
-    use astra_gui::{ContentMeasurer, IntrinsicSize, MeasureTextRequest, Rect, Wrap};
-    use cosmic_text::{fontdb, Attrs, Buffer, FontSystem, Metrics, Shaping};
+    use astra_gui::{
+        CacheStats, ContentMeasurer, HorizontalAlign, IntrinsicSize, MeasureTextRequest, Rect,
+        TextOverflow, VerticalAlign, Wrap,
+    };
+    use cosmic_text::{fontdb, Attrs, Buffer, Family, Font, FontSystem, Metrics, Shaping};
     use std::collections::HashMap;
     use std::hash::{Hash, Hasher};
+    use std::ops::Range;
+    use std::sync::Arc;
+
+    /// Characters that never start a font run of their own: whitespace and
+    /// controls, and marks that modify the previous character (combining marks,
+    /// joiners, variation selectors, emoji skin tones and tags). Keeping them
+    /// with their base character keeps emoji sequences in a single font.
+    fn joins_previous(ch: char) -> bool {
+        ch.is_whitespace()
+            || ch.is_control()
+            || matches!(
+                ch,
+                '\u{0300}'..='\u{036F}'
+                    | '\u{200C}'..='\u{200D}'
+                    | '\u{20D0}'..='\u{20FF}'
+                    | '\u{FE00}'..='\u{FE0F}'
+                    | '\u{1F3FB}'..='\u{1F3FF}'
+                    | '\u{E0020}'..='\u{E007F}'
+                    | '\u{E0100}'..='\u{E01EF}'
+            )
+    }
 
     /// Cache key for text measurements.
     /// Uses hash of text content + measurement parameters.
     #[derive(Clone, Debug, PartialEq, Eq, Hash)]
     struct MeasurementCacheKey {
         text_hash: u64,
+        spans_hash: u64,       // span boundaries and font overrides (not colors)
         font_size_scaled: u32, // font_size * 1000 to avoid float in hash
         max_width_scaled: Option<u32>, // max_width * 1000
         wrap: Wrap,
+        max_lines: Option<usize>,
         line_height_scaled: u32, // line_height_multiplier * 1000
         font_weight: u16,
         font_style: FontStyle,
+        font_features: Vec<FontFeature>,
+        font_width: FontWidth,
+        tab_width: u16,
     }
 
     impl MeasurementCacheKey {
         fn from_request(request: &MeasureTextRequest<'_>) -> Self {
-            // Hash the text content
+            // Hash the text content and family
             let mut hasher = std::collections::hash_map::DefaultHasher::new();
             request.text.hash(&mut hasher);
+            request.family.hash(&mut hasher);
             let text_hash = hasher.finish();
 
             Self {
                 text_hash,
+                spans_hash: span_layout_hash(request.spans),
                 font_size_scaled: (request.font_size * 1000.0) as u32,
                 max_width_scaled: request.max_width.map(|w| (w * 1000.0) as u32),
                 wrap: request.wrap,
+                max_lines: request.max_lines,
                 line_height_scaled: (request.line_height_multiplier * 1000.0) as u32,
                 font_weight: request.font_weight.to_weight(),
                 font_style: request.font_style,
+                font_features: request.font_features.to_vec(),
+                font_width: request.font_width,
+                tab_width: request.tab_width,
             }
         }
     }
@@ -312,10 +713,22 @@ pub mod cosmic {
         // Raster cache for swash (used by cosmic-text under the hood).
         swash_cache: cosmic_text::SwashCache,
 
+        /// Scaler state for LCD glyphs, which `swash_cache` only renders as grayscale
+        scale_context: swash::scale::ScaleContext,
+
         /// Measurement cache: (text_hash, font_size, max_width, wrap) -> IntrinsicSize
         /// This caches the expensive text measurement operation to avoid re-measuring
         /// unchanged text on every frame.
         measurement_cache: HashMap<MeasurementCacheKey, IntrinsicSize>,
+
+        /// Measurement cache hit/miss counters, reported via `cache_stats`
+        measurement_cache_stats: CacheStats,
+
+        /// Families tried after the requested one and `DEFAULT_FAMILY`
+        fallback_families: Vec<String>,
+
+        /// Face used to check glyph coverage per family name (`None`: not installed)
+        family_faces: HashMap<String, Option<fontdb::ID>>,
     }
 
     impl CosmicEngine {
@@ -335,13 +748,62 @@ pub mod cosmic {
                 .db_mut()
                 .load_font_data(astra_gui_fonts::inter::italic_variable_opsz_wght().to_vec());
 
+            // JetBrains Mono is the default family of code content (`astra_gui::DEFAULT_CODE_FAMILY`)
+            font_system
+                .db_mut()
+                .load_font_data(astra_gui_fonts::jetbrains_mono::variable_wght().to_vec());
+            font_system
+                .db_mut()
+                .load_font_data(astra_gui_fonts::jetbrains_mono::italic_variable_wght().to_vec());
+
             Self {
                 font_system,
                 swash_cache: cosmic_text::SwashCache::new(),
+                scale_context: swash::scale::ScaleContext::new(),
                 measurement_cache: HashMap::new(),
+                measurement_cache_stats: CacheStats::default(),
+                fallback_families: DEFAULT_FALLBACK_FAMILIES
+                    .iter()
+                    .map(|family| family.to_string())
+                    .collect(),
+                family_faces: HashMap::new(),
             }
         }
 
+        /// Engine sharing this one's fonts, see [`super::Engine::fork`]
+        pub fn fork(&self) -> Self {
+            // A cloned database keeps the face ids glyph keys refer to
+            let font_system = FontSystem::new_with_locale_and_db(
+                self.font_system.locale().to_string(),
+                self.font_system.db().clone(),
+            );
+
+            Self {
+                font_system,
+                swash_cache: cosmic_text::SwashCache::new(),
+                scale_context: swash::scale::ScaleContext::new(),
+                measurement_cache: HashMap::new(),
+                measurement_cache_stats: CacheStats::default(),
+                fallback_families: self.fallback_families.clone(),
+                family_faces: HashMap::new(),
+            }
+        }
+
+        /// Replace the fallback families, see [`super::Engine::set_fallback_families`]
+        pub fn set_fallback_families(
+            &mut self,
+            families: impl IntoIterator<Item = impl Into<String>>,
+        ) {
+            self.fallback_families = families.into_iter().map(Into::into).collect();
+            // Cached sizes may have been measured with other fonts
+            self.measurement_cache.clear();
+        }
+
+        /// The current fallback families, in priority order
+        pub fn fallback_families(&self) -> &[String] {
+            &self.fallback_families
+        }
+
         /// Access the underlying `FontSystem` if callers want to customize further.
         pub fn font_system_mut(&mut self) -> &mut FontSystem {
             &mut self.font_system
@@ -363,13 +825,29 @@ pub mod cosmic {
             self.measurement_cache.len()
         }
 
-        fn make_attrs(&self, font_weight: u16, font_style: FontStyle) -> Attrs<'static> {
-            // `Attrs` holds references internally, so returning `Attrs<'static>` must not borrow
-            // from parameters. Build attrs with weight and style.
+        /// Reset the measurement cache hit/miss counters.
+        pub fn reset_cache_stats(&mut self) {
+            self.measurement_cache_stats = CacheStats::default();
+        }
 
+        fn make_attrs<'a>(
+            family: &'a str,
+            font_weight: u16,
+            font_style: FontStyle,
+            font_features: &[FontFeature],
+            font_width: FontWidth,
+        ) -> Attrs<'a> {
+            // Build attrs with family, weight, width, style and features.
+
+            let mut features = cosmic_text::FontFeatures::new();
+            for feature in font_features {
+                features.set(cosmic_text::FeatureTag::new(&feature.tag), feature.value);
+            }
             let attrs = Attrs::new()
-                .family(cosmic_text::Family::Name("Inter"))
-                .weight(cosmic_text::Weight(font_weight));
+                .family(Family::Name(family))
+                .weight(cosmic_text::Weight(font_weight))
+                .stretch(Self::cosmic_stretch(font_width))
+                .font_features(features);
 
             // Set style if italic
             let attrs = match font_style {
@@ -380,9 +858,233 @@ pub mod cosmic {
             attrs
         }
 
-        fn make_attrs_text(&self, font_weight: u16, font_style: FontStyle) -> Attrs<'static> {
+        fn make_attrs_text<'a>(
+            family: &'a str,
+            font_weight: u16,
+            font_style: FontStyle,
+            font_features: &[FontFeature],
+            font_width: FontWidth,
+        ) -> Attrs<'a> {
             // Same as make_attrs
-            self.make_attrs(font_weight, font_style)
+            Self::make_attrs(family, font_weight, font_style, font_features, font_width)
+        }
+
+        /// Convert astra-gui FontWidth to the matching fontdb stretch
+        fn cosmic_stretch(width: FontWidth) -> fontdb::Stretch {
+            match width {
+                FontWidth::UltraCondensed => fontdb::Stretch::UltraCondensed,
+                FontWidth::ExtraCondensed => fontdb::Stretch::ExtraCondensed,
+                FontWidth::Condensed => fontdb::Stretch::Condensed,
+                FontWidth::SemiCondensed => fontdb::Stretch::SemiCondensed,
+                FontWidth::Normal => fontdb::Stretch::Normal,
+                FontWidth::SemiExpanded => fontdb::Stretch::SemiExpanded,
+                FontWidth::Expanded => fontdb::Stretch::Expanded,
+                FontWidth::ExtraExpanded => fontdb::Stretch::ExtraExpanded,
+                FontWidth::UltraExpanded => fontdb::Stretch::UltraExpanded,
+            }
+        }
+
+        /// Font resolution order for a request: the requested family, Inter, then the fallbacks
+        fn family_chain(&self, family: Option<&str>) -> Vec<String> {
+            let mut chain: Vec<String> = Vec::with_capacity(self.fallback_families.len() + 2);
+            let families = family
+                .into_iter()
+                .chain([DEFAULT_FAMILY])
+                .chain(self.fallback_families.iter().map(String::as_str));
+            for family in families {
+                if !chain.iter().any(|known| known == family) {
+                    chain.push(family.to_string());
+                }
+            }
+            chain
+        }
+
+        /// Font of the regular face of `family`, if it's installed
+        fn family_font(&mut self, family: &str) -> Option<Arc<Font>> {
+            let id = match self.family_faces.get(family) {
+                Some(&id) => id,
+                None => {
+                    let id = self.font_system.db().query(&fontdb::Query {
+                        families: &[fontdb::Family::Name(family)],
+                        ..Default::default()
+                    });
+                    self.family_faces.insert(family.to_string(), id);
+                    id
+                }
+            }?;
+            self.font_system.get_font(id, fontdb::Weight::NORMAL)
+        }
+
+        /// Render an LCD mask of the glyph behind `cache_key`, with the scaler
+        /// settings cosmic-text's raster cache uses for grayscale masks
+        ///
+        /// `None` for color glyphs (and missing fonts), which render as usual.
+        fn rasterize_lcd(
+            &mut self,
+            key: GlyphKey,
+            cache_key: cosmic_text::CacheKey,
+            offset: [i32; 2],
+        ) -> Option<GlyphBitmap> {
+            use swash::scale::{image::Content, Render, Source, StrikeWith};
+            use swash::zeno::{Format, Vector};
+
+            let font = self
+                .font_system
+                .get_font(cache_key.font_id, cache_key.font_weight)?;
+            let font = font.as_swash();
+
+            let weight_axis = swash::Tag::from_be_bytes(*b"wght");
+            let mut builder = self
+                .scale_context
+                .builder(font)
+                .size(key.px_size as f32)
+                .hint(true);
+            if let Some(axis) = font.variations().find_by_tag(weight_axis) {
+                builder = builder.variations(std::iter::once(swash::Setting {
+                    tag: weight_axis,
+                    value: f32::from(key.weight).clamp(axis.min_value(), axis.max_value()),
+                }));
+            }
+            let mut scaler = builder.build();
+
+            let image = Render::new(&[
+                Source::ColorOutline(0),
+                Source::ColorBitmap(StrikeWith::BestFit),
+                Source::Outline,
+            ])
+            .format(Format::Subpixel)
+            .offset(Vector::new(
+                cache_key.x_bin.as_float(),
+                cache_key.y_bin.as_float(),
+            ))
+            .render(&mut scaler, cache_key.glyph_id)?;
+
+            if image.content != Content::SubpixelMask {
+                return None;
+            }
+
+            Some(GlyphBitmap {
+                key,
+                format: GlyphFormat::Lcd,
+                size_px: [image.placement.width, image.placement.height],
+                bearing_px: [
+                    image.placement.left + offset[0],
+                    -image.placement.top + offset[1],
+                ],
+                advance_px: [0.0, 0.0],
+                pixels: image.data,
+            })
+        }
+
+        /// Split `text` into runs tagged with the index of the first family in
+        /// `chain` that has glyphs for them
+        ///
+        /// Characters no family covers stay with the requested family, leaving
+        /// them to cosmic-text's own fallback.
+        fn font_runs(&mut self, text: &str, chain: &[String]) -> Vec<(Range<usize>, usize)> {
+            if chain.len() < 2 || text.is_empty() {
+                return vec![(0..text.len(), 0)];
+            }
+
+            let fonts: Vec<Option<Arc<Font>>> = chain
+                .iter()
+                .map(|family| self.family_font(family))
+                .collect();
+            let covers = |font: &Option<Arc<Font>>, ch: char| {
+                font.as_ref()
+                    .is_some_and(|font| font.as_swash().charmap().map(ch) != 0)
+            };
+
+            let mut runs: Vec<(Range<usize>, usize)> = Vec::new();
+            for (index, ch) in text.char_indices() {
+                let end = index + ch.len_utf8();
+                let family = match runs.last() {
+                    Some(&(_, current)) if joins_previous(ch) => current,
+                    _ => fonts.iter().position(|font| covers(font, ch)).unwrap_or(0),
+                };
+                match runs.last_mut() {
+                    Some((range, current)) if *current == family => range.end = end,
+                    _ => runs.push((index..end, family)),
+                }
+            }
+            runs
+        }
+
+        /// Drop the glyphs of `line` that don't fit in `max_width` together with
+        /// `ellipsis`, then append the ellipsis after the last kept glyph
+        ///
+        /// `glyph_ends` holds each glyph's right edge and whether it is
+        /// whitespace; trailing whitespace is dropped so the ellipsis hugs the text.
+        fn truncate_with_ellipsis(
+            line: &mut ShapedLine,
+            glyph_ends: &[(f32, bool)],
+            max_width: f32,
+            ellipsis: &ShapedLine,
+        ) {
+            let available = max_width - ellipsis.metrics.width_px;
+            let mut keep = glyph_ends
+                .iter()
+                .take_while(|&&(end, _)| end <= available)
+                .count();
+            while keep > 0 && glyph_ends[keep - 1].1 {
+                keep -= 1;
+            }
+            let cut_x = keep.checked_sub(1).map_or(0.0, |last| glyph_ends[last].0);
+
+            // The ellipsis takes the span (and so the color) of the text it replaces
+            let span = line.glyphs.get(keep.saturating_sub(1)).and_then(|g| g.span);
+            line.glyphs.truncate(keep);
+            line.carets.retain(|stop| stop.x_px <= cut_x);
+            line.underlines.retain_mut(|underline| {
+                underline.width_px = underline.width_px.min(cut_x - underline.x_px);
+                underline.width_px > 0.0
+            });
+
+            // Move the ellipsis from its own baseline onto the line's
+            let dy = line.metrics.baseline_px - ellipsis.metrics.baseline_px;
+            line.glyphs
+                .extend(ellipsis.glyphs.iter().map(|glyph| PositionedGlyph {
+                    x_px: cut_x.round() + glyph.x_px,
+                    y_px: glyph.y_px + dy,
+                    span,
+                    ..*glyph
+                }));
+            line.metrics.width_px = cut_x + ellipsis.metrics.width_px;
+        }
+
+        /// Add the caret stops on both sides of `glyph`'s cluster, `offset` being
+        /// the byte offset of the glyph's paragraph in the shaped text
+        fn push_caret_stops(
+            carets: &mut Vec<CaretStop>,
+            glyph: &cosmic_text::LayoutGlyph,
+            offset: usize,
+        ) {
+            let (start_x, end_x) = if glyph.level.is_rtl() {
+                (glyph.x + glyph.w, glyph.x)
+            } else {
+                (glyph.x, glyph.x + glyph.w)
+            };
+            carets.push(CaretStop {
+                index: offset + glyph.start,
+                x_px: start_x,
+            });
+            carets.push(CaretStop {
+                index: offset + glyph.end,
+                x_px: end_x,
+            });
+        }
+
+        /// Put a line's caret stops in text order, one per index; a line without
+        /// glyphs gets a single stop at `line_start`
+        fn finish_caret_stops(carets: &mut Vec<CaretStop>, line_start: usize) {
+            carets.sort_by_key(|stop| stop.index);
+            carets.dedup_by_key(|stop| stop.index);
+            if carets.is_empty() {
+                carets.push(CaretStop {
+                    index: line_start,
+                    x_px: 0.0,
+                });
+            }
         }
 
         /// Convert astra-gui Wrap to cosmic-text Wrap
@@ -400,6 +1102,8 @@ pub mod cosmic {
         fn shape_line(&mut self, req: ShapeLineRequest<'_>) -> (ShapedLine, LinePlacement) {
             let metrics = Metrics::new(req.font_px, req.font_px * 1.2);
             let mut buffer = Buffer::new(&mut self.font_system, metrics);
+            // Tabs advance to the next stop from the line start
+            buffer.set_tab_width(&mut self.font_system, req.tab_width.max(1));
 
             // Prevent wrapping: set a huge width and line height from metrics.
             buffer.set_size(
@@ -408,11 +1112,24 @@ pub mod cosmic {
                 Some(metrics.line_height),
             );
 
-            let attrs = self.make_attrs(req.font_weight, req.font_style);
+            let chain = self.family_chain(req.family);
+            let runs = self.font_runs(req.text, &chain);
+            let attrs = Self::make_attrs(
+                &chain[0],
+                req.font_weight,
+                req.font_style,
+                req.font_features,
+                req.font_width,
+            );
 
-            buffer.set_text(
+            buffer.set_rich_text(
                 &mut self.font_system,
-                req.text,
+                runs.into_iter().map(|(range, family)| {
+                    (
+                        &req.text[range],
+                        attrs.clone().family(Family::Name(&chain[family])),
+                    )
+                }),
                 &attrs,
                 Shaping::Advanced,
                 None,
@@ -421,11 +1138,14 @@ pub mod cosmic {
 
             let mut out = ShapedLine {
                 glyphs: Vec::new(),
+                underlines: Vec::new(),
                 metrics: LineMetrics {
                     width_px: 0.0,
                     height_px: metrics.line_height,
                     baseline_px: 0.0,
                 },
+                fade_out_px: None,
+                carets: Vec::new(),
             };
 
             // `layout_runs()` may yield multiple runs even for one line; we treat them as one line.
@@ -470,8 +1190,11 @@ pub mod cosmic {
                         f32::from_bits(physical.cache_key.font_size_bits)
                             .round()
                             .max(1.0) as u16,
-                        req.font_weight as i16, // Store weight for variable font rasterization
-                    );
+                        req.font_weight, // Weight for variable font rasterization
+                    )
+                    // `physical.x` is whole pixels; the fraction is in the subpixel bin
+                    .snap_x(physical.cache_key.x_bin.as_float())
+                    .0;
 
                     // Convert to line-top-left space:
                     // - `run.line_y` is the baseline y from the top of the line box
@@ -481,11 +1204,14 @@ pub mod cosmic {
                         key,
                         x_px: physical.x as f32,
                         y_px: run.line_y + physical.y as f32,
+                        span: None,
                     });
+                    Self::push_caret_stops(&mut out.carets, glyph, 0);
                 }
 
                 // Single line requested; use the first visible run.
             }
+            Self::finish_caret_stops(&mut out.carets, 0);
 
             let origin_px = align_origin(
                 req.rect,
@@ -501,6 +1227,7 @@ pub mod cosmic {
         fn shape_text(&mut self, req: ShapeTextRequest<'_>) -> (ShapedText, LinePlacement) {
             let metrics = Metrics::new(req.font_px, req.font_px * req.line_height_multiplier);
             let mut buffer = Buffer::new(&mut self.font_system, metrics);
+            buffer.set_tab_width(&mut self.font_system, req.tab_width.max(1));
 
             // Set wrapping based on the wrap mode
             let wrap_width = if req.wrap == Wrap::None {
@@ -512,31 +1239,105 @@ pub mod cosmic {
             buffer.set_size(&mut self.font_system, wrap_width, None);
             buffer.set_wrap(&mut self.font_system, Self::cosmic_wrap(req.wrap));
 
-            let attrs = self.make_attrs_text(req.font_weight, req.font_style);
-
-            buffer.set_text(
-                &mut self.font_system,
-                req.text,
-                &attrs,
-                Shaping::Advanced,
-                None,
+            // Each span (or the whole plain text) is split further into font runs
+            let chain = self.family_chain(req.family);
+            let attrs = Self::make_attrs_text(
+                &chain[0],
+                req.font_weight,
+                req.font_style,
+                req.font_features,
+                req.font_width,
             );
+
+            if req.spans.is_empty() {
+                let runs = self.font_runs(req.text, &chain);
+                buffer.set_rich_text(
+                    &mut self.font_system,
+                    runs.into_iter().map(|(range, family)| {
+                        (
+                            &req.text[range],
+                            attrs.clone().family(Family::Name(&chain[family])),
+                        )
+                    }),
+                    &attrs,
+                    Shaping::Advanced,
+                    None,
+                );
+            } else {
+                let span_runs: Vec<_> = req
+                    .spans
+                    .iter()
+                    .map(|span| self.font_runs(&span.text, &chain))
+                    .collect();
+
+                // Glyphs carry their span index in the attrs metadata
+                let mut runs = Vec::new();
+                for (index, (span, font_runs)) in req.spans.iter().zip(span_runs).enumerate() {
+                    let weight = span
+                        .font_weight
+                        .map_or(req.font_weight, |weight| weight.to_weight());
+                    let mut span_attrs = Self::make_attrs_text(
+                        &chain[0],
+                        weight,
+                        span.font_style.unwrap_or(req.font_style),
+                        req.font_features,
+                        req.font_width,
+                    )
+                    .metadata(index);
+                    if let Some(scale) = span.font_scale {
+                        let font_px = req.font_px * scale;
+                        span_attrs = span_attrs
+                            .metrics(Metrics::new(font_px, font_px * req.line_height_multiplier));
+                    }
+                    for (range, family) in font_runs {
+                        runs.push((
+                            &span.text[range],
+                            span_attrs.clone().family(Family::Name(&chain[family])),
+                        ));
+                    }
+                }
+                buffer.set_rich_text(&mut self.font_system, runs, &attrs, Shaping::Advanced, None);
+            }
             buffer.shape_until_scroll(&mut self.font_system, false);
 
             let mut shaped_lines = Vec::new();
             let mut total_width = 0.0f32;
             let mut total_height = 0.0f32;
+            // "…" shaped on first use by an overflowing line
+            let mut ellipsis: Option<ShapedLine> = None;
+
+            // Byte offset of each paragraph in the text, for caret stops
+            let mut paragraph_starts = Vec::with_capacity(buffer.lines.len());
+            let mut paragraph_start = 0;
+            for paragraph in &buffer.lines {
+                paragraph_starts.push(paragraph_start);
+                paragraph_start += paragraph.text().len() + paragraph.ending().as_str().len();
+            }
+
+            // Lines past `max_lines` are dropped; the last kept one is clamped
+            let run_count = buffer.layout_runs().count();
+            let max_lines = req.max_lines.unwrap_or(usize::MAX);
 
             // Iterate all layout runs (one per visual line)
-            for run in buffer.layout_runs() {
+            for (index, run) in buffer.layout_runs().enumerate() {
+                if index == max_lines {
+                    break;
+                }
+                let clamped = index + 1 == max_lines && index + 1 < run_count;
                 let mut line = ShapedLine {
                     glyphs: Vec::new(),
+                    underlines: Vec::new(),
                     metrics: LineMetrics {
                         width_px: run.line_w,
                         height_px: run.line_height,
                         baseline_px: (run.line_y - run.line_top).max(0.0),
                     },
+                    fade_out_px: None,
+                    carets: Vec::new(),
                 };
+                let paragraph_start = paragraph_starts[run.line_i];
+                // Right edge of each glyph, and whether it's whitespace, for truncation
+                let mut glyph_ends = Vec::new();
 
                 // Collect glyphs for this line
                 for glyph in run.glyphs.iter() {
@@ -552,16 +1353,90 @@ pub mod cosmic {
                         f32::from_bits(physical.cache_key.font_size_bits)
                             .round()
                             .max(1.0) as u16,
-                        req.font_weight as i16, // Store weight for variable font rasterization
-                    );
+                        // Weight for variable font rasterization (per glyph, since rich
+                        // text spans may override it)
+                        glyph.font_weight.0,
+                    )
+                    .snap_x(physical.cache_key.x_bin.as_float())
+                    .0;
 
                     // Position in line-local space (baseline-relative)
                     let baseline_offset = (run.line_y - run.line_top).max(0.0);
+                    let span = (!req.spans.is_empty()).then_some(glyph.metadata);
                     line.glyphs.push(PositionedGlyph {
                         key,
                         x_px: physical.x as f32,
                         y_px: baseline_offset + physical.y as f32,
+                        span,
                     });
+                    Self::push_caret_stops(&mut line.carets, glyph, paragraph_start);
+                    if req.overflow == TextOverflow::Ellipsis {
+                        let is_space = run.text[glyph.start..glyph.end].trim().is_empty();
+                        glyph_ends.push((glyph.x + glyph.w, is_space));
+                    }
+
+                    if let Some(span) = span.filter(|&span| req.spans[span].underline) {
+                        // Extend the previous underline across glyphs of the same span
+                        match line.underlines.last_mut() {
+                            Some(underline)
+                                if underline.span == span
+                                    && (underline.x_px + underline.width_px - glyph.x).abs()
+                                        < 0.5 =>
+                            {
+                                underline.width_px += glyph.w;
+                            }
+                            _ => {
+                                let thickness_px = (glyph.font_size / 14.0).max(1.0).round();
+                                line.underlines.push(Underline {
+                                    x_px: glyph.x,
+                                    y_px: (baseline_offset + glyph.font_size * 0.1).round(),
+                                    width_px: glyph.w,
+                                    thickness_px,
+                                    span,
+                                });
+                            }
+                        }
+                    }
+                }
+
+                // Blank lines get a single caret at their start
+                Self::finish_caret_stops(&mut line.carets, paragraph_start);
+
+                // Half a pixel of slack so rounding doesn't truncate text that fits
+                let max_width = req.rect.width();
+                let overflows = line.metrics.width_px > max_width + 0.5;
+                if overflows || clamped {
+                    match req.overflow {
+                        TextOverflow::Ellipsis => {
+                            let ellipsis = ellipsis.get_or_insert_with(|| {
+                                self.shape_line(ShapeLineRequest {
+                                    text: "\u{2026}",
+                                    rect: req.rect,
+                                    font_px: req.font_px,
+                                    h_align: HorizontalAlign::Left,
+                                    v_align: VerticalAlign::Top,
+                                    family: req.family,
+                                    font_weight: req.font_weight,
+                                    font_style: req.font_style,
+                                    font_features: req.font_features,
+                                    font_width: req.font_width,
+                                    tab_width: req.tab_width,
+                                })
+                                .0
+                            });
+                            // A clamped line that fits keeps its text and gains the ellipsis
+                            let limit =
+                                max_width.min(line.metrics.width_px + ellipsis.metrics.width_px);
+                            Self::truncate_with_ellipsis(&mut line, &glyph_ends, limit, ellipsis);
+                        }
+                        TextOverflow::Fade if overflows => {
+                            // Fade over two ems, or the whole line in very narrow nodes
+                            let fade_px = (req.font_px * 2.0).min(max_width);
+                            line.fade_out_px = Some([max_width - fade_px, max_width]);
+                            line.metrics.width_px = max_width;
+                        }
+                        TextOverflow::Clip | TextOverflow::Fade => {}
+                    }
                 }
 
                 total_width = total_width.max(line.metrics.width_px);
@@ -609,17 +1484,25 @@ pub mod cosmic {
             // Those offsets must be applied when placing the bitmap quad.
             //
             // IMPORTANT: For variable fonts (like Inter), the weight parameter controls the weight axis.
-            // We must use the weight that was used during shaping (stored in subpixel_x_64).
+            // We must use the weight that was used during shaping (stored in `key.weight`).
+            //
+            // The subpixel offset is one of cosmic-text's own bins, so it maps back exactly.
 
             let (cache_key, x, y) = cosmic_text::CacheKey::new(
                 font_id,
                 key.glyph_id as u16,
                 key.px_size as f32,
-                (0.0, 0.0),
-                fontdb::Weight(key.subpixel_x_64 as u16), // Use weight from shaping for variable fonts
+                (key.subpixel_x(), 0.0),
+                fontdb::Weight(key.weight), // Use weight from shaping for variable fonts
                 cosmic_text::CacheKeyFlags::empty(),
             );
 
+            if key.antialiasing == GlyphAntialiasing::Lcd {
+                if let Some(bitmap) = self.rasterize_lcd(key, cache_key, [x, y]) {
+                    return Some(bitmap);
+                }
+            }
+
             let image_opt = self
                 .swash_cache
                 .get_image(&mut self.font_system, cache_key)
@@ -627,11 +1510,13 @@ pub mod cosmic {
 
             let image = image_opt?;
 
-            // We only support coverage masks for now (which is what the WGPU shader expects).
-            // If we ever encounter color glyphs, implement conversion or switch the atlas format.
-            if image.content != cosmic_text::SwashContent::Mask {
-                return None;
-            }
+            // Color glyphs (emoji, COLR layers, color bitmaps) go to a separate RGBA atlas.
+            // Subpixel masks are never requested here; LCD glyphs take `rasterize_lcd` above.
+            let format = match image.content {
+                cosmic_text::SwashContent::Mask => GlyphFormat::Mask,
+                cosmic_text::SwashContent::Color => GlyphFormat::Color,
+                cosmic_text::SwashContent::SubpixelMask => return None,
+            };
 
             let w = image.placement.width;
             let h = image.placement.height;
@@ -655,6 +1540,7 @@ pub mod cosmic {
 
             Some(GlyphBitmap {
                 key,
+                format,
                 size_px: [w, h],
                 bearing_px,
                 advance_px,
@@ -668,10 +1554,12 @@ pub mod cosmic {
             // Check cache first
             let cache_key = MeasurementCacheKey::from_request(&request);
             if let Some(cached) = self.measurement_cache.get(&cache_key) {
+                self.measurement_cache_stats.hits += 1;
                 return *cached;
             }
 
             // Cache miss - perform actual measurement
+            self.measurement_cache_stats.misses += 1;
             // Determine wrap mode for measurement
             // If no width constraint is provided, disable wrapping for measurement
             // to get the natural text dimensions
@@ -687,15 +1575,23 @@ pub mod cosmic {
 
             let shape_request = ShapeTextRequest {
                 text: request.text,
+                spans: request.spans,
                 rect: dummy_rect,
                 font_px: request.font_size,
                 h_align: request.h_align,
                 v_align: request.v_align,
                 family: request.family,
                 wrap,
+                // Intrinsic sizes are the untruncated text's
+                overflow: TextOverflow::Clip,
+                // Clamped text measures as tall as the lines it keeps
+                max_lines: request.max_lines,
                 line_height_multiplier: request.line_height_multiplier,
                 font_weight: request.font_weight.to_weight(),
                 font_style: request.font_style,
+                font_features: request.font_features,
+                font_width: request.font_width,
+                tab_width: request.tab_width,
             };
 
             let (shaped_text, _placement) = self.shape_text(shape_request);
@@ -717,5 +1613,187 @@ pub mod cosmic {
 
             result
         }
+
+        fn cache_stats(&self) -> Option<CacheStats> {
+            Some(self.measurement_cache_stats)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{FontStyle, FontWidth, GlyphKey, LineMetrics, ShapeTextRequest};
+        use astra_gui::TextSpan;
+
+        fn glyph(glyph_id: u32, x_px: f32, span: Option<usize>) -> PositionedGlyph {
+            PositionedGlyph {
+                key: GlyphKey::new(FontId(0), glyph_id, 16, 400),
+                x_px,
+                y_px: 0.0,
+                span,
+            }
+        }
+
+        fn shape(
+            engine: &mut CosmicEngine,
+            text: &str,
+            width: f32,
+            overflow: TextOverflow,
+        ) -> ShapedText {
+            engine
+                .shape_text(ShapeTextRequest {
+                    text,
+                    spans: &[] as &[TextSpan],
+                    rect: Rect::new([0.0, 0.0], [width, 100.0]),
+                    font_px: 16.0,
+                    h_align: HorizontalAlign::Left,
+                    v_align: VerticalAlign::Top,
+                    family: None,
+                    wrap: Wrap::None,
+                    overflow,
+                    max_lines: None,
+                    line_height_multiplier: 1.2,
+                    font_weight: 400,
+                    font_style: FontStyle::Normal,
+                    font_features: &[],
+                    font_width: FontWidth::Normal,
+                    tab_width: 8,
+                })
+                .0
+        }
+
+        fn ellipsis_glyph(engine: &mut CosmicEngine) -> u32 {
+            let (line, _) = engine.shape_line(ShapeLineRequest {
+                text: "\u{2026}",
+                rect: Rect::new([0.0, 0.0], [100.0, 100.0]),
+                font_px: 16.0,
+                h_align: HorizontalAlign::Left,
+                v_align: VerticalAlign::Top,
+                family: None,
+                font_weight: 400,
+                font_style: FontStyle::Normal,
+                font_features: &[],
+                font_width: FontWidth::Normal,
+                tab_width: 8,
+            });
+            line.glyphs[0].key.glyph_id
+        }
+
+        #[test]
+        fn test_ellipsis_replaces_glyphs_past_the_width() {
+            // "ab cd" with 10 px glyphs and an 8 px ellipsis
+            let mut line = ShapedLine {
+                glyphs: (0..5).map(|i| glyph(i, i as f32 * 10.0, Some(1))).collect(),
+                underlines: vec![Underline {
+                    x_px: 0.0,
+                    y_px: 14.0,
+                    width_px: 50.0,
+                    thickness_px: 1.0,
+                    span: 1,
+                }],
+                metrics: LineMetrics {
+                    width_px: 50.0,
+                    height_px: 20.0,
+                    baseline_px: 15.0,
+                },
+                ..Default::default()
+            };
+            let glyph_ends = [
+                (10.0, false),
+                (20.0, false),
+                (30.0, true),
+                (40.0, false),
+                (50.0, false),
+            ];
+            let ellipsis = ShapedLine {
+                glyphs: vec![glyph(99, 0.0, None)],
+                metrics: LineMetrics {
+                    width_px: 8.0,
+                    height_px: 20.0,
+                    baseline_px: 12.0,
+                },
+                ..Default::default()
+            };
+
+            // Three glyphs fit beside the ellipsis, but the third is a space
+            CosmicEngine::truncate_with_ellipsis(&mut line, &glyph_ends, 38.0, &ellipsis);
+            let ids: Vec<u32> = line.glyphs.iter().map(|g| g.key.glyph_id).collect();
+            assert_eq!(ids, vec![0, 1, 99]);
+            let last = line.glyphs[2];
+            assert_eq!((last.x_px, last.y_px, last.span), (20.0, 3.0, Some(1)));
+            assert_eq!(line.metrics.width_px, 28.0);
+            assert_eq!(line.underlines[0].width_px, 20.0);
+        }
+
+        #[test]
+        fn test_ellipsis_alone_when_nothing_fits() {
+            let mut line = ShapedLine {
+                glyphs: vec![glyph(0, 0.0, None)],
+                underlines: vec![Underline {
+                    x_px: 0.0,
+                    y_px: 14.0,
+                    width_px: 10.0,
+                    thickness_px: 1.0,
+                    span: 0,
+                }],
+                ..Default::default()
+            };
+            let ellipsis = ShapedLine {
+                glyphs: vec![glyph(99, 0.0, None)],
+                metrics: LineMetrics {
+                    width_px: 8.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            CosmicEngine::truncate_with_ellipsis(&mut line, &[(10.0, false)], 12.0, &ellipsis);
+            assert_eq!(line.glyphs.len(), 1);
+            assert_eq!(line.glyphs[0].key.glyph_id, 99);
+            assert_eq!(line.metrics.width_px, 8.0);
+            assert!(line.underlines.is_empty());
+        }
+
+        #[test]
+        fn test_shaped_ellipsis_fits_the_width() {
+            let mut engine = CosmicEngine::new_default();
+            let ellipsis = ellipsis_glyph(&mut engine);
+
+            // Multi-byte characters around the cut must not split a character
+            for text in [
+                "The quick brown fox jumps",
+                "Grüße aus Köln, naïve café ☕ ü",
+            ] {
+                let clipped = shape(&mut engine, text, 80.0, TextOverflow::Clip);
+                let truncated = shape(&mut engine, text, 80.0, TextOverflow::Ellipsis);
+                let line = &truncated.lines[0];
+                assert!(line.metrics.width_px <= 80.5, "{text}: {line:?}");
+                assert_eq!(line.glyphs.last().unwrap().key.glyph_id, ellipsis);
+                assert!(line.glyphs.len() < clipped.lines[0].glyphs.len());
+            }
+        }
+
+        #[test]
+        fn test_text_that_fits_is_not_truncated() {
+            let mut engine = CosmicEngine::new_default();
+            let clipped = shape(&mut engine, "Fits", 200.0, TextOverflow::Clip);
+            let truncated = shape(&mut engine, "Fits", 200.0, TextOverflow::Ellipsis);
+            assert_eq!(
+                truncated.lines[0].glyphs.len(),
+                clipped.lines[0].glyphs.len()
+            );
+            assert_eq!(truncated.total_width, clipped.total_width);
+        }
+
+        #[test]
+        fn test_fade_covers_the_end_of_an_overflowing_line() {
+            let mut engine = CosmicEngine::new_default();
+            let faded = shape(&mut engine, "The quick brown fox", 60.0, TextOverflow::Fade);
+            assert_eq!(faded.lines[0].fade_out_px, Some([28.0, 60.0]));
+            assert_eq!(faded.lines[0].metrics.width_px, 60.0);
+
+            let fits = shape(&mut engine, "Fox", 200.0, TextOverflow::Fade);
+            assert_eq!(fits.lines[0].fade_out_px, None);
+        }
     }
 }
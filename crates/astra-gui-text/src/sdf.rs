@@ -0,0 +1,157 @@
+//! Signed distance field glyphs.
+//!
+//! A bitmap glyph is only sharp at the size it was rasterized at, so zooming
+//! fills the atlas with a copy per pixel size. A distance field stores, per
+//! texel, how far it is from the glyph outline; sampled with bilinear
+//! filtering and thresholded at the outline, one entry renders crisply at any
+//! size.
+//!
+//! The field is derived from a coverage mask rasterized at [`SDF_REFERENCE_PX`]:
+//! - byte `128` lies on the outline
+//! - larger values are inside the glyph, smaller values outside
+//! - one byte step is `2 * SDF_SPREAD_PX / 255` reference pixels
+//!
+//! Renderers scale quads by `px_size / SDF_REFERENCE_PX` and threshold at 0.5
+//! with a smoothing width of about one screen pixel (e.g. via `fwidth`).
+
+use crate::{GlyphBitmap, GlyphFormat};
+
+/// Font size distance fields are rasterized at, whatever size they are drawn at
+pub const SDF_REFERENCE_PX: u16 = 64;
+
+/// Distance in reference pixels covered on each side of the outline; the
+/// field is padded by this much so edges can fade out
+pub const SDF_SPREAD_PX: u32 = 8;
+
+/// Convert a coverage mask glyph (rasterized at [`SDF_REFERENCE_PX`]) into a
+/// [`GlyphFormat::Sdf`] glyph
+///
+/// The bitmap grows by [`SDF_SPREAD_PX`] on every side and its bearing moves
+/// accordingly. Color glyphs and empty bitmaps are returned unchanged.
+pub fn glyph_to_sdf(bitmap: GlyphBitmap) -> GlyphBitmap {
+    if bitmap.format != GlyphFormat::Mask || bitmap.pixels.is_empty() {
+        return bitmap;
+    }
+
+    let spread = SDF_SPREAD_PX as i32;
+    GlyphBitmap {
+        format: GlyphFormat::Sdf,
+        size_px: [
+            bitmap.size_px[0] + 2 * SDF_SPREAD_PX,
+            bitmap.size_px[1] + 2 * SDF_SPREAD_PX,
+        ],
+        bearing_px: [bitmap.bearing_px[0] - spread, bitmap.bearing_px[1] - spread],
+        pixels: coverage_to_sdf(&bitmap.pixels, bitmap.size_px, SDF_SPREAD_PX),
+        ..bitmap
+    }
+}
+
+/// Distance field of a coverage mask, padded by `spread_px` on every side
+///
+/// Texels at least half covered count as inside. Each output texel holds the
+/// distance to the nearest texel of the opposite side, clamped to `spread_px`
+/// and mapped so the outline lands on 128.
+pub fn coverage_to_sdf(coverage: &[u8], size_px: [u32; 2], spread_px: u32) -> Vec<u8> {
+    let [w, h] = size_px.map(|v| v as usize);
+    let spread = spread_px as usize;
+    let out_w = w + 2 * spread;
+    let out_h = h + 2 * spread;
+
+    let inside: Vec<bool> = (0..out_w * out_h)
+        .map(|i| {
+            let (x, y) = (i % out_w, i / out_w);
+            (spread..spread + w).contains(&x)
+                && (spread..spread + h).contains(&y)
+                && coverage[(y - spread) * w + (x - spread)] >= 128
+        })
+        .collect();
+
+    // Squared distances to the nearest inside and the nearest outside texel
+    let to_inside = squared_distance_transform(&inside, true, out_w, out_h);
+    let to_outside = squared_distance_transform(&inside, false, out_w, out_h);
+
+    let max_distance = spread_px as f32;
+    inside
+        .iter()
+        .enumerate()
+        .map(|(i, &is_inside)| {
+            // The outline runs halfway between neighboring texels of either side
+            let signed = if is_inside {
+                to_outside[i].sqrt() - 0.5
+            } else {
+                0.5 - to_inside[i].sqrt()
+            };
+            let value = 0.5 + signed.clamp(-max_distance, max_distance) / (2.0 * max_distance);
+            (value * 255.0).round() as u8
+        })
+        .collect()
+}
+
+/// Stand-in for "no feature texel in range"; large but finite so the parabola
+/// intersections below stay well defined
+const FAR: f32 = 1.0e20;
+
+/// Exact squared Euclidean distance from every texel to the nearest texel whose
+/// `inside` equals `feature` (Felzenszwalb & Huttenlocher): a 1D transform over
+/// every column, then over every row
+fn squared_distance_transform(inside: &[bool], feature: bool, w: usize, h: usize) -> Vec<f32> {
+    let mut grid: Vec<f32> = inside
+        .iter()
+        .map(|&is_inside| if is_inside == feature { 0.0 } else { FAR })
+        .collect();
+
+    let n = w.max(h);
+    let mut f = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    let mut v = vec![0; n];
+    let mut z = vec![0.0; n + 1];
+
+    for x in 0..w {
+        for (y, value) in f[..h].iter_mut().enumerate() {
+            *value = grid[y * w + x];
+        }
+        distance_transform_1d(&f[..h], &mut d[..h], &mut v, &mut z);
+        for (y, &value) in d[..h].iter().enumerate() {
+            grid[y * w + x] = value;
+        }
+    }
+    for row in grid.chunks_exact_mut(w) {
+        f[..w].copy_from_slice(row);
+        distance_transform_1d(&f[..w], row, &mut v, &mut z);
+    }
+    grid
+}
+
+/// 1D squared distance transform of the sampled function `f` into `d`: the
+/// lower envelope of the parabolas rooted at each sample
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let intersection = |q: usize, p: usize| {
+        let (qf, pf) = (q as f32, p as f32);
+        ((f[q] + qf * qf) - (f[p] + pf * pf)) / (2.0 * qf - 2.0 * pf)
+    };
+
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in 1..f.len() {
+        let mut s = intersection(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+
+    k = 0;
+    for (q, out) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let offset = q as f32 - v[k] as f32;
+        *out = offset * offset + f[v[k]];
+    }
+}
@@ -0,0 +1,179 @@
+//! Badges and chips example
+//!
+//! Demonstrates `Badge` pinned to the corners of icon tiles (a count that
+//! caps at "99+", a dot and a status text) and removable `Chip` tags. The
+//! chips along the top double as buttons for changing the unread count.
+//!
+//! Controls:
+//! - Click "New message" / "Mark all read" to change the inbox badge
+//! - Click × on a tag to remove it, "Reset tags" to bring them back
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, Corner, CornerShape, DebugOptions,
+    HorizontalAlign, Layout, Node, Size, Spacing, Style, TextContent, UiContext, VerticalAlign,
+};
+use astra_gui_interactive::{Badge, Chip};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const TAGS: [&str; 6] = ["rust", "gui", "wgpu", "layout", "text", "animation"];
+
+struct BadgesAndChipsExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    unread: u32,
+    tags: Vec<&'static str>,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+/// A square tile with a label, standing in for an icon button
+fn tile(label: &str) -> Node {
+    Node::new()
+        .with_width(Size::lpx(72.0))
+        .with_height(Size::lpx(72.0))
+        .with_content(Content::Text(
+            TextContent::new(label)
+                .with_font_size(Size::lpx(14.0))
+                .with_color(mocha::TEXT)
+                .with_h_align(HorizontalAlign::Center)
+                .with_v_align(VerticalAlign::Center),
+        ))
+        .with_style(Style {
+            fill_color: Some(mocha::SURFACE0),
+            corner_shape: Some(CornerShape::Round(Size::lpx(12.0))),
+            ..Default::default()
+        })
+}
+
+fn row(children: Vec<Node>) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_gap(Size::lpx(24.0))
+        .with_layout_direction(Layout::Horizontal)
+        .with_children(children)
+}
+
+impl ExampleApp for BadgesAndChipsExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            unread: 3,
+            tags: TAGS.to_vec(),
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Badges and Chips Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (800, 600)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let mut unread = self.unread;
+        let controls = row(vec![
+            Chip::new("New message")
+                .with_id("new_message")
+                .on_click(|| unread += 1)
+                .node(ctx),
+            Chip::new("+100 messages")
+                .with_id("many_messages")
+                .on_click(|| unread += 100)
+                .node(ctx),
+            Chip::new("Mark all read")
+                .with_id("mark_read")
+                .on_click(|| unread = 0)
+                .node(ctx),
+        ]);
+        self.unread = unread;
+
+        let badges = row(vec![
+            Badge::count(self.unread)
+                .anchor(tile("Inbox"), Corner::TopRight)
+                .node(ctx),
+            Badge::dot()
+                .anchor(tile("Updates"), Corner::TopRight)
+                .node(ctx),
+            Badge::text("New")
+                .anchor(tile("Plugins"), Corner::BottomRight)
+                .node(ctx),
+            Badge::count(7).node(ctx),
+        ]);
+
+        // Tags are keyed by name, so removing one keeps the others' IDs
+        let mut removed = None;
+        let mut tags: Vec<Node> = self
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(index, tag)| {
+                Chip::new(*tag)
+                    .with_id(format!("tag_{}", tag))
+                    .on_remove(|| removed = Some(index))
+                    .node(ctx)
+            })
+            .collect();
+        let mut reset = false;
+        tags.push(
+            Chip::new("Reset tags")
+                .with_id("reset_tags")
+                .on_click(|| reset = true)
+                .node(ctx),
+        );
+        if let Some(index) = removed {
+            self.tags.remove(index);
+        }
+        if reset {
+            self.tags = TAGS.to_vec();
+        }
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(24.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Badges and Chips", 28.0, mocha::TEXT),
+                controls,
+                badges,
+                text("Tags", 18.0, mocha::SUBTEXT1),
+                row(tags).with_gap(Size::lpx(8.0)),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<BadgesAndChipsExample>();
+}
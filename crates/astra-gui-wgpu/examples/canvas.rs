@@ -0,0 +1,252 @@
+//! Canvas example
+//!
+//! Demonstrates custom drawing with `Canvas` and its `Painter`: a plot with a
+//! grid, labels and two curves, and a dial gizmo drawn from lines, circles and
+//! text. The dial is interactive and shifts the phase of the plotted curves.
+//!
+//! Controls:
+//! - Click or drag on the dial to turn it
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use std::f32::consts::{PI, TAU};
+
+use astra_gui::{
+    catppuccin::mocha, Component, Content, DebugOptions, HorizontalAlign, InteractionEvent, Layout,
+    Node, Painter, Rect, Size, Spacing, Style, TextContent, UiContext, VerticalAlign,
+};
+use astra_gui_interactive::Canvas;
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const DIAL_SIZE: f32 = 220.0;
+const DIAL_PADDING: f32 = 8.0;
+const SAMPLES: usize = 200;
+
+struct CanvasExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    /// Dial angle in radians, clockwise from the top
+    phase: f32,
+}
+
+fn label(text: impl Into<String>, size: f32, color: astra_gui::Color) -> TextContent {
+    TextContent::new(text)
+        .with_font_size(Size::lpx(size))
+        .with_color(color)
+}
+
+/// Two phase-shifted curves over a labelled grid
+fn paint_plot(painter: &mut Painter, phase: f32) {
+    let (width, height) = painter.size();
+    let plot = Rect::new([40.0, 10.0], [width - 10.0, height - 30.0]);
+    let to_screen = |x: f32, y: f32| {
+        [
+            plot.min[0] + x / TAU * plot.width(),
+            plot.min[1] + (1.0 - y) * 0.5 * plot.height(),
+        ]
+    };
+
+    for step in 0..=4 {
+        let x = step as f32 / 4.0 * TAU;
+        let [left, _] = to_screen(x, 0.0);
+        painter.line(
+            [left, plot.min[1]],
+            [left, plot.max[1]],
+            1.0,
+            mocha::SURFACE1,
+        );
+        painter.text(
+            [left, plot.max[1] + 6.0],
+            label(format!("{}π/2", step), 13.0, mocha::SUBTEXT0)
+                .with_h_align(HorizontalAlign::Center)
+                .with_v_align(VerticalAlign::Top),
+        );
+    }
+    for value in [-1.0, 0.0, 1.0] {
+        let [_, top] = to_screen(0.0, value);
+        painter.line([plot.min[0], top], [plot.max[0], top], 1.0, mocha::SURFACE1);
+        painter.text(
+            [plot.min[0] - 8.0, top],
+            label(format!("{}", value), 13.0, mocha::SUBTEXT0)
+                .with_h_align(HorizontalAlign::Right)
+                .with_v_align(VerticalAlign::Center),
+        );
+    }
+    painter.rect_stroke(plot, 1.0, mocha::OVERLAY0);
+
+    let curve = |f: fn(f32) -> f32| {
+        (0..=SAMPLES).map(move |i| {
+            let x = i as f32 / SAMPLES as f32 * TAU;
+            to_screen(x, f(x + phase))
+        })
+    };
+    painter.path(curve(f32::sin), 2.5, mocha::BLUE);
+    painter.path(curve(f32::cos), 2.5, mocha::PEACH);
+
+    // Markers on the peaks of the sine
+    for i in 0..=SAMPLES {
+        let x = i as f32 / SAMPLES as f32 * TAU;
+        let previous = (x - TAU / SAMPLES as f32 + phase).sin();
+        let next = (x + TAU / SAMPLES as f32 + phase).sin();
+        if (x + phase).sin() > previous.max(next) {
+            let [left, top] = to_screen(x, (x + phase).sin());
+            painter.convex_polygon(
+                [
+                    [left, top - 6.0],
+                    [left + 6.0, top - 14.0],
+                    [left - 6.0, top - 14.0],
+                ],
+                mocha::BLUE,
+            );
+        }
+    }
+}
+
+/// A round dial with ticks and a handle pointing at `angle`
+fn paint_dial(painter: &mut Painter, angle: f32) {
+    let (width, height) = painter.size();
+    let center = [width * 0.5, height * 0.5];
+    let radius = width.min(height) * 0.5 - 8.0;
+    let at = |angle: f32, distance: f32| {
+        [
+            center[0] + angle.sin() * distance,
+            center[1] - angle.cos() * distance,
+        ]
+    };
+
+    painter.circle(center, radius, mocha::SURFACE0);
+    painter.circle_stroke(center, radius, 2.0, mocha::OVERLAY0);
+    for tick in 0..12 {
+        let tick_angle = tick as f32 / 12.0 * TAU;
+        let inner = if tick % 3 == 0 {
+            radius - 16.0
+        } else {
+            radius - 8.0
+        };
+        painter.line(
+            at(tick_angle, inner),
+            at(tick_angle, radius - 2.0),
+            2.0,
+            mocha::OVERLAY1,
+        );
+    }
+
+    painter.line(center, at(angle, radius - 24.0), 4.0, mocha::MAUVE);
+    painter.circle(at(angle, radius - 24.0), 8.0, mocha::MAUVE);
+    painter.circle(center, 5.0, mocha::TEXT);
+    painter.text(
+        [center[0], center[1] + radius * 0.45],
+        label(
+            format!("{:.0}°", angle.to_degrees().rem_euclid(360.0)),
+            18.0,
+            mocha::TEXT,
+        )
+        .with_h_align(HorizontalAlign::Center)
+        .with_v_align(VerticalAlign::Center),
+    );
+}
+
+impl ExampleApp for CanvasExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            phase: 0.0,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Canvas Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (1000, 700)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        // Turn the dial toward the pointer; positions are relative to the
+        // dial node, so remove its padding to get painter coordinates
+        for event in ctx.events_for("dial") {
+            let (InteractionEvent::Click { .. }
+            | InteractionEvent::DragStart { .. }
+            | InteractionEvent::DragMove { .. }) = event.event
+            else {
+                continue;
+            };
+            let center = (DIAL_SIZE - 2.0 * DIAL_PADDING) * 0.5;
+            let x = event.local_position.x / event.zoom - DIAL_PADDING - center;
+            let y = event.local_position.y / event.zoom - DIAL_PADDING - center;
+            if x != 0.0 || y != 0.0 {
+                self.phase = x.atan2(-y);
+            }
+        }
+
+        let phase = self.phase;
+        let plot = Canvas::new(move |painter| paint_plot(painter, phase))
+            .height(Size::lpx(360.0))
+            .node(ctx);
+        let dial = Canvas::new(move |painter| paint_dial(painter, phase))
+            .width(Size::lpx(DIAL_SIZE))
+            .height(Size::lpx(DIAL_SIZE))
+            .with_id("dial")
+            .node(ctx);
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_content(Content::Text(label("Canvas", 28.0, mocha::TEXT))),
+                plot,
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_gap(Size::lpx(24.0))
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_children(vec![
+                        dial,
+                        Node::new()
+                            .with_width(Size::Fill)
+                            .with_content(Content::Text(label(
+                                "Drag the dial to shift the phase of the curves.\n\
+                                 Everything here is drawn with a Painter: lines, \
+                                 circles, paths, triangles and text.",
+                                16.0,
+                                mocha::SUBTEXT1,
+                            ))),
+                    ]),
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_content(Content::Text(label(
+                        DEBUG_HELP_TEXT_ONELINE,
+                        14.0,
+                        mocha::OVERLAY1,
+                    ))),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<CanvasExample>();
+}
@@ -0,0 +1,186 @@
+//! Code editor example
+//!
+//! Demonstrates `CodeEditor` with line numbers, the current-line highlight,
+//! bracket matching and a soft wrap toggle. Highlighting comes from a small
+//! keyword highlighter registered on the context.
+//!
+//! Controls:
+//! - Click the editor and type; place the caret next to a bracket to see its pair
+//! - Toggle "Soft wrap" and "Line numbers" with the checkboxes
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, TextSpan, UiContext,
+};
+use astra_gui_interactive::{checkbox, checkbox_clicked, CodeEditor};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const SAMPLE: &str = r#"// A small sample to edit
+use std::collections::HashMap;
+
+fn word_counts(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace().map(|w| w.to_lowercase()) {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn main() {
+    let counts = word_counts("the quick brown fox jumps over the lazy dog, and the dog sleeps");
+    if let Some(n) = counts.get("the") {
+        println!("'the' appears {} times", n);
+    }
+}
+"#;
+
+const KEYWORDS: [&str; 16] = [
+    "as", "else", "fn", "for", "if", "impl", "in", "let", "match", "mut", "pub", "return", "self",
+    "struct", "use", "while",
+];
+
+/// Colors Rust keywords, numbers, strings and line comments
+fn highlight_rust(code: &str, language: &str) -> Vec<TextSpan> {
+    if language != "rust" {
+        return vec![TextSpan::new(code)];
+    }
+
+    let mut spans = Vec::new();
+    let mut rest = code;
+    while let Some(first) = rest.chars().next() {
+        let (len, color) = if rest.starts_with("//") {
+            (rest.find('\n').unwrap_or(rest.len()), mocha::OVERLAY1)
+        } else if first == '"' {
+            let end = rest[1..].find('"').map_or(rest.len(), |i| i + 2);
+            (end, mocha::GREEN)
+        } else if first.is_alphanumeric() || first == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            let color = if KEYWORDS.contains(&word) {
+                mocha::MAUVE
+            } else if first.is_ascii_digit() {
+                mocha::PEACH
+            } else if first.is_uppercase() {
+                mocha::YELLOW
+            } else {
+                mocha::TEXT
+            };
+            (end, color)
+        } else {
+            (first.len_utf8(), mocha::OVERLAY2)
+        };
+        spans.push(TextSpan::new(&rest[..len]).with_color(color));
+        rest = &rest[len..];
+    }
+    spans
+}
+
+struct CodeEditorExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    source: String,
+    soft_wrap: bool,
+    line_numbers: bool,
+    highlighter_set: bool,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+impl ExampleApp for CodeEditorExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            source: SAMPLE.to_string(),
+            soft_wrap: false,
+            line_numbers: true,
+            highlighter_set: false,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Code Editor Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (900, 640)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        if !self.highlighter_set {
+            ctx.set_highlighter(highlight_rust);
+            self.highlighter_set = true;
+        }
+
+        if checkbox_clicked(ctx, "soft_wrap") {
+            self.soft_wrap = !self.soft_wrap;
+        }
+        if checkbox_clicked(ctx, "line_numbers") {
+            self.line_numbers = !self.line_numbers;
+        }
+
+        let toggles = Node::new()
+            .with_width(Size::Fill)
+            .with_gap(Size::lpx(24.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                checkbox(ctx, "soft_wrap", self.soft_wrap, "Soft wrap"),
+                checkbox(ctx, "line_numbers", self.line_numbers, "Line numbers"),
+            ]);
+
+        let lines = self.source.lines().count();
+        let editor = CodeEditor::new(&mut self.source, "rust")
+            .with_id("code")
+            .soft_wrap(self.soft_wrap)
+            .line_numbers(self.line_numbers)
+            .node(ctx);
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Code Editor", 28.0, mocha::TEXT),
+                toggles,
+                editor,
+                text(format!("{} lines", lines), 14.0, mocha::SUBTEXT0),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<CodeEditorExample>();
+}
@@ -6,6 +6,7 @@
 //! Controls:
 //! - Click headers to expand/collapse sections
 //! - Nested collapsibles work independently
+//! - In the accordion, opening a section closes the others
 //! - Debug controls (M/P/B/C/R/G/O/T/D/S)
 //! - ESC: quit
 
@@ -19,7 +20,8 @@ use astra_gui::{
     Spacing, TextContent, UiContext, VerticalAlign,
 };
 use astra_gui_interactive::{
-    Button, ButtonStyle, Collapsible, CollapsibleStyle, Slider, SliderStyle, Toggle, ToggleStyle,
+    Accordion, Button, ButtonStyle, Collapsible, CollapsibleStyle, Slider, SliderStyle, Toggle,
+    ToggleStyle,
 };
 use astra_gui_text::Engine as TextEngine;
 use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
@@ -35,6 +37,7 @@ struct AppState {
     section3_expanded: bool,
     nested1_expanded: bool,
     nested2_expanded: bool,
+    accordion_open: Option<usize>,
 
     // Component states
     toggle_value: bool,
@@ -59,6 +62,7 @@ impl ExampleApp for CollapsibleExample {
                 section3_expanded: true,
                 nested1_expanded: false,
                 nested2_expanded: true,
+                accordion_open: Some(0),
                 toggle_value: false,
                 slider_value: 50.0,
                 counter: 0,
@@ -90,6 +94,7 @@ impl ExampleApp for CollapsibleExample {
             section3_expanded,
             nested1_expanded,
             nested2_expanded,
+            accordion_open,
             toggle_value,
             slider_value,
             counter,
@@ -101,6 +106,7 @@ impl ExampleApp for CollapsibleExample {
                 s.section3_expanded,
                 s.nested1_expanded,
                 s.nested2_expanded,
+                s.accordion_open,
                 s.toggle_value,
                 s.slider_value,
                 s.counter,
@@ -113,6 +119,7 @@ impl ExampleApp for CollapsibleExample {
         let state_section3 = self.state.clone();
         let state_nested1 = self.state.clone();
         let state_nested2 = self.state.clone();
+        let state_accordion = self.state.clone();
         let state_dec = self.state.clone();
         let state_inc = self.state.clone();
         let state_toggle = self.state.clone();
@@ -304,6 +311,39 @@ impl ExampleApp for CollapsibleExample {
             ])
             .node(ctx);
 
+        // Accordion: at most one of its sections is open
+        let accordion_text = |text: &str| {
+            vec![Node::new()
+                .with_width(Size::Fill)
+                .with_content(Content::Text(
+                    TextContent::new(text.to_string())
+                        .with_font_size(Size::lpx(20.0))
+                        .with_color(mocha::SUBTEXT1)
+                        .with_h_align(HorizontalAlign::Left)
+                        .with_v_align(VerticalAlign::Center),
+                ))]
+        };
+        let accordion = Accordion::new(accordion_open)
+            .with_style(
+                CollapsibleStyle::default().with_corners(CornerShape::Round(Size::lpx(10.0))),
+            )
+            .section(
+                "Accordion: First",
+                accordion_text("Opening another section closes this one."),
+            )
+            .section(
+                "Accordion: Second",
+                accordion_text("Only one section is open at a time."),
+            )
+            .section(
+                "Accordion: Third",
+                accordion_text("Click the open header to close it."),
+            )
+            .on_change(move |open| {
+                state_accordion.borrow_mut().accordion_open = open;
+            })
+            .node(ctx);
+
         Node::new()
             .with_zoom(1.5)
             .with_width(Size::Fill)
@@ -337,6 +377,7 @@ impl ExampleApp for CollapsibleExample {
                 section1,
                 section2,
                 section3,
+                accordion,
                 // Spacer
                 Node::new().with_height(Size::Fill),
                 // Debug help
@@ -0,0 +1,168 @@
+//! Drag and drop example
+//!
+//! Demonstrates moving data between nodes: each card attaches its index as
+//! the drag payload when a drag starts, along with a ghost preview, and the
+//! columns are drop targets that highlight while a card is over them and take
+//! the card when it is dropped.
+//!
+//! Controls:
+//! - Drag a card onto the other column to move it
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Content, CornerShape, DebugOptions, DragGhost, InteractionEvent,
+    Layout, Node, NodeId, Size, Spacing, Stroke, Style, TextContent, Transition, UiContext,
+};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const COLUMNS: [&str; 2] = ["To do", "Done"];
+
+struct DragAndDropExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    /// Card titles with the column they are in
+    cards: Vec<(&'static str, usize)>,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+fn card(title: &str) -> Node {
+    Node::new()
+        .with_width(Size::lpx(260.0))
+        .with_padding(Spacing::all(Size::lpx(12.0)))
+        .with_style(Style {
+            fill_color: Some(mocha::SURFACE0),
+            corner_shape: Some(CornerShape::Round(Size::lpx(8.0))),
+            ..Default::default()
+        })
+        .with_child(text(title, 16.0, mocha::TEXT))
+}
+
+impl ExampleApp for DragAndDropExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            cards: vec![
+                ("Write the release notes", 0),
+                ("Fix the flaky test", 0),
+                ("Review the layout PR", 0),
+                ("Update the screenshots", 1),
+            ],
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Drag and Drop Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (760, 560)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        // Start carrying a card when its drag starts
+        for (index, (title, _)) in self.cards.iter().enumerate() {
+            let id = format!("card_{index}");
+            let started = ctx
+                .events_for(&id)
+                .any(|e| matches!(e.event, InteractionEvent::DragStart { .. }));
+            if started {
+                ctx.set_drag_payload(index);
+                let title = *title;
+                ctx.set_drag_ghost(DragGhost::new(move || card(title)));
+            }
+        }
+
+        // Move dropped cards into the column they were dropped on
+        for column in 0..COLUMNS.len() {
+            if let Some(index) = ctx.take_drop::<usize>(&format!("column_{column}")) {
+                self.cards[index].1 = column;
+            }
+        }
+
+        let columns = (0..COLUMNS.len())
+            .map(|column| {
+                let id = format!("column_{column}");
+                let over = ctx.is_drag_over(&id);
+                let mut children = vec![text(COLUMNS[column], 18.0, mocha::SUBTEXT1)];
+                children.extend(
+                    self.cards
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, (_, c))| *c == column)
+                        .map(|(index, (title, _))| {
+                            card(title).with_id(NodeId::new(format!("card_{index}")))
+                        }),
+                );
+                Node::new()
+                    .with_id(NodeId::new(&id))
+                    .with_drop_target(true)
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_padding(Spacing::all(Size::lpx(16.0)))
+                    .with_gap(Size::lpx(10.0))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_style(Style {
+                        fill_color: Some(if over { mocha::SURFACE1 } else { mocha::MANTLE }),
+                        stroke: Some(Stroke::new(
+                            Size::lpx(2.0),
+                            if over { mocha::BLUE } else { mocha::SURFACE0 },
+                        )),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(12.0))),
+                        ..Default::default()
+                    })
+                    .with_transition(Transition::quick())
+                    .with_children(children)
+            })
+            .collect();
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Drag and Drop", 28.0, mocha::TEXT),
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_gap(Size::lpx(16.0))
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_children(columns),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<DragAndDropExample>();
+}
@@ -0,0 +1,125 @@
+//! Drop zone example
+//!
+//! Demonstrates `DropZone` with files dragged in from the system file
+//! manager: one zone only takes images, the other takes any file. Dropped
+//! files are listed below the zones.
+//!
+//! Controls:
+//! - Drag files from your file manager onto a zone
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, UiContext,
+};
+use astra_gui_interactive::DropZone;
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+use std::path::PathBuf;
+
+/// Most recent drops to list
+const MAX_LISTED: usize = 10;
+
+struct DropZoneExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    dropped: Vec<(&'static str, PathBuf)>,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+impl ExampleApp for DropZoneExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            dropped: Vec::new(),
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Drop Zone Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (800, 700)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let mut dropped = Vec::new();
+        let zones = Node::new()
+            .with_width(Size::Fill)
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                DropZone::new("Drop images here\n(png, jpg, gif)")
+                    .accept(&["png", "jpg", "jpeg", "gif"])
+                    .on_drop(|files| dropped.extend(files.iter().map(|f| ("Images", f.clone()))))
+                    .node(ctx),
+                DropZone::new("Drop any file here")
+                    .on_drop(|files| dropped.extend(files.iter().map(|f| ("Files", f.clone()))))
+                    .node(ctx),
+            ]);
+        self.dropped.extend(dropped);
+        let skip = self.dropped.len().saturating_sub(MAX_LISTED);
+        self.dropped.drain(..skip);
+
+        let mut list: Vec<Node> = self
+            .dropped
+            .iter()
+            .rev()
+            .map(|(zone, path)| text(format!("{}: {}", zone, path.display()), 14.0, mocha::TEXT))
+            .collect();
+        if list.is_empty() {
+            list.push(text("Nothing dropped yet", 14.0, mocha::SUBTEXT0));
+        }
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Drop Zone", 28.0, mocha::TEXT),
+                zones,
+                text("Dropped files", 18.0, mocha::SUBTEXT1),
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_gap(Size::lpx(4.0))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_children(list),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<DropZoneExample>();
+}
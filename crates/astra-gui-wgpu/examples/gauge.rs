@@ -0,0 +1,223 @@
+//! Gauge example
+//!
+//! A small server dashboard: needle gauges with colored zones for CPU
+//! temperature and load, filled gauges for memory and network, and a disk
+//! usage pie drawn with `Painter::sector`. The readings jump to new values
+//! every second and the gauges ease toward them.
+//!
+//! Controls:
+//! - Click "Spike" to push all readings to their maximum
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use std::f32::consts::{PI, TAU};
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, CornerShape, DebugOptions, Layout, Node, Size,
+    Spacing, Style, TextContent, UiContext,
+};
+use astra_gui_interactive::{Canvas, Chip, Gauge, GaugeIndicator};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+/// Disk usage per category, in GB
+const DISK: [(&str, f32, Color); 4] = [
+    ("System", 42.0, mocha::BLUE),
+    ("Logs", 18.0, mocha::PEACH),
+    ("Backups", 96.0, mocha::MAUVE),
+    ("Free", 100.0, mocha::SURFACE1),
+];
+
+struct GaugeExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    tick: u64,
+    spike: bool,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+/// A repeatable pseudo-random reading in `0.0..1.0` for a tick and channel
+fn reading(tick: u64, channel: u64) -> f32 {
+    let mut x = tick.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ channel.wrapping_mul(0xBF58_476D);
+    x ^= x >> 31;
+    x = x.wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 29;
+    (x % 10_000) as f32 / 10_000.0
+}
+
+fn card(title: &str, gauge: Node) -> Node {
+    Node::new()
+        .with_padding(Spacing::all(Size::lpx(16.0)))
+        .with_gap(Size::lpx(8.0))
+        .with_layout_direction(Layout::Vertical)
+        .with_style(Style {
+            fill_color: Some(mocha::MANTLE),
+            corner_shape: Some(CornerShape::Round(Size::lpx(12.0))),
+            ..Default::default()
+        })
+        .with_children(vec![text(title, 16.0, mocha::SUBTEXT1), gauge])
+}
+
+impl ExampleApp for GaugeExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            tick: 0,
+            spike: false,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Gauge Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (1000, 640)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let tick = ctx.time() as u64;
+        if tick != self.tick {
+            self.tick = tick;
+            self.spike = false;
+        }
+        let level = |channel| {
+            if self.spike {
+                1.0
+            } else {
+                reading(tick, channel)
+            }
+        };
+
+        let temperature = 35.0 + level(0) * 65.0;
+        let load = level(1) * 100.0;
+        let memory = 4.0 + level(2) * 12.0;
+        let network = level(3) * 950.0;
+
+        let mut spike = false;
+        let spike_button = Chip::new("Spike")
+            .with_id("spike")
+            .on_click(|| spike = true)
+            .node(ctx);
+        if spike {
+            self.spike = true;
+        }
+
+        let gauges = Node::new()
+            .with_width(Size::Fill)
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                card(
+                    "CPU temperature",
+                    Gauge::new(temperature, 20.0..=100.0)
+                        .zone(20.0..=70.0, mocha::GREEN)
+                        .zone(70.0..=85.0, mocha::YELLOW)
+                        .zone(85.0..=100.0, mocha::RED)
+                        .unit("°C")
+                        .label("package")
+                        .with_id("temperature")
+                        .node(ctx),
+                ),
+                card(
+                    "CPU load",
+                    Gauge::new(load, 0.0..=100.0)
+                        .zone(80.0..=100.0, mocha::RED)
+                        .unit("%")
+                        .label("8 cores")
+                        .with_id("load")
+                        .node(ctx),
+                ),
+                card(
+                    "Memory",
+                    Gauge::new(memory, 0.0..=16.0)
+                        .zone(0.0..=12.0, mocha::TEAL)
+                        .zone(12.0..=16.0, mocha::PEACH)
+                        .indicator(GaugeIndicator::Fill)
+                        .decimals(1)
+                        .unit(" GB")
+                        .label("of 16 GB")
+                        .with_id("memory")
+                        .node(ctx),
+                ),
+                card(
+                    "Network",
+                    Gauge::new(network, 0.0..=1000.0)
+                        .indicator(GaugeIndicator::Fill)
+                        .unit(" Mb/s")
+                        .label("eth0")
+                        .with_id("network")
+                        .node(ctx),
+                ),
+            ]);
+
+        let total: f32 = DISK.iter().map(|(_, size, _)| size).sum();
+        let disk = Canvas::new(move |painter| {
+            let (width, height) = painter.size();
+            let center = [height * 0.5, height * 0.5];
+            let mut angle = -0.5 * PI;
+            for (_, size, color) in DISK {
+                let sweep = size / total * TAU;
+                painter.sector(center, height * 0.5, angle, angle + sweep, color);
+                angle += sweep;
+            }
+            for (index, (name, size, color)) in DISK.iter().enumerate() {
+                let y = 12.0 + index as f32 * 24.0;
+                painter.circle([height + 24.0, y], 6.0, *color);
+                painter.text(
+                    [height + 40.0, y - 9.0],
+                    TextContent::new(format!("{} - {} GB", name, size))
+                        .with_font_size(Size::lpx(14.0))
+                        .with_color(mocha::TEXT),
+                );
+            }
+        })
+        .width(Size::lpx(360.0))
+        .height(Size::lpx(140.0));
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(20.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Server Dashboard", 28.0, mocha::TEXT),
+                spike_button,
+                gauges,
+                card("Disk usage", disk.node(ctx)),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<GaugeExample>();
+}
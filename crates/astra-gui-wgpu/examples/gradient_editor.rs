@@ -0,0 +1,182 @@
+//! Gradient editor example
+//!
+//! Demonstrates `GradientEditor` editing a linear gradient that fills a large
+//! preview card, with preset chips that swap in a different gradient. The
+//! card is restyled from the `Fill` the editor reports on every change.
+//!
+//! Controls:
+//! - Drag a stop handle along the bar to move it
+//! - Click the bar to add a stop, right-click a handle to remove it
+//! - Drag the H/S/L/A values to recolor the selected stop
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use std::f32::consts::PI;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, CornerShape, DebugOptions, Fill, HorizontalAlign,
+    Layout, LinearGradient, Node, Size, Spacing, Style, TextContent, UiContext, VerticalAlign,
+};
+use astra_gui_interactive::{Chip, GradientEditor};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+struct GradientEditorExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    gradient: LinearGradient,
+    fill: Fill,
+    changes: usize,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+fn sunset() -> LinearGradient {
+    LinearGradient::new([
+        (0.0, mocha::MAUVE),
+        (0.55, mocha::RED),
+        (1.0, mocha::YELLOW),
+    ])
+    .with_angle(0.75 * PI)
+}
+
+fn ocean() -> LinearGradient {
+    LinearGradient::new([
+        (0.0, mocha::CRUST),
+        (0.4, mocha::BLUE),
+        (0.8, mocha::TEAL),
+        (1.0, mocha::GREEN),
+    ])
+}
+
+fn fade() -> LinearGradient {
+    LinearGradient::new([(0.0, mocha::TEXT), (1.0, mocha::TEXT.with_alpha(0.0))]).with_angle(PI)
+}
+
+impl ExampleApp for GradientEditorExample {
+    fn new() -> Self {
+        let gradient = sunset();
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            fill: Fill::LinearGradient(gradient.clone()),
+            gradient,
+            changes: 0,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Gradient Editor Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (900, 640)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let mut preset = None;
+        let presets = Node::new()
+            .with_width(Size::Fill)
+            .with_gap(Size::lpx(12.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                Chip::new("Sunset")
+                    .with_id("sunset")
+                    .on_click(|| preset = Some(sunset()))
+                    .node(ctx),
+                Chip::new("Ocean")
+                    .with_id("ocean")
+                    .on_click(|| preset = Some(ocean()))
+                    .node(ctx),
+                Chip::new("Fade")
+                    .with_id("fade")
+                    .on_click(|| preset = Some(fade()))
+                    .node(ctx),
+            ]);
+        if let Some(preset) = preset {
+            self.fill = Fill::LinearGradient(preset.clone());
+            self.gradient = preset;
+        }
+
+        let mut fill = None;
+        let editor = GradientEditor::new(&mut self.gradient)
+            .with_id("gradient")
+            .on_change(|new_fill| fill = Some(new_fill))
+            .node(ctx);
+        if let Some(fill) = fill {
+            self.fill = fill;
+            self.changes += 1;
+        }
+
+        let Fill::LinearGradient(gradient) = &self.fill else {
+            unreachable!("the editor only reports linear gradients");
+        };
+        let preview = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_style(Style {
+                corner_shape: Some(CornerShape::Round(Size::lpx(16.0))),
+                ..Style::linear_gradient(gradient.clone())
+            })
+            .with_content(Content::Text(
+                TextContent::new(format!(
+                    "{} stops at {:.0}°",
+                    gradient.stops.len(),
+                    gradient.angle.to_degrees()
+                ))
+                .with_font_size(Size::lpx(20.0))
+                .with_color(mocha::CRUST)
+                .with_h_align(HorizontalAlign::Left)
+                .with_v_align(VerticalAlign::Bottom),
+            ));
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Gradient Editor", 28.0, mocha::TEXT),
+                presets,
+                editor,
+                preview,
+                text(
+                    format!("{} edits reported", self.changes),
+                    14.0,
+                    mocha::SUBTEXT0,
+                ),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<GradientEditorExample>();
+}
@@ -0,0 +1,184 @@
+//! Property inspector example
+//!
+//! Demonstrates `Inspector` editing the properties of a light: a toggle for
+//! booleans, drag values for floats, a swatch with an HSLA picker for colors
+//! and a text input for strings. The preview on the left follows the edits.
+//!
+//! Controls:
+//! - Click a toggle, drag a number or click it to type a value
+//! - Click a color swatch to open its picker, click again to close it
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, CornerShape, DebugOptions, HorizontalAlign,
+    Layout, Node, Size, Spacing, Style, TextContent, UiContext, VerticalAlign,
+};
+use astra_gui_interactive::{Inspector, Property};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+struct Light {
+    name: String,
+    visible: bool,
+    radius: f32,
+    intensity: f32,
+    color: Color,
+    background: Color,
+}
+
+struct InspectorExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    light: Light,
+    last_change: Option<String>,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+impl InspectorExample {
+    /// The light drawn as a circle over its background
+    fn preview(&self) -> Node {
+        let light = &self.light;
+        let mut children = Vec::new();
+        if light.visible {
+            children.push(
+                Node::new()
+                    .with_width(Size::lpx(light.radius * 2.0))
+                    .with_height(Size::lpx(light.radius * 2.0))
+                    .with_style(Style {
+                        fill_color: Some(
+                            light
+                                .color
+                                .with_alpha(light.color.a * (light.intensity / 10.0).min(1.0)),
+                        ),
+                        corner_shape: Some(CornerShape::Round(Size::lpx(light.radius))),
+                        ..Default::default()
+                    }),
+            );
+        }
+        children.push(
+            Node::new().with_content(Content::Text(
+                TextContent::new(&light.name)
+                    .with_font_size(Size::lpx(16.0))
+                    .with_color(mocha::TEXT),
+            )),
+        );
+
+        Node::new()
+            .with_width(Size::lpx(320.0))
+            .with_height(Size::lpx(320.0))
+            .with_gap(Size::lpx(12.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_h_align(HorizontalAlign::Center)
+            .with_v_align(VerticalAlign::Center)
+            .with_style(Style {
+                fill_color: Some(light.background),
+                corner_shape: Some(CornerShape::Round(Size::lpx(12.0))),
+                ..Default::default()
+            })
+            .with_children(children)
+    }
+}
+
+impl ExampleApp for InspectorExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            light: Light {
+                name: "Key light".to_string(),
+                visible: true,
+                radius: 60.0,
+                intensity: 8.0,
+                color: mocha::YELLOW,
+                background: mocha::CRUST,
+            },
+            last_change: None,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Property Inspector Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (900, 600)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let preview = self.preview();
+
+        let mut last_change = None;
+        let light = &mut self.light;
+        let mut id = "light-01".to_string();
+        let inspector = Inspector::new(vec![
+            Property::text("Name", &mut light.name),
+            Property::text("ID", &mut id).disabled(true),
+            Property::bool("Visible", &mut light.visible),
+            Property::float("Radius", &mut light.radius)
+                .range(10.0..=140.0)
+                .speed(0.5),
+            Property::float("Intensity", &mut light.intensity).range(0.0..=10.0),
+            Property::color("Color", &mut light.color),
+            Property::color("Background", &mut light.background),
+        ])
+        .with_id("light_inspector")
+        .on_change(|label| last_change = Some(label.to_string()))
+        .node(ctx);
+        if last_change.is_some() {
+            self.last_change = last_change;
+        }
+
+        let status = match &self.last_change {
+            Some(label) => format!("Last change: {}", label),
+            None => "No changes yet".to_string(),
+        };
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(24.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Property Inspector", 28.0, mocha::TEXT),
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_gap(Size::lpx(32.0))
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_children(vec![preview, inspector]),
+                text(status, 14.0, mocha::SUBTEXT0),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<InspectorExample>();
+}
@@ -5,9 +5,13 @@
 //!
 //! Controls:
 //! - Click +/- buttons to change counter
-//! - Click toggle to enable/disable buttons
+//! - Click toggle to enable/disable buttons (a spinner shows while disabled)
+//! - Use the menu bar (or arrow keys while a menu is open) to change the counter
+//! - Right-click the counter for its context menu
+//! - Choose "Reset..." in the Counter menu for a confirmation dialog
 //! - Drag sliders to adjust values
 //! - Click text input to type
+//! - Click Show/Hide in the password field to reveal it
 //! - Debug controls (M/P/B/C/R/G/O/T/D/S)
 //! - ESC: quit
 
@@ -16,12 +20,13 @@
 mod shared;
 
 use astra_gui::{
-    catppuccin::mocha, Component, Content, DebugOptions, HorizontalAlign, Layout, Node, Shape,
-    Size, Spacing, StyledRect, TextContent, UiContext, VerticalAlign,
+    catppuccin::mocha, Component, Content, DebugOptions, HorizontalAlign, Layout, Node, NodeId,
+    Shape, Size, Spacing, StyledRect, TextContent, UiContext, VerticalAlign,
 };
 use astra_gui_interactive::{
-    Button, ButtonStyle, CursorShape, CursorStyle, Slider, SliderStyle, TextInput, TextInputStyle,
-    Toggle, ToggleStyle,
+    Button, ButtonStyle, Checkbox, ContextMenu, CursorShape, CursorStyle, Menu, MenuBar, MenuItem,
+    Modal, NumberInput, Slider, SliderStyle, Spinner, TextArea, TextInput, TextInputStyle, Toggle,
+    ToggleStyle,
 };
 use astra_gui_text::Engine as TextEngine;
 use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
@@ -64,11 +69,16 @@ fn labeled_caret_input(label: &str, input: Node) -> Node {
 struct AppState {
     counter: i32,
     nodes_disabled: bool,
+    show_counter_sign: bool,
+    confirm_reset: bool,
     slider_value: f32,
     continuous_slider_value: f32,
     text_line: String,
     text_underline: String,
     text_block: String,
+    password: String,
+    notes: String,
+    quantity: f32,
 }
 
 struct Interactive {
@@ -85,11 +95,16 @@ impl ExampleApp for Interactive {
             state: Rc::new(RefCell::new(AppState {
                 counter: 0,
                 nodes_disabled: false,
+                show_counter_sign: false,
+                confirm_reset: false,
                 slider_value: 7.0,
                 continuous_slider_value: 50.0,
                 text_line: String::new(),
                 text_underline: String::new(),
                 text_block: String::new(),
+                password: String::new(),
+                notes: String::new(),
+                quantity: 1.0,
             })),
         }
     }
@@ -114,12 +129,20 @@ impl ExampleApp for Interactive {
         // Clone Rc for use in callbacks
         let state = self.state.clone();
 
+        // Actions chosen in the counter's context menu last frame
+        match ctx.context_menu_action("counter_display") {
+            Some("reset") => state.borrow_mut().counter = 0,
+            Some("double") => state.borrow_mut().counter *= 2,
+            _ => {}
+        }
+
         // Read current values for display
-        let (counter, nodes_disabled, slider_value, continuous_slider_value) = {
+        let (counter, nodes_disabled, show_counter_sign, slider_value, continuous_slider_value) = {
             let s = state.borrow();
             (
                 s.counter,
                 s.nodes_disabled,
+                s.show_counter_sign,
                 s.slider_value,
                 s.continuous_slider_value,
             )
@@ -129,17 +152,89 @@ impl ExampleApp for Interactive {
         let state_dec = state.clone();
         let state_inc = state.clone();
         let state_toggle = state.clone();
+        let state_checkbox = state.clone();
         let state_stepped = state.clone();
         let state_continuous = state.clone();
         let state_text = state.clone();
 
-        Node::new()
-            .with_zoom(2.0)
+        // Menu bar acting on the app state
+        let state_action = |f: fn(&mut AppState)| {
+            let state = state.clone();
+            move || f(&mut state.borrow_mut())
+        };
+        let menu_bar = MenuBar::new()
+            .menu(
+                Menu::new("Counter")
+                    .item(
+                        MenuItem::new("Increment")
+                            .shortcut("+")
+                            .on_select(state_action(|s| s.counter += 1)),
+                    )
+                    .item(
+                        MenuItem::new("Decrement")
+                            .shortcut("-")
+                            .on_select(state_action(|s| s.counter -= 1)),
+                    )
+                    .separator()
+                    .item(MenuItem::submenu(
+                        "Set to",
+                        vec![
+                            MenuItem::new("0").on_select(state_action(|s| s.counter = 0)),
+                            MenuItem::new("10").on_select(state_action(|s| s.counter = 10)),
+                            MenuItem::new("100").on_select(state_action(|s| s.counter = 100)),
+                        ],
+                    ))
+                    .item(
+                        MenuItem::new("Reset...")
+                            .disabled(counter == 0)
+                            .on_select(state_action(|s| s.confirm_reset = true)),
+                    )
+                    .item(
+                        MenuItem::new("Negate")
+                            .disabled(counter == 0)
+                            .on_select(state_action(|s| s.counter = -s.counter)),
+                    ),
+            )
+            .menu(
+                Menu::new("Options").item(
+                    MenuItem::new(if nodes_disabled {
+                        "Enable buttons"
+                    } else {
+                        "Disable buttons"
+                    })
+                    .on_select(state_action(|s| s.nodes_disabled = !s.nodes_disabled)),
+                ),
+            )
+            .node(ctx);
+
+        // Confirmation dialog, blocking the rest of the UI while open
+        let confirm_reset = state.borrow().confirm_reset;
+        let reset_dialog = Modal::new(confirm_reset)
+            .title("Reset counter?")
+            .content(
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_content(Content::Text(
+                        TextContent::new(format!("The count of {} will be set to 0.", counter))
+                            .with_font_size(Size::lpx(16.0))
+                            .with_color(mocha::SUBTEXT1),
+                    )),
+            )
+            .confirm_label("Reset")
+            .on_confirm(state_action(|s| {
+                s.counter = 0;
+                s.confirm_reset = false;
+            }))
+            .on_cancel(state_action(|s| s.confirm_reset = false))
+            .node(ctx);
+
+        let content = Node::new()
             .with_width(Size::Fill)
             .with_height(Size::Fill)
             .with_layout_direction(Layout::Vertical)
             .with_gap(Size::lpx(24.0))
             .with_children(vec![
+                menu_bar,
                 // Spacer
                 Node::new().with_height(Size::Fill),
                 // Title
@@ -152,16 +247,30 @@ impl ExampleApp for Interactive {
                             .with_h_align(HorizontalAlign::Center)
                             .with_v_align(VerticalAlign::Center),
                     )),
-                // Counter display
+                // Counter display with a right-click menu
                 Node::new()
                     .with_width(Size::Fill)
-                    .with_content(Content::Text(
-                        TextContent::new(format!("Count: {}", counter))
-                            .with_font_size(Size::lpx(48.0))
-                            .with_color(mocha::LAVENDER)
-                            .with_h_align(HorizontalAlign::Center)
-                            .with_v_align(VerticalAlign::Center),
-                    )),
+                    .with_h_align(HorizontalAlign::Center)
+                    .with_child(
+                        ContextMenu::new(
+                            Node::new()
+                                .with_id(NodeId::new("counter_display"))
+                                .with_content(Content::Text(
+                                    TextContent::new(if show_counter_sign {
+                                        format!("Count: {:+}", counter)
+                                    } else {
+                                        format!("Count: {}", counter)
+                                    })
+                                    .with_font_size(Size::lpx(48.0))
+                                    .with_color(mocha::LAVENDER)
+                                    .with_h_align(HorizontalAlign::Center)
+                                    .with_v_align(VerticalAlign::Center),
+                                )),
+                        )
+                        .item(MenuItem::new("Reset").action("reset"))
+                        .item(MenuItem::new("Double").action("double"))
+                        .node(ctx),
+                    ),
                 // Centered button container
                 Node::new()
                     .with_width(Size::Fill)
@@ -228,6 +337,20 @@ impl ExampleApp for Interactive {
                                 );
                             })
                             .node(ctx),
+                        // Checkbox
+                        Checkbox::new(show_counter_sign)
+                            .label("Show sign")
+                            .disabled(nodes_disabled)
+                            .on_toggle(move |checked| {
+                                state_checkbox.borrow_mut().show_counter_sign = checked;
+                            })
+                            .node(ctx),
+                        // Spinner standing in while the buttons are disabled
+                        if nodes_disabled {
+                            Spinner::new().size(24.0).node(ctx)
+                        } else {
+                            Node::new()
+                        },
                         // Spacer
                         Node::new().with_width(Size::Fill),
                     ]),
@@ -285,6 +408,55 @@ impl ExampleApp for Interactive {
                         // Spacer
                         Node::new().with_width(Size::Fill),
                     ]),
+                // Password field, masked until revealed
+                labeled_caret_input("Password", {
+                    let mut s = state_text.borrow_mut();
+                    TextInput::new(&mut s.password)
+                        .placeholder("Password")
+                        .password(true)
+                        .reveal_toggle(true)
+                        .disabled(nodes_disabled)
+                        .build(ctx)
+                }),
+                // Multi-line notes
+                {
+                    let mut s = state_text.borrow_mut();
+                    TextArea::new(&mut s.notes)
+                        .placeholder("Notes (wraps and scrolls; Ctrl+C/X/V)")
+                        .disabled(nodes_disabled)
+                        .on_change(|v| println!("Notes: {} chars", v.chars().count()))
+                        .node(ctx)
+                },
+                // Number input section
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_gap(Size::lpx(16.0))
+                    .with_children(vec![
+                        // Spacer
+                        Node::new().with_width(Size::Fill),
+                        // Label
+                        Node::new()
+                            .with_width(Size::lpx(150.0))
+                            .with_content(Content::Text(
+                                TextContent::new("Quantity:".to_string())
+                                    .with_font_size(Size::lpx(20.0))
+                                    .with_color(mocha::TEXT)
+                                    .with_h_align(HorizontalAlign::Right)
+                                    .with_v_align(VerticalAlign::Center),
+                            )),
+                        // Stepper (Shift = x10, Alt = x0.1)
+                        {
+                            let mut s = state_text.borrow_mut();
+                            NumberInput::new(&mut s.quantity)
+                                .range(0.0..=100.0)
+                                .disabled(nodes_disabled)
+                                .on_change(|v| println!("Quantity: {}", v))
+                                .node(ctx)
+                        },
+                        // Spacer
+                        Node::new().with_width(Size::Fill),
+                    ]),
                 // Stepped slider section
                 Node::new()
                     .with_width(Size::Fill)
@@ -388,7 +560,14 @@ impl ExampleApp for Interactive {
                             .with_h_align(HorizontalAlign::Left)
                             .with_v_align(VerticalAlign::Center),
                     )),
-            ])
+            ]);
+
+        Node::new()
+            .with_zoom(2.0)
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Stack)
+            .with_children(vec![content, reset_dialog])
     }
 }
 
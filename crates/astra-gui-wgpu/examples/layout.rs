@@ -5,8 +5,8 @@
 mod shared;
 
 use astra_gui::{
-    catppuccin::mocha, Content, CornerShape, DebugOptions, HorizontalAlign, Layout, Node, Shape,
-    Size, Spacing, Stroke, Style, StyledRect, TextContent, UiContext, VerticalAlign,
+    catppuccin::mocha, style, Content, CornerShape, DebugOptions, HorizontalAlign, Layout, Node,
+    Shape, Size, Spacing, StyledRect, TextContent, UiContext, VerticalAlign,
 };
 use shared::{debug_controls::DEBUG_HELP_TEXT_ONELINE, run_example, ExampleApp};
 
@@ -39,12 +39,9 @@ impl ExampleApp for LayoutExample {
 }
 
 fn child() -> Node {
-    Node::new().with_height(Size::Fill).with_style(Style {
-        fill_color: Some(mocha::SURFACE1),
-        corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-        stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-        ..Default::default()
-    })
+    Node::new()
+        .with_height(Size::Fill)
+        .with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) })
 }
 
 fn create_demo_ui(_width: f32, _height: f32, _debug_options: &DebugOptions) -> Node {
@@ -60,38 +57,18 @@ fn create_demo_ui(_width: f32, _height: f32, _debug_options: &DebugOptions) -> N
                 .with_gap(Size::lpx(10.0))
                 .with_height(Size::rel(0.15))
                 .with_padding(Spacing::all(Size::lpx(20.0)))
-                .with_style(Style {
-                    fill_color: Some(mocha::SURFACE0),
-                    corner_shape: Some(CornerShape::Round(Size::lpx(50.0))),
-                    stroke: Some(Stroke::new(Size::lpx(3.0), mocha::BLUE)),
-                    ..Default::default()
-                })
+                .with_style(style! { fill: mocha::SURFACE0, radius: 50, stroke: (3, mocha::BLUE) })
                 .with_children(vec![
                     Node::new()
                         .with_width(Size::Relative(0.7))
-                        .with_style(Style {
-                            fill_color: Some(mocha::SURFACE1),
-                            corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                            stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                            ..Default::default()
-                        }),
+                        .with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
                     Node::new()
                         .with_width(Size::Fill)
                         .with_layout_direction(Layout::Vertical)
                         .with_gap(Size::lpx(10.0))
                         .with_children(vec![
-                            Node::new().with_height(Size::Fill).with_style(Style {
-                                fill_color: Some(mocha::SURFACE1),
-                                corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                                stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                                ..Default::default()
-                            }),
-                            Node::new().with_height(Size::Fill).with_style(Style {
-                                fill_color: Some(mocha::SURFACE1),
-                                corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                                stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                                ..Default::default()
-                            }),
+                            Node::new().with_height(Size::Fill).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
+                            Node::new().with_height(Size::Fill).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
                         ]),
                 ]),
             // Main content area - horizontal layout
@@ -105,12 +82,7 @@ fn create_demo_ui(_width: f32, _height: f32, _debug_options: &DebugOptions) -> N
                         .with_width(Size::rel(0.25))
                         .with_padding(Spacing::all(Size::lpx(20.0)))
                         .with_gap(Size::lpx(10.0))
-                        .with_style(Style {
-                            fill_color: Some(mocha::SURFACE0),
-                            corner_shape: Some(CornerShape::Round(Size::lpx(50.0))),
-                            stroke: Some(Stroke::new(Size::lpx(3.0), mocha::MAUVE)),
-                            ..Default::default()
-                        })
+                        .with_style(style! { fill: mocha::SURFACE0, radius: 50, stroke: (3, mocha::MAUVE) })
                         .with_layout_direction(Layout::Vertical)
                         .with_children(vec![
                             // Sidebar items
@@ -130,46 +102,29 @@ fn create_demo_ui(_width: f32, _height: f32, _debug_options: &DebugOptions) -> N
                         .with_width(Size::rel(0.75))
                         .with_padding(Spacing::all(Size::lpx(20.0)))
                         .with_gap(Size::lpx(20.0))
-                        .with_style(Style {
-                            fill_color: Some(mocha::SURFACE0),
-                            corner_shape: Some(CornerShape::Round(Size::lpx(50.0))),
-                            stroke: Some(Stroke::new(Size::lpx(3.0), mocha::PEACH)),
-                            ..Default::default()
-                        })
+                        .with_style(style! { fill: mocha::SURFACE0, radius: 50, stroke: (3, mocha::PEACH) })
                         .with_layout_direction(Layout::Vertical)
                         .with_children(vec![
                             // Content cards in vertical layout
-                            Node::new().with_height(Size::rel(0.3)).with_style(Style {
-                                fill_color: Some(mocha::SURFACE1),
-                                corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                                stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                                ..Default::default()
-                            }),
+                            Node::new().with_height(Size::rel(0.3)).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
                             // Horizontal row of smaller cards
                             Node::new()
                                 .with_height(Size::rel(0.3))
                                 .with_gap(Size::lpx(20.0))
                                 .with_layout_direction(Layout::Horizontal)
                                 .with_children(vec![
-                                    Node::new().with_width(Size::rel(0.5)).with_style(Style {
-                                        fill_color: Some(mocha::SURFACE1),
-                                        corner_shape: Some(CornerShape::Cut(Size::lpx(30.0))),
-                                        stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                                        ..Default::default()
+                                    Node::new().with_width(Size::rel(0.5)).with_style(style! {
+                                        fill: mocha::SURFACE1,
+                                        corner: CornerShape::Cut(Size::lpx(30.0)),
+                                        stroke: (3, mocha::SURFACE2),
                                     }),
-                                    Node::new().with_width(Size::rel(0.5)).with_style(Style {
-                                        fill_color: Some(mocha::SURFACE1),
-                                        corner_shape: Some(CornerShape::Cut(Size::lpx(30.0))),
-                                        stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                                        ..Default::default()
+                                    Node::new().with_width(Size::rel(0.5)).with_style(style! {
+                                        fill: mocha::SURFACE1,
+                                        corner: CornerShape::Cut(Size::lpx(30.0)),
+                                        stroke: (3, mocha::SURFACE2),
                                     }),
                                 ]),
-                            Node::new().with_height(Size::rel(0.4)).with_style(Style {
-                                fill_color: Some(mocha::SURFACE1),
-                                corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                                stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                                ..Default::default()
-                            }),
+                            Node::new().with_height(Size::rel(0.4)).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
                         ]),
                 ]),
             // Footer - 10% height with three Fill children laid out horizontally with gap
@@ -178,31 +133,11 @@ fn create_demo_ui(_width: f32, _height: f32, _debug_options: &DebugOptions) -> N
                 .with_padding(Spacing::all(Size::lpx(20.0)))
                 .with_gap(Size::lpx(20.0))
                 .with_layout_direction(Layout::Horizontal)
-                .with_style(Style {
-                    fill_color: Some(mocha::SURFACE0),
-                    corner_shape: Some(CornerShape::Round(Size::lpx(50.0))),
-                    stroke: Some(Stroke::new(Size::lpx(3.0), mocha::BLUE)),
-                    ..Default::default()
-                })
+                .with_style(style! { fill: mocha::SURFACE0, radius: 50, stroke: (3, mocha::BLUE) })
                 .with_children(vec![
-                    Node::new().with_width(Size::Fill).with_style(Style {
-                        fill_color: Some(mocha::SURFACE1),
-                        corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                        stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                        ..Default::default()
-                    }),
-                    Node::new().with_width(Size::Fill).with_style(Style {
-                        fill_color: Some(mocha::SURFACE1),
-                        corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                        stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                        ..Default::default()
-                    }),
-                    Node::new().with_width(Size::Fill).with_style(Style {
-                        fill_color: Some(mocha::SURFACE1),
-                        corner_shape: Some(CornerShape::Round(Size::lpx(30.0))),
-                        stroke: Some(Stroke::new(Size::lpx(3.0), mocha::SURFACE2)),
-                        ..Default::default()
-                    }),
+                    Node::new().with_width(Size::Fill).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
+                    Node::new().with_width(Size::Fill).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
+                    Node::new().with_width(Size::Fill).with_style(style! { fill: mocha::SURFACE1, radius: 30, stroke: (3, mocha::SURFACE2) }),
                 ]),
         ]);
 
@@ -210,10 +145,7 @@ fn create_demo_ui(_width: f32, _height: f32, _debug_options: &DebugOptions) -> N
     let help_text = Node::new()
         .with_height(Size::lpx(30.0))
         .with_padding(Spacing::horizontal(Size::lpx(10.0)))
-        .with_style(Style {
-            fill_color: Some(mocha::SURFACE0),
-            ..Default::default()
-        })
+        .with_style(style! { fill: mocha::SURFACE0 })
         .with_content(Content::Text(
             TextContent::new(DEBUG_HELP_TEXT_ONELINE)
                 .with_font_size(Size::lpx(16.0))
@@ -0,0 +1,174 @@
+//! Virtualized list example
+//!
+//! Demonstrates a `ListView` over 100 000 rows of varying height. Only the rows
+//! in view are built each frame; row heights are estimated until measured.
+//!
+//! Controls:
+//! - Mouse wheel or the scrollbar to scroll
+//! - "Prepend" inserts rows above, without moving the rows in view
+//! - "Append" adds rows at the end
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Component, Content, CornerShape, DebugOptions, HorizontalAlign, Layout,
+    Node, Size, Spacing, Style, TextContent, UiContext, VerticalAlign,
+};
+use astra_gui_interactive::{Button, ListView};
+use astra_gui_text::Engine as TextEngine;
+use shared::{run_example, ExampleApp};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const INITIAL_ROWS: u64 = 100_000;
+
+/// Rows of the list, identified by a key that stays with them as rows are
+/// inserted around them
+#[derive(Default)]
+struct Rows {
+    keys: Vec<u64>,
+    next_key: u64,
+}
+
+impl Rows {
+    fn take_keys(&mut self, count: u64) -> Vec<u64> {
+        let keys = (self.next_key..self.next_key + count).collect();
+        self.next_key += count;
+        keys
+    }
+}
+
+struct ListViewExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    rows: Rc<RefCell<Rows>>,
+}
+
+/// A row with one to three lines of text, depending on its key
+fn row(key: u64) -> Node {
+    let lines = (key % 3 + 1) as usize;
+    let text = (0..lines)
+        .map(|line| {
+            if line == 0 {
+                format!("Row {}", key)
+            } else {
+                "Some more text that makes this row taller".to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Node::new()
+        .with_width(Size::Fill)
+        .with_padding(Spacing::symmetric(Size::lpx(12.0), Size::lpx(4.0)))
+        .with_child(
+            Node::new()
+                .with_width(Size::Fill)
+                .with_padding(Spacing::all(Size::lpx(10.0)))
+                .with_style(Style {
+                    fill_color: Some(if key.is_multiple_of(2) {
+                        mocha::SURFACE0
+                    } else {
+                        mocha::SURFACE1
+                    }),
+                    corner_shape: Some(CornerShape::Round(Size::lpx(8.0))),
+                    ..Default::default()
+                })
+                .with_content(Content::Text(
+                    TextContent::new(text)
+                        .with_font_size(Size::lpx(18.0))
+                        .with_color(mocha::TEXT)
+                        .with_h_align(HorizontalAlign::Left)
+                        .with_v_align(VerticalAlign::Center),
+                )),
+        )
+}
+
+impl ExampleApp for ListViewExample {
+    fn new() -> Self {
+        let mut rows = Rows::default();
+        rows.keys = rows.take_keys(INITIAL_ROWS);
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            rows: Rc::new(RefCell::new(rows)),
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - List View Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (800, 900)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let rows_prepend = self.rows.clone();
+        let rows_append = self.rows.clone();
+        let toolbar = Node::new()
+            .with_width(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(12.0)))
+            .with_gap(Size::lpx(8.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                Button::new("Prepend 10")
+                    .on_click(move || {
+                        let mut rows = rows_prepend.borrow_mut();
+                        let mut keys = rows.take_keys(10);
+                        keys.append(&mut rows.keys);
+                        rows.keys = keys;
+                    })
+                    .node(ctx),
+                Button::new("Append 10")
+                    .on_click(move || {
+                        let mut rows = rows_append.borrow_mut();
+                        let mut keys = rows.take_keys(10);
+                        rows.keys.append(&mut keys);
+                    })
+                    .node(ctx),
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_height(Size::Fill)
+                    .with_content(Content::Text(
+                        TextContent::new(format!("{} rows", self.rows.borrow().keys.len()))
+                            .with_font_size(Size::lpx(18.0))
+                            .with_color(mocha::SUBTEXT0)
+                            .with_h_align(HorizontalAlign::Right)
+                            .with_v_align(VerticalAlign::Center),
+                    )),
+            ]);
+
+        let rows = self.rows.borrow();
+        let list = ListView::new(rows.keys.len(), |_ctx, index| row(rows.keys[index]))
+            .key(|index| rows.keys[index])
+            .estimated_row_height(60.0)
+            .node(ctx);
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![toolbar, list])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<ListViewExample>();
+}
@@ -0,0 +1,154 @@
+//! Nine-slice example
+//!
+//! Demonstrates `Style::nine_slice`: one small frame texture skins panels of
+//! every size, with its corners kept sharp at 3x and the edges and center
+//! stretched. The last panel is tinted and the disabled one greyed out with a
+//! filter.
+//!
+//! Controls:
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Content, DebugOptions, Filter, HorizontalAlign, Layout, NineSlice,
+    NineSliceImage, Node, Size, Spacing, Style, TextContent, TextureId, UiContext, VerticalAlign,
+};
+use astra_gui_text::Engine as TextEngine;
+use astra_gui_wgpu::Renderer;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+/// Side of the generated frame texture, in texels
+const FRAME_SIZE: u32 = 16;
+/// Width of the frame's border, in texels
+const FRAME_INSET: f32 = 5.0;
+
+struct NineSliceExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    frame: Option<TextureId>,
+}
+
+/// A beveled frame: light top-left edge, dark bottom-right edge, dark center
+fn frame_texels() -> Vec<u8> {
+    let last = FRAME_SIZE - 1;
+    let mut texels = Vec::with_capacity((FRAME_SIZE * FRAME_SIZE * 4) as usize);
+    for y in 0..FRAME_SIZE {
+        for x in 0..FRAME_SIZE {
+            let edge = x.min(y).min(last - x).min(last - y);
+            let rgba = match edge {
+                0 => [17, 17, 27, 255],
+                1 if x.min(y) == 1 => [205, 214, 244, 255],
+                1 => [69, 71, 90, 255],
+                2..=3 => [137, 180, 250, 255],
+                4 => [17, 17, 27, 255],
+                _ => [49, 50, 68, 255],
+            };
+            texels.extend_from_slice(&rgba);
+        }
+    }
+    texels
+}
+
+fn label(text: &str) -> Node {
+    Node::new().with_content(Content::Text(
+        TextContent::new(text)
+            .with_font_size(Size::lpx(16.0))
+            .with_color(mocha::TEXT),
+    ))
+}
+
+impl NineSliceExample {
+    fn panel(&self, text: &str, width: f32, height: f32, tint: Color) -> Node {
+        let frame = self.frame.unwrap_or(TextureId(u64::MAX));
+        let image = NineSliceImage::new(
+            frame,
+            [FRAME_SIZE as f32; 2],
+            NineSlice::uniform(FRAME_INSET).with_scale(3.0),
+        )
+        .with_tint(tint);
+        Node::new()
+            .with_width(Size::lpx(width))
+            .with_height(Size::lpx(height))
+            .with_h_align(HorizontalAlign::Center)
+            .with_v_align(VerticalAlign::Center)
+            .with_style(Style::nine_slice(image))
+            .with_child(label(text))
+    }
+}
+
+impl ExampleApp for NineSliceExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            frame: None,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Nine-Slice Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (900, 600)
+    }
+
+    fn on_gpu_ready(
+        &mut self,
+        renderer: &mut Renderer,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        self.frame = Some(renderer.register_texture(
+            device,
+            queue,
+            [FRAME_SIZE, FRAME_SIZE],
+            &frame_texels(),
+        ));
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let white = Color::rgb(1.0, 1.0, 1.0);
+        let panels = Node::new()
+            .with_gap(Size::lpx(24.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                self.panel("Button", 140.0, 56.0, white),
+                self.panel("Wide dialog", 320.0, 200.0, white),
+                self.panel("Tinted", 120.0, 260.0, Color::rgb(1.0, 0.7, 0.6)),
+                self.panel("Disabled", 140.0, 100.0, white)
+                    .with_filter(Filter::Grayscale(1.0))
+                    .with_filter(Filter::Brightness(0.7)),
+            ]);
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(24.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style::fill(mocha::BASE))
+            .with_children(vec![
+                label("One 16x16 frame texture, nine-sliced to any size"),
+                panels,
+                label(DEBUG_HELP_TEXT_ONELINE),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<NineSliceExample>();
+}
@@ -0,0 +1,154 @@
+//! Pagination example
+//!
+//! Demonstrates `Pagination` paging through a table of 237 orders, ten per
+//! page. The long page range is shortened with ellipses around the current
+//! page.
+//!
+//! Controls:
+//! - Click a page number or the arrows to change pages
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, CornerShape, DebugOptions, HorizontalAlign,
+    Layout, Node, Size, Spacing, Style, TextContent, UiContext,
+};
+use astra_gui_interactive::Pagination;
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const ORDERS: usize = 237;
+const ROWS_PER_PAGE: usize = 10;
+const COLUMNS: [(&str, f32); 3] = [("Order", 120.0), ("Customer", 240.0), ("Total", 120.0)];
+
+const CUSTOMERS: [&str; 6] = [
+    "Ada Lovelace",
+    "Grace Hopper",
+    "Alan Turing",
+    "Edsger Dijkstra",
+    "Barbara Liskov",
+    "Donald Knuth",
+];
+
+struct PaginationExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    page: usize,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new().with_content(Content::Text(
+        TextContent::new(text)
+            .with_font_size(Size::lpx(size))
+            .with_color(color),
+    ))
+}
+
+/// A table row with one cell per column
+fn row(cells: [String; 3], color: Color, fill: Option<Color>) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_padding(Spacing::symmetric(Size::lpx(12.0), Size::lpx(8.0)))
+        .with_layout_direction(Layout::Horizontal)
+        .with_style(Style {
+            fill_color: fill,
+            corner_shape: Some(CornerShape::Round(Size::lpx(6.0))),
+            ..Default::default()
+        })
+        .with_children(
+            cells
+                .into_iter()
+                .zip(COLUMNS)
+                .map(|(cell, (_, width))| text(cell, 16.0, color).with_width(Size::lpx(width)))
+                .collect(),
+        )
+}
+
+impl ExampleApp for PaginationExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            page: 0,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Pagination Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (800, 700)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let page_count = ORDERS.div_ceil(ROWS_PER_PAGE);
+        let pagination = Pagination::new(&mut self.page, page_count).node(ctx);
+
+        let first = self.page * ROWS_PER_PAGE;
+        let last = (first + ROWS_PER_PAGE).min(ORDERS);
+        let mut table = vec![row(
+            COLUMNS.map(|(title, _)| title.to_string()),
+            mocha::SUBTEXT0,
+            None,
+        )];
+        table.extend((first..last).map(|order| {
+            row(
+                [
+                    format!("#{}", 10_000 + order),
+                    CUSTOMERS[order % CUSTOMERS.len()].to_string(),
+                    format!("${}.{:02}", 20 + order * 7 % 180, order * 13 % 100),
+                ],
+                mocha::TEXT,
+                Some(if order % 2 == 0 {
+                    mocha::SURFACE0
+                } else {
+                    mocha::MANTLE
+                }),
+            )
+        }));
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Pagination", 28.0, mocha::TEXT),
+                Node::new()
+                    .with_width(Size::lpx(520.0))
+                    .with_gap(Size::lpx(2.0))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_children(table),
+                pagination,
+                text(
+                    format!("Orders {}–{} of {}", first + 1, last, ORDERS),
+                    16.0,
+                    mocha::SUBTEXT1,
+                ),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<PaginationExample>();
+}
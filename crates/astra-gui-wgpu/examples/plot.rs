@@ -0,0 +1,134 @@
+//! Plot example
+//!
+//! Demonstrates the `Plot` component with line, bar and scatter series.
+//!
+//! Controls:
+//! - Hover a point or bar to see its value
+//! - Drag to pan, mouse wheel to zoom around the pointer
+//! - Right-click a plot to fit its data again
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, UiContext,
+};
+use astra_gui_interactive::{Plot, Series};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+struct PlotExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+}
+
+/// Deterministic noise in -1..1, so the scatter looks random but stays put
+fn noise(seed: u32) -> f32 {
+    let hash = seed.wrapping_mul(2_654_435_761).rotate_left(13) ^ 0x5bd1_e995;
+    (hash % 2000) as f32 / 1000.0 - 1.0
+}
+
+impl ExampleApp for PlotExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Plot Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (1000, 800)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let waves = Plot::new()
+            .series(Series::line(
+                "sin(x)",
+                (0..=200).map(|i| {
+                    let x = i as f32 * 0.05;
+                    [x, x.sin()]
+                }),
+            ))
+            .series(Series::line(
+                "cos(x) / 2",
+                (0..=200).map(|i| {
+                    let x = i as f32 * 0.05;
+                    [x, x.cos() * 0.5]
+                }),
+            ))
+            .series(Series::scatter(
+                "samples",
+                (0..40).map(|i| {
+                    let x = i as f32 * 0.25;
+                    [x, x.sin() + noise(i) * 0.2]
+                }),
+            ))
+            .node(ctx);
+
+        let months = [3.2, 4.1, 5.6, 4.8, 6.3, 7.9, 7.1, 8.4, 6.6, 5.2, 4.0, 3.5];
+        let sales = Plot::new()
+            .series(Series::bar(
+                "2024",
+                months
+                    .iter()
+                    .enumerate()
+                    .map(|(month, value)| [month as f32 + 1.0, *value]),
+            ))
+            .series(Series::bar(
+                "2025",
+                months
+                    .iter()
+                    .enumerate()
+                    .map(|(month, value)| [month as f32 + 1.0, value * 1.2 - 1.0]),
+            ))
+            .node(ctx);
+
+        let text = |text: &str, size: f32, color| {
+            Node::new()
+                .with_width(Size::Fill)
+                .with_content(Content::Text(
+                    TextContent::new(text)
+                        .with_font_size(Size::lpx(size))
+                        .with_color(color),
+                ))
+        };
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Plot", 28.0, mocha::TEXT),
+                waves,
+                sales,
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<PlotExample>();
+}
@@ -0,0 +1,142 @@
+//! Range slider example
+//!
+//! Demonstrates `RangeSlider` as a continuous price filter over a small
+//! product list, and as a stepped opening-hours picker with a minimum gap
+//! between the handles.
+//!
+//! Controls:
+//! - Drag a handle, or click the track to move the nearest handle
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, UiContext,
+};
+use astra_gui_interactive::{RangeSlider, SliderStyle};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const PRODUCTS: [(&str, f32); 8] = [
+    ("Desk lamp", 35.0),
+    ("Office chair", 240.0),
+    ("Monitor arm", 85.0),
+    ("Keyboard", 120.0),
+    ("Standing desk", 480.0),
+    ("Headphones", 199.0),
+    ("Webcam", 65.0),
+    ("Mouse", 45.0),
+];
+
+struct RangeSliderExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    min_price: f32,
+    max_price: f32,
+    min_hour: f32,
+    max_hour: f32,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+impl ExampleApp for RangeSliderExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            min_price: 50.0,
+            max_price: 250.0,
+            min_hour: 9.0,
+            max_hour: 17.0,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Range Slider Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (700, 700)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let style = SliderStyle {
+            track_width: 400.0,
+            ..SliderStyle::from_theme(ctx.theme())
+        };
+        let price = RangeSlider::new(&mut self.min_price, &mut self.max_price, 0.0..=500.0)
+            .with_style(style.clone())
+            .node(ctx);
+        let hours = RangeSlider::new(&mut self.min_hour, &mut self.max_hour, 0.0..=24.0)
+            .step(1.0)
+            .min_gap(2.0)
+            .with_style(style)
+            .node(ctx);
+
+        let products = PRODUCTS
+            .iter()
+            .filter(|(_, price)| (self.min_price..=self.max_price).contains(price))
+            .map(|(name, price)| text(format!("{}  ${:.0}", name, price), 16.0, mocha::TEXT))
+            .collect::<Vec<_>>();
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Range Slider", 28.0, mocha::TEXT),
+                text(
+                    format!("Price: ${:.0} – ${:.0}", self.min_price, self.max_price),
+                    18.0,
+                    mocha::SUBTEXT1,
+                ),
+                price,
+                Node::new()
+                    .with_width(Size::Fill)
+                    .with_gap(Size::lpx(4.0))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_children(products),
+                text(
+                    format!(
+                        "Opening hours: {:02.0}:00 – {:02.0}:00 (at least 2 hours)",
+                        self.min_hour, self.max_hour
+                    ),
+                    18.0,
+                    mocha::SUBTEXT1,
+                ),
+                hours,
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<RangeSliderExample>();
+}
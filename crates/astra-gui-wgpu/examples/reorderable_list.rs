@@ -0,0 +1,156 @@
+//! Reorderable list example
+//!
+//! Demonstrates a `ReorderableList` of tasks with rows of different heights.
+//! While a row is dragged, a ghost of it follows the pointer and the other
+//! rows slide apart around the drop position.
+//!
+//! Controls:
+//! - Drag a row to move it
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Component, Content, CornerShape, DebugOptions, Layout, Node, Size, Spacing,
+    Style, TextContent, UiContext,
+};
+use astra_gui_interactive::ReorderableList;
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+struct ReorderableListExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    tasks: Vec<(&'static str, &'static str)>,
+    last_move: Option<(usize, usize)>,
+}
+
+fn text(text: &str, size: f32, color: astra_gui::Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+/// A task row with a grip, a title and an optional note below it
+fn task_row(title: &str, note: &str) -> Node {
+    let mut details = Node::new()
+        .with_width(Size::Fill)
+        .with_gap(Size::lpx(4.0))
+        .with_layout_direction(Layout::Vertical)
+        .with_child(text(title, 18.0, mocha::TEXT));
+    if !note.is_empty() {
+        details = details.with_child(text(note, 14.0, mocha::SUBTEXT0));
+    }
+
+    Node::new()
+        .with_width(Size::Fill)
+        .with_padding(Spacing::all(Size::lpx(12.0)))
+        .with_gap(Size::lpx(12.0))
+        .with_layout_direction(Layout::Horizontal)
+        .with_style(Style {
+            fill_color: Some(mocha::SURFACE0),
+            corner_shape: Some(CornerShape::Round(Size::lpx(6.0))),
+            ..Default::default()
+        })
+        .with_children(vec![
+            Node::new()
+                .with_width(Size::lpx(16.0))
+                .with_content(Content::Text(
+                    TextContent::new("⠿")
+                        .with_font_size(Size::lpx(18.0))
+                        .with_color(mocha::OVERLAY1),
+                )),
+            details,
+        ])
+}
+
+impl ExampleApp for ReorderableListExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            tasks: vec![
+                ("Write the release notes", ""),
+                (
+                    "Review pull requests",
+                    "Two waiting on the layout engine, one on text shaping",
+                ),
+                ("Update the screenshots", ""),
+                ("Tag the release", "After CI is green on main"),
+                ("Announce it", ""),
+                (
+                    "Plan the next milestone",
+                    "Collect the open feature requests first",
+                ),
+            ],
+            last_move: None,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Reorderable List Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (700, 700)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        // Rows are built from a snapshot, so the callback can move the tasks
+        let tasks = self.tasks.clone();
+        let mut last_move = self.last_move;
+        let list = ReorderableList::new(tasks.len(), |_ctx, index| {
+            task_row(tasks[index].0, tasks[index].1)
+        })
+        .width(Size::lpx(480.0))
+        .on_reorder(|from, to| {
+            let task = self.tasks.remove(from);
+            self.tasks.insert(to, task);
+            last_move = Some((from, to));
+        })
+        .node(ctx);
+        self.last_move = last_move;
+
+        let status = match self.last_move {
+            Some((from, to)) => format!("Moved task {} to position {}", from + 1, to + 1),
+            None => "Drag a task to change its position".to_string(),
+        };
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Reorderable List", 28.0, mocha::TEXT),
+                text(&status, 16.0, mocha::SUBTEXT1),
+                list,
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<ReorderableListExample>();
+}
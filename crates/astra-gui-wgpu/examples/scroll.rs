@@ -5,6 +5,8 @@
 //! Controls:
 //! - Debug controls (M/P/B/C/R/G/O/T/D/S)
 //! - Mouse wheel to scroll
+//! - Flick a trackpad over the vertical list to scroll it with momentum
+//! - Drag the vertical list's scrollbar, or click its track to page
 //! - ESC: quit
 //!
 //! Note: Debug controls are shared across examples via `shared::debug_controls`.
@@ -14,9 +16,11 @@
 mod shared;
 
 use astra_gui::{
-    catppuccin::mocha, Content, CornerShape, DebugOptions, HorizontalAlign, Layout, Node, NodeId,
-    Overflow, Size, Spacing, Style, TextContent, UiContext, VerticalAlign,
+    catppuccin::mocha, Component, Content, CornerShape, DebugOptions, HorizontalAlign, Layout,
+    Node, NodeId, Overflow, ScrollMomentum, Size, Spacing, Style, TextContent, UiContext,
+    VerticalAlign,
 };
+use astra_gui_interactive::ScrollArea;
 use astra_gui_text::Engine as TextEngine;
 use shared::{run_example, ExampleApp};
 
@@ -54,7 +58,7 @@ impl ExampleApp for ScrollExample {
         (1200, 1000)
     }
 
-    fn build_ui(&mut self, _ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
         // Create a scrollable container with many items - STRESS TEST with nested children
         let mut items = Vec::new();
         for (i, &height) in self.item_heights.iter().enumerate() {
@@ -123,21 +127,25 @@ impl ExampleApp for ScrollExample {
             );
         }
 
-        // Scrollable container - scroll state is now managed automatically
+        // Scrollable container - scroll state is now managed automatically, and the
+        // scroll area overlays a scrollbar that fades out while idle
         let scroll_container = Node::new()
             .with_id(NodeId::new("scroll_container"))
-            .with_width(Size::lpx(400.0))
-            .with_height(Size::Fill)
             .with_padding(Spacing::all(Size::lpx(10.0)))
             .with_gap(Size::lpx(10.0))
             .with_layout_direction(Layout::Vertical)
             .with_overflow(Overflow::Scroll)
+            .with_scroll_momentum(ScrollMomentum::default().with_overscroll(80.0))
             .with_style(Style {
                 fill_color: Some(mocha::MANTLE),
                 corner_shape: Some(CornerShape::Round(Size::lpx(12.0))),
                 ..Default::default()
             })
             .with_children(items);
+        let scroll_container = ScrollArea::new(scroll_container)
+            .width(Size::lpx(400.0))
+            .horizontal(false)
+            .node(ctx);
 
         // Create horizontal scrollable container - STRESS TEST with nested children
         let mut horizontal_items = Vec::new();
@@ -0,0 +1,180 @@
+//! Search input example
+//!
+//! Demonstrates `SearchInput` searching a list of countries. The search runs
+//! in the debounced filter callback, so it only runs once typing pauses; the
+//! number of searches is shown to make the debounce visible.
+//!
+//! Controls:
+//! - Type to search, Up/Down to highlight a suggestion
+//! - Enter or click to pick a suggestion, Escape to close the suggestions
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, UiContext,
+};
+use astra_gui_interactive::SearchInput;
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const COUNTRIES: [&str; 40] = [
+    "Argentina",
+    "Australia",
+    "Austria",
+    "Belgium",
+    "Brazil",
+    "Canada",
+    "Chile",
+    "China",
+    "Colombia",
+    "Croatia",
+    "Czechia",
+    "Denmark",
+    "Egypt",
+    "Estonia",
+    "Finland",
+    "France",
+    "Germany",
+    "Greece",
+    "Iceland",
+    "India",
+    "Indonesia",
+    "Ireland",
+    "Italy",
+    "Japan",
+    "Kenya",
+    "Mexico",
+    "Netherlands",
+    "New Zealand",
+    "Nigeria",
+    "Norway",
+    "Peru",
+    "Poland",
+    "Portugal",
+    "South Africa",
+    "South Korea",
+    "Spain",
+    "Sweden",
+    "Switzerland",
+    "United Kingdom",
+    "United States",
+];
+
+/// Most suggestions to show at once
+const MAX_MATCHES: usize = 8;
+
+struct SearchInputExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    query: String,
+    matches: Vec<String>,
+    searches: usize,
+    picked: Option<String>,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+/// Countries containing the query, ignoring case
+fn search(query: &str) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    COUNTRIES
+        .iter()
+        .filter(|country| country.to_lowercase().contains(&query))
+        .take(MAX_MATCHES)
+        .map(|country| country.to_string())
+        .collect()
+}
+
+impl ExampleApp for SearchInputExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            query: String::new(),
+            matches: Vec::new(),
+            searches: 0,
+            picked: None,
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Search Input Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (700, 600)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let mut query = None;
+        let mut picked = None;
+        let search_input = SearchInput::new(&mut self.query, &self.matches)
+            .placeholder("Search countries...")
+            .on_filter(|text| query = Some(text.to_string()))
+            .on_select(|index| picked = Some(index))
+            .node(ctx);
+        if let Some(index) = picked {
+            self.picked = Some(self.matches[index].clone());
+        }
+        if let Some(query) = query {
+            self.matches = search(&query);
+            self.searches += 1;
+        }
+
+        let picked = match &self.picked {
+            Some(country) => format!("Picked: {}", country),
+            None => "Nothing picked yet".to_string(),
+        };
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Search Input", 28.0, mocha::TEXT),
+                search_input,
+                text(picked, 18.0, mocha::SUBTEXT1),
+                text(
+                    format!("Searches run: {}", self.searches),
+                    16.0,
+                    mocha::SUBTEXT0,
+                ),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<SearchInputExample>();
+}
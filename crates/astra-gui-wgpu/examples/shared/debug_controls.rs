@@ -12,6 +12,8 @@
 //! - G: gaps (purple overlay)
 //! - O: transform origins (orange crosshair)
 //! - T: text line bounds (cyan outline)
+//! - H: interaction heatmap (hover/click density)
+//! - V: overdraw view (per-pixel draw count)
 //! - D: toggle all debug visualizations
 //!
 //! Usage (in an example):
@@ -36,8 +38,11 @@ pub const DEBUG_HELP_TEXT: &str = "Debug controls:
   G - Toggle gaps (purple overlay)
   O - Toggle transform origins (orange crosshair)
   T - Toggle text line bounds (cyan outline)
+  H - Toggle interaction heatmap
+  V - Toggle overdraw view
   D - Toggle all debug visualizations
   F - Toggle frame profiling
+  X - Export next frame as a Chrome trace
   ESC - Exit";
 
 /// Single-line help text suitable for an in-app HUD/footer label.
@@ -46,7 +51,7 @@ pub const DEBUG_HELP_TEXT: &str = "Debug controls:
 /// wants to show an always-visible hint.
 #[allow(dead_code)]
 pub const DEBUG_HELP_TEXT_ONELINE: &str =
-    "M:Margins | P:Padding | B:Borders | C:Content | R:ClipRects | G:Gaps | O:Origins | T:Text | D:All | F:Profiling | ESC:Exit";
+    "M:Margins | P:Padding | B:Borders | C:Content | R:ClipRects | G:Gaps | O:Origins | T:Text | H:Heatmap | V:Overdraw | D:All | F:Profiling | X:Trace | ESC:Exit";
 
 /// Handles shared debug keybinds for examples.
 ///
@@ -110,6 +115,16 @@ pub fn handle_debug_keybinds(event: &WindowEvent, debug_options: &mut DebugOptio
             println!("Text bounds: {}", debug_options.show_text_bounds);
             true
         }
+        winit::keyboard::KeyCode::KeyH => {
+            debug_options.show_heatmap = !debug_options.show_heatmap;
+            println!("Heatmap: {}", debug_options.show_heatmap);
+            true
+        }
+        winit::keyboard::KeyCode::KeyV => {
+            debug_options.show_overdraw = !debug_options.show_overdraw;
+            println!("Overdraw: {}", debug_options.show_overdraw);
+            true
+        }
         winit::keyboard::KeyCode::KeyD => {
             if debug_options.is_enabled() {
                 *debug_options = DebugOptions::none();
@@ -1,5 +1,6 @@
 use astra_gui::{DebugOptions, Node, UiContext};
 use astra_gui_text::Engine as TextEngine;
+use astra_gui_wgpu::Renderer;
 use winit::window::Window;
 
 /// Core trait that all examples must implement.
@@ -50,6 +51,16 @@ pub trait ExampleApp: Sized {
     /// Useful for examples that need to detect display PPI
     fn on_window_created(&mut self, _window: &Window) {}
 
+    /// Optional: Called once the renderer exists
+    /// Useful for examples that register textures
+    fn on_gpu_ready(
+        &mut self,
+        _renderer: &mut Renderer,
+        _device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+    ) {
+    }
+
     /// Optional: Custom ESC key handling
     /// Return true to prevent default exit behavior
     /// Useful for examples with focus management
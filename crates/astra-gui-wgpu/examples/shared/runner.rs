@@ -1,8 +1,8 @@
 use super::debug_controls::{handle_debug_keybinds, DEBUG_HELP_TEXT};
 use super::example_app::ExampleApp;
 use super::gpu_state::GpuState;
-use astra_gui::{FullOutput, Rect, UiContext};
-use astra_gui_wgpu::WinitInputExt;
+use astra_gui::{ContentMeasurer, CursorIcon, FrameTrace, FullOutput, Rect, UiContext};
+use astra_gui_wgpu::{apply_platform_output, WinitInputExt};
 use std::sync::Arc;
 use std::time::Instant;
 use winit::{
@@ -47,6 +47,10 @@ pub struct AppRunner<T: ExampleApp> {
     ctx: UiContext,
     last_frame_time: Instant,
     frame_stats: FrameStats,
+    /// Export the next frame as a Chrome trace (requested with X)
+    export_trace: bool,
+    /// Cursor icon currently applied to the window
+    cursor_icon: CursorIcon,
     #[cfg(feature = "profiling")]
     enable_profiling: bool,
 }
@@ -59,6 +63,10 @@ impl<T: ExampleApp> AppRunner<T> {
         // same default fonts as layout/rendering so measurements agree.
         let mut ctx = UiContext::new();
         ctx.set_measurer(astra_gui_text::Engine::new_default());
+        #[cfg(feature = "clipboard")]
+        if let Some(clipboard) = astra_gui_wgpu::SystemClipboard::new() {
+            ctx.set_clipboard(clipboard);
+        }
 
         Self {
             window: None,
@@ -67,6 +75,8 @@ impl<T: ExampleApp> AppRunner<T> {
             ctx,
             last_frame_time: Instant::now(),
             frame_stats: FrameStats::default(),
+            export_trace: false,
+            cursor_icon: CursorIcon::Default,
             #[cfg(feature = "profiling")]
             enable_profiling: std::env::var("PROFILE").is_ok(),
         }
@@ -121,21 +131,53 @@ impl<T: ExampleApp> AppRunner<T> {
         }
         let layout_time = layout_start.elapsed();
 
+        // Record interaction heatmap samples while the overlay is shown
+        let debug_options = self.app.debug_options_mut().copied();
+        self.ctx
+            .set_heatmap_recording(debug_options.is_some_and(|o| o.show_heatmap));
+
         // End frame - dispatches events and updates transitions
         let event_start = Instant::now();
         self.ctx.end_frame(&mut ui);
         let event_time = event_start.elapsed();
 
+        // Snapshot the laid-out tree before output generation consumes it
+        let trace = self.export_trace.then(|| FrameTrace::capture(&ui));
+
         // Generate output
         let output_start = Instant::now();
-        let debug_options = self.app.debug_options_mut().copied();
-        let output = FullOutput::from_laid_out_node(
+        let mut output = FullOutput::from_laid_out_node(
             ui,
             (size.width as f32, size.height as f32),
             debug_options,
         );
+        output.add_heatmap_overlay(self.ctx.heatmap(), (size.width as f32, size.height as f32));
+        if let Some(mut ghost) = self.ctx.drag_ghost_layer() {
+            if zoom != 1.0 {
+                ghost = ghost.with_zoom(zoom);
+            }
+            output.add_layer(FullOutput::from_node_with_debug_measurer_and_scale_factor(
+                ghost,
+                (size.width as f32, size.height as f32),
+                None,
+                self.app
+                    .text_engine()
+                    .map(|engine| engine as &mut dyn ContentMeasurer),
+                1.0,
+            ));
+        }
         let output_time = output_start.elapsed();
 
+        // Show the cursor requested by the hovered (or dragged) node and
+        // carry out window and URL requests
+        output.platform = self.ctx.take_platform_output();
+        if output.platform.cursor_icon != self.cursor_icon || !output.platform.requests.is_empty() {
+            if let Some(window) = &self.window {
+                apply_platform_output(window, &output.platform);
+            }
+            self.cursor_icon = output.platform.cursor_icon;
+        }
+
         // Render
         let render_start = Instant::now();
         let Some(gpu_state) = &mut self.gpu_state else {
@@ -157,6 +199,20 @@ impl<T: ExampleApp> AppRunner<T> {
         }
         let render_time = render_start.elapsed();
 
+        if let Some(trace) = trace {
+            self.export_trace = false;
+            self.write_trace(
+                trace,
+                [
+                    build_time,
+                    layout_time,
+                    event_time,
+                    output_time,
+                    render_time,
+                ],
+            );
+        }
+
         // Clear input state for next frame
         self.ctx.input_mut().begin_frame();
 
@@ -216,6 +272,36 @@ impl<T: ExampleApp> AppRunner<T> {
     }
 }
 
+impl<T: ExampleApp> AppRunner<T> {
+    /// Write a captured frame to `ASTRA_TRACE_PATH` (default `astra_frame_trace.json`)
+    fn write_trace(&mut self, mut trace: FrameTrace, phase_times: [std::time::Duration; 5]) {
+        for (name, time) in ["build", "layout", "events", "output", "render"]
+            .into_iter()
+            .zip(phase_times)
+        {
+            trace.add_phase(name, time.as_secs_f32() * 1000.0);
+        }
+        if let Some(stats) = self
+            .app
+            .text_engine()
+            .and_then(|engine| engine.cache_stats())
+        {
+            trace.add_cache_stats("text measurement", stats);
+        }
+
+        let path = std::env::var("ASTRA_TRACE_PATH")
+            .unwrap_or_else(|_| "astra_frame_trace.json".to_string());
+        match trace.write_chrome_trace(&path) {
+            Ok(()) => println!(
+                "Frame trace ({} nodes) written to {}",
+                trace.node_count(),
+                path
+            ),
+            Err(e) => eprintln!("Failed to write frame trace to {}: {}", path, e),
+        }
+    }
+}
+
 impl<T: ExampleApp> ApplicationHandler for AppRunner<T> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_some() {
@@ -233,7 +319,10 @@ impl<T: ExampleApp> ApplicationHandler for AppRunner<T> {
         self.app.on_window_created(&window);
 
         self.window = Some(window.clone());
-        self.gpu_state = Some(pollster::block_on(GpuState::new(window)));
+        let mut gpu_state = pollster::block_on(GpuState::new(window));
+        self.app
+            .on_gpu_ready(&mut gpu_state.renderer, &gpu_state.device, &gpu_state.queue);
+        self.gpu_state = Some(gpu_state);
     }
 
     fn window_event(
@@ -292,6 +381,18 @@ impl<T: ExampleApp> ApplicationHandler for AppRunner<T> {
                 }
             }
 
+            WindowEvent::KeyboardInput {
+                event: ref key_event,
+                ..
+            } if matches!(
+                key_event.physical_key,
+                winit::keyboard::PhysicalKey::Code(winit::keyboard::KeyCode::KeyX)
+            ) && key_event.state == ElementState::Pressed =>
+            {
+                // Export the next frame as a Chrome trace
+                self.export_trace = true;
+            }
+
             WindowEvent::Resized(physical_size) => {
                 if let Some(gpu_state) = &mut self.gpu_state {
                     gpu_state.resize(physical_size);
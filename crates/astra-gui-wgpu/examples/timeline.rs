@@ -0,0 +1,165 @@
+//! Timeline example
+//!
+//! Demonstrates `Timeline` with three keyframe tracks, a playhead that plays
+//! back in a loop and buttons that add keyframes at the playhead or delete
+//! the selected ones. Keyframes snap to 1/30 s frames.
+//!
+//! Controls:
+//! - Click or drag the ruler to move the playhead
+//! - Click a keyframe to select it (Shift adds), drag empty space to box-select
+//! - Drag a selected keyframe to move the whole selection
+//! - Mouse wheel zooms the time axis, Shift+wheel pans it
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, UiContext,
+};
+use astra_gui_interactive::{Chip, Timeline, TimelineTrack};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const DURATION: f32 = 8.0;
+const FRAME: f32 = 1.0 / 30.0;
+
+struct TimelineExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    playhead: f32,
+    playing: bool,
+    last_time: f64,
+    tracks: [(&'static str, Vec<f32>); 3],
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+impl ExampleApp for TimelineExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            playhead: 0.0,
+            playing: false,
+            last_time: 0.0,
+            tracks: [
+                ("Position", vec![0.0, 1.5, 3.0, 6.0]),
+                ("Rotation", vec![0.5, 2.5, 4.5]),
+                ("Opacity", vec![0.0, 7.0]),
+            ],
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Timeline Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (1000, 520)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let now = ctx.time();
+        if self.playing {
+            self.playhead = (self.playhead + (now - self.last_time) as f32) % DURATION;
+        }
+        self.last_time = now;
+
+        let (mut play, mut add, mut delete) = (false, false, false);
+        let controls = Node::new()
+            .with_width(Size::Fill)
+            .with_gap(Size::lpx(12.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                Chip::new(if self.playing { "Pause" } else { "Play" })
+                    .with_id("play")
+                    .on_click(|| play = true)
+                    .node(ctx),
+                Chip::new("Add position key")
+                    .with_id("add")
+                    .on_click(|| add = true)
+                    .node(ctx),
+                Chip::new("Delete selected")
+                    .with_id("delete")
+                    .on_click(|| delete = true)
+                    .node(ctx),
+            ]);
+        if play {
+            self.playing = !self.playing;
+        }
+        if add {
+            let time = (self.playhead / FRAME).round() * FRAME;
+            self.tracks[0].1.push(time);
+        }
+        if delete {
+            let state = ctx.memory().timeline("timeline");
+            let mut selected = std::mem::take(&mut state.selected);
+            // Remove from the back so the remaining indices stay valid
+            selected.sort_unstable_by(|a, b| b.cmp(a));
+            for (track, key) in selected {
+                self.tracks[track].1.remove(key);
+            }
+        }
+
+        let [(position_name, position), (rotation_name, rotation), (opacity_name, opacity)] =
+            &mut self.tracks;
+        let timeline = Timeline::new(&mut self.playhead, DURATION)
+            .track(TimelineTrack::new(*position_name, position))
+            .track(TimelineTrack::new(*rotation_name, rotation).color(mocha::PEACH))
+            .track(TimelineTrack::new(*opacity_name, opacity).color(mocha::GREEN))
+            .snap(FRAME)
+            .with_id("timeline")
+            .node(ctx);
+
+        let selected = ctx.memory().timeline("timeline").selected.len();
+        let status = format!(
+            "Playhead {:.2} s (frame {}), {} keyframes selected",
+            self.playhead,
+            (self.playhead / FRAME).round() as u32,
+            selected
+        );
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(vec![
+                text("Timeline", 28.0, mocha::TEXT),
+                controls,
+                timeline,
+                text(status, 14.0, mocha::SUBTEXT0),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ])
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<TimelineExample>();
+}
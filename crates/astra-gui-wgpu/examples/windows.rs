@@ -0,0 +1,181 @@
+//! Floating windows example
+//!
+//! Demonstrates `Window` panels over a workspace: drag them by the title bar,
+//! resize them from any edge or corner, collapse them to the title bar and
+//! close them. Pressing anywhere in a window raises it above the others.
+//!
+//! Controls:
+//! - Drag a title bar to move its window, an edge or corner to resize it
+//! - Click the triangle to collapse a window, × to close it
+//! - Click "Reopen windows" to bring closed windows back
+//! - Debug controls (M/P/B/C/R/G/O/T/D/S)
+//! - ESC: quit
+
+#![allow(unused_imports, unused_variables, dead_code)]
+
+mod shared;
+
+use astra_gui::{
+    catppuccin::mocha, Color, Component, Content, DebugOptions, Layout, Node, Size, Spacing, Style,
+    TextContent, UiContext,
+};
+use astra_gui_interactive::{checkbox, checkbox_clicked, Chip, TextArea, Window};
+use astra_gui_text::Engine as TextEngine;
+use shared::debug_controls::DEBUG_HELP_TEXT_ONELINE;
+use shared::{run_example, ExampleApp};
+
+const LAYERS: [&str; 4] = ["Background", "Terrain", "Props", "Lighting"];
+
+struct WindowsExample {
+    text_engine: TextEngine,
+    debug_options: DebugOptions,
+    layers_open: bool,
+    notes_open: bool,
+    about_open: bool,
+    visible_layers: [bool; 4],
+    notes: String,
+}
+
+fn text(text: impl Into<String>, size: f32, color: Color) -> Node {
+    Node::new()
+        .with_width(Size::Fill)
+        .with_content(Content::Text(
+            TextContent::new(text)
+                .with_font_size(Size::lpx(size))
+                .with_color(color),
+        ))
+}
+
+impl ExampleApp for WindowsExample {
+    fn new() -> Self {
+        Self {
+            text_engine: TextEngine::new_default(),
+            debug_options: DebugOptions::none(),
+            layers_open: true,
+            notes_open: true,
+            about_open: true,
+            visible_layers: [true; 4],
+            notes: "Windows remember where they were, even after closing.".to_string(),
+        }
+    }
+
+    fn window_title() -> &'static str {
+        "Astra GUI - Floating Windows Example"
+    }
+
+    fn window_size() -> (u32, u32) {
+        (1000, 700)
+    }
+
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        let mut reopen = false;
+        let workspace = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_padding(Spacing::all(Size::lpx(24.0)))
+            .with_gap(Size::lpx(16.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_children(vec![
+                text("Floating Windows", 28.0, mocha::TEXT),
+                Chip::new("Reopen windows")
+                    .with_id("reopen")
+                    .on_click(|| reopen = true)
+                    .node(ctx),
+                Node::new().with_height(Size::Fill),
+                text(DEBUG_HELP_TEXT_ONELINE, 14.0, mocha::OVERLAY1),
+            ]);
+        if reopen {
+            self.layers_open = true;
+            self.notes_open = true;
+            self.about_open = true;
+        }
+
+        let mut children = vec![workspace];
+
+        if self.layers_open {
+            let mut rows = Vec::new();
+            for (index, layer) in LAYERS.iter().enumerate() {
+                let id = format!("layer_{}", index);
+                if checkbox_clicked(ctx, &id) {
+                    self.visible_layers[index] = !self.visible_layers[index];
+                }
+                rows.push(checkbox(ctx, &id, self.visible_layers[index], *layer));
+            }
+            let content = Node::new()
+                .with_width(Size::Fill)
+                .with_gap(Size::lpx(10.0))
+                .with_layout_direction(Layout::Vertical)
+                .with_children(rows);
+            let mut open = true;
+            children.push(
+                Window::new("Layers", content)
+                    .with_id("layers")
+                    .default_position(60.0, 120.0)
+                    .default_size(240.0, 220.0)
+                    .on_close(|| open = false)
+                    .node(ctx),
+            );
+            self.layers_open = open;
+        }
+
+        if self.notes_open {
+            let content = TextArea::new(&mut self.notes)
+                .with_id("notes_text")
+                .node(ctx);
+            let mut open = true;
+            children.push(
+                Window::new("Notes", content)
+                    .with_id("notes")
+                    .default_position(340.0, 160.0)
+                    .default_size(360.0, 260.0)
+                    .min_size(220.0, 140.0)
+                    .on_close(|| open = false)
+                    .node(ctx),
+            );
+            self.notes_open = open;
+        }
+
+        if self.about_open {
+            let content = text(
+                "This window has a fixed size and can't be collapsed. Drag the others \
+                 over it and click it to bring it back to the front.",
+                15.0,
+                mocha::SUBTEXT1,
+            );
+            let mut open = true;
+            children.push(
+                Window::new("About", content)
+                    .with_id("about")
+                    .default_position(560.0, 80.0)
+                    .default_size(300.0, 150.0)
+                    .resizable(false)
+                    .collapsible(false)
+                    .on_close(|| open = false)
+                    .node(ctx),
+            );
+            self.about_open = open;
+        }
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Stack)
+            .with_style(Style {
+                fill_color: Some(mocha::BASE),
+                ..Default::default()
+            })
+            .with_children(children)
+    }
+
+    fn text_engine(&mut self) -> Option<&mut TextEngine> {
+        Some(&mut self.text_engine)
+    }
+
+    fn debug_options_mut(&mut self) -> Option<&mut DebugOptions> {
+        Some(&mut self.debug_options)
+    }
+}
+
+fn main() {
+    run_example::<WindowsExample>();
+}
@@ -2,6 +2,8 @@
 //!
 //! Controls:
 //! - Mouse wheel: Browser-style zoom (scales everything with layout reflow)
+//! - Pinch (touchscreen or trackpad): Zoom
+//! - Two-finger drag (touchscreen or trackpad): Pan camera
 //! - Arrow keys: Pan camera
 //! - R: Reset zoom and pan
 //! - M/P/B/C/G/D: Debug visualizations (Margins/Padding/Borders/Content/Gaps/All)
@@ -13,8 +15,8 @@
 mod shared;
 
 use astra_gui::{
-    catppuccin::mocha, CornerShape, DebugOptions, FullOutput, HorizontalAlign, Layout, Node, Rect,
-    Size, Spacing, Stroke, TextContent, UiContext, VerticalAlign, ZIndex,
+    catppuccin::mocha, CornerShape, DebugOptions, FullOutput, HorizontalAlign, Layout, Node,
+    NodeId, Rect, Size, Spacing, Stroke, TextContent, UiContext, VerticalAlign, ZIndex,
 };
 use astra_gui_text::Engine as TextEngine;
 use astra_gui_wgpu::WinitInputExt;
@@ -53,7 +55,16 @@ impl Zoom {
         (800, 700)
     }
 
-    fn build_ui(&mut self, _ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+    fn build_ui(&mut self, ctx: &mut UiContext, _width: f32, _height: f32) -> Node {
+        // Pinches and two-finger drags anywhere over the view zoom and pan it
+        if let Some(zoom) = ctx.pinch_zoom("view") {
+            self.zoom_level = (self.zoom_level * zoom).clamp(0.25, 10.0);
+        }
+        if let Some((dx, dy)) = ctx.pan_delta("view") {
+            self.pan_offset.0 += dx;
+            self.pan_offset.1 += dy;
+        }
+
         // Debug info panel (not affected by content zoom, positioned absolutely)
         let debug_panel = Node::new()
             .with_style(astra_gui::Style {
@@ -66,7 +77,7 @@ impl Zoom {
             .with_z_index(ZIndex::OVERLAY) // Ensure panel renders on top of grid
             .with_content(astra_gui::Content::Text(
                 TextContent::new(format!(
-                    "Zoom: {:.0}%\nPan: ({:.0}, {:.0})\n\nWheel: Zoom\nArrows: Pan\nR: Reset",
+                    "Zoom: {:.0}%\nPan: ({:.0}, {:.0})\n\nWheel or pinch: Zoom\nArrows or two fingers: Pan\nR: Reset",
                     self.zoom_level * 100.0,
                     self.pan_offset.0,
                     self.pan_offset.1
@@ -137,6 +148,7 @@ impl Zoom {
 
         // Root: Stack layout with debug panel on top
         Node::new()
+            .with_id(NodeId::new("view"))
             .with_height(Size::Fill)
             .with_width(Size::Fill)
             .with_layout_direction(Layout::Stack)
@@ -367,6 +379,8 @@ fn main() {
     println!("\nZoom Example");
     println!("Controls:");
     println!("  Mouse Wheel - Browser-style zoom");
+    println!("  Pinch       - Zoom (touchscreen or trackpad)");
+    println!("  Two Fingers - Pan camera (touchscreen or trackpad)");
     println!("  Arrow Keys  - Pan camera");
     println!("  R           - Reset zoom and pan");
     println!("  {}", DEBUG_HELP_TEXT);
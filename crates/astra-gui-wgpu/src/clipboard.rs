@@ -0,0 +1,86 @@
+//! System clipboard integration
+//!
+//! Connects the `UiContext` clipboard to the operating system's, so text
+//! copied in the app can be pasted elsewhere and the other way around.
+
+use astra_gui::{Clipboard, UiContext};
+use winit::event::{ElementState, WindowEvent};
+use winit::keyboard::Key as WinitKey;
+
+/// The system clipboard, backed by arboard
+///
+/// Install it once and every text widget copies to and pastes from the
+/// system clipboard:
+///
+/// ```ignore
+/// if let Some(clipboard) = SystemClipboard::new() {
+///     ctx.set_clipboard(clipboard);
+/// }
+/// ```
+///
+/// The context only reads it when a paste shortcut (Ctrl+V, or Cmd+V on
+/// macOS) is pressed, right before widgets handle it.
+pub struct SystemClipboard {
+    clipboard: arboard::Clipboard,
+    /// Whether Ctrl or Cmd is held, tracked from modifier events
+    command_held: bool,
+}
+
+impl SystemClipboard {
+    /// Connect to the system clipboard, or `None` where it is unavailable
+    /// (e.g. no display server)
+    pub fn new() -> Option<Self> {
+        arboard::Clipboard::new().ok().map(|clipboard| Self {
+            clipboard,
+            command_held: false,
+        })
+    }
+
+    /// Load the system clipboard into the context when a paste shortcut is
+    /// pressed
+    #[deprecated(note = "install the clipboard with `UiContext::set_clipboard` instead")]
+    pub fn handle_winit_event(&mut self, event: &WindowEvent, ctx: &mut UiContext) {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                let state = modifiers.state();
+                self.command_held = state.control_key() || state.super_key();
+            }
+            WindowEvent::KeyboardInput { event, .. }
+                if event.state == ElementState::Pressed && self.command_held =>
+            {
+                let is_paste = matches!(
+                    &event.logical_key,
+                    WinitKey::Character(text) if text.eq_ignore_ascii_case("v")
+                );
+                if is_paste {
+                    if let Ok(text) = self.clipboard.get_text() {
+                        ctx.set_clipboard_text(text);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Write text copied during the frame to the system clipboard
+    ///
+    /// Call after `end_frame`.
+    #[deprecated(note = "install the clipboard with `UiContext::set_clipboard` instead")]
+    pub fn apply(&mut self, ctx: &mut UiContext) {
+        if let Some(text) = ctx.take_copied_text() {
+            // Failing to write leaves the copy usable inside the app
+            let _ = self.clipboard.set_text(text);
+        }
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.clipboard.get_text().ok()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        // Failing to write leaves the copy usable inside the app
+        let _ = self.clipboard.set_text(text);
+    }
+}
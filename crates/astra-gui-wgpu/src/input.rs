@@ -3,7 +3,7 @@
 //! This module provides conversion from winit events to astra-gui's
 //! backend-agnostic input types.
 
-use astra_gui::{InputState, Key, MouseButton, NamedKey, Point};
+use astra_gui::{CursorIcon, InputState, Key, MouseButton, NamedKey, Point};
 use winit::event::{ElementState, WindowEvent};
 use winit::keyboard::Key as WinitKey;
 
@@ -36,8 +36,8 @@ impl WinitInputExt for InputState {
                         self.add_scroll_delta(x * PIXELS_PER_LINE, y * PIXELS_PER_LINE);
                     }
                     MouseScrollDelta::PixelDelta(pos) => {
-                        // Pixel delta - use directly
-                        self.add_scroll_delta(pos.x as f32, pos.y as f32);
+                        // Pixel delta (trackpads) - follow it one to one
+                        self.add_precise_scroll_delta(pos.x as f32, pos.y as f32);
                     }
                 }
             }
@@ -78,9 +78,7 @@ impl WinitInputExt for InputState {
                                     && text.len() == 1
                                     && text.chars().next().unwrap().is_alphabetic();
                                 if !is_shortcut {
-                                    for ch in text.chars() {
-                                        self.type_character(ch);
-                                    }
+                                    self.type_text(text);
                                 }
                             }
                             WinitKey::Named(winit::keyboard::NamedKey::Space) => {
@@ -95,6 +93,38 @@ impl WinitInputExt for InputState {
                     }
                 }
             }
+            WindowEvent::Ime(winit::event::Ime::Commit(text)) => {
+                // Text composed with an input method, when IME is enabled
+                self.type_text(text);
+            }
+            WindowEvent::PinchGesture { delta, .. } => {
+                // Trackpad pinches report the change in magnification
+                self.add_pinch((1.0 + *delta as f32).max(0.01));
+            }
+            WindowEvent::PanGesture { delta, .. } => {
+                self.add_pan(delta.x, delta.y);
+            }
+            WindowEvent::Touch(touch) => {
+                use winit::event::TouchPhase;
+                let position = Point {
+                    x: touch.location.x as f32,
+                    y: touch.location.y as f32,
+                };
+                match touch.phase {
+                    TouchPhase::Started => self.touch_start(touch.id, position),
+                    TouchPhase::Moved => self.touch_move(touch.id, position),
+                    TouchPhase::Ended | TouchPhase::Cancelled => self.touch_end(touch.id),
+                }
+            }
+            WindowEvent::HoveredFile(path) => {
+                self.hover_file(path.clone());
+            }
+            WindowEvent::HoveredFileCancelled => {
+                self.cancel_file_hover();
+            }
+            WindowEvent::DroppedFile(path) => {
+                self.drop_file(path.clone());
+            }
             _ => {
                 // Ignore other events
             }
@@ -128,6 +158,28 @@ pub fn convert_mouse_button_to_winit(button: MouseButton) -> winit::event::Mouse
     }
 }
 
+/// Convert an astra-gui cursor icon hint to a winit cursor icon
+///
+/// `apply_platform_output` applies it along with the frame's other requests.
+pub fn to_winit_cursor(icon: CursorIcon) -> winit::window::CursorIcon {
+    use winit::window::CursorIcon as Winit;
+    match icon {
+        CursorIcon::Default => Winit::Default,
+        CursorIcon::Pointer => Winit::Pointer,
+        CursorIcon::Text => Winit::Text,
+        CursorIcon::Grab => Winit::Grab,
+        CursorIcon::Grabbing => Winit::Grabbing,
+        CursorIcon::Move => Winit::Move,
+        CursorIcon::NotAllowed => Winit::NotAllowed,
+        CursorIcon::Crosshair => Winit::Crosshair,
+        CursorIcon::EwResize => Winit::EwResize,
+        CursorIcon::NsResize => Winit::NsResize,
+        CursorIcon::NeswResize => Winit::NeswResize,
+        CursorIcon::NwseResize => Winit::NwseResize,
+        _ => Winit::Default,
+    }
+}
+
 /// Convert winit Key to astra-gui Key
 pub fn convert_key(key: &WinitKey) -> Key {
     match key {
@@ -189,7 +241,10 @@ pub fn convert_named_key(key: &winit::keyboard::NamedKey) -> NamedKey {
 #[allow(dead_code)]
 pub fn convert_key_to_winit(key: &Key) -> WinitKey {
     match key {
-        Key::Named(named) => WinitKey::Named(convert_named_key_to_winit(named)),
+        Key::Named(named) => match convert_named_key_to_winit(named) {
+            Some(named) => WinitKey::Named(named),
+            None => WinitKey::Unidentified(winit::keyboard::NativeKey::Unidentified),
+        },
         Key::Character(s) => WinitKey::Character(s.as_str().into()),
         Key::Unknown => WinitKey::Unidentified(winit::keyboard::NativeKey::Unidentified),
     }
@@ -197,11 +252,13 @@ pub fn convert_key_to_winit(key: &Key) -> WinitKey {
 
 /// Convert astra-gui NamedKey to winit NamedKey
 /// Convert astra-gui NamedKey to winit NamedKey
+///
+/// Returns `None` for keys this backend does not know yet.
 #[allow(dead_code)]
-pub fn convert_named_key_to_winit(key: &NamedKey) -> winit::keyboard::NamedKey {
+pub fn convert_named_key_to_winit(key: &NamedKey) -> Option<winit::keyboard::NamedKey> {
     use winit::keyboard::NamedKey as WN;
 
-    match key {
+    let named = match key {
         NamedKey::Enter => WN::Enter,
         NamedKey::Escape => WN::Escape,
         NamedKey::Backspace => WN::Backspace,
@@ -240,5 +297,7 @@ pub fn convert_named_key_to_winit(key: &NamedKey) -> winit::keyboard::NamedKey {
         NamedKey::Pause => WN::Pause,
         NamedKey::NumLock => WN::NumLock,
         NamedKey::ContextMenu => WN::ContextMenu,
-    }
+        _ => return None,
+    };
+    Some(named)
 }
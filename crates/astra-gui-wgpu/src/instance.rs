@@ -1,4 +1,30 @@
-use astra_gui::{AntiAliasing, ClippedShape, CornerShape, Shape};
+use astra_gui::{
+    AntiAliasing, ClippedShape, Color, ColorMatrix, ConicGradient, CornerKind, CornerShape,
+    LinearGradient, NineSliceImage, Shape, Size,
+};
+
+/// Corner type for `CornerShape::PerCorner`
+///
+/// The per-corner kinds (same numbering as the uniform corner types) are packed
+/// into bits 8.. of `shape_corner_type`, three bits per corner in clockwise order
+/// starting at the top left.
+const PER_CORNER_TYPE: u32 = 5;
+
+/// Flag in `shape_corner_type` marking an inset shadow instance
+///
+/// The instance draws only the shadow cast inward from the rect's edges, with
+/// its parameters in `effect`.
+const INSET_SHADOW_FLAG: u32 = 1 << 24;
+
+/// Flag in `shape_corner_type` marking a linear gradient segment instance
+const LINEAR_GRADIENT_FLAG: u32 = 1 << 25;
+
+/// Flag in `shape_corner_type` marking a nine-slice patch instance
+///
+/// The instance is a square-cornered patch tinted by `fill_color`, sampling
+/// the draw's texture across the UV rect in `effect`. Patches are drawn
+/// without anti-aliasing so neighbors tile without seams.
+const TEXTURED_FLAG: u32 = 1 << 26;
 
 /// Instance data for SDF-based rectangle rendering.
 ///
@@ -26,25 +52,40 @@ pub struct RectInstance {
     /// Stroke width in pixels (0 = no stroke)
     pub stroke_width: f32,
     /// Shape/corner type:
-    /// For rectangles: 0=None, 1=Round, 2=Cut, 3=InverseRound, 4=Squircle
+    /// For rectangles: 0=None, 1=Round, 2=Cut, 3=InverseRound, 4=Squircle, 5=PerCorner
+    /// (with per-corner kinds packed into the upper bits), optionally with
+    /// `INSET_SHADOW_FLAG`
     /// For triangles: 100 = Triangle
     pub shape_corner_type: u32,
-    /// Parameter 1: corner radius for rects, or triangle v0.x for triangles
+    /// Parameter 1: corner radius (top-left for PerCorner) for rects, or triangle v0.x for triangles
     pub param1: f32,
     /// Parameter 2: corner smoothness for rects, or triangle v0.y for triangles
     pub param2: f32,
-    /// Parameter 3: unused for rects, or triangle v1.x for triangles
+    /// Parameter 3: top-right radius for PerCorner rects, or triangle v1.x for triangles
     pub param3: f32,
-    /// Parameter 4: unused for rects, or triangle v1.y for triangles
+    /// Parameter 4: bottom-right radius for PerCorner rects, or triangle v1.y for triangles
     pub param4: f32,
-    /// Parameter 5: unused for rects, or triangle v2.x for triangles
+    /// Parameter 5: bottom-left radius for PerCorner rects, or triangle v2.x for triangles
     pub param5: f32,
-    /// Parameter 6: unused for rects, or triangle v2.y for triangles
+    /// Parameter 6: soft edge (blur) width for rects, or triangle v2.y for triangles
     pub param6: f32,
     /// Stroke offset for alignment (0 = centered, positive = outward, negative = inward)
     pub stroke_offset: f32,
     /// Anti-aliasing mode: 0 = None, 1 = Analytical
     pub anti_aliasing: u32,
+    /// Fill effect parameters, all zero for plain fills:
+    /// - Conic gradient segment: gradient center relative to the rect center
+    ///   (pixels), then the segment's start and end in turns clockwise from the
+    ///   top. The fill fades from `fill_color` to `stroke_color` across the
+    ///   segment and nothing is drawn outside it.
+    /// - Linear gradient segment (`LINEAR_GRADIENT_FLAG` set): the gradient
+    ///   direction divided by the gradient line's length (pixels), then the
+    ///   segment's start and end along the line. Fades like a conic segment.
+    /// - Inset shadow (`INSET_SHADOW_FLAG` set): shadow offset x/y, blur and
+    ///   spread in pixels. `fill_color` is the shadow color.
+    /// - Nine-slice patch (`TEXTURED_FLAG` set): the texture coordinates of
+    ///   the patch's top-left and bottom-right corners.
+    pub effect: [f32; 4],
 }
 
 impl RectInstance {
@@ -147,6 +188,182 @@ impl RectInstance {
                 AntiAliasing::None => 0,
                 AntiAliasing::Analytical => 1,
             },
+            effect: [0.0; 4],
+        }
+    }
+
+    /// Push the instances drawing a rect shape
+    ///
+    /// Plain rects are a single instance. Rects with a gradient, a nine-slice
+    /// or an inset shadow are split into layers: the fill (one instance per
+    /// gradient segment, each fading between two stop colors, or per
+    /// nine-slice patch), then the inset shadow, then a fill-less instance for
+    /// the stroke. A nine-slice takes precedence over gradients, and a conic
+    /// gradient over a linear one.
+    pub fn push_rect(clipped: &ClippedShape, out: &mut Vec<RectInstance>) {
+        let base = Self::from(clipped);
+        let Shape::Rect(rect) = &clipped.shape else {
+            out.push(base);
+            return;
+        };
+        let gradient = rect
+            .conic_gradient
+            .as_ref()
+            .filter(|gradient| !gradient.stops.is_empty());
+        let linear_gradient = rect
+            .linear_gradient
+            .as_ref()
+            .filter(|gradient| !gradient.stops.is_empty());
+        if gradient.is_none()
+            && linear_gradient.is_none()
+            && rect.nine_slice.is_none()
+            && rect.inset_shadow.is_none()
+        {
+            out.push(base);
+            return;
+        }
+
+        let fill = Self {
+            stroke_color: [0; 4],
+            stroke_width: 0.0,
+            stroke_offset: 0.0,
+            ..base
+        };
+        match (&rect.nine_slice, gradient, linear_gradient) {
+            (Some(image), ..) => Self::push_nine_slice(fill, clipped, image, out),
+            (None, Some(gradient), _) => Self::push_conic_segments(fill, clipped, gradient, out),
+            (None, None, Some(gradient)) => {
+                Self::push_linear_segments(fill, clipped, gradient, out)
+            }
+            (None, None, None) => out.push(fill),
+        }
+
+        if let Some(shadow) = rect.inset_shadow {
+            let resolve = |size: Size| size.resolve_physical_or_zero(1.0);
+            out.push(Self {
+                fill_color: color_to_unorm(shadow.color, clipped.opacity),
+                shape_corner_type: base.shape_corner_type | INSET_SHADOW_FLAG,
+                effect: [
+                    resolve(shadow.offset_x),
+                    resolve(shadow.offset_y),
+                    resolve(shadow.blur).max(0.0),
+                    resolve(shadow.spread),
+                ],
+                ..fill
+            });
+        }
+
+        if base.stroke_width > 0.0 {
+            out.push(Self {
+                fill_color: [0; 4],
+                ..base
+            });
+        }
+    }
+
+    fn push_nine_slice(
+        fill: RectInstance,
+        clipped: &ClippedShape,
+        image: &NineSliceImage,
+        out: &mut Vec<RectInstance>,
+    ) {
+        let tint = color_to_unorm(image.tint, clipped.opacity);
+        for patch in image.patches(clipped.node_rect) {
+            let (min, max) = (patch.rect.min, patch.rect.max);
+            if max[0] <= min[0] || max[1] <= min[1] {
+                continue;
+            }
+            out.push(Self {
+                center: [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5],
+                half_size: [(max[0] - min[0]) * 0.5, (max[1] - min[1]) * 0.5],
+                fill_color: tint,
+                shape_corner_type: TEXTURED_FLAG,
+                param1: 0.0,
+                param2: 0.0,
+                param3: 0.0,
+                param4: 0.0,
+                param5: 0.0,
+                param6: 0.0,
+                anti_aliasing: 0,
+                effect: [
+                    patch.uv.min[0],
+                    patch.uv.min[1],
+                    patch.uv.max[0],
+                    patch.uv.max[1],
+                ],
+                ..fill
+            });
+        }
+    }
+
+    fn push_conic_segments(
+        fill: RectInstance,
+        clipped: &ClippedShape,
+        gradient: &ConicGradient,
+        out: &mut Vec<RectInstance>,
+    ) {
+        let rect = clipped.node_rect;
+        let center = [
+            (rect.max[0] - rect.min[0]) * (gradient.center[0] - 0.5),
+            (rect.max[1] - rect.min[1]) * (gradient.center[1] - 0.5),
+        ];
+        let start = gradient.start_angle / std::f32::consts::TAU;
+
+        // The first and last stop colors extend to the start and end of the turn
+        let first = gradient.stops[0];
+        let last = gradient.stops[gradient.stops.len() - 1];
+        let stops = std::iter::once((0.0, first.1))
+            .chain(gradient.stops.iter().copied())
+            .chain(std::iter::once((1.0, last.1)))
+            .collect::<Vec<_>>();
+
+        for pair in stops.windows(2) {
+            let ((p0, c0), (p1, c1)) = (pair[0], pair[1]);
+            if p1 <= p0 {
+                continue;
+            }
+            out.push(Self {
+                fill_color: color_to_unorm(c0, clipped.opacity),
+                stroke_color: color_to_unorm(c1, clipped.opacity),
+                effect: [center[0], center[1], start + p0, start + p1],
+                ..fill
+            });
+        }
+    }
+
+    fn push_linear_segments(
+        fill: RectInstance,
+        clipped: &ClippedShape,
+        gradient: &LinearGradient,
+        out: &mut Vec<RectInstance>,
+    ) {
+        // Positions along the gradient line are the dot product of the pixel
+        // (relative to the center) with the direction over the line's length
+        let [width, height] = [fill.half_size[0] * 2.0, fill.half_size[1] * 2.0];
+        let direction = [gradient.angle.sin(), -gradient.angle.cos()];
+        let length = ((width * direction[0]).abs() + (height * direction[1]).abs()).max(1e-3);
+
+        // The first and last stop colors extend past both ends of the line,
+        // so anti-aliased edges just outside the rect are colored too
+        let first = gradient.stops[0];
+        let last = gradient.stops[gradient.stops.len() - 1];
+        let stops = std::iter::once((-1.0, first.1))
+            .chain(gradient.stops.iter().copied())
+            .chain(std::iter::once((2.0, last.1)))
+            .collect::<Vec<_>>();
+
+        for pair in stops.windows(2) {
+            let ((p0, c0), (p1, c1)) = (pair[0], pair[1]);
+            if p1 <= p0 {
+                continue;
+            }
+            out.push(Self {
+                fill_color: color_to_unorm(c0, clipped.opacity),
+                stroke_color: color_to_unorm(c1, clipped.opacity),
+                shape_corner_type: fill.shape_corner_type | LINEAR_GRADIENT_FLAG,
+                effect: [direction[0] / length, direction[1] / length, p0, p1],
+                ..fill
+            });
         }
     }
 
@@ -171,17 +388,12 @@ impl RectInstance {
                 shader_location: 3,
                 format: wgpu::VertexFormat::Float32x2,
             },
-            // rotation: f32 at location 4
+            // rotation, scale: vec2<f32> at location 4 (packed to stay within
+            // the 16 vertex attribute limit)
             wgpu::VertexAttribute {
                 offset: 24,
                 shader_location: 4,
-                format: wgpu::VertexFormat::Float32,
-            },
-            // scale: f32 at location 6
-            wgpu::VertexAttribute {
-                offset: 28,
-                shader_location: 6,
-                format: wgpu::VertexFormat::Float32,
+                format: wgpu::VertexFormat::Float32x2,
             },
             // transform_origin: vec2<f32> at location 5
             wgpu::VertexAttribute {
@@ -243,6 +455,12 @@ impl RectInstance {
                 shader_location: 15,
                 format: wgpu::VertexFormat::Uint32,
             },
+            // effect: vec4<f32> at location 6
+            wgpu::VertexAttribute {
+                offset: 88,
+                shader_location: 6,
+                format: wgpu::VertexFormat::Float32x4,
+            },
         ];
 
         wgpu::VertexBufferLayout {
@@ -332,13 +550,51 @@ impl From<&ClippedShape> for RectInstance {
         };
 
         // Convert corner shape to type + parameters
-        let (corner_type, param1, param2) = match rect.corner_shape {
-            CornerShape::None => (0, 0.0, 0.0),
-            CornerShape::Round(radius) => (1, radius.resolve_physical_or_zero(1.0), 0.0),
-            CornerShape::Cut(distance) => (2, distance.resolve_physical_or_zero(1.0), 0.0),
-            CornerShape::InverseRound(radius) => (3, radius.resolve_physical_or_zero(1.0), 0.0),
-            CornerShape::Squircle { radius, smoothness } => {
-                (4, radius.resolve_physical_or_zero(1.0), smoothness)
+        let (corner_type, param1, param2, [param3, param4, param5]) = match rect.corner_shape {
+            CornerShape::None => (0, 0.0, 0.0, [0.0; 3]),
+            CornerShape::Round(radius) => (1, radius.resolve_physical_or_zero(1.0), 0.0, [0.0; 3]),
+            CornerShape::Cut(distance) => {
+                (2, distance.resolve_physical_or_zero(1.0), 0.0, [0.0; 3])
+            }
+            CornerShape::InverseRound(radius) => {
+                (3, radius.resolve_physical_or_zero(1.0), 0.0, [0.0; 3])
+            }
+            CornerShape::Squircle { radius, smoothness } => (
+                4,
+                radius.resolve_physical_or_zero(1.0),
+                smoothness,
+                [0.0; 3],
+            ),
+            CornerShape::PerCorner(corners) => {
+                // Kinds are packed into the (flat) corner type, sizes go into params 1, 3, 4, 5
+                let mut corner_type = PER_CORNER_TYPE;
+                let mut sizes = [0.0; 4];
+                let mut smoothness = 0.0;
+                for (i, corner) in corners.as_array().into_iter().enumerate() {
+                    let (kind, size) = match corner {
+                        CornerKind::Square => (0, 0.0),
+                        CornerKind::Round(radius) => (1, radius.resolve_physical_or_zero(1.0)),
+                        CornerKind::Cut(distance) => (2, distance.resolve_physical_or_zero(1.0)),
+                        CornerKind::InverseRound(radius) => {
+                            (3, radius.resolve_physical_or_zero(1.0))
+                        }
+                        CornerKind::Squircle {
+                            radius,
+                            smoothness: s,
+                        } => {
+                            smoothness = s;
+                            (4, radius.resolve_physical_or_zero(1.0))
+                        }
+                    };
+                    corner_type |= kind << (8 + 3 * i);
+                    sizes[i] = size;
+                }
+                (
+                    corner_type,
+                    sizes[0],
+                    smoothness,
+                    [sizes[1], sizes[2], sizes[3]],
+                )
             }
         };
 
@@ -355,15 +611,74 @@ impl From<&ClippedShape> for RectInstance {
             shape_corner_type: corner_type,
             param1,
             param2,
-            param3: 0.0,
-            param4: 0.0,
-            param5: 0.0,
-            param6: 0.0,
+            param3,
+            param4,
+            param5,
+            // Soft edge width for blurred rects (shadows)
+            param6: rect.blur.resolve_physical_or_zero(1.0),
             stroke_offset,
             anti_aliasing: match rect.anti_aliasing {
                 AntiAliasing::None => 0,
                 AntiAliasing::Analytical => 1,
             },
+            effect: [0.0; 4],
+        }
+    }
+}
+
+/// Instance data for compositing a filtered offscreen layer
+///
+/// Each instance draws the layer texture onto the target once, passing its
+/// colors through the row-major color matrix.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct FilterInstance {
+    /// Rows of the color matrix
+    pub rows: [[f32; 3]; 3],
+}
+
+impl FilterInstance {
+    pub const fn desc() -> wgpu::VertexBufferLayout<'static> {
+        const ATTRIBUTES: &[wgpu::VertexAttribute] = &[
+            // row0: vec3<f32> at location 0
+            wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+            // row1: vec3<f32> at location 1
+            wgpu::VertexAttribute {
+                offset: 12,
+                shader_location: 1,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+            // row2: vec3<f32> at location 2
+            wgpu::VertexAttribute {
+                offset: 24,
+                shader_location: 2,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+        ];
+
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<FilterInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: ATTRIBUTES,
         }
     }
 }
+
+impl From<ColorMatrix> for FilterInstance {
+    fn from(matrix: ColorMatrix) -> Self {
+        Self { rows: matrix.0 }
+    }
+}
+
+fn color_to_unorm(color: Color, opacity: f32) -> [u8; 4] {
+    [
+        (color.r * 255.0).round().clamp(0.0, 255.0) as u8,
+        (color.g * 255.0).round().clamp(0.0, 255.0) as u8,
+        (color.b * 255.0).round().clamp(0.0, 255.0) as u8,
+        ((color.a * opacity) * 255.0).round().clamp(0.0, 255.0) as u8,
+    ]
+}
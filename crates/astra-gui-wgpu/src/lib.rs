@@ -5,13 +5,20 @@
 //! This crate provides:
 //! - WGPU-based rendering for astra-gui
 //! - Winit input event conversion via `WinitInputExt`
+//! - Automatic light/dark theme switching via `AutoTheme`
+//! - System clipboard access via `SystemClipboard` (`clipboard` feature)
+//! - Cursor, window and URL requests via `apply_platform_output`
 //!
 //! For the core UI types (InputState, EventDispatcher, UiContext, etc.),
 //! import them directly from `astra_gui`.
 
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod events;
 mod input;
 mod instance;
+mod platform;
+mod theme;
 
 #[cfg(feature = "text-cosmic")]
 mod text;
@@ -19,8 +26,18 @@ mod text;
 // Re-export event and state types from astra-gui core
 pub use events::*;
 
-// Export the winit input adapter extension trait
-pub use input::WinitInputExt;
+// Export the winit input adapter extension trait and cursor conversion
+pub use input::{to_winit_cursor, WinitInputExt};
+
+// Export the platform request execution
+pub use platform::{apply_platform_output, to_winit_resize_direction};
+
+// Export the OS color scheme integration
+pub use theme::{to_color_scheme, AutoTheme};
+
+// Export the system clipboard integration
+#[cfg(feature = "clipboard")]
+pub use clipboard::SystemClipboard;
 
 // Re-export winit key types for convenience (used by interactive components)
 pub use winit::event::MouseButton as WinitMouseButton;
@@ -31,11 +48,15 @@ pub use astra_gui::{
     AntiAliasing, InputState, Key, MouseButton, NamedKey, UiContext, WidgetMemory,
 };
 
+// Re-export the glyph antialiasing modes accepted by `Renderer::set_glyph_antialiasing`
+#[cfg(feature = "text-cosmic")]
+pub use astra_gui_text::GlyphAntialiasing;
+
 use astra_gui::{
-    ClippedShape, Color, CornerShape, FullOutput, HorizontalAlign, Rect, Shape, Size, Stroke,
-    StyledRect, Transform2D, VerticalAlign, ZIndex,
+    ClippedShape, Color, ColorMatrix, CornerShape, FullOutput, HorizontalAlign, Rect, Shape, Size,
+    Stroke, StyledRect, TextureId, Transform2D, VerticalAlign, ZIndex,
 };
-use instance::RectInstance;
+use instance::{FilterInstance, RectInstance};
 
 #[cfg(feature = "text-cosmic")]
 use astra_gui_text as gui_text;
@@ -56,6 +77,23 @@ struct SdfDraw {
     scissor: (u32, u32, u32, u32),
     instance_start: u32,
     instance_count: u32,
+    /// Texture sampled by nine-slice patches (`None` for untextured instances)
+    texture: Option<TextureId>,
+}
+
+/// A draw recorded while processing the layers, in draw order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DrawCommand {
+    Sdf(usize),  // Index into sdf_draws (handles both rects and triangles)
+    Text(usize), // Index into text_draws
+}
+
+/// The draw commands of a rendering layer.
+#[derive(Debug)]
+struct LayerDrawCommands {
+    /// Color filter the layer is composited through
+    filter: Option<ColorMatrix>,
+    commands: Vec<DrawCommand>,
 }
 
 /// A rendering layer containing shapes at a specific z-index with rendering ranges.
@@ -63,6 +101,8 @@ struct SdfDraw {
 struct RenderLayer<'a> {
     #[allow(dead_code)]
     z_index: astra_gui::ZIndex,
+    /// Color filter the layer is composited through (see `Node::with_filter`)
+    filter: Option<ColorMatrix>,
     shapes: Vec<&'a astra_gui::ClippedShape>,
 }
 
@@ -75,14 +115,226 @@ const INITIAL_TEXT_INDEX_CAPACITY: usize = 8192;
 const ATLAS_SIZE_PX: u32 = 4096;
 #[cfg(feature = "text-cosmic")]
 const ATLAS_PADDING_PX: u32 = 1;
+/// Initial side of the RGBA color glyph atlas; emoji are rare enough that it
+/// starts smaller than the mask atlas and doubles on demand
+#[cfg(feature = "text-cosmic")]
+const COLOR_ATLAS_SIZE_PX: u32 = 1024;
+
+/// Atlas font size of distance field glyphs, which bitmap glyphs never use
+/// (their size is at least 1px); one entry serves every drawn size
+#[cfg(feature = "text-cosmic")]
+const SDF_GLYPH_PX: u16 = 0;
+
+/// Atlas key of a text engine glyph key drawn at `font_px`
+///
+/// The variant packs the weight (low 10 bits), the subpixel bin (next 2 bits)
+/// and LCD antialiasing (bit 12).
+#[cfg(feature = "text-cosmic")]
+fn atlas_glyph_key(key: gui_text::GlyphKey, font_px: u16) -> text::atlas::GlyphKey {
+    let bin = (key.subpixel_x_64 as u16 * gui_text::SUBPIXEL_BINS as u16 / 64) & 0b11;
+    let lcd = (key.antialiasing == gui_text::GlyphAntialiasing::Lcd) as u16;
+    text::atlas::GlyphKey::new(
+        key.font_id.0,
+        key.glyph_id,
+        font_px,
+        (key.weight & 0x3ff) | bin << 10 | lcd << 12,
+    )
+}
+
+/// Text engine key to re-rasterize an atlas entry with (inverse of [`atlas_glyph_key`])
+#[cfg(feature = "text-cosmic")]
+fn text_glyph_key(key: &text::atlas::GlyphKey) -> gui_text::GlyphKey {
+    let bin = (key.variant >> 10) & 0b11;
+    let antialiasing = if key.variant >> 12 & 1 == 1 {
+        gui_text::GlyphAntialiasing::Lcd
+    } else {
+        gui_text::GlyphAntialiasing::Grayscale
+    };
+    let (key_with_bin, _) = gui_text::GlyphKey::new(
+        gui_text::FontId(key.font_id),
+        key.glyph_id,
+        key.font_px,
+        key.variant & 0x3ff,
+    )
+    .snap_x(bin as f32 / gui_text::SUBPIXEL_BINS as f32);
+    key_with_bin.with_antialiasing(antialiasing)
+}
+
+/// Whether glyphs of `format` are stored in the RGBA atlas
+#[cfg(feature = "text-cosmic")]
+fn in_color_atlas(format: gui_text::GlyphFormat) -> bool {
+    matches!(
+        format,
+        gui_text::GlyphFormat::Color | gui_text::GlyphFormat::Lcd
+    )
+}
+
+/// Encode LCD coverage (RGBA, linear) for the sRGB color atlas, so sampling decodes
+/// it back to linear coverage
+#[cfg(feature = "text-cosmic")]
+fn encode_lcd_coverage(pixels: &mut [u8]) {
+    for value in pixels.iter_mut() {
+        let linear = *value as f32 / 255.0;
+        let srgb = if linear <= 0.003_130_8 {
+            linear * 12.92
+        } else {
+            1.055 * linear.powf(1.0 / 2.4) - 0.055
+        };
+        *value = (srgb * 255.0).round() as u8;
+    }
+}
+
+/// Offsets a bitmap glyph is stamped at to dilate it into an outline `width`
+/// pixels wide: rings a pixel apart, each with enough copies to close the gaps
+#[cfg(feature = "text-cosmic")]
+fn outline_offsets(width: f32) -> Vec<[f32; 2]> {
+    let mut offsets = Vec::new();
+    let mut radius = width;
+    while radius > 0.0 {
+        let count = ((std::f32::consts::TAU * radius).ceil() as usize).max(8);
+        offsets.extend((0..count).map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            [radius * angle.cos(), radius * angle.sin()]
+        }));
+        radius -= 1.0;
+    }
+    offsets
+}
+
+/// Atlas entry holding a small opaque block, sampled to draw text decorations
+/// (underlines) as solid quads through the text pipeline
+#[cfg(feature = "text-cosmic")]
+const SOLID_GLYPH_KEY: text::atlas::GlyphKey = text::atlas::GlyphKey::new(u64::MAX, 0, 0, 0);
+#[cfg(feature = "text-cosmic")]
+const SOLID_GLYPH_SIZE_PX: u32 = 4;
+
+/// Additive blending used by the overdraw debug view, so every shaded fragment
+/// accumulates onto the pixel instead of covering it.
+const OVERDRAW_BLENDING: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+/// A texture sampled by nine-slice patches
+struct ImageTexture {
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Offscreen layer that filtered subtrees are drawn into before being
+/// composited onto the target
+struct FilterTexture {
+    size: [u32; 2],
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+/// How text glyphs are rasterized and drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GlyphRenderMode {
+    /// Coverage bitmaps rasterized per pixel size: sharpest at 1:1, but every
+    /// zoom level adds a new copy of each glyph to the atlas
+    #[default]
+    Bitmap,
+    /// Signed distance fields rasterized once and scaled on the GPU: a single
+    /// atlas entry per glyph at any zoom, at the cost of slightly softer small text
+    Sdf,
+}
+
+/// When text missing from the shaping cache is shaped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextShapingMode {
+    /// On the render thread, in the frame the text first appears; a frame with
+    /// a lot of new text stalls until it's shaped
+    #[default]
+    Immediate,
+    /// Texts of at least `min_bytes` are shaped on a worker thread. Until the
+    /// layout lands they draw with the latest layout of the same text (e.g.
+    /// before a resize), or not at all; keep redrawing while
+    /// [`Renderer::has_pending_text_shaping`] is true. Shorter texts are shaped
+    /// immediately.
+    Background { min_bytes: usize },
+}
+
+/// Frames a shaped text stays cached without being drawn
+#[cfg(feature = "text-cosmic")]
+const SHAPE_CACHE_MAX_AGE: u64 = 120;
+
+/// Hash of everything that affects a text shape's layout: text, font size, rect
+/// dimensions, wrap, line height, weight, style, spans, family, overflow, line
+/// limit, features, width and tab width
+///
+/// Hashed instead of kept as a tuple so cache lookups don't clone the text
+/// every frame; a 64-bit collision between two texts drawn together is
+/// vanishingly unlikely.
+#[cfg(feature = "text-cosmic")]
+fn shape_cache_key(text_shape: &astra_gui::TextShape, font_size_px: f32) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let rect = text_shape.rect;
+    let mut hasher = rustc_hash::FxHasher::default();
+    text_shape.text.hash(&mut hasher);
+    (font_size_px as u32).hash(&mut hasher);
+    ((rect.max[0] - rect.min[0]) as u32).hash(&mut hasher);
+    ((rect.max[1] - rect.min[1]) as u32).hash(&mut hasher);
+    text_shape.wrap.hash(&mut hasher);
+    ((text_shape.line_height_multiplier * 100.0) as u32).hash(&mut hasher);
+    text_shape.font_weight.to_weight().hash(&mut hasher);
+    text_shape.font_style.hash(&mut hasher);
+    gui_text::span_layout_hash(&text_shape.spans).hash(&mut hasher);
+    text_shape.font_family.hash(&mut hasher);
+    text_shape.overflow.hash(&mut hasher);
+    text_shape.max_lines.hash(&mut hasher);
+    text_shape.font_features.hash(&mut hasher);
+    text_shape.font_width.hash(&mut hasher);
+    text_shape.tab_width.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash of a text alone, keying the layouts drawn while it's shaped in the background
+#[cfg(feature = "text-cosmic")]
+fn text_hash(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = rustc_hash::FxHasher::default();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Shaped text in the shape cache
+#[cfg(feature = "text-cosmic")]
+struct CachedShape {
+    /// Shared so drawing it doesn't copy its glyphs
+    shaped: std::sync::Arc<gui_text::ShapedText>,
+    /// Frame generation that last drew it
+    last_used: u64,
+}
 
 /// WGPU renderer for astra-gui
 pub struct Renderer {
     uniform_buffer: wgpu::Buffer,
     uniform_bind_group: wgpu::BindGroup,
 
+    // Textures of nine-slice backgrounds, bound per SDF draw; untextured draws
+    // bind an empty texture
+    image_bind_group_layout: wgpu::BindGroupLayout,
+    image_sampler: wgpu::Sampler,
+    empty_image: ImageTexture,
+    images: rustc_hash::FxHashMap<TextureId, ImageTexture>,
+    next_texture_id: u64,
+
     // SDF rendering pipeline (analytic anti-aliasing for both rects and triangles)
     sdf_pipeline: wgpu::RenderPipeline,
+    // Additive variant used by the overdraw debug view
+    sdf_overdraw_pipeline: wgpu::RenderPipeline,
     sdf_instance_buffer: wgpu::Buffer,
     sdf_instance_capacity: usize,
     sdf_instances: Vec<RectInstance>,
@@ -91,9 +343,20 @@ pub struct Renderer {
     sdf_quad_index_buffer: wgpu::Buffer,
     last_frame_sdf_instance_count: usize,
 
+    // Color filter layers (`Node::with_filter`): runs of filtered shapes are
+    // drawn into `filter_texture`, then composited through their color matrix
+    surface_format: wgpu::TextureFormat,
+    filter_pipeline: wgpu::RenderPipeline,
+    filter_instance_buffer: wgpu::Buffer,
+    filter_instance_capacity: usize,
+    filter_instances: Vec<FilterInstance>,
+    filter_texture: Option<FilterTexture>,
+
     #[cfg(feature = "text-cosmic")]
     text_pipeline: wgpu::RenderPipeline,
     #[cfg(feature = "text-cosmic")]
+    text_overdraw_pipeline: wgpu::RenderPipeline,
+    #[cfg(feature = "text-cosmic")]
     text_vertex_buffer: wgpu::Buffer,
     #[cfg(feature = "text-cosmic")]
     text_index_buffer: wgpu::Buffer,
@@ -122,41 +385,61 @@ pub struct Renderer {
     #[cfg(feature = "text-cosmic")]
     atlas_sampler: wgpu::Sampler,
     #[cfg(feature = "text-cosmic")]
+    atlas_linear_sampler: wgpu::Sampler,
+    #[cfg(feature = "text-cosmic")]
     atlas: text::atlas::GlyphAtlas,
+    #[cfg(feature = "text-cosmic")]
+    glyph_render_mode: GlyphRenderMode,
+    #[cfg(feature = "text-cosmic")]
+    glyph_antialiasing: GlyphAntialiasing,
+
+    // Color glyph atlas (RGBA, emoji and COLR fonts)
+    #[cfg(feature = "text-cosmic")]
+    color_atlas_texture: wgpu::Texture,
+    #[cfg(feature = "text-cosmic")]
+    color_atlas: text::atlas::GlyphAtlas,
 
     // Backend-agnostic text shaping/raster engine (Inter via astra-gui-fonts).
     #[cfg(feature = "text-cosmic")]
     text_engine: gui_text::Engine,
 
     // Text shaping cache - stores pre-shaped text to avoid expensive reshaping every frame
-    // Key: (text, font_size, width, height, wrap, line_height * 100, font_weight, font_style)
+    // Key: `shape_cache_key`; entries not drawn for `SHAPE_CACHE_MAX_AGE` frames are evicted
     // NOTE: Only caches ShapedText, NOT LinePlacement (which contains absolute positions)
     #[cfg(feature = "text-cosmic")]
-    shape_cache: std::collections::HashMap<
-        (
-            String,
-            u32,
-            u32,
-            u32,
-            astra_gui::Wrap,
-            u32,
-            u16,
-            astra_gui::FontStyle,
-        ),
-        gui_text::ShapedText,
-    >,
+    shape_cache: rustc_hash::FxHashMap<u64, CachedShape>,
+    #[cfg(feature = "text-cosmic")]
+    shape_cache_generation: u64,
+
+    // Background shaping (`TextShapingMode::Background`): the worker, started on
+    // first use and keyed by (shape key, text hash), and per text hash the shape
+    // key of the latest layout it produced, drawn while the same text is reshaped
+    #[cfg(feature = "text-cosmic")]
+    text_shaping_mode: TextShapingMode,
+    #[cfg(feature = "text-cosmic")]
+    background_shaper: Option<text::shaper::BackgroundShaper<(u64, u64)>>,
+    #[cfg(feature = "text-cosmic")]
+    shape_placeholders: rustc_hash::FxHashMap<u64, u64>,
 
     // Glyph metrics cache - stores bearing, size, AND atlas placement to avoid lookups
-    // Key: GlyphKey (font_id, glyph_id, px_size, subpixel)
+    // Key: GlyphKey (font_id, glyph_id, px_size, weight + subpixel bin + LCD)
     #[cfg(feature = "text-cosmic")]
     glyph_metrics_cache: std::collections::HashMap<
         text::atlas::GlyphKey,
-        ([i32; 2], [u32; 2], text::atlas::PlacedGlyph), // (bearing_px, size_px, placement)
+        // (bearing_px, size_px, placement, bitmap format)
+        (
+            [i32; 2],
+            [u32; 2],
+            text::atlas::PlacedGlyph,
+            gui_text::GlyphFormat,
+        ),
     >,
 
     // Atlas resize tracking
     #[cfg(feature = "text-cosmic")]
     atlas_needs_resize: bool,
+    #[cfg(feature = "text-cosmic")]
+    color_atlas_needs_resize: bool,
 
     // For proactive estimation of atlas space needs
     #[cfg(feature = "text-cosmic")]
@@ -208,10 +491,49 @@ impl Renderer {
             }],
         });
 
+        // Nine-slice textures: one bind group per texture
+        let image_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Astra UI Image Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+
+        let image_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Astra UI Image Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::MipmapFilterMode::Nearest,
+            ..Default::default()
+        });
+
+        // Textures start zeroed, so unregistered textures draw nothing
+        let empty_image =
+            Self::create_image_texture(device, &image_bind_group_layout, &image_sampler, [1, 1]);
+
         // Create pipeline layout for SDF rendering
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Astra UI Pipeline Layout"),
-            bind_group_layouts: &[&globals_bind_group_layout],
+            bind_group_layouts: &[&globals_bind_group_layout, &image_bind_group_layout],
             immediate_size: 0,
         });
 
@@ -221,52 +543,65 @@ impl Renderer {
             source: wgpu::ShaderSource::Wgsl(include_str!("shaders/ui_sdf.wgsl").into()),
         });
 
-        let sdf_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Astra UI SDF Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &sdf_shader,
-                entry_point: Some("vs_main"),
-                buffers: &[
-                    // Vertex buffer: unit quad
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        attributes: &[wgpu::VertexAttribute {
-                            offset: 0,
-                            shader_location: 0,
-                            format: wgpu::VertexFormat::Float32x2,
-                        }],
-                    },
-                    // Instance buffer
-                    RectInstance::desc(),
-                ],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &sdf_shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview_mask: None,
-            cache: None,
-        });
+        let create_sdf_pipeline = |label: &str, fragment_entry: &str, blend: wgpu::BlendState| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &sdf_shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[
+                        // Vertex buffer: unit quad
+                        wgpu::VertexBufferLayout {
+                            array_stride: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &[wgpu::VertexAttribute {
+                                offset: 0,
+                                shader_location: 0,
+                                format: wgpu::VertexFormat::Float32x2,
+                            }],
+                        },
+                        // Instance buffer
+                        RectInstance::desc(),
+                    ],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &sdf_shader,
+                    entry_point: Some(fragment_entry),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend: Some(blend),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        };
+
+        let sdf_pipeline = create_sdf_pipeline(
+            "Astra UI SDF Pipeline",
+            "fs_main",
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+        let sdf_overdraw_pipeline = create_sdf_pipeline(
+            "Astra UI SDF Overdraw Pipeline",
+            "fs_overdraw",
+            OVERDRAW_BLENDING,
+        );
 
         // Unit quad vertices: [-1, -1] to [1, 1]
         let quad_vertices: &[[f32; 2]] = &[
@@ -309,16 +644,69 @@ impl Renderer {
             mapped_at_creation: false,
         });
 
+        // Create filter layer pipeline and buffers
+        let filter_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Astra UI Filter Layer Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/filter_layer.wgsl").into()),
+        });
+
+        let filter_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Astra UI Filter Layer Pipeline Layout"),
+                bind_group_layouts: &[&image_bind_group_layout],
+                immediate_size: 0,
+            });
+
+        let filter_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Astra UI Filter Layer Pipeline"),
+            layout: Some(&filter_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &filter_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[FilterInstance::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &filter_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    // The layer holds premultiplied colors (shapes drawn over
+                    // transparent black with alpha blending)
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        });
+
+        const INITIAL_FILTER_INSTANCE_CAPACITY: usize = 4;
+        let filter_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Astra UI Filter Instance Buffer"),
+            size: (INITIAL_FILTER_INSTANCE_CAPACITY * std::mem::size_of::<FilterInstance>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         #[cfg(feature = "text-cosmic")]
         let (
             text_pipeline,
+            text_overdraw_pipeline,
             text_vertex_buffer,
             text_index_buffer,
             atlas_texture,
             atlas_bind_group,
             atlas_bind_group_layout,
             atlas_sampler,
+            atlas_linear_sampler,
             atlas,
+            color_atlas_texture,
+            color_atlas,
         ) = {
             // Load text shader
             let text_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
@@ -342,7 +730,7 @@ impl Renderer {
                 view_formats: &[],
             });
 
-            let atlas_view = atlas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let color_atlas_texture = Self::create_color_atlas_texture(device, COLOR_ATLAS_SIZE_PX);
 
             let atlas_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
                 label: Some("Astra UI Glyph Atlas Sampler"),
@@ -357,6 +745,18 @@ impl Renderer {
                 ..Default::default()
             });
 
+            // Scaled glyphs (distance fields, color glyphs) need bilinear filtering
+            let atlas_linear_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Astra UI Glyph Atlas Linear Sampler"),
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                mipmap_filter: wgpu::MipmapFilterMode::Nearest,
+                ..Default::default()
+            });
+
             let atlas_bind_group_layout =
                 device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                     label: Some("Astra UI Text Atlas Bind Group Layout"),
@@ -377,23 +777,33 @@ impl Renderer {
                             ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                             count: None,
                         },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                            count: None,
+                        },
                     ],
                 });
 
-            let atlas_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Astra UI Text Atlas Bind Group"),
-                layout: &atlas_bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&atlas_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(&atlas_sampler),
-                    },
-                ],
-            });
+            let atlas_bind_group = Self::create_atlas_bind_group(
+                device,
+                &atlas_bind_group_layout,
+                &atlas_texture,
+                &color_atlas_texture,
+                &atlas_sampler,
+                &atlas_linear_sampler,
+            );
 
             // Pipeline layout (text): globals + atlas
             let text_pipeline_layout =
@@ -403,39 +813,53 @@ impl Renderer {
                     immediate_size: 0,
                 });
 
-            let text_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("Astra UI Text Pipeline"),
-                layout: Some(&text_pipeline_layout),
-                vertex: wgpu::VertexState {
-                    module: &text_shader,
-                    entry_point: Some("vs_main"),
-                    buffers: &[text::vertex::TextVertex::desc()],
-                    compilation_options: Default::default(),
-                },
-                fragment: Some(wgpu::FragmentState {
-                    module: &text_shader,
-                    entry_point: Some("fs_main"),
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: surface_format,
-                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                    compilation_options: Default::default(),
-                }),
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    strip_index_format: None,
-                    front_face: wgpu::FrontFace::Ccw,
-                    cull_mode: None,
-                    polygon_mode: wgpu::PolygonMode::Fill,
-                    unclipped_depth: false,
-                    conservative: false,
-                },
-                depth_stencil: None,
-                multisample: wgpu::MultisampleState::default(),
-                multiview_mask: None,
-                cache: None,
-            });
+            let create_text_pipeline =
+                |label: &str, fragment_entry: &str, blend: wgpu::BlendState| {
+                    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                        label: Some(label),
+                        layout: Some(&text_pipeline_layout),
+                        vertex: wgpu::VertexState {
+                            module: &text_shader,
+                            entry_point: Some("vs_main"),
+                            buffers: &[text::vertex::TextVertex::desc()],
+                            compilation_options: Default::default(),
+                        },
+                        fragment: Some(wgpu::FragmentState {
+                            module: &text_shader,
+                            entry_point: Some(fragment_entry),
+                            targets: &[Some(wgpu::ColorTargetState {
+                                format: surface_format,
+                                blend: Some(blend),
+                                write_mask: wgpu::ColorWrites::ALL,
+                            })],
+                            compilation_options: Default::default(),
+                        }),
+                        primitive: wgpu::PrimitiveState {
+                            topology: wgpu::PrimitiveTopology::TriangleList,
+                            strip_index_format: None,
+                            front_face: wgpu::FrontFace::Ccw,
+                            cull_mode: None,
+                            polygon_mode: wgpu::PolygonMode::Fill,
+                            unclipped_depth: false,
+                            conservative: false,
+                        },
+                        depth_stencil: None,
+                        multisample: wgpu::MultisampleState::default(),
+                        multiview_mask: None,
+                        cache: None,
+                    })
+                };
+
+            let text_pipeline = create_text_pipeline(
+                "Astra UI Text Pipeline",
+                "fs_main",
+                wgpu::BlendState::ALPHA_BLENDING,
+            );
+            let text_overdraw_pipeline = create_text_pipeline(
+                "Astra UI Text Overdraw Pipeline",
+                "fs_overdraw",
+                OVERDRAW_BLENDING,
+            );
 
             let text_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
                 label: Some("Astra UI Text Vertex Buffer"),
@@ -454,16 +878,25 @@ impl Renderer {
 
             let atlas =
                 text::atlas::GlyphAtlas::new(ATLAS_SIZE_PX, ATLAS_SIZE_PX, ATLAS_PADDING_PX);
+            let color_atlas = text::atlas::GlyphAtlas::new(
+                COLOR_ATLAS_SIZE_PX,
+                COLOR_ATLAS_SIZE_PX,
+                ATLAS_PADDING_PX,
+            );
 
             (
                 text_pipeline,
+                text_overdraw_pipeline,
                 text_vertex_buffer,
                 text_index_buffer,
                 atlas_texture,
                 atlas_bind_group,
                 atlas_bind_group_layout,
                 atlas_sampler,
+                atlas_linear_sampler,
                 atlas,
+                color_atlas_texture,
+                color_atlas,
             )
         };
 
@@ -471,7 +904,14 @@ impl Renderer {
             uniform_buffer,
             uniform_bind_group,
 
+            image_bind_group_layout,
+            image_sampler,
+            empty_image,
+            images: rustc_hash::FxHashMap::default(),
+            next_texture_id: 0,
+
             sdf_pipeline,
+            sdf_overdraw_pipeline,
             sdf_instance_buffer,
             sdf_instance_capacity: INITIAL_SDF_INSTANCE_CAPACITY,
             sdf_instances: Vec::new(),
@@ -480,9 +920,18 @@ impl Renderer {
             sdf_quad_index_buffer,
             last_frame_sdf_instance_count: 0,
 
+            surface_format,
+            filter_pipeline,
+            filter_instance_buffer,
+            filter_instance_capacity: INITIAL_FILTER_INSTANCE_CAPACITY,
+            filter_instances: Vec::new(),
+            filter_texture: None,
+
             #[cfg(feature = "text-cosmic")]
             text_pipeline,
             #[cfg(feature = "text-cosmic")]
+            text_overdraw_pipeline,
+            #[cfg(feature = "text-cosmic")]
             text_vertex_buffer,
             #[cfg(feature = "text-cosmic")]
             text_index_buffer,
@@ -509,16 +958,36 @@ impl Renderer {
             #[cfg(feature = "text-cosmic")]
             atlas_sampler,
             #[cfg(feature = "text-cosmic")]
+            atlas_linear_sampler,
+            #[cfg(feature = "text-cosmic")]
             atlas,
             #[cfg(feature = "text-cosmic")]
+            glyph_render_mode: GlyphRenderMode::Bitmap,
+            #[cfg(feature = "text-cosmic")]
+            glyph_antialiasing: GlyphAntialiasing::Grayscale,
+            #[cfg(feature = "text-cosmic")]
+            color_atlas_texture,
+            #[cfg(feature = "text-cosmic")]
+            color_atlas,
+            #[cfg(feature = "text-cosmic")]
             text_engine: gui_text::Engine::new_default(),
             #[cfg(feature = "text-cosmic")]
-            shape_cache: std::collections::HashMap::new(),
+            shape_cache: rustc_hash::FxHashMap::default(),
+            #[cfg(feature = "text-cosmic")]
+            shape_cache_generation: 0,
+            #[cfg(feature = "text-cosmic")]
+            text_shaping_mode: TextShapingMode::Immediate,
+            #[cfg(feature = "text-cosmic")]
+            background_shaper: None,
+            #[cfg(feature = "text-cosmic")]
+            shape_placeholders: rustc_hash::FxHashMap::default(),
             #[cfg(feature = "text-cosmic")]
             glyph_metrics_cache: std::collections::HashMap::new(),
             #[cfg(feature = "text-cosmic")]
             atlas_needs_resize: false,
             #[cfg(feature = "text-cosmic")]
+            color_atlas_needs_resize: false,
+            #[cfg(feature = "text-cosmic")]
             avg_glyph_size_estimate_px: 32, // Conservative initial estimate
             #[cfg(feature = "text-cosmic")]
             max_texture_dimension_2d: device.limits().max_texture_dimension_2d,
@@ -533,6 +1002,309 @@ impl Renderer {
         &mut self.text_engine
     }
 
+    /// How glyphs are rasterized and drawn
+    #[cfg(feature = "text-cosmic")]
+    pub fn glyph_render_mode(&self) -> GlyphRenderMode {
+        self.glyph_render_mode
+    }
+
+    /// Switch between bitmap and distance field glyphs
+    ///
+    /// Use [`GlyphRenderMode::Sdf`] for UIs that zoom or scale text freely. Glyphs
+    /// are cached per mode, so switching back and forth doesn't re-rasterize.
+    #[cfg(feature = "text-cosmic")]
+    pub fn set_glyph_render_mode(&mut self, mode: GlyphRenderMode) {
+        self.glyph_render_mode = mode;
+    }
+
+    /// How bitmap glyph edges are antialiased
+    #[cfg(feature = "text-cosmic")]
+    pub fn glyph_antialiasing(&self) -> GlyphAntialiasing {
+        self.glyph_antialiasing
+    }
+
+    /// Switch between grayscale and LCD (RGB subpixel) antialiasing of bitmap glyphs
+    ///
+    /// [`GlyphAntialiasing::Lcd`] sharpens small text on horizontal-RGB panels but
+    /// shows color fringes elsewhere (BGR or rotated panels, screenshots, projectors).
+    /// Distance field glyphs ([`GlyphRenderMode::Sdf`]) are always grayscale.
+    #[cfg(feature = "text-cosmic")]
+    pub fn set_glyph_antialiasing(&mut self, antialiasing: GlyphAntialiasing) {
+        self.glyph_antialiasing = antialiasing;
+    }
+
+    /// When text missing from the shaping cache is shaped
+    #[cfg(feature = "text-cosmic")]
+    pub fn text_shaping_mode(&self) -> TextShapingMode {
+        self.text_shaping_mode
+    }
+
+    /// Shape long texts on a worker thread instead of stalling the frame
+    ///
+    /// ```ignore
+    /// renderer.set_text_shaping_mode(TextShapingMode::Background { min_bytes: 4096 });
+    /// ```
+    ///
+    /// The worker copies the text engine's fonts when it starts; call
+    /// [`Renderer::clear_text_caches`] after loading fonts so it restarts with them.
+    #[cfg(feature = "text-cosmic")]
+    pub fn set_text_shaping_mode(&mut self, mode: TextShapingMode) {
+        self.text_shaping_mode = mode;
+    }
+
+    /// Whether texts are still being shaped in the background; redraw until
+    /// this is false so their layouts replace the placeholders
+    #[cfg(feature = "text-cosmic")]
+    pub fn has_pending_text_shaping(&self) -> bool {
+        self.background_shaper
+            .as_ref()
+            .is_some_and(|shaper| shaper.is_pending())
+    }
+
+    /// Drop shaped text and cached text measurements
+    ///
+    /// Shapes not drawn for a couple of seconds are evicted on their own, but
+    /// measurements are kept; call this after `UiContext::set_text_scale`
+    /// changes to release both at once. Rasterized glyphs stay in the atlas. The
+    /// background shaping worker is restarted with the text engine's current fonts.
+    #[cfg(feature = "text-cosmic")]
+    pub fn clear_text_caches(&mut self) {
+        self.shape_cache.clear();
+        self.text_engine.clear_measurement_cache();
+        self.background_shaper = None;
+        self.shape_placeholders.clear();
+    }
+
+    /// RGBA texture backing the color glyph atlas
+    #[cfg(feature = "text-cosmic")]
+    fn create_color_atlas_texture(device: &wgpu::Device, size_px: u32) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Astra UI Color Glyph Atlas"),
+            size: wgpu::Extent3d {
+                width: size_px,
+                height: size_px,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            // Color glyphs are rasterized as sRGB; the view decodes them to linear
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        })
+    }
+
+    /// Upload an RGBA image (8 bits per channel, sRGB, rows top to bottom) for
+    /// nine-slice backgrounds (`Style::nine_slice`)
+    ///
+    /// Returns the handle a `NineSliceImage` refers to. Rects using a texture
+    /// that isn't registered draw no background.
+    pub fn register_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        size: [u32; 2],
+        rgba: &[u8],
+    ) -> TextureId {
+        let [width, height] = [size[0].max(1), size[1].max(1)];
+        let image = Self::create_image_texture(
+            device,
+            &self.image_bind_group_layout,
+            &self.image_sampler,
+            [width, height],
+        );
+        if rgba.len() >= (width * height * 4) as usize {
+            queue.write_texture(
+                image.texture.as_image_copy(),
+                rgba,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(width * 4),
+                    rows_per_image: Some(height),
+                },
+                wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        let id = TextureId(self.next_texture_id);
+        self.next_texture_id += 1;
+        self.images.insert(id, image);
+        id
+    }
+
+    /// Free a texture registered with [`Renderer::register_texture`]
+    pub fn unregister_texture(&mut self, id: TextureId) {
+        self.images.remove(&id);
+    }
+
+    fn create_image_texture(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        [width, height]: [u32; 2],
+    ) -> ImageTexture {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Astra UI Image Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Astra UI Image Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+        ImageTexture {
+            texture,
+            bind_group,
+        }
+    }
+
+    /// Bind group exposing both glyph atlases and their shared sampler to the text shader
+    #[cfg(feature = "text-cosmic")]
+    fn create_atlas_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        atlas_texture: &wgpu::Texture,
+        color_atlas_texture: &wgpu::Texture,
+        sampler: &wgpu::Sampler,
+        linear_sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        let atlas_view = atlas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let color_atlas_view =
+            color_atlas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Astra UI Text Atlas Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&color_atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(linear_sampler),
+                },
+            ],
+        })
+    }
+
+    /// Double the color atlas (up to the GPU limit) and drop its glyphs
+    ///
+    /// Unlike the mask atlas, color glyphs are not re-uploaded eagerly: they
+    /// are evicted from the metrics cache and re-rasterized on next use.
+    #[cfg(feature = "text-cosmic")]
+    fn resize_color_atlas(&mut self, device: &wgpu::Device) {
+        self.color_atlas_needs_resize = false;
+
+        let (old_width, old_height) = self.color_atlas.dimensions();
+        let new_size = (old_width.max(old_height) * 2)
+            .next_power_of_two()
+            .min(self.max_texture_dimension_2d);
+        if new_size == old_width && new_size == old_height {
+            // Already at the limit: start over so the glyphs on screen now get a slot
+            self.color_atlas.clear();
+        } else {
+            self.color_atlas_texture = Self::create_color_atlas_texture(device, new_size);
+            self.color_atlas.resize_to(new_size, new_size);
+            self.atlas_bind_group = Self::create_atlas_bind_group(
+                device,
+                &self.atlas_bind_group_layout,
+                &self.atlas_texture,
+                &self.color_atlas_texture,
+                &self.atlas_sampler,
+                &self.atlas_linear_sampler,
+            );
+        }
+
+        self.glyph_metrics_cache
+            .retain(|_, (_bearing, _size, _placed, format)| !in_color_atlas(*format));
+    }
+
+    /// Upload the opaque block behind `SOLID_GLYPH_KEY` at its atlas placement
+    #[cfg(feature = "text-cosmic")]
+    fn upload_solid_glyph(
+        queue: &wgpu::Queue,
+        atlas_texture: &wgpu::Texture,
+        placed: text::atlas::PlacedGlyph,
+    ) {
+        let rect_px = placed.rect_px;
+        let pad = placed.padding_px;
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: atlas_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: rect_px.min.x + pad,
+                    y: rect_px.min.y + pad,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &[255; (SOLID_GLYPH_SIZE_PX * SOLID_GLYPH_SIZE_PX) as usize],
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(SOLID_GLYPH_SIZE_PX),
+                rows_per_image: Some(SOLID_GLYPH_SIZE_PX),
+            },
+            wgpu::Extent3d {
+                width: SOLID_GLYPH_SIZE_PX,
+                height: SOLID_GLYPH_SIZE_PX,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Atlas placement of the solid block, inserting it on first use
+    #[cfg(feature = "text-cosmic")]
+    fn solid_glyph(&mut self, queue: &wgpu::Queue) -> Option<text::atlas::PlacedGlyph> {
+        match self
+            .atlas
+            .insert(SOLID_GLYPH_KEY, [SOLID_GLYPH_SIZE_PX, SOLID_GLYPH_SIZE_PX])
+        {
+            text::atlas::AtlasInsert::AlreadyPresent => self.atlas.get(&SOLID_GLYPH_KEY),
+            text::atlas::AtlasInsert::Placed(placed) => {
+                Self::upload_solid_glyph(queue, &self.atlas_texture, placed);
+                Some(placed)
+            }
+            text::atlas::AtlasInsert::Full => {
+                self.atlas_needs_resize = true;
+                None
+            }
+        }
+    }
+
     #[cfg(feature = "text-cosmic")]
     fn resize_atlas(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
         // Collect all cached glyphs before resize (we need to preserve them)
@@ -632,16 +1404,21 @@ impl Renderer {
                 continue;
             };
 
+            if *key == SOLID_GLYPH_KEY {
+                Self::upload_solid_glyph(queue, &self.atlas_texture, new_placed);
+                continue;
+            }
+
             // Convert atlas key back to text engine key for rasterization
-            let text_key = gui_text::GlyphKey::new(
-                gui_text::FontId(key.font_id),
-                key.glyph_id,
-                key.font_px,
-                key.variant as i16,
-            );
+            let text_key = text_glyph_key(key);
 
-            // Re-rasterize the glyph
-            let Some(bitmap) = self.text_engine.rasterize_glyph(text_key) else {
+            // Re-rasterize the glyph (distance field entries have no size of their own)
+            let bitmap = if key.font_px == SDF_GLYPH_PX {
+                self.text_engine.rasterize_glyph_sdf(text_key)
+            } else {
+                self.text_engine.rasterize_glyph(text_key)
+            };
+            let Some(bitmap) = bitmap else {
                 continue;
             };
 
@@ -680,7 +1457,10 @@ impl Renderer {
         // Update metrics cache with new placements
         // (Keep bearing and size, update placement)
         let mut updated_cache = std::mem::take(&mut self.glyph_metrics_cache);
-        for (atlas_key, (_bearing, _size, old_placed)) in updated_cache.iter_mut() {
+        for (atlas_key, (_bearing, _size, old_placed, format)) in updated_cache.iter_mut() {
+            if in_color_atlas(*format) {
+                continue;
+            }
             if let Some(new_placed) = self.atlas.get(atlas_key) {
                 *old_placed = new_placed;
             }
@@ -688,28 +1468,19 @@ impl Renderer {
         self.glyph_metrics_cache = updated_cache;
 
         // Recreate bind group with new texture
-        let atlas_view = self
-            .atlas_texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-        self.atlas_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Astra UI Atlas Bind Group"),
-            layout: &self.atlas_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&atlas_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.atlas_sampler),
-                },
-            ],
-        });
+        self.atlas_bind_group = Self::create_atlas_bind_group(
+            device,
+            &self.atlas_bind_group_layout,
+            &self.atlas_texture,
+            &self.color_atlas_texture,
+            &self.atlas_sampler,
+            &self.atlas_linear_sampler,
+        );
 
         self.atlas_needs_resize = false;
     }
 
-    /// Group shapes into rendering layers by z-index, with shapes separated by type within each layer.
+    /// Group shapes into rendering layers by z-index and color filter, with shapes separated by type within each layer.
     fn group_into_layers<'a>(shapes: &'a [astra_gui::ClippedShape]) -> Vec<RenderLayer<'a>> {
         if shapes.is_empty() {
             return Vec::new();
@@ -717,17 +1488,20 @@ impl Renderer {
 
         let mut layers: Vec<RenderLayer> = Vec::new();
         let mut current_z_index = shapes[0].z_index;
+        let mut current_filter = shapes[0].filter;
         let mut current_shapes = Vec::new();
 
         for shape in shapes {
-            if shape.z_index != current_z_index {
+            if shape.z_index != current_z_index || shape.filter != current_filter {
                 // Save current layer and start new one
                 layers.push(RenderLayer {
                     z_index: current_z_index,
+                    filter: current_filter,
                     shapes: current_shapes,
                 });
                 current_shapes = Vec::new();
                 current_z_index = shape.z_index;
+                current_filter = shape.filter;
             }
 
             current_shapes.push(shape);
@@ -737,6 +1511,7 @@ impl Renderer {
         if !current_shapes.is_empty() {
             layers.push(RenderLayer {
                 z_index: current_z_index,
+                filter: current_filter,
                 shapes: current_shapes,
             });
         }
@@ -744,6 +1519,217 @@ impl Renderer {
         layers
     }
 
+    /// Begin a render pass drawing UI into `view`
+    fn begin_pass<'a>(
+        encoder: &'a mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Astra UI Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        })
+    }
+
+    /// Record the draw commands of `layers`, in order, into a render pass
+    fn draw_layers(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        layers: &[LayerDrawCommands],
+        text_draws: &[ClippedDraw],
+        show_overdraw: bool,
+    ) {
+        let sdf_pipeline = if show_overdraw {
+            &self.sdf_overdraw_pipeline
+        } else {
+            &self.sdf_pipeline
+        };
+        #[cfg(feature = "text-cosmic")]
+        let text_pipeline = if show_overdraw {
+            &self.text_overdraw_pipeline
+        } else {
+            &self.text_pipeline
+        };
+
+        // Track current pipeline state to avoid redundant switches
+        #[derive(PartialEq)]
+        enum PipelineState {
+            None,
+            Sdf,
+            Text,
+        }
+        let mut current_pipeline = PipelineState::None;
+        // Texture bound for the SDF pipeline (reset when text rebinds group 1)
+        let mut current_texture = None;
+
+        for command in layers.iter().flat_map(|layer| &layer.commands) {
+            match command {
+                DrawCommand::Sdf(idx) => {
+                    let draw = &self.sdf_draws[*idx];
+
+                    if current_pipeline != PipelineState::Sdf {
+                        render_pass.set_pipeline(sdf_pipeline);
+                        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                        render_pass.set_vertex_buffer(0, self.sdf_quad_vertex_buffer.slice(..));
+                        render_pass.set_vertex_buffer(1, self.sdf_instance_buffer.slice(..));
+                        render_pass.set_index_buffer(
+                            self.sdf_quad_index_buffer.slice(..),
+                            wgpu::IndexFormat::Uint32,
+                        );
+                        current_pipeline = PipelineState::Sdf;
+                        current_texture = None;
+                    }
+
+                    if current_texture != Some(draw.texture) {
+                        let image = draw
+                            .texture
+                            .and_then(|id| self.images.get(&id))
+                            .unwrap_or(&self.empty_image);
+                        render_pass.set_bind_group(1, &image.bind_group, &[]);
+                        current_texture = Some(draw.texture);
+                    }
+
+                    let (x, y, w, h) = draw.scissor;
+                    render_pass.set_scissor_rect(x, y, w, h);
+                    render_pass.draw_indexed(
+                        0..6,
+                        0,
+                        draw.instance_start..(draw.instance_start + draw.instance_count),
+                    );
+                }
+                DrawCommand::Text(idx) => {
+                    #[cfg(feature = "text-cosmic")]
+                    {
+                        let draw = &text_draws[*idx];
+
+                        if current_pipeline != PipelineState::Text {
+                            render_pass.set_pipeline(text_pipeline);
+                            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                            render_pass.set_bind_group(1, &self.atlas_bind_group, &[]);
+                            render_pass.set_vertex_buffer(0, self.text_vertex_buffer.slice(..));
+                            render_pass.set_index_buffer(
+                                self.text_index_buffer.slice(..),
+                                wgpu::IndexFormat::Uint32,
+                            );
+                            current_pipeline = PipelineState::Text;
+                        }
+
+                        let (x, y, w, h) = draw.scissor;
+                        render_pass.set_scissor_rect(x, y, w, h);
+                        render_pass.draw_indexed(draw.index_start..draw.index_end, 0, 0..1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Union of the scissor rects of the draw commands of `layers`
+    fn draw_bounds(
+        &self,
+        layers: &[LayerDrawCommands],
+        text_draws: &[ClippedDraw],
+    ) -> Option<(u32, u32, u32, u32)> {
+        layers
+            .iter()
+            .flat_map(|layer| &layer.commands)
+            .map(|command| match command {
+                DrawCommand::Sdf(idx) => self.sdf_draws[*idx].scissor,
+                DrawCommand::Text(idx) => text_draws[*idx].scissor,
+            })
+            .map(|(x, y, w, h)| (x, y, x + w, y + h))
+            .reduce(|(min_x, min_y, max_x, max_y), (x0, y0, x1, y1)| {
+                (min_x.min(x0), min_y.min(y0), max_x.max(x1), max_y.max(y1))
+            })
+            .map(|(min_x, min_y, max_x, max_y)| (min_x, min_y, max_x - min_x, max_y - min_y))
+    }
+
+    /// (Re)create the offscreen filter layer when the target size changes
+    fn ensure_filter_texture(&mut self, device: &wgpu::Device, size: [u32; 2]) {
+        if self
+            .filter_texture
+            .as_ref()
+            .is_some_and(|filter_texture| filter_texture.size == size)
+        {
+            return;
+        }
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Astra UI Filter Layer Texture"),
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Astra UI Filter Layer Bind Group"),
+            layout: &self.image_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.image_sampler),
+                },
+            ],
+        });
+        self.filter_texture = Some(FilterTexture {
+            size,
+            view,
+            bind_group,
+        });
+    }
+
+    /// Render a `FullOutput` serialized with `FullOutput::to_bytes`
+    ///
+    /// Entry point for thin clients that draw a UI built in another process
+    /// or on another machine. Fails without rendering if the payload cannot be
+    /// decoded (e.g. it was written by a newer schema version).
+    #[cfg(feature = "remote")]
+    pub fn render_serialized(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        screen_width: f32,
+        screen_height: f32,
+        bytes: &[u8],
+    ) -> Result<(), astra_gui::OutputDecodeError> {
+        let output = FullOutput::from_bytes(bytes)?;
+        self.render(
+            device,
+            queue,
+            encoder,
+            target,
+            screen_width,
+            screen_height,
+            &output,
+        );
+        Ok(())
+    }
+
     pub fn render(
         &mut self,
         device: &wgpu::Device,
@@ -759,6 +1745,10 @@ impl Renderer {
         if self.atlas_needs_resize {
             self.resize_atlas(device, queue);
         }
+        #[cfg(feature = "text-cosmic")]
+        if self.color_atlas_needs_resize {
+            self.resize_color_atlas(device);
+        }
 
         // STAGE 1: Proactive estimation
         #[cfg(feature = "text-cosmic")]
@@ -807,6 +1797,33 @@ impl Renderer {
             .reserve(self.last_frame_sdf_instance_count);
         self.sdf_draws.clear();
 
+        // Layouts shaped in the background since the last frame, then evict
+        // shapes that haven't been drawn for a while
+        #[cfg(feature = "text-cosmic")]
+        {
+            self.shape_cache_generation += 1;
+            let generation = self.shape_cache_generation;
+            if let Some(shaper) = &mut self.background_shaper {
+                for ((key, text_hash), shaped) in shaper.finished() {
+                    self.shape_placeholders.insert(text_hash, key);
+                    self.shape_cache.insert(
+                        key,
+                        CachedShape {
+                            shaped: std::sync::Arc::new(shaped),
+                            last_used: generation,
+                        },
+                    );
+                }
+            }
+            if generation.is_multiple_of(SHAPE_CACHE_MAX_AGE) {
+                self.shape_cache
+                    .retain(|_, cached| generation - cached.last_used <= SHAPE_CACHE_MAX_AGE);
+                let shape_cache = &self.shape_cache;
+                self.shape_placeholders
+                    .retain(|_, key| shape_cache.contains_key(key));
+            }
+        }
+
         // Text buffers
         self.text_vertices.clear();
         self.text_vertices
@@ -818,13 +1835,7 @@ impl Renderer {
         let mut text_draws: Vec<ClippedDraw> = Vec::with_capacity(self.last_frame_text_draw_count);
 
         // Track draw commands for each layer to enable interleaved rendering
-        #[derive(Debug, Clone, Copy, PartialEq)]
-        enum DrawCommand {
-            Sdf(usize),  // Index into sdf_draws (handles both rects and triangles)
-            Text(usize), // Index into text_draws
-        }
-
-        let mut layer_draw_commands: Vec<Vec<DrawCommand>> = Vec::with_capacity(layers.len());
+        let mut layer_draw_commands: Vec<LayerDrawCommands> = Vec::with_capacity(layers.len());
 
         // Collect debug rectangles for text line bounds
         // (rect, color, stroke, clip_rect, transform)
@@ -852,7 +1863,18 @@ impl Renderer {
                             let scissor = (sc_min_x as u32, sc_min_y as u32, sc_w, sc_h);
                             let instance_index = self.sdf_instances.len() as u32;
 
-                            self.sdf_instances.push(RectInstance::from(*clipped));
+                            // Nine-slice backgrounds bind their texture for the draw
+                            let texture = match &clipped.shape {
+                                Shape::Rect(StyledRect {
+                                    nine_slice: Some(image),
+                                    ..
+                                }) => Some(image.texture),
+                                _ => None,
+                            };
+
+                            // Gradient fills and nine-slices expand to several instances
+                            RectInstance::push_rect(clipped, &mut self.sdf_instances);
+                            let instance_count = self.sdf_instances.len() as u32 - instance_index;
 
                             // Try to batch with previous draw if same scissor
                             // IMPORTANT: Only batch if the previous command was also SDF and from this layer
@@ -867,17 +1889,19 @@ impl Renderer {
                             if can_batch {
                                 if let Some(last_draw) = self.sdf_draws.last_mut() {
                                     if last_draw.scissor == scissor
+                                        && last_draw.texture == texture
                                         && last_draw.instance_start + last_draw.instance_count
                                             == instance_index
                                     {
                                         // Extend existing batch
-                                        last_draw.instance_count += 1;
+                                        last_draw.instance_count += instance_count;
                                     } else {
-                                        // Start new batch (different scissor or non-consecutive)
+                                        // Start new batch (different scissor, texture or non-consecutive)
                                         self.sdf_draws.push(SdfDraw {
                                             scissor,
                                             instance_start: instance_index,
-                                            instance_count: 1,
+                                            instance_count,
+                                            texture,
                                         });
                                         current_layer_commands
                                             .push(DrawCommand::Sdf(self.sdf_draws.len() - 1));
@@ -888,7 +1912,8 @@ impl Renderer {
                                 self.sdf_draws.push(SdfDraw {
                                     scissor,
                                     instance_start: instance_index,
-                                    instance_count: 1,
+                                    instance_count,
+                                    texture,
                                 });
                                 current_layer_commands
                                     .push(DrawCommand::Sdf(self.sdf_draws.len() - 1));
@@ -936,6 +1961,7 @@ impl Renderer {
                                             scissor,
                                             instance_start: instance_index,
                                             instance_count: 1,
+                                            texture: None,
                                         });
                                         current_layer_commands
                                             .push(DrawCommand::Sdf(self.sdf_draws.len() - 1));
@@ -947,6 +1973,7 @@ impl Renderer {
                                     scissor,
                                     instance_start: instance_index,
                                     instance_count: 1,
+                                    texture: None,
                                 });
                                 current_layer_commands
                                     .push(DrawCommand::Sdf(self.sdf_draws.len() - 1));
@@ -991,38 +2018,82 @@ impl Renderer {
                                 .try_resolve_with_scale(width, 1.0)
                                 .unwrap_or(16.0);
 
-                            // Create cache key from text + font size + rect dimensions + wrap + line height + weight + style
-                            let cache_key = (
-                                text.to_string(),
-                                font_size_px as u32,
-                                (rect.max[0] - rect.min[0]) as u32,
-                                (rect.max[1] - rect.min[1]) as u32,
-                                text_shape.wrap,
-                                (text_shape.line_height_multiplier * 100.0) as u32,
-                                text_shape.font_weight.to_weight(),
-                                text_shape.font_style,
-                            );
+                            let cache_key = shape_cache_key(text_shape, font_size_px);
+                            let generation = self.shape_cache_generation;
 
-                            let shaped = if let Some(cached) = self.shape_cache.get(&cache_key) {
+                            let shaped = if let Some(cached) = self.shape_cache.get_mut(&cache_key)
+                            {
                                 // Cache hit - reuse shaped text
-                                cached.clone()
+                                cached.last_used = generation;
+                                cached.shaped.clone()
                             } else {
                                 // Cache miss - shape the text
-                                let (shaped_text, _placement) =
-                                    self.text_engine.shape_text(gui_text::ShapeTextRequest {
-                                        text,
-                                        rect,
-                                        font_px: font_size_px,
-                                        h_align: text_shape.h_align,
-                                        v_align: text_shape.v_align,
-                                        family: None,
-                                        wrap: text_shape.wrap,
-                                        line_height_multiplier: text_shape.line_height_multiplier,
-                                        font_weight: text_shape.font_weight.to_weight(),
-                                        font_style: text_shape.font_style,
-                                    });
-                                self.shape_cache.insert(cache_key, shaped_text.clone());
-                                shaped_text
+                                let request = gui_text::ShapeTextRequest {
+                                    text,
+                                    spans: &text_shape.spans,
+                                    rect,
+                                    font_px: font_size_px,
+                                    h_align: text_shape.h_align,
+                                    v_align: text_shape.v_align,
+                                    family: text_shape.font_family.as_deref(),
+                                    wrap: text_shape.wrap,
+                                    overflow: text_shape.overflow,
+                                    max_lines: text_shape.max_lines,
+                                    line_height_multiplier: text_shape.line_height_multiplier,
+                                    font_weight: text_shape.font_weight.to_weight(),
+                                    font_style: text_shape.font_style,
+                                    font_features: &text_shape.font_features,
+                                    font_width: text_shape.font_width,
+                                    tab_width: text_shape.tab_width,
+                                };
+
+                                // Long texts go to the worker; draw the previous layout
+                                // of the same text (or nothing) until it lands
+                                let background = match self.text_shaping_mode {
+                                    TextShapingMode::Background { min_bytes } => {
+                                        text.len() >= min_bytes
+                                    }
+                                    TextShapingMode::Immediate => false,
+                                };
+                                let shaper = if background {
+                                    let text_engine = &self.text_engine;
+                                    Some(self.background_shaper.get_or_insert_with(|| {
+                                        text::shaper::BackgroundShaper::spawn(text_engine.fork())
+                                    }))
+                                    .filter(|shaper| shaper.is_running())
+                                } else {
+                                    None
+                                };
+                                if let Some(shaper) = shaper {
+                                    let text_hash = text_hash(text);
+                                    shaper.submit(
+                                        (cache_key, text_hash),
+                                        text::shaper::ShapeJob::new(&request),
+                                    );
+                                    let placeholder = self
+                                        .shape_placeholders
+                                        .get(&text_hash)
+                                        .and_then(|key| self.shape_cache.get_mut(key));
+                                    match placeholder {
+                                        Some(placeholder) => {
+                                            placeholder.last_used = generation;
+                                            placeholder.shaped.clone()
+                                        }
+                                        None => continue,
+                                    }
+                                } else {
+                                    let (shaped_text, _placement) =
+                                        self.text_engine.shape_text(request);
+                                    let shaped_text = std::sync::Arc::new(shaped_text);
+                                    self.shape_cache.insert(
+                                        cache_key,
+                                        CachedShape {
+                                            shaped: shaped_text.clone(),
+                                            last_used: generation,
+                                        },
+                                    );
+                                    shaped_text
+                                }
                             };
 
                             // Always recalculate placement for this specific rect position
@@ -1046,6 +2117,81 @@ impl Renderer {
                             };
                             let has_rotation = rotation.abs() > 0.0001;
 
+                            // Apply full transform (translation + rotation) to the glyph and decoration quad vertices
+                            let translation = clipped.transform.translation;
+                            let transform_origin =
+                                if let Some(abs_origin) = clipped.transform.absolute_origin {
+                                    abs_origin
+                                } else {
+                                    // Fallback: resolve origin relative to the node rect
+                                    let node_width =
+                                        clipped.node_rect.max[0] - clipped.node_rect.min[0];
+                                    let node_height =
+                                        clipped.node_rect.max[1] - clipped.node_rect.min[1];
+                                    let (origin_x, origin_y) =
+                                        clipped.transform.origin.resolve(node_width, node_height);
+                                    [
+                                        clipped.node_rect.min[0] + origin_x,
+                                        clipped.node_rect.min[1] + origin_y,
+                                    ]
+                                };
+
+                            // Helper to apply translation first, then rotation around the transform origin
+                            // Uses pre-calculated cos_r and sin_r from outside the loop
+                            let apply_transform = |pos: [f32; 2]| -> [f32; 2] {
+                                // 1. Apply translation first
+                                let mut x = pos[0] + translation.x;
+                                let mut y = pos[1] + translation.y;
+
+                                // 2. Apply rotation if present (use pre-calculated trig values)
+                                if has_rotation {
+                                    // Translate to origin
+                                    x -= transform_origin[0];
+                                    y -= transform_origin[1];
+
+                                    // Rotate (clockwise positive) - uses pre-calculated cos_r and sin_r
+                                    let rx = x * cos_r + y * sin_r;
+                                    let ry = -x * sin_r + y * cos_r;
+
+                                    x = rx;
+                                    y = ry;
+
+                                    // Translate back from origin
+                                    x += transform_origin[0];
+                                    y += transform_origin[1];
+                                }
+
+                                [x, y]
+                            };
+
+                            // Outlines go under every glyph of the shape, so their quads'
+                            // indices are collected here and put before the glyphs' at the end.
+                            // Bitmap glyphs are stamped around a ring, distance fields thresholded
+                            // further out.
+                            let outline = text_shape
+                                .outline
+                                .map(|outline| {
+                                    let width = outline
+                                        .width
+                                        .try_resolve_with_scale(rect.max[0] - rect.min[0], 1.0)
+                                        .unwrap_or(0.0);
+                                    let color = outline.color;
+                                    (
+                                        [color.r, color.g, color.b, color.a * clipped.opacity],
+                                        width,
+                                    )
+                                })
+                                .filter(|&(color, width)| color[3] > 0.0 && width > 0.0);
+                            let outline_stamps = match outline {
+                                Some((_, width))
+                                    if self.glyph_render_mode == GlyphRenderMode::Bitmap =>
+                                {
+                                    outline_offsets(width)
+                                }
+                                _ => vec![[0.0, 0.0]],
+                            };
+                            let mut outline_indices = Vec::new();
+
                             // Render all lines
                             let mut current_y = origin_y;
                             for line in &shaped.lines {
@@ -1060,40 +2206,84 @@ impl Renderer {
                                     HorizontalAlign::Right => rect.max[0] - line.metrics.width_px,
                                 };
 
+                                // Opacity of the fade-out (`TextOverflow::Fade`) at a screen x,
+                                // applied per vertex so it ramps smoothly across glyph quads
+                                let fade_alpha = |x: f32| match line.fade_out_px {
+                                    Some([start, end]) => ((line_x + end - x)
+                                        / (end - start).max(f32::EPSILON))
+                                    .clamp(0.0, 1.0),
+                                    None => 1.0,
+                                };
+
+                                // Distance field glyphs are stored at the reference size and
+                                // scaled to the drawn size here
+                                let sdf = self.glyph_render_mode == GlyphRenderMode::Sdf;
                                 for g in &line.glyphs {
+                                    // Unrotated bitmap glyphs snap to the pixel grid in screen
+                                    // space, keeping the fraction as a subpixel bin; rotated
+                                    // ones keep the shaped bin and are filtered instead
+                                    let mut key = g.key;
+                                    let mut glyph_x = line_x + g.x_px;
+                                    let mut glyph_y = current_y + g.y_px;
+                                    if sdf {
+                                        glyph_x += key.subpixel_x();
+                                    } else {
+                                        key = key.with_antialiasing(self.glyph_antialiasing);
+                                        if !has_rotation {
+                                            let (snapped, x) = key
+                                                .snap_x(glyph_x + key.subpixel_x() + translation.x);
+                                            key = snapped;
+                                            glyph_x = x - translation.x;
+                                            glyph_y =
+                                                (glyph_y + translation.y).round() - translation.y;
+                                        }
+                                    }
+
                                     // Map glyph key to atlas key
-                                    let atlas_key = text::atlas::GlyphKey::new(
-                                        g.key.font_id.0,
-                                        g.key.glyph_id,
-                                        g.key.px_size,
-                                        g.key.subpixel_x_64 as u16,
+                                    let atlas_key = atlas_glyph_key(
+                                        key,
+                                        if sdf { SDF_GLYPH_PX } else { key.px_size },
                                     );
 
                                     // OPTIMIZATION: Check metrics cache first (includes placement)
-                                    let (glyph_bearing, glyph_size, placed) = if let Some(&(
-                                        bearing,
-                                        size,
-                                        placement,
-                                    )) =
+                                    let (glyph_bearing, glyph_size, placed, format) = if let Some(
+                                        &(bearing, size, placement, format),
+                                    ) =
                                         self.glyph_metrics_cache.get(&atlas_key)
                                     {
                                         // Cache hit - use cached metrics and placement (no atlas lookup!)
-                                        (bearing, size, placement)
+                                        (bearing, size, placement, format)
                                     } else {
                                         // Cache miss - need to rasterize and upload
-                                        let Some(bitmap) = self.text_engine.rasterize_glyph(g.key)
-                                        else {
+                                        let bitmap = if sdf {
+                                            self.text_engine.rasterize_glyph_sdf(key)
+                                        } else {
+                                            self.text_engine.rasterize_glyph(key)
+                                        };
+                                        let Some(mut bitmap) = bitmap else {
                                             continue;
                                         };
 
+                                        // Color glyphs (emoji) and LCD masks go to the RGBA atlas,
+                                        // everything else to the single channel atlas
+                                        let format = bitmap.format;
+                                        let is_color = in_color_atlas(format);
+                                        if format == gui_text::GlyphFormat::Lcd {
+                                            encode_lcd_coverage(&mut bitmap.pixels);
+                                        }
+                                        let (atlas, atlas_texture, bytes_per_px) = if is_color {
+                                            (&mut self.color_atlas, &self.color_atlas_texture, 4)
+                                        } else {
+                                            (&mut self.atlas, &self.atlas_texture, 1)
+                                        };
+
                                         // Insert into atlas
-                                        let placed = match self
-                                            .atlas
+                                        let placed = match atlas
                                             .insert(atlas_key.clone(), bitmap.size_px)
                                         {
                                             text::atlas::AtlasInsert::AlreadyPresent => {
                                                 // Already in atlas, get placement
-                                                self.atlas.get(&atlas_key)
+                                                atlas.get(&atlas_key)
                                             }
                                             text::atlas::AtlasInsert::Placed(p) => {
                                                 // Newly placed - upload texture
@@ -1102,7 +2292,7 @@ impl Renderer {
                                                 let pad = p.padding_px;
                                                 queue.write_texture(
                                                     wgpu::TexelCopyTextureInfo {
-                                                        texture: &self.atlas_texture,
+                                                        texture: atlas_texture,
                                                         mip_level: 0,
                                                         origin: wgpu::Origin3d {
                                                             x: rect_px.min.x + pad,
@@ -1114,7 +2304,9 @@ impl Renderer {
                                                     &bitmap.pixels,
                                                     wgpu::TexelCopyBufferLayout {
                                                         offset: 0,
-                                                        bytes_per_row: Some(bitmap.size_px[0]),
+                                                        bytes_per_row: Some(
+                                                            bitmap.size_px[0] * bytes_per_px,
+                                                        ),
                                                         rows_per_image: Some(bitmap.size_px[1]),
                                                     },
                                                     wgpu::Extent3d {
@@ -1143,7 +2335,11 @@ impl Renderer {
                                 );
 
                                                 // Mark for resize before next frame
-                                                self.atlas_needs_resize = true;
+                                                if is_color {
+                                                    self.color_atlas_needs_resize = true;
+                                                } else {
+                                                    self.atlas_needs_resize = true;
+                                                }
 
                                                 // Update size estimate for better future predictions
                                                 let glyph_area =
@@ -1162,101 +2358,125 @@ impl Renderer {
                                         };
 
                                         // Cache metrics AND placement for future frames
-                                        let metrics = (bitmap.bearing_px, bitmap.size_px, p);
+                                        let metrics =
+                                            (bitmap.bearing_px, bitmap.size_px, p, format);
                                         self.glyph_metrics_cache.insert(atlas_key.clone(), metrics);
-                                        (bitmap.bearing_px, bitmap.size_px, p)
+                                        metrics
                                     };
 
-                                    let x0 = line_x + g.x_px + glyph_bearing[0] as f32;
-                                    let y0 = current_y + g.y_px + glyph_bearing[1] as f32;
-                                    let x1 = x0 + glyph_size[0] as f32;
-                                    let y1 = y0 + glyph_size[1] as f32;
-
-                                    // Apply full transform (translation + rotation) to the glyph quad vertices
-                                    let translation = clipped.transform.translation;
-                                    let transform_origin = if let Some(abs_origin) =
-                                        clipped.transform.absolute_origin
-                                    {
-                                        abs_origin
+                                    let scale = if sdf {
+                                        g.key.px_size as f32
+                                            / gui_text::sdf::SDF_REFERENCE_PX as f32
                                     } else {
-                                        // Fallback: resolve origin relative to the node rect
-                                        let node_width =
-                                            clipped.node_rect.max[0] - clipped.node_rect.min[0];
-                                        let node_height =
-                                            clipped.node_rect.max[1] - clipped.node_rect.min[1];
-                                        let (origin_x, origin_y) = clipped
-                                            .transform
-                                            .origin
-                                            .resolve(node_width, node_height);
-                                        [
-                                            clipped.node_rect.min[0] + origin_x,
-                                            clipped.node_rect.min[1] + origin_y,
-                                        ]
-                                    };
-
-                                    // Helper to apply translation first, then rotation around the transform origin
-                                    // Uses pre-calculated cos_r and sin_r from outside the loop
-                                    let apply_transform = |pos: [f32; 2]| -> [f32; 2] {
-                                        // 1. Apply translation first
-                                        let mut x = pos[0] + translation.x;
-                                        let mut y = pos[1] + translation.y;
-
-                                        // 2. Apply rotation if present (use pre-calculated trig values)
-                                        if has_rotation {
-                                            // Translate to origin
-                                            x -= transform_origin[0];
-                                            y -= transform_origin[1];
-
-                                            // Rotate (clockwise positive) - uses pre-calculated cos_r and sin_r
-                                            let rx = x * cos_r + y * sin_r;
-                                            let ry = -x * sin_r + y * cos_r;
-
-                                            x = rx;
-                                            y = ry;
-
-                                            // Translate back from origin
-                                            x += transform_origin[0];
-                                            y += transform_origin[1];
-                                        }
-
-                                        [x, y]
+                                        1.0
                                     };
-
-                                    let p0 = apply_transform([x0, y0]);
-                                    let p1 = apply_transform([x1, y0]);
-                                    let p2 = apply_transform([x1, y1]);
-                                    let p3 = apply_transform([x0, y1]);
+                                    let x0 = glyph_x + glyph_bearing[0] as f32 * scale;
+                                    let y0 = glyph_y + glyph_bearing[1] as f32 * scale;
+                                    let x1 = x0 + glyph_size[0] as f32 * scale;
+                                    let y1 = y0 + glyph_size[1] as f32 * scale;
+
+                                    // Fully faded out
+                                    if fade_alpha(x0) <= 0.0 {
+                                        continue;
+                                    }
 
                                     // Apply opacity from ClippedShape to text color
+                                    let glyph_color = match g.span {
+                                        Some(span) => text_shape.span_color(span),
+                                        None => text_shape.color,
+                                    };
                                     let color = [
-                                        text_shape.color.r,
-                                        text_shape.color.g,
-                                        text_shape.color.b,
-                                        text_shape.color.a * clipped.opacity,
+                                        glyph_color.r,
+                                        glyph_color.g,
+                                        glyph_color.b,
+                                        glyph_color.a * clipped.opacity,
                                     ];
                                     let uv = placed.uv;
+                                    // Untransformed quad corners offset by `dx`/`dy`, with their UVs
+                                    let corners = |dx: f32, dy: f32| {
+                                        [
+                                            ([x0 + dx, y0 + dy], [uv.min[0], uv.min[1]]),
+                                            ([x1 + dx, y0 + dy], [uv.max[0], uv.min[1]]),
+                                            ([x1 + dx, y1 + dy], [uv.max[0], uv.max[1]]),
+                                            ([x0 + dx, y1 + dy], [uv.min[0], uv.max[1]]),
+                                        ]
+                                    };
+
+                                    // Color glyphs have no mask to dilate and go without an outline
+                                    if let Some(([r, g, b, a], width)) =
+                                        outline.filter(|_| format != gui_text::GlyphFormat::Color)
+                                    {
+                                        // Field value at `width` screen pixels outside the outline
+                                        let threshold = 0.5
+                                            - width
+                                                / scale
+                                                / (2.0 * gui_text::sdf::SDF_SPREAD_PX as f32);
+                                        for &[dx, dy] in &outline_stamps {
+                                            let base = self.text_vertices.len() as u32;
+                                            for (corner, uv) in corners(dx, dy) {
+                                                let pos = apply_transform(corner);
+                                                let tint = [r, g, b, a * fade_alpha(corner[0])];
+                                                self.text_vertices.push(match format {
+                                                    gui_text::GlyphFormat::Sdf => {
+                                                        text::vertex::TextVertex::sdf_outline(
+                                                            pos, uv, tint, threshold,
+                                                        )
+                                                    }
+                                                    gui_text::GlyphFormat::Lcd => {
+                                                        text::vertex::TextVertex::lcd_glyph(
+                                                            pos, uv, tint,
+                                                        )
+                                                        .filtered()
+                                                    }
+                                                    _ => {
+                                                        text::vertex::TextVertex::new(pos, uv, tint)
+                                                            .filtered()
+                                                    }
+                                                });
+                                            }
+                                            outline_indices.extend_from_slice(&[
+                                                base,
+                                                base + 1,
+                                                base + 2,
+                                                base,
+                                                base + 2,
+                                                base + 3,
+                                            ]);
+                                        }
+                                    }
 
                                     let base = self.text_vertices.len() as u32;
-                                    self.text_vertices.push(text::vertex::TextVertex::new(
-                                        p0,
-                                        [uv.min[0], uv.min[1]],
-                                        color,
-                                    ));
-                                    self.text_vertices.push(text::vertex::TextVertex::new(
-                                        p1,
-                                        [uv.max[0], uv.min[1]],
-                                        color,
-                                    ));
-                                    self.text_vertices.push(text::vertex::TextVertex::new(
-                                        p2,
-                                        [uv.max[0], uv.max[1]],
-                                        color,
-                                    ));
-                                    self.text_vertices.push(text::vertex::TextVertex::new(
-                                        p3,
-                                        [uv.min[0], uv.max[1]],
-                                        color,
-                                    ));
+                                    for (corner, uv) in corners(0.0, 0.0) {
+                                        let pos = apply_transform(corner);
+                                        let fade = fade_alpha(corner[0]);
+                                        let [r, g, b, a] = color;
+                                        let tint = [r, g, b, a * fade];
+                                        let vertex = match format {
+                                            // Color glyphs keep their own colors and only fade
+                                            gui_text::GlyphFormat::Color => {
+                                                text::vertex::TextVertex::color_glyph(
+                                                    pos,
+                                                    uv,
+                                                    clipped.opacity * fade,
+                                                )
+                                            }
+                                            gui_text::GlyphFormat::Sdf => {
+                                                text::vertex::TextVertex::sdf_glyph(pos, uv, tint)
+                                            }
+                                            gui_text::GlyphFormat::Lcd => {
+                                                text::vertex::TextVertex::lcd_glyph(pos, uv, tint)
+                                            }
+                                            gui_text::GlyphFormat::Mask => {
+                                                text::vertex::TextVertex::new(pos, uv, tint)
+                                            }
+                                        };
+                                        // Rotated bitmaps no longer line up with the pixel grid
+                                        self.text_vertices.push(if has_rotation {
+                                            vertex.filtered()
+                                        } else {
+                                            vertex
+                                        });
+                                    }
 
                                     self.text_indices.extend_from_slice(&[
                                         base,
@@ -1268,6 +2488,51 @@ impl Renderer {
                                     ]);
                                 }
 
+                                // Underlines sample the solid atlas block so they batch with the glyphs
+                                let solid = if line.underlines.is_empty() {
+                                    None
+                                } else {
+                                    self.solid_glyph(queue)
+                                };
+                                if let Some(solid) = solid {
+                                    let uv = [
+                                        (solid.uv.min[0] + solid.uv.max[0]) * 0.5,
+                                        (solid.uv.min[1] + solid.uv.max[1]) * 0.5,
+                                    ];
+                                    for underline in &line.underlines {
+                                        let x0 = line_x + underline.x_px;
+                                        let y0 = current_y + underline.y_px;
+                                        let x1 = x0 + underline.width_px;
+                                        let y1 = y0 + underline.thickness_px;
+
+                                        let span_color = text_shape.span_color(underline.span);
+                                        let color = [
+                                            span_color.r,
+                                            span_color.g,
+                                            span_color.b,
+                                            span_color.a * clipped.opacity,
+                                        ];
+
+                                        let base = self.text_vertices.len() as u32;
+                                        for corner in [[x0, y0], [x1, y0], [x1, y1], [x0, y1]] {
+                                            let [r, g, b, a] = color;
+                                            self.text_vertices.push(text::vertex::TextVertex::new(
+                                                apply_transform(corner),
+                                                uv,
+                                                [r, g, b, a * fade_alpha(corner[0])],
+                                            ));
+                                        }
+                                        self.text_indices.extend_from_slice(&[
+                                            base,
+                                            base + 1,
+                                            base + 2,
+                                            base,
+                                            base + 2,
+                                            base + 3,
+                                        ]);
+                                    }
+                                }
+
                                 // Debug: Show text line bounds (cyan outline)
                                 if let Some(debug_opts) = output.debug_options.as_ref() {
                                     if debug_opts.show_text_bounds {
@@ -1294,6 +2559,9 @@ impl Renderer {
                                 // Move to next line
                                 current_y += line.metrics.height_px;
                             }
+                            let index_start_usize = index_start as usize;
+                            self.text_indices
+                                .splice(index_start_usize..index_start_usize, outline_indices);
                             let index_end = self.text_indices.len() as u32;
                             if index_end > index_start {
                                 // Try to batch with previous draw if same scissor
@@ -1337,10 +2605,15 @@ impl Renderer {
                             }
                         }
                     }
+                    // Shape kinds this renderer does not know yet are skipped
+                    _ => {}
                 }
             } // End for clipped in layer.shapes
 
-            layer_draw_commands.push(current_layer_commands);
+            layer_draw_commands.push(LayerDrawCommands {
+                filter: layer.filter,
+                commands: current_layer_commands,
+            });
         } // End for layer in layers
 
         // Add debug text line bounds as SDF rectangles (rendered on top)
@@ -1355,6 +2628,11 @@ impl Renderer {
                     stroke: Some(stroke),
                     corner_shape: CornerShape::None,
                     anti_aliasing: AntiAliasing::None,
+                    blur: Size::ppx(0.0),
+                    conic_gradient: None,
+                    linear_gradient: None,
+                    inset_shadow: None,
+                    nine_slice: None,
                 };
 
                 // Create a ClippedShape for this debug rectangle with the text's transform
@@ -1366,6 +2644,7 @@ impl Renderer {
                     transform,                 // Use the transform from the text shape
                     z_index: ZIndex(i32::MAX), // Render on top
                     tree_index: 0,
+                    filter: None,
                 };
 
                 // Compute scissor rect
@@ -1403,6 +2682,7 @@ impl Renderer {
                                     scissor,
                                     instance_start: instance_index,
                                     instance_count: 1,
+                                    texture: None,
                                 });
                                 debug_layer_commands
                                     .push(DrawCommand::Sdf(self.sdf_draws.len() - 1));
@@ -1413,6 +2693,7 @@ impl Renderer {
                             scissor,
                             instance_start: instance_index,
                             instance_count: 1,
+                            texture: None,
                         });
                         debug_layer_commands.push(DrawCommand::Sdf(self.sdf_draws.len() - 1));
                     }
@@ -1420,13 +2701,13 @@ impl Renderer {
             }
 
             if !debug_layer_commands.is_empty() {
-                layer_draw_commands.push(debug_layer_commands);
+                layer_draw_commands.push(LayerDrawCommands {
+                    filter: None,
+                    commands: debug_layer_commands,
+                });
             }
         }
 
-        // Store layer count for later use in render pass
-        let layer_count = layer_draw_commands.len();
-
         // Update uniforms
         let uniforms = [screen_width, screen_height];
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&uniforms));
@@ -1492,89 +2773,106 @@ impl Renderer {
         self.last_frame_text_index_count = self.text_indices.len();
         self.last_frame_text_draw_count = text_draws.len();
 
-        // Render pass
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Astra UI Render Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: target,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load, // Preserve existing content
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-            multiview_mask: None,
-        });
-
-        // Layer-based rendering: Render each z-index layer completely before moving to the next
-        // This ensures text respects z-index and doesn't always render on top
+        // The overdraw view replaces normal shading with an additive draw count,
+        // so it starts from black instead of the existing content
+        let show_overdraw = output
+            .debug_options
+            .as_ref()
+            .is_some_and(|opts| opts.show_overdraw);
+        let mut load = if show_overdraw {
+            wgpu::LoadOp::Clear(wgpu::Color::BLACK)
+        } else {
+            wgpu::LoadOp::Load // Preserve existing content
+        };
 
-        // Track current pipeline state to avoid redundant switches
-        #[derive(PartialEq)]
-        enum PipelineState {
-            None,
-            Sdf,
-            Text,
+        // Consecutive layers with the same color filter form a run drawn in one
+        // render pass. The overdraw view counts draws, so it ignores filters.
+        let mut runs: Vec<(Option<ColorMatrix>, std::ops::Range<usize>)> = Vec::new();
+        for (layer_idx, layer) in layer_draw_commands.iter().enumerate() {
+            let filter = layer.filter.filter(|_| !show_overdraw);
+            match runs.last_mut() {
+                Some((run_filter, run)) if *run_filter == filter => run.end = layer_idx + 1,
+                _ => runs.push((filter, layer_idx..layer_idx + 1)),
+            }
+        }
+        // An empty frame still begins a pass (the overdraw view clears the target)
+        if runs.is_empty() {
+            runs.push((None, 0..0));
         }
-        let mut current_pipeline = PipelineState::None;
-
-        for layer_idx in 0..layer_count {
-            let commands = &layer_draw_commands[layer_idx];
 
-            for command in commands {
-                match command {
-                    DrawCommand::Sdf(idx) => {
-                        let draw = &self.sdf_draws[*idx];
+        // Upload the color matrix of each filtered run
+        self.filter_instances.clear();
+        self.filter_instances.extend(
+            runs.iter()
+                .filter_map(|(filter, _)| filter.map(FilterInstance::from)),
+        );
+        if !self.filter_instances.is_empty() {
+            if self.filter_instances.len() > self.filter_instance_capacity {
+                self.filter_instance_capacity =
+                    (self.filter_instances.len() * 2).next_power_of_two();
+                self.filter_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Astra UI Filter Instance Buffer"),
+                    size: (self.filter_instance_capacity * std::mem::size_of::<FilterInstance>())
+                        as u64,
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+            }
 
-                        if current_pipeline != PipelineState::Sdf {
-                            render_pass.set_pipeline(&self.sdf_pipeline);
-                            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-                            render_pass.set_vertex_buffer(0, self.sdf_quad_vertex_buffer.slice(..));
-                            render_pass.set_vertex_buffer(1, self.sdf_instance_buffer.slice(..));
-                            render_pass.set_index_buffer(
-                                self.sdf_quad_index_buffer.slice(..),
-                                wgpu::IndexFormat::Uint32,
-                            );
-                            current_pipeline = PipelineState::Sdf;
-                        }
+            queue.write_buffer(
+                &self.filter_instance_buffer,
+                0,
+                bytemuck::cast_slice(&self.filter_instances),
+            );
 
-                        let (x, y, w, h) = draw.scissor;
-                        render_pass.set_scissor_rect(x, y, w, h);
-                        render_pass.draw_indexed(
-                            0..6,
-                            0,
-                            draw.instance_start..(draw.instance_start + draw.instance_count),
-                        );
-                    }
-                    DrawCommand::Text(idx) => {
-                        #[cfg(feature = "text-cosmic")]
-                        {
-                            let draw = &text_draws[*idx];
-
-                            if current_pipeline != PipelineState::Text {
-                                render_pass.set_pipeline(&self.text_pipeline);
-                                render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-                                render_pass.set_bind_group(1, &self.atlas_bind_group, &[]);
-                                render_pass.set_vertex_buffer(0, self.text_vertex_buffer.slice(..));
-                                render_pass.set_index_buffer(
-                                    self.text_index_buffer.slice(..),
-                                    wgpu::IndexFormat::Uint32,
-                                );
-                                current_pipeline = PipelineState::Text;
-                            }
+            self.ensure_filter_texture(
+                device,
+                [
+                    (screen_width.ceil() as u32).max(1),
+                    (screen_height.ceil() as u32).max(1),
+                ],
+            );
+        }
 
-                            let (x, y, w, h) = draw.scissor;
-                            render_pass.set_scissor_rect(x, y, w, h);
-                            render_pass.draw_indexed(draw.index_start..draw.index_end, 0, 0..1);
-                        }
-                    }
+        // Layer-based rendering: Render each z-index layer completely before moving to the next
+        // This ensures text respects z-index and doesn't always render on top
+        let mut filter_instance = 0;
+        for (filter, run) in &runs {
+            let layers = &layer_draw_commands[run.clone()];
+
+            let filter_texture = match (filter, &self.filter_texture) {
+                (Some(_), Some(filter_texture)) => filter_texture,
+                _ => {
+                    let mut render_pass = Self::begin_pass(encoder, target, load);
+                    load = wgpu::LoadOp::Load;
+                    self.draw_layers(&mut render_pass, layers, &text_draws, show_overdraw);
+                    continue;
                 }
+            };
+
+            // Draw the run into the offscreen layer...
+            {
+                let mut layer_pass = Self::begin_pass(
+                    encoder,
+                    &filter_texture.view,
+                    wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                );
+                self.draw_layers(&mut layer_pass, layers, &text_draws, show_overdraw);
+            }
+
+            // ...then composite it onto the target through the filter, within
+            // the bounds of the run's draws
+            let mut render_pass = Self::begin_pass(encoder, target, load);
+            load = wgpu::LoadOp::Load;
+            if let Some((x, y, w, h)) = self.draw_bounds(layers, &text_draws) {
+                render_pass.set_pipeline(&self.filter_pipeline);
+                render_pass.set_bind_group(0, &filter_texture.bind_group, &[]);
+                render_pass.set_vertex_buffer(0, self.filter_instance_buffer.slice(..));
+                render_pass.set_scissor_rect(x, y, w, h);
+                render_pass.draw(0..3, filter_instance..filter_instance + 1);
             }
-        } // End layer loop
+            filter_instance += 1;
+        }
 
         // Update frame tracking
         self.last_frame_sdf_instance_count = self.sdf_instances.len();
@@ -0,0 +1,72 @@
+//! Platform request execution
+//!
+//! Carries out the `PlatformOutput` a frame produced on a winit window: the
+//! cursor icon, window moves and resizes started from custom title bars and
+//! borders, and URLs opened with the system's default handler.
+
+use astra_gui::{PlatformOutput, PlatformRequest, ResizeDirection};
+use std::process::Command;
+use winit::window::Window;
+
+use crate::input::to_winit_cursor;
+
+/// Execute a frame's platform output on a window
+///
+/// ```ignore
+/// ctx.end_frame(&mut ui);
+/// let mut output = FullOutput::from_laid_out_node(ui, size, None);
+/// output.platform = ctx.take_platform_output();
+/// apply_platform_output(&window, &output.platform);
+/// ```
+///
+/// Requests the platform can't honor, such as moving a window on a platform
+/// without client-side window dragging, are ignored.
+pub fn apply_platform_output(window: &Window, output: &PlatformOutput) {
+    window.set_cursor(to_winit_cursor(output.cursor_icon));
+    for request in &output.requests {
+        match request {
+            PlatformRequest::DragWindow => {
+                let _ = window.drag_window();
+            }
+            PlatformRequest::ResizeWindow(direction) => {
+                let _ = window.drag_resize_window(to_winit_resize_direction(*direction));
+            }
+            PlatformRequest::OpenUrl(url) => {
+                let _ = open_url(url);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert an astra-gui resize direction to a winit one
+pub fn to_winit_resize_direction(direction: ResizeDirection) -> winit::window::ResizeDirection {
+    use winit::window::ResizeDirection as Winit;
+    match direction {
+        ResizeDirection::North => Winit::North,
+        ResizeDirection::South => Winit::South,
+        ResizeDirection::East => Winit::East,
+        ResizeDirection::West => Winit::West,
+        ResizeDirection::NorthEast => Winit::NorthEast,
+        ResizeDirection::NorthWest => Winit::NorthWest,
+        ResizeDirection::SouthEast => Winit::SouthEast,
+        ResizeDirection::SouthWest => Winit::SouthWest,
+    }
+}
+
+/// Open a URL with the system's default handler, without waiting for it
+fn open_url(url: &str) -> std::io::Result<()> {
+    // The URL is passed as a single argument, never through a shell
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+
+    command.arg(url).spawn().map(drop)
+}
@@ -0,0 +1,58 @@
+// Color filter layer compositing shader
+//
+// The renderer is expected to provide:
+// - the offscreen layer, the size of the target, holding the filtered
+//   subtree drawn over transparent black (premultiplied colors)
+// - per instance, the rows of the layer's color matrix (linear RGB)
+// - a scissor rect bounding the layer's draws
+//
+// A single triangle covers the target; each fragment loads its layer pixel,
+// unpremultiplies it, applies the matrix and premultiplies the result again.
+//
+// Blending should be PREMULTIPLIED_ALPHA (src over).
+
+@group(0) @binding(0)
+var layer: texture_2d<f32>;
+
+struct VertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) row0: vec3<f32>,
+    @location(1) row1: vec3<f32>,
+    @location(2) row2: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) row0: vec3<f32>,
+    @location(1) @interpolate(flat) row1: vec3<f32>,
+    @location(2) @interpolate(flat) row2: vec3<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    // Fullscreen triangle: (-1, -1), (3, -1), (-1, 3)
+    let uv = vec2<f32>(f32((in.vertex_index << 1u) & 2u), f32(in.vertex_index & 2u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.row0 = in.row0;
+    out.row1 = in.row1;
+    out.row2 = in.row2;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureLoad(layer, vec2<i32>(in.position.xy), 0);
+    if texel.a <= 0.0 {
+        discard;
+    }
+
+    let color = texel.rgb / texel.a;
+    let filtered = clamp(
+        vec3<f32>(dot(in.row0, color), dot(in.row1, color), dot(in.row2, color)),
+        vec3<f32>(0.0),
+        vec3<f32>(1.0),
+    );
+    return vec4<f32>(filtered * texel.a, texel.a);
+}
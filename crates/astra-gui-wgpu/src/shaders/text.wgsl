@@ -1,9 +1,15 @@
-// Textured glyph shader (alpha mask atlas)
+// Textured glyph shader (alpha mask atlas + color atlas)
 //
 // The renderer is expected to provide:
 // - positions in screen-space pixels (top-left origin, +Y down)
-// - UVs into a single-channel (R8) atlas where the glyph coverage is stored in `.r`
-// - a per-vertex RGBA tint color (linear)
+// - UVs into either a single-channel (R8) atlas where the glyph coverage (or, for
+//   distance field glyphs, the distance to the outline) is stored in `.r`,
+//   or an RGBA (sRGB) atlas holding color glyphs such as emoji and LCD glyphs
+//   (per-channel coverage, sRGB encoded so sampling returns linear coverage)
+// - a per-vertex RGBA tint color (linear); color glyphs only use its alpha
+// - how the vertex samples the atlases (mask, color, distance field or LCD),
+//   plus a bit selecting bilinear filtering for glyphs off the pixel grid and,
+//   for distance field outlines, the threshold in the upper 16 bits
 //
 // Blending should be ALPHA (src over).
 
@@ -20,16 +26,31 @@ var glyph_atlas: texture_2d<f32>;
 @group(1) @binding(1)
 var glyph_sampler: sampler;
 
+@group(1) @binding(2)
+var color_atlas: texture_2d<f32>;
+
+// Bilinear sampler for scaled or rotated glyphs (distance fields, color glyphs)
+@group(1) @binding(3)
+var linear_sampler: sampler;
+
+const COLOR_ATLAS: u32 = 1u;
+const SDF_ATLAS: u32 = 2u;
+const LCD_ATLAS: u32 = 3u;
+const LINEAR_FILTER: u32 = 256u;
+const SDF_THRESHOLD_SHIFT: u32 = 16u;
+
 struct VertexInput {
     @location(0) pos_px: vec2<f32>,
     @location(1) uv: vec2<f32>,
     @location(2) color: vec4<f32>,
+    @location(3) atlas: u32,
 };
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) uv: vec2<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) atlas: u32,
 };
 
 @vertex
@@ -42,16 +63,55 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 
     out.uv = in.uv;
     out.color = in.color;
+    out.atlas = in.atlas;
 
     return out;
 }
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let cov = textureSample(glyph_atlas, glyph_sampler, in.uv).r;
+    // Every sample (and derivative) is taken up front so they stay in uniform control flow
+    let color_texel = textureSample(color_atlas, linear_sampler, in.uv);
+    let lcd_texel = textureSample(color_atlas, glyph_sampler, in.uv);
+    let linear_mask = textureSample(glyph_atlas, linear_sampler, in.uv).r;
+    let distance_width = fwidth(linear_mask);
+    let nearest_mask = textureSample(glyph_atlas, glyph_sampler, in.uv).r;
+
+    let atlas = in.atlas & 255u;
+    let filtered = (in.atlas & LINEAR_FILTER) != 0u;
+
+    if atlas == COLOR_ATLAS {
+        return vec4<f32>(color_texel.rgb, color_texel.a * in.color.a);
+    }
+
+    // LCD coverage per channel; blending has a single alpha, so use the strongest
+    // channel for it and let the tint carry the per-channel difference
+    if atlas == LCD_ATLAS {
+        let lcd_cov = select(lcd_texel.rgb, color_texel.rgb, filtered);
+        let lcd_alpha = max(lcd_cov.r, max(lcd_cov.g, lcd_cov.b));
+        return vec4<f32>(in.color.rgb * lcd_cov, in.color.a * lcd_alpha);
+    }
+
+    // Distance fields store 0.5 on the outline; antialias over about one screen pixel.
+    // Text outlines threshold further out to dilate the glyph.
+    var cov = select(nearest_mask, linear_mask, filtered);
+    if atlas == SDF_ATLAS {
+        let distance = linear_mask;
+        let threshold_bits = in.atlas >> SDF_THRESHOLD_SHIFT;
+        let threshold = select(0.5, f32(threshold_bits) / 65535.0, threshold_bits != 0u);
+        let half_width = max(distance_width * 0.5, 1e-4);
+        cov = smoothstep(threshold - half_width, threshold + half_width, distance);
+    }
 
     // Atlas is a coverage mask; tint alpha is multiplied by coverage.
     // RGB is also pre-multiplied by coverage so standard alpha blending works well.
     let a = in.color.a * cov;
     return vec4<f32>(in.color.rgb * cov, a);
 }
+
+// Overdraw visualization: each glyph quad fragment adds a constant tint with
+// additive blending. Matches the SDF shader's `fs_overdraw` ramp.
+@fragment
+fn fs_overdraw(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(0.125, 0.0625, 0.03125, 1.0);
+}
@@ -19,16 +19,16 @@ struct InstanceInput {
     @location(1) center: vec2<f32>,
     @location(2) half_size: vec2<f32>,
     @location(3) translation: vec2<f32>,
-    @location(4) rotation: f32,
+    @location(4) rotation_scale: vec2<f32>,  // rotation, scale
     @location(5) transform_origin: vec2<f32>,
-    @location(6) scale: f32,
+    @location(6) effect: vec4<f32>,  // conic gradient segment or inset shadow parameters
     @location(7) fill_color: vec4<f32>,
     @location(8) stroke_color: vec4<f32>,
     @location(9) stroke_width: f32,
     @location(10) shape_corner_type: u32,
     @location(11) params12: vec2<f32>,  // param1, param2
     @location(12) params34: vec2<f32>,  // param3, param4
-    @location(13) params56: vec2<f32>,  // param5, param6
+    @location(13) params56: vec2<f32>,  // param5, param6 (blur width for rects)
     @location(14) stroke_offset: f32,
     @location(15) anti_aliasing: u32,  // 0 = None, 1 = Analytical
 }
@@ -48,11 +48,24 @@ struct VertexOutput {
     @location(10) params56: vec2<f32>,
     @location(11) stroke_offset: f32,
     @location(12) @interpolate(flat) anti_aliasing: u32,
+    @location(13) @interpolate(flat) effect: vec4<f32>,
 }
 
 @group(0) @binding(0)
 var<uniform> uniforms: Uniforms;
 
+// Texture of the draw's nine-slice patches (an empty texture for other draws)
+@group(1) @binding(0)
+var fill_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var fill_sampler: sampler;
+
+// Flag in shape_corner_type marking an inset shadow instance (see instance.rs)
+const INSET_SHADOW_FLAG: u32 = 0x1000000u;
+const LINEAR_GRADIENT_FLAG: u32 = 0x2000000u;
+const TEXTURED_FLAG: u32 = 0x4000000u;
+
 // ============================================================================
 // Vertex Shader
 // ============================================================================
@@ -65,7 +78,9 @@ fn vs_main(vert: VertexInput, inst: InstanceInput) -> VertexOutput {
     // For Outset/Centered alignments, stroke can extend beyond shape edge
     // Calculate maximum extent the stroke can reach
     let max_stroke_extent = max(0.0, inst.stroke_width + abs(inst.stroke_offset));
-    let padding = select(0.0, max_stroke_extent, inst.stroke_width > 0.0);
+    // Blurred rects fade out across half the blur width outside the shape
+    let blur_extent = select(0.0, inst.params56.y * 0.5 + 1.0, inst.shape_corner_type != 100u);
+    let padding = max(select(0.0, max_stroke_extent, inst.stroke_width > 0.0), blur_extent);
     let expanded_size = inst.half_size + vec2<f32>(padding);
 
     // Apply transforms: Scale → Rotate → Translate (around transform origin)
@@ -77,11 +92,11 @@ fn vs_main(vert: VertexInput, inst: InstanceInput) -> VertexOutput {
     let centered = local_pos - inst.transform_origin;
 
     // 3. Scale
-    let scaled = centered * inst.scale;
+    let scaled = centered * inst.rotation_scale.y;
 
     // 4. Rotate (clockwise positive, CSS convention)
-    let cos_r = cos(inst.rotation);
-    let sin_r = sin(inst.rotation);
+    let cos_r = cos(inst.rotation_scale.x);
+    let sin_r = sin(inst.rotation_scale.x);
     let rotated = vec2<f32>(
         scaled.x * cos_r + scaled.y * sin_r,
         -scaled.x * sin_r + scaled.y * cos_r
@@ -106,10 +121,11 @@ fn vs_main(vert: VertexInput, inst: InstanceInput) -> VertexOutput {
     out.params12 = inst.params12;
     out.params34 = inst.params34;
     out.half_size = inst.half_size;
-    out.scale = inst.scale;
+    out.scale = inst.rotation_scale.y;
     out.params56 = inst.params56;
     out.stroke_offset = inst.stroke_offset;
     out.anti_aliasing = inst.anti_aliasing;
+    out.effect = inst.effect;
 
     return out;
 }
@@ -230,12 +246,76 @@ fn sd_triangle(p: vec2<f32>, p0: vec2<f32>, p1: vec2<f32>, p2: vec2<f32>) -> f32
     return -sqrt(d.x) * sign(d.y);
 }
 
+// Signed distance to a rect's shape without stroke, with corner sizes shrunk
+// by `inset` (used for the hole of inset shadows). `sizes` holds the corner
+// sizes in clockwise order from the top left (all equal for uniform corners).
+fn sd_rect_shape(p: vec2<f32>, half_size: vec2<f32>, shape_corner_type: u32, sizes: vec4<f32>, smoothness: f32, inset: f32) -> f32 {
+    var corner_type = shape_corner_type;
+    var size = sizes.x;
+    if (shape_corner_type & 0xFFu) == 5u {
+        var corner_index: u32;
+        if p.y < 0.0 {
+            corner_index = select(0u, 1u, p.x >= 0.0);
+        } else {
+            corner_index = select(3u, 2u, p.x >= 0.0);
+        }
+        corner_type = (shape_corner_type >> (8u + 3u * corner_index)) & 7u;
+        size = sizes[corner_index];
+    }
+
+    switch corner_type {
+        case 1u: {
+            return sd_rounded_box(p, half_size, max(0.0, size - inset));
+        }
+        case 2u: {
+            return sd_chamfer_box(p, half_size, max(0.0, size - inset));
+        }
+        case 3u: {
+            // Concave corners grow as the shape shrinks
+            return sd_inverse_round_box(p, half_size, max(0.0, size + inset), 0.0);
+        }
+        case 4u: {
+            return sd_squircle_box(p, half_size, max(0.0, size - inset), smoothness);
+        }
+        default: {
+            return sd_box(p, half_size);
+        }
+    }
+}
+
 // ============================================================================
 // Fragment Shader
 // ============================================================================
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return shade(in);
+}
+
+// Overdraw visualization: every fragment that survives the early discard adds
+// a constant tint with additive blending, so the final color encodes how many
+// times the pixel was shaded (red saturates at 8, green at 16, blue at 32).
+@fragment
+fn fs_overdraw(in: VertexOutput) -> @location(0) vec4<f32> {
+    _ = shade(in);
+    return vec4<f32>(0.125, 0.0625, 0.03125, 1.0);
+}
+
+fn shade(in: VertexOutput) -> vec4<f32> {
+    // Inset shadow, linear gradient and nine-slice patch instances share the
+    // rect's shape, flagged in the type
+    let inset_shadow = (in.shape_corner_type & INSET_SHADOW_FLAG) != 0u;
+    let linear_gradient = (in.shape_corner_type & LINEAR_GRADIENT_FLAG) != 0u;
+    let textured = (in.shape_corner_type & TEXTURED_FLAG) != 0u;
+    let shape_corner_type = in.shape_corner_type
+        & ~(INSET_SHADOW_FLAG | LINEAR_GRADIENT_FLAG | TEXTURED_FLAG);
+    let anti_aliasing = in.anti_aliasing;
+
+    // Patch texture coordinates span the UV rect in `effect` across the patch
+    // (sampled before any discard, at a fixed level so no derivatives are needed)
+    let patch_pos = clamp(in.local_pos / max(in.half_size * 2.0, vec2<f32>(1e-4)) + 0.5, vec2<f32>(0.0), vec2<f32>(1.0));
+    let texel = textureSampleLevel(fill_texture, fill_sampler, mix(in.effect.xy, in.effect.zw, patch_pos), 0.0);
+
     // Compute signed distance based on shape type
     // dist = distance to stroke boundary, fill_dist = distance to original shape boundary
     var dist: f32;
@@ -243,7 +323,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     let stroke_offset = in.stroke_offset; // + in.stroke_width / 2.0;
 
-    if in.shape_corner_type == 100u {
+    if shape_corner_type == 100u {
         // Triangle - vertices stored in params as world-space coordinates
         let tri_v0 = in.params12;
         let tri_v1 = in.params34;
@@ -252,10 +332,26 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         fill_dist = dist; // Triangle doesn't support stroke offset yet
     } else {
         // Rectangle - compute distance based on corner type
-        let corner_type = in.shape_corner_type;
-        let corner_param1 = in.params12.x;
+        var corner_type = shape_corner_type;
+        var corner_param1 = in.params12.x;
         let corner_param2 = in.params12.y;
 
+        if (corner_type & 0xFFu) == 5u {
+            // Per-corner shapes: pick the corner of the quadrant this fragment is in.
+            // The SDFs are symmetric, so evaluating one corner's shape in its own
+            // quadrant gives the distance to the mixed shape.
+            // Corner index in clockwise order: 0=TL, 1=TR, 2=BR, 3=BL (y points down)
+            var corner_index: u32;
+            if in.local_pos.y < 0.0 {
+                corner_index = select(0u, 1u, in.local_pos.x >= 0.0);
+            } else {
+                corner_index = select(3u, 2u, in.local_pos.x >= 0.0);
+            }
+            let corner_sizes = array<f32, 4>(in.params12.x, in.params34.x, in.params34.y, in.params56.x);
+            corner_type = (shape_corner_type >> (8u + 3u * corner_index)) & 7u;
+            corner_param1 = corner_sizes[corner_index];
+        }
+
         // Calculate stroke boundary with offset for alignment
         // The stroke extends stroke_width on each side of dist=0, so we add stroke_width/2
         // to shift the boundary so the stroke aligns correctly
@@ -303,8 +399,11 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // NOTE: this must run AFTER `dist` is computed above, otherwise the
     // derivatives are taken of an undefined value (aa_width collapses to 0 and
     // fill-only shapes disappear).
+    // Soft edge width for blurred rects (shadows); triangles keep a vertex in params56.y
+    let blur = select(0.0, max(in.params56.y, 0.0), shape_corner_type != 100u);
+
     var aa_width: f32;
-    if in.anti_aliasing == 1u {
+    if anti_aliasing == 1u {
         // Analytical AA: compute from screen-space derivatives
         aa_width = length(vec2<f32>(dpdx(dist), dpdy(dist)));
 
@@ -312,7 +411,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         // When stroke is inset, we need to check against fill boundary, not stroke boundary
         // Otherwise we'd discard fill pixels that should be visible
         let outer_dist = min(dist, fill_dist);
-        if outer_dist > aa_width * 1.5 {
+        if outer_dist > aa_width * 1.5 + blur * 0.5 {
             discard;
         }
     } else {
@@ -328,7 +427,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // out.
     var fill_cov: f32;
     var stroke_cov: f32;
-    if in.anti_aliasing == 1u {
+    if anti_aliasing == 1u {
         let aa = max(aa_width, 1e-5);
         // Coverage of an iso-contour d <= 0: 1 inside, 0 outside, linear across 1px.
         fill_cov = clamp(0.5 - fill_dist / aa, 0.0, 1.0);
@@ -340,14 +439,74 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         stroke_cov = select(0.0, 1.0, dist <= 0.0 && dist >= -in.stroke_width);
     }
 
+    // Blurred fill: fade across the blur width centered on the edge, which
+    // approximates a Gaussian-blurred shape well enough for shadows
+    if blur > 0.0 {
+        fill_cov = 1.0 - smoothstep(-blur * 0.5, blur * 0.5, fill_dist);
+    }
+
+    // Inset shadow: shade the part of the shape outside a "hole" (the shape
+    // shrunk by the spread and moved by the offset), fading across the blur
+    if inset_shadow {
+        let spread = in.effect.w;
+        let hole_dist = sd_rect_shape(
+            in.local_pos - in.effect.xy,
+            max(in.half_size - vec2<f32>(spread), vec2<f32>(0.0)),
+            shape_corner_type,
+            vec4<f32>(in.params12.x, in.params34.x, in.params34.y, in.params56.x),
+            in.params12.y,
+            spread
+        );
+        let shadow_blur = in.effect.z;
+        var shadow_cov: f32;
+        if shadow_blur > 0.0 {
+            shadow_cov = smoothstep(-shadow_blur * 0.5, shadow_blur * 0.5, hole_dist);
+        } else if anti_aliasing == 1u {
+            shadow_cov = clamp(0.5 + hole_dist / max(aa_width, 1e-5), 0.0, 1.0);
+        } else {
+            shadow_cov = select(0.0, 1.0, hole_dist > 0.0);
+        }
+        fill_cov = fill_cov * shadow_cov;
+    }
+
+    // Conic gradient segment: fade from the fill color to the color in the
+    // stroke slot across the segment, and draw nothing outside it. Segments of
+    // one gradient share their edges exactly, so they tile without seams.
+    var fill_color = in.fill_color;
+    if textured {
+        // Nine-slice patch: the texel tinted by the fill color
+        fill_color = in.fill_color * texel;
+        stroke_cov = 0.0;
+    } else if linear_gradient {
+        // Linear gradient segment: the same fade along the gradient line
+        let position = dot(in.local_pos, in.effect.xy) + 0.5;
+        if position < in.effect.z || position >= in.effect.w {
+            discard;
+        }
+        let t = (position - in.effect.z) / (in.effect.w - in.effect.z);
+        fill_color = mix(in.fill_color, in.stroke_color, t);
+        stroke_cov = 0.0;
+    } else if !inset_shadow && in.effect.w > in.effect.z {
+        let rel = in.local_pos - in.effect.xy;
+        // Turns clockwise from the top (screen y points down)
+        let turn = atan2(rel.x, -rel.y) / 6.2831853;
+        let span = in.effect.w - in.effect.z;
+        let t = fract(turn - in.effect.z);
+        if t >= span {
+            discard;
+        }
+        fill_color = mix(in.fill_color, in.stroke_color, t / span);
+        stroke_cov = 0.0;
+    }
+
     // Stroke over fill, straight (non-premultiplied) alpha to match the
     // pipeline's SrcAlpha / OneMinusSrcAlpha blending.
-    let fa = in.fill_color.a * fill_cov;
+    let fa = fill_color.a * fill_cov;
     let sa = in.stroke_color.a * stroke_cov;
     let out_a = sa + fa * (1.0 - sa);
     var out_rgb = vec3<f32>(0.0, 0.0, 0.0);
     if out_a > 0.0001 {
-        out_rgb = (in.stroke_color.rgb * sa + in.fill_color.rgb * fa * (1.0 - sa)) / out_a;
+        out_rgb = (in.stroke_color.rgb * sa + fill_color.rgb * fa * (1.0 - sa)) / out_a;
     }
 
     return vec4<f32>(out_rgb, out_a);
@@ -7,6 +7,7 @@
 //! Current structure:
 //! - `atlas`: CPU-side glyph atlas placement + cache
 //! - `vertex`: GPU vertex format for glyph quads
+//! - `shaper`: worker thread shaping long texts off the render thread
 //!
 //! Note: Text shaping/rasterization is handled by the `astra-gui-text` crate,
 //! which provides the backend-agnostic text engine using cosmic-text.
@@ -18,3 +19,6 @@ pub mod atlas;
 
 #[cfg(feature = "text-cosmic")]
 pub mod vertex;
+
+#[cfg(feature = "text-cosmic")]
+pub mod shaper;
@@ -0,0 +1,165 @@
+//! Text shaping on a worker thread.
+//!
+//! Used by `TextShapingMode::Background`: texts missing from the renderer's
+//! shape cache are sent to a worker that owns a fork of the renderer's text
+//! engine, and the shaped results are merged back into the cache on later
+//! frames.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::mpsc;
+use std::thread;
+
+use astra_gui::{
+    FontFeature, FontStyle, FontWidth, HorizontalAlign, Rect, TextOverflow, TextSpan,
+    VerticalAlign, Wrap,
+};
+use astra_gui_text::{self as gui_text, TextEngine};
+
+/// Owned copy of a [`gui_text::ShapeTextRequest`], to send to the worker
+#[derive(Clone, Debug)]
+pub struct ShapeJob {
+    text: String,
+    spans: Vec<TextSpan>,
+    rect: Rect,
+    font_px: f32,
+    h_align: HorizontalAlign,
+    v_align: VerticalAlign,
+    family: Option<String>,
+    wrap: Wrap,
+    overflow: TextOverflow,
+    max_lines: Option<usize>,
+    line_height_multiplier: f32,
+    font_weight: u16,
+    font_style: FontStyle,
+    font_features: Vec<FontFeature>,
+    font_width: FontWidth,
+    tab_width: u16,
+}
+
+impl ShapeJob {
+    pub fn new(req: &gui_text::ShapeTextRequest<'_>) -> Self {
+        Self {
+            text: req.text.to_string(),
+            spans: req.spans.to_vec(),
+            rect: req.rect,
+            font_px: req.font_px,
+            h_align: req.h_align,
+            v_align: req.v_align,
+            family: req.family.map(str::to_string),
+            wrap: req.wrap,
+            overflow: req.overflow,
+            max_lines: req.max_lines,
+            line_height_multiplier: req.line_height_multiplier,
+            font_weight: req.font_weight,
+            font_style: req.font_style,
+            font_features: req.font_features.to_vec(),
+            font_width: req.font_width,
+            tab_width: req.tab_width,
+        }
+    }
+
+    fn request(&self) -> gui_text::ShapeTextRequest<'_> {
+        gui_text::ShapeTextRequest {
+            text: &self.text,
+            spans: &self.spans,
+            rect: self.rect,
+            font_px: self.font_px,
+            h_align: self.h_align,
+            v_align: self.v_align,
+            family: self.family.as_deref(),
+            wrap: self.wrap,
+            overflow: self.overflow,
+            max_lines: self.max_lines,
+            line_height_multiplier: self.line_height_multiplier,
+            font_weight: self.font_weight,
+            font_style: self.font_style,
+            font_features: &self.font_features,
+            font_width: self.font_width,
+            tab_width: self.tab_width,
+        }
+    }
+}
+
+/// A worker thread shaping texts keyed by `K`
+///
+/// Dropping the shaper closes the job queue; the worker exits after the job it
+/// is on without blocking the caller.
+pub struct BackgroundShaper<K> {
+    jobs: mpsc::Sender<(K, ShapeJob)>,
+    results: mpsc::Receiver<(K, gui_text::ShapedText)>,
+    /// Keys submitted and not yet returned
+    pending: HashSet<K>,
+    /// The worker stopped (it panicked); callers shape on their own thread again
+    stopped: bool,
+}
+
+impl<K: Hash + Eq + Clone + Send + 'static> BackgroundShaper<K> {
+    /// Start a worker shaping with `engine`, usually a [`gui_text::Engine::fork`]
+    /// of the engine that rasterizes the results
+    pub fn spawn(mut engine: gui_text::Engine) -> Self {
+        let (jobs, job_queue) = mpsc::channel::<(K, ShapeJob)>();
+        let (result_sender, results) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name("astra-text-shaper".to_string())
+            .spawn(move || {
+                for (key, job) in job_queue {
+                    let (shaped, _placement) = engine.shape_text(job.request());
+                    if result_sender.send((key, shaped)).is_err() {
+                        break;
+                    }
+                }
+            });
+
+        Self {
+            jobs,
+            results,
+            pending: HashSet::new(),
+            stopped: spawned.is_err(),
+        }
+    }
+
+    /// Queue `job` unless `key` is already being shaped
+    pub fn submit(&mut self, key: K, job: ShapeJob) {
+        if self.stopped || !self.pending.insert(key.clone()) {
+            return;
+        }
+        if self.jobs.send((key, job)).is_err() {
+            self.stop();
+        }
+    }
+
+    /// Shaped texts that arrived since the last call
+    pub fn finished(&mut self) -> Vec<(K, gui_text::ShapedText)> {
+        let mut finished = Vec::new();
+        loop {
+            match self.results.try_recv() {
+                Ok((key, shaped)) => {
+                    self.pending.remove(&key);
+                    finished.push((key, shaped));
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.stop();
+                    break;
+                }
+            }
+        }
+        finished
+    }
+
+    /// Whether submitted texts are still being shaped
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Whether the worker is accepting jobs
+    pub fn is_running(&self) -> bool {
+        !self.stopped
+    }
+
+    fn stop(&mut self) {
+        self.stopped = true;
+        self.pending.clear();
+    }
+}
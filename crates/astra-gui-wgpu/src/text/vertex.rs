@@ -3,7 +3,7 @@ use bytemuck::{Pod, Zeroable};
 /// Vertex format for text glyph quads.
 ///
 /// Positions are in screen-space pixels (same coordinate convention as the UI geometry pipeline).
-/// UVs are normalized texture coordinates into the glyph atlas.
+/// UVs are normalized texture coordinates into the glyph atlas selected by `atlas`.
 /// Color is linear RGBA in `[0, 1]`.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -11,11 +11,85 @@ pub struct TextVertex {
     pub pos: [f32; 2],
     pub uv: [f32; 2],
     pub color: [f32; 4],
+    /// `MASK_ATLAS`, `COLOR_ATLAS`, `SDF_ATLAS` or `LCD_ATLAS`, optionally with
+    /// the `LINEAR_FILTER` bit; distance field outlines keep their threshold in
+    /// the upper 16 bits
+    pub atlas: u32,
 }
 
 impl TextVertex {
+    /// Sample the R8 coverage atlas and tint with `color`
+    pub const MASK_ATLAS: u32 = 0;
+    /// Sample the RGBA color atlas; only `color`'s alpha applies
+    pub const COLOR_ATLAS: u32 = 1;
+    /// Sample a distance field from the R8 atlas and tint with `color`
+    pub const SDF_ATLAS: u32 = 2;
+    /// Sample per-channel LCD coverage from the RGBA atlas and tint with `color`
+    pub const LCD_ATLAS: u32 = 3;
+    /// Sample masks bilinearly instead of texel-exact (for glyphs off the pixel grid)
+    pub const LINEAR_FILTER: u32 = 1 << 8;
+    /// Shift of the distance field threshold in `atlas` (0 for the outline at 0.5)
+    pub const SDF_THRESHOLD_SHIFT: u32 = 16;
+
     pub const fn new(pos: [f32; 2], uv: [f32; 2], color: [f32; 4]) -> Self {
-        Self { pos, uv, color }
+        Self {
+            pos,
+            uv,
+            color,
+            atlas: Self::MASK_ATLAS,
+        }
+    }
+
+    /// Vertex of a color glyph (emoji), faded by `opacity`
+    pub const fn color_glyph(pos: [f32; 2], uv: [f32; 2], opacity: f32) -> Self {
+        Self {
+            pos,
+            uv,
+            color: [1.0, 1.0, 1.0, opacity],
+            atlas: Self::COLOR_ATLAS,
+        }
+    }
+
+    /// Vertex of a distance field glyph, tinted with `color`
+    pub const fn sdf_glyph(pos: [f32; 2], uv: [f32; 2], color: [f32; 4]) -> Self {
+        Self {
+            pos,
+            uv,
+            color,
+            atlas: Self::SDF_ATLAS,
+        }
+    }
+
+    /// Vertex of a distance field glyph's outline: the glyph dilated to where
+    /// the field reaches `threshold` (below 0.5), tinted with `color`
+    pub fn sdf_outline(pos: [f32; 2], uv: [f32; 2], color: [f32; 4], threshold: f32) -> Self {
+        let threshold = (threshold.clamp(0.0, 0.5) * u16::MAX as f32)
+            .round()
+            .max(1.0) as u32;
+        Self {
+            pos,
+            uv,
+            color,
+            atlas: Self::SDF_ATLAS | threshold << Self::SDF_THRESHOLD_SHIFT,
+        }
+    }
+
+    /// Vertex of an LCD glyph, tinted with `color`
+    pub const fn lcd_glyph(pos: [f32; 2], uv: [f32; 2], color: [f32; 4]) -> Self {
+        Self {
+            pos,
+            uv,
+            color,
+            atlas: Self::LCD_ATLAS,
+        }
+    }
+
+    /// This vertex with bilinear filtering of its glyph
+    pub const fn filtered(self) -> Self {
+        Self {
+            atlas: self.atlas | Self::LINEAR_FILTER,
+            ..self
+        }
     }
 
     pub const fn desc() -> wgpu::VertexBufferLayout<'static> {
@@ -38,6 +112,13 @@ impl TextVertex {
                 shader_location: 2,
                 format: wgpu::VertexFormat::Float32x4,
             },
+            // atlas
+            wgpu::VertexAttribute {
+                offset: (std::mem::size_of::<[f32; 2]>() * 2 + std::mem::size_of::<[f32; 4]>())
+                    as wgpu::BufferAddress,
+                shader_location: 3,
+                format: wgpu::VertexFormat::Uint32,
+            },
         ];
 
         wgpu::VertexBufferLayout {
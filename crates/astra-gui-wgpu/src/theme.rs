@@ -0,0 +1,91 @@
+//! Automatic light/dark theme switching
+//!
+//! Follows the operating system's color scheme through winit and keeps the
+//! `UiContext` theme in sync with it.
+
+use astra_gui::{ColorScheme, ThemePair, UiContext};
+use winit::event::WindowEvent;
+use winit::window::{Theme as WinitTheme, Window};
+
+/// Convert a winit window theme to a color scheme
+pub fn to_color_scheme(theme: WinitTheme) -> ColorScheme {
+    match theme {
+        WinitTheme::Light => ColorScheme::Light,
+        WinitTheme::Dark => ColorScheme::Dark,
+    }
+}
+
+/// Switches the `UiContext` theme between a light and a dark variant to
+/// match the OS color scheme
+///
+/// ```ignore
+/// let mut auto_theme = AutoTheme::new(ThemePair::default());
+///
+/// // After creating the window
+/// auto_theme.detect(&window, &mut ctx);
+///
+/// // In `window_event`
+/// if auto_theme.handle_winit_event(&event, &mut ctx) {
+///     window.request_redraw();
+/// }
+/// ```
+///
+/// Widgets pick up the new theme on the next build and animate to it through
+/// their style transitions.
+#[derive(Debug, Clone, Copy)]
+pub struct AutoTheme {
+    themes: ThemePair,
+    scheme: ColorScheme,
+}
+
+impl AutoTheme {
+    pub fn new(themes: ThemePair) -> Self {
+        Self {
+            themes,
+            scheme: ColorScheme::default(),
+        }
+    }
+
+    /// Current color scheme (dark until one is detected)
+    pub fn scheme(&self) -> ColorScheme {
+        self.scheme
+    }
+
+    /// Replace the theme pair and apply the variant for the current scheme
+    pub fn set_themes(&mut self, themes: ThemePair, ctx: &mut UiContext) {
+        self.themes = themes;
+        ctx.set_theme(themes.get(self.scheme));
+    }
+
+    /// Read the window's color scheme and apply the matching theme
+    ///
+    /// Platforms that can't report a scheme keep the current one.
+    pub fn detect(&mut self, window: &Window, ctx: &mut UiContext) {
+        if let Some(theme) = window.theme() {
+            self.scheme = to_color_scheme(theme);
+        }
+        ctx.set_theme(self.themes.get(self.scheme));
+    }
+
+    /// Apply the matching theme when the OS color scheme changes
+    ///
+    /// Returns true if the scheme changed and the UI should be redrawn.
+    pub fn handle_winit_event(&mut self, event: &WindowEvent, ctx: &mut UiContext) -> bool {
+        let WindowEvent::ThemeChanged(theme) = event else {
+            return false;
+        };
+        let scheme = to_color_scheme(*theme);
+        if scheme == self.scheme {
+            return false;
+        }
+        self.scheme = scheme;
+        ctx.set_theme(self.themes.get(scheme));
+        true
+    }
+}
+
+impl Default for AutoTheme {
+    fn default() -> Self {
+        Self::new(ThemePair::default())
+    }
+}
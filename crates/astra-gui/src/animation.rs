@@ -0,0 +1,186 @@
+//! Continuous, time-driven style animations
+//!
+//! A [`Transition`](crate::Transition) runs once when a node's style changes.
+//! An [`Animation`] instead repeats for as long as it is part of the node's
+//! style: a pulsing glow, a spinning loader, a rotating gradient. Animations
+//! are sampled from the `UiContext` frame clock in `end_frame`, and while any
+//! node is animated `UiContext::has_active_transitions` returns true so the
+//! event loop keeps redrawing.
+
+use crate::color::{Color, ColorSpace};
+use crate::layout::Size;
+use crate::primitives::Shadow;
+use crate::style::Style;
+use crate::transition::{ease_in_out, lerp_color_in, lerp_f32, lerp_size, linear, EasingFn};
+
+/// Style property driven by an animation, with the values at the start and
+/// end of a cycle
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum AnimatedProperty {
+    /// Node opacity
+    Opacity(f32, f32),
+    /// Fill color
+    FillColor(Color, Color),
+    /// Rotation in radians (clockwise positive)
+    Rotation(f32, f32),
+    /// Start angle of the style's conic gradient in radians
+    GradientAngle(f32, f32),
+    /// Drop shadow color; without a shadow in the style this animates a glow
+    /// (a shadow with no offset or blur)
+    ShadowColor(Color, Color),
+    /// Drop shadow blur
+    ShadowBlur(Size, Size),
+}
+
+/// What an animation does at the end of each cycle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Repeat {
+    /// Jump back to the start (spinners, rotating gradients)
+    #[default]
+    Loop,
+    /// Play backwards, then forwards again (pulses)
+    Alternate,
+}
+
+/// Repeating animation of one style property
+///
+/// Add animations to a style with `Style::animated`; they override the
+/// property's static value and run until the style no longer contains them:
+///
+/// ```ignore
+/// let glow = Style {
+///     shadow: Some(Shadow::new(Size::lpx(0.0), Size::lpx(0.0), Size::lpx(12.0), accent)),
+///     ..Default::default()
+/// }
+/// .merge(&Style::animated(Animation::pulse(
+///     AnimatedProperty::ShadowColor(accent.with_alpha(0.2), accent),
+///     1.2,
+/// )));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Animation {
+    pub property: AnimatedProperty,
+    /// Length of one cycle in seconds
+    pub duration: f32,
+    pub easing: EasingFn,
+    pub repeat: Repeat,
+}
+
+impl Animation {
+    /// Create a linear, looping animation
+    pub fn new(property: AnimatedProperty, duration: f32) -> Self {
+        Self {
+            property,
+            duration,
+            easing: linear,
+            repeat: Repeat::Loop,
+        }
+    }
+
+    /// Create an eased animation that plays forwards and backwards, for pulses
+    pub fn pulse(property: AnimatedProperty, duration: f32) -> Self {
+        Self {
+            property,
+            duration,
+            easing: ease_in_out,
+            repeat: Repeat::Alternate,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: EasingFn) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Eased progress through the current cycle at `time` seconds
+    pub fn progress(&self, time: f64) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        let cycles = time.max(0.0) / f64::from(self.duration);
+        let phase = cycles.fract() as f32;
+        let t = match self.repeat {
+            Repeat::Loop => phase,
+            Repeat::Alternate if cycles as u64 % 2 == 1 => 1.0 - phase,
+            Repeat::Alternate => phase,
+        };
+        (self.easing)(t)
+    }
+
+    /// Write the property's value at `time` seconds into `style`
+    pub fn apply(&self, style: &mut Style, time: f64) {
+        let t = self.progress(time);
+        let lerp_color = |a, b| lerp_color_in(a, b, t, ColorSpace::default());
+        match self.property {
+            AnimatedProperty::Opacity(a, b) => style.opacity = Some(lerp_f32(a, b, t)),
+            AnimatedProperty::FillColor(a, b) => style.fill_color = Some(lerp_color(a, b)),
+            AnimatedProperty::Rotation(a, b) => style.rotation = Some(lerp_f32(a, b, t)),
+            AnimatedProperty::GradientAngle(a, b) => {
+                if let Some(gradient) = &mut style.gradient {
+                    gradient.start_angle = lerp_f32(a, b, t);
+                }
+            }
+            AnimatedProperty::ShadowColor(a, b) => {
+                style.shadow.get_or_insert_with(glow).color = lerp_color(a, b);
+            }
+            AnimatedProperty::ShadowBlur(a, b) => {
+                style.shadow.get_or_insert_with(glow).blur = lerp_size(a, b, t);
+            }
+        }
+    }
+
+    /// Whether two animations animate the same way (easing functions can't
+    /// be compared reliably and are ignored)
+    pub(crate) fn same_as(&self, other: &Animation) -> bool {
+        self.property == other.property
+            && self.duration == other.duration
+            && self.repeat == other.repeat
+    }
+}
+
+fn glow() -> Shadow {
+    Shadow::new(
+        Size::lpx(0.0),
+        Size::lpx(0.0),
+        Size::lpx(0.0),
+        Color::transparent(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alternate_plays_backwards_every_other_cycle() {
+        let animation =
+            Animation::new(AnimatedProperty::Opacity(0.0, 1.0), 2.0).with_repeat(Repeat::Alternate);
+
+        assert!((animation.progress(0.5) - 0.25).abs() < 1e-5);
+        assert!((animation.progress(2.5) - 0.75).abs() < 1e-5);
+        assert!((animation.progress(4.5) - 0.25).abs() < 1e-5);
+
+        let looping = Animation::new(AnimatedProperty::Opacity(0.0, 1.0), 2.0);
+        assert!((looping.progress(2.5) - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_shadow_color_animates_a_glow_without_shadow() {
+        let mut style = Style::default();
+        Animation::new(
+            AnimatedProperty::ShadowColor(Color::transparent(), Color::rgb(1.0, 0.0, 0.0)),
+            1.0,
+        )
+        .apply(&mut style, 0.5);
+
+        let shadow = style.shadow.unwrap();
+        assert_eq!(shadow.offset_y, Size::lpx(0.0));
+        assert!(shadow.color.a > 0.0 && shadow.color.a < 1.0);
+    }
+}
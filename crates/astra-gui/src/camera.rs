@@ -0,0 +1,226 @@
+//! Zoom and pan controller for canvas-style views
+//!
+//! Node graphs, maps and image viewers all turn the same input into a camera:
+//! the wheel and pinches zoom around the pointer, and dragging or two-finger
+//! pans move the view. [`CameraController`] does this once. It reads the
+//! input over the node the view is built on, eases toward
+//! its target, and applies the result to the root as its zoom level and pan
+//! offset (`Node::with_zoom`, `Node::with_pan_offset`).
+//!
+//! Zooming keeps the point under the pointer in place for content positioned
+//! in fixed sizes; `Size::Fill` content reflows with the window instead.
+
+use crate::context::UiContext;
+use crate::events::InteractionEvent;
+use crate::input::MouseButton;
+use crate::layout::{Size, Translation};
+use crate::node::Node;
+use crate::primitives::Point;
+
+/// Default zoom range
+const DEFAULT_MIN_ZOOM: f32 = 0.1;
+const DEFAULT_MAX_ZOOM: f32 = 10.0;
+
+/// Default zoom per pixel of wheel scroll (a 20 px wheel step zooms ~10%)
+const DEFAULT_ZOOM_SPEED: f32 = 0.005;
+
+/// Default exponential smoothing rate, matching smooth scrolling
+const DEFAULT_SMOOTHING: f32 = 10.0;
+
+/// Below this the camera counts as settled on its target
+const SETTLE_EPSILON: f32 = 0.001;
+
+/// Turns wheel, pinch, pan and drag input into the root's zoom and pan
+///
+/// # Example
+///
+/// ```ignore
+/// // Kept in app state across frames
+/// let mut camera = CameraController::new().with_zoom_range(0.25, 4.0);
+///
+/// // Every frame, before building the UI
+/// camera.update(&ctx, "graph");
+/// let root = camera.apply(Node::new().with_id(NodeId::new("graph")).with_children(nodes));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CameraController {
+    zoom: f32,
+    pan: (f32, f32),
+    target_zoom: f32,
+    target_pan: (f32, f32),
+    min_zoom: f32,
+    max_zoom: f32,
+    zoom_speed: f32,
+    smoothing: f32,
+    zoom_to_cursor: bool,
+    pan_button: Option<MouseButton>,
+    /// Whether the pan button is dragging the view
+    panning: bool,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CameraController {
+    /// Create a controller at 100% zoom and no pan
+    ///
+    /// By default the wheel zooms around the pointer, the middle button pans
+    /// and changes ease in over a few frames.
+    pub fn new() -> Self {
+        Self {
+            zoom: 1.0,
+            pan: (0.0, 0.0),
+            target_zoom: 1.0,
+            target_pan: (0.0, 0.0),
+            min_zoom: DEFAULT_MIN_ZOOM,
+            max_zoom: DEFAULT_MAX_ZOOM,
+            zoom_speed: DEFAULT_ZOOM_SPEED,
+            smoothing: DEFAULT_SMOOTHING,
+            zoom_to_cursor: true,
+            pan_button: Some(MouseButton::Middle),
+            panning: false,
+        }
+    }
+
+    /// Set the zoom limits (default 0.1 to 10.0)
+    pub fn with_zoom_range(mut self, min: f32, max: f32) -> Self {
+        self.min_zoom = min.max(f32::EPSILON);
+        self.max_zoom = max.max(self.min_zoom);
+        self.target_zoom = self.target_zoom.clamp(self.min_zoom, self.max_zoom);
+        self.zoom = self.zoom.clamp(self.min_zoom, self.max_zoom);
+        self
+    }
+
+    /// Set how much a pixel of wheel scroll zooms (default 0.005)
+    pub fn with_zoom_speed(mut self, speed: f32) -> Self {
+        self.zoom_speed = speed;
+        self
+    }
+
+    /// Set the exponential smoothing rate: higher is snappier, 0 jumps
+    /// straight to the target (default 10.0)
+    pub fn with_smoothing(mut self, rate: f32) -> Self {
+        self.smoothing = rate.max(0.0);
+        self
+    }
+
+    /// Set whether zooming keeps the point under the pointer in place
+    /// (default true); otherwise it zooms around the window's top-left corner
+    pub fn with_zoom_to_cursor(mut self, zoom_to_cursor: bool) -> Self {
+        self.zoom_to_cursor = zoom_to_cursor;
+        self
+    }
+
+    /// Set the mouse button that pans by dragging the view, or `None` to
+    /// pan with gestures only (default middle)
+    pub fn with_pan_button(mut self, button: Option<MouseButton>) -> Self {
+        self.pan_button = button;
+        self
+    }
+
+    /// Get the current zoom level
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Get the current pan offset in physical pixels
+    pub fn pan(&self) -> (f32, f32) {
+        self.pan
+    }
+
+    /// Check if the camera is still easing toward its target
+    pub fn is_animating(&self) -> bool {
+        (self.target_zoom - self.zoom).abs() > SETTLE_EPSILON
+            || (self.target_pan.0 - self.pan.0).abs() > SETTLE_EPSILON
+            || (self.target_pan.1 - self.pan.1).abs() > SETTLE_EPSILON
+    }
+
+    /// Zoom by `factor`, keeping `anchor` in place when zooming to the cursor
+    pub fn zoom_by(&mut self, factor: f32, anchor: Option<Point>) {
+        let old = self.target_zoom;
+        let new = (old * factor).clamp(self.min_zoom, self.max_zoom);
+        if let Some(anchor) = anchor.filter(|_| self.zoom_to_cursor) {
+            let ratio = new / old;
+            self.target_pan = (
+                anchor.x - (anchor.x - self.target_pan.0) * ratio,
+                anchor.y - (anchor.y - self.target_pan.1) * ratio,
+            );
+        }
+        self.target_zoom = new;
+    }
+
+    /// Pan by a distance in physical pixels
+    pub fn pan_by(&mut self, dx: f32, dy: f32) {
+        self.target_pan.0 += dx;
+        self.target_pan.1 += dy;
+    }
+
+    /// Jump to a zoom level and pan offset without easing
+    pub fn set_view(&mut self, zoom: f32, pan: (f32, f32)) {
+        self.target_zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+        self.target_pan = pan;
+        self.zoom = self.target_zoom;
+        self.pan = pan;
+    }
+
+    /// Ease back to 100% zoom and no pan
+    pub fn reset(&mut self) {
+        self.target_zoom = 1.0_f32.clamp(self.min_zoom, self.max_zoom);
+        self.target_pan = (0.0, 0.0);
+    }
+
+    /// Read the input over the node with ID `id` and ease toward the new
+    /// target
+    ///
+    /// Call once per frame, after `UiContext::begin_frame` and before
+    /// building the view. The wheel is read from this frame's input, gestures
+    /// and drags from last frame's events.
+    pub fn update(&mut self, ctx: &UiContext, id: &str) {
+        let wheel = ctx.input().scroll_delta.1;
+        if wheel != 0.0 && ctx.is_hovered(id) {
+            let anchor = ctx.input().cursor_position;
+            self.zoom_by((wheel * self.zoom_speed).exp(), anchor);
+        }
+
+        for event in ctx.events().iter().filter(|e| e.target.as_str() == id) {
+            match event.event {
+                InteractionEvent::Pinch { zoom, position } => self.zoom_by(zoom, Some(position)),
+                InteractionEvent::Pan { delta, .. } => self.pan_by(delta.0, delta.1),
+                InteractionEvent::DragStart { button, .. } => {
+                    self.panning = self.pan_button == Some(button);
+                }
+                InteractionEvent::DragMove { delta, .. } if self.panning => {
+                    self.pan_by(delta.x, delta.y);
+                }
+                InteractionEvent::DragEnd { .. } => self.panning = false,
+                _ => {}
+            }
+        }
+
+        // Ease toward the target at the last frame's pace
+        let dt = ctx.last_frame_time_ms().unwrap_or(0.0) / 1000.0;
+        let t = if self.smoothing > 0.0 {
+            1.0 - (-self.smoothing * dt).exp()
+        } else {
+            1.0
+        };
+        self.zoom += (self.target_zoom - self.zoom) * t;
+        self.pan.0 += (self.target_pan.0 - self.pan.0) * t;
+        self.pan.1 += (self.target_pan.1 - self.pan.1) * t;
+        if !self.is_animating() {
+            self.zoom = self.target_zoom;
+            self.pan = self.target_pan;
+        }
+    }
+
+    /// Apply the current zoom and pan to the root node
+    pub fn apply(&self, root: Node) -> Node {
+        root.with_zoom(self.zoom).with_pan_offset(Translation::new(
+            Size::ppx(self.pan.0),
+            Size::ppx(self.pan.1),
+        ))
+    }
+}
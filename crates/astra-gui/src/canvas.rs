@@ -0,0 +1,507 @@
+//! Immediate drawing into a node
+//!
+//! A node built with `Node::with_painter` runs its paint function once its
+//! layout is known, drawing lines, rects, circles, paths and text in
+//! coordinates local to the node's content rect (logical pixels, origin at
+//! the top-left after padding). The drawing is turned into the same rect,
+//! triangle and text shapes every other node produces, so plots and custom
+//! gizmos work with any backend and follow the node's clipping, transform,
+//! opacity and filters.
+//!
+//! Strokes are rounded: lines and path segments are drawn as capsules, so
+//! the segments of a path join without gaps.
+//!
+//! Angles are in radians, starting at the positive x axis and increasing
+//! clockwise on screen (the y axis points down).
+
+use crate::color::Color;
+use crate::content::{TextContent, Wrap};
+use crate::layout::Size;
+use crate::primitives::{
+    CornerShape, Point, Rect, Shape, Stroke, StrokeAlignment, StyledRect, StyledTriangle,
+    TextShape, TriangleSpec,
+};
+
+/// A shape drawn by a painter, rotated by `rotation` around the center of `rect`
+pub(crate) struct PaintedShape {
+    pub rect: Rect,
+    pub shape: Shape,
+    pub rotation: f32,
+}
+
+/// Drawing API handed to a node's paint function
+///
+/// # Example
+///
+/// ```ignore
+/// Node::new()
+///     .with_width(Size::Fill)
+///     .with_height(Size::lpx(200.0))
+///     .with_painter(move |painter| {
+///         let (width, height) = painter.size();
+///         let points = samples.iter().enumerate().map(|(i, sample)| {
+///             [i as f32 / (samples.len() - 1) as f32 * width, (1.0 - sample) * height]
+///         });
+///         painter.path(points, 2.0, mocha::BLUE);
+///     })
+/// ```
+pub struct Painter {
+    /// Content rect of the node, in physical pixels
+    rect: Rect,
+    scale_factor: f32,
+    text_scale: f32,
+    shapes: Vec<PaintedShape>,
+}
+
+impl Painter {
+    pub(crate) fn new(rect: Rect, scale_factor: f32, text_scale: f32) -> Self {
+        Self {
+            rect,
+            scale_factor,
+            text_scale,
+            shapes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn into_shapes(self) -> Vec<PaintedShape> {
+        self.shapes
+    }
+
+    /// Size of the drawing area (the node's content rect) in logical pixels
+    pub fn size(&self) -> (f32, f32) {
+        (
+            self.rect.width() / self.scale_factor,
+            self.rect.height() / self.scale_factor,
+        )
+    }
+
+    /// The drawing area in local coordinates, from the origin to `size()`
+    pub fn local_rect(&self) -> Rect {
+        let (width, height) = self.size();
+        Rect::new([0.0, 0.0], [width, height])
+    }
+
+    /// Draw a straight line with round caps
+    pub fn line(&mut self, from: impl Into<Point>, to: impl Into<Point>, width: f32, color: Color) {
+        let (from, to) = (self.to_physical(from.into()), self.to_physical(to.into()));
+        let width = width * self.scale_factor;
+        let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        // A horizontal capsule around the midpoint, rotated onto the segment
+        let center = [(from[0] + to[0]) * 0.5, (from[1] + to[1]) * 0.5];
+        let half = [(length + width) * 0.5, width * 0.5];
+        let rect = Rect::new(
+            [center[0] - half[0], center[1] - half[1]],
+            [center[0] + half[0], center[1] + half[1]],
+        );
+        self.shapes.push(PaintedShape {
+            rect,
+            shape: Shape::Rect(
+                StyledRect::new(rect, color)
+                    .with_corner_shape(CornerShape::Round(Size::ppx(width * 0.5))),
+            ),
+            rotation: (-dy).atan2(dx),
+        });
+    }
+
+    /// Draw a line through the points, in order
+    pub fn path(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<Point>>,
+        width: f32,
+        color: Color,
+    ) {
+        let points: Vec<Point> = points.into_iter().map(Into::into).collect();
+        for segment in points.windows(2) {
+            self.line(segment[0], segment[1], width, color);
+        }
+    }
+
+    /// Draw a line through the points and back to the first one
+    pub fn closed_path(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<Point>>,
+        width: f32,
+        color: Color,
+    ) {
+        let mut points: Vec<Point> = points.into_iter().map(Into::into).collect();
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+        self.path(points, width, color);
+    }
+
+    /// Fill a convex polygon
+    ///
+    /// The polygon is split into a fan of triangles from its first point, so
+    /// concave polygons are not filled correctly.
+    pub fn convex_polygon(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<Point>>,
+        fill: Color,
+    ) {
+        let points: Vec<Point> = points.into_iter().map(Into::into).collect();
+        for index in 1..points.len().saturating_sub(1) {
+            self.triangle(points[0], points[index], points[index + 1], fill);
+        }
+    }
+
+    /// Fill a triangle
+    pub fn triangle(
+        &mut self,
+        a: impl Into<Point>,
+        b: impl Into<Point>,
+        c: impl Into<Point>,
+        fill: Color,
+    ) {
+        let vertices = [
+            self.to_physical(a.into()),
+            self.to_physical(b.into()),
+            self.to_physical(c.into()),
+        ];
+        let min = [
+            vertices.iter().map(|v| v[0]).fold(f32::INFINITY, f32::min),
+            vertices.iter().map(|v| v[1]).fold(f32::INFINITY, f32::min),
+        ];
+        let max = [
+            vertices
+                .iter()
+                .map(|v| v[0])
+                .fold(f32::NEG_INFINITY, f32::max),
+            vertices
+                .iter()
+                .map(|v| v[1])
+                .fold(f32::NEG_INFINITY, f32::max),
+        ];
+        let rect = Rect::new(min, max);
+        // Degenerate triangles cover no area
+        if rect.width() <= f32::EPSILON || rect.height() <= f32::EPSILON {
+            return;
+        }
+
+        // Triangle points are relative to their bounding rect
+        let relative = |v: [f32; 2]| {
+            [
+                (v[0] - min[0]) / rect.width(),
+                (v[1] - min[1]) / rect.height(),
+            ]
+        };
+        let spec = TriangleSpec::Points {
+            p1: relative(vertices[0]),
+            p2: relative(vertices[1]),
+            p3: relative(vertices[2]),
+        };
+        self.shapes.push(PaintedShape {
+            rect,
+            shape: Shape::Triangle(StyledTriangle::new(rect, spec, fill)),
+            rotation: 0.0,
+        });
+    }
+
+    /// Fill a rect
+    pub fn rect(&mut self, rect: Rect, fill: Color) {
+        self.rounded_rect(rect, 0.0, fill);
+    }
+
+    /// Fill a rect with rounded corners
+    pub fn rounded_rect(&mut self, rect: Rect, radius: f32, fill: Color) {
+        let rect = self.rect_to_physical(rect);
+        self.push_rect(rect, radius * self.scale_factor, fill, None);
+    }
+
+    /// Draw the outline of a rect, centered on its edges
+    pub fn rect_stroke(&mut self, rect: Rect, width: f32, color: Color) {
+        let rect = self.rect_to_physical(rect);
+        let stroke = self.stroke(width, color);
+        self.push_rect(rect, 0.0, Color::transparent(), Some(stroke));
+    }
+
+    /// Fill a circle
+    pub fn circle(&mut self, center: impl Into<Point>, radius: f32, fill: Color) {
+        let rect = self.circle_rect(center.into(), radius);
+        self.push_rect(rect, rect.width() * 0.5, fill, None);
+    }
+
+    /// Draw the outline of a circle, centered on its radius
+    pub fn circle_stroke(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        width: f32,
+        color: Color,
+    ) {
+        let rect = self.circle_rect(center.into(), radius);
+        let stroke = self.stroke(width, color);
+        self.push_rect(rect, rect.width() * 0.5, Color::transparent(), Some(stroke));
+    }
+
+    /// Draw an arc of a circle, centered on its radius, from `start` to `end`
+    ///
+    /// The arc runs clockwise when `end > start` and counter-clockwise
+    /// otherwise; like paths, it is built from short capsules with round caps.
+    pub fn arc(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        start: f32,
+        end: f32,
+        width: f32,
+        color: Color,
+    ) {
+        let points = arc_points(center.into(), radius, start, end);
+        self.path(points, width, color);
+    }
+
+    /// Fill a pie slice of a circle between the angles `start` and `end`
+    pub fn sector(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        start: f32,
+        end: f32,
+        fill: Color,
+    ) {
+        let center = center.into();
+        let points = arc_points(center, radius, start, end);
+        // A fan around the center stays correct for slices wider than 180°
+        for edge in points.windows(2) {
+            self.triangle(center, edge[0], edge[1], fill);
+        }
+    }
+
+    /// Draw text anchored at a point
+    ///
+    /// The text's alignment picks the anchor: left-aligned text starts at the
+    /// point, centered text is centered on it and right-aligned text ends at
+    /// it (likewise vertically). Text drawn by a painter never wraps.
+    pub fn text(&mut self, anchor: impl Into<Point>, content: TextContent) {
+        let anchor = self.to_physical(anchor.into());
+        let rect = Rect::new(anchor, anchor);
+        let mut text = TextShape::new(rect, &content);
+        let scale = self.scale_factor * self.text_scale;
+        text.font_size = Size::lpx(
+            content
+                .font_size
+                .try_resolve_with_scale(0.0, scale)
+                .unwrap_or(16.0),
+        );
+        text.wrap = Wrap::None;
+        self.shapes.push(PaintedShape {
+            rect,
+            shape: Shape::Text(text),
+            rotation: 0.0,
+        });
+    }
+
+    fn to_physical(&self, point: Point) -> [f32; 2] {
+        [
+            self.rect.min[0] + point.x * self.scale_factor,
+            self.rect.min[1] + point.y * self.scale_factor,
+        ]
+    }
+
+    fn rect_to_physical(&self, rect: Rect) -> Rect {
+        Rect::new(
+            self.to_physical(rect.min_point()),
+            self.to_physical(rect.max_point()),
+        )
+    }
+
+    fn circle_rect(&self, center: Point, radius: f32) -> Rect {
+        self.rect_to_physical(Rect::new(
+            [center.x - radius, center.y - radius],
+            [center.x + radius, center.y + radius],
+        ))
+    }
+
+    fn stroke(&self, width: f32, color: Color) -> Stroke {
+        Stroke::new(Size::ppx(width * self.scale_factor), color)
+            .with_alignment(StrokeAlignment::Centered)
+    }
+
+    fn push_rect(&mut self, rect: Rect, radius: f32, fill: Color, stroke: Option<Stroke>) {
+        let mut styled = StyledRect::new(rect, fill);
+        if radius > 0.0 {
+            styled = styled.with_corner_shape(CornerShape::Round(Size::ppx(radius)));
+        }
+        if let Some(stroke) = stroke {
+            styled = styled.with_stroke(stroke);
+        }
+        self.shapes.push(PaintedShape {
+            rect,
+            shape: Shape::Rect(styled),
+            rotation: 0.0,
+        });
+    }
+}
+
+/// Points along an arc, spaced closely enough that the chords look round
+fn arc_points(center: Point, radius: f32, start: f32, end: f32) -> Vec<Point> {
+    let sweep = end - start;
+    let segments = ((radius.abs() * sweep.abs()) / 4.0)
+        .ceil()
+        .clamp(1.0, 256.0) as usize;
+    (0..=segments)
+        .map(|index| {
+            let angle = start + sweep * index as f32 / segments as f32;
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{HorizontalAlign, VerticalAlign};
+
+    fn painter() -> Painter {
+        // Content rect at (10, 20), 200x100 logical pixels at 2x
+        Painter::new(Rect::new([10.0, 20.0], [410.0, 220.0]), 2.0, 1.0)
+    }
+
+    #[test]
+    fn test_size_is_logical() {
+        let painter = painter();
+        assert_eq!(painter.size(), (200.0, 100.0));
+        assert_eq!(painter.local_rect(), Rect::new([0.0, 0.0], [200.0, 100.0]));
+    }
+
+    #[test]
+    fn test_line_is_rotated_capsule() {
+        let mut painter = painter();
+        painter.line([0.0, 0.0], [0.0, 10.0], 2.0, Color::rgb(1.0, 1.0, 1.0));
+        let shapes = painter.into_shapes();
+        let line = &shapes[0];
+
+        // Length plus the round caps along x, the width along y, around the midpoint
+        assert_eq!(line.rect, Rect::new([-2.0, 28.0], [22.0, 32.0]));
+        assert!((line.rotation + std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        let Shape::Rect(rect) = &line.shape else {
+            panic!("line should be a rect");
+        };
+        assert_eq!(rect.corner_shape, CornerShape::Round(Size::ppx(2.0)));
+    }
+
+    #[test]
+    fn test_path_draws_one_line_per_segment() {
+        let mut painter = painter();
+        painter.path(
+            [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]],
+            1.0,
+            Color::rgb(1.0, 1.0, 1.0),
+        );
+        assert_eq!(painter.into_shapes().len(), 2);
+
+        let mut painter = self::painter();
+        painter.closed_path(
+            [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]],
+            1.0,
+            Color::rgb(1.0, 1.0, 1.0),
+        );
+        assert_eq!(painter.into_shapes().len(), 3);
+    }
+
+    #[test]
+    fn test_circle_is_round_rect() {
+        let mut painter = painter();
+        painter.circle_stroke([50.0, 50.0], 10.0, 1.0, Color::rgb(1.0, 1.0, 1.0));
+        let shapes = painter.into_shapes();
+        assert_eq!(shapes[0].rect, Rect::new([90.0, 100.0], [130.0, 140.0]));
+        let Shape::Rect(rect) = &shapes[0].shape else {
+            panic!("circle should be a rect");
+        };
+        assert_eq!(rect.corner_shape, CornerShape::Round(Size::ppx(20.0)));
+        assert_eq!(
+            rect.stroke.map(|stroke| stroke.alignment),
+            Some(StrokeAlignment::Centered)
+        );
+    }
+
+    #[test]
+    fn test_convex_polygon_is_triangle_fan() {
+        let mut painter = painter();
+        painter.convex_polygon(
+            [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            Color::rgb(1.0, 1.0, 1.0),
+        );
+        let shapes = painter.into_shapes();
+        assert_eq!(shapes.len(), 2);
+        let Shape::Triangle(triangle) = &shapes[1].shape else {
+            panic!("polygon should be triangles");
+        };
+        assert_eq!(
+            triangle.vertices(),
+            [[10.0, 20.0], [30.0, 40.0], [10.0, 40.0]]
+        );
+    }
+
+    #[test]
+    fn test_arc_segments_follow_the_circle() {
+        use std::f32::consts::PI;
+
+        let mut painter = painter();
+        painter.arc(
+            [50.0, 50.0],
+            20.0,
+            PI,
+            2.0 * PI,
+            2.0,
+            Color::rgb(1.0, 1.0, 1.0),
+        );
+        let shapes = painter.into_shapes();
+        assert!(shapes.len() > 8);
+
+        // Each capsule is centered just inside the circle (on its chord)
+        let center = [110.0, 120.0];
+        for shape in &shapes {
+            let middle = [
+                (shape.rect.min[0] + shape.rect.max[0]) * 0.5,
+                (shape.rect.min[1] + shape.rect.max[1]) * 0.5,
+            ];
+            let distance =
+                ((middle[0] - center[0]).powi(2) + (middle[1] - center[1]).powi(2)).sqrt();
+            assert!(distance > 39.0 && distance <= 40.0);
+            // The upper half: clockwise from the left through the top
+            assert!(middle[1] <= center[1]);
+        }
+    }
+
+    #[test]
+    fn test_sector_is_fan_around_center() {
+        use std::f32::consts::PI;
+
+        let mut painter = painter();
+        painter.sector([50.0, 50.0], 20.0, 0.0, 1.5 * PI, Color::rgb(1.0, 1.0, 1.0));
+        let shapes = painter.into_shapes();
+        assert!(shapes.len() > 8);
+        for shape in &shapes {
+            let Shape::Triangle(triangle) = &shape.shape else {
+                panic!("sector should be triangles");
+            };
+            assert_eq!(triangle.vertices()[0], [110.0, 120.0]);
+        }
+    }
+
+    #[test]
+    fn test_text_is_anchored_and_scaled() {
+        let mut painter = painter();
+        painter.text(
+            [100.0, 50.0],
+            TextContent::new("label")
+                .with_font_size(Size::lpx(12.0))
+                .with_h_align(HorizontalAlign::Center)
+                .with_v_align(VerticalAlign::Center),
+        );
+        let shapes = painter.into_shapes();
+        let Shape::Text(text) = &shapes[0].shape else {
+            panic!("text should be a text shape");
+        };
+        assert_eq!(text.rect, Rect::new([210.0, 120.0], [210.0, 120.0]));
+        assert_eq!(text.font_size, Size::lpx(24.0));
+        assert_eq!(text.wrap, Wrap::None);
+    }
+}
@@ -0,0 +1,22 @@
+//! Clipboard access for copy and paste
+//!
+//! Widgets copy with `UiContext::copy_to_clipboard` and paste from
+//! `UiContext::clipboard_text`. On their own these only share text inside the
+//! app; a [`Clipboard`] installed with `UiContext::set_clipboard` connects them
+//! to the system clipboard, so every text widget gets working cut, copy and
+//! paste without the app wiring each one.
+
+/// A clipboard that text can be copied to and pasted from
+///
+/// Backends implement this for the platform clipboard, e.g. the wgpu crate's
+/// `SystemClipboard`. Tests can implement it over shared memory.
+pub trait Clipboard {
+    /// Read the clipboard text, or `None` if it is empty, not text, or
+    /// unavailable
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Replace the clipboard contents with `text`
+    ///
+    /// Failures are ignored; the copy still pastes inside the app.
+    fn set_text(&mut self, text: &str);
+}
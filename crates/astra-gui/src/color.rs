@@ -1,5 +1,6 @@
 /// RGBA color in linear space with values in [0, 1]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -71,6 +72,140 @@ impl Color {
         let darker = l1.min(l2);
         (lighter + 0.05) / (darker + 0.05)
     }
+
+    /// Create a color from HSL in sRGB space
+    ///
+    /// `hue` is in degrees, `saturation` and `lightness` are in [0, 1], matching
+    /// CSS `hsl()`.
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        Self::hsla(hue, saturation, lightness, 1.0)
+    }
+
+    /// Create a color from HSL in sRGB space with alpha
+    pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        let s = saturation.clamp(0.0, 1.0);
+        let l = lightness.clamp(0.0, 1.0);
+        let a = s * l.min(1.0 - l);
+        let channel = |n: f32| {
+            let k = (n + hue.rem_euclid(360.0) / 30.0) % 12.0;
+            l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        Self::rgba(
+            srgb_to_linear(channel(0.0)),
+            srgb_to_linear(channel(8.0)),
+            srgb_to_linear(channel(4.0)),
+            alpha,
+        )
+    }
+
+    /// Convert to HSL in sRGB space: `[hue in degrees, saturation, lightness]`
+    pub fn to_hsl(&self) -> [f32; 3] {
+        let r = linear_to_srgb(self.r);
+        let g = linear_to_srgb(self.g);
+        let b = linear_to_srgb(self.b);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta <= f32::EPSILON {
+            return [0.0, 0.0, lightness];
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        [hue * 60.0, saturation, lightness]
+    }
+
+    /// Create a color from OKLab (`lightness` in [0, 1])
+    ///
+    /// Colors outside the sRGB gamut are clamped.
+    pub fn oklab(lightness: f32, a: f32, b: f32) -> Self {
+        Self::oklaba(lightness, a, b, 1.0)
+    }
+
+    /// Create a color from OKLab with alpha
+    pub fn oklaba(lightness: f32, a: f32, b: f32, alpha: f32) -> Self {
+        let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+        Self::rgba(
+            (4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s).clamp(0.0, 1.0),
+            (-1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s).clamp(0.0, 1.0),
+            (-0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s).clamp(0.0, 1.0),
+            alpha,
+        )
+    }
+
+    /// Convert to OKLab: `[lightness, a, b]`
+    pub fn to_oklab(&self) -> [f32; 3] {
+        let l = (0.412_221_46 * self.r + 0.536_332_55 * self.g + 0.051_445_995 * self.b).cbrt();
+        let m = (0.211_903_5 * self.r + 0.680_699_5 * self.g + 0.107_396_96 * self.b).cbrt();
+        let s = (0.088_302_46 * self.r + 0.281_718_85 * self.g + 0.629_978_7 * self.b).cbrt();
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        ]
+    }
+
+    /// Create a color from OKLCH, matching CSS `oklch()`
+    ///
+    /// `lightness` is in [0, 1], `chroma` is typically in [0, 0.4] and `hue` is
+    /// in degrees. Colors outside the sRGB gamut are clamped.
+    pub fn oklch(lightness: f32, chroma: f32, hue: f32) -> Self {
+        Self::oklcha(lightness, chroma, hue, 1.0)
+    }
+
+    /// Create a color from OKLCH with alpha
+    pub fn oklcha(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Self {
+        let (sin, cos) = hue.to_radians().sin_cos();
+        Self::oklaba(lightness, chroma * cos, chroma * sin, alpha)
+    }
+
+    /// Convert to OKLCH: `[lightness, chroma, hue in degrees]`
+    pub fn to_oklch(&self) -> [f32; 3] {
+        let [l, a, b] = self.to_oklab();
+        [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+    }
+}
+
+/// Color space in which colors are interpolated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorSpace {
+    /// Component-wise in linear RGB; cheap, but mid-colors between distant
+    /// hues turn gray and muddy
+    LinearRgb,
+    /// Straight line in OKLab; perceptually even lightness, no hue detours
+    #[default]
+    Oklab,
+    /// Along the shorter hue arc in OKLCH; keeps mid-colors saturated
+    Oklch,
+}
+
+/// Exact sRGB transfer function (encoded [0, 1] to linear)
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Inverse sRGB transfer function (linear to encoded [0, 1])
+fn linear_to_srgb(c: f32) -> f32 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
 }
 
 /// CSS color constants
@@ -223,3 +358,134 @@ pub mod catppuccin {
         pub const CRUST: Color = Color::srgba(24, 25, 38, 255);
     }
 }
+
+/// Nord color palette (`NORD0`..`NORD15`)
+pub mod nord {
+    use super::Color;
+
+    // Polar Night: backgrounds
+    pub const NORD0: Color = Color::srgba(46, 52, 64, 255);
+    pub const NORD1: Color = Color::srgba(59, 66, 82, 255);
+    pub const NORD2: Color = Color::srgba(67, 76, 94, 255);
+    pub const NORD3: Color = Color::srgba(76, 86, 106, 255);
+    // Snow Storm: text
+    pub const NORD4: Color = Color::srgba(216, 222, 233, 255);
+    pub const NORD5: Color = Color::srgba(229, 233, 240, 255);
+    pub const NORD6: Color = Color::srgba(236, 239, 244, 255);
+    // Frost: accents
+    pub const NORD7: Color = Color::srgba(143, 188, 187, 255);
+    pub const NORD8: Color = Color::srgba(136, 192, 208, 255);
+    pub const NORD9: Color = Color::srgba(129, 161, 193, 255);
+    pub const NORD10: Color = Color::srgba(94, 129, 172, 255);
+    // Aurora: red, orange, yellow, green, purple
+    pub const NORD11: Color = Color::srgba(191, 97, 106, 255);
+    pub const NORD12: Color = Color::srgba(208, 135, 112, 255);
+    pub const NORD13: Color = Color::srgba(235, 203, 139, 255);
+    pub const NORD14: Color = Color::srgba(163, 190, 140, 255);
+    pub const NORD15: Color = Color::srgba(180, 142, 173, 255);
+}
+
+/// Gruvbox color palette
+pub mod gruvbox {
+    use super::Color;
+
+    pub mod dark {
+        use super::Color;
+
+        pub const BG0_H: Color = Color::srgba(29, 32, 33, 255);
+        pub const BG0: Color = Color::srgba(40, 40, 40, 255);
+        pub const BG0_S: Color = Color::srgba(50, 48, 47, 255);
+        pub const BG1: Color = Color::srgba(60, 56, 54, 255);
+        pub const BG2: Color = Color::srgba(80, 73, 69, 255);
+        pub const BG3: Color = Color::srgba(102, 92, 84, 255);
+        pub const BG4: Color = Color::srgba(124, 111, 100, 255);
+        pub const FG: Color = Color::srgba(235, 219, 178, 255);
+        pub const FG2: Color = Color::srgba(213, 196, 161, 255);
+        pub const FG3: Color = Color::srgba(189, 174, 147, 255);
+        pub const FG4: Color = Color::srgba(168, 153, 132, 255);
+        pub const GRAY: Color = Color::srgba(146, 131, 116, 255);
+        pub const RED: Color = Color::srgba(251, 73, 52, 255);
+        pub const GREEN: Color = Color::srgba(184, 187, 38, 255);
+        pub const YELLOW: Color = Color::srgba(250, 189, 47, 255);
+        pub const BLUE: Color = Color::srgba(131, 165, 152, 255);
+        pub const PURPLE: Color = Color::srgba(211, 134, 155, 255);
+        pub const AQUA: Color = Color::srgba(142, 192, 124, 255);
+        pub const ORANGE: Color = Color::srgba(254, 128, 25, 255);
+    }
+
+    pub mod light {
+        use super::Color;
+
+        pub const BG0_H: Color = Color::srgba(249, 245, 215, 255);
+        pub const BG0: Color = Color::srgba(251, 241, 199, 255);
+        pub const BG0_S: Color = Color::srgba(242, 229, 188, 255);
+        pub const BG1: Color = Color::srgba(235, 219, 178, 255);
+        pub const BG2: Color = Color::srgba(213, 196, 161, 255);
+        pub const BG3: Color = Color::srgba(189, 174, 147, 255);
+        pub const BG4: Color = Color::srgba(168, 153, 132, 255);
+        pub const FG: Color = Color::srgba(60, 56, 54, 255);
+        pub const FG2: Color = Color::srgba(80, 73, 69, 255);
+        pub const FG3: Color = Color::srgba(102, 92, 84, 255);
+        pub const FG4: Color = Color::srgba(124, 111, 100, 255);
+        pub const GRAY: Color = Color::srgba(146, 131, 116, 255);
+        pub const RED: Color = Color::srgba(157, 0, 6, 255);
+        pub const GREEN: Color = Color::srgba(121, 116, 14, 255);
+        pub const YELLOW: Color = Color::srgba(181, 118, 20, 255);
+        pub const BLUE: Color = Color::srgba(7, 102, 120, 255);
+        pub const PURPLE: Color = Color::srgba(143, 63, 113, 255);
+        pub const AQUA: Color = Color::srgba(66, 123, 88, 255);
+        pub const ORANGE: Color = Color::srgba(175, 58, 3, 255);
+    }
+}
+
+/// Dracula color palette
+pub mod dracula {
+    use super::Color;
+
+    pub const BACKGROUND: Color = Color::srgba(40, 42, 54, 255);
+    /// Darker background used for sidebars and panels
+    pub const BACKGROUND_DARK: Color = Color::srgba(33, 34, 44, 255);
+    /// Darkest background used for title bars and inputs
+    pub const BACKGROUND_DARKER: Color = Color::srgba(25, 26, 33, 255);
+    pub const CURRENT_LINE: Color = Color::srgba(68, 71, 90, 255);
+    pub const SELECTION: Color = Color::srgba(68, 71, 90, 255);
+    pub const FOREGROUND: Color = Color::srgba(248, 248, 242, 255);
+    pub const COMMENT: Color = Color::srgba(98, 114, 164, 255);
+    pub const CYAN: Color = Color::srgba(139, 233, 253, 255);
+    pub const GREEN: Color = Color::srgba(80, 250, 123, 255);
+    pub const ORANGE: Color = Color::srgba(255, 184, 108, 255);
+    pub const PINK: Color = Color::srgba(255, 121, 198, 255);
+    pub const PURPLE: Color = Color::srgba(189, 147, 249, 255);
+    pub const RED: Color = Color::srgba(255, 85, 85, 255);
+    pub const YELLOW: Color = Color::srgba(241, 250, 140, 255);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Color, b: Color) {
+        for (x, y) in [(a.r, b.r), (a.g, b.g), (a.b, b.b), (a.a, b.a)] {
+            assert!((x - y).abs() < 1e-3, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_hsl_matches_css() {
+        assert_close(Color::hsl(0.0, 1.0, 0.5), css::RED);
+        assert_close(Color::hsl(120.0, 1.0, 0.5), css::LIME);
+        assert_close(Color::hsl(-240.0, 1.0, 0.5), css::LIME);
+
+        let [h, s, l] = Color::hsl(200.0, 0.5, 0.4).to_hsl();
+        assert!((h - 200.0).abs() < 0.5 && (s - 0.5).abs() < 0.01 && (l - 0.4).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_oklch_round_trips() {
+        assert_close(Color::oklch(1.0, 0.0, 0.0), Color::rgb(1.0, 1.0, 1.0));
+
+        let color = Color::srgba(30, 144, 200, 255);
+        let [l, c, h] = color.to_oklch();
+        assert_close(Color::oklch(l, c, h), color);
+    }
+}
@@ -0,0 +1,422 @@
+//! Constraint-based layout on top of the flow layouts
+//!
+//! Nodes can declare linear relations between their edges and those of other
+//! nodes, e.g. `this.left == label.right + 8` or `this.width == header.width`.
+//! Constraints are solved after the regular flow layout, so they coexist with
+//! `Layout::Horizontal`, `Layout::Vertical` and `Layout::Stack`: the flow
+//! layout provides the starting positions and the solver only adjusts nodes
+//! that declare constraints.
+//!
+//! This makes it possible to align nodes across different branches of the
+//! tree without nesting extra containers, which is common in toolbars and
+//! inspector panels.
+//!
+//! The solver is a small iterative relaxation rather than a full simplex
+//! solver. It converges for chains of equalities and non-conflicting
+//! inequalities, which covers alignment use cases. Conflicting constraints are
+//! resolved in favour of the constraint declared last.
+
+use crate::layout::Size;
+use crate::node::{Node, NodeId};
+use crate::primitives::Rect;
+use std::collections::HashMap;
+
+/// Maximum number of relaxation passes per frame
+const MAX_ITERATIONS: usize = 32;
+
+/// Changes smaller than this (in physical pixels) count as converged
+const EPSILON: f32 = 0.01;
+
+/// An edge, center line or dimension of a node's rect
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    Width,
+    Height,
+}
+
+impl Anchor {
+    /// Read this anchor's value from a rect
+    pub fn value(&self, rect: &Rect) -> f32 {
+        match self {
+            Anchor::Left => rect.min[0],
+            Anchor::Right => rect.max[0],
+            Anchor::Top => rect.min[1],
+            Anchor::Bottom => rect.max[1],
+            Anchor::CenterX => (rect.min[0] + rect.max[0]) * 0.5,
+            Anchor::CenterY => (rect.min[1] + rect.max[1]) * 0.5,
+            Anchor::Width => rect.width(),
+            Anchor::Height => rect.height(),
+        }
+    }
+
+    /// Whether satisfying this anchor resizes the node instead of moving it
+    pub fn is_dimension(&self) -> bool {
+        matches!(self, Anchor::Width | Anchor::Height)
+    }
+
+    /// Return `rect` adjusted so that this anchor has the given value
+    ///
+    /// Edges and center lines move the rect without resizing it. Width and
+    /// height resize it while keeping the top-left corner fixed.
+    fn apply(&self, rect: Rect, value: f32) -> Rect {
+        let delta = value - self.value(&rect);
+        let mut rect = rect;
+        match self {
+            Anchor::Left | Anchor::Right | Anchor::CenterX => {
+                rect.min[0] += delta;
+                rect.max[0] += delta;
+            }
+            Anchor::Top | Anchor::Bottom | Anchor::CenterY => {
+                rect.min[1] += delta;
+                rect.max[1] += delta;
+            }
+            Anchor::Width => rect.max[0] = rect.min[0] + value.max(0.0),
+            Anchor::Height => rect.max[1] = rect.min[1] + value.max(0.0),
+        }
+        rect
+    }
+}
+
+/// How the constrained anchor relates to the target value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Relation {
+    #[default]
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+/// A linear relation between an anchor of a node and an anchor of another node
+///
+/// The constrained value is `source * multiplier + constant`. Without a
+/// source the anchor is related to the constant alone.
+///
+/// # Example
+///
+/// ```ignore
+/// // this.left == title.right + 8
+/// Constraint::new(Anchor::Left)
+///     .equal_to("title", Anchor::Right)
+///     .with_constant(Size::lpx(8.0))
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraint {
+    anchor: Anchor,
+    relation: Relation,
+    source: Option<(NodeId, Anchor)>,
+    multiplier: f32,
+    constant: Size,
+}
+
+impl Constraint {
+    /// Create a constraint on the given anchor of the node it is attached to
+    ///
+    /// Defaults to `anchor == 0` until a source or constant is set.
+    pub fn new(anchor: Anchor) -> Self {
+        Self {
+            anchor,
+            relation: Relation::Equal,
+            source: None,
+            multiplier: 1.0,
+            constant: Size::Logical(0.0),
+        }
+    }
+
+    /// Require the anchor to equal an anchor of another node
+    pub fn equal_to(self, node: impl Into<NodeId>, anchor: Anchor) -> Self {
+        self.related_to(Relation::Equal, node, anchor)
+    }
+
+    /// Require the anchor to be at least an anchor of another node
+    pub fn at_least(self, node: impl Into<NodeId>, anchor: Anchor) -> Self {
+        self.related_to(Relation::GreaterOrEqual, node, anchor)
+    }
+
+    /// Require the anchor to be at most an anchor of another node
+    pub fn at_most(self, node: impl Into<NodeId>, anchor: Anchor) -> Self {
+        self.related_to(Relation::LessOrEqual, node, anchor)
+    }
+
+    fn related_to(mut self, relation: Relation, node: impl Into<NodeId>, anchor: Anchor) -> Self {
+        self.relation = relation;
+        self.source = Some((node.into(), anchor));
+        self
+    }
+
+    /// Set the relation without changing the source
+    pub fn with_relation(mut self, relation: Relation) -> Self {
+        self.relation = relation;
+        self
+    }
+
+    /// Set the factor applied to the source value
+    pub fn with_multiplier(mut self, multiplier: f32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the constant added to the source value
+    ///
+    /// Only fixed sizes (`Size::Logical` and `Size::Physical`) are meaningful
+    /// here; other variants resolve to zero.
+    pub fn with_constant(mut self, constant: Size) -> Self {
+        self.constant = constant;
+        self
+    }
+
+    pub fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    pub fn relation(&self) -> Relation {
+        self.relation
+    }
+
+    pub fn source(&self) -> Option<&(NodeId, Anchor)> {
+        self.source.as_ref()
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        self.multiplier
+    }
+
+    pub fn constant(&self) -> Size {
+        self.constant
+    }
+}
+
+/// Solved rect for a node, addressed by its child-index path from the root
+#[derive(Clone, Debug)]
+pub(crate) struct SolvedRect {
+    pub path: Vec<usize>,
+    pub rect: Rect,
+    /// Whether the solved rect differs from the flow layout in size
+    pub resized: bool,
+}
+
+/// A node participating in the solve (constrained or referenced by ID)
+struct Entry {
+    path: Vec<usize>,
+    rect: Rect,
+    original: Rect,
+    /// One past the index of the last entry inside this node's subtree
+    subtree_end: usize,
+}
+
+/// Solve all constraints in the tree rooted at `root`
+///
+/// Returns `None` if no node declares constraints. Constraints referencing
+/// unknown or not yet laid out nodes are ignored.
+pub(crate) fn solve(root: &Node, scale_factor: f32) -> Option<Vec<SolvedRect>> {
+    let mut entries = Vec::new();
+    let mut ids = HashMap::new();
+    let mut constraints = Vec::new();
+    let mut path = Vec::new();
+    collect(root, &mut path, &mut entries, &mut ids, &mut constraints);
+
+    if constraints.is_empty() {
+        return None;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut max_change: f32 = 0.0;
+
+        for &(owner, constraint) in &constraints {
+            let constant = constraint.constant.resolve_physical_or_zero(scale_factor);
+            let target = match &constraint.source {
+                Some((id, anchor)) => match ids.get(id.as_str()) {
+                    Some(&source) => {
+                        let source: &Entry = &entries[source];
+                        anchor.value(&source.rect) * constraint.multiplier + constant
+                    }
+                    None => continue,
+                },
+                None => constant,
+            };
+
+            let current = constraint.anchor.value(&entries[owner].rect);
+            let satisfied = match constraint.relation {
+                Relation::Equal => (current - target).abs() <= EPSILON,
+                Relation::LessOrEqual => current <= target + EPSILON,
+                Relation::GreaterOrEqual => current >= target - EPSILON,
+            };
+            if satisfied {
+                continue;
+            }
+
+            max_change = max_change.max((current - target).abs());
+            let old = entries[owner].rect;
+            let new = constraint.anchor.apply(old, target);
+            entries[owner].rect = new;
+
+            // Moving a node carries the rest of its subtree along
+            if !constraint.anchor.is_dimension() {
+                let dx = new.min[0] - old.min[0];
+                let dy = new.min[1] - old.min[1];
+                let end = entries[owner].subtree_end;
+                for entry in &mut entries[owner + 1..end] {
+                    entry.rect.min[0] += dx;
+                    entry.rect.max[0] += dx;
+                    entry.rect.min[1] += dy;
+                    entry.rect.max[1] += dy;
+                }
+            }
+        }
+
+        if max_change <= EPSILON {
+            break;
+        }
+    }
+
+    Some(
+        entries
+            .into_iter()
+            .filter(|e| e.rect != e.original)
+            .map(|e| SolvedRect {
+                resized: (e.rect.width() - e.original.width()).abs() > EPSILON
+                    || (e.rect.height() - e.original.height()).abs() > EPSILON,
+                path: e.path,
+                rect: e.rect,
+            })
+            .collect(),
+    )
+}
+
+/// Gather laid out nodes that have an ID or constraints, in pre-order
+fn collect<'a>(
+    node: &'a Node,
+    path: &mut Vec<usize>,
+    entries: &mut Vec<Entry>,
+    ids: &mut HashMap<&'a str, usize>,
+    constraints: &mut Vec<(usize, &'a Constraint)>,
+) {
+    let index = match node.computed_layout() {
+        Some(computed) if node.id().is_some() || !node.constraints().is_empty() => {
+            let index = entries.len();
+            entries.push(Entry {
+                path: path.clone(),
+                rect: computed.rect,
+                original: computed.rect,
+                subtree_end: index + 1,
+            });
+            if let Some(id) = node.id() {
+                ids.insert(id.as_str(), index);
+            }
+            constraints.extend(node.constraints().iter().map(|c| (index, c)));
+            Some(index)
+        }
+        _ => None,
+    };
+
+    for (i, child) in node.children().iter().enumerate() {
+        path.push(i);
+        collect(child, path, entries, ids, constraints);
+        path.pop();
+    }
+
+    if let Some(index) = index {
+        entries[index].subtree_end = entries.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+
+    fn rect_of(node: &Node, id: &str) -> Rect {
+        fn find<'a>(node: &'a Node, id: &str) -> Option<&'a Node> {
+            if node.id().is_some_and(|n| n.as_str() == id) {
+                return Some(node);
+            }
+            node.children().iter().find_map(|c| find(c, id))
+        }
+        find(node, id)
+            .and_then(|n| n.computed_layout())
+            .map(|c| c.rect)
+            .expect("node not found or not laid out")
+    }
+
+    #[test]
+    fn test_edges_align_across_branches() {
+        let mut root = Node::new()
+            .with_width(Size::lpx(400.0))
+            .with_height(Size::lpx(200.0))
+            .with_children(vec![
+                Node::new()
+                    .with_height(Size::lpx(50.0))
+                    .with_child(Node::new().with_id("label").with_size(120.0, 20.0)),
+                Node::new().with_height(Size::lpx(50.0)).with_child(
+                    Node::new()
+                        .with_id("field")
+                        .with_size(80.0, 20.0)
+                        .with_constraint(
+                            Constraint::new(Anchor::Left)
+                                .equal_to("label", Anchor::Right)
+                                .with_constant(Size::lpx(8.0)),
+                        ),
+                ),
+            ]);
+
+        root.compute_layout(Rect::from_min_size([0.0, 0.0], [400.0, 200.0]));
+
+        let label = rect_of(&root, "label");
+        let field = rect_of(&root, "field");
+        assert_eq!(field.min[0], label.max[0] + 8.0);
+        // Vertical position from the flow layout is preserved
+        assert_eq!(field.min[1], 50.0);
+        assert_eq!(field.width(), 80.0);
+    }
+
+    #[test]
+    fn test_width_constraint_relayouts_siblings() {
+        let mut root = Node::new()
+            .with_width(Size::lpx(400.0))
+            .with_height(Size::lpx(100.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                Node::new()
+                    .with_id("a")
+                    .with_size(50.0, 20.0)
+                    .with_constraint(
+                        Constraint::new(Anchor::Width)
+                            .equal_to("reference", Anchor::Width)
+                            .with_multiplier(0.5),
+                    ),
+                Node::new().with_id("b").with_size(30.0, 20.0),
+                Node::new().with_id("reference").with_size(200.0, 20.0),
+            ]);
+
+        root.compute_layout(Rect::from_min_size([0.0, 0.0], [400.0, 100.0]));
+
+        assert_eq!(rect_of(&root, "a").width(), 100.0);
+        // The sibling is pushed along by the resized node
+        assert_eq!(rect_of(&root, "b").min[0], 100.0);
+        assert_eq!(rect_of(&root, "reference").width(), 200.0);
+    }
+
+    #[test]
+    fn test_inequality_only_applies_when_violated() {
+        let mut root = Node::new()
+            .with_width(Size::lpx(400.0))
+            .with_height(Size::lpx(100.0))
+            .with_layout_direction(Layout::Horizontal)
+            .with_children(vec![
+                Node::new().with_id("a").with_size(50.0, 20.0),
+                Node::new()
+                    .with_id("b")
+                    .with_size(50.0, 20.0)
+                    .with_constraint(Constraint::new(Anchor::Left).at_least("a", Anchor::Left)),
+            ]);
+
+        root.compute_layout(Rect::from_min_size([0.0, 0.0], [400.0, 100.0]));
+
+        assert_eq!(rect_of(&root, "b").min[0], 50.0);
+    }
+}
@@ -30,6 +30,22 @@ impl FontWeight {
             FontWeight::Custom(w) => w.clamp(100, 900),
         }
     }
+
+    /// Inverse of `to_weight`: standard weights map to their named variant
+    pub fn from_weight(weight: u16) -> Self {
+        match weight {
+            100 => FontWeight::Thin,
+            200 => FontWeight::ExtraLight,
+            300 => FontWeight::Light,
+            400 => FontWeight::Normal,
+            500 => FontWeight::Medium,
+            600 => FontWeight::SemiBold,
+            700 => FontWeight::Bold,
+            800 => FontWeight::ExtraBold,
+            900 => FontWeight::Black,
+            w => FontWeight::Custom(w),
+        }
+    }
 }
 
 impl Default for FontWeight {
@@ -46,14 +62,133 @@ pub enum FontStyle {
     Italic,
 }
 
+/// Font width, for families with condensed or expanded faces
+///
+/// Picks the family's face closest to this width. Variable fonts are only
+/// varied along their weight axis, so their widths need faces of their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontWidth {
+    UltraCondensed, // 50%
+    ExtraCondensed, // 62.5%
+    Condensed,      // 75%
+    SemiCondensed,  // 87.5%
+    #[default]
+    Normal, // 100%
+    SemiExpanded,   // 112.5%
+    Expanded,       // 125%
+    ExtraExpanded,  // 150%
+    UltraExpanded,  // 200%
+}
+
+impl FontWidth {
+    /// Width as a percentage of the normal width (the `wdth` axis value)
+    pub fn to_percentage(self) -> f32 {
+        match self {
+            FontWidth::UltraCondensed => 50.0,
+            FontWidth::ExtraCondensed => 62.5,
+            FontWidth::Condensed => 75.0,
+            FontWidth::SemiCondensed => 87.5,
+            FontWidth::Normal => 100.0,
+            FontWidth::SemiExpanded => 112.5,
+            FontWidth::Expanded => 125.0,
+            FontWidth::ExtraExpanded => 150.0,
+            FontWidth::UltraExpanded => 200.0,
+        }
+    }
+}
+
+/// An OpenType feature setting applied when shaping text
+///
+/// The constants enable common features; [`FontFeature::off`] turns one off
+/// (e.g. ligatures, which fonts enable by default). Fonts without a feature
+/// ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontFeature {
+    /// Four-letter OpenType feature tag, e.g. `*b"tnum"`
+    pub tag: [u8; 4],
+    /// 0 turns the feature off, 1 on; some features pick an alternate by index
+    pub value: u32,
+}
+
+impl FontFeature {
+    /// Kerning between character pairs
+    pub const KERNING: Self = Self::new(*b"kern", 1);
+    /// Standard ligatures (fi, fl, ...)
+    pub const STANDARD_LIGATURES: Self = Self::new(*b"liga", 1);
+    /// Contextual ligatures
+    pub const CONTEXTUAL_LIGATURES: Self = Self::new(*b"clig", 1);
+    /// Contextual alternates (e.g. coding font arrows like `->`)
+    pub const CONTEXTUAL_ALTERNATES: Self = Self::new(*b"calt", 1);
+    /// Discretionary ligatures (off by default in most fonts)
+    pub const DISCRETIONARY_LIGATURES: Self = Self::new(*b"dlig", 1);
+    /// Lowercase letters as small capitals
+    pub const SMALL_CAPS: Self = Self::new(*b"smcp", 1);
+    /// Uppercase letters as small capitals
+    pub const CAPITALS_TO_SMALL_CAPS: Self = Self::new(*b"c2sc", 1);
+    /// Digits of equal width, so changing numbers don't shift
+    pub const TABULAR_NUMBERS: Self = Self::new(*b"tnum", 1);
+    /// Digits of their natural widths
+    pub const PROPORTIONAL_NUMBERS: Self = Self::new(*b"pnum", 1);
+    /// Digits at capital height
+    pub const LINING_NUMBERS: Self = Self::new(*b"lnum", 1);
+    /// Digits with ascenders and descenders, for running text
+    pub const OLDSTYLE_NUMBERS: Self = Self::new(*b"onum", 1);
+    /// Zero with a slash, to tell it from the letter O
+    pub const SLASHED_ZERO: Self = Self::new(*b"zero", 1);
+    /// Diagonal fractions (1/2 drawn as ½)
+    pub const FRACTIONS: Self = Self::new(*b"frac", 1);
+
+    pub const fn new(tag: [u8; 4], value: u32) -> Self {
+        Self { tag, value }
+    }
+
+    /// Stylistic set `n` (1-20, `ss01`..`ss20`), the font's own alternate glyphs
+    pub const fn stylistic_set(n: u8) -> Self {
+        let n = if n < 1 {
+            1
+        } else if n > 20 {
+            20
+        } else {
+            n
+        };
+        Self::new([b's', b's', b'0' + n / 10, b'0' + n % 10], 1)
+    }
+
+    /// This feature turned off
+    pub const fn off(self) -> Self {
+        Self::new(self.tag, 0)
+    }
+}
+
 /// Content that can be displayed in a node
 ///
 /// Content nodes are leaf nodes that cannot have children. They represent
 /// actual UI elements like text, inputs, images, etc.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Content {
     /// Text content with styling
     Text(TextContent),
+    /// Source code, drawn as monospace text colored by a [`Highlighter`](crate::Highlighter)
+    Code(CodeContent),
+}
+
+impl Content {
+    /// The text drawn for this content (code draws its highlighted text)
+    pub fn text(&self) -> &TextContent {
+        match self {
+            Content::Text(text) => text,
+            Content::Code(code) => &code.text,
+        }
+    }
+
+    /// Mutable access to the text drawn for this content
+    pub fn text_mut(&mut self) -> &mut TextContent {
+        match self {
+            Content::Text(text) => text,
+            Content::Code(code) => &mut code.text,
+        }
+    }
 }
 
 /// Text wrapping mode
@@ -75,11 +210,160 @@ impl Default for Wrap {
     }
 }
 
+/// What happens to a line of text wider than its node
+///
+/// Only lines that don't fit after wrapping are affected, so it mostly matters
+/// for `Wrap::None` labels in fixed-width nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextOverflow {
+    /// Draw the whole line and let the node's clip rect cut it
+    #[default]
+    Clip,
+    /// Drop the glyphs that don't fit and end the line with "…"
+    Ellipsis,
+    /// Fade the line out towards the node's right edge
+    Fade,
+}
+
+/// Which text properties fall back to values inherited from ancestor nodes
+///
+/// Ancestors provide values with `Node::with_font_size` and
+/// `Node::with_text_color`; the nearest ancestor that sets a property wins.
+/// Without such an ancestor the text content's own value is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextInherit {
+    /// Inherit the font size
+    pub font_size: bool,
+    /// Inherit the text color
+    pub color: bool,
+}
+
+impl TextInherit {
+    /// Inherit every inheritable property
+    pub const ALL: Self = Self {
+        font_size: true,
+        color: true,
+    };
+
+    /// Use the text content's own values only
+    pub const NONE: Self = Self {
+        font_size: false,
+        color: false,
+    };
+}
+
+/// A run of text with its own styling inside rich text
+///
+/// Unset properties fall back to the surrounding [`TextContent`]'s values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    /// The text of this span
+    pub text: String,
+    /// Text color override
+    pub color: Option<Color>,
+    /// Font weight override
+    pub font_weight: Option<FontWeight>,
+    /// Font style override
+    pub font_style: Option<FontStyle>,
+    /// Font size as a multiple of the text's font size (like CSS `em`), so
+    /// spans follow zoom and inherited font sizes
+    pub font_scale: Option<f32>,
+    /// Draw a line under this span
+    pub underline: bool,
+}
+
+impl TextSpan {
+    /// Create a span that uses the text's styling
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            font_weight: None,
+            font_style: None,
+            font_scale: None,
+            underline: false,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_font_weight(mut self, weight: FontWeight) -> Self {
+        self.font_weight = Some(weight);
+        self
+    }
+
+    pub fn with_font_style(mut self, style: FontStyle) -> Self {
+        self.font_style = Some(style);
+        self
+    }
+
+    /// Set the font size relative to the text's font size
+    pub fn with_font_scale(mut self, scale: f32) -> Self {
+        self.font_scale = Some(scale);
+        self
+    }
+
+    /// Convenience method to set bold weight
+    pub fn bold(self) -> Self {
+        self.with_font_weight(FontWeight::Bold)
+    }
+
+    /// Convenience method to set italic style
+    pub fn italic(self) -> Self {
+        self.with_font_style(FontStyle::Italic)
+    }
+
+    /// Underline this span
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+}
+
+impl From<&str> for TextSpan {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for TextSpan {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Outline drawn around every glyph of a text, behind its fill
+///
+/// Keeps text legible over busy or unknown backgrounds (game HUDs, labels on
+/// maps and video). The outline grows outwards from the glyph edges and doesn't
+/// affect layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextOutline {
+    /// Distance the outline extends past the glyph edges; scales with the text
+    pub width: crate::layout::Size,
+    /// Outline color
+    pub color: Color,
+}
+
+impl TextOutline {
+    pub fn new(width: crate::layout::Size, color: Color) -> Self {
+        Self { width, color }
+    }
+}
+
 /// Text content configuration
 #[derive(Debug, Clone)]
 pub struct TextContent {
-    /// The text to display
+    /// The text to display (with spans, their concatenated text)
     pub text: String,
+    /// Styled runs making up the text; empty for plain text
+    ///
+    /// Set with [`TextContent::rich`] or [`TextContent::with_span`], which keep
+    /// `text` in sync.
+    pub spans: Vec<TextSpan>,
     /// Font size in logical pixels
     pub font_size: crate::layout::Size,
     /// Text color
@@ -90,39 +374,111 @@ pub struct TextContent {
     pub v_align: VerticalAlign,
     /// Text wrapping mode
     pub wrap: Wrap,
+    /// Handling of lines wider than the node (default: Clip)
+    pub overflow: TextOverflow,
+    /// Maximum number of lines shown; later lines are dropped (default: no limit)
+    pub max_lines: Option<usize>,
     /// Line height as a multiplier of font size (default: 1.2)
     pub line_height_multiplier: f32,
     /// Font weight (default: Normal/400)
     pub font_weight: FontWeight,
     /// Font style (default: Normal)
     pub font_style: FontStyle,
+    /// Preferred font family (default: the backend's default font, Inter)
+    ///
+    /// Characters the family has no glyphs for are taken from the backend's
+    /// fallback families, resolved per run.
+    pub font_family: Option<String>,
+    /// OpenType features applied on top of the font's defaults, later settings
+    /// of a tag winning (default: none)
+    pub font_features: Vec<FontFeature>,
+    /// Font width (default: Normal)
+    pub font_width: FontWidth,
+    /// Distance between tab stops, in widths of a space (default: 8)
+    ///
+    /// A tab advances to the next stop, measured from the start of its line.
+    pub tab_width: u16,
+    /// Outline around the glyphs (default: none)
+    pub outline: Option<TextOutline>,
+    /// Properties taken from ancestors (default: all, cleared by `with_font_size`/`with_color`)
+    pub inherit: TextInherit,
 }
 
 impl TextContent {
+    /// Default distance between tab stops, in widths of a space
+    pub const DEFAULT_TAB_WIDTH: u16 = 8;
+
     /// Create new text content with default styling
     pub fn new(text: impl Into<String>) -> Self {
         Self {
             text: text.into(),
+            spans: Vec::new(),
             font_size: crate::layout::Size::lpx(16.0),
             color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             h_align: HorizontalAlign::Left,
             v_align: VerticalAlign::Top,
             wrap: Wrap::Word,
+            overflow: TextOverflow::Clip,
+            max_lines: None,
             line_height_multiplier: 1.2,
             font_weight: FontWeight::Normal,
             font_style: FontStyle::Normal,
+            font_family: None,
+            font_features: Vec::new(),
+            font_width: FontWidth::Normal,
+            tab_width: Self::DEFAULT_TAB_WIDTH,
+            outline: None,
+            inherit: TextInherit::ALL,
         }
     }
 
-    /// Set the font size
+    /// Create rich text from styled spans
+    ///
+    /// ```ignore
+    /// TextContent::rich([
+    ///     TextSpan::new("Press "),
+    ///     TextSpan::new("Save").bold().with_color(mocha::LAVENDER),
+    ///     TextSpan::new(" to keep your changes"),
+    /// ])
+    /// ```
+    pub fn rich(spans: impl IntoIterator<Item = impl Into<TextSpan>>) -> Self {
+        spans
+            .into_iter()
+            .fold(Self::new(String::new()), |content, span| {
+                content.with_span(span)
+            })
+    }
+
+    /// Append a styled span
+    ///
+    /// Text set with `new` before the first span becomes a plain span of its own.
+    pub fn with_span(mut self, span: impl Into<TextSpan>) -> Self {
+        if self.spans.is_empty() && !self.text.is_empty() {
+            self.spans.push(TextSpan::new(self.text.clone()));
+        }
+        let span = span.into();
+        self.text.push_str(&span.text);
+        self.spans.push(span);
+        self
+    }
+
+    /// Set the font size (no longer inherited)
     pub fn with_font_size(mut self, size: crate::layout::Size) -> Self {
         self.font_size = size;
+        self.inherit.font_size = false;
         self
     }
 
-    /// Set the text color
+    /// Set the text color (no longer inherited)
     pub fn with_color(mut self, color: Color) -> Self {
         self.color = color;
+        self.inherit.color = false;
+        self
+    }
+
+    /// Set which properties are inherited from ancestor nodes
+    pub fn with_inherit(mut self, inherit: TextInherit) -> Self {
+        self.inherit = inherit;
         self
     }
 
@@ -144,6 +500,33 @@ impl TextContent {
         self
     }
 
+    /// Set how lines wider than the node end
+    ///
+    /// ```ignore
+    /// TextContent::new("a label that may not fit")
+    ///     .with_wrap(Wrap::None)
+    ///     .with_overflow(TextOverflow::Ellipsis)
+    /// ```
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Show at most `lines` lines, e.g. for previews in cards and list items
+    ///
+    /// The text measures as tall as the lines shown. With
+    /// [`TextOverflow::Ellipsis`] the last line ends with "…" when text was cut.
+    ///
+    /// ```ignore
+    /// TextContent::new(article.body)
+    ///     .with_max_lines(3)
+    ///     .with_overflow(TextOverflow::Ellipsis)
+    /// ```
+    pub fn with_max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines.max(1));
+        self
+    }
+
     /// Set line height multiplier
     pub fn with_line_height(mut self, multiplier: f32) -> Self {
         self.line_height_multiplier = multiplier;
@@ -162,6 +545,69 @@ impl TextContent {
         self
     }
 
+    /// Set the preferred font family, by family name (e.g. `"JetBrains Mono"`)
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Apply an OpenType feature
+    ///
+    /// ```ignore
+    /// TextContent::new(format!("{fps:.1} fps"))
+    ///     .with_font_feature(FontFeature::TABULAR_NUMBERS)
+    ///     .with_font_feature(FontFeature::SLASHED_ZERO)
+    /// ```
+    pub fn with_font_feature(mut self, feature: FontFeature) -> Self {
+        self.font_features.push(feature);
+        self
+    }
+
+    /// Set the font width
+    pub fn with_font_width(mut self, width: FontWidth) -> Self {
+        self.font_width = width;
+        self
+    }
+
+    /// Set the distance between tab stops, in widths of a space (at least 1)
+    ///
+    /// ```ignore
+    /// TextContent::new(source).with_font_family("JetBrains Mono").with_tab_width(4)
+    /// ```
+    pub fn with_tab_width(mut self, spaces: u16) -> Self {
+        self.tab_width = spaces.max(1);
+        self
+    }
+
+    /// Convenience method to draw digits at equal widths, so numbers that
+    /// change every frame (counters, timers, dashboards) don't jitter
+    pub fn tabular_numbers(self) -> Self {
+        self.with_font_feature(FontFeature::TABULAR_NUMBERS)
+    }
+
+    /// Convenience method to draw lowercase letters as small capitals
+    pub fn small_caps(self) -> Self {
+        self.with_font_feature(FontFeature::SMALL_CAPS)
+    }
+
+    /// Convenience method to turn off standard and contextual ligatures
+    pub fn without_ligatures(self) -> Self {
+        self.with_font_feature(FontFeature::STANDARD_LIGATURES.off())
+            .with_font_feature(FontFeature::CONTEXTUAL_LIGATURES.off())
+    }
+
+    /// Outline the glyphs with `width` of `color`
+    ///
+    /// ```ignore
+    /// TextContent::new("Wave 3")
+    ///     .with_font_size(Size::lpx(32.0))
+    ///     .with_outline(Size::lpx(2.0), Color::rgb(0.0, 0.0, 0.0))
+    /// ```
+    pub fn with_outline(mut self, width: crate::layout::Size, color: Color) -> Self {
+        self.outline = Some(TextOutline::new(width, color));
+        self
+    }
+
     /// Convenience method to set bold weight
     pub fn bold(mut self) -> Self {
         self.font_weight = FontWeight::Bold;
@@ -175,6 +621,93 @@ impl TextContent {
     }
 }
 
+/// Family code is drawn in by default (bundled with `astra-gui-fonts`)
+pub const DEFAULT_CODE_FAMILY: &str = "JetBrains Mono";
+
+/// Source code content for editors, consoles and diff viewers
+///
+/// Laid out and drawn as its [`TextContent`], whose spans hold the syntax
+/// highlighting once [`CodeContent::highlighted`] (or
+/// [`UiContext::highlight`](crate::UiContext::highlight)) has run; until then the
+/// code is drawn in the text color.
+///
+/// ```ignore
+/// let code = CodeContent::new("fn main() {}", "rust").highlighted(&highlighter);
+/// Node::new().with_content(Content::Code(code))
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeContent {
+    /// Language name or file extension the highlighter picks a grammar by
+    /// (e.g. `"rust"`, `"rs"`, `"diff"`)
+    pub language: String,
+    /// The code and its styling; `text.text` is the code
+    pub text: TextContent,
+}
+
+impl CodeContent {
+    /// Create unhighlighted code in [`DEFAULT_CODE_FAMILY`], without wrapping
+    pub fn new(code: impl Into<String>, language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            text: TextContent::new(code)
+                .with_wrap(Wrap::None)
+                .with_font_family(DEFAULT_CODE_FAMILY),
+        }
+    }
+
+    /// The source code
+    pub fn code(&self) -> &str {
+        &self.text.text
+    }
+
+    /// Color the code with `highlighter`
+    ///
+    /// Spans that don't add up to the code are ignored, leaving it plain.
+    pub fn highlighted(mut self, highlighter: &dyn crate::Highlighter) -> Self {
+        let spans = highlighter.highlight(self.code(), &self.language);
+        self.set_spans(spans);
+        self
+    }
+
+    /// Replace the highlighting spans, if their texts add up to the code
+    pub(crate) fn set_spans(&mut self, spans: Vec<TextSpan>) {
+        let mut rest = self.text.text.as_str();
+        for span in &spans {
+            match rest.strip_prefix(span.text.as_str()) {
+                Some(tail) => rest = tail,
+                None => return,
+            }
+        }
+        if rest.is_empty() {
+            self.text.spans = spans;
+        }
+    }
+
+    /// Set the font size (no longer inherited)
+    pub fn with_font_size(mut self, size: crate::layout::Size) -> Self {
+        self.text = self.text.with_font_size(size);
+        self
+    }
+
+    /// Set the color of code the highlighter leaves uncolored (no longer inherited)
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.text = self.text.with_color(color);
+        self
+    }
+
+    /// Set the font family (default: [`DEFAULT_CODE_FAMILY`])
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.text = self.text.with_font_family(family);
+        self
+    }
+
+    /// Set text wrapping mode (default: [`Wrap::None`])
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.text = self.text.with_wrap(wrap);
+        self
+    }
+}
+
 /// Horizontal text alignment
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HorizontalAlign {
@@ -7,6 +7,7 @@
 //! - Interactive state manager (handles style transitions)
 //! - Widget memory (stores internal widget state like text buffers, cursors)
 //! - Content measurer (for text measurement)
+//! - Code highlighter (for `Content::Code`)
 //! - ID stack (for generating unique widget IDs)
 //!
 //! This design is inspired by egui's `Context` and enables a clean API where
@@ -14,11 +15,22 @@
 //! while the context handles all the internal complexity.
 
 use crate::{
-    ContentMeasurer, EventDispatcher, InputState, InteractionEvent, InteractionState,
-    InteractiveStateManager, IntrinsicSize, MeasureTextRequest, MouseButton, Node, NodeId,
-    TargetedEvent, WidgetMemory,
+    hit_test_point, Clipboard, CodeContent, ContentMeasurer, CustomEvent, DragGhost,
+    EventDispatcher, Highlighter, InputPlayback, InputRecording, InputState, InteractionEvent,
+    InteractionHeatmap, InteractionState, InteractiveStateManager, IntrinsicSize, Key,
+    MeasureTextRequest, MouseButton, Node, NodeId, RecordedFrame, RetainedTree, ScrollMetrics,
+    StyleSheet, TargetedEvent, TextSpan, Theme, TokenTable, TokenValue, WidgetMemory,
 };
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Number of frame times kept by default (two seconds at 60 FPS)
+const DEFAULT_FRAME_HISTORY_LEN: usize = 120;
+
+/// Highlighted snippets kept before the highlight cache starts over
+const HIGHLIGHT_CACHE_CAPACITY: usize = 256;
 
 /// The main UI context that coordinates all UI operations
 ///
@@ -50,6 +62,15 @@ pub struct UiContext {
     /// Events from the last frame (available during UI building)
     events: Vec<TargetedEvent>,
 
+    /// Events sent by widgets with `send_event`, delivered with the next frame's events
+    sent_events: Vec<TargetedEvent>,
+
+    /// Window and URL requests for the platform layer, until taken
+    platform_requests: Vec<crate::PlatformRequest>,
+
+    /// Size of the root node laid out last frame
+    viewport_size: (f32, f32),
+
     /// Interaction states for nodes (for style transitions)
     interaction_states: HashMap<NodeId, InteractionState>,
 
@@ -65,6 +86,12 @@ pub struct UiContext {
     /// Content measurer for text measurement
     measurer: Option<Box<dyn ContentMeasurer>>,
 
+    /// Highlighter coloring code content in `highlight`
+    highlighter: Option<Box<dyn Highlighter>>,
+
+    /// Spans from `highlighter`, keyed by a hash of language and code
+    highlight_cache: HashMap<u64, Vec<TextSpan>>,
+
     /// ID stack for hierarchical ID generation
     id_stack: Vec<String>,
 
@@ -74,9 +101,66 @@ pub struct UiContext {
     /// Scale factor for the display
     scale_factor: f32,
 
+    /// Accessibility text scale, applied to the root node's font sizes only
+    text_scale: f32,
+
     /// Timestamp of the previous `end_frame`, used to derive the per-frame
     /// delta time that drives smooth scroll animations.
     last_frame_time: Option<std::time::Instant>,
+
+    /// Frame clock in seconds: the time between `end_frame` calls, summed.
+    /// Style animations are sampled at this time.
+    clock: f64,
+
+    /// Durations of the most recent frames in milliseconds (oldest first)
+    frame_times: VecDeque<f32>,
+
+    /// Maximum number of entries kept in `frame_times`
+    frame_history_len: usize,
+
+    /// Input recorded frame by frame, while recording
+    input_recording: Option<InputRecording>,
+
+    /// Recorded input replayed in place of live input, while playing back
+    input_playback: Option<InputPlayback>,
+
+    /// The tree kept between frames in retained mode
+    retained: RetainedTree,
+
+    /// Recorded duration of the frame being replayed, used instead of the
+    /// measured one
+    playback_frame_time: Option<f32>,
+
+    /// Whether a node closure consumed the event being handled
+    propagation_stopped: bool,
+
+    /// Accumulated hover/click positions for the heatmap debug overlay
+    heatmap: InteractionHeatmap,
+
+    /// Whether `end_frame` records samples into the heatmap
+    heatmap_recording: bool,
+
+    /// Semantic color palette that widgets resolve their styles against
+    theme: Theme,
+
+    /// Class rules resolved onto nodes in `end_frame`
+    style_sheet: StyleSheet,
+
+    /// Design-token values that `var(..)` bindings resolve against
+    tokens: TokenTable,
+
+    /// Preview shown under the pointer for the active drag, with its source node
+    drag_ghost: Option<(NodeId, DragGhost)>,
+
+    /// Text that widgets paste; copies land here and backends keep it in sync
+    /// with the system clipboard
+    clipboard_text: String,
+
+    /// Text copied since the backend last took it with `take_copied_text`
+    copied_text: Option<String>,
+
+    /// Clipboard that copies are written to and pastes are read from
+    clipboard: Option<Box<dyn Clipboard>>,
 }
 
 impl UiContext {
@@ -85,15 +169,38 @@ impl UiContext {
         Self {
             input: InputState::new(),
             events: Vec::new(),
+            sent_events: Vec::new(),
+            platform_requests: Vec::new(),
+            viewport_size: (0.0, 0.0),
             interaction_states: HashMap::new(),
             dispatcher: EventDispatcher::new(),
             state_manager: InteractiveStateManager::new(),
             memory: WidgetMemory::new(),
             measurer: None,
+            highlighter: None,
+            highlight_cache: HashMap::new(),
             id_stack: Vec::new(),
             id_counter: 0,
             scale_factor: 1.0,
+            text_scale: 1.0,
             last_frame_time: None,
+            clock: 0.0,
+            frame_times: VecDeque::with_capacity(DEFAULT_FRAME_HISTORY_LEN),
+            frame_history_len: DEFAULT_FRAME_HISTORY_LEN,
+            heatmap: InteractionHeatmap::default(),
+            heatmap_recording: false,
+            input_recording: None,
+            input_playback: None,
+            retained: RetainedTree::new(),
+            playback_frame_time: None,
+            propagation_stopped: false,
+            theme: Theme::default(),
+            style_sheet: StyleSheet::default(),
+            tokens: TokenTable::default(),
+            drag_ghost: None,
+            clipboard_text: String::new(),
+            clipboard: None,
+            copied_text: None,
         }
     }
 
@@ -119,6 +226,71 @@ impl UiContext {
     pub fn scale_factor(&self) -> f32 {
         self.scale_factor
     }
+
+    /// Set the text scale, which enlarges text without zooming the interface
+    ///
+    /// Font sizes are multiplied by `text_scale` (1.0 = 100%); paddings, fixed
+    /// sizes and gaps stay as they are, and nodes sized to their content grow
+    /// with the text. Applied to the root in [`Self::inject_dimension_overrides`].
+    /// Shaped text is cached per font size, so renderers holding on to shapes
+    /// should drop them when this changes (`Renderer::clear_text_caches` in
+    /// astra-gui-wgpu).
+    pub fn set_text_scale(&mut self, text_scale: f32) {
+        self.text_scale = text_scale.max(0.1);
+    }
+
+    /// Get the current text scale
+    pub fn text_scale(&self) -> f32 {
+        self.text_scale
+    }
+
+    /// Set the theme that widgets resolve their default styles against
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Get the current theme
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Set the stylesheet that node classes are resolved against
+    pub fn set_style_sheet(&mut self, style_sheet: StyleSheet) {
+        self.style_sheet = style_sheet;
+    }
+
+    /// Get the current stylesheet
+    pub fn style_sheet(&self) -> &StyleSheet {
+        &self.style_sheet
+    }
+
+    /// Get mutable access to the current stylesheet
+    pub fn style_sheet_mut(&mut self) -> &mut StyleSheet {
+        &mut self.style_sheet
+    }
+
+    /// Replace the design-token table
+    pub fn set_tokens(&mut self, tokens: TokenTable) {
+        self.tokens = tokens;
+    }
+
+    /// Override a single design token at runtime
+    ///
+    /// Every node bound to the token picks up the new value at the end of
+    /// the frame and animates towards it with its transition.
+    pub fn set_token(&mut self, name: impl Into<String>, value: impl Into<TokenValue>) {
+        self.tokens.set(name, value);
+    }
+
+    /// Get the current design-token table
+    pub fn tokens(&self) -> &TokenTable {
+        &self.tokens
+    }
+
+    /// Get mutable access to the design-token table
+    pub fn tokens_mut(&mut self) -> &mut TokenTable {
+        &mut self.tokens
+    }
     // ========== Frame Lifecycle ==========
 
     /// Begin a new frame
@@ -129,6 +301,23 @@ impl UiContext {
     pub fn begin_frame(&mut self) {
         self.state_manager.begin_frame();
         self.id_counter = 0;
+
+        // Replay recorded input in place of the live input
+        if let Some(playback) = &mut self.input_playback {
+            self.playback_frame_time = playback.next_frame(&mut self.input);
+            if self.playback_frame_time.is_none() {
+                self.input_playback = None;
+                self.input = InputState::new();
+            }
+        }
+        if let Some(recording) = &mut self.input_recording {
+            recording.push(RecordedFrame {
+                dt: 0.0,
+                input: self.input.clone(),
+            });
+        }
+
+        self.read_clipboard_for_paste();
     }
 
     /// End the current frame
@@ -136,6 +325,16 @@ impl UiContext {
     /// This should be called after building UI and computing layout.
     /// It dispatches events which will be available in the next frame.
     pub fn end_frame(&mut self, root: &mut Node) {
+        // Resolve stylesheet classes before auto-IDs and transitions see the styles
+        if !self.style_sheet.is_empty() {
+            self.style_sheet.apply(root);
+        }
+
+        // Resolve design tokens on top, so overrides feed into transitions
+        if !self.tokens.is_empty() {
+            self.tokens.apply(root);
+        }
+
         // Assign auto-IDs to nodes that need them
         InteractiveStateManager::assign_auto_ids(root);
 
@@ -145,46 +344,285 @@ impl UiContext {
         // Dispatch events based on input and hit testing
         let (events, interaction_states) = self.dispatcher.dispatch(&self.input, root);
         self.events = events;
+        self.events.append(&mut self.sent_events);
+        self.platform_requests
+            .extend(self.dispatcher.take_platform_requests());
         self.interaction_states = interaction_states;
+        self.run_event_handlers(root);
+        if let Some(layout) = root.computed_layout() {
+            self.viewport_size = (layout.rect.width(), layout.rect.height());
+        }
+
+        // The ghost lives exactly as long as the drag it was attached to
+        if self
+            .drag_ghost
+            .as_ref()
+            .is_some_and(|(source, _)| self.dispatcher.drag_target() != Some(source))
+        {
+            self.drag_ghost = None;
+        }
 
-        // Update style transitions
-        self.state_manager
-            .update_transitions(root, &self.interaction_states);
+        if self.heatmap_recording {
+            self.record_heatmap_samples(root);
+        }
 
-        // Advance smooth scroll animations toward their targets. Derive dt from
-        // the time since the previous frame so the easing is framerate
-        // independent. Without this, scroll_offset never moves toward
-        // scroll_target and scrolling has no visible effect.
+        // Advance the frame clock. Derive dt from the time since the previous
+        // frame so animations and scroll easing are framerate independent.
+        // Replayed frames take their recorded time, so animations match.
         let now = std::time::Instant::now();
-        let dt = self
-            .last_frame_time
-            .map(|prev| (now - prev).as_secs_f32())
-            .unwrap_or(0.0)
-            .clamp(0.0, 0.1);
-        self.last_frame_time = Some(now);
-        if dt > 0.0 {
-            root.update_all_scroll_animations(dt);
+        let frame_time = self
+            .playback_frame_time
+            .take()
+            .or_else(|| self.last_frame_time.map(|prev| (now - prev).as_secs_f32()));
+        if let Some(recording) = &mut self.input_recording {
+            recording.set_last_dt(frame_time.unwrap_or(0.0));
+        }
+        if let Some(frame_time) = frame_time {
+            self.record_frame_time(frame_time * 1000.0);
+            self.clock += f64::from(frame_time);
         }
+        let dt = frame_time.unwrap_or(0.0).clamp(0.0, 0.1);
+        self.last_frame_time = Some(now);
+
+        // Update style transitions and sample style animations
+        self.state_manager.set_clock(self.clock);
+        self.state_manager.update_transitions(
+            root,
+            &self.interaction_states,
+            self.dispatcher.focused_node(),
+        );
+
+        // Advance smooth scroll animations toward their targets. Without this,
+        // scroll_offset never moves toward scroll_target and scrolling has no
+        // visible effect.
+        // Always advance, even on the first frame (dt = 0): instant containers
+        // snap to programmatically set targets and the other modes don't move.
+        root.update_all_scroll_animations(dt);
 
         // Sync scroll state for persistence
         self.dispatcher.sync_scroll_state(root);
+        self.dispatcher.sync_focus_within(root);
+        self.dispatcher.sync_measured_sizes(root);
+    }
+
+    /// Get the tree kept between frames in retained mode
+    ///
+    /// Instead of building and laying out a new tree every frame, update the
+    /// kept one with `RetainedTree::update` or `RetainedTree::mutate`, lay out
+    /// what changed with `RetainedTree::layout` and finish the frame with
+    /// `end_retained_frame`.
+    pub fn retained(&mut self) -> &mut RetainedTree {
+        &mut self.retained
+    }
+
+    /// End the current frame for the retained tree, like `end_frame`
+    pub fn end_retained_frame(&mut self) {
+        let Some(mut root) = self.retained.take_root() else {
+            return;
+        };
+        self.end_frame(&mut root);
+        self.retained.set_root(root);
+    }
+
+    /// Run the `Node::on_click`, `on_drag` and `on_scroll` closures for the
+    /// events just dispatched
+    ///
+    /// Each event bubbles from its target up through the target's ancestors,
+    /// running their closures for it, until one calls `stop_propagation`.
+    fn run_event_handlers(&mut self, root: &mut Node) {
+        enum Call {
+            Click,
+            Drag(crate::Point),
+            Scroll((f32, f32)),
+        }
+
+        let mut calls: Vec<(NodeId, Call)> = self
+            .events
+            .iter()
+            .filter_map(|e| match e.event {
+                InteractionEvent::Click { .. } => Some((e.target.clone(), Call::Click)),
+                InteractionEvent::DragMove { delta, .. } => {
+                    Some((e.target.clone(), Call::Drag(delta)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // The wheel starts at the topmost hovered node; hover events run from
+        // the bottom to the top
+        let wheel = self.input.scroll_delta;
+        if wheel != (0.0, 0.0) {
+            let target = self
+                .events
+                .iter()
+                .rev()
+                .find(|e| matches!(e.event, InteractionEvent::Hover { .. }));
+            if let Some(e) = target {
+                calls.push((e.target.clone(), Call::Scroll(wheel)));
+            }
+        }
+
+        for (target, call) in calls {
+            let mut path = Vec::new();
+            Self::path_to(root, &target, &mut path);
+            self.propagation_stopped = false;
+
+            // Take each closure out of its node while it runs, so it can
+            // borrow the context
+            for id in path.iter().rev() {
+                let Some(node) = self.dispatcher.find_node_by_id_mut(root, id) else {
+                    continue;
+                };
+                let handlers = node.event_handlers_mut();
+                match call {
+                    Call::Click => {
+                        if let Some(mut f) = handlers.click.take() {
+                            f(self);
+                            handlers.click = Some(f);
+                        }
+                    }
+                    Call::Drag(delta) => {
+                        if let Some(mut f) = handlers.drag.take() {
+                            f(self, delta);
+                            handlers.drag = Some(f);
+                        }
+                    }
+                    Call::Scroll(delta) => {
+                        if let Some(mut f) = handlers.scroll.take() {
+                            f(self, delta);
+                            handlers.scroll = Some(f);
+                        }
+                    }
+                }
+                if self.propagation_stopped {
+                    break;
+                }
+            }
+        }
+        self.propagation_stopped = false;
+    }
+
+    /// Collect the IDs of the identified nodes from `node` down to `target`,
+    /// returning whether `target` was found
+    fn path_to(node: &Node, target: &NodeId, path: &mut Vec<NodeId>) -> bool {
+        let id = node.id();
+        if let Some(id) = id {
+            path.push(id.clone());
+            if id == target {
+                return true;
+            }
+        }
+        if node
+            .children()
+            .iter()
+            .any(|child| Self::path_to(child, target, path))
+        {
+            return true;
+        }
+        if id.is_some() {
+            path.pop();
+        }
+        false
+    }
+
+    /// Stop the event being handled from reaching any more node closures
+    ///
+    /// Call this from a `Node::on_click`, `on_drag` or `on_scroll` closure to
+    /// consume the event, so ancestors with closures of their own don't also
+    /// react to it. Outside a closure it has no effect.
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    fn record_heatmap_samples(&mut self, root: &Node) {
+        let Some(position) = self.input.cursor_position else {
+            return;
+        };
+        self.heatmap.record_hover(position);
+
+        let pressed = [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+            .into_iter()
+            .filter(|b| self.input.is_button_just_pressed(*b))
+            .count();
+        if pressed > 0 {
+            let hit = hit_test_point(root, position)
+                .iter()
+                .any(|h| h.node_id.is_some());
+            for _ in 0..pressed {
+                self.heatmap.record_click(position, hit);
+            }
+        }
     }
 
     /// Inject dimension overrides before layout
     ///
     /// Call this after building the UI tree but before computing layout.
-    /// This applies interpolated dimensions from ongoing transitions.
+    /// This applies interpolated dimensions from ongoing transitions and
+    /// layout properties (padding) from stylesheet classes, and sets the
+    /// root's text scale.
     pub fn inject_dimension_overrides(&self, root: &mut Node) {
+        root.set_text_scale(self.text_scale);
+        if !self.style_sheet.is_empty() {
+            self.style_sheet.apply_layout(root);
+        }
+        if !self.tokens.is_empty() {
+            self.tokens.apply_layout(root);
+        }
         self.state_manager.inject_dimension_overrides(root);
     }
 
-    /// Check if any transitions are currently active
+    /// Check if any transitions or style animations are currently active
     ///
     /// Use this to determine if continuous redraws are needed.
     pub fn has_active_transitions(&self) -> bool {
         self.state_manager.has_active_transitions()
     }
 
+    // ========== Frame Clock ==========
+
+    /// Frame clock in seconds, advanced by the time between `end_frame` calls
+    ///
+    /// Style animations are sampled at this time.
+    pub fn time(&self) -> f64 {
+        self.clock
+    }
+
+    /// Durations of the most recent frames in milliseconds, oldest first
+    ///
+    /// Measured between consecutive `end_frame` calls, so the first frame
+    /// does not produce an entry.
+    pub fn frame_times(&self) -> &VecDeque<f32> {
+        &self.frame_times
+    }
+
+    /// Duration of the previous frame in milliseconds
+    pub fn last_frame_time_ms(&self) -> Option<f32> {
+        self.frame_times.back().copied()
+    }
+
+    /// Set how many frame times are kept (default 120)
+    pub fn set_frame_history_len(&mut self, len: usize) {
+        self.frame_history_len = len;
+        while self.frame_times.len() > len {
+            self.frame_times.pop_front();
+        }
+    }
+
+    /// Number of frame times kept
+    pub fn frame_history_len(&self) -> usize {
+        self.frame_history_len
+    }
+
+    fn record_frame_time(&mut self, ms: f32) {
+        if self.frame_history_len == 0 {
+            return;
+        }
+        if self.frame_times.len() >= self.frame_history_len {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(ms);
+    }
+
     // ========== Input State Access ==========
 
     /// Get the current input state
@@ -239,6 +677,42 @@ impl UiContext {
         })
     }
 
+    /// Check if a widget was double-clicked (with any button) in the last frame
+    pub fn was_double_clicked(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::DoubleClick { .. }) && e.target.as_str() == id
+        })
+    }
+
+    /// Check if a widget was triple-clicked (with any button) in the last frame
+    pub fn was_triple_clicked(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::TripleClick { .. }) && e.target.as_str() == id
+        })
+    }
+
+    /// Set the longest time between clicks that still counts them as a double
+    /// or triple click (default: 500 ms)
+    pub fn set_multi_click_interval(&mut self, interval: std::time::Duration) {
+        self.dispatcher.set_multi_click_interval(interval);
+    }
+
+    /// Check if a widget was long-pressed (with any button) in the last frame
+    ///
+    /// This fires once per press, when the button has been held for the
+    /// long-press duration without the pointer moving away.
+    pub fn was_long_pressed(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::LongPress { .. }) && e.target.as_str() == id
+        })
+    }
+
+    /// Set how long a button must be held on a widget to long-press it
+    /// (default: 500 ms)
+    pub fn set_long_press_duration(&mut self, duration: std::time::Duration) {
+        self.dispatcher.set_long_press_duration(duration);
+    }
+
     /// Check if a widget is currently hovered
     pub fn is_hovered(&self, id: &str) -> bool {
         self.events
@@ -246,6 +720,198 @@ impl UiContext {
             .any(|e| matches!(e.event, InteractionEvent::Hover { .. }) && e.target.as_str() == id)
     }
 
+    /// Check if the cursor moved onto a widget or its descendants in the last
+    /// frame
+    pub fn pointer_entered(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::PointerEnter { .. }) && e.target.as_str() == id
+        })
+    }
+
+    /// Check if the cursor moved off a widget and all its descendants in the
+    /// last frame
+    pub fn pointer_left(&self, id: &str) -> bool {
+        self.events
+            .iter()
+            .any(|e| matches!(e.event, InteractionEvent::PointerLeave) && e.target.as_str() == id)
+    }
+
+    /// Check if the cursor came to rest on a widget in the last frame
+    ///
+    /// Unlike [`is_hovered`](Self::is_hovered), this only fires once the
+    /// cursor has stayed on the widget for the hover-intent delay, so
+    /// tooltips and previews don't flicker as it sweeps across the UI.
+    pub fn hover_started(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::HoverStart { .. }) && e.target.as_str() == id
+        })
+    }
+
+    /// Check if the cursor left a widget it had come to rest on in the last frame
+    pub fn hover_ended(&self, id: &str) -> bool {
+        self.events
+            .iter()
+            .any(|e| matches!(e.event, InteractionEvent::HoverEnd) && e.target.as_str() == id)
+    }
+
+    /// Get how long the cursor has rested on a widget, once past the
+    /// hover-intent delay
+    ///
+    /// Compare against a longer duration for "still hovered after N ms"
+    /// behavior, e.g. `ctx.hovered_for("id") >= Some(Duration::from_millis(800))`.
+    pub fn hovered_for(&self, id: &str) -> Option<std::time::Duration> {
+        self.events.iter().find_map(|e| match e.event {
+            InteractionEvent::HoverHeld { elapsed, .. } if e.target.as_str() == id => Some(elapsed),
+            _ => None,
+        })
+    }
+
+    /// Set how long the cursor must rest on a widget before it counts as
+    /// hovered with intent (default: 300 ms)
+    pub fn set_hover_intent_delay(&mut self, delay: std::time::Duration) {
+        self.dispatcher.set_hover_intent_delay(delay);
+    }
+
+    /// Check if a mouse button was pressed in the last frame outside a widget
+    ///
+    /// Presses on identified nodes inside the widget count as inside, even when
+    /// they overflow its bounds, so open popups can close on a click elsewhere.
+    pub fn was_pressed_outside(&self, id: &str) -> bool {
+        self.dispatcher.was_pressed_outside(&NodeId::new(id))
+    }
+
+    /// Check if a mouse button was pressed in the last frame on a widget or
+    /// inside it, counting only the topmost node under the pointer
+    pub fn was_pressed_inside(&self, id: &str) -> bool {
+        self.dispatcher.was_pressed_inside(&NodeId::new(id))
+    }
+
+    /// Check if a context menu was requested for a widget (right-clicked) in
+    /// the last frame
+    pub fn was_context_menu_requested(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::ContextMenuRequested { .. })
+                && e.target.as_str() == id
+        })
+    }
+
+    /// Get the action chosen in a widget's context menu in the last frame, if any
+    pub fn context_menu_action(&self, id: &str) -> Option<&str> {
+        self.events.iter().find_map(|e| match &e.event {
+            InteractionEvent::ContextMenuAction { action } if e.target.as_str() == id => {
+                Some(action.as_str())
+            }
+            _ => None,
+        })
+    }
+
+    /// Get the zoom factor of a pinch over a widget in the last frame, if any
+    pub fn pinch_zoom(&self, id: &str) -> Option<f32> {
+        self.events.iter().find_map(|e| match e.event {
+            InteractionEvent::Pinch { zoom, .. } if e.target.as_str() == id => Some(zoom),
+            _ => None,
+        })
+    }
+
+    /// Get the two-finger pan over a widget in the last frame, if any
+    pub fn pan_delta(&self, id: &str) -> Option<(f32, f32)> {
+        self.events.iter().find_map(|e| match e.event {
+            InteractionEvent::Pan { delta, .. } if e.target.as_str() == id => Some(delta),
+            _ => None,
+        })
+    }
+
+    /// Get the files being dragged over a widget from outside the window, if any
+    pub fn hovered_files(&self, id: &str) -> Option<&[PathBuf]> {
+        self.events.iter().find_map(|e| match &e.event {
+            InteractionEvent::FileHovered(paths) if e.target.as_str() == id => {
+                Some(paths.as_slice())
+            }
+            _ => None,
+        })
+    }
+
+    /// Get the files dropped on a widget from outside the window in the last
+    /// frame, if any
+    pub fn dropped_files(&self, id: &str) -> Option<&[PathBuf]> {
+        self.events.iter().find_map(|e| match &e.event {
+            InteractionEvent::FileDropped(paths) if e.target.as_str() == id => {
+                Some(paths.as_slice())
+            }
+            _ => None,
+        })
+    }
+
+    /// Whether files are being dragged over the window from outside it
+    pub fn is_file_drag_active(&self) -> bool {
+        !self.input.files_hovered.is_empty()
+    }
+
+    /// Send an event to a widget, delivered with the next frame's events as if
+    /// it had been dispatched from input
+    ///
+    /// Lets composite widgets report what happened to the node they act on,
+    /// e.g. a context menu sending the chosen action to its target.
+    pub fn send_event(&mut self, event: TargetedEvent) {
+        self.sent_events.push(event);
+    }
+
+    /// Send an application-defined event to a node, delivered with the next
+    /// frame's events
+    ///
+    /// The receiver reads it with [`custom_event`](Self::custom_event) or
+    /// [`custom_events`](Self::custom_events), or as an
+    /// `InteractionEvent::Custom` in [`events_for`](Self::events_for).
+    pub fn emit(&mut self, target: impl Into<NodeId>, event: CustomEvent) {
+        self.send_event(TargetedEvent {
+            event: InteractionEvent::Custom(event),
+            target: target.into(),
+            local_position: crate::Point::zero(),
+            zoom: 1.0,
+        });
+    }
+
+    /// Get the custom events sent to a widget in the last frame
+    pub fn custom_events<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a CustomEvent> {
+        self.events_for(id).filter_map(|e| match &e.event {
+            InteractionEvent::Custom(event) => Some(event),
+            _ => None,
+        })
+    }
+
+    /// Get the first custom event with the given name sent to a widget in the
+    /// last frame
+    pub fn custom_event(&self, id: &str, name: &str) -> Option<&CustomEvent> {
+        self.events.iter().find_map(|e| match &e.event {
+            InteractionEvent::Custom(event) if e.target.as_str() == id && event.name() == name => {
+                Some(event)
+            }
+            _ => None,
+        })
+    }
+
+    /// Route the pointer to a widget until every mouse button is released
+    ///
+    /// Call this when a press or drag starts on the widget, e.g. a slider
+    /// thumb. It then receives `PointerMove` and `PointerUp` events wherever
+    /// the pointer goes, even outside the window, its drag keeps going and
+    /// ends there too, and `PointerCaptureLost` when the capture ends.
+    pub fn capture_pointer(&mut self, id: &str) {
+        self.dispatcher.capture_pointer(NodeId::new(id));
+    }
+
+    /// Release pointer capture before the buttons are released
+    pub fn release_pointer_capture(&mut self) {
+        self.dispatcher.release_pointer_capture();
+    }
+
+    /// Check if a widget holds pointer capture
+    pub fn has_pointer_capture(&self, id: &str) -> bool {
+        self.dispatcher
+            .pointer_capture()
+            .is_some_and(|target| target.as_str() == id)
+    }
+
     /// Check if a widget is being dragged
     pub fn is_dragging(&self, id: &str) -> bool {
         self.events.iter().any(|e| {
@@ -270,58 +936,335 @@ impl UiContext {
         })
     }
 
-    /// Get all events targeting a specific widget
-    pub fn events_for<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a TargetedEvent> {
-        self.events.iter().filter(move |e| e.target.as_str() == id)
+    /// Show a preview under the pointer for the drag in progress
+    ///
+    /// Call this when handling a `DragStart` event. The ghost stays until the
+    /// drag ends; without an active drag it is ignored. See [`DragGhost`].
+    pub fn set_drag_ghost(&mut self, ghost: DragGhost) {
+        self.drag_ghost = self
+            .dispatcher
+            .drag_target()
+            .map(|source| (source.clone(), ghost));
     }
 
-    /// Get the interaction state for a widget
-    pub fn interaction_state(&self, id: &str) -> InteractionState {
-        let node_id = NodeId::new(id);
-        self.interaction_states
-            .get(&node_id)
-            .copied()
-            .unwrap_or(InteractionState::Idle)
+    /// Check if a drag ghost is being shown
+    pub fn has_drag_ghost(&self) -> bool {
+        self.drag_ghost.is_some()
     }
 
-    // ========== Focus Management ==========
+    /// Build the overlay layer for the current drag ghost, if any
+    ///
+    /// The layer is a separate full-window tree, so it never takes part in hit
+    /// testing. Lay it out and append it on top of the frame's output, e.g. with
+    /// `FullOutput::add_layer`.
+    pub fn drag_ghost_layer(&self) -> Option<Node> {
+        let (_, ghost) = self.drag_ghost.as_ref()?;
+        let cursor = self.input.cursor_position?;
+        let grab_offset = self.dispatcher.drag_grab_offset()?;
+        Some(ghost.layer(cursor, grab_offset))
+    }
 
-    /// Get the currently focused widget ID
-    pub fn focused_widget(&self) -> Option<&NodeId> {
-        self.dispatcher.focused_node()
+    /// Attach data to the drag in progress
+    ///
+    /// Call this when handling a `DragStart` event. Nodes marked with
+    /// `Node::with_drop_target` then receive `DragEnter`, `DragOver`,
+    /// `DragLeave` and `Drop` events as the drag passes over them, and the
+    /// one it is released on can [`take_drop`](Self::take_drop) the payload.
+    /// Without an active drag the payload is discarded.
+    pub fn set_drag_payload<T: Any>(&mut self, payload: T) {
+        self.dispatcher.set_drag_payload(Box::new(payload));
     }
 
-    /// Check if a widget is focused
-    pub fn is_focused(&self, id: &str) -> bool {
-        self.dispatcher
-            .focused_node()
-            .map(|fid| fid.as_str() == id)
-            .unwrap_or(false)
+    /// Attach an already boxed payload to the drag in progress
+    ///
+    /// Like [`set_drag_payload`](Self::set_drag_payload), for payloads whose
+    /// type is only known at runtime.
+    pub fn set_drag_payload_boxed(&mut self, payload: Box<dyn Any>) {
+        self.dispatcher.set_drag_payload(payload);
     }
 
-    /// Set the focused widget
-    pub fn set_focus(&mut self, id: Option<&str>) {
-        self.dispatcher.set_focus(id.map(|s| NodeId::new(s)));
+    /// Get the data carried by the drag in progress, if it is a `T`
+    ///
+    /// Drop targets can use this to only highlight for payloads they accept.
+    pub fn drag_payload<T: Any>(&self) -> Option<&T> {
+        self.dispatcher.drag_payload()?.downcast_ref()
     }
 
-    /// Update cursor blink for a focused text widget
-    pub fn update_cursor_blink(&mut self, id: &str, blink_rate_ms: u64) -> bool {
+    /// Check if a drag carrying a payload is over a drop target
+    pub fn is_drag_over(&self, id: &str) -> bool {
         self.dispatcher
-            .update_cursor_blink(&NodeId::new(id), blink_rate_ms)
+            .drop_target()
+            .is_some_and(|target| target.as_str() == id)
     }
 
-    /// Reset cursor blink to visible (call when text changes)
-    pub fn reset_cursor_blink(&mut self, id: &str) {
-        self.dispatcher.reset_cursor_blink(&NodeId::new(id));
+    /// Take the payload dropped on a widget in the last frame, if it is a `T`
+    ///
+    /// A payload of another type is left in place, so several types can be
+    /// tried in turn.
+    pub fn take_drop<T: Any>(&mut self, id: &str) -> Option<T> {
+        let id = NodeId::new(id);
+        if !self.dispatcher.dropped_payload(&id)?.is::<T>() {
+            return None;
+        }
+        let payload = self.dispatcher.take_drop(&id)?;
+        payload.downcast().ok().map(|payload| *payload)
     }
 
-    /// Check if cursor should be visible for a widget
-    pub fn is_cursor_visible(&self, id: &str) -> bool {
-        self.dispatcher.is_cursor_visible(&NodeId::new(id))
+    /// Take the payload dropped on a widget in the last frame, whatever its type
+    pub fn take_drop_boxed(&mut self, id: &str) -> Option<Box<dyn Any>> {
+        self.dispatcher.take_drop(&NodeId::new(id))
     }
 
-    // ========== Widget Memory ==========
-
+    /// Check if a peek was requested for a widget in the last frame
+    ///
+    /// This fires once per hover, after the delay set with `Node::with_peek_delay`.
+    pub fn was_peek_requested(&self, id: &str) -> bool {
+        self.events.iter().any(|e| {
+            matches!(e.event, InteractionEvent::PeekRequested { .. }) && e.target.as_str() == id
+        })
+    }
+
+    /// Check if a peek is currently open for a widget
+    pub fn is_peek_open(&self, id: &str) -> bool {
+        self.dispatcher.is_peek_open(&NodeId::new(id))
+    }
+
+    /// Dismiss an open peek for a widget
+    pub fn dismiss_peek(&mut self, id: &str) {
+        self.dispatcher.dismiss_peek(&NodeId::new(id));
+    }
+
+    /// Get all events targeting a specific widget
+    pub fn events_for<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a TargetedEvent> {
+        self.events.iter().filter(move |e| e.target.as_str() == id)
+    }
+
+    /// Get the interaction state for a widget
+    pub fn interaction_state(&self, id: &str) -> InteractionState {
+        let node_id = NodeId::new(id);
+        self.interaction_states
+            .get(&node_id)
+            .copied()
+            .unwrap_or(InteractionState::Idle)
+    }
+
+    /// Size of the root node laid out in the last frame, i.e. the window in
+    /// physical pixels (zero before the first frame)
+    pub fn viewport_size(&self) -> (f32, f32) {
+        self.viewport_size
+    }
+
+    // ========== Scrolling ==========
+
+    /// Scroll position and range of the `Overflow::Scroll` node with the given
+    /// ID, as of the last frame (`None` before its first frame)
+    pub fn scroll_metrics(&self, id: &str) -> Option<ScrollMetrics> {
+        self.dispatcher.scroll_metrics(id)
+    }
+
+    /// Scroll the `Overflow::Scroll` node with the given ID toward `target`
+    /// (layout pixels), clamped to its range; applied in `end_frame`
+    pub fn scroll_to(&mut self, id: &str, target: (f32, f32)) {
+        self.dispatcher.scroll_to(id, target);
+    }
+
+    /// Move the scroll position of the `Overflow::Scroll` node with the given
+    /// ID by `delta` (layout pixels) without easing; applied in `end_frame`
+    ///
+    /// Keeps visible content in place when content above it changes size.
+    pub fn shift_scroll(&mut self, id: &str, delta: (f32, f32)) {
+        self.dispatcher.shift_scroll(id, delta);
+    }
+
+    /// Size (layout pixels) of the node with the given ID as laid out in the
+    /// last frame, if it is marked with `Node::with_measure_size`
+    pub fn measured_size(&self, id: &str) -> Option<(f32, f32)> {
+        self.dispatcher.measured_size(id)
+    }
+
+    // ========== Focus Management ==========
+
+    /// Get the currently focused widget ID
+    pub fn focused_widget(&self) -> Option<&NodeId> {
+        self.dispatcher.focused_node()
+    }
+
+    /// Get the cursor icon requested by the node under the pointer
+    ///
+    /// Resolved in `end_frame`; backends apply it to the window.
+    pub fn cursor_icon(&self) -> crate::CursorIcon {
+        self.dispatcher.cursor_icon()
+    }
+
+    /// Ask the platform layer to open a URL with the system's default handler
+    ///
+    /// Delivered with the next `take_platform_output`.
+    pub fn open_url(&mut self, url: impl Into<String>) {
+        self.platform_requests
+            .push(crate::PlatformRequest::OpenUrl(url.into()));
+    }
+
+    /// Take what the platform layer should do after this frame: the cursor
+    /// icon and the window moves, resizes and URLs requested since the last
+    /// call
+    ///
+    /// Call it after `end_frame` and store it in the frame's
+    /// `FullOutput::platform` for the backend to execute.
+    pub fn take_platform_output(&mut self) -> crate::PlatformOutput {
+        crate::PlatformOutput {
+            cursor_icon: self.cursor_icon(),
+            requests: std::mem::take(&mut self.platform_requests),
+        }
+    }
+
+    /// Check if a widget is focused
+    pub fn is_focused(&self, id: &str) -> bool {
+        self.dispatcher
+            .focused_node()
+            .map(|fid| fid.as_str() == id)
+            .unwrap_or(false)
+    }
+
+    /// Check if a widget is focused or contains the focused widget, as of the
+    /// last frame
+    ///
+    /// Lets containers such as dialogs keep focus from leaving them.
+    pub fn is_focus_within(&self, id: &str) -> bool {
+        self.dispatcher.is_focus_within(&NodeId::new(id))
+    }
+
+    /// Set the focused widget
+    ///
+    /// For widgets keeping track of their own focus; no Focus or Blur events
+    /// are sent. Use [`Self::focus`] to move focus the way Tab does.
+    pub fn set_focus(&mut self, id: Option<&str>) {
+        self.dispatcher.set_focus(id.map(|s| NodeId::new(s)));
+    }
+
+    /// Move keyboard focus to a node, as if it had been tabbed to
+    ///
+    /// The previously focused node gets a `Blur` event and this one a `Focus`
+    /// event with the next frame's events; keys pressed from then on are sent
+    /// to it as `KeyDown` events.
+    pub fn focus(&mut self, id: impl Into<NodeId>) {
+        self.dispatcher.focus(Some(id.into()));
+    }
+
+    /// Clear keyboard focus, sending the focused node a `Blur` event with the
+    /// next frame's events
+    pub fn blur(&mut self) {
+        self.dispatcher.focus(None);
+    }
+
+    /// Check if a widget gained focus in the last frame
+    pub fn gained_focus(&self, id: &str) -> bool {
+        self.events
+            .iter()
+            .any(|e| matches!(e.event, InteractionEvent::Focus) && e.target.as_str() == id)
+    }
+
+    /// Check if a widget lost focus in the last frame
+    pub fn lost_focus(&self, id: &str) -> bool {
+        self.events
+            .iter()
+            .any(|e| matches!(e.event, InteractionEvent::Blur) && e.target.as_str() == id)
+    }
+
+    /// Get the keys pressed while a widget had focus in the last frame
+    pub fn keys_pressed_on<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Key> + 'a {
+        self.events.iter().filter_map(move |e| match &e.event {
+            InteractionEvent::KeyDown { key } if e.target.as_str() == id => Some(key),
+            _ => None,
+        })
+    }
+
+    /// Update cursor blink for a focused text widget
+    pub fn update_cursor_blink(&mut self, id: &str, blink_rate_ms: u64) -> bool {
+        self.dispatcher
+            .update_cursor_blink(&NodeId::new(id), blink_rate_ms)
+    }
+
+    /// Reset cursor blink to visible (call when text changes)
+    pub fn reset_cursor_blink(&mut self, id: &str) {
+        self.dispatcher.reset_cursor_blink(&NodeId::new(id));
+    }
+
+    /// Check if cursor should be visible for a widget
+    pub fn is_cursor_visible(&self, id: &str) -> bool {
+        self.dispatcher.is_cursor_visible(&NodeId::new(id))
+    }
+
+    // ========== Clipboard ==========
+
+    /// Connect copy and paste to a clipboard, such as the system's
+    ///
+    /// Copies are written to it right away, and it is read at the start of
+    /// any frame where a paste shortcut (Ctrl+V or Cmd+V) was pressed, before
+    /// widgets handle the shortcut. See [`Clipboard`].
+    pub fn set_clipboard(&mut self, clipboard: impl Clipboard + 'static) {
+        self.clipboard = Some(Box::new(clipboard));
+    }
+
+    /// Check if a clipboard is connected with `set_clipboard`
+    pub fn has_clipboard(&self) -> bool {
+        self.clipboard.is_some()
+    }
+
+    /// Copy text to the clipboard
+    ///
+    /// Pasting inside the app works right away. The text is written to the
+    /// clipboard set with `set_clipboard`, or else kept for backends to pick
+    /// up with `take_copied_text`.
+    pub fn copy_to_clipboard(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        self.clipboard_text.clone_from(&text);
+        match &mut self.clipboard {
+            Some(clipboard) => clipboard.set_text(&text),
+            None => self.copied_text = Some(text),
+        }
+    }
+
+    /// Text to paste: the last copy, or what was read from the clipboard for
+    /// this frame's paste shortcut
+    pub fn clipboard_text(&self) -> &str {
+        &self.clipboard_text
+    }
+
+    /// Set the text to paste without copying it, e.g. from the system clipboard
+    /// before a paste shortcut is handled
+    pub fn set_clipboard_text(&mut self, text: impl Into<String>) {
+        self.clipboard_text = text.into();
+    }
+
+    /// Take the text copied since the last call, for the backend to write to
+    /// the system clipboard after `end_frame`
+    ///
+    /// Always `None` while a clipboard is set with `set_clipboard`, which
+    /// receives copies directly.
+    pub fn take_copied_text(&mut self) -> Option<String> {
+        self.copied_text.take()
+    }
+
+    /// Load the clipboard into the paste text if a paste shortcut was pressed
+    fn read_clipboard_for_paste(&mut self) {
+        let Some(clipboard) = &mut self.clipboard else {
+            return;
+        };
+        let paste = (self.input.ctrl_held || self.input.super_held)
+            && self
+                .input
+                .keys_just_pressed
+                .iter()
+                .any(|key| matches!(key, Key::Character(ch) if ch.eq_ignore_ascii_case("v")));
+        if paste {
+            if let Some(text) = clipboard.get_text() {
+                self.clipboard_text = text;
+            }
+        }
+    }
+
+    // ========== Widget Memory ==========
+
     /// Get access to widget memory for storing internal state
     pub fn memory(&mut self) -> &mut WidgetMemory {
         &mut self.memory
@@ -332,6 +1275,73 @@ impl UiContext {
         &self.memory
     }
 
+    // ========== Interaction Heatmap ==========
+
+    /// Enable or disable recording of hover and click positions into the heatmap
+    ///
+    /// Recorded samples persist across frames until `heatmap_mut().clear()`.
+    pub fn set_heatmap_recording(&mut self, enabled: bool) {
+        self.heatmap_recording = enabled;
+    }
+
+    /// Check if heatmap recording is enabled
+    pub fn is_heatmap_recording(&self) -> bool {
+        self.heatmap_recording
+    }
+
+    /// Get the accumulated interaction heatmap
+    pub fn heatmap(&self) -> &InteractionHeatmap {
+        &self.heatmap
+    }
+
+    /// Get mutable access to the interaction heatmap
+    pub fn heatmap_mut(&mut self) -> &mut InteractionHeatmap {
+        &mut self.heatmap
+    }
+
+    // ========== Input Recording ==========
+
+    /// Start recording the input of every frame, discarding any recording in
+    /// progress
+    ///
+    /// Each frame's input is captured in `begin_frame`, as widgets and the
+    /// dispatcher see it, along with the frame's duration.
+    pub fn start_input_recording(&mut self) {
+        self.input_recording = Some(InputRecording::new());
+    }
+
+    /// Stop recording input and return the recording, if one was in progress
+    pub fn stop_input_recording(&mut self) -> Option<InputRecording> {
+        self.input_recording.take()
+    }
+
+    /// Check if input is being recorded
+    pub fn is_recording_input(&self) -> bool {
+        self.input_recording.is_some()
+    }
+
+    /// Replay a recording, one frame per `begin_frame`, in place of live input
+    ///
+    /// Each replayed frame uses its recorded duration for transitions and
+    /// scroll animations. Live input is ignored until playback finishes, after
+    /// which the input starts from an empty state.
+    pub fn play_input(&mut self, recording: InputRecording) {
+        self.input_playback = Some(InputPlayback::new(recording));
+    }
+
+    /// Stop replaying input before the recording ends
+    pub fn stop_input_playback(&mut self) {
+        if self.input_playback.take().is_some() {
+            self.playback_frame_time = None;
+            self.input = InputState::new();
+        }
+    }
+
+    /// Check if recorded input is being replayed
+    pub fn is_playing_input(&self) -> bool {
+        self.input_playback.is_some()
+    }
+
     // ========== Content Measurement ==========
 
     /// Get mutable access to the content measurer, if set
@@ -353,6 +1363,44 @@ impl UiContext {
         }
     }
 
+    // ========== Code Highlighting ==========
+
+    /// Set the highlighter that `highlight` colors code with
+    pub fn set_highlighter(&mut self, highlighter: impl Highlighter + 'static) {
+        self.highlighter = Some(Box::new(highlighter));
+        self.highlight_cache.clear();
+    }
+
+    /// Color code with the context's highlighter
+    ///
+    /// Results are cached by language and code, so unchanged code is only
+    /// highlighted once. Without a highlighter the code stays plain.
+    ///
+    /// ```ignore
+    /// let code = ctx.highlight(CodeContent::new(source, "rust"));
+    /// Node::new().with_content(Content::Code(code))
+    /// ```
+    pub fn highlight(&mut self, mut code: CodeContent) -> CodeContent {
+        let Some(highlighter) = &self.highlighter else {
+            return code;
+        };
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        code.language.hash(&mut hasher);
+        code.code().hash(&mut hasher);
+        let key = hasher.finish();
+
+        if !self.highlight_cache.contains_key(&key) {
+            if self.highlight_cache.len() >= HIGHLIGHT_CACHE_CAPACITY {
+                self.highlight_cache.clear();
+            }
+            let spans = highlighter.highlight(code.code(), &code.language);
+            self.highlight_cache.insert(key, spans);
+        }
+        code.set_spans(self.highlight_cache[&key].clone());
+        code
+    }
+
     // ========== ID Generation ==========
 
     /// Generate a unique ID for a widget
@@ -451,6 +1499,7 @@ impl std::fmt::Debug for UiContext {
             .field("memory", &self.memory)
             .field("id_stack", &self.id_stack)
             .field("scale_factor", &self.scale_factor)
+            .field("text_scale", &self.text_scale)
             .finish()
     }
 }
@@ -618,6 +1667,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_scroll_routes_each_axis_to_the_innermost_container() {
+        use crate::{Layout, Overflow, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+
+        // One frame over a wide table inside a tall page; return the
+        // (table, page) scroll offsets
+        let run = |delta: (f32, f32), precise: bool| {
+            let mut ctx = UiContext::new();
+            ctx.begin_frame();
+            let mut page = Node::new()
+                .with_id(NodeId::new("page"))
+                .with_width(Size::lpx(100.0))
+                .with_height(Size::lpx(100.0))
+                .with_layout_direction(Layout::Vertical)
+                .with_overflow(Overflow::Scroll)
+                .with_children(vec![
+                    Node::new()
+                        .with_id(NodeId::new("table"))
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(50.0))
+                        .with_layout_direction(Layout::Horizontal)
+                        .with_overflow(Overflow::Scroll)
+                        .with_child(
+                            Node::new()
+                                .with_width(Size::lpx(500.0))
+                                .with_height(Size::lpx(50.0)),
+                        ),
+                    Node::new()
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(500.0)),
+                ]);
+            ctx.input_mut().cursor_position = Some(Point::new(50.0, 25.0));
+            if precise {
+                ctx.input_mut().add_precise_scroll_delta(delta.0, delta.1);
+            } else {
+                ctx.input_mut().add_scroll_delta(delta.0, delta.1);
+            }
+            page.compute_layout(window);
+            ctx.end_frame(&mut page);
+            (page.children()[0].scroll_target(), page.scroll_target())
+        };
+
+        // Trackpads move each container one to one
+        assert_eq!(run((-30.0, 0.0), true), ((30.0, 0.0), (0.0, 0.0)));
+        assert_eq!(run((0.0, -30.0), true), ((0.0, 0.0), (0.0, 30.0)));
+        assert_eq!(run((-30.0, -20.0), true), ((30.0, 0.0), (0.0, 20.0)));
+
+        // A wheel over the table still scrolls the page, which can scroll vertically
+        let (table, page) = run((0.0, -10.0), false);
+        assert_eq!(table, (0.0, 0.0));
+        assert!(page.0 == 0.0 && page.1 > 0.0);
+    }
+
     #[test]
     fn test_scroll_speed_is_configurable() {
         use crate::{Layout, Overflow, Point, Rect, Size};
@@ -660,15 +1764,2036 @@ mod tests {
     }
 
     #[test]
-    fn test_focus_management() {
+    fn test_scroll_smoothing_modes() {
+        use crate::transition::ease_in_out;
+        use crate::{Layout, Overflow, Point, Rect, ScrollSmoothing, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let build = |smoothing: ScrollSmoothing| {
+            Node::new()
+                .with_id(NodeId::new("s"))
+                .with_width(Size::lpx(100.0))
+                .with_height(Size::lpx(100.0))
+                .with_layout_direction(Layout::Vertical)
+                .with_overflow(Overflow::Scroll)
+                .with_scroll_smoothing(smoothing)
+                .with_child(
+                    Node::new()
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(500.0)),
+                )
+        };
+
+        // Instant scrolling lands on the target within the frame of the wheel event.
         let mut ctx = UiContext::new();
+        ctx.begin_frame();
+        let mut root = build(ScrollSmoothing::Instant);
+        ctx.input_mut().cursor_position = Some(Point::new(50.0, 50.0));
+        ctx.input_mut().scroll_delta = (0.0, -10.0);
+        root.compute_layout(window);
+        ctx.end_frame(&mut root);
+        assert!(root.scroll_target().1 > 0.0);
+        assert_eq!(root.scroll_offset(), root.scroll_target());
+
+        // Eased scrolling is partway after less than the duration and exactly on
+        // target once the duration has elapsed.
+        let mut node = build(ScrollSmoothing::eased(0.2, ease_in_out));
+        node.set_scroll_target((0.0, 100.0));
+        assert!(node.update_scroll_animation(0.1));
+        let halfway = node.scroll_offset().1;
+        assert!(halfway > 0.0 && halfway < 100.0, "got {halfway}");
+        node.update_scroll_animation(0.1);
+        assert_eq!(node.scroll_offset(), (0.0, 100.0));
+        assert!(!node.update_scroll_animation(0.1));
+    }
 
-        assert!(!ctx.is_focused("my_input"));
+    #[test]
+    fn test_scroll_momentum_coasts_after_a_flick() {
+        use crate::{Layout, Overflow, Rect, ScrollMomentum, Size};
 
-        ctx.set_focus(Some("my_input"));
-        assert!(ctx.is_focused("my_input"));
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let dt = 1.0 / 60.0;
 
-        ctx.set_focus(None);
-        assert!(!ctx.is_focused("my_input"));
+        // Flick down at `step` pixels per frame for a few frames, then let go;
+        // return the largest and the final offset
+        let flick = |momentum: ScrollMomentum, step: f32| {
+            let mut node = Node::new()
+                .with_width(Size::lpx(100.0))
+                .with_height(Size::lpx(100.0))
+                .with_layout_direction(Layout::Vertical)
+                .with_overflow(Overflow::Scroll)
+                .with_scroll_momentum(momentum)
+                .with_child(
+                    Node::new()
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(300.0)),
+                );
+            node.compute_layout(window);
+
+            for _ in 0..3 {
+                let offset = (0.0, node.scroll_offset().1 + step);
+                node.set_scroll_offset(offset);
+                node.set_scroll_target(offset);
+                let mut animation = node.scroll_animation();
+                animation.flick_input = (0.0, step);
+                node.set_scroll_animation(animation);
+                assert!(node.update_scroll_animation(dt));
+            }
+            let released = node.scroll_offset().1;
+
+            let mut highest = released;
+            for frame in 0.. {
+                let moving = node.update_scroll_animation(dt);
+                highest = highest.max(node.scroll_offset().1);
+                if !moving {
+                    break;
+                }
+                assert!(frame < 1000, "momentum never settled");
+            }
+            assert!(highest > released);
+            assert_eq!(node.scroll_offset(), node.scroll_target());
+            (highest, node.scroll_offset().1)
+        };
+
+        // A gentle flick coasts on and stops on its own
+        let (_, end) = flick(ScrollMomentum::default(), 5.0);
+        assert!(end > 15.0 && end < 200.0, "got {end}");
+
+        // A hard one stops at the end...
+        assert_eq!(flick(ScrollMomentum::default(), 30.0), (200.0, 200.0));
+
+        // ...or rubber-bands past it and springs back
+        let (highest, end) = flick(ScrollMomentum::default().with_overscroll(40.0), 30.0);
+        assert!(highest > 200.0 && highest <= 240.0, "got {highest}");
+        assert_eq!(end, 200.0);
+    }
+
+    #[test]
+    fn test_scroll_to_clamps_and_reports_metrics() {
+        use crate::{Layout, Overflow, Rect, ScrollSmoothing, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let build = || {
+            Node::new()
+                .with_id(NodeId::new("s"))
+                .with_width(Size::lpx(100.0))
+                .with_height(Size::lpx(100.0))
+                .with_layout_direction(Layout::Vertical)
+                .with_overflow(Overflow::Scroll)
+                .with_scroll_smoothing(ScrollSmoothing::Instant)
+                .with_child(
+                    Node::new()
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(500.0)),
+                )
+        };
+        let frame = |ctx: &mut UiContext| {
+            ctx.begin_frame();
+            let mut root = build();
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            root.scroll_offset()
+        };
+        let mut ctx = UiContext::new();
+
+        assert_eq!(ctx.scroll_metrics("s"), None);
+        frame(&mut ctx);
+        let metrics = ctx.scroll_metrics("s").unwrap();
+        assert_eq!(metrics.max_scroll, (0.0, 400.0));
+        assert_eq!(metrics.viewport, (100.0, 100.0));
+        assert_eq!(metrics.visible_fraction().1, 0.2);
+
+        // Targets past the end are clamped and persist across frames
+        ctx.scroll_to("s", (0.0, 1000.0));
+        assert_eq!(frame(&mut ctx), (0.0, 400.0));
+        assert_eq!(frame(&mut ctx), (0.0, 400.0));
+        assert_eq!(ctx.scroll_metrics("s").unwrap().progress().1, 1.0);
+    }
+
+    #[test]
+    fn test_shift_scroll_and_measured_size() {
+        use crate::{Layout, Overflow, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let build = |row_height: f32| {
+            Node::new()
+                .with_id(NodeId::new("s"))
+                .with_width(Size::lpx(100.0))
+                .with_height(Size::lpx(100.0))
+                .with_layout_direction(Layout::Vertical)
+                .with_overflow(Overflow::Scroll)
+                .with_children(vec![
+                    Node::new()
+                        .with_id(NodeId::new("row"))
+                        .with_measure_size(true)
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(row_height)),
+                    Node::new()
+                        .with_id(NodeId::new("unmeasured"))
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(500.0)),
+                ])
+        };
+        let frame = |ctx: &mut UiContext, row_height: f32| {
+            ctx.begin_frame();
+            let mut root = build(row_height);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            (root.scroll_offset(), root.scroll_target())
+        };
+        let mut ctx = UiContext::new();
+
+        frame(&mut ctx, 50.0);
+        assert_eq!(ctx.measured_size("row"), Some((100.0, 50.0)));
+        assert_eq!(ctx.measured_size("unmeasured"), None);
+
+        // Shifts move the offset immediately, even with smooth scrolling,
+        // and are clamped to the scroll range
+        ctx.scroll_to("s", (0.0, 100.0));
+        frame(&mut ctx, 50.0);
+        ctx.shift_scroll("s", (0.0, 30.0));
+        let (offset, target) = frame(&mut ctx, 80.0);
+        assert!(offset.1 >= 30.0);
+        assert_eq!(target, (0.0, 130.0));
+        assert_eq!(ctx.measured_size("row"), Some((100.0, 80.0)));
+
+        ctx.shift_scroll("s", (0.0, 1000.0));
+        assert_eq!(frame(&mut ctx, 80.0), ((0.0, 480.0), (0.0, 480.0)));
+    }
+
+    #[test]
+    fn test_clipboard_round_trip() {
+        let mut ctx = UiContext::new();
+        assert_eq!(ctx.clipboard_text(), "");
+        assert_eq!(ctx.take_copied_text(), None);
+
+        ctx.copy_to_clipboard("copied");
+        assert_eq!(ctx.clipboard_text(), "copied");
+        assert_eq!(ctx.take_copied_text().as_deref(), Some("copied"));
+        assert_eq!(ctx.take_copied_text(), None);
+
+        // Text from the system clipboard is pasted but not copied back
+        ctx.set_clipboard_text("system");
+        assert_eq!(ctx.clipboard_text(), "system");
+        assert_eq!(ctx.take_copied_text(), None);
+    }
+
+    #[test]
+    fn test_set_clipboard_copies_and_pastes_through_it() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct SharedClipboard(Rc<RefCell<String>>);
+
+        impl Clipboard for SharedClipboard {
+            fn get_text(&mut self) -> Option<String> {
+                Some(self.0.borrow().clone()).filter(|text| !text.is_empty())
+            }
+
+            fn set_text(&mut self, text: &str) {
+                *self.0.borrow_mut() = text.to_string();
+            }
+        }
+
+        let system = Rc::new(RefCell::new(String::from("from elsewhere")));
+        let mut ctx = UiContext::new();
+        ctx.set_clipboard(SharedClipboard(system.clone()));
+        assert!(ctx.has_clipboard());
+
+        // Copies go straight to the clipboard
+        ctx.copy_to_clipboard("copied");
+        assert_eq!(system.borrow().as_str(), "copied");
+        assert_eq!(ctx.take_copied_text(), None);
+
+        // The clipboard is only read for a paste shortcut
+        *system.borrow_mut() = "pasted".to_string();
+        ctx.begin_frame();
+        assert_eq!(ctx.clipboard_text(), "copied");
+
+        ctx.input_mut().ctrl_held = true;
+        ctx.input_mut()
+            .keys_just_pressed
+            .push(Key::Character("v".to_string()));
+        ctx.begin_frame();
+        assert_eq!(ctx.clipboard_text(), "pasted");
+    }
+
+    #[test]
+    fn test_emitted_events_arrive_with_the_next_frame() {
+        let mut ctx = UiContext::new();
+
+        ctx.begin_frame();
+        ctx.emit("list", CustomEvent::new("refresh"));
+        ctx.emit("list", CustomEvent::new("select").with_data(3_usize));
+        ctx.emit("other", CustomEvent::new("refresh"));
+        assert!(ctx.custom_event("list", "refresh").is_none());
+        ctx.end_frame(&mut Node::new());
+
+        let select = ctx.custom_event("list", "select").unwrap();
+        assert_eq!(select.data::<usize>(), Some(&3));
+        assert_eq!(select.data::<u32>(), None);
+        assert!(ctx
+            .custom_event("list", "refresh")
+            .unwrap()
+            .data::<usize>()
+            .is_none());
+        assert_eq!(ctx.custom_events("list").count(), 2);
+        assert_eq!(ctx.custom_events("other").count(), 1);
+        assert!(ctx.custom_event("missing", "refresh").is_none());
+
+        // Delivered once
+        ctx.begin_frame();
+        ctx.end_frame(&mut Node::new());
+        assert_eq!(ctx.custom_events("list").count(), 0);
+    }
+
+    #[test]
+    fn test_focus_management() {
+        let mut ctx = UiContext::new();
+
+        assert!(!ctx.is_focused("my_input"));
+
+        ctx.set_focus(Some("my_input"));
+        assert!(ctx.is_focused("my_input"));
+
+        ctx.set_focus(None);
+        assert!(!ctx.is_focused("my_input"));
+    }
+
+    #[test]
+    fn test_peek_requested_after_delay_and_dismissed_on_leave() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        let frame = |ctx: &mut UiContext, cursor: Point| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("link"))
+                        .with_width(Size::lpx(50.0))
+                        .with_height(Size::lpx(20.0))
+                        .with_peek_delay(std::time::Duration::from_millis(20)),
+                );
+            ctx.input_mut().cursor_position = Some(cursor);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        // Hovering starts the timer but doesn't open the peek yet
+        frame(&mut ctx, Point::new(10.0, 10.0));
+        assert!(!ctx.was_peek_requested("link"));
+        assert!(!ctx.is_peek_open("link"));
+
+        // After the delay the peek is requested exactly once
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        frame(&mut ctx, Point::new(10.0, 10.0));
+        assert!(ctx.was_peek_requested("link"));
+        assert!(ctx.is_peek_open("link"));
+
+        frame(&mut ctx, Point::new(12.0, 10.0));
+        assert!(!ctx.was_peek_requested("link"));
+        assert!(ctx.is_peek_open("link"));
+
+        // Leaving the anchor dismisses it
+        frame(&mut ctx, Point::new(150.0, 150.0));
+        assert!(!ctx.is_peek_open("link"));
+        assert!(ctx
+            .events_for("link")
+            .any(|e| matches!(e.event, InteractionEvent::PeekDismissed)));
+    }
+
+    #[test]
+    fn test_node_closures_run_for_their_events() {
+        use crate::{Layout, Overflow, Point, Rect, Size};
+        use std::cell::{Cell, RefCell};
+        use std::rc::Rc;
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 100.0]);
+        let mut ctx = UiContext::new();
+        let clicks = Rc::new(Cell::new(0));
+        let dragged = Rc::new(Cell::new(0.0));
+        let scrolled = Rc::new(RefCell::new(Vec::new()));
+
+        // Nodes without IDs: a button, and a scroll handler inside another
+        let frame = |ctx: &mut UiContext, x: f32, press: Option<bool>, wheel: f32| {
+            ctx.begin_frame();
+            let (clicks, dragged) = (clicks.clone(), dragged.clone());
+            let (outer, inner) = (scrolled.clone(), scrolled.clone());
+            let mut root = Node::new()
+                .with_layout_direction(Layout::Horizontal)
+                .with_children(vec![
+                    Node::new()
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(100.0))
+                        .on_click(move |_| clicks.set(clicks.get() + 1))
+                        .on_drag(move |_, delta| dragged.set(dragged.get() + delta.x)),
+                    Node::new()
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(100.0))
+                        .with_overflow(Overflow::Hidden)
+                        .on_scroll(move |_, _| outer.borrow_mut().push("outer"))
+                        .with_child(
+                            Node::new()
+                                .with_width(Size::lpx(50.0))
+                                .with_height(Size::lpx(100.0))
+                                .on_scroll(move |ctx, delta| {
+                                    assert_eq!(delta, (0.0, wheel));
+                                    inner.borrow_mut().push("inner");
+                                    ctx.stop_propagation();
+                                }),
+                        ),
+                ]);
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = Some(Point::new(x, 50.0));
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Left),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Left),
+                None => {}
+            }
+            if wheel != 0.0 {
+                ctx.input_mut().add_scroll_delta(0.0, wheel);
+            }
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        frame(&mut ctx, 10.0, Some(true), 0.0);
+        frame(&mut ctx, 10.0, Some(false), 0.0);
+        assert_eq!(clicks.get(), 1);
+
+        // Dragging reports the movement
+        frame(&mut ctx, 10.0, Some(true), 0.0);
+        frame(&mut ctx, 10.0, None, 0.0);
+        frame(&mut ctx, 30.0, None, 0.0);
+        frame(&mut ctx, 35.0, None, 0.0);
+        frame(&mut ctx, 35.0, Some(false), 0.0);
+        assert_eq!(dragged.get(), 25.0);
+
+        // The wheel starts at the innermost closure under the pointer, which
+        // consumes it
+        frame(&mut ctx, 120.0, None, 40.0);
+        frame(&mut ctx, 180.0, None, 40.0);
+        frame(&mut ctx, 180.0, None, 0.0);
+        assert_eq!(*scrolled.borrow(), ["inner", "outer"]);
+    }
+
+    #[test]
+    fn test_node_closures_bubble_until_propagation_stops() {
+        use crate::{Point, Rect, Size};
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        // Click a card inside a panel, both with click closures
+        let click = |ctx: &mut UiContext, stop: bool| {
+            let (panel, card) = (log.clone(), log.clone());
+            let node = |ctx: &mut UiContext| {
+                ctx.begin_frame();
+                let (panel, card) = (panel.clone(), card.clone());
+                let mut root = Node::new()
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0))
+                    .on_click(move |_| panel.borrow_mut().push("panel"))
+                    .with_child(
+                        Node::new()
+                            .with_width(Size::lpx(50.0))
+                            .with_height(Size::lpx(50.0))
+                            .on_click(move |ctx| {
+                                card.borrow_mut().push("card");
+                                if stop {
+                                    ctx.stop_propagation();
+                                }
+                            }),
+                    );
+                root.compute_layout(window);
+                root
+            };
+            ctx.input_mut().cursor_position = Some(Point::new(10.0, 10.0));
+            for press in [true, false] {
+                let mut root = node(ctx);
+                if press {
+                    ctx.input_mut().press_button(MouseButton::Left);
+                } else {
+                    ctx.input_mut().release_button(MouseButton::Left);
+                }
+                ctx.end_frame(&mut root);
+                ctx.input_mut().begin_frame();
+            }
+            log.take()
+        };
+
+        assert_eq!(click(&mut ctx, false), ["card", "panel"]);
+        assert_eq!(click(&mut ctx, true), ["card"]);
+    }
+
+    #[test]
+    fn test_recorded_input_replays_the_same_interaction() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+
+        // Run a frame, returning whether the button was clicked
+        let frame = |ctx: &mut UiContext| {
+            ctx.begin_frame();
+            let mut root = Node::new().with_child(
+                Node::new()
+                    .with_id(NodeId::new("button"))
+                    .with_width(Size::lpx(50.0))
+                    .with_height(Size::lpx(50.0)),
+            );
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+            ctx.was_clicked("button")
+        };
+
+        // Record a press and release on the button
+        let mut ctx = UiContext::new();
+        ctx.start_input_recording();
+        ctx.input_mut().cursor_position = Some(Point::new(10.0, 10.0));
+        ctx.input_mut().press_button(MouseButton::Left);
+        assert!(!frame(&mut ctx));
+        ctx.input_mut().release_button(MouseButton::Left);
+        assert!(frame(&mut ctx));
+        let recording = ctx.stop_input_recording().unwrap();
+        assert_eq!(recording.len(), 2);
+        assert_eq!(recording.frames()[0].dt, 0.0);
+        let recorded_time = ctx.time();
+
+        // Replayed on a fresh context, live input is ignored and the click
+        // happens again with the recorded frame times
+        let mut ctx = UiContext::new();
+        ctx.play_input(recording);
+        ctx.input_mut().cursor_position = Some(Point::new(90.0, 90.0));
+        assert!(!frame(&mut ctx));
+        assert!(frame(&mut ctx));
+        assert_eq!(ctx.time(), recorded_time);
+
+        // Once it ends, input starts over empty
+        assert!(ctx.is_playing_input());
+        assert!(!frame(&mut ctx));
+        assert!(!ctx.is_playing_input());
+        assert_eq!(ctx.input().cursor_position, None);
+    }
+
+    #[test]
+    fn test_pointer_enter_and_leave_follow_hover_boundaries() {
+        use crate::{Layout, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [300.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        // A "row" holding cells "a" and "b", beside a cell "c"
+        let frame = |ctx: &mut UiContext, x: Option<f32>| {
+            ctx.begin_frame();
+            let cell = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0))
+            };
+            let mut root = Node::new()
+                .with_layout_direction(Layout::Horizontal)
+                .with_children(vec![
+                    Node::new()
+                        .with_id(NodeId::new("row"))
+                        .with_width(Size::lpx(200.0))
+                        .with_layout_direction(Layout::Horizontal)
+                        .with_children(vec![cell("a"), cell("b")]),
+                    cell("c"),
+                ]);
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = x.map(|x| Point::new(x, 50.0));
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        let changes = |ctx: &UiContext| -> Vec<String> {
+            ctx.events()
+                .iter()
+                .filter_map(|e| match e.event {
+                    InteractionEvent::PointerEnter { .. } => {
+                        Some(format!("enter {}", e.target.as_str()))
+                    }
+                    InteractionEvent::PointerLeave => Some(format!("leave {}", e.target.as_str())),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        frame(&mut ctx, Some(50.0));
+        assert_eq!(changes(&ctx), ["enter row", "enter a"]);
+        assert!(ctx.pointer_entered("row"));
+        frame(&mut ctx, Some(60.0));
+        assert!(changes(&ctx).is_empty());
+
+        // Moving between children keeps the parent entered
+        frame(&mut ctx, Some(150.0));
+        assert_eq!(changes(&ctx), ["leave a", "enter b"]);
+        frame(&mut ctx, Some(250.0));
+        assert_eq!(changes(&ctx), ["leave b", "leave row", "enter c"]);
+        assert!(ctx.pointer_left("row"));
+
+        // Leaving the window leaves everything
+        frame(&mut ctx, None);
+        assert_eq!(changes(&ctx), ["leave c"]);
+    }
+
+    #[test]
+    fn test_hover_intent_waits_for_the_cursor_to_rest() {
+        use crate::{Layout, Point, Rect, Size};
+        use std::time::Duration;
+
+        let window = Rect::from_min_size([0.0, 0.0], [300.0, 100.0]);
+        let mut ctx = UiContext::new();
+        ctx.set_hover_intent_delay(Duration::from_millis(20));
+
+        let frame = |ctx: &mut UiContext, x: f32| {
+            ctx.begin_frame();
+            let cell = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0))
+            };
+            let mut root = Node::new()
+                .with_layout_direction(Layout::Horizontal)
+                .with_children(vec![cell("a"), cell("b"), cell("c")]);
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = Some(Point::new(x, 50.0));
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        // Sweeping across starts no hovers
+        for x in [50.0, 150.0, 250.0] {
+            frame(&mut ctx, x);
+            assert!(ctx.is_hovered(["a", "b", "c"][(x / 100.0) as usize]));
+            assert!(!ctx.hover_started("a") && !ctx.hover_started("b"));
+        }
+        assert_eq!(ctx.hovered_for("c"), None);
+
+        // Resting starts one, then reports how long it has lasted
+        std::thread::sleep(Duration::from_millis(30));
+        frame(&mut ctx, 255.0);
+        assert!(ctx.hover_started("c"));
+        let held = ctx.hovered_for("c").unwrap();
+        assert!(held >= Duration::from_millis(20));
+        frame(&mut ctx, 260.0);
+        assert!(!ctx.hover_started("c"));
+        assert!(ctx.hovered_for("c").unwrap() >= held);
+
+        // Leaving ends it; nodes passed on the way never started
+        frame(&mut ctx, 150.0);
+        assert!(ctx.hover_ended("c"));
+        assert_eq!(ctx.hovered_for("c"), None);
+        frame(&mut ctx, 50.0);
+        assert!(!ctx.hover_ended("b"));
+    }
+
+    #[test]
+    fn test_drag_ghost_follows_pointer_until_drag_ends() {
+        use crate::{DragGhost, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        let frame = |ctx: &mut UiContext, cursor: Point, press: Option<bool>| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("item"))
+                        .with_width(Size::lpx(50.0))
+                        .with_height(Size::lpx(20.0)),
+                );
+            ctx.input_mut().cursor_position = Some(cursor);
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Left),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Left),
+                None => {}
+            }
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        // Without a drag, setting a ghost does nothing
+        ctx.set_drag_ghost(DragGhost::new(Node::new));
+        assert!(!ctx.has_drag_ghost());
+
+        frame(&mut ctx, Point::new(10.0, 5.0), Some(true));
+        frame(&mut ctx, Point::new(12.0, 5.0), None);
+        assert!(ctx.is_dragging("item"));
+        ctx.set_drag_ghost(DragGhost::new(|| {
+            Node::new()
+                .with_width(Size::lpx(30.0))
+                .with_height(Size::lpx(30.0))
+        }));
+
+        // The ghost keeps the grab point under the pointer
+        frame(&mut ctx, Point::new(100.0, 80.0), None);
+        let layer = ctx.drag_ghost_layer().unwrap();
+        let translation = layer.translation();
+        assert_eq!(
+            (translation.x, translation.y),
+            (Size::ppx(88.0), Size::ppx(75.0))
+        );
+        assert_eq!(layer.opacity(), 0.6);
+
+        frame(&mut ctx, Point::new(100.0, 80.0), Some(false));
+        assert!(!ctx.has_drag_ghost());
+        assert!(ctx.drag_ghost_layer().is_none());
+    }
+
+    #[test]
+    fn test_drag_config_applies_threshold_axis_lock_and_snapping() {
+        use crate::{DragAxis, DragConfig, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        let frame = |ctx: &mut UiContext, cursor: Point, press: Option<bool>| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("handle"))
+                        .with_width(Size::lpx(50.0))
+                        .with_height(Size::lpx(20.0))
+                        .with_drag(
+                            DragConfig::new()
+                                .with_threshold(5.0)
+                                .with_axis(DragAxis::X)
+                                .with_snap(10.0),
+                        ),
+                );
+            ctx.input_mut().cursor_position = Some(cursor);
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Left),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Left),
+                None => {}
+            }
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        let drag_position = |ctx: &UiContext| {
+            ctx.events().iter().find_map(|e| match e.event {
+                InteractionEvent::DragStart { position, .. }
+                | InteractionEvent::DragMove { position, .. }
+                | InteractionEvent::DragEnd { position, .. } => Some(position),
+                _ => None,
+            })
+        };
+
+        // A press that stays under the threshold is still a click
+        frame(&mut ctx, Point::new(10.0, 5.0), Some(true));
+        frame(&mut ctx, Point::new(13.0, 5.0), None);
+        assert!(!ctx.is_dragging("handle"));
+        frame(&mut ctx, Point::new(13.0, 5.0), Some(false));
+        assert!(ctx.was_clicked("handle"));
+
+        // Vertical movement doesn't count towards an X-locked threshold
+        frame(&mut ctx, Point::new(10.0, 5.0), Some(true));
+        frame(&mut ctx, Point::new(12.0, 15.0), None);
+        assert!(!ctx.is_dragging("handle"));
+
+        // Past the threshold the drag starts, locked to X and snapped
+        frame(&mut ctx, Point::new(16.0, 30.0), None);
+        assert!(ctx.is_dragging("handle"));
+        assert_eq!(drag_position(&ctx), Some(Point::new(20.0, 5.0)));
+
+        // Moves within the same grid cell send nothing
+        frame(&mut ctx, Point::new(24.0, 50.0), None);
+        assert_eq!(ctx.drag_delta("handle"), None);
+        frame(&mut ctx, Point::new(26.0, 50.0), None);
+        assert_eq!(ctx.drag_delta("handle"), Some(Point::new(10.0, 0.0)));
+        assert_eq!(drag_position(&ctx), Some(Point::new(30.0, 5.0)));
+
+        frame(&mut ctx, Point::new(41.0, 50.0), Some(false));
+        assert_eq!(drag_position(&ctx), Some(Point::new(40.0, 5.0)));
+    }
+
+    #[test]
+    fn test_platform_output_gathers_window_and_url_requests() {
+        use crate::{
+            CursorIcon, Layout, PlatformRequest, Point, Rect, ResizeDirection, Size, WindowAction,
+        };
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        let press = |ctx: &mut UiContext, cursor: Point| {
+            ctx.begin_frame();
+            let mut root = Node::new().with_children(vec![
+                Node::new()
+                    .with_layout_direction(Layout::Horizontal)
+                    .with_height(Size::lpx(20.0))
+                    .with_window_action(WindowAction::Drag)
+                    .with_children(vec![
+                        Node::new().with_width(Size::lpx(100.0)),
+                        Node::new()
+                            .with_id(NodeId::new("close"))
+                            .with_width(Size::lpx(20.0)),
+                    ]),
+                Node::new()
+                    .with_height(Size::lpx(10.0))
+                    .with_cursor(CursorIcon::NsResize)
+                    .with_window_action(WindowAction::Resize(ResizeDirection::South)),
+            ]);
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = Some(cursor);
+            ctx.input_mut().press_button(MouseButton::Left);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().release_button(MouseButton::Left);
+            ctx.input_mut().begin_frame();
+        };
+
+        // The title bar and its unidentified label move the window
+        press(&mut ctx, Point::new(50.0, 10.0));
+        let output = ctx.take_platform_output();
+        assert_eq!(output.requests, vec![PlatformRequest::DragWindow]);
+        assert!(ctx.take_platform_output().requests.is_empty());
+
+        // An identified node on the title bar keeps the press
+        press(&mut ctx, Point::new(110.0, 10.0));
+        assert!(ctx.take_platform_output().requests.is_empty());
+
+        press(&mut ctx, Point::new(50.0, 25.0));
+        ctx.open_url("https://example.com");
+        let output = ctx.take_platform_output();
+        assert_eq!(output.cursor_icon, CursorIcon::NsResize);
+        assert_eq!(
+            output.requests,
+            vec![
+                PlatformRequest::ResizeWindow(ResizeDirection::South),
+                PlatformRequest::OpenUrl("https://example.com".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_camera_controller_zooms_to_the_cursor_and_pans() {
+        use crate::{CameraController, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+        let mut camera = CameraController::new()
+            .with_zoom_range(0.5, 4.0)
+            .with_zoom_speed(std::f32::consts::LN_2 / 20.0)
+            .with_smoothing(0.0);
+
+        let frame = |ctx: &mut UiContext,
+                     camera: &mut CameraController,
+                     (x, y): (f32, f32),
+                     wheel: f32,
+                     press: Option<bool>| {
+            ctx.input_mut().cursor_position = Some(Point::new(x, y));
+            ctx.input_mut().scroll_delta = (0.0, wheel);
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Middle),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Middle),
+                None => {}
+            }
+            ctx.begin_frame();
+            camera.update(ctx, "view");
+            let mut root = camera.apply(
+                Node::new()
+                    .with_id(NodeId::new("view"))
+                    .with_width(Size::lpx(200.0))
+                    .with_height(Size::lpx(200.0)),
+            );
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        let close = |a: (f32, f32), b: (f32, f32)| (a.0 - b.0).hypot(a.1 - b.1) < 0.01;
+
+        // A wheel step doubles the zoom around the cursor
+        frame(&mut ctx, &mut camera, (100.0, 100.0), 0.0, None);
+        frame(&mut ctx, &mut camera, (100.0, 100.0), 20.0, None);
+        assert!((camera.zoom() - 2.0).abs() < 0.001);
+        assert!(close(camera.pan(), (-100.0, -100.0)));
+
+        // Dragging with the pan button moves the view
+        frame(&mut ctx, &mut camera, (100.0, 100.0), 0.0, Some(true));
+        frame(&mut ctx, &mut camera, (110.0, 105.0), 0.0, None);
+        frame(&mut ctx, &mut camera, (120.0, 115.0), 0.0, None);
+        frame(&mut ctx, &mut camera, (120.0, 115.0), 0.0, Some(false));
+        assert!(close(camera.pan(), (-90.0, -90.0)));
+
+        // Zoom stays within its range
+        frame(&mut ctx, &mut camera, (0.0, 0.0), 200.0, None);
+        assert_eq!(camera.zoom(), 4.0);
+    }
+
+    #[test]
+    fn test_retained_tree_lays_out_only_changed_subtrees() {
+        use crate::{Layout, LayoutPass, Point, Rect, Size, Spacing};
+
+        let window = Rect::from_min_size([0.0, 0.0], [400.0, 400.0]);
+        let mut ctx = UiContext::new();
+
+        let item = |id: &str, revision: u64| {
+            Node::new()
+                .with_id(NodeId::new(id))
+                .with_revision(revision)
+                .with_width(Size::lpx(50.0))
+                .with_height(Size::lpx(50.0))
+                .with_padding(Spacing::all(Size::lpx(revision as f32)))
+        };
+        let build = |a: u64, b: u64| {
+            Node::new().with_revision(0).with_child(
+                Node::new()
+                    .with_id(NodeId::new("panel"))
+                    .with_revision(0)
+                    .with_width(Size::lpx(200.0))
+                    .with_height(Size::lpx(200.0))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_children(vec![item("a", a), item("b", b)]),
+            )
+        };
+        // Both items sit in the panel, the root's only child
+        let rect = |ctx: &mut UiContext, item: usize| {
+            let panel = &ctx.retained().root().unwrap().children()[0];
+            panel.children()[item].computed_layout().unwrap().rect
+        };
+
+        // The first tree is laid out as a whole
+        ctx.retained().update(build(0, 0));
+        assert_eq!(ctx.retained().layout(window, None), LayoutPass::Full);
+        let b = rect(&mut ctx, 1);
+
+        // Unchanged revisions keep the previous layout
+        ctx.retained().update(build(0, 0));
+        assert!(!ctx.retained().needs_layout());
+        assert_eq!(ctx.retained().layout(window, None), LayoutPass::Skipped);
+
+        // A changed fixed-size node is laid out again where it was
+        ctx.retained().update(build(0, 1));
+        assert_eq!(ctx.retained().layout(window, None), LayoutPass::Subtrees(1));
+        assert_eq!(rect(&mut ctx, 1), b);
+
+        // Mutations are tracked the same way
+        assert!(ctx
+            .retained()
+            .mutate("a", |node| *node = std::mem::take(node).with_opacity(0.5)));
+        assert!(!ctx.retained().mutate("missing", |_| {}));
+        assert_eq!(ctx.retained().layout(window, None), LayoutPass::Subtrees(1));
+
+        // Resizing a node lays out its fixed-size parent
+        ctx.retained().mutate("a", |node| {
+            *node = std::mem::take(node).with_height(Size::lpx(80.0))
+        });
+        assert_eq!(ctx.retained().layout(window, None), LayoutPass::Subtrees(1));
+        assert_eq!(rect(&mut ctx, 1).min[1], b.min[1] + 30.0);
+
+        // ... and resizing a node without one lays out everything
+        ctx.retained().mutate("panel", |node| {
+            *node = std::mem::take(node).with_width(Size::lpx(300.0))
+        });
+        assert_eq!(ctx.retained().layout(window, None), LayoutPass::Full);
+
+        // Events are dispatched against the kept tree
+        let b = rect(&mut ctx, 1);
+        ctx.input_mut().cursor_position = Some(Point::new(b.min[0] + 1.0, b.min[1] + 1.0));
+        ctx.begin_frame();
+        ctx.end_retained_frame();
+        ctx.input_mut().begin_frame();
+        ctx.begin_frame();
+        assert!(ctx.is_hovered("b"));
+    }
+
+    #[test]
+    fn test_retained_tree_skips_layout_for_visual_changes() {
+        use crate::{Layout, LayoutPass, Rect, RetainedTree, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [400.0, 400.0]);
+        let mut tree = RetainedTree::new();
+
+        // No revisions: every node is rebuilt and compared
+        let build = |height: f32, opacity: f32| {
+            Node::new().with_child(
+                Node::new()
+                    .with_width(Size::lpx(200.0))
+                    .with_height(Size::lpx(200.0))
+                    .with_layout_direction(Layout::Vertical)
+                    .with_children(vec![
+                        Node::new()
+                            .with_width(Size::lpx(50.0))
+                            .with_height(Size::lpx(height)),
+                        Node::new()
+                            .with_width(Size::lpx(50.0))
+                            .with_height(Size::lpx(50.0))
+                            .with_opacity(opacity),
+                    ]),
+            )
+        };
+        let second = |tree: &RetainedTree| {
+            let panel = &tree.root().unwrap().children()[0];
+            let child = &panel.children()[1];
+            (child.computed_layout().unwrap().rect, child.opacity())
+        };
+
+        tree.update(build(50.0, 1.0));
+        assert_eq!(tree.layout(window, None), LayoutPass::Full);
+        let (rect, _) = second(&tree);
+
+        // New visuals keep the previous layout
+        tree.update(build(50.0, 0.5));
+        assert!(!tree.needs_layout());
+        assert_eq!(tree.layout(window, None), LayoutPass::Skipped);
+        assert_eq!(second(&tree), (rect, 0.5));
+
+        // A new size lays out the fixed-size parent
+        tree.update(build(80.0, 0.5));
+        assert_eq!(tree.layout(window, None), LayoutPass::Subtrees(1));
+        assert_eq!(second(&tree).0.min[1], rect.min[1] + 30.0);
+
+        // Removing a child lays out its parent too
+        let mut root = build(80.0, 0.5);
+        let mut children = root.children_mut()[0].take_children();
+        children.pop();
+        root.children_mut()[0].set_children(children);
+        tree.update(root);
+        assert_eq!(tree.layout(window, None), LayoutPass::Subtrees(1));
+    }
+
+    #[test]
+    fn test_retained_tree_reuses_shapes_of_unchanged_subtrees() {
+        use crate::color::css;
+        use crate::output::ShapeCache;
+        use crate::{
+            Color, FullOutput, LayoutPass, Rect, RetainedTree, Shape, Size, Style, StyledRect,
+        };
+
+        let window = Rect::from_min_size([0.0, 0.0], [400.0, 400.0]);
+        let mut tree = RetainedTree::new();
+
+        let item = |id: &str, revision: u64, color: Color| {
+            Node::new()
+                .with_id(NodeId::new(id))
+                .with_revision(revision)
+                .with_width(Size::lpx(50.0))
+                .with_height(Size::lpx(50.0))
+                .with_shape(Shape::Rect(StyledRect::new(Rect::default(), color)))
+        };
+        // The panel has no revision, so it is rebuilt every time
+        let build = |b: u64, opacity: f32| {
+            Node::new().with_child(
+                Node::new()
+                    .with_width(Size::lpx(200.0))
+                    .with_height(Size::lpx(200.0))
+                    .with_opacity(opacity)
+                    .with_children(vec![
+                        item("a", 0, css::RED),
+                        item("b", b, css::GREEN),
+                        item("c", 0, css::BLUE).with_style(Style::opacity(1.0)),
+                    ]),
+            )
+        };
+        let cache = |tree: &RetainedTree, item: usize| {
+            let node = &tree.root().unwrap().children()[0].children()[item];
+            match &*node.shape_cache().borrow() {
+                ShapeCache::Empty => "empty",
+                ShapeCache::Volatile => "volatile",
+                ShapeCache::Shapes { .. } => "shapes",
+            }
+        };
+        let output =
+            |tree: &RetainedTree| format!("{:?}", tree.output((400.0, 400.0), None).shapes);
+        let fresh = |tree: &RetainedTree| {
+            let root = tree.root().unwrap();
+            format!(
+                "{:?}",
+                FullOutput::from_laid_out_tree(root, (400.0, 400.0), None).shapes
+            )
+        };
+
+        // Static revisioned subtrees keep their shapes, styled ones don't
+        tree.update(build(0, 1.0));
+        tree.layout(window, None);
+        let first = output(&tree);
+        assert_eq!(first, fresh(&tree));
+        assert_eq!(
+            [cache(&tree, 0), cache(&tree, 1), cache(&tree, 2)],
+            ["shapes", "shapes", "volatile"]
+        );
+
+        // Unchanged subtrees reuse them
+        tree.update(build(0, 1.0));
+        assert_eq!(tree.layout(window, None), LayoutPass::Skipped);
+        assert_eq!(cache(&tree, 0), "shapes");
+        assert_eq!(output(&tree), first);
+
+        // A new revision drops the node's shapes
+        tree.update(build(1, 1.0));
+        assert_eq!([cache(&tree, 0), cache(&tree, 1)], ["shapes", "empty"]);
+        tree.layout(window, None);
+        assert_eq!(output(&tree), fresh(&tree));
+
+        // So does a mutation
+        tree.mutate("a", |node| {
+            *node = std::mem::take(node)
+                .with_revision(0)
+                .with_shape(Shape::Rect(StyledRect::new(Rect::default(), css::WHITE)))
+        });
+        tree.layout(window, None);
+        let mutated = output(&tree);
+        assert_ne!(mutated, first);
+        assert_eq!(mutated, fresh(&tree));
+
+        // Cached shapes aren't reused under a different ancestor opacity
+        tree.update(build(1, 0.5));
+        assert_eq!(tree.layout(window, None), LayoutPass::Skipped);
+        let faded = tree.output((400.0, 400.0), None);
+        assert!(faded.shapes.iter().all(|shape| shape.opacity == 0.5));
+    }
+
+    #[test]
+    fn test_files_go_to_the_node_under_the_pointer() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        let frame = |ctx: &mut UiContext| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("zone"))
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(100.0)),
+                );
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        // The pointer is unknown while files are dragged in, so nothing is
+        // hovered, and the drop waits for the pointer
+        ctx.input_mut().hover_file(PathBuf::from("photo.png"));
+        frame(&mut ctx);
+        assert!(ctx.is_file_drag_active());
+        assert!(ctx.hovered_files("zone").is_none());
+        ctx.input_mut().drop_file(PathBuf::from("photo.png"));
+        frame(&mut ctx);
+        assert!(!ctx.is_file_drag_active());
+        assert!(ctx.dropped_files("zone").is_none());
+
+        ctx.input_mut().cursor_position = Some(Point::new(50.0, 50.0));
+        frame(&mut ctx);
+        assert_eq!(
+            ctx.dropped_files("zone"),
+            Some([PathBuf::from("photo.png")].as_slice())
+        );
+        frame(&mut ctx);
+        assert!(ctx.dropped_files("zone").is_none());
+
+        // With a known pointer, hovering files target the node under it
+        ctx.input_mut().hover_file(PathBuf::from("notes.txt"));
+        frame(&mut ctx);
+        assert_eq!(ctx.hovered_files("zone").map(|files| files.len()), Some(1));
+        ctx.input_mut().cursor_position = Some(Point::new(150.0, 150.0));
+        frame(&mut ctx);
+        assert!(ctx.hovered_files("zone").is_none());
+    }
+
+    #[test]
+    fn test_heatmap_records_hovers_and_dead_clicks() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+        ctx.set_heatmap_recording(true);
+
+        let press_at = |ctx: &mut UiContext, cursor: Point| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("button"))
+                        .with_width(Size::lpx(50.0))
+                        .with_height(Size::lpx(50.0)),
+                );
+            ctx.input_mut().cursor_position = Some(cursor);
+            ctx.input_mut()
+                .buttons_just_pressed
+                .insert(MouseButton::Left);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        press_at(&mut ctx, Point::new(10.0, 10.0));
+        press_at(&mut ctx, Point::new(150.0, 150.0));
+
+        let on_button = ctx.heatmap().cell_at(Point::new(10.0, 10.0));
+        assert_eq!(on_button.hovers, 1);
+        assert_eq!(on_button.clicks, 1);
+        assert_eq!(on_button.dead_clicks, 0);
+
+        let empty_space = ctx.heatmap().cell_at(Point::new(150.0, 150.0));
+        assert_eq!(empty_space.clicks, 0);
+        assert_eq!(empty_space.dead_clicks, 1);
+    }
+
+    #[test]
+    fn test_pressed_outside_counts_overflowing_children_as_inside() {
+        use crate::{Layout, Point, Rect, Size, Translation};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        // A menu whose popup hangs below its bounds
+        let press_at = |ctx: &mut UiContext, cursor: Point| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("menu"))
+                        .with_width(Size::lpx(50.0))
+                        .with_height(Size::lpx(20.0))
+                        .with_layout_direction(Layout::Stack)
+                        .with_child(
+                            Node::new()
+                                .with_id(NodeId::new("popup"))
+                                .with_width(Size::lpx(80.0))
+                                .with_height(Size::lpx(60.0))
+                                .with_translation(Translation::new(
+                                    Size::lpx(0.0),
+                                    Size::lpx(20.0),
+                                )),
+                        ),
+                );
+            ctx.input_mut().cursor_position = Some(cursor);
+            ctx.input_mut().press_button(MouseButton::Left);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().release_button(MouseButton::Left);
+            ctx.input_mut().begin_frame();
+        };
+
+        assert!(!ctx.was_pressed_outside("menu"));
+        press_at(&mut ctx, Point::new(70.0, 50.0));
+        assert!(ctx.is_hovered("popup"));
+        assert!(!ctx.was_pressed_outside("menu"));
+        assert!(!ctx.was_pressed_outside("popup"));
+
+        press_at(&mut ctx, Point::new(10.0, 10.0));
+        assert!(!ctx.was_pressed_outside("menu"));
+        assert!(ctx.was_pressed_outside("popup"));
+
+        press_at(&mut ctx, Point::new(150.0, 150.0));
+        assert!(ctx.was_pressed_outside("menu"));
+    }
+
+    #[test]
+    fn test_pressed_inside_counts_only_the_topmost_node() {
+        use crate::{Layout, Point, Rect, Size, Translation, ZIndex};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 200.0]);
+        let mut ctx = UiContext::new();
+
+        // Two overlapping panels; "back" comes later in the tree but sits lower
+        let panel = |id: &str, x: f32, z: i32| {
+            Node::new()
+                .with_id(NodeId::new(id))
+                .with_width(Size::lpx(100.0))
+                .with_height(Size::lpx(100.0))
+                .with_translation(Translation::new(Size::lpx(x), Size::lpx(0.0)))
+                .with_z_index(ZIndex(z))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new(format!("{}_button", id)))
+                        .with_width(Size::lpx(20.0))
+                        .with_height(Size::lpx(20.0)),
+                )
+        };
+        let press_at = |ctx: &mut UiContext, cursor: Point| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(200.0))
+                .with_layout_direction(Layout::Stack)
+                .with_children(vec![panel("front", 0.0, 2), panel("back", 50.0, 1)]);
+            ctx.input_mut().cursor_position = Some(cursor);
+            ctx.input_mut().press_button(MouseButton::Left);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().release_button(MouseButton::Left);
+            ctx.input_mut().begin_frame();
+        };
+
+        press_at(&mut ctx, Point::new(75.0, 50.0));
+        assert!(ctx.was_pressed_inside("front"));
+        assert!(!ctx.was_pressed_inside("back"));
+        assert!(!ctx.was_pressed_outside("back"));
+
+        press_at(&mut ctx, Point::new(10.0, 10.0));
+        assert!(ctx.was_pressed_inside("front"));
+        assert!(ctx.was_pressed_inside("front_button"));
+
+        press_at(&mut ctx, Point::new(120.0, 50.0));
+        assert!(ctx.was_pressed_inside("back"));
+        assert!(!ctx.was_pressed_inside("front"));
+
+        press_at(&mut ctx, Point::new(180.0, 180.0));
+        assert!(!ctx.was_pressed_inside("back"));
+    }
+
+    #[test]
+    fn test_input_barrier_blocks_nodes_below_and_tracks_focus_within() {
+        use crate::{Layout, Point, Rect, Size, ZIndex};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        // A button covered by a modal backdrop holding a dialog
+        let frame = |ctx: &mut UiContext, cursor: Point, click: bool| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(100.0))
+                .with_layout_direction(Layout::Stack)
+                .with_children(vec![
+                    Node::new()
+                        .with_id(NodeId::new("behind"))
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(100.0)),
+                    Node::new()
+                        .with_id(NodeId::new("backdrop"))
+                        .with_width(Size::Fill)
+                        .with_height(Size::Fill)
+                        .with_input_barrier(true)
+                        .with_child(
+                            Node::new()
+                                .with_id(NodeId::new("dialog"))
+                                .with_width(Size::lpx(50.0))
+                                .with_height(Size::lpx(50.0)),
+                        ),
+                    // Drawn above the backdrop despite coming first in the tree
+                    Node::new()
+                        .with_id(NodeId::new("toast"))
+                        .with_width(Size::lpx(20.0))
+                        .with_height(Size::lpx(20.0))
+                        .with_z_index(ZIndex::TOOLTIP),
+                ]);
+            ctx.input_mut().cursor_position = Some(cursor);
+            if click {
+                ctx.input_mut().press_button(MouseButton::Left);
+                ctx.input_mut().release_button(MouseButton::Left);
+            }
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        frame(&mut ctx, Point::new(40.0, 40.0), true);
+        assert!(ctx.was_clicked("dialog"));
+        assert!(ctx.is_hovered("backdrop"));
+        assert!(!ctx.is_hovered("behind"));
+        assert!(!ctx.was_clicked("behind"));
+
+        frame(&mut ctx, Point::new(80.0, 80.0), true);
+        assert!(ctx.was_clicked("backdrop"));
+        assert!(!ctx.was_clicked("behind"));
+
+        frame(&mut ctx, Point::new(10.0, 10.0), true);
+        assert!(ctx.was_clicked("toast"));
+
+        ctx.set_focus(Some("dialog"));
+        frame(&mut ctx, Point::new(40.0, 40.0), false);
+        assert!(ctx.is_focus_within("dialog"));
+        assert!(ctx.is_focus_within("backdrop"));
+        assert!(!ctx.is_focus_within("behind"));
+    }
+
+    #[test]
+    fn test_right_click_requests_context_menu_and_sent_events_arrive() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 100.0]);
+        let mut ctx = UiContext::new();
+        let frame = |ctx: &mut UiContext, release: Option<MouseButton>| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_width(Size::lpx(200.0))
+                .with_height(Size::lpx(100.0))
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("file"))
+                        .with_width(Size::lpx(50.0))
+                        .with_height(Size::lpx(50.0)),
+                );
+            ctx.input_mut().cursor_position = Some(Point::new(10.0, 10.0));
+            if let Some(button) = release {
+                ctx.input_mut().press_button(button);
+                ctx.input_mut().release_button(button);
+            }
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        frame(&mut ctx, Some(MouseButton::Left));
+        assert!(ctx.was_clicked("file"));
+        assert!(!ctx.was_context_menu_requested("file"));
+        assert_eq!(ctx.viewport_size(), (200.0, 100.0));
+
+        frame(&mut ctx, Some(MouseButton::Right));
+        assert!(ctx.was_context_menu_requested("file"));
+
+        // Events sent while building arrive with the next frame's events only
+        ctx.send_event(TargetedEvent {
+            event: InteractionEvent::ContextMenuAction {
+                action: "rename".to_string(),
+            },
+            target: NodeId::new("file"),
+            local_position: Point::zero(),
+            zoom: 1.0,
+        });
+        assert_eq!(ctx.context_menu_action("file"), None);
+        frame(&mut ctx, None);
+        assert_eq!(ctx.context_menu_action("file"), Some("rename"));
+        assert!(!ctx.was_context_menu_requested("file"));
+        frame(&mut ctx, None);
+        assert_eq!(ctx.context_menu_action("file"), None);
+    }
+
+    #[test]
+    fn test_focused_style_applies_while_focused() {
+        use crate::{Rect, Size, Style, Transition};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        let run_frame = |ctx: &mut UiContext| {
+            ctx.begin_frame();
+            let mut root = Node::new().with_child(
+                Node::new()
+                    .with_id(NodeId::new("field"))
+                    .with_width(Size::lpx(10.0))
+                    .with_style(Style::opacity(1.0))
+                    .with_focused_style(Style::opacity(0.5))
+                    .with_transition(Transition::instant()),
+            );
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            root.children()[0].opacity()
+        };
+
+        assert_eq!(run_frame(&mut ctx), 1.0);
+
+        ctx.set_focus(Some("field"));
+        assert_eq!(run_frame(&mut ctx), 0.5);
+
+        ctx.set_focus(None);
+        assert_eq!(run_frame(&mut ctx), 1.0);
+    }
+
+    #[test]
+    fn test_tab_moves_focus_in_layout_order_and_routes_keys() {
+        use crate::{NamedKey, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        let run_frame = |ctx: &mut UiContext, keys: Vec<Key>, shift: bool| {
+            ctx.begin_frame();
+            let field = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_height(Size::lpx(10.0))
+                    .with_focusable(true)
+            };
+            let mut root = Node::new().with_children(vec![
+                field("first"),
+                Node::new().with_children(vec![
+                    field("second"),
+                    field("disabled").with_disabled(true),
+                    Node::new()
+                        .with_id(NodeId::new("label"))
+                        .with_height(Size::lpx(10.0)),
+                ]),
+                field("third"),
+            ]);
+            root.compute_layout(window);
+            ctx.input_mut().keys_just_pressed = keys;
+            ctx.input_mut().shift_held = shift;
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        let tab = || vec![Key::Named(NamedKey::Tab)];
+
+        run_frame(&mut ctx, tab(), false);
+        assert!(ctx.is_focused("first"));
+        assert!(ctx.gained_focus("first"));
+
+        run_frame(&mut ctx, tab(), false);
+        assert!(ctx.is_focused("second"));
+        assert!(ctx.lost_focus("first"));
+
+        // The disabled field is skipped and the order wraps around
+        run_frame(&mut ctx, tab(), false);
+        assert!(ctx.is_focused("third"));
+        run_frame(&mut ctx, tab(), false);
+        assert!(ctx.is_focused("first"));
+        run_frame(&mut ctx, tab(), true);
+        assert!(ctx.is_focused("third"));
+
+        // Other keys go to the focused node
+        run_frame(&mut ctx, vec![Key::Character("a".into())], false);
+        let keys: Vec<_> = ctx.keys_pressed_on("third").cloned().collect();
+        assert_eq!(keys, vec![Key::Character("a".into())]);
+
+        // Programmatic focus sends the events too
+        ctx.focus("second");
+        run_frame(&mut ctx, vec![], false);
+        assert!(ctx.is_focused("second"));
+        assert!(ctx.gained_focus("second"));
+        assert!(ctx.lost_focus("third"));
+
+        // A non-focusable node holding focus keeps Tab for itself
+        ctx.set_focus(Some("label"));
+        run_frame(&mut ctx, tab(), false);
+        assert!(ctx.is_focused("label"));
+        assert_eq!(ctx.keys_pressed_on("label").count(), 1);
+
+        ctx.blur();
+        run_frame(&mut ctx, vec![], false);
+        assert!(ctx.focused_widget().is_none());
+        assert!(ctx.lost_focus("label"));
+    }
+
+    #[test]
+    fn test_focus_scope_traps_tab_keys_and_pointer_and_restores_focus() {
+        use crate::{NamedKey, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        let run_frame = |ctx: &mut UiContext, open: bool, keys: Vec<Key>, cursor: Option<Point>| {
+            ctx.begin_frame();
+            let field = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_height(Size::lpx(10.0))
+                    .with_focusable(true)
+            };
+            let mut children = vec![field("page")];
+            if open {
+                children.push(
+                    Node::new()
+                        .with_focus_scope(true)
+                        .with_children(vec![field("ok"), field("cancel")]),
+                );
+            }
+            let mut root = Node::new().with_children(children);
+            root.compute_layout(window);
+            ctx.input_mut().keys_just_pressed = keys;
+            ctx.input_mut().cursor_position = cursor;
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        let tab = || vec![Key::Named(NamedKey::Tab)];
+
+        run_frame(&mut ctx, false, tab(), None);
+        assert!(ctx.is_focused("page"));
+
+        // Opening the scope moves focus into it
+        run_frame(&mut ctx, true, vec![], None);
+        assert!(ctx.is_focused("ok"));
+        assert!(ctx.lost_focus("page"));
+
+        // Tab cycles inside the scope only
+        run_frame(&mut ctx, true, tab(), None);
+        assert!(ctx.is_focused("cancel"));
+        run_frame(&mut ctx, true, tab(), None);
+        assert!(ctx.is_focused("ok"));
+
+        // Nodes outside the scope are not hit and don't get keys
+        run_frame(&mut ctx, true, vec![], Some(Point::new(5.0, 5.0)));
+        assert!(!ctx.is_hovered("page"));
+        run_frame(&mut ctx, true, vec![], Some(Point::new(5.0, 15.0)));
+        assert!(ctx.is_hovered("ok"));
+        ctx.focus("page");
+        run_frame(&mut ctx, true, vec![Key::Character("a".into())], None);
+        assert_eq!(ctx.keys_pressed_on("page").count(), 0);
+        ctx.focus("ok");
+
+        // Closing the scope restores the focus it started with
+        run_frame(&mut ctx, false, vec![], None);
+        assert!(ctx.is_focused("page"));
+        assert!(ctx.gained_focus("page"));
+    }
+
+    #[test]
+    fn test_pinch_and_pan_reach_every_node_under_the_gesture() {
+        use crate::{Layout, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        let run_frame = |ctx: &mut UiContext| {
+            ctx.begin_frame();
+            let half = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_width(Size::lpx(50.0))
+                    .with_height(Size::Fill)
+            };
+            let mut root = Node::new()
+                .with_id(NodeId::new("view"))
+                .with_width(Size::Fill)
+                .with_height(Size::Fill)
+                .with_layout_direction(Layout::Horizontal)
+                .with_children(vec![half("left"), half("right")]);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+
+        // Two fingers over the left node spread apart and move down
+        ctx.input_mut().touch_start(0, Point::new(10.0, 10.0));
+        ctx.input_mut().touch_start(1, Point::new(30.0, 10.0));
+        ctx.input_mut().touch_move(1, Point::new(50.0, 30.0));
+        run_frame(&mut ctx);
+
+        for id in ["view", "left"] {
+            assert!(ctx.pinch_zoom(id).is_some_and(|zoom| zoom > 1.0));
+            assert_eq!(ctx.pan_delta(id), Some((10.0, 10.0)));
+        }
+        assert_eq!(ctx.pinch_zoom("right"), None);
+
+        // No gesture without movement
+        run_frame(&mut ctx);
+        assert_eq!(ctx.pinch_zoom("view"), None);
+        assert_eq!(ctx.pan_delta("view"), None);
+    }
+
+    #[test]
+    fn test_quick_clicks_count_as_double_and_triple_clicks() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        // Press and release on the button, returning (click, double, triple)
+        let click = |ctx: &mut UiContext, x: f32| {
+            for press in [true, false] {
+                ctx.begin_frame();
+                let mut root = Node::new().with_child(
+                    Node::new()
+                        .with_id(NodeId::new("item"))
+                        .with_width(Size::lpx(100.0))
+                        .with_height(Size::lpx(100.0)),
+                );
+                root.compute_layout(window);
+                ctx.input_mut().cursor_position = Some(Point::new(x, 10.0));
+                if press {
+                    ctx.input_mut().press_button(MouseButton::Left);
+                } else {
+                    ctx.input_mut().release_button(MouseButton::Left);
+                }
+                ctx.end_frame(&mut root);
+                ctx.input_mut().begin_frame();
+            }
+            (
+                ctx.was_clicked("item"),
+                ctx.was_double_clicked("item"),
+                ctx.was_triple_clicked("item"),
+            )
+        };
+
+        assert_eq!(click(&mut ctx, 10.0), (true, false, false));
+        assert_eq!(click(&mut ctx, 10.0), (true, true, false));
+        assert_eq!(click(&mut ctx, 11.0), (true, false, true));
+        // The fourth click starts a new run
+        assert_eq!(click(&mut ctx, 11.0), (true, false, false));
+
+        // Moving away between clicks breaks the run
+        assert_eq!(click(&mut ctx, 50.0), (true, false, false));
+
+        // So does waiting longer than the interval
+        ctx.set_multi_click_interval(std::time::Duration::ZERO);
+        assert_eq!(click(&mut ctx, 50.0), (true, false, false));
+    }
+
+    #[test]
+    fn test_long_press_fires_once_after_holding_still() {
+        use crate::{Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+        ctx.set_long_press_duration(std::time::Duration::from_millis(20));
+
+        // Run a frame with the cursor at `x`, optionally pressing or releasing
+        let frame = |ctx: &mut UiContext, x: f32, press: Option<bool>| {
+            ctx.begin_frame();
+            let mut root = Node::new().with_child(
+                Node::new()
+                    .with_id(NodeId::new("item"))
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0)),
+            );
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = Some(Point::new(x, 10.0));
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Left),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Left),
+                None => {}
+            }
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+            ctx.was_long_pressed("item")
+        };
+
+        assert!(!frame(&mut ctx, 10.0, Some(true)));
+        assert!(!frame(&mut ctx, 12.0, None));
+
+        // Held past the duration, within the allowed movement
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        assert!(frame(&mut ctx, 14.0, None));
+        assert!(!frame(&mut ctx, 14.0, None));
+        assert!(!frame(&mut ctx, 14.0, Some(false)));
+
+        // Moving away cancels the press
+        assert!(!frame(&mut ctx, 10.0, Some(true)));
+        assert!(!frame(&mut ctx, 40.0, None));
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        assert!(!frame(&mut ctx, 40.0, None));
+        assert!(!frame(&mut ctx, 40.0, Some(false)));
+
+        // So does releasing before the duration
+        assert!(!frame(&mut ctx, 10.0, Some(true)));
+        assert!(!frame(&mut ctx, 10.0, Some(false)));
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        assert!(!frame(&mut ctx, 10.0, None));
+    }
+
+    #[test]
+    fn test_pointer_capture_follows_the_pointer_out_of_the_window() {
+        use crate::{Layout, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [200.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        // Run a frame with the cursor at `x` (`None` outside the window)
+        let frame = |ctx: &mut UiContext, x: Option<f32>, press: Option<bool>| {
+            ctx.begin_frame();
+            let cell = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0))
+            };
+            let mut root = Node::new()
+                .with_layout_direction(Layout::Horizontal)
+                .with_children(vec![cell("thumb"), cell("other")]);
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = x.map(|x| Point::new(x, 50.0));
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Left),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Left),
+                None => {}
+            }
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        let events = |ctx: &UiContext, id: &str| -> Vec<&'static str> {
+            ctx.events_for(id)
+                .filter_map(|e| match e.event {
+                    InteractionEvent::DragStart { .. } => Some("drag start"),
+                    InteractionEvent::DragEnd { .. } => Some("drag end"),
+                    InteractionEvent::PointerMove { .. } => Some("move"),
+                    InteractionEvent::PointerUp { .. } => Some("up"),
+                    InteractionEvent::PointerCaptureLost => Some("lost"),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        frame(&mut ctx, Some(20.0), Some(true));
+        ctx.capture_pointer("thumb");
+        assert!(ctx.has_pointer_capture("thumb"));
+
+        // The pointer leaves the thumb before the drag starts, but the drag
+        // still starts on the thumb, local to it
+        frame(&mut ctx, Some(150.0), None);
+        assert_eq!(events(&ctx, "thumb"), ["move", "drag start"]);
+        assert!(events(&ctx, "other").is_empty());
+        let start = ctx
+            .events_for("thumb")
+            .find(|e| matches!(e.event, InteractionEvent::DragStart { .. }))
+            .unwrap();
+        assert_eq!(start.local_position, Point::new(150.0, 50.0));
+
+        // Released outside the window, the drag ends and capture is lost
+        frame(&mut ctx, None, None);
+        assert!(events(&ctx, "thumb").is_empty());
+        frame(&mut ctx, None, Some(false));
+        assert_eq!(events(&ctx, "thumb"), ["drag end", "up", "lost"]);
+        assert!(!ctx.has_pointer_capture("thumb"));
+        frame(&mut ctx, Some(150.0), None);
+        assert!(!ctx.is_dragging("thumb"));
+    }
+
+    #[test]
+    fn test_drag_payload_is_dropped_on_the_target_under_the_pointer() {
+        use crate::{Layout, Point, Rect, Size};
+
+        let window = Rect::from_min_size([0.0, 0.0], [300.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        let frame = |ctx: &mut UiContext, x: f32, press: Option<bool>| {
+            ctx.begin_frame();
+            let cell = |id: &str| {
+                Node::new()
+                    .with_id(NodeId::new(id))
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0))
+            };
+            let mut root = Node::new()
+                .with_layout_direction(Layout::Horizontal)
+                .with_children(vec![
+                    cell("card"),
+                    cell("bin").with_drop_target(true),
+                    cell("shelf"),
+                ]);
+            root.compute_layout(window);
+            ctx.input_mut().cursor_position = Some(Point::new(x, 50.0));
+            match press {
+                Some(true) => ctx.input_mut().press_button(MouseButton::Left),
+                Some(false) => ctx.input_mut().release_button(MouseButton::Left),
+                None => {}
+            }
+            ctx.end_frame(&mut root);
+            ctx.input_mut().begin_frame();
+        };
+        // The drag-and-drop events a node received in the last frame
+        let dnd = |ctx: &UiContext, id: &str| -> Vec<&str> {
+            ctx.events_for(id)
+                .filter_map(|e| match e.event {
+                    InteractionEvent::DragEnter { .. } => Some("enter"),
+                    InteractionEvent::DragOver { .. } => Some("over"),
+                    InteractionEvent::DragLeave => Some("leave"),
+                    InteractionEvent::Drop { .. } => Some("drop"),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Without a payload, drop targets see nothing
+        frame(&mut ctx, 50.0, Some(true));
+        frame(&mut ctx, 55.0, None);
+        assert!(ctx.is_dragging("card"));
+        frame(&mut ctx, 150.0, None);
+        assert!(dnd(&ctx, "bin").is_empty());
+        ctx.set_drag_payload(7_u32);
+        assert_eq!(ctx.drag_payload::<u32>(), Some(&7));
+        assert_eq!(ctx.drag_payload::<String>(), None);
+
+        frame(&mut ctx, 160.0, None);
+        assert!(ctx.is_drag_over("bin"));
+        assert_eq!(dnd(&ctx, "bin"), ["enter", "over"]);
+
+        // Only drop targets take part
+        frame(&mut ctx, 250.0, None);
+        assert_eq!(dnd(&ctx, "bin"), ["leave"]);
+        assert!(dnd(&ctx, "shelf").is_empty());
+
+        frame(&mut ctx, 150.0, None);
+        frame(&mut ctx, 150.0, Some(false));
+        assert_eq!(dnd(&ctx, "bin"), ["drop"]);
+        assert!(!ctx.is_drag_over("bin"));
+        assert_eq!(ctx.take_drop::<String>("bin"), None);
+        assert_eq!(ctx.take_drop::<u32>("shelf"), None);
+        assert_eq!(ctx.take_drop::<u32>("bin"), Some(7));
+        assert_eq!(ctx.take_drop::<u32>("bin"), None);
+
+        // A payload released away from any drop target is discarded
+        frame(&mut ctx, 50.0, Some(true));
+        frame(&mut ctx, 55.0, None);
+        ctx.set_drag_payload(8_u32);
+        frame(&mut ctx, 250.0, Some(false));
+        assert!(ctx.drag_payload::<u32>().is_none());
+        assert!(ctx.take_drop_boxed("shelf").is_none());
+    }
+
+    #[test]
+    fn test_selected_style_layers_under_focus() {
+        use crate::{Rect, Size, Style, Transition};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+
+        let run_frame = |ctx: &mut UiContext, selected: bool| {
+            ctx.begin_frame();
+            let mut root = Node::new().with_child(
+                Node::new()
+                    .with_id(NodeId::new("tab"))
+                    .with_width(Size::lpx(10.0))
+                    .with_selected(selected)
+                    .with_style(Style {
+                        opacity: Some(1.0),
+                        rotation: Some(0.0),
+                        ..Default::default()
+                    })
+                    .with_selected_style(Style {
+                        opacity: Some(0.5),
+                        rotation: Some(1.0),
+                        ..Default::default()
+                    })
+                    .with_focused_style(Style::opacity(0.25))
+                    .with_transition(Transition::instant()),
+            );
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            let tab = &root.children()[0];
+            (tab.opacity(), tab.rotation())
+        };
+
+        assert_eq!(run_frame(&mut ctx, false), (1.0, 0.0));
+        assert_eq!(run_frame(&mut ctx, true), (0.5, 1.0));
+
+        ctx.set_focus(Some("tab"));
+        assert_eq!(run_frame(&mut ctx, true), (0.25, 1.0));
+
+        ctx.set_focus(None);
+        assert_eq!(run_frame(&mut ctx, false), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_token_override_animates_dependent_nodes() {
+        use crate::transition::linear;
+        use crate::{var, Rect, Size, StyleProperty, TokenTable, Transition};
+
+        let window = Rect::from_min_size([0.0, 0.0], [100.0, 100.0]);
+        let mut ctx = UiContext::new();
+        ctx.set_tokens(TokenTable::new().with("fade", 1.0));
+
+        let run_frame = |ctx: &mut UiContext| {
+            ctx.begin_frame();
+            let mut root = Node::new()
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("slow"))
+                        .with_width(Size::lpx(10.0))
+                        .with_var(StyleProperty::Opacity, var("fade"))
+                        .with_transition(Transition::new(10.0, linear)),
+                )
+                .with_child(
+                    Node::new()
+                        .with_id(NodeId::new("instant"))
+                        .with_width(Size::lpx(10.0))
+                        .with_var(StyleProperty::Opacity, var("fade"))
+                        .with_transition(Transition::instant()),
+                );
+            ctx.inject_dimension_overrides(&mut root);
+            root.compute_layout(window);
+            ctx.end_frame(&mut root);
+            root
+        };
+
+        let root = run_frame(&mut ctx);
+        assert_eq!(root.children()[0].opacity(), 1.0);
+
+        ctx.set_token("fade", 0.0);
+        let root = run_frame(&mut ctx);
+        assert!(root.children()[0].opacity() > 0.5);
+        assert_eq!(root.children()[1].opacity(), 0.0);
+        assert!(ctx.has_active_transitions());
+    }
+
+    #[test]
+    fn test_style_animations_follow_frame_clock() {
+        use crate::{AnimatedProperty, Animation, Rect, Size, Style};
+
+        let mut ctx = UiContext::new();
+        let animation = Animation::new(AnimatedProperty::Opacity(0.0, 1.0), 1000.0);
+        let run_frame = |ctx: &mut UiContext, animated: bool| {
+            ctx.begin_frame();
+            let style = if animated {
+                Style::animated(animation)
+            } else {
+                Style::opacity(1.0)
+            };
+            let mut root = Node::new()
+                .with_width(Size::lpx(10.0))
+                .with_height(Size::lpx(10.0))
+                .with_style(style);
+            root.compute_layout(Rect::new([0.0, 0.0], [100.0, 100.0]));
+            ctx.end_frame(&mut root);
+            root
+        };
+
+        run_frame(&mut ctx, true);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let root = run_frame(&mut ctx, true);
+        assert!(ctx.time() > 0.0);
+        assert!((root.opacity() - animation.progress(ctx.time())).abs() < 1e-5);
+        assert!(ctx.has_active_transitions());
+
+        run_frame(&mut ctx, false);
+        assert!(!ctx.has_active_transitions());
+    }
+
+    #[test]
+    fn test_frame_times_are_bounded() {
+        let mut ctx = UiContext::new();
+        ctx.set_frame_history_len(3);
+        let mut root = Node::new();
+        for _ in 0..6 {
+            ctx.end_frame(&mut root);
+        }
+
+        assert_eq!(ctx.frame_times().len(), 3);
+        assert!(ctx.last_frame_time_ms().is_some());
+
+        ctx.set_frame_history_len(1);
+        assert_eq!(ctx.frame_times().len(), 1);
+    }
+
+    #[test]
+    fn test_highlight_caches_spans_per_code() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let calls = Rc::new(Cell::new(0));
+        let counted = calls.clone();
+        let mut ctx = UiContext::new();
+        ctx.set_highlighter(move |code: &str, _language: &str| {
+            counted.set(counted.get() + 1);
+            vec![crate::TextSpan::new(code).bold()]
+        });
+
+        let first = ctx.highlight(crate::CodeContent::new("let x = 1;", "rust"));
+        let second = ctx.highlight(crate::CodeContent::new("let x = 1;", "rust"));
+        ctx.highlight(crate::CodeContent::new("let x = 1;", "python"));
+
+        assert_eq!(first.text.spans.len(), 1);
+        assert_eq!(second.text.spans, first.text.spans);
+        assert_eq!(calls.get(), 2);
     }
 }
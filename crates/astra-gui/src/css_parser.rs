@@ -0,0 +1,653 @@
+//! CSS-like stylesheet format (enabled with the `css` feature)
+//!
+//! Parses a small subset of CSS into `StyleSheet`/`StyleRule` values, so styles
+//! can live in a text file and be reloaded at runtime:
+//!
+//! ```css
+//! /* Cards */
+//! .card {
+//!     background: #313244;
+//!     color: rgb(205, 214, 244);
+//!     border: 1px solid #585b70;
+//!     border-radius: 8px;
+//!     padding: 8px 12px;
+//!     transition: 150ms ease-out;
+//! }
+//! .card:hover, .card:active { background: #45475a; }
+//! .danger:disabled { opacity: 0.5; }
+//! ```
+//!
+//! Selectors are class names with an optional `:hover`, `:active`, `:focus`,
+//! `:checked` (alias `:selected`) or `:disabled` state. Supported properties are `background`/`background-color`, `color`,
+//! `caret-color`, `border`, `border-width`, `border-color`, `border-radius`,
+//! `outline`, `outline-width`, `outline-color`, `outline-offset`, `opacity`,
+//! `padding` and `transition`. Lengths are logical pixels (`px`)
+//! or percentages; colors are hex, `rgb()`/`rgba()`, `hsl()`/`hsla()`,
+//! `oklch()`, `transparent` or one of the basic CSS color names.
+
+use crate::color::{css, Color};
+use crate::layout::{Size, Spacing};
+use crate::primitives::{CornerShape, Outline, Stroke};
+use crate::style::Style;
+use crate::stylesheet::{StyleRule, StyleSheet};
+use crate::transition::{self, EasingFn, Transition};
+use std::fmt;
+
+/// Error produced when parsing the CSS-like format
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssError {
+    /// 1-based line the error was found on
+    pub line: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for CssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CssError {}
+
+impl StyleSheet {
+    /// Parse a stylesheet from the CSS-like format
+    pub fn from_css(source: &str) -> Result<StyleSheet, CssError> {
+        let source = strip_comments(source);
+        let mut sheet = StyleSheet::new();
+        let mut rest = source.as_str();
+        let mut offset = 0;
+
+        while let Some(open) = rest.find('{') {
+            let selectors = &rest[..open];
+            let line = line_at(&source, offset + open);
+            let close = rest[open..]
+                .find('}')
+                .map(|close| open + close)
+                .ok_or_else(|| error(line, "missing '}'"))?;
+            let body = &rest[open + 1..close];
+            if body.contains('{') {
+                return Err(error(line, "nested blocks are not supported"));
+            }
+
+            let declarations = parse_declarations(body, line_at(&source, offset + open + 1))?;
+            for selector in selectors.split(',') {
+                let (class, state) = parse_selector(selector.trim(), line)?;
+                let rule = declarations.clone().rule_for(state, line)?;
+                let merged = match sheet.rule(class) {
+                    Some(existing) => existing.merge(&rule),
+                    None => rule,
+                };
+                sheet.set_rule(class, merged);
+            }
+
+            offset += close + 1;
+            rest = &rest[close + 1..];
+        }
+
+        if !rest.trim().is_empty() {
+            return Err(error(
+                line_at(&source, offset),
+                "expected '{' after selector",
+            ));
+        }
+
+        Ok(sheet)
+    }
+}
+
+impl StyleRule {
+    /// Parse a rule from a CSS declaration block without braces or selector
+    ///
+    /// e.g. `"background: #1e1e2e; padding: 4px 8px; transition: 150ms"`
+    pub fn from_css(declarations: &str) -> Result<StyleRule, CssError> {
+        let declarations = strip_comments(declarations);
+        parse_declarations(&declarations, 1)?.rule_for(State::Idle, 1)
+    }
+}
+
+impl Style {
+    /// Parse a style from a CSS declaration block without braces or selector
+    ///
+    /// Layout properties (`padding`) and `transition` are not part of `Style`
+    /// and are rejected; use `StyleRule::from_css` for those.
+    pub fn from_css(declarations: &str) -> Result<Style, CssError> {
+        let declarations = strip_comments(declarations);
+        let parsed = parse_declarations(&declarations, 1)?;
+        if parsed.padding.is_some() || parsed.transition.is_some() {
+            return Err(error(1, "padding and transition are not style properties"));
+        }
+        Ok(parsed.style)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    Hover,
+    Active,
+    Focus,
+    Selected,
+    Disabled,
+}
+
+#[derive(Clone, Default)]
+struct Declarations {
+    style: Style,
+    border_width: Option<Size>,
+    border_color: Option<Color>,
+    outline_width: Option<Size>,
+    outline_color: Option<Color>,
+    outline_offset: Option<Size>,
+    padding: Option<Spacing>,
+    transition: Option<Transition>,
+}
+
+impl Declarations {
+    fn rule_for(mut self, state: State, line: usize) -> Result<StyleRule, CssError> {
+        if self.border_width.is_some() || self.border_color.is_some() {
+            self.style.stroke = Some(Stroke::new(
+                self.border_width.unwrap_or(Size::lpx(1.0)),
+                self.border_color.unwrap_or(css::BLACK),
+            ));
+        }
+
+        if self.outline_width.is_some()
+            || self.outline_color.is_some()
+            || self.outline_offset.is_some()
+        {
+            self.style.outline = Some(
+                Outline::new(
+                    self.outline_width.unwrap_or(Size::lpx(1.0)),
+                    self.outline_color.unwrap_or(css::BLACK),
+                )
+                .with_offset(self.outline_offset.unwrap_or(Size::lpx(0.0))),
+            );
+        }
+
+        if state != State::Idle && (self.padding.is_some() || self.transition.is_some()) {
+            return Err(error(
+                line,
+                "padding and transition can only be set on the plain class selector",
+            ));
+        }
+
+        let mut rule = StyleRule::new();
+        rule.padding = self.padding;
+        rule.transition = self.transition;
+        match state {
+            State::Idle => rule.style = Some(self.style),
+            State::Hover => rule.hover_style = Some(self.style),
+            State::Active => rule.active_style = Some(self.style),
+            State::Focus => rule.focused_style = Some(self.style),
+            State::Selected => rule.selected_style = Some(self.style),
+            State::Disabled => rule.disabled_style = Some(self.style),
+        }
+        Ok(rule)
+    }
+}
+
+fn error(line: usize, message: impl Into<String>) -> CssError {
+    CssError {
+        line,
+        message: message.into(),
+    }
+}
+
+fn line_at(source: &str, byte: usize) -> usize {
+    source[..byte.min(source.len())].matches('\n').count() + 1
+}
+
+/// Replace `/* */` comments with spaces, keeping newlines so line numbers stay valid
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        let end = rest[start + 2..]
+            .find("*/")
+            .map(|end| start + 2 + end + 2)
+            .unwrap_or(rest.len());
+        out.extend(
+            rest[start..end]
+                .chars()
+                .map(|c| if c == '\n' { '\n' } else { ' ' }),
+        );
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_selector(selector: &str, line: usize) -> Result<(&str, State), CssError> {
+    let class = selector
+        .strip_prefix('.')
+        .ok_or_else(|| error(line, format!("expected a class selector, got '{selector}'")))?;
+    let (class, state) = match class.split_once(':') {
+        Some((class, "hover")) => (class, State::Hover),
+        Some((class, "active")) => (class, State::Active),
+        Some((class, "focus")) => (class, State::Focus),
+        Some((class, "checked" | "selected")) => (class, State::Selected),
+        Some((class, "disabled")) => (class, State::Disabled),
+        Some((_, state)) => return Err(error(line, format!("unknown state ':{state}'"))),
+        None => (class, State::Idle),
+    };
+
+    let valid = !class.is_empty()
+        && class
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(error(line, format!("invalid class name '{class}'")));
+    }
+    Ok((class, state))
+}
+
+fn parse_declarations(body: &str, first_line: usize) -> Result<Declarations, CssError> {
+    let mut parsed = Declarations::default();
+    let mut line = first_line;
+
+    for declaration in body.split(';') {
+        let decl_line = first_line_of(declaration, line);
+        line += declaration.matches('\n').count();
+
+        let declaration = declaration.trim();
+        if declaration.is_empty() {
+            continue;
+        }
+        let (name, value) = declaration.split_once(':').ok_or_else(|| {
+            error(
+                decl_line,
+                format!("expected 'property: value', got '{declaration}'"),
+            )
+        })?;
+        let name = name.trim();
+        let value = value.trim();
+        let err = |message: String| error(decl_line, message);
+
+        match name {
+            "background" | "background-color" => {
+                parsed.style.fill_color = Some(parse_color(value).map_err(err)?)
+            }
+            "color" => parsed.style.text_color = Some(parse_color(value).map_err(err)?),
+            "caret-color" => parsed.style.cursor_color = Some(parse_color(value).map_err(err)?),
+            "opacity" => {
+                parsed.style.opacity = Some(parse_number(value).map_err(err)?.clamp(0.0, 1.0))
+            }
+            "border-radius" => {
+                parsed.style.corner_shape =
+                    Some(CornerShape::Round(parse_length(value).map_err(err)?))
+            }
+            "border-width" => parsed.border_width = Some(parse_length(value).map_err(err)?),
+            "border-color" => parsed.border_color = Some(parse_color(value).map_err(err)?),
+            "border" => {
+                if value == "none" {
+                    parsed.border_width = Some(Size::lpx(0.0));
+                    continue;
+                }
+                for part in split_values(value) {
+                    if part == "solid" {
+                        continue;
+                    }
+                    if let Ok(width) = parse_length(part) {
+                        parsed.border_width = Some(width);
+                    } else {
+                        parsed.border_color = Some(parse_color(part).map_err(err)?);
+                    }
+                }
+            }
+            "outline-width" => parsed.outline_width = Some(parse_length(value).map_err(err)?),
+            "outline-color" => parsed.outline_color = Some(parse_color(value).map_err(err)?),
+            "outline-offset" => parsed.outline_offset = Some(parse_length(value).map_err(err)?),
+            "outline" => {
+                if value == "none" {
+                    parsed.outline_width = Some(Size::lpx(0.0));
+                    continue;
+                }
+                for part in split_values(value) {
+                    if part == "solid" {
+                        continue;
+                    }
+                    if let Ok(width) = parse_length(part) {
+                        parsed.outline_width = Some(width);
+                    } else {
+                        parsed.outline_color = Some(parse_color(part).map_err(err)?);
+                    }
+                }
+            }
+            "padding" => parsed.padding = Some(parse_padding(value).map_err(err)?),
+            "transition" => parsed.transition = Some(parse_transition(value).map_err(err)?),
+            _ => return Err(err(format!("unknown property '{name}'"))),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Line of the first non-whitespace character of `text`, which starts on `line`
+fn first_line_of(text: &str, line: usize) -> usize {
+    let leading = text.len() - text.trim_start().len();
+    line + text[..leading].matches('\n').count()
+}
+
+/// Split a value on whitespace, keeping function arguments like `rgb(1, 2, 3)` together
+fn split_values(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(s) = start.take() {
+                    parts.push(&value[s..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        parts.push(&value[s..]);
+    }
+    parts
+}
+
+fn parse_number(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .map_err(|_| format!("expected a number, got '{value}'"))
+}
+
+/// Parse a number or percentage as a fraction (`40%` and `0.4` are both 0.4)
+fn parse_fraction(value: &str) -> Result<f32, String> {
+    match value.strip_suffix('%') {
+        Some(percent) => Ok(parse_number(percent)? / 100.0),
+        None => parse_number(value),
+    }
+}
+
+/// Split the arguments of `hsl()`/`oklch()`, which may be separated by commas
+/// (`hsl(200, 50%, 40%, 0.5)`) or spaces with a slash before alpha
+/// (`oklch(0.7 0.1 200 / 50%)`), into the channels and the alpha
+fn color_function_args(args: &str) -> Result<(Vec<&str>, f32), String> {
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let mut channels: Vec<&str> = channels
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let alpha = match alpha {
+        Some(alpha) => Some(alpha),
+        None if channels.len() == 4 => channels.pop(),
+        None => None,
+    };
+    let alpha = match alpha {
+        Some(alpha) => parse_fraction(alpha)?.clamp(0.0, 1.0),
+        None => 1.0,
+    };
+    Ok((channels, alpha))
+}
+
+fn parse_length(value: &str) -> Result<Size, String> {
+    if let Some(px) = value.strip_suffix("px") {
+        Ok(Size::lpx(parse_number(px)?))
+    } else if let Some(percent) = value.strip_suffix('%') {
+        Ok(Size::rel(parse_number(percent)? / 100.0))
+    } else if value == "0" {
+        Ok(Size::lpx(0.0))
+    } else {
+        Err(format!("expected a length in px or %, got '{value}'"))
+    }
+}
+
+fn parse_padding(value: &str) -> Result<Spacing, String> {
+    let lengths = split_values(value)
+        .into_iter()
+        .map(parse_length)
+        .collect::<Result<Vec<_>, _>>()?;
+    let (top, right, bottom, left) = match lengths[..] {
+        [all] => (all, all, all, all),
+        [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+        [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+        [top, right, bottom, left] => (top, right, bottom, left),
+        _ => return Err(format!("expected 1 to 4 lengths, got '{value}'")),
+    };
+    Ok(Spacing {
+        top,
+        right,
+        bottom,
+        left,
+    })
+}
+
+fn parse_duration(value: &str) -> Result<f32, String> {
+    if let Some(ms) = value.strip_suffix("ms") {
+        Ok(parse_number(ms)? / 1000.0)
+    } else if let Some(s) = value.strip_suffix('s') {
+        parse_number(s)
+    } else {
+        Err(format!("expected a duration in s or ms, got '{value}'"))
+    }
+}
+
+fn parse_easing(value: &str) -> Result<EasingFn, String> {
+    Ok(match value {
+        "linear" => transition::linear,
+        "ease-in" => transition::ease_in,
+        "ease-out" => transition::ease_out,
+        "ease" | "ease-in-out" => transition::ease_in_out,
+        "ease-in-cubic" => transition::ease_in_cubic,
+        "ease-out-cubic" => transition::ease_out_cubic,
+        "ease-in-out-cubic" => transition::ease_in_out_cubic,
+        _ => return Err(format!("unknown easing '{value}'")),
+    })
+}
+
+fn parse_transition(value: &str) -> Result<Transition, String> {
+    match split_values(value)[..] {
+        ["none"] => Ok(Transition::instant()),
+        [duration] => Ok(Transition::new(
+            parse_duration(duration)?,
+            transition::ease_out,
+        )),
+        [duration, easing] => Ok(Transition::new(
+            parse_duration(duration)?,
+            parse_easing(easing)?,
+        )),
+        _ => Err(format!("expected '<duration> [easing]', got '{value}'")),
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| format!("invalid hex color '{value}'"));
+    }
+
+    if let Some(args) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        let channel = |part: &str| -> Result<u8, String> {
+            part.parse::<u8>()
+                .map_err(|_| format!("expected a color channel 0-255, got '{part}'"))
+        };
+        return match parts[..] {
+            [r, g, b] => Ok(Color::srgba(channel(r)?, channel(g)?, channel(b)?, 255)),
+            [r, g, b, a] => Ok(Color::srgba(channel(r)?, channel(g)?, channel(b)?, 255)
+                .with_alpha(parse_number(a)?.clamp(0.0, 1.0))),
+            _ => Err(format!("expected 3 or 4 color components, got '{value}'")),
+        };
+    }
+
+    if let Some(args) = value
+        .strip_prefix("hsla(")
+        .or_else(|| value.strip_prefix("hsl("))
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let (channels, alpha) = color_function_args(args)?;
+        return match channels[..] {
+            [h, s, l] => Ok(Color::hsla(
+                parse_number(h.trim_end_matches("deg"))?,
+                parse_fraction(s)?,
+                parse_fraction(l)?,
+                alpha,
+            )),
+            _ => Err(format!(
+                "expected hue, saturation and lightness, got '{value}'"
+            )),
+        };
+    }
+
+    if let Some(args) = value
+        .strip_prefix("oklch(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let (channels, alpha) = color_function_args(args)?;
+        return match channels[..] {
+            [l, c, h] => Ok(Color::oklcha(
+                parse_fraction(l)?,
+                parse_number(c)?,
+                parse_number(h.trim_end_matches("deg"))?,
+                alpha,
+            )),
+            _ => Err(format!("expected lightness, chroma and hue, got '{value}'")),
+        };
+    }
+
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "transparent" => Color::transparent(),
+        "aqua" => css::AQUA,
+        "black" => css::BLACK,
+        "blue" => css::BLUE,
+        "fuchsia" => css::FUCHSIA,
+        "gray" | "grey" => css::GRAY,
+        "green" => css::GREEN,
+        "lime" => css::LIME,
+        "maroon" => css::MAROON,
+        "navy" => css::NAVY,
+        "olive" => css::OLIVE,
+        "purple" => css::PURPLE,
+        "red" => css::RED,
+        "silver" => css::SILVER,
+        "teal" => css::TEAL,
+        "white" => css::WHITE,
+        "yellow" => css::YELLOW,
+        _ => return Err(format!("unknown color '{value}'")),
+    })
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let short = |i: usize| digit(i).map(|d| d * 17);
+
+    let (r, g, b, a) = match hex.len() {
+        3 => (short(0)?, short(1)?, short(2)?, 255),
+        4 => (short(0)?, short(1)?, short(2)?, short(3)?),
+        6 => (byte(0)?, byte(2)?, byte(4)?, 255),
+        8 => (byte(0)?, byte(2)?, byte(4)?, byte(6)?),
+        _ => return None,
+    };
+    Some(Color::srgba(r, g, b, a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catppuccin::mocha;
+
+    #[test]
+    fn test_parse_stylesheet() {
+        let sheet = StyleSheet::from_css(
+            "/* cards */
+            .card {
+                background: #1e1e2e;
+                border: 2px solid rgba(88, 91, 112, 0.5);
+                border-radius: 8px;
+                padding: 4px 8px;
+                transition: 150ms linear;
+            }
+            .card:hover, .button:active { background: #313244; }
+            .card:focus { border-color: #b4befe; outline: 2px solid #b4befe; outline-offset: 3px; }
+            .card:checked { opacity: 0.8; }",
+        )
+        .unwrap();
+
+        let card = sheet.rule("card").unwrap();
+        let style = card.style().unwrap();
+        assert_eq!(style.fill_color, Some(mocha::BASE));
+        let stroke = style.stroke.unwrap();
+        assert_eq!(stroke.width, Size::lpx(2.0));
+        assert_eq!(stroke.color.a, 0.5);
+        assert_eq!(style.corner_shape.map(|c| c.extent()), Some(Size::lpx(8.0)));
+        assert_eq!(
+            card.padding(),
+            Some(Spacing::symmetric(Size::lpx(8.0), Size::lpx(4.0)))
+        );
+        assert_eq!(card.transition().unwrap().duration, 0.15);
+        assert_eq!(
+            card.hover_style().unwrap().fill_color,
+            Some(mocha::SURFACE0)
+        );
+        assert_eq!(
+            card.focused_style().unwrap().stroke.map(|s| s.color),
+            Some(mocha::LAVENDER)
+        );
+        let outline = card.focused_style().unwrap().outline.unwrap();
+        assert_eq!(outline.width, Size::lpx(2.0));
+        assert_eq!(outline.offset, Size::lpx(3.0));
+        assert_eq!(outline.color, mocha::LAVENDER);
+        assert_eq!(card.selected_style().unwrap().opacity, Some(0.8));
+        assert_eq!(
+            sheet
+                .rule("button")
+                .unwrap()
+                .active_style()
+                .unwrap()
+                .fill_color,
+            Some(mocha::SURFACE0)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err =
+            StyleSheet::from_css(".card {\n  background: #1e1e2e;\n  shadow: 4px;\n}").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(err.message.contains("shadow"));
+
+        assert!(StyleSheet::from_css("card { opacity: 1 }").is_err());
+        assert!(StyleSheet::from_css(".card:hover { padding: 4px }").is_err());
+        assert!(Style::from_css("color: #12345").is_err());
+    }
+
+    #[test]
+    fn test_parse_inline_style() {
+        let style = Style::from_css("color: white; opacity: 0.25").unwrap();
+        assert_eq!(style.text_color, Some(css::WHITE));
+        assert_eq!(style.opacity, Some(0.25));
+    }
+
+    #[test]
+    fn test_parse_hsl_and_oklch_colors() {
+        let style =
+            Style::from_css("color: hsl(120deg, 100%, 50%); background: oklch(0.7 0.1 200 / 50%)")
+                .unwrap();
+        assert_eq!(style.text_color, Some(Color::hsl(120.0, 1.0, 0.5)));
+        assert_eq!(style.fill_color, Some(Color::oklcha(0.7, 0.1, 200.0, 0.5)));
+        assert_eq!(
+            Style::from_css("color: hsla(0, 100%, 50%, 0.25)")
+                .unwrap()
+                .text_color,
+            Some(Color::hsla(0.0, 1.0, 0.5, 0.25))
+        );
+        assert!(Style::from_css("color: oklch(0.7 0.1)").is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! Mouse cursor icon hints
+//!
+//! Nodes request a cursor icon with `Node::with_cursor`. After dispatching
+//! events, `UiContext::cursor_icon` reports the icon of the topmost hovered node
+//! that requested one. While a node is being dragged its icon is kept even if the
+//! pointer leaves it, so resize handles keep their resize cursor. Backends map
+//! the hint to the platform cursor (see `astra_gui_wgpu::to_winit_cursor`).
+
+/// Backend-agnostic mouse cursor icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum CursorIcon {
+    /// The platform's default arrow
+    #[default]
+    Default,
+    /// A pointing hand, for links and buttons
+    Pointer,
+    /// A text selection caret
+    Text,
+    /// Something can be grabbed
+    Grab,
+    /// Something is being grabbed
+    Grabbing,
+    /// Something can be moved in any direction
+    Move,
+    /// The action is not allowed
+    NotAllowed,
+    /// Precise selection
+    Crosshair,
+    /// Resize horizontally (left/right edges)
+    EwResize,
+    /// Resize vertically (top/bottom edges)
+    NsResize,
+    /// Resize along the top-right/bottom-left diagonal
+    NeswResize,
+    /// Resize along the top-left/bottom-right diagonal
+    NwseResize,
+}
@@ -17,6 +17,10 @@ pub struct DebugOptions {
     pub show_transform_origins: bool,
     /// Show text line bounds (cyan outline for each line)
     pub show_text_bounds: bool,
+    /// Show the accumulated interaction heatmap (see `InteractionHeatmap`)
+    pub show_heatmap: bool,
+    /// Replace normal rendering with a per-pixel draw count (additive tint)
+    pub show_overdraw: bool,
 }
 
 impl DebugOptions {
@@ -31,10 +35,14 @@ impl DebugOptions {
             show_gaps: false,
             show_transform_origins: false,
             show_text_bounds: false,
+            show_heatmap: false,
+            show_overdraw: false,
         }
     }
 
     /// Create debug options with all visualizations enabled
+    ///
+    /// The overdraw view is left off since it replaces normal rendering.
     pub const fn all() -> Self {
         Self {
             show_margins: true,
@@ -45,6 +53,8 @@ impl DebugOptions {
             show_gaps: true,
             show_transform_origins: true,
             show_text_bounds: true,
+            show_heatmap: true,
+            show_overdraw: false,
         }
     }
 
@@ -90,6 +100,18 @@ impl DebugOptions {
         self
     }
 
+    /// Enable interaction heatmap visualization
+    pub const fn with_heatmap(mut self, enabled: bool) -> Self {
+        self.show_heatmap = enabled;
+        self
+    }
+
+    /// Enable overdraw visualization
+    pub const fn with_overdraw(mut self, enabled: bool) -> Self {
+        self.show_overdraw = enabled;
+        self
+    }
+
     /// Check if any debug visualization is enabled
     pub const fn is_enabled(&self) -> bool {
         self.show_margins
@@ -100,5 +122,7 @@ impl DebugOptions {
             || self.show_gaps
             || self.show_transform_origins
             || self.show_text_bounds
+            || self.show_heatmap
+            || self.show_overdraw
     }
 }
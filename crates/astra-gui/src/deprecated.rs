@@ -0,0 +1,109 @@
+//! Deprecated crate-root paths
+//!
+//! Easing and interpolation helpers used to be re-exported at the crate root.
+//! They now live only in [`transition`](crate::transition); these forwarding
+//! functions keep old `astra_gui::lerp_color`-style paths compiling, with a
+//! deprecation warning, until the next breaking release.
+
+use crate::color::Color;
+use crate::layout::Size;
+use crate::primitives::{CornerKind, CornerShape, Outline, Stroke, StrokeAlignment};
+use crate::style::Style;
+use crate::transition;
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::linear`")]
+pub fn linear(t: f32) -> f32 {
+    transition::linear(t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::ease_in`")]
+pub fn ease_in(t: f32) -> f32 {
+    transition::ease_in(t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::ease_out`")]
+pub fn ease_out(t: f32) -> f32 {
+    transition::ease_out(t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::ease_in_out`")]
+pub fn ease_in_out(t: f32) -> f32 {
+    transition::ease_in_out(t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::ease_in_cubic`")]
+pub fn ease_in_cubic(t: f32) -> f32 {
+    transition::ease_in_cubic(t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::ease_out_cubic`")]
+pub fn ease_out_cubic(t: f32) -> f32 {
+    transition::ease_out_cubic(t)
+}
+
+#[deprecated(
+    since = "0.8.0",
+    note = "use `astra_gui::transition::ease_in_out_cubic`"
+)]
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    transition::ease_in_out_cubic(t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::lerp_f32`")]
+pub fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
+    transition::lerp_f32(a, b, t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::lerp_color`")]
+pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    transition::lerp_color(a, b, t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::lerp_size`")]
+pub fn lerp_size(a: Size, b: Size, t: f32) -> Size {
+    transition::lerp_size(a, b, t)
+}
+
+#[deprecated(
+    since = "0.8.0",
+    note = "use `astra_gui::transition::lerp_stroke_alignment`"
+)]
+pub fn lerp_stroke_alignment(
+    a: StrokeAlignment,
+    b: StrokeAlignment,
+    stroke_width: f32,
+    t: f32,
+) -> StrokeAlignment {
+    transition::lerp_stroke_alignment(a, b, stroke_width, t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::lerp_stroke`")]
+pub fn lerp_stroke(a: Stroke, b: Stroke, t: f32) -> Stroke {
+    transition::lerp_stroke(a, b, t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::lerp_outline`")]
+pub fn lerp_outline(a: Outline, b: Outline, t: f32) -> Outline {
+    transition::lerp_outline(a, b, t)
+}
+
+#[deprecated(
+    since = "0.8.0",
+    note = "use `astra_gui::transition::lerp_corner_shape`"
+)]
+pub fn lerp_corner_shape(a: CornerShape, b: CornerShape, t: f32) -> CornerShape {
+    transition::lerp_corner_shape(a, b, t)
+}
+
+#[deprecated(
+    since = "0.8.0",
+    note = "use `astra_gui::transition::lerp_corner_kind`"
+)]
+pub fn lerp_corner_kind(a: CornerKind, b: CornerKind, t: f32) -> CornerKind {
+    transition::lerp_corner_kind(a, b, t)
+}
+
+#[deprecated(since = "0.8.0", note = "use `astra_gui::transition::lerp_style`")]
+pub fn lerp_style(from: &Style, to: &Style, t: f32) -> Style {
+    transition::lerp_style(from, to, t)
+}
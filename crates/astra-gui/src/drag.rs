@@ -0,0 +1,115 @@
+//! Per-node drag configuration
+//!
+//! Nodes tune how the `EventDispatcher` drags them with `Node::with_drag`.
+//! A threshold keeps small movements of a press from starting a drag, so a
+//! slightly shaky click still clicks. An axis lock keeps only the horizontal
+//! or vertical part of the movement, and grid snapping moves the drag in
+//! fixed steps from where the button was pressed. The positions and deltas of
+//! `DragStart`, `DragMove` and `DragEnd` are already constrained, so widgets
+//! don't repeat the math.
+
+use crate::primitives::Point;
+
+/// The axes a drag may move along
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DragAxis {
+    /// Move freely
+    #[default]
+    Both,
+    /// Move horizontally only
+    X,
+    /// Move vertically only
+    Y,
+}
+
+/// How a node is dragged
+///
+/// # Example
+///
+/// ```ignore
+/// Node::new()
+///     .with_id(NodeId::new("card"))
+///     .with_drag(DragConfig::new().with_threshold(4.0).with_snap(16.0))
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DragConfig {
+    threshold: f32,
+    axis: DragAxis,
+    snap: Option<f32>,
+}
+
+impl DragConfig {
+    /// Create the default configuration: no threshold, both axes, no snapping
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how far in pixels the pointer must move from the press before the
+    /// drag starts (default 0)
+    ///
+    /// Only movement along the locked axis counts.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold.max(0.0);
+        self
+    }
+
+    /// Lock the drag to one axis
+    pub fn with_axis(mut self, axis: DragAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Snap the drag to a grid of `step` pixels, measured from the press
+    pub fn with_snap(mut self, step: f32) -> Self {
+        self.snap = (step > 0.0).then_some(step);
+        self
+    }
+
+    /// Get the drag threshold in pixels
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Get the axis lock
+    pub fn axis(&self) -> DragAxis {
+        self.axis
+    }
+
+    /// Get the snapping grid step in pixels, if any
+    pub fn snap(&self) -> Option<f32> {
+        self.snap
+    }
+
+    /// Get the movement from `origin` to `position` along the locked axes
+    fn locked_delta(&self, origin: Point, position: Point) -> Point {
+        let delta = Point {
+            x: position.x - origin.x,
+            y: position.y - origin.y,
+        };
+        match self.axis {
+            DragAxis::Both => delta,
+            DragAxis::X => Point { x: delta.x, y: 0.0 },
+            DragAxis::Y => Point { x: 0.0, y: delta.y },
+        }
+    }
+
+    /// Check if the pointer moved far enough from `origin` to start a drag
+    pub(crate) fn exceeds_threshold(&self, origin: Point, position: Point) -> bool {
+        let delta = self.locked_delta(origin, position);
+        delta.x.hypot(delta.y) >= self.threshold
+    }
+
+    /// Get the drag position for a pointer at `position`, for a drag pressed
+    /// at `origin`
+    pub(crate) fn constrain(&self, origin: Point, position: Point) -> Point {
+        let mut delta = self.locked_delta(origin, position);
+        if let Some(step) = self.snap {
+            delta.x = (delta.x / step).round() * step;
+            delta.y = (delta.y / step).round() * step;
+        }
+        Point {
+            x: origin.x + delta.x,
+            y: origin.y + delta.y,
+        }
+    }
+}
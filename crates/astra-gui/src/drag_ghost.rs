@@ -0,0 +1,105 @@
+//! Drag ghost previews
+//!
+//! While a node is being dragged, the app can show an arbitrary preview under
+//! the pointer (a "3 items" stack, a thumbnail, a label) instead of moving the
+//! source itself. The preview is composed by the app when the drag starts and
+//! handed to `UiContext::set_drag_ghost`; the context keeps it until the drag
+//! ends.
+//!
+//! The ghost is not part of the UI tree: `UiContext::drag_ghost_layer` builds
+//! a separate full-window layer that backends append on top of the frame with
+//! `FullOutput::add_layer`. It is therefore never hit-tested, so hovers and
+//! drop targets under the pointer keep receiving events.
+
+use crate::layout::{Size, Translation, ZIndex};
+use crate::node::Node;
+use crate::primitives::Point;
+
+/// Default opacity of a drag ghost
+const DEFAULT_GHOST_OPACITY: f32 = 0.6;
+
+/// App-composed preview shown under the pointer while dragging
+///
+/// # Example
+///
+/// ```ignore
+/// for event in ctx.events_for("file_list").cloned().collect::<Vec<_>>() {
+///     if let InteractionEvent::DragStart { .. } = event.event {
+///         let count = selection.len();
+///         ctx.set_drag_ghost(DragGhost::new(move || stack_preview(count)));
+///     }
+/// }
+/// ```
+pub struct DragGhost {
+    build: Box<dyn Fn() -> Node>,
+    offset: Option<(f32, f32)>,
+    opacity: f32,
+}
+
+impl DragGhost {
+    /// Create a ghost from a function building its node subtree
+    ///
+    /// The function runs every frame while the drag lasts, so the ghost is
+    /// laid out like any other node.
+    pub fn new(build: impl Fn() -> Node + 'static) -> Self {
+        Self {
+            build: Box::new(build),
+            offset: None,
+            opacity: DEFAULT_GHOST_OPACITY,
+        }
+    }
+
+    /// Set the offset in logical pixels from the pointer to the ghost's top-left corner
+    ///
+    /// By default the ghost keeps the pointer where the source was grabbed.
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Some((x, y));
+        self
+    }
+
+    /// Set the ghost opacity (default 0.6)
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Build the full-window layer with the ghost under `cursor`
+    ///
+    /// `grab_offset` is the pointer position within the source when the drag
+    /// started, in physical pixels.
+    pub(crate) fn layer(&self, cursor: Point, grab_offset: Point) -> Node {
+        // Translations rather than `Place` so the ghost lands in the same spot
+        // whether or not the layer is laid out with a measurer
+        let (anchor, offset) = match self.offset {
+            Some((x, y)) => (cursor, Translation::new(Size::lpx(x), Size::lpx(y))),
+            None => (
+                Point {
+                    x: cursor.x - grab_offset.x,
+                    y: cursor.y - grab_offset.y,
+                },
+                Translation::ZERO,
+            ),
+        };
+
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_z_index(ZIndex::OVERLAY)
+            .with_translation(Translation::new(Size::ppx(anchor.x), Size::ppx(anchor.y)))
+            .with_opacity(self.opacity)
+            .with_child(
+                Node::new()
+                    .with_translation(offset)
+                    .with_child((self.build)()),
+            )
+    }
+}
+
+impl std::fmt::Debug for DragGhost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragGhost")
+            .field("offset", &self.offset)
+            .field("opacity", &self.opacity)
+            .finish_non_exhaustive()
+    }
+}
@@ -4,8 +4,38 @@
 //! from input state and hit-testing results. It is backend-agnostic and does
 //! not depend on any specific windowing library.
 
-use crate::{hit_test_point, InputState, MouseButton, Node, NodeId, Overflow, Point};
-use std::collections::HashMap;
+use crate::drag::DragConfig;
+use crate::node::ScrollAnimation;
+use crate::platform::{PlatformRequest, WindowAction};
+use crate::{Node, NodeId, Overflow, Point, Rect, ScrollSmoothing};
+
+pub use crate::cursor::CursorIcon;
+pub use crate::drag_ghost::DragGhost;
+pub use crate::hit_test::{hit_test_deepest, hit_test_point, HitTestResult};
+pub use crate::input::{InputState, Key, MouseButton, NamedKey};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default longest time between clicks that still counts them as a double or
+/// triple click
+pub const DEFAULT_MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Farthest the pointer may move between clicks of a double or triple click,
+/// in physical pixels
+const MULTI_CLICK_DISTANCE: f32 = 4.0;
+
+/// Default time the cursor must rest on a node before `HoverStart` is sent
+pub const DEFAULT_HOVER_INTENT_DELAY: Duration = Duration::from_millis(300);
+
+/// Default time a button must be held on a node to long-press it
+pub const DEFAULT_LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// Farthest the pointer may move while held before a long press is
+/// cancelled, in physical pixels
+const LONG_PRESS_DISTANCE: f32 = 8.0;
 
 /// Interaction state of a node (for style transitions)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -20,6 +50,53 @@ pub enum InteractionState {
     Disabled,
 }
 
+/// Scroll position and range of an `Overflow::Scroll` node, as of the last frame
+///
+/// All values are in layout pixels along (horizontal, vertical).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollMetrics {
+    /// Current scroll offset
+    pub offset: (f32, f32),
+    /// Offset the container is easing toward
+    pub target: (f32, f32),
+    /// Largest offset; 0 on axes whose content fits
+    pub max_scroll: (f32, f32),
+    /// Size of the container
+    pub viewport: (f32, f32),
+}
+
+impl ScrollMetrics {
+    /// Fraction of the content that is visible on each axis (1 when it fits)
+    pub fn visible_fraction(&self) -> (f32, f32) {
+        let fraction = |viewport: f32, max: f32| {
+            if viewport + max > 0.0 {
+                viewport / (viewport + max)
+            } else {
+                1.0
+            }
+        };
+        (
+            fraction(self.viewport.0, self.max_scroll.0),
+            fraction(self.viewport.1, self.max_scroll.1),
+        )
+    }
+
+    /// Scroll position on each axis, from 0 (start) to 1 (end)
+    pub fn progress(&self) -> (f32, f32) {
+        let progress = |offset: f32, max: f32| {
+            if max > 0.0 {
+                (offset / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+        (
+            progress(self.offset.0, self.max_scroll.0),
+            progress(self.offset.1, self.max_scroll.1),
+        )
+    }
+}
+
 impl Default for InteractionState {
     fn default() -> Self {
         Self::Idle
@@ -28,6 +105,7 @@ impl Default for InteractionState {
 
 /// Type of interaction event
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum InteractionEvent {
     /// Mouse click event (button pressed and released on same target)
     Click {
@@ -36,11 +114,57 @@ pub enum InteractionEvent {
         /// Position of the click in window coordinates
         position: Point,
     },
+    /// Second click on the same node in quick succession, sent after its `Click`
+    DoubleClick {
+        /// Which mouse button was clicked
+        button: MouseButton,
+        /// Position of the click in window coordinates
+        position: Point,
+    },
+    /// Third click on the same node in quick succession, sent after its
+    /// `Click`. The click after it starts counting again.
+    TripleClick {
+        /// Which mouse button was clicked
+        button: MouseButton,
+        /// Position of the click in window coordinates
+        position: Point,
+    },
+    /// A button was held on the node for the long-press duration without the
+    /// pointer moving away; fired once per press
+    LongPress {
+        /// Which mouse button is held
+        button: MouseButton,
+        /// Position of the pointer in window coordinates
+        position: Point,
+    },
     /// Mouse hover event (cursor over node)
     Hover {
         /// Current cursor position
         position: Point,
     },
+    /// The cursor moved onto the node or one of its descendants
+    PointerEnter {
+        /// Current cursor position
+        position: Point,
+    },
+    /// The cursor moved off the node and all of its descendants
+    PointerLeave,
+    /// The cursor rested on the node for the hover-intent delay; fired once
+    /// per hover, so sweeping across the node does not trigger it
+    HoverStart {
+        /// Current cursor position
+        position: Point,
+    },
+    /// The cursor is still on a node that received `HoverStart`; fired every
+    /// frame from the `HoverStart` frame on
+    HoverHeld {
+        /// Current cursor position
+        position: Point,
+        /// How long the cursor has been on the node
+        elapsed: Duration,
+    },
+    /// The cursor left a node that received `HoverStart`
+    HoverEnd,
     /// Drag start event (button pressed and moved)
     DragStart {
         /// Which mouse button started the drag
@@ -62,10 +186,69 @@ pub enum InteractionEvent {
         /// Position where drag ended
         position: Point,
     },
+    /// The pointer moved while the node holds pointer capture, wherever it is
+    PointerMove {
+        /// Current cursor position
+        position: Point,
+        /// Movement since the last position
+        delta: Point,
+    },
+    /// A mouse button was released while the node holds pointer capture,
+    /// wherever the pointer is
+    PointerUp {
+        /// Which mouse button was released
+        button: MouseButton,
+        /// Cursor position, or the last known one if it left the window
+        position: Point,
+    },
+    /// The node no longer holds pointer capture
+    PointerCaptureLost,
+    /// A drag carrying a payload moved onto the drop target
+    DragEnter {
+        /// Current cursor position
+        position: Point,
+    },
+    /// A drag carrying a payload is over the drop target; fired every frame
+    /// while it stays there
+    DragOver {
+        /// Current cursor position
+        position: Point,
+    },
+    /// A drag carrying a payload left the drop target without dropping
+    DragLeave,
+    /// A drag carrying a payload was released over the drop target; take the
+    /// payload with `UiContext::take_drop`
+    Drop {
+        /// Position where the payload was dropped
+        position: Point,
+    },
     /// Node gained focus
     Focus,
     /// Node lost focus
     Blur,
+    /// A key was pressed while the node had focus
+    KeyDown {
+        /// The key that was pressed
+        key: Key,
+    },
+    /// Pinch gesture over the node (touchscreen or trackpad)
+    ///
+    /// Sent to every identified node under the gesture, like hover.
+    Pinch {
+        /// Zoom factor of this frame's pinch (above 1.0 zooms in)
+        zoom: f32,
+        /// Where the pinch is centered
+        position: Point,
+    },
+    /// Two-finger pan gesture over the node (touchscreen or trackpad)
+    ///
+    /// Sent to every identified node under the gesture, like hover.
+    Pan {
+        /// Pan movement this frame (horizontal, vertical) in pixels
+        delta: (f32, f32),
+        /// Where the pan is centered
+        position: Point,
+    },
     /// Mouse wheel scroll event
     Scroll {
         /// Scroll delta (horizontal, vertical)
@@ -73,6 +256,89 @@ pub enum InteractionEvent {
         /// Position of the scroll
         position: Point,
     },
+    /// The cursor rested on a peek anchor for its configured delay
+    /// (see `Node::with_peek_delay`). Fired once per hover.
+    PeekRequested {
+        /// Cursor position when the peek was requested
+        position: Point,
+    },
+    /// An open peek was dismissed (cursor left the anchor or a button was pressed)
+    PeekDismissed,
+    /// The node was right-clicked; fired alongside the right-button `Click`
+    ContextMenuRequested {
+        /// Position of the click in window coordinates
+        position: Point,
+    },
+    /// An action was chosen in the node's context menu (sent by the menu
+    /// widget through `UiContext::send_event`)
+    ContextMenuAction {
+        /// Identifier of the chosen menu item
+        action: String,
+    },
+    /// Files dragged in from outside the window are over the node; fired
+    /// every frame while they hover it
+    FileHovered(Vec<PathBuf>),
+    /// Files dragged in from outside the window were dropped on the node
+    FileDropped(Vec<PathBuf>),
+    /// An application-defined event sent with `UiContext::emit`
+    Custom(CustomEvent),
+}
+
+/// An application-defined event, sent to a node with `UiContext::emit`
+///
+/// Custom events travel with the input events, so components can signal each
+/// other by node ID without channels of their own. They carry a name to match
+/// on and optionally data of any type.
+///
+/// # Example
+///
+/// ```ignore
+/// ctx.emit("playlist", CustomEvent::new("track_added").with_data(track_id));
+///
+/// // Next frame, in the playlist component
+/// if let Some(event) = ctx.custom_event("playlist", "track_added") {
+///     let track_id: &u64 = event.data().unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CustomEvent {
+    name: String,
+    data: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl CustomEvent {
+    /// Create an event with the given name and no data
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            data: None,
+        }
+    }
+
+    /// Attach data to the event, replacing any earlier data
+    pub fn with_data<T: Any + Send + Sync>(mut self, data: T) -> Self {
+        self.data = Some(Arc::new(data));
+        self
+    }
+
+    /// Get the event name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the event data, if it is a `T`
+    pub fn data<T: Any>(&self) -> Option<&T> {
+        self.data.as_deref()?.downcast_ref()
+    }
+}
+
+impl std::fmt::Debug for CustomEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomEvent")
+            .field("name", &self.name)
+            .field("has_data", &self.data.is_some())
+            .finish()
+    }
 }
 
 /// An interaction event targeted at a specific node
@@ -100,8 +366,74 @@ struct DragState {
     last_pos: Point,
     /// The origin (top-left) of the target node in screen coordinates
     node_origin: Point,
+    /// Pointer position within the target node when the drag started
+    grab_offset: Point,
+    /// The zoom factor at the target node
+    zoom: f32,
+    /// Cursor icon under the pointer when the drag started, kept for the whole drag
+    cursor: Option<CursorIcon>,
+    /// Where the button was pressed, which axis locks and snapping are relative to
+    origin: Point,
+    /// The target's axis lock and snapping
+    config: DragConfig,
+}
+
+/// A press that turns into a drag once the pointer moves past the target's
+/// drag threshold
+#[derive(Debug, Clone)]
+struct PendingDrag {
+    /// Where the button was pressed
+    position: Point,
+    /// The topmost identified node under the press, with its origin and zoom
+    target: Option<(NodeId, Point, f32)>,
+}
+
+/// The node holding pointer capture
+#[derive(Debug, Clone)]
+struct CaptureState {
+    /// The capturing node
+    target: NodeId,
+    /// Last known cursor position while captured
+    last_pos: Option<Point>,
+    /// The origin (top-left) of the node in screen coordinates, as of the
+    /// last frame it was under the pointer
+    node_origin: Option<Point>,
+    /// The zoom factor at the node
+    zoom: f32,
+}
+
+/// The most recent click, for counting double and triple clicks
+#[derive(Debug, Clone)]
+struct ClickState {
+    /// The node that was clicked
+    target: NodeId,
+    /// Which button clicked it
+    button: MouseButton,
+    /// When the click happened
+    time: Instant,
+    /// Where the click happened
+    position: Point,
+    /// Clicks in the run so far (1 to 3)
+    count: u32,
+}
+
+/// A held button, for detecting long presses
+#[derive(Debug, Clone)]
+struct PressState {
+    /// The node the button was pressed on
+    target: NodeId,
+    /// Which button is held
+    button: MouseButton,
+    /// When the button was pressed
+    time: Instant,
+    /// Where the button was pressed
+    position: Point,
+    /// The origin (top-left) of the target node in screen coordinates
+    node_origin: Point,
     /// The zoom factor at the target node
     zoom: f32,
+    /// Whether the long press has already been sent
+    fired: bool,
 }
 
 /// Cursor blink state tracker
@@ -113,6 +445,31 @@ struct CursorBlinkState {
     visible: bool,
 }
 
+/// Hover tracking for hover-intent events
+#[derive(Debug, Clone)]
+struct HoverIntentState {
+    /// When the cursor moved onto the node
+    since: Instant,
+    /// Whether `HoverStart` has been sent
+    started: bool,
+}
+
+/// Hover tracking for a peek anchor
+#[derive(Debug, Clone)]
+struct PeekState {
+    /// When the cursor started hovering the anchor
+    hover_start: Instant,
+    /// The anchor's rect from the most recent hit test
+    anchor_rect: Rect,
+    /// Whether the peek has been requested and not yet dismissed
+    open: bool,
+    /// Whether the peek is suppressed until the cursor leaves the anchor
+    suppressed: bool,
+}
+
+/// A scroll container's offset, target and eased animation, kept across frames
+type ScrollEntry = ((f32, f32), (f32, f32), ScrollAnimation);
+
 /// Event dispatcher that generates interaction events from input state
 ///
 /// This maintains state across frames to detect interactions like clicks,
@@ -122,12 +479,66 @@ pub struct EventDispatcher {
     hovered_nodes: Vec<NodeId>,
     /// Current drag state, if dragging
     drag_state: Option<DragState>,
+    /// The press a drag may start from
+    pending_drag: Option<PendingDrag>,
+    /// Window moves and resizes requested by presses since the last take
+    platform_requests: Vec<PlatformRequest>,
+    /// Open focus scopes in the order they opened, each with the node that
+    /// was focused before it
+    focus_scopes: Vec<(NodeId, Option<NodeId>)>,
+    /// The node holding pointer capture, set with `capture_pointer`
+    capture: Option<CaptureState>,
+    /// Data carried by the current drag, set with `set_drag_payload`
+    drag_payload: Option<Box<dyn Any>>,
+    /// The drop target the payload is currently over
+    drop_target: Option<NodeId>,
+    /// The payload dropped in the last dispatch, with the node it was dropped on
+    dropped: Option<(NodeId, Box<dyn Any>)>,
+    /// The last click, for counting double and triple clicks
+    last_click: Option<ClickState>,
+    /// Longest time between clicks that still counts as a double or triple click
+    multi_click_interval: Duration,
+    /// The held button, if any, for detecting long presses
+    press: Option<PressState>,
+    /// How long a button must be held to long-press a node
+    long_press_duration: Duration,
     /// Currently focused node ID, if any
     focused_node: Option<NodeId>,
     /// Cursor blink states for focused text inputs (node_id -> blink_state)
     cursor_blink_states: HashMap<NodeId, CursorBlinkState>,
-    /// Persistent scroll state (node_id -> (scroll_offset, scroll_target))
-    scroll_state: HashMap<String, ((f32, f32), (f32, f32))>,
+    /// Persistent scroll state (node_id -> (scroll_offset, scroll_target, eased animation))
+    scroll_state: HashMap<String, ScrollEntry>,
+    /// Scroll targets requested with `scroll_to`, applied in the next `restore_scroll_state`
+    scroll_requests: HashMap<String, (f32, f32)>,
+    /// Immediate scroll offset shifts requested with `shift_scroll`, applied
+    /// in the next `restore_scroll_state`
+    scroll_shifts: HashMap<String, (f32, f32)>,
+    /// Position and range of identified scroll containers, recorded in `sync_scroll_state`
+    scroll_metrics: HashMap<String, ScrollMetrics>,
+    /// Hover tracking for currently hovered peek anchors
+    peek_states: HashMap<NodeId, PeekState>,
+    /// Hover tracking for hover-intent events, for every hovered identified node
+    hover_intents: HashMap<NodeId, HoverIntentState>,
+    /// How long the cursor must rest on a node before `HoverStart` is sent
+    hover_intent_delay: Duration,
+    /// Cursor icon resolved in the last dispatch
+    cursor_icon: CursorIcon,
+    /// Nodes a mouse button was pressed inside during the last dispatch, or
+    /// `None` if no button was pressed
+    pressed_within: Option<HashSet<NodeId>>,
+    /// The topmost identified node a mouse button was pressed on during the
+    /// last dispatch, and its identified ancestors
+    pressed_target_within: HashSet<NodeId>,
+    /// The focused node and its identified ancestors, as of the last
+    /// `sync_focus_within`
+    focus_within: HashSet<NodeId>,
+    /// Blur and Focus events for focus moved with `focus`, and
+    /// `PointerCaptureLost` for capture released between frames, sent with
+    /// the next dispatch
+    queued_events: Vec<TargetedEvent>,
+    /// Laid-out sizes of nodes marked with `with_measure_size`, as of the last
+    /// `sync_measured_sizes`
+    measured_sizes: HashMap<String, (f32, f32)>,
 }
 
 impl EventDispatcher {
@@ -136,9 +547,246 @@ impl EventDispatcher {
         Self {
             hovered_nodes: Vec::new(),
             drag_state: None,
+            pending_drag: None,
+            platform_requests: Vec::new(),
+            focus_scopes: Vec::new(),
+            capture: None,
+            drag_payload: None,
+            drop_target: None,
+            dropped: None,
+            last_click: None,
+            multi_click_interval: DEFAULT_MULTI_CLICK_INTERVAL,
+            press: None,
+            long_press_duration: DEFAULT_LONG_PRESS_DURATION,
             focused_node: None,
             cursor_blink_states: HashMap::new(),
             scroll_state: HashMap::new(),
+            scroll_requests: HashMap::new(),
+            scroll_shifts: HashMap::new(),
+            scroll_metrics: HashMap::new(),
+            peek_states: HashMap::new(),
+            hover_intents: HashMap::new(),
+            hover_intent_delay: DEFAULT_HOVER_INTENT_DELAY,
+            cursor_icon: CursorIcon::Default,
+            pressed_within: None,
+            pressed_target_within: HashSet::new(),
+            focus_within: HashSet::new(),
+            queued_events: Vec::new(),
+            measured_sizes: HashMap::new(),
+        }
+    }
+
+    /// Check if a mouse button was pressed in the last dispatch outside the
+    /// node with the given ID
+    ///
+    /// A press counts as inside when it lands on the node or on any identified
+    /// node below it, including children drawn outside its bounds such as
+    /// dropdowns, so popups can be dismissed by clicking elsewhere.
+    pub fn was_pressed_outside(&self, id: &NodeId) -> bool {
+        self.pressed_within
+            .as_ref()
+            .is_some_and(|within| !within.contains(id))
+    }
+
+    /// Check if a mouse button was pressed in the last dispatch on the node
+    /// with the given ID or on an identified node below it
+    ///
+    /// Unlike [`Self::was_pressed_outside`], only the topmost node under the
+    /// pointer counts, so nodes covered by it (such as an overlapped window)
+    /// were not pressed.
+    pub fn was_pressed_inside(&self, id: &NodeId) -> bool {
+        self.pressed_target_within.contains(id)
+    }
+
+    /// Check if the node with the given ID is focused or contains the focused
+    /// node, as of the last `sync_focus_within`
+    pub fn is_focus_within(&self, id: &NodeId) -> bool {
+        self.focus_within.contains(id)
+    }
+
+    /// Record the focused node and its identified ancestors in the laid-out tree
+    pub fn sync_focus_within(&mut self, root: &Node) {
+        self.focus_within.clear();
+        if let Some(focused) = self.focused_node.clone() {
+            Self::collect_within(root, std::slice::from_ref(&focused), &mut self.focus_within);
+        }
+    }
+
+    /// Set the longest time between clicks that still counts them as a
+    /// double or triple click (default: [`DEFAULT_MULTI_CLICK_INTERVAL`])
+    pub fn set_multi_click_interval(&mut self, interval: Duration) {
+        self.multi_click_interval = interval;
+    }
+
+    /// Get the longest time between clicks of a double or triple click
+    pub fn multi_click_interval(&self) -> Duration {
+        self.multi_click_interval
+    }
+
+    /// Set how long the cursor must rest on a node before it receives
+    /// `HoverStart` (default: [`DEFAULT_HOVER_INTENT_DELAY`])
+    pub fn set_hover_intent_delay(&mut self, delay: Duration) {
+        self.hover_intent_delay = delay;
+    }
+
+    /// Get how long the cursor must rest on a node before `HoverStart`
+    pub fn hover_intent_delay(&self) -> Duration {
+        self.hover_intent_delay
+    }
+
+    /// Set how long a button must be held on a node, without the pointer
+    /// moving away, to long-press it (default: [`DEFAULT_LONG_PRESS_DURATION`])
+    pub fn set_long_press_duration(&mut self, duration: Duration) {
+        self.long_press_duration = duration;
+    }
+
+    /// Get how long a button must be held on a node to long-press it
+    pub fn long_press_duration(&self) -> Duration {
+        self.long_press_duration
+    }
+
+    /// Count a click, returning how many clicks in a row it makes (1 to 3)
+    ///
+    /// Clicks continue a run when they hit the same node with the same button,
+    /// soon enough and close enough to the previous one.
+    fn count_click(&mut self, target: &NodeId, button: MouseButton, position: Point) -> u32 {
+        let now = Instant::now();
+        let count = match &self.last_click {
+            Some(last)
+                if last.target == *target
+                    && last.button == button
+                    && last.count < 3
+                    && now.duration_since(last.time) <= self.multi_click_interval
+                    && (position.x - last.position.x).hypot(position.y - last.position.y)
+                        <= MULTI_CLICK_DISTANCE =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some(ClickState {
+            target: target.clone(),
+            button,
+            time: now,
+            position,
+            count,
+        });
+        count
+    }
+
+    /// Get the cursor icon requested by the node under the pointer
+    ///
+    /// While dragging, the icon that was shown when the drag started is kept.
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    /// Take the window moves and resizes requested by presses
+    pub fn take_platform_requests(&mut self) -> Vec<PlatformRequest> {
+        std::mem::take(&mut self.platform_requests)
+    }
+
+    /// Get the node being dragged, if any
+    pub fn drag_target(&self) -> Option<&NodeId> {
+        self.drag_state.as_ref().map(|drag| &drag.target)
+    }
+
+    /// Get where the dragged node was grabbed, relative to its top-left corner
+    pub fn drag_grab_offset(&self) -> Option<Point> {
+        self.drag_state.as_ref().map(|drag| drag.grab_offset)
+    }
+
+    /// Route the pointer to a node until every mouse button is released
+    ///
+    /// The node receives `PointerMove` and `PointerUp` events wherever the
+    /// pointer goes, even outside the node or the window, and a drag started
+    /// while it holds capture drags it rather than the node under the
+    /// pointer. Capturing a new node releases the previous one.
+    pub fn capture_pointer(&mut self, node_id: NodeId) {
+        if self.pointer_capture() == Some(&node_id) {
+            return;
+        }
+        self.release_pointer_capture();
+
+        // Start from where the node was dragged or pressed, if it was
+        let seen = self
+            .drag_state
+            .as_ref()
+            .filter(|drag| drag.target == node_id)
+            .map(|drag| (drag.last_pos, drag.node_origin, drag.zoom))
+            .or_else(|| {
+                self.press
+                    .as_ref()
+                    .filter(|press| press.target == node_id)
+                    .map(|press| (press.position, press.node_origin, press.zoom))
+            });
+        self.capture = Some(CaptureState {
+            target: node_id,
+            last_pos: seen.map(|(pos, _, _)| pos),
+            node_origin: seen.map(|(_, origin, _)| origin),
+            zoom: seen.map_or(1.0, |(_, _, zoom)| zoom),
+        });
+    }
+
+    /// Release pointer capture, sending `PointerCaptureLost` to the node that
+    /// held it with the next dispatch
+    pub fn release_pointer_capture(&mut self) {
+        if let Some(capture) = self.capture.take() {
+            self.queued_events.push(TargetedEvent {
+                event: InteractionEvent::PointerCaptureLost,
+                target: capture.target,
+                local_position: Point::zero(),
+                zoom: capture.zoom,
+            });
+        }
+    }
+
+    /// Get the node holding pointer capture
+    pub fn pointer_capture(&self) -> Option<&NodeId> {
+        self.capture.as_ref().map(|capture| &capture.target)
+    }
+
+    /// Attach data to the drag in progress, replacing any earlier payload
+    ///
+    /// Drop targets under the pointer receive `DragEnter`, `DragOver`,
+    /// `DragLeave` and `Drop` events for the rest of the drag. Without an
+    /// active drag the payload is discarded.
+    pub fn set_drag_payload(&mut self, payload: Box<dyn Any>) {
+        if self.drag_state.is_some() {
+            self.drag_payload = Some(payload);
+        }
+    }
+
+    /// Get the data carried by the drag in progress, if any
+    pub fn drag_payload(&self) -> Option<&dyn Any> {
+        self.drag_payload.as_deref()
+    }
+
+    /// Get the drop target the dragged payload is over, if any
+    pub fn drop_target(&self) -> Option<&NodeId> {
+        self.drop_target.as_ref()
+    }
+
+    /// Get the payload dropped on the node with the given ID in the last
+    /// dispatch, leaving it in place
+    pub fn dropped_payload(&self, id: &NodeId) -> Option<&dyn Any> {
+        self.dropped
+            .as_ref()
+            .filter(|(target, _)| target == id)
+            .map(|(_, payload)| payload.as_ref())
+    }
+
+    /// Take the payload dropped on the node with the given ID in the last
+    /// dispatch
+    pub fn take_drop(&mut self, id: &NodeId) -> Option<Box<dyn Any>> {
+        if self
+            .dropped
+            .as_ref()
+            .is_some_and(|(target, _)| target == id)
+        {
+            self.dropped.take().map(|(_, payload)| payload)
+        } else {
+            None
         }
     }
 
@@ -175,6 +823,205 @@ impl EventDispatcher {
         self.focused_node = node_id;
     }
 
+    /// Move focus, sending Blur and Focus events for the change with the next
+    /// dispatch
+    ///
+    /// Unlike [`Self::set_focus`], which widgets use to track their own focus
+    /// silently, this is how focus moves between nodes: by clicks, Tab and
+    /// `UiContext::focus`.
+    pub fn focus(&mut self, node_id: Option<NodeId>) {
+        let mut events = std::mem::take(&mut self.queued_events);
+        self.move_focus(node_id, &mut events);
+        self.queued_events = events;
+    }
+
+    /// Set the focused node, pushing Blur and Focus events if it changes
+    fn move_focus(&mut self, node_id: Option<NodeId>, events: &mut Vec<TargetedEvent>) {
+        if node_id == self.focused_node {
+            return;
+        }
+        let focus_event = |event, target: &NodeId| TargetedEvent {
+            event,
+            target: target.clone(),
+            local_position: Point::zero(),
+            zoom: 1.0,
+        };
+        if let Some(ref old_focus) = self.focused_node {
+            events.push(focus_event(InteractionEvent::Blur, old_focus));
+        }
+        if let Some(ref new_focus) = node_id {
+            events.push(focus_event(InteractionEvent::Focus, new_focus));
+        }
+        self.set_focus(node_id);
+    }
+
+    /// Move focus to the next (or with Shift, previous) focusable node on Tab
+    /// and send the other keys pressed to the focused node
+    ///
+    /// Tab only moves focus while nothing is focused or a focusable node is,
+    /// so widgets that take focus without being focusable (such as code
+    /// editors) still receive it.
+    fn process_keys(&mut self, input: &InputState, root: &Node, events: &mut Vec<TargetedEvent>) {
+        // Inside an open focus scope, only its subtree takes part
+        let scope = self
+            .focus_scopes
+            .last()
+            .and_then(|(id, _)| Self::find_node(root, id));
+        let tab = Key::Named(NamedKey::Tab);
+        let mut order = Vec::new();
+        let focused_focusable = Self::collect_focus_order(
+            scope.unwrap_or(root),
+            self.focused_node.as_ref(),
+            &mut order,
+        );
+        let traverses = !order.is_empty()
+            && input.keys_just_pressed.contains(&tab)
+            && (self.focused_node.is_none() || focused_focusable != Some(false));
+        let focused = self
+            .focused_node
+            .as_ref()
+            .filter(|_| scope.is_none() || focused_focusable.is_some());
+
+        if let Some(focused) = focused {
+            for key in &input.keys_just_pressed {
+                if traverses && *key == tab {
+                    continue;
+                }
+                events.push(TargetedEvent {
+                    event: InteractionEvent::KeyDown { key: key.clone() },
+                    target: focused.clone(),
+                    local_position: Point::zero(),
+                    zoom: 1.0,
+                });
+            }
+        }
+
+        if traverses {
+            let len = order.len();
+            let current = self
+                .focused_node
+                .as_ref()
+                .and_then(|focused| order.iter().position(|id| id == focused));
+            let next = match (current, input.shift_held) {
+                (Some(index), false) => (index + 1) % len,
+                (Some(index), true) => (index + len - 1) % len,
+                (None, false) => 0,
+                (None, true) => len - 1,
+            };
+            self.move_focus(Some(order[next].clone()), events);
+        }
+    }
+
+    /// Track focus scopes opening and closing, moving focus into each scope
+    /// that opens and back to where it was when one closes
+    fn update_focus_scopes(&mut self, root: &Node, events: &mut Vec<TargetedEvent>) {
+        let mut open = Vec::new();
+        Self::collect_focus_scopes(root, &mut open);
+
+        // Close scopes that left the tree, along with any opened after them
+        if let Some(closed) = self
+            .focus_scopes
+            .iter()
+            .position(|(id, _)| !open.contains(id))
+        {
+            let (_, restore) = self.focus_scopes.drain(closed..).next().unwrap();
+            self.move_focus(restore, events);
+        }
+
+        for id in open {
+            if self.focus_scopes.iter().any(|(open, _)| *open == id) {
+                continue;
+            }
+            let mut order = Vec::new();
+            if let Some(scope) = Self::find_node(root, &id) {
+                Self::collect_focus_order(scope, None, &mut order);
+            }
+            self.focus_scopes.push((id, self.focused_node.clone()));
+            self.move_focus(order.into_iter().next(), events);
+        }
+    }
+
+    /// Collect the IDs of the focus scopes in the tree, in tree order
+    fn collect_focus_scopes(node: &Node, scopes: &mut Vec<NodeId>) {
+        if let Some(id) = node.id().filter(|_| node.is_focus_scope()) {
+            scopes.push(id.clone());
+        }
+        for child in node.children() {
+            Self::collect_focus_scopes(child, scopes);
+        }
+    }
+
+    /// Find the node with the given ID
+    fn find_node<'a>(node: &'a Node, id: &NodeId) -> Option<&'a Node> {
+        if node.id() == Some(id) {
+            return Some(node);
+        }
+        node.children()
+            .iter()
+            .find_map(|child| Self::find_node(child, id))
+    }
+
+    /// Send this frame's pinch and two-finger pan to the identified nodes
+    /// under the gesture
+    fn process_gestures(&self, input: &InputState, root: &Node, events: &mut Vec<TargetedEvent>) {
+        let pinched = (input.pinch_zoom - 1.0).abs() > f32::EPSILON;
+        let panned = input.pan_delta.0.abs() > 0.001 || input.pan_delta.1.abs() > 0.001;
+        if !pinched && !panned {
+            return;
+        }
+        let Some(position) = input.gesture_position.or(input.cursor_position) else {
+            return;
+        };
+        for hit in hit_test_point(root, position) {
+            let Some(node_id) = hit.node_id else {
+                continue;
+            };
+            let mut push = |event| {
+                events.push(TargetedEvent {
+                    event,
+                    target: node_id.clone(),
+                    local_position: hit.local_pos,
+                    zoom: hit.zoom,
+                })
+            };
+            if pinched {
+                push(InteractionEvent::Pinch {
+                    zoom: input.pinch_zoom,
+                    position,
+                });
+            }
+            if panned {
+                push(InteractionEvent::Pan {
+                    delta: input.pan_delta,
+                    position,
+                });
+            }
+        }
+    }
+
+    /// Collect the enabled focusable nodes in layout order, returning whether
+    /// the focused node is focusable if it is in the tree
+    fn collect_focus_order(
+        node: &Node,
+        focused: Option<&NodeId>,
+        order: &mut Vec<NodeId>,
+    ) -> Option<bool> {
+        let mut found = None;
+        if let Some(id) = node.id() {
+            let focusable = node.is_focusable() && !node.is_disabled();
+            if focusable {
+                order.push(id.clone());
+            }
+            if focused == Some(id) {
+                found = Some(focusable);
+            }
+        }
+        for child in node.children() {
+            found = Self::collect_focus_order(child, focused, order).or(found);
+        }
+        found
+    }
+
     /// Update cursor blink state for a focused node
     ///
     /// Call this each frame to update the cursor visibility.
@@ -209,6 +1056,31 @@ impl EventDispatcher {
             .unwrap_or(true)
     }
 
+    /// Check if a peek is currently open for a node
+    pub fn is_peek_open(&self, node_id: &NodeId) -> bool {
+        self.peek_states
+            .get(node_id)
+            .map(|s| s.open)
+            .unwrap_or(false)
+    }
+
+    /// Get the rect of a hovered peek anchor from the last dispatch
+    ///
+    /// Useful for positioning a preview relative to its anchor.
+    pub fn peek_anchor_rect(&self, node_id: &NodeId) -> Option<Rect> {
+        self.peek_states.get(node_id).map(|s| s.anchor_rect)
+    }
+
+    /// Close an open peek without waiting for the cursor to leave the anchor
+    ///
+    /// The peek will not be requested again until the anchor is re-hovered.
+    pub fn dismiss_peek(&mut self, node_id: &NodeId) {
+        if let Some(state) = self.peek_states.get_mut(node_id) {
+            state.open = false;
+            state.suppressed = true;
+        }
+    }
+
     /// Dispatch events based on input state and UI tree
     ///
     /// This is the main method that should be called each frame after
@@ -228,21 +1100,74 @@ impl EventDispatcher {
         input: &InputState,
         root: &mut Node,
     ) -> (Vec<TargetedEvent>, HashMap<NodeId, InteractionState>) {
-        let mut events = Vec::new();
+        let mut events = std::mem::take(&mut self.queued_events);
         let mut interaction_states = HashMap::new();
+        self.pressed_within = None;
+        self.pressed_target_within.clear();
+        self.dropped = None;
+
+        // Confine focus to the most recently opened focus scope
+        self.update_focus_scopes(root, &mut events);
+
+        // Keys go to the focused node whether or not the cursor is in the window
+        self.process_keys(input, root, &mut events);
+
+        // Touch gestures have a position of their own, and may come without a cursor
+        self.process_gestures(input, root, &mut events);
 
         // Get current cursor position
         let cursor_pos = match input.cursor_position {
             Some(pos) => pos,
             None => {
                 // Cursor left window - clear hover states
+                Self::push_pointer_leaves(&self.hovered_nodes, &[], &mut events);
                 self.hovered_nodes.clear();
-                return (events, interaction_states);
+                self.cursor_icon = CursorIcon::Default;
+                self.press = None;
+                self.process_hover_intents(&[], None, &mut events);
+
+                // A drag holding pointer capture still ends when released
+                // outside the window
+                let captured_drag_released = self.drag_state.as_ref().is_some_and(|drag| {
+                    input.is_button_just_released(drag.button)
+                        && self.pointer_capture() == Some(&drag.target)
+                });
+                if captured_drag_released {
+                    let drag = self.drag_state.take().unwrap();
+                    events.push(TargetedEvent {
+                        event: InteractionEvent::DragEnd {
+                            button: drag.button,
+                            position: drag.last_pos,
+                        },
+                        target: drag.target,
+                        local_position: Point {
+                            x: drag.last_pos.x - drag.node_origin.x,
+                            y: drag.last_pos.y - drag.node_origin.y,
+                        },
+                        zoom: drag.zoom,
+                    });
+                }
+                self.process_pointer_capture(&[], None, input, &mut events);
+
+                if let Some(target) = self.drop_target.take() {
+                    events.push(TargetedEvent {
+                        event: InteractionEvent::DragLeave,
+                        target,
+                        local_position: Point::zero(),
+                        zoom: 1.0,
+                    });
+                }
+                self.process_peeks(&[], None, input, &mut events);
+                return (events, interaction_states);
             }
         };
 
-        // Hit test to find nodes under cursor
-        let hits = hit_test_point(root, cursor_pos);
+        // Hit test to find nodes under cursor, ignoring those outside an open
+        // focus scope
+        let mut hits = hit_test_point(root, cursor_pos);
+        if let Some((scope, _)) = self.focus_scopes.last() {
+            hits.retain(|hit| hit.focus_scope.as_ref() == Some(scope));
+        }
 
         // Build list of currently hovered node IDs
         let mut current_hovered: Vec<NodeId> = Vec::new();
@@ -252,56 +1177,76 @@ impl EventDispatcher {
             }
         }
 
+        // Record which subtrees a press landed in, for click-outside checks
+        if !input.buttons_just_pressed.is_empty() {
+            let mut within = HashSet::new();
+            Self::collect_within(root, &current_hovered, &mut within);
+            self.pressed_within = Some(within);
+            if let Some(target) = hits.iter().rev().find_map(|h| h.node_id.clone()) {
+                Self::collect_within(root, &[target], &mut self.pressed_target_within);
+            }
+            self.pending_drag = Some(PendingDrag {
+                position: cursor_pos,
+                target: hits.iter().rev().find_map(|h| {
+                    let origin = Point {
+                        x: cursor_pos.x - h.local_pos.x,
+                        y: cursor_pos.y - h.local_pos.y,
+                    };
+                    Some((h.node_id.clone()?, origin, h.zoom))
+                }),
+            });
+        }
+
+        // Route the pointer to the node holding capture
+        self.process_pointer_capture(&hits, Some(cursor_pos), input, &mut events);
+
+        // Handle presses held long enough to count as long presses
+        self.process_long_press(&hits, cursor_pos, input, &mut events);
+
         // Check for focus changes (click to focus)
         if input.is_button_just_pressed(MouseButton::Left) {
-            // Find the topmost focusable node (must have an ID)
+            // The topmost focusable node, or else the outermost identified one
             let new_focus = hits
                 .iter()
-                .find(|h| h.node_id.is_some())
+                .rfind(|h| h.focusable)
+                .or_else(|| hits.iter().find(|h| h.node_id.is_some()))
                 .and_then(|hit| hit.node_id.clone());
+            self.move_focus(new_focus, &mut events);
 
-            // Generate blur event for previously focused node
-            if let Some(ref old_focus) = self.focused_node {
-                if new_focus.as_ref() != Some(old_focus) {
-                    events.push(TargetedEvent {
-                        event: InteractionEvent::Blur,
-                        target: old_focus.clone(),
-                        local_position: Point::zero(),
-                        zoom: 1.0,
-                    });
-                }
-            }
-
-            // Generate focus event for newly focused node
-            if let Some(ref new_focus_id) = new_focus {
-                if self.focused_node.as_ref() != Some(new_focus_id) {
-                    events.push(TargetedEvent {
-                        event: InteractionEvent::Focus,
-                        target: new_focus_id.clone(),
-                        local_position: Point::zero(),
-                        zoom: 1.0,
-                    });
+            // Move or resize the window from the pressed node, unless an
+            // identified node above it takes the press
+            let action = hits
+                .iter()
+                .rfind(|h| h.node_id.is_some() || h.window_action.is_some())
+                .and_then(|h| h.window_action);
+            match action {
+                Some(WindowAction::Drag) => {
+                    self.platform_requests.push(PlatformRequest::DragWindow)
                 }
+                Some(WindowAction::Resize(direction)) => self
+                    .platform_requests
+                    .push(PlatformRequest::ResizeWindow(direction)),
+                None => {}
             }
-
-            self.set_focus(new_focus);
         }
 
         // Handle drag state
         if let Some(ref mut drag) = self.drag_state {
             // Check if drag button was released
+            // Apply the target's axis lock and snapping
+            let position = drag.config.constrain(drag.origin, cursor_pos);
             if input.is_button_just_released(drag.button) {
                 // Calculate local position within the target node
                 let local_position = Point {
-                    x: cursor_pos.x - drag.node_origin.x,
-                    y: cursor_pos.y - drag.node_origin.y,
+                    x: position.x - drag.node_origin.x,
+                    y: position.y - drag.node_origin.y,
                 };
 
                 // Generate DragEnd event
                 events.push(TargetedEvent {
                     event: InteractionEvent::DragEnd {
                         button: drag.button,
-                        position: cursor_pos,
+                        position,
                     },
                     target: drag.target.clone(),
                     local_position,
@@ -313,35 +1258,35 @@ impl EventDispatcher {
             } else {
                 // Generate DragMove event
                 let delta = Point {
-                    x: cursor_pos.x - drag.last_pos.x,
-                    y: cursor_pos.y - drag.last_pos.y,
+                    x: position.x - drag.last_pos.x,
+                    y: position.y - drag.last_pos.y,
                 };
 
                 if delta.x.abs() > 0.001 || delta.y.abs() > 0.001 {
                     // Calculate local position within the target node
                     let local_position = Point {
-                        x: cursor_pos.x - drag.node_origin.x,
-                        y: cursor_pos.y - drag.node_origin.y,
+                        x: position.x - drag.node_origin.x,
+                        y: position.y - drag.node_origin.y,
                     };
 
                     events.push(TargetedEvent {
-                        event: InteractionEvent::DragMove {
-                            position: cursor_pos,
-                            delta,
-                        },
+                        event: InteractionEvent::DragMove { position, delta },
                         target: drag.target.clone(),
                         local_position,
                         zoom: drag.zoom,
                     });
                 }
 
-                drag.last_pos = cursor_pos;
+                drag.last_pos = position;
 
                 // Mark drag target as active
                 interaction_states.insert(drag.target.clone(), InteractionState::Active);
             }
         }
 
+        // Resolve the drop target for a drag carrying a payload
+        self.process_drop_targets(&hits, cursor_pos, input, &mut events);
+
         // Clear drag state if button released
         if self
             .drag_state
@@ -351,41 +1296,72 @@ impl EventDispatcher {
         {
             self.drag_state = None;
         }
+        let buttons = [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+        if !buttons
+            .into_iter()
+            .any(|button| input.is_button_down(button))
+        {
+            self.pending_drag = None;
+        }
 
         // Check for new drag start
         if self.drag_state.is_none() {
             for button in [MouseButton::Left, MouseButton::Right, MouseButton::Middle] {
                 if input.is_button_down(button) && !input.is_button_just_pressed(button) {
                     // Button held from previous frame - check if we should start a drag
-                    // Find first hit with a node_id (skip nodes without IDs)
-                    if let Some(hit) = hits.iter().rfind(|h| h.node_id.is_some()) {
-                        if let Some(ref node_id) = hit.node_id {
-                            // Calculate node origin from cursor position and local position
-                            let node_origin = Point {
-                                x: cursor_pos.x - hit.local_pos.x,
-                                y: cursor_pos.y - hit.local_pos.y,
-                            };
-
-                            // Start drag
-                            self.drag_state = Some(DragState {
-                                button,
-                                target: node_id.clone(),
-                                last_pos: cursor_pos,
-                                node_origin,
-                                zoom: hit.zoom,
-                            });
-
-                            events.push(TargetedEvent {
-                                event: InteractionEvent::DragStart {
-                                    button,
-                                    position: cursor_pos,
-                                },
-                                target: node_id.clone(),
-                                local_position: hit.local_pos,
-                                zoom: hit.zoom,
-                            });
+                    // The node holding capture is dragged wherever the pointer
+                    // is; otherwise the first hit with a node_id under the
+                    // press (skip nodes without IDs)
+                    let captured = self.capture.as_ref().and_then(|capture| {
+                        Some((capture.target.clone(), capture.node_origin?, capture.zoom))
+                    });
+                    let pending = self.pending_drag.as_ref();
+                    let target = captured
+                        .or_else(|| pending.and_then(|pending| pending.target.clone()))
+                        .or_else(|| {
+                            hits.iter().rev().find_map(|h| {
+                                let origin = Point {
+                                    x: cursor_pos.x - h.local_pos.x,
+                                    y: cursor_pos.y - h.local_pos.y,
+                                };
+                                Some((h.node_id.clone()?, origin, h.zoom))
+                            })
+                        });
+                    if let Some((node_id, node_origin, zoom)) = target {
+                        // Wait until the pointer moves past the target's threshold
+                        let config = Self::find_node(root, &node_id)
+                            .map_or_else(DragConfig::new, Node::drag_config);
+                        let origin = pending.map_or(cursor_pos, |pending| pending.position);
+                        if !config.exceeds_threshold(origin, cursor_pos) {
                             break;
                         }
+                        let position = config.constrain(origin, cursor_pos);
+                        let local_pos = Point {
+                            x: position.x - node_origin.x,
+                            y: position.y - node_origin.y,
+                        };
+
+                        // Start drag
+                        self.drag_state = Some(DragState {
+                            button,
+                            target: node_id.clone(),
+                            last_pos: position,
+                            node_origin,
+                            grab_offset: local_pos,
+                            zoom,
+                            cursor: hits.iter().rev().find_map(|h| h.cursor),
+                            origin,
+                            config,
+                        });
+                        self.pending_drag = None;
+
+                        events.push(TargetedEvent {
+                            event: InteractionEvent::DragStart { button, position },
+                            target: node_id,
+                            local_position: local_pos,
+                            zoom,
+                        });
+                        break;
                     }
                 }
             }
@@ -407,6 +1383,35 @@ impl EventDispatcher {
                                 local_position: hit.local_pos,
                                 zoom: hit.zoom,
                             });
+                            let multi_click = match self.count_click(node_id, button, cursor_pos) {
+                                2 => Some(InteractionEvent::DoubleClick {
+                                    button,
+                                    position: cursor_pos,
+                                }),
+                                3 => Some(InteractionEvent::TripleClick {
+                                    button,
+                                    position: cursor_pos,
+                                }),
+                                _ => None,
+                            };
+                            if let Some(event) = multi_click {
+                                events.push(TargetedEvent {
+                                    event,
+                                    target: node_id.clone(),
+                                    local_position: hit.local_pos,
+                                    zoom: hit.zoom,
+                                });
+                            }
+                            if button == MouseButton::Right {
+                                events.push(TargetedEvent {
+                                    event: InteractionEvent::ContextMenuRequested {
+                                        position: cursor_pos,
+                                    },
+                                    target: node_id.clone(),
+                                    local_position: hit.local_pos,
+                                    zoom: hit.zoom,
+                                });
+                            }
                         }
                     }
                 }
@@ -436,15 +1441,62 @@ impl EventDispatcher {
             }
         }
 
+        // Send hover-intent events to nodes the cursor has rested on
+        self.process_hover_intents(&hits, Some(cursor_pos), &mut events);
+
+        // Files dragged in from outside go to the topmost identified node
+        // under the pointer, like clicks
+        if let Some(hit) = hits.iter().rfind(|h| h.node_id.is_some()) {
+            let mut push = |event| {
+                events.push(TargetedEvent {
+                    event,
+                    target: hit.node_id.clone().unwrap(),
+                    local_position: hit.local_pos,
+                    zoom: hit.zoom,
+                })
+            };
+            if !input.files_hovered.is_empty() {
+                push(InteractionEvent::FileHovered(input.files_hovered.clone()));
+            }
+            if !input.files_dropped.is_empty() {
+                push(InteractionEvent::FileDropped(input.files_dropped.clone()));
+            }
+        }
+
+        // Handle hover-delayed peeks
+        self.process_peeks(&hits, Some(cursor_pos), input, &mut events);
+
         // Handle scroll events
         if input.scroll_delta.0.abs() > 0.001 || input.scroll_delta.1.abs() > 0.001 {
-            self.process_scroll_event(
-                root,
-                cursor_pos,
-                input.scroll_delta,
-                input.shift_held,
-                &mut events,
-            );
+            self.process_scroll_event(root, cursor_pos, input, &mut events);
+        }
+
+        // The topmost node requesting a cursor wins; a drag keeps its cursor
+        // even when the pointer leaves the dragged node
+        self.cursor_icon = self
+            .drag_state
+            .as_ref()
+            .and_then(|drag| drag.cursor)
+            .or_else(|| hits.iter().rev().find_map(|h| h.cursor))
+            .unwrap_or_default();
+
+        // Send leaves, innermost first, then enters, outermost first, for
+        // nodes whose hovered state changed; hits include every identified
+        // ancestor, so moving between children doesn't leave the parent
+        Self::push_pointer_leaves(&self.hovered_nodes, &current_hovered, &mut events);
+        for hit in &hits {
+            if let Some(node_id) = &hit.node_id {
+                if !self.hovered_nodes.contains(node_id) {
+                    events.push(TargetedEvent {
+                        event: InteractionEvent::PointerEnter {
+                            position: cursor_pos,
+                        },
+                        target: node_id.clone(),
+                        local_position: hit.local_pos,
+                        zoom: hit.zoom,
+                    });
+                }
+            }
         }
 
         // Update hovered nodes list
@@ -453,31 +1505,149 @@ impl EventDispatcher {
         (events, interaction_states)
     }
 
-    /// Restore scroll state to nodes after UI rebuild
-    pub fn restore_scroll_state(&self, root: &mut Node) {
-        self.restore_scroll_state_recursive(root);
+    /// Scroll the `Overflow::Scroll` node with the given ID toward `target`
+    ///
+    /// Applied in the next `restore_scroll_state`, clamped to the node's scroll
+    /// range; the node eases there with its scroll smoothing.
+    pub fn scroll_to(&mut self, id: &str, target: (f32, f32)) {
+        self.scroll_requests.insert(id.to_string(), target);
+    }
+
+    /// Move the scroll position of the `Overflow::Scroll` node with the given
+    /// ID by `delta` without easing
+    ///
+    /// Applied in the next `restore_scroll_state` to both the offset and the
+    /// target, so an ease in progress carries on from the new position. Used
+    /// to keep content in place when rows are inserted or resized above it.
+    pub fn shift_scroll(&mut self, id: &str, delta: (f32, f32)) {
+        let shift = self.scroll_shifts.entry(id.to_string()).or_default();
+        shift.0 += delta.0;
+        shift.1 += delta.1;
+    }
+
+    /// Scroll position and range of the `Overflow::Scroll` node with the given
+    /// ID, as of the last `sync_scroll_state`
+    pub fn scroll_metrics(&self, id: &str) -> Option<ScrollMetrics> {
+        self.scroll_metrics.get(id).copied()
+    }
+
+    /// Collect the IDs of nodes that are in `hits` or have an identified
+    /// descendant in `hits`, returning whether `node` is one of them
+    fn collect_within(node: &Node, hits: &[NodeId], within: &mut HashSet<NodeId>) -> bool {
+        let mut inside = node.id().is_some_and(|id| hits.contains(id));
+        for child in node.children() {
+            inside |= Self::collect_within(child, hits, within);
+        }
+        if inside {
+            if let Some(id) = node.id() {
+                within.insert(id.clone());
+            }
+        }
+        inside
+    }
+
+    /// Restore scroll state to nodes after UI rebuild, then apply requested
+    /// scroll targets
+    pub fn restore_scroll_state(&mut self, root: &mut Node) {
+        let mut requests = std::mem::take(&mut self.scroll_requests);
+        let mut shifts = std::mem::take(&mut self.scroll_shifts);
+        self.restore_scroll_state_recursive(root, &mut requests, &mut shifts);
     }
 
-    fn restore_scroll_state_recursive(&self, node: &mut Node) {
+    fn restore_scroll_state_recursive(
+        &mut self,
+        node: &mut Node,
+        requests: &mut HashMap<String, (f32, f32)>,
+        shifts: &mut HashMap<String, (f32, f32)>,
+    ) {
         // Check if this node has saved scroll state
-        if let Some(id) = node.id() {
-            if let Some(&(offset, target)) = self.scroll_state.get(id.as_str()) {
+        if let Some(id) = node.id().map(|id| id.as_str().to_string()) {
+            if let Some(&(offset, target, animation)) = self.scroll_state.get(&id) {
                 node.set_scroll_offset(offset);
                 node.set_scroll_target(target);
+                node.set_scroll_animation(animation);
+            }
+
+            let max_scroll = node
+                .computed_layout()
+                .map(|layout| layout.max_scroll)
+                .unwrap_or((0.0, 0.0));
+            let clamp =
+                |(x, y): (f32, f32)| (x.clamp(0.0, max_scroll.0), y.clamp(0.0, max_scroll.1));
+            let shift = shifts.remove(&id);
+            if let Some((dx, dy)) = shift {
+                let moved = |(x, y): (f32, f32)| clamp((x + dx, y + dy));
+                let mut animation = node.scroll_animation();
+                animation.from = moved(animation.from);
+                animation.to = moved(animation.to);
+                node.set_scroll_offset(moved(node.scroll_offset()));
+                node.set_scroll_target(moved(node.scroll_target()));
+                node.set_scroll_animation(animation);
+            }
+
+            let request = requests.remove(&id);
+            if let Some(target) = request {
+                node.set_scroll_animation(ScrollAnimation {
+                    velocity: (0.0, 0.0),
+                    ..node.scroll_animation()
+                });
+                node.set_scroll_target(clamp(target));
+                if matches!(node.scroll_smoothing(), ScrollSmoothing::Instant) {
+                    node.set_scroll_offset(node.scroll_target());
+                }
+            }
+
+            if shift.is_some() || request.is_some() {
+                self.scroll_state.insert(
+                    id,
+                    (
+                        node.scroll_offset(),
+                        node.scroll_target(),
+                        node.scroll_animation(),
+                    ),
+                );
             }
         }
 
         // Recursively restore for children
         for child in node.children_mut() {
-            self.restore_scroll_state_recursive(child);
+            self.restore_scroll_state_recursive(child, requests, shifts);
         }
     }
 
     /// Sync scroll state from nodes to internal storage
     pub fn sync_scroll_state(&mut self, root: &Node) {
+        self.scroll_metrics.clear();
         self.sync_scroll_state_recursive(root);
     }
 
+    /// Laid-out size of the node with the given ID, if it is marked with
+    /// `with_measure_size`, as of the last `sync_measured_sizes`
+    pub fn measured_size(&self, id: &str) -> Option<(f32, f32)> {
+        self.measured_sizes.get(id).copied()
+    }
+
+    /// Record the laid-out sizes of identified nodes marked with
+    /// `with_measure_size`
+    pub fn sync_measured_sizes(&mut self, root: &Node) {
+        self.measured_sizes.clear();
+        self.sync_measured_sizes_recursive(root);
+    }
+
+    fn sync_measured_sizes_recursive(&mut self, node: &Node) {
+        if node.measures_size() {
+            if let (Some(id), Some(layout)) = (node.id(), node.computed_layout()) {
+                self.measured_sizes.insert(
+                    id.as_str().to_string(),
+                    (layout.rect.width(), layout.rect.height()),
+                );
+            }
+        }
+        for child in node.children() {
+            self.sync_measured_sizes_recursive(child);
+        }
+    }
+
     fn sync_scroll_state_recursive(&mut self, node: &Node) {
         // Save scroll state if node has an ID and non-zero scroll
         if let Some(id) = node.id() {
@@ -485,8 +1655,24 @@ impl EventDispatcher {
             let target = node.scroll_target();
 
             if offset != (0.0, 0.0) || target != (0.0, 0.0) {
-                self.scroll_state
-                    .insert(id.as_str().to_string(), (offset, target));
+                self.scroll_state.insert(
+                    id.as_str().to_string(),
+                    (offset, target, node.scroll_animation()),
+                );
+            }
+
+            if node.overflow() == Overflow::Scroll {
+                if let Some(layout) = node.computed_layout() {
+                    self.scroll_metrics.insert(
+                        id.as_str().to_string(),
+                        ScrollMetrics {
+                            offset,
+                            target,
+                            max_scroll: layout.max_scroll,
+                            viewport: (layout.rect.width(), layout.rect.height()),
+                        },
+                    );
+                }
             }
         }
 
@@ -496,87 +1682,469 @@ impl EventDispatcher {
         }
     }
 
+    /// Scroll the containers under the cursor by this frame's scroll delta
+    ///
+    /// Each axis goes to the innermost `Overflow::Scroll` container that can
+    /// scroll along it, so a sideways swipe over a wide table scrolls the
+    /// table while a vertical one still scrolls the page around it. Mice
+    /// usually only report a vertical delta, so:
+    /// - Holding Shift routes it to horizontal scrolling.
+    /// - If nothing under the cursor scrolls vertically, a wheel's vertical
+    ///   delta scrolls horizontally instead.
+    ///
+    /// Wheel deltas are scaled by the container's scroll speed and eased by
+    /// its smoothing; precise (trackpad) deltas move it one to one at once.
     fn process_scroll_event(
         &mut self,
         root: &mut Node,
         position: Point,
-        delta: (f32, f32),
-        shift_held: bool,
+        input: &InputState,
         events: &mut Vec<TargetedEvent>,
     ) {
-        // Find scrollable nodes under cursor
+        let delta = input.scroll_delta;
         let hits = hit_test_point(root, position);
 
-        // Find the first scrollable node in the hit chain
-        for hit in &hits {
-            if let Some(ref node_id) = hit.node_id {
-                // Find the node and check if it's scrollable
-                if let Some(node) = self.find_node_by_id_mut(root, node_id) {
-                    // A node is scrollable if it has Scroll overflow
-                    let is_scrollable = node.overflow() == Overflow::Scroll;
-
-                    if is_scrollable {
-                        // max_scroll is cached on the computed layout during the
-                        // layout pass; it also tells us which axes can scroll.
-                        let max_scroll = node
-                            .computed_layout()
-                            .map(|layout| layout.max_scroll)
-                            .unwrap_or((0.0, 0.0));
-                        let can_x = max_scroll.0 > 0.0;
-                        let can_y = max_scroll.1 > 0.0;
-
-                        // Per-container speed multiplier (tunable via
-                        // `Node::with_scroll_speed`).
-                        let speed = node.scroll_speed();
-                        let scaled = (delta.0 * speed, delta.1 * speed);
-
-                        // Map the wheel delta onto the scroll axes. Mice usually
-                        // only report a vertical delta, so:
-                        // - Holding Shift routes it to horizontal scrolling.
-                        // - A container that can only scroll horizontally routes
-                        //   the vertical wheel to X automatically.
-                        // Any native horizontal delta is always applied to X.
-                        let mut dx = -scaled.0;
-                        let mut dy = -scaled.1;
-                        if shift_held || (!can_y && can_x) {
-                            dx += -scaled.1;
-                            dy = 0.0;
-                        }
+        // Scroll containers under the cursor, innermost first, with their
+        // scrollable range. max_scroll is cached on the computed layout during
+        // the layout pass; it also tells us which axes can scroll.
+        let mut containers = Vec::new();
+        for hit in hits.iter().rev() {
+            let Some(node_id) = &hit.node_id else {
+                continue;
+            };
+            if let Some(node) = self.find_node_by_id_mut(root, node_id) {
+                if node.overflow() == Overflow::Scroll {
+                    let max_scroll = node
+                        .computed_layout()
+                        .map(|layout| layout.max_scroll)
+                        .unwrap_or((0.0, 0.0));
+                    containers.push((hit, max_scroll));
+                }
+            }
+        }
+        let can_x = containers.iter().position(|(_, max)| max.0 > 0.0);
+        let can_y = containers.iter().position(|(_, max)| max.1 > 0.0);
+
+        // Scroll offsets grow opposite to the wheel
+        let mut dx = -delta.0;
+        let mut dy = -delta.1;
+        if input.shift_held || (!input.scroll_precise && can_y.is_none() && can_x.is_some()) {
+            dx += dy;
+            dy = 0.0;
+        }
 
-                        // Apply scroll to this node
-                        node.scroll_by((dx, dy));
+        for (index, (hit, max_scroll)) in containers.iter().enumerate() {
+            let axis_delta = (
+                if can_x == Some(index) { dx } else { 0.0 },
+                if can_y == Some(index) { dy } else { 0.0 },
+            );
+            if axis_delta == (0.0, 0.0) {
+                continue;
+            }
+            let node_id = hit.node_id.as_ref().unwrap();
+            let Some(node) = self.find_node_by_id_mut(root, node_id) else {
+                continue;
+            };
+
+            // Per-container speed multiplier (tunable via `Node::with_scroll_speed`)
+            let speed = if input.scroll_precise {
+                1.0
+            } else {
+                node.scroll_speed()
+            };
+            node.scroll_by((axis_delta.0 * speed, axis_delta.1 * speed));
+
+            // Precise deltas feed the flick speed for momentum; wheel steps stop it
+            let mut animation = node.scroll_animation();
+            if input.scroll_precise {
+                animation.flick_input.0 += axis_delta.0;
+                animation.flick_input.1 += axis_delta.1;
+            } else {
+                animation.velocity = (0.0, 0.0);
+            }
+            node.set_scroll_animation(animation);
 
-                        // Clamp the scroll target to the scrollable range so the
-                        // content can't be scrolled past its bounds.
-                        let target = node.scroll_target();
-                        node.set_scroll_target((
-                            target.0.clamp(0.0, max_scroll.0),
-                            target.1.clamp(0.0, max_scroll.1),
-                        ));
+            // Clamp the scroll target to the scrollable range so the
+            // content can't be scrolled past its bounds.
+            let target = node.scroll_target();
+            node.set_scroll_target((
+                target.0.clamp(0.0, max_scroll.0),
+                target.1.clamp(0.0, max_scroll.1),
+            ));
+
+            // Instant containers and precise deltas jump within the same frame
+            if input.scroll_precise || matches!(node.scroll_smoothing(), ScrollSmoothing::Instant) {
+                node.set_scroll_offset(node.scroll_target());
+            }
 
-                        // Generate scroll event
-                        events.push(TargetedEvent {
-                            event: InteractionEvent::Scroll { delta, position },
-                            target: node_id.clone(),
-                            local_position: hit.local_pos,
-                            zoom: hit.zoom,
-                        });
+            events.push(TargetedEvent {
+                event: InteractionEvent::Scroll { delta, position },
+                target: node_id.clone(),
+                local_position: hit.local_pos,
+                zoom: hit.zoom,
+            });
+
+            // Save scroll state
+            self.scroll_state.insert(
+                node_id.as_str().to_string(),
+                (
+                    node.scroll_offset(),
+                    node.scroll_target(),
+                    node.scroll_animation(),
+                ),
+            );
+        }
+    }
+
+    /// Send drag-and-drop events to the topmost drop target under the pointer
+    /// while the drag carries a payload, handing the payload over on release
+    fn process_drop_targets(
+        &mut self,
+        hits: &[HitTestResult],
+        cursor_pos: Point,
+        input: &InputState,
+        events: &mut Vec<TargetedEvent>,
+    ) {
+        let Some(drag) = &self.drag_state else {
+            self.drag_payload = None;
+            return;
+        };
+        if self.drag_payload.is_none() {
+            return;
+        }
 
-                        // Save scroll state
-                        self.scroll_state.insert(
-                            node_id.as_str().to_string(),
-                            (node.scroll_offset(), node.scroll_target()),
-                        );
+        let hit = hits.iter().rfind(|h| h.drop_target && h.node_id.is_some());
+        let target = hit.and_then(|h| h.node_id.clone());
+        let event = |event, hit: &HitTestResult| TargetedEvent {
+            event,
+            target: hit.node_id.clone().unwrap(),
+            local_position: hit.local_pos,
+            zoom: hit.zoom,
+        };
 
-                        // Only scroll the first scrollable ancestor
-                        break;
-                    }
+        if target != self.drop_target {
+            if let Some(old) = self.drop_target.take() {
+                events.push(TargetedEvent {
+                    event: InteractionEvent::DragLeave,
+                    target: old,
+                    local_position: Point::zero(),
+                    zoom: 1.0,
+                });
+            }
+            if let Some(hit) = hit {
+                events.push(event(
+                    InteractionEvent::DragEnter {
+                        position: cursor_pos,
+                    },
+                    hit,
+                ));
+            }
+            self.drop_target = target;
+        }
+
+        if input.is_button_just_released(drag.button) {
+            let payload = self.drag_payload.take();
+            if let (Some(hit), Some(payload)) = (hit, payload) {
+                events.push(event(
+                    InteractionEvent::Drop {
+                        position: cursor_pos,
+                    },
+                    hit,
+                ));
+                self.dropped = hit.node_id.clone().map(|id| (id, payload));
+            }
+            self.drop_target = None;
+        } else if let Some(hit) = hit {
+            events.push(event(
+                InteractionEvent::DragOver {
+                    position: cursor_pos,
+                },
+                hit,
+            ));
+        }
+    }
+
+    /// Send `PointerLeave` to the previously hovered nodes that are no longer
+    /// hovered, innermost first
+    fn push_pointer_leaves(
+        previous: &[NodeId],
+        current: &[NodeId],
+        events: &mut Vec<TargetedEvent>,
+    ) {
+        for node_id in previous.iter().rev() {
+            if !current.contains(node_id) {
+                events.push(TargetedEvent {
+                    event: InteractionEvent::PointerLeave,
+                    target: node_id.clone(),
+                    local_position: Point::zero(),
+                    zoom: 1.0,
+                });
+            }
+        }
+    }
+
+    /// Send `PointerMove` and `PointerUp` events to the node holding pointer
+    /// capture wherever the pointer is, releasing the capture once every
+    /// button is up
+    fn process_pointer_capture(
+        &mut self,
+        hits: &[HitTestResult],
+        cursor_pos: Option<Point>,
+        input: &InputState,
+        events: &mut Vec<TargetedEvent>,
+    ) {
+        let Some(capture) = &mut self.capture else {
+            return;
+        };
+
+        // Keep the node's origin from the frames it is under the pointer, so
+        // positions stay local to it after the pointer leaves
+        if let (Some(pos), Some(hit)) = (
+            cursor_pos,
+            hits.iter()
+                .find(|h| h.node_id.as_ref() == Some(&capture.target)),
+        ) {
+            capture.node_origin = Some(Point {
+                x: pos.x - hit.local_pos.x,
+                y: pos.y - hit.local_pos.y,
+            });
+            capture.zoom = hit.zoom;
+        }
+
+        let Some(position) = cursor_pos.or(capture.last_pos) else {
+            return;
+        };
+        let delta = capture.last_pos.map(|last| Point {
+            x: position.x - last.x,
+            y: position.y - last.y,
+        });
+        capture.last_pos = Some(position);
+
+        let origin = capture.node_origin.unwrap_or(position);
+        let mut push = |event| {
+            events.push(TargetedEvent {
+                event,
+                target: capture.target.clone(),
+                local_position: Point {
+                    x: position.x - origin.x,
+                    y: position.y - origin.y,
+                },
+                zoom: capture.zoom,
+            })
+        };
+        if let Some(delta) = delta.filter(|d| d.x.abs() > 0.001 || d.y.abs() > 0.001) {
+            push(InteractionEvent::PointerMove { position, delta });
+        }
+        for button in [MouseButton::Left, MouseButton::Right, MouseButton::Middle] {
+            if input.is_button_just_released(button) {
+                push(InteractionEvent::PointerUp { button, position });
+            }
+        }
+
+        // Capture ends with the last button released
+        let released = !input.buttons_just_released.is_empty() && input.buttons_pressed.is_empty();
+        if released {
+            push(InteractionEvent::PointerCaptureLost);
+            self.capture = None;
+        }
+    }
+
+    /// Track how long the cursor has been on each identified node, sending
+    /// `HoverStart` once it has rested for the hover-intent delay, then
+    /// `HoverHeld` every frame and `HoverEnd` when it leaves
+    fn process_hover_intents(
+        &mut self,
+        hits: &[HitTestResult],
+        cursor_pos: Option<Point>,
+        events: &mut Vec<TargetedEvent>,
+    ) {
+        let now = Instant::now();
+
+        // Forget nodes that are no longer hovered, ending started hovers
+        self.hover_intents.retain(|id, state| {
+            let still_hovered = hits.iter().any(|h| h.node_id.as_ref() == Some(id));
+            if !still_hovered && state.started {
+                events.push(TargetedEvent {
+                    event: InteractionEvent::HoverEnd,
+                    target: id.clone(),
+                    local_position: Point::zero(),
+                    zoom: 1.0,
+                });
+            }
+            still_hovered
+        });
+
+        let Some(position) = cursor_pos else {
+            return;
+        };
+        for hit in hits {
+            let Some(node_id) = &hit.node_id else {
+                continue;
+            };
+            let state = self
+                .hover_intents
+                .entry(node_id.clone())
+                .or_insert(HoverIntentState {
+                    since: now,
+                    started: false,
+                });
+            let elapsed = now.duration_since(state.since);
+            if elapsed < self.hover_intent_delay {
+                continue;
+            }
+
+            let mut push = |event| {
+                events.push(TargetedEvent {
+                    event,
+                    target: node_id.clone(),
+                    local_position: hit.local_pos,
+                    zoom: hit.zoom,
+                })
+            };
+            if !state.started {
+                state.started = true;
+                push(InteractionEvent::HoverStart { position });
+            }
+            push(InteractionEvent::HoverHeld { position, elapsed });
+        }
+    }
+
+    /// Track the button pressed on the topmost identified node, sending
+    /// `LongPress` once it has been held long enough
+    ///
+    /// Releasing the button or moving the pointer farther than
+    /// `LONG_PRESS_DISTANCE` from where it was pressed cancels the press.
+    fn process_long_press(
+        &mut self,
+        hits: &[HitTestResult],
+        cursor_pos: Point,
+        input: &InputState,
+        events: &mut Vec<TargetedEvent>,
+    ) {
+        let now = Instant::now();
+        let pressed = [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+            .into_iter()
+            .find(|&b| input.is_button_just_pressed(b));
+        if let Some(button) = pressed {
+            self.press = hits
+                .iter()
+                .rfind(|h| h.node_id.is_some())
+                .map(|hit| PressState {
+                    target: hit.node_id.clone().unwrap(),
+                    button,
+                    time: now,
+                    position: cursor_pos,
+                    node_origin: Point {
+                        x: cursor_pos.x - hit.local_pos.x,
+                        y: cursor_pos.y - hit.local_pos.y,
+                    },
+                    zoom: hit.zoom,
+                    fired: false,
+                });
+        }
+
+        let Some(press) = &mut self.press else {
+            return;
+        };
+        let moved = (cursor_pos.x - press.position.x).hypot(cursor_pos.y - press.position.y);
+        if !input.is_button_down(press.button) || moved > LONG_PRESS_DISTANCE {
+            self.press = None;
+            return;
+        }
+
+        if !press.fired && now.duration_since(press.time) >= self.long_press_duration {
+            press.fired = true;
+            events.push(TargetedEvent {
+                event: InteractionEvent::LongPress {
+                    button: press.button,
+                    position: cursor_pos,
+                },
+                target: press.target.clone(),
+                local_position: Point {
+                    x: cursor_pos.x - press.node_origin.x,
+                    y: cursor_pos.y - press.node_origin.y,
+                },
+                zoom: press.zoom,
+            });
+        }
+    }
+
+    fn process_peeks(
+        &mut self,
+        hits: &[HitTestResult],
+        cursor_pos: Option<Point>,
+        input: &InputState,
+        events: &mut Vec<TargetedEvent>,
+    ) {
+        let now = Instant::now();
+
+        // Forget anchors that are no longer hovered, dismissing any open peeks
+        self.peek_states.retain(|id, state| {
+            let still_hovered = hits
+                .iter()
+                .any(|h| h.peek_delay.is_some() && h.node_id.as_ref() == Some(id));
+            if !still_hovered && state.open {
+                events.push(TargetedEvent {
+                    event: InteractionEvent::PeekDismissed,
+                    target: id.clone(),
+                    local_position: Point::zero(),
+                    zoom: 1.0,
+                });
+            }
+            still_hovered
+        });
+
+        let Some(position) = cursor_pos else {
+            return;
+        };
+        let button_pressed = [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+            .into_iter()
+            .any(|b| input.is_button_just_pressed(b));
+
+        for hit in hits {
+            let (Some(node_id), Some(delay)) = (&hit.node_id, hit.peek_delay) else {
+                continue;
+            };
+
+            let state = self
+                .peek_states
+                .entry(node_id.clone())
+                .or_insert(PeekState {
+                    hover_start: now,
+                    anchor_rect: hit.node_rect,
+                    open: false,
+                    suppressed: false,
+                });
+            state.anchor_rect = hit.node_rect;
+
+            // Pressing a button dismisses the peek until the anchor is re-hovered
+            if button_pressed {
+                if state.open {
+                    events.push(TargetedEvent {
+                        event: InteractionEvent::PeekDismissed,
+                        target: node_id.clone(),
+                        local_position: hit.local_pos,
+                        zoom: hit.zoom,
+                    });
                 }
+                state.open = false;
+                state.suppressed = true;
+                continue;
+            }
+
+            if !state.open && !state.suppressed && now - state.hover_start >= delay {
+                state.open = true;
+                events.push(TargetedEvent {
+                    event: InteractionEvent::PeekRequested { position },
+                    target: node_id.clone(),
+                    local_position: hit.local_pos,
+                    zoom: hit.zoom,
+                });
             }
         }
     }
 
-    fn find_node_by_id_mut<'a>(
+    pub(crate) fn find_node_by_id_mut<'a>(
         &self,
         node: &'a mut Node,
         target_id: &NodeId,
@@ -0,0 +1,118 @@
+//! Color filters for node subtrees
+//!
+//! `Node::with_filter` applies a color filter to a node and all its children,
+//! like CSS `filter: grayscale(80%)`. Typical uses are greying out a disabled
+//! panel or dimming the background behind a modal.
+//!
+//! The supported filters are 3x3 color matrices. While collecting shapes,
+//! each shape of a filtered subtree gets the filters of its node and
+//! ancestors, composed, in `ClippedShape::filter`. Backends draw consecutive
+//! shapes with the same filter into an offscreen layer and composite that
+//! through the filter, so textures and color glyphs (emoji) are filtered like
+//! everything else, and overlapping translucent shapes blend before
+//! filtering, as in CSS.
+
+use crate::color::Color;
+
+/// Relative luminance coefficients of linear RGB (same as `Color::luminance`)
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Color filter applied to a node and its children
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Filter {
+    /// Desaturate towards gray (0.0 = unchanged, 1.0 = fully gray)
+    Grayscale(f32),
+    /// Scale all channels (0.0 = black, 1.0 = unchanged, above 1.0 = brighter)
+    Brightness(f32),
+    /// Scale saturation (0.0 = gray, 1.0 = unchanged, above 1.0 = more vivid)
+    Saturate(f32),
+    /// Arbitrary color matrix
+    Matrix(ColorMatrix),
+}
+
+impl Filter {
+    /// The color matrix this filter applies
+    pub fn matrix(&self) -> ColorMatrix {
+        match *self {
+            Filter::Grayscale(amount) => saturation_matrix(1.0 - amount.clamp(0.0, 1.0)),
+            Filter::Brightness(amount) => {
+                let b = amount.max(0.0);
+                ColorMatrix([[b, 0.0, 0.0], [0.0, b, 0.0], [0.0, 0.0, b]])
+            }
+            Filter::Saturate(amount) => saturation_matrix(amount.max(0.0)),
+            Filter::Matrix(matrix) => matrix,
+        }
+    }
+}
+
+/// Row-major 3x3 matrix applied to the linear RGB channels of a color
+///
+/// Alpha is left unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorMatrix(pub [[f32; 3]; 3]);
+
+impl ColorMatrix {
+    pub const IDENTITY: Self = Self([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    /// Apply the matrix to a color, clamping the result to [0, 1]
+    pub fn apply(&self, color: Color) -> Color {
+        let [r, g, b] = self
+            .0
+            .map(|row| (row[0] * color.r + row[1] * color.g + row[2] * color.b).clamp(0.0, 1.0));
+        Color::rgba(r, g, b, color.a)
+    }
+
+    /// Matrix applying `self` first and then `outer`
+    pub fn then(&self, outer: &ColorMatrix) -> ColorMatrix {
+        let (a, b) = (&outer.0, &self.0);
+        ColorMatrix(std::array::from_fn(|row| {
+            std::array::from_fn(|col| (0..3).map(|k| a[row][k] * b[k][col]).sum())
+        }))
+    }
+}
+
+impl Default for ColorMatrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Saturation matrix (filter effects spec, with linear RGB luminance weights)
+fn saturation_matrix(s: f32) -> ColorMatrix {
+    ColorMatrix(std::array::from_fn(|row| {
+        std::array::from_fn(|col| {
+            let diagonal = if row == col { 1.0 } else { 0.0 };
+            LUMA[col] + (diagonal - LUMA[col]) * s
+        })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grayscale_keeps_luminance() {
+        let color = Color::rgba(0.8, 0.2, 0.1, 0.5);
+        let gray = Filter::Grayscale(1.0).matrix().apply(color);
+
+        assert!((gray.r - color.luminance()).abs() < 1e-5);
+        assert!((gray.r - gray.g).abs() < 1e-5 && (gray.g - gray.b).abs() < 1e-5);
+        assert_eq!(gray.a, 0.5);
+        let unchanged = Filter::Grayscale(0.0).matrix().apply(color);
+        assert!((unchanged.r - color.r).abs() < 1e-5 && (unchanged.b - color.b).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_filters_compose_inner_first() {
+        let color = Color::rgb(0.2, 0.4, 0.6);
+        let dim = Filter::Brightness(0.5).matrix();
+        let gray = Filter::Grayscale(1.0).matrix();
+
+        let composed = gray.then(&dim).apply(color);
+        let sequential = dim.apply(gray.apply(color));
+        assert!((composed.r - sequential.r).abs() < 1e-5);
+        assert!((composed.b - sequential.b).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,162 @@
+//! Interaction heatmap for UX debugging
+//!
+//! Accumulates cursor and click positions over a session into a coarse grid
+//! and turns them into a translucent overlay. Clicks that land where no node
+//! with an ID exists are tracked separately as "dead clicks", which helps
+//! finding places where users expect something to be interactive.
+
+use crate::color::Color;
+use crate::layout::{Size, Transform2D, ZIndex};
+use crate::primitives::{ClippedShape, Point, Rect, Shape, Stroke, StyledRect};
+use std::collections::HashMap;
+
+/// Accumulated samples for a single heatmap cell
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeatCell {
+    /// Number of frames the cursor spent over this cell
+    pub hovers: u32,
+    /// Number of clicks that hit a node with an ID
+    pub clicks: u32,
+    /// Number of clicks that hit no node with an ID
+    pub dead_clicks: u32,
+}
+
+/// Session-long accumulation of hover and click positions
+///
+/// Recording is driven by `UiContext` when enabled with
+/// `UiContext::set_heatmap_recording`. Render it with
+/// `FullOutput::add_heatmap_overlay` when `DebugOptions::show_heatmap` is set.
+#[derive(Debug, Clone)]
+pub struct InteractionHeatmap {
+    /// Size of a grid cell in physical pixels
+    cell_size: f32,
+    /// Samples per grid cell
+    cells: HashMap<(i32, i32), HeatCell>,
+}
+
+impl InteractionHeatmap {
+    /// Create an empty heatmap with the given cell size in physical pixels
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Get the cell size in physical pixels
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Remove all recorded samples
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Check whether nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn cell_key(&self, position: Point) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Record a frame of the cursor resting at `position`
+    pub fn record_hover(&mut self, position: Point) {
+        let key = self.cell_key(position);
+        self.cells.entry(key).or_default().hovers += 1;
+    }
+
+    /// Record a click at `position`
+    ///
+    /// `hit` tells whether the click landed on a node with an ID.
+    pub fn record_click(&mut self, position: Point, hit: bool) {
+        let key = self.cell_key(position);
+        let cell = self.cells.entry(key).or_default();
+        if hit {
+            cell.clicks += 1;
+        } else {
+            cell.dead_clicks += 1;
+        }
+    }
+
+    /// Get the samples recorded for the cell containing `position`
+    pub fn cell_at(&self, position: Point) -> HeatCell {
+        self.cells
+            .get(&self.cell_key(position))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Iterate over all cells with their screen rects
+    pub fn cells(&self) -> impl Iterator<Item = (Rect, &HeatCell)> {
+        self.cells.iter().map(move |(&(x, y), cell)| {
+            let min = [x as f32 * self.cell_size, y as f32 * self.cell_size];
+            (
+                Rect::from_min_size(min, [self.cell_size, self.cell_size]),
+                cell,
+            )
+        })
+    }
+
+    /// Build overlay shapes for the heatmap, clipped to `window_rect`
+    ///
+    /// Hover density is drawn as a blue-to-yellow fill, clicks as an orange
+    /// fill and dead clicks as a red outline. All shapes render on top of the UI.
+    pub fn overlay_shapes(&self, window_rect: Rect) -> Vec<ClippedShape> {
+        let max_hovers = self.cells.values().map(|c| c.hovers).max().unwrap_or(0);
+        let max_clicks = self
+            .cells
+            .values()
+            .map(|c| c.clicks.max(c.dead_clicks))
+            .max()
+            .unwrap_or(0);
+
+        let mut shapes = Vec::new();
+        let mut push = |rect: Rect, styled: StyledRect| {
+            let mut clipped = ClippedShape::with_transform(
+                window_rect,
+                rect,
+                Shape::Rect(styled),
+                Transform2D::IDENTITY,
+            );
+            clipped.z_index = ZIndex(i32::MAX);
+            shapes.push(clipped);
+        };
+
+        for (rect, cell) in self.cells() {
+            if cell.hovers > 0 && max_hovers > 0 {
+                let t = cell.hovers as f32 / max_hovers as f32;
+                let color = Color::rgba(t, t, 1.0 - t, 0.1 + 0.4 * t);
+                push(rect, StyledRect::new(rect, color));
+            }
+            if cell.clicks > 0 {
+                let t = cell.clicks as f32 / max_clicks as f32;
+                push(
+                    rect,
+                    StyledRect::new(rect, Color::rgba(1.0, 0.5, 0.0, 0.3 + 0.5 * t)),
+                );
+            }
+            if cell.dead_clicks > 0 {
+                let t = cell.dead_clicks as f32 / max_clicks as f32;
+                push(
+                    rect,
+                    StyledRect::new(rect, Color::rgba(1.0, 0.0, 0.0, 0.2 + 0.4 * t))
+                        .with_stroke(Stroke::new(Size::ppx(2.0), Color::rgba(1.0, 0.0, 0.0, 1.0))),
+                );
+            }
+        }
+
+        shapes
+    }
+}
+
+impl Default for InteractionHeatmap {
+    fn default() -> Self {
+        Self::new(16.0)
+    }
+}
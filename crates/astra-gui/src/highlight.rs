@@ -0,0 +1,171 @@
+//! Syntax highlighting for [`Content::Code`](crate::Content::Code)
+//!
+//! A [`Highlighter`] turns source code into colored [`TextSpan`]s. Any
+//! tokenizer can be plugged in; the `syntect` feature provides
+//! [`SyntectHighlighter`] with syntect's bundled grammars and themes.
+
+use crate::content::TextSpan;
+
+/// Splits source code into styled spans
+pub trait Highlighter {
+    /// Highlight `code` written in `language` (a name or file extension)
+    ///
+    /// The span texts must concatenate to exactly `code`; unknown languages
+    /// should return the code as a single plain span.
+    fn highlight(&self, code: &str, language: &str) -> Vec<TextSpan>;
+}
+
+impl<F> Highlighter for F
+where
+    F: Fn(&str, &str) -> Vec<TextSpan>,
+{
+    fn highlight(&self, code: &str, language: &str) -> Vec<TextSpan> {
+        self(code, language)
+    }
+}
+
+/// Highlighter backed by syntect's bundled grammars and themes
+#[cfg(feature = "syntect")]
+pub struct SyntectHighlighter {
+    syntaxes: syntect::parsing::SyntaxSet,
+    theme: syntect::highlighting::Theme,
+}
+
+#[cfg(feature = "syntect")]
+impl SyntectHighlighter {
+    /// Theme used by [`SyntectHighlighter::new`]
+    pub const DEFAULT_THEME: &'static str = "base16-ocean.dark";
+
+    /// Load the bundled grammars with the [`Self::DEFAULT_THEME`] colors
+    pub fn new() -> Self {
+        Self {
+            syntaxes: syntect::parsing::SyntaxSet::load_defaults_newlines(),
+            theme: Self::bundled_theme(Self::DEFAULT_THEME)
+                .expect("default theme is bundled with syntect"),
+        }
+    }
+
+    /// Names of the themes bundled with syntect (e.g. `"InspiredGitHub"`,
+    /// `"Solarized (dark)"`)
+    pub fn theme_names() -> Vec<String> {
+        syntect::highlighting::ThemeSet::load_defaults()
+            .themes
+            .into_keys()
+            .collect()
+    }
+
+    /// Use a bundled theme by name; unknown names keep the current theme
+    pub fn with_theme(mut self, name: &str) -> Self {
+        if let Some(theme) = Self::bundled_theme(name) {
+            self.theme = theme;
+        }
+        self
+    }
+
+    /// Use a theme loaded with syntect (e.g. from a `.tmTheme` file)
+    pub fn with_custom_theme(mut self, theme: syntect::highlighting::Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn bundled_theme(name: &str) -> Option<syntect::highlighting::Theme> {
+        syntect::highlighting::ThemeSet::load_defaults()
+            .themes
+            .remove(name)
+    }
+}
+
+#[cfg(feature = "syntect")]
+impl Default for SyntectHighlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "syntect")]
+impl Highlighter for SyntectHighlighter {
+    fn highlight(&self, code: &str, language: &str) -> Vec<TextSpan> {
+        use syntect::highlighting::FontStyle as SyntectStyle;
+
+        let syntax = self
+            .syntaxes
+            .find_syntax_by_token(language)
+            .unwrap_or_else(|| self.syntaxes.find_syntax_plain_text());
+        let mut lines = syntect::easy::HighlightLines::new(syntax, &self.theme);
+
+        let mut spans = Vec::new();
+        for line in syntect::util::LinesWithEndings::from(code) {
+            let Ok(ranges) = lines.highlight_line(line, &self.syntaxes) else {
+                return vec![TextSpan::new(code)];
+            };
+            for (style, text) in ranges {
+                let color = style.foreground;
+                let mut span = TextSpan::new(text)
+                    .with_color(crate::Color::srgba(color.r, color.g, color.b, color.a));
+                if style.font_style.contains(SyntectStyle::BOLD) {
+                    span = span.bold();
+                }
+                if style.font_style.contains(SyntectStyle::ITALIC) {
+                    span = span.italic();
+                }
+                if style.font_style.contains(SyntectStyle::UNDERLINE) {
+                    span = span.underline();
+                }
+                spans.push(span);
+            }
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CodeContent, Color};
+
+    /// Colors every `fn` keyword, leaves the rest plain
+    fn keywords(code: &str, _language: &str) -> Vec<TextSpan> {
+        let mut spans = Vec::new();
+        let mut rest = code;
+        while let Some(index) = rest.find("fn") {
+            if index > 0 {
+                spans.push(TextSpan::new(&rest[..index]));
+            }
+            spans.push(TextSpan::new("fn").with_color(Color::rgb(1.0, 0.0, 0.0)));
+            rest = &rest[index + 2..];
+        }
+        if !rest.is_empty() {
+            spans.push(TextSpan::new(rest));
+        }
+        spans
+    }
+
+    #[test]
+    fn test_highlighted_code_keeps_text_and_gets_spans() {
+        let code = CodeContent::new("fn main() {}", "rust").highlighted(&keywords);
+
+        assert_eq!(code.code(), "fn main() {}");
+        assert_eq!(code.text.spans.len(), 2);
+        assert_eq!(code.text.spans[0].color, Some(Color::rgb(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_spans_not_matching_the_code_are_ignored() {
+        let wrong = |_: &str, _: &str| vec![TextSpan::new("something else")];
+        let code = CodeContent::new("fn main() {}", "rust").highlighted(&wrong);
+
+        assert!(code.text.spans.is_empty());
+        assert_eq!(code.code(), "fn main() {}");
+    }
+
+    #[cfg(feature = "syntect")]
+    #[test]
+    fn test_syntect_highlights_rust() {
+        let source = "fn main() {\n    let x = 1;\n}\n";
+        let code = CodeContent::new(source, "rs").highlighted(&SyntectHighlighter::new());
+
+        assert!(code.text.spans.len() > 3);
+        let colors: Vec<_> = code.text.spans.iter().map(|span| span.color).collect();
+        assert!(colors.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
@@ -20,13 +20,28 @@ pub struct HitTestResult {
     pub zoom: f32,
     /// The z-index of the node for layering
     pub z_index: crate::layout::ZIndex,
+    /// Hover delay before a peek is requested, if the node is a peek anchor
+    pub peek_delay: Option<std::time::Duration>,
+    /// Cursor icon requested by the node, if any
+    pub cursor: Option<crate::CursorIcon>,
+    /// Whether the node blocks input to everything drawn below it
+    pub input_barrier: bool,
+    /// Whether the node can take keyboard focus
+    pub focusable: bool,
+    /// Whether drags carrying a payload can be dropped on the node
+    pub drop_target: bool,
+    /// The innermost focus scope containing the node, itself included
+    pub focus_scope: Option<NodeId>,
+    /// What pressing the node does to the window
+    pub window_action: Option<crate::WindowAction>,
 }
 
 /// Hit-test a point against a node tree
 ///
 /// Returns all nodes that contain the point, ordered from root to leaf (shallow to deep).
 /// This respects overflow clipping and transforms - nodes outside their parent's clip rect
-/// or transformed space are excluded.
+/// or transformed space are excluded, as are nodes drawn below an input barrier
+/// (see `Node::with_input_barrier`).
 ///
 /// # Arguments
 /// * `root` - The root node to test against
@@ -56,19 +71,25 @@ pub fn hit_test_point(root: &Node, point: Point) -> Vec<HitTestResult> {
 
     let mut results = Vec::new();
     let initial_zoom = root.zoom().unwrap_or(1.0);
-    hit_test_recursive(
-        root,
-        point,
-        None,
-        initial_transform,
-        initial_zoom,
-        &mut results,
-    );
+    let inherited = Inherited {
+        clip_rect: None,
+        transform: initial_transform,
+        zoom: initial_zoom,
+        z_index: crate::layout::ZIndex::DEFAULT,
+        scope: None,
+    };
+    hit_test_recursive(root, point, inherited, &mut results);
 
     // Sort results by z-index to ensure higher z-index nodes come later
     // This makes them the "deepest" target for event dispatch
     results.sort_by_key(|hit| hit.z_index.0);
 
+    // Results are now in drawing order, so everything before the topmost
+    // barrier is covered by it
+    if let Some(barrier) = results.iter().rposition(|hit| hit.input_barrier) {
+        results.drain(..barrier);
+    }
+
     results
 }
 
@@ -87,23 +108,42 @@ pub fn hit_test_deepest(root: &Node, point: Point) -> Option<HitTestResult> {
     hit_test_point(root, point).pop()
 }
 
+/// State a node inherits from its ancestors during hit testing
+#[derive(Clone, Copy)]
+struct Inherited<'a> {
+    /// The current clipping rectangle (None means no clipping)
+    clip_rect: Option<Rect>,
+    /// Accumulated transform from parent nodes
+    transform: Transform2D,
+    /// Accumulated zoom from parent nodes
+    zoom: f32,
+    /// Z-index inherited from parent nodes
+    z_index: crate::layout::ZIndex,
+    /// The innermost focus scope around the node
+    scope: Option<&'a NodeId>,
+}
+
 /// Recursive helper for hit testing
 ///
 /// # Arguments
 /// * `node` - Current node being tested
 /// * `point` - The point in world screen coordinates
-/// * `clip_rect` - The current clipping rectangle (None means no clipping)
-/// * `parent_transform` - Accumulated transform from parent nodes
-/// * `parent_zoom` - Accumulated zoom from parent nodes
+/// * `parent` - State inherited from the parent nodes
 /// * `results` - Accumulator for hit test results
-fn hit_test_recursive(
-    node: &Node,
+fn hit_test_recursive<'a>(
+    node: &'a Node,
     point: Point,
-    clip_rect: Option<Rect>,
-    parent_transform: Transform2D,
-    parent_zoom: f32,
+    parent: Inherited<'a>,
     results: &mut Vec<HitTestResult>,
 ) {
+    let Inherited {
+        clip_rect,
+        transform: parent_transform,
+        zoom: parent_zoom,
+        z_index: parent_z_index,
+        scope: parent_scope,
+    } = parent;
+
     // Get the computed layout for this node (untransformed rect)
     let Some(computed) = node.computed_layout() else {
         return; // Node hasn't been laid out yet, skip it
@@ -120,6 +160,15 @@ fn hit_test_recursive(
     // Calculate accumulated zoom for this node
     let current_zoom = node.zoom().unwrap_or(parent_zoom);
 
+    // Z-index is inherited like in rendering, so hits sort in drawing order
+    let current_z_index = node.z_index().unwrap_or(parent_z_index);
+
+    let current_scope = if node.is_focus_scope() {
+        node.id()
+    } else {
+        parent_scope
+    };
+
     // Build local transform from node properties
     // Use current_zoom when resolving translations so they match the layout
     let local_transform = Transform2D {
@@ -157,14 +206,16 @@ fn hit_test_recursive(
         }
     }
 
-    // Check if transformed point is within this node's untransformed bounds
-    if !node_rect.contains(local_test_point) {
+    // Check if transformed point is within this node's untransformed bounds. Children
+    // of nodes that don't clip them can still be hit outside (dropdowns, popovers).
+    let inside = node_rect.contains(local_test_point);
+    if !inside && node.overflow() != Overflow::Visible {
         return; // Point is outside this node, skip it and children
     }
 
     // Skip disabled nodes - they should not receive interaction events
     // However, we still need to test their children (they might not be disabled)
-    if !node.is_disabled() {
+    if inside && !node.is_disabled() {
         // Point is within this node! Add it to results
         // Use the transformed local point for the local position
         let local_pos = Point {
@@ -177,7 +228,14 @@ fn hit_test_recursive(
             local_pos,
             node_rect,
             zoom: current_zoom,
-            z_index: node.z_index().unwrap_or(crate::layout::ZIndex::DEFAULT),
+            z_index: current_z_index,
+            peek_delay: node.peek_delay(),
+            cursor: node.cursor(),
+            input_barrier: node.is_input_barrier(),
+            focusable: node.is_focusable() && !node.is_disabled(),
+            drop_target: node.is_drop_target() && !node.is_disabled(),
+            focus_scope: current_scope.cloned(),
+            window_action: node.window_action(),
         });
     }
 
@@ -234,13 +292,13 @@ fn hit_test_recursive(
 
     // Recursively test children with accumulated transform and zoom
     for child in node.children() {
-        hit_test_recursive(
-            child,
-            point,
-            child_clip_rect,
-            world_transform,
-            current_zoom,
-            results,
-        );
+        let inherited = Inherited {
+            clip_rect: child_clip_rect,
+            transform: world_transform,
+            zoom: current_zoom,
+            z_index: current_z_index,
+            scope: current_scope,
+        };
+        hit_test_recursive(child, point, inherited, results);
     }
 }
@@ -4,10 +4,12 @@
 //! independent of any specific windowing library (winit, SDL, etc.).
 
 use crate::Point;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
 
 /// Backend-agnostic mouse button representation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     /// Left mouse button
     Left,
@@ -21,6 +23,8 @@ pub enum MouseButton {
 
 /// Backend-agnostic named key representation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum NamedKey {
     /// Enter/Return key
     Enter,
@@ -78,6 +82,7 @@ pub enum NamedKey {
 
 /// Backend-agnostic key representation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     /// A named key (Enter, Escape, arrows, etc.)
     Named(NamedKey),
@@ -87,6 +92,20 @@ pub enum Key {
     Unknown,
 }
 
+/// A processed keyboard event, in the order it happened within a frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyEvent {
+    /// A key went down
+    Pressed(Key),
+    /// A held key repeated, at the platform's repeat delay and rate
+    Repeat(Key),
+    /// A key went up
+    Released(Key),
+    /// Text committed by typing; consecutive characters are merged
+    Text(String),
+}
+
 /// Tracks the current state of mouse and keyboard input
 ///
 /// This structure maintains both the current state and frame-specific events
@@ -95,6 +114,7 @@ pub enum Key {
 /// This is backend-agnostic - windowing libraries should convert their
 /// events to update this structure.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputState {
     /// Current cursor position in window coordinates, if known
     pub cursor_position: Option<Point>,
@@ -117,6 +137,16 @@ pub struct InputState {
     /// Keys released this frame
     pub keys_just_released: Vec<Key>,
 
+    /// Keys currently held down
+    pub keys_down: HashSet<Key>,
+
+    /// Every key press, repeat, release and typed text this frame, in order
+    ///
+    /// Unlike `keys_just_pressed`, which only holds the repeats the backend
+    /// allows, this keeps every repeat, so widgets can handle held keys
+    /// without tracking repeat timing themselves.
+    pub key_events: Vec<KeyEvent>,
+
     /// Whether Shift is currently held down
     pub shift_held: bool,
 
@@ -131,6 +161,28 @@ pub struct InputState {
 
     /// Scroll delta this frame (horizontal, vertical) in pixels
     pub scroll_delta: (f32, f32),
+    /// Whether this frame's scroll delta came from a precise device such as a
+    /// trackpad, in exact pixels rather than wheel steps
+    pub scroll_precise: bool,
+
+    /// Zoom factor of pinch gestures this frame (1.0 = no pinch)
+    pub pinch_zoom: f32,
+
+    /// Two-finger pan this frame (horizontal, vertical) in pixels
+    pub pan_delta: (f32, f32),
+
+    /// Where this frame's pinch or pan is centered: between the fingers on a
+    /// touchscreen, or `None` to use the cursor (trackpad gestures)
+    pub gesture_position: Option<Point>,
+
+    /// Fingers on a touchscreen, by touch ID
+    pub touches: BTreeMap<u64, Point>,
+
+    /// Files being dragged over the window from outside it
+    pub files_hovered: Vec<PathBuf>,
+
+    /// Files dropped on the window, waiting to be dispatched
+    pub files_dropped: Vec<PathBuf>,
 }
 
 impl InputState {
@@ -144,11 +196,20 @@ impl InputState {
             characters_typed: Vec::new(),
             keys_just_pressed: Vec::new(),
             keys_just_released: Vec::new(),
+            keys_down: HashSet::new(),
+            key_events: Vec::new(),
             shift_held: false,
             ctrl_held: false,
             alt_held: false,
             super_held: false,
             scroll_delta: (0.0, 0.0),
+            scroll_precise: false,
+            pinch_zoom: 1.0,
+            pan_delta: (0.0, 0.0),
+            gesture_position: None,
+            touches: BTreeMap::new(),
+            files_hovered: Vec::new(),
+            files_dropped: Vec::new(),
         }
     }
 
@@ -162,7 +223,17 @@ impl InputState {
         self.characters_typed.clear();
         self.keys_just_pressed.clear();
         self.keys_just_released.clear();
+        self.key_events.clear();
         self.scroll_delta = (0.0, 0.0);
+        self.scroll_precise = false;
+        self.pinch_zoom = 1.0;
+        self.pan_delta = (0.0, 0.0);
+        self.gesture_position = None;
+        // Most platforms don't report the pointer while files are dragged in,
+        // so drops wait for its position to go to the node under it
+        if self.cursor_position.is_some() {
+            self.files_dropped.clear();
+        }
     }
 
     /// Record a mouse button press
@@ -195,6 +266,13 @@ impl InputState {
             }
         }
 
+        self.keys_down.insert(key.clone());
+        self.key_events.push(if is_repeat {
+            KeyEvent::Repeat(key.clone())
+        } else {
+            KeyEvent::Pressed(key.clone())
+        });
+
         // Record the key press if it's not a repeat, or if repeats are allowed
         if !is_repeat || allow_repeat {
             self.keys_just_pressed.push(key);
@@ -214,12 +292,25 @@ impl InputState {
             }
         }
 
+        self.keys_down.remove(&key);
+        self.key_events.push(KeyEvent::Released(key.clone()));
         self.keys_just_released.push(key);
     }
 
     /// Record a character typed (for text input)
     pub fn type_character(&mut self, ch: char) {
         self.characters_typed.push(ch);
+        match self.key_events.last_mut() {
+            Some(KeyEvent::Text(text)) => text.push(ch),
+            _ => self.key_events.push(KeyEvent::Text(ch.to_string())),
+        }
+    }
+
+    /// Record text committed at once, e.g. by an input method
+    pub fn type_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            self.type_character(ch);
+        }
     }
 
     /// Update cursor position
@@ -227,12 +318,91 @@ impl InputState {
         self.cursor_position = position;
     }
 
+    /// Record a file being dragged over the window
+    pub fn hover_file(&mut self, path: PathBuf) {
+        self.files_hovered.push(path);
+    }
+
+    /// Record that the hovered files left the window without being dropped
+    pub fn cancel_file_hover(&mut self) {
+        self.files_hovered.clear();
+    }
+
+    /// Record a file dropped on the window
+    pub fn drop_file(&mut self, path: PathBuf) {
+        self.files_hovered.clear();
+        self.files_dropped.push(path);
+    }
+
     /// Add scroll delta
     pub fn add_scroll_delta(&mut self, horizontal: f32, vertical: f32) {
         self.scroll_delta.0 += horizontal;
         self.scroll_delta.1 += vertical;
     }
 
+    /// Add scroll delta from a precise device such as a trackpad, in pixels
+    ///
+    /// Scroll containers follow precise deltas one to one, without their
+    /// scroll speed or smoothing, since the platform already smooths them.
+    pub fn add_precise_scroll_delta(&mut self, horizontal: f32, vertical: f32) {
+        self.add_scroll_delta(horizontal, vertical);
+        self.scroll_precise = true;
+    }
+
+    /// Add a pinch gesture's zoom factor (e.g. 1.1 to zoom in by 10%)
+    pub fn add_pinch(&mut self, zoom: f32) {
+        self.pinch_zoom *= zoom;
+    }
+
+    /// Add two-finger pan movement in pixels
+    pub fn add_pan(&mut self, horizontal: f32, vertical: f32) {
+        self.pan_delta.0 += horizontal;
+        self.pan_delta.1 += vertical;
+    }
+
+    /// Record a finger touching the screen
+    pub fn touch_start(&mut self, id: u64, position: Point) {
+        self.touches.insert(id, position);
+    }
+
+    /// Record a finger moving on the screen
+    ///
+    /// While two or more fingers are down, moving one of the first two turns
+    /// into a pinch (the change in distance between them) and a pan (the
+    /// movement of the point halfway between them).
+    pub fn touch_move(&mut self, id: u64, position: Point) {
+        let before = self.two_finger_span();
+        let Some(touch) = self.touches.get_mut(&id) else {
+            return;
+        };
+        *touch = position;
+        if let (Some((center_before, distance_before)), Some((center, distance))) =
+            (before, self.two_finger_span())
+        {
+            if distance_before > 0.0 && distance > 0.0 {
+                self.add_pinch(distance / distance_before);
+            }
+            self.add_pan(center.x - center_before.x, center.y - center_before.y);
+            self.gesture_position = Some(center);
+        }
+    }
+
+    /// Record a finger leaving the screen (or the touch being cancelled)
+    pub fn touch_end(&mut self, id: u64) {
+        self.touches.remove(&id);
+    }
+
+    /// Center of and distance between the first two fingers, if two are down
+    fn two_finger_span(&self) -> Option<(Point, f32)> {
+        let mut fingers = self.touches.values();
+        let (a, b) = (fingers.next()?, fingers.next()?);
+        let center = Point {
+            x: (a.x + b.x) * 0.5,
+            y: (a.y + b.y) * 0.5,
+        };
+        Some((center, (a.x - b.x).hypot(a.y - b.y)))
+    }
+
     /// Check if a mouse button is currently held down
     pub fn is_button_down(&self, button: MouseButton) -> bool {
         self.buttons_pressed.contains(&button)
@@ -260,6 +430,33 @@ impl InputState {
             .any(|k| matches!(k, Key::Named(n) if *n == named))
     }
 
+    /// Check if a specific key is currently held down
+    pub fn is_key_down(&self, key: &Key) -> bool {
+        self.keys_down.contains(key)
+    }
+
+    /// Iterate over the keys pressed or repeated this frame, in order
+    ///
+    /// Use this for actions that should repeat while a key is held, such as
+    /// arrow-key navigation and Backspace.
+    pub fn keys_pressed_or_repeated(&self) -> impl Iterator<Item = &Key> {
+        self.key_events.iter().filter_map(|event| match event {
+            KeyEvent::Pressed(key) | KeyEvent::Repeat(key) => Some(key),
+            _ => None,
+        })
+    }
+
+    /// Get the text committed this frame, in order
+    pub fn text_input(&self) -> String {
+        self.key_events
+            .iter()
+            .filter_map(|event| match event {
+                KeyEvent::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Check if any modifier key is held (Ctrl, Alt, Super, but not Shift)
     pub fn any_modifier_held(&self) -> bool {
         self.ctrl_held || self.alt_held || self.super_held
@@ -328,4 +525,97 @@ mod tests {
         input.begin_frame();
         assert!(input.characters_typed.is_empty());
     }
+
+    #[test]
+    fn test_key_events_keep_presses_repeats_and_text_in_order() {
+        let mut input = InputState::new();
+        let left = Key::Named(NamedKey::ArrowLeft);
+        let a = Key::Character("a".into());
+
+        input.press_key(a.clone(), false, false);
+        input.type_character('a');
+        input.press_key(a.clone(), true, false);
+        input.type_character('a');
+        input.press_key(left.clone(), false, true);
+        input.type_text("bc");
+        input.type_character('d');
+        assert!(input.is_key_down(&a));
+
+        // Repeats are all kept, even those left out of `keys_just_pressed`
+        assert_eq!(input.keys_just_pressed, vec![a.clone(), left.clone()]);
+        assert_eq!(
+            input.key_events,
+            vec![
+                KeyEvent::Pressed(a.clone()),
+                KeyEvent::Text("a".into()),
+                KeyEvent::Repeat(a.clone()),
+                KeyEvent::Text("a".into()),
+                KeyEvent::Pressed(left.clone()),
+                KeyEvent::Text("bcd".into()),
+            ]
+        );
+        assert_eq!(input.keys_pressed_or_repeated().count(), 3);
+        assert_eq!(input.text_input(), "aabcd");
+
+        input.begin_frame();
+        input.release_key(a.clone());
+        assert!(!input.is_key_down(&a));
+        assert!(input.is_key_down(&left));
+        assert_eq!(input.key_events, vec![KeyEvent::Released(a)]);
+        assert_eq!(input.text_input(), "");
+    }
+
+    #[test]
+    fn test_file_drop_waits_for_cursor_position() {
+        let mut input = InputState::new();
+
+        input.hover_file(PathBuf::from("a.png"));
+        input.hover_file(PathBuf::from("b.png"));
+        assert_eq!(input.files_hovered.len(), 2);
+
+        // Dropping ends the hover; the pointer position is unknown, so the
+        // drop is kept across frames
+        input.drop_file(PathBuf::from("a.png"));
+        input.drop_file(PathBuf::from("b.png"));
+        assert!(input.files_hovered.is_empty());
+        input.begin_frame();
+        assert_eq!(input.files_dropped.len(), 2);
+
+        input.set_cursor_position(Some(Point::new(10.0, 10.0)));
+        input.begin_frame();
+        assert!(input.files_dropped.is_empty());
+
+        input.hover_file(PathBuf::from("c.png"));
+        input.cancel_file_hover();
+        assert!(input.files_hovered.is_empty());
+    }
+
+    #[test]
+    fn test_two_finger_touch_pinches_and_pans() {
+        let mut input = InputState::new();
+
+        // One finger alone is no gesture
+        input.touch_start(1, Point::new(100.0, 100.0));
+        input.touch_move(1, Point::new(110.0, 100.0));
+        assert_eq!(input.pinch_zoom, 1.0);
+        assert_eq!(input.gesture_position, None);
+
+        // Spreading the fingers from 100 to 200 px apart doubles the zoom,
+        // and the midpoint moving right by 50 px pans
+        input.touch_start(2, Point::new(210.0, 100.0));
+        input.touch_move(2, Point::new(310.0, 100.0));
+        assert!((input.pinch_zoom - 2.0).abs() < 1e-5);
+        assert_eq!(input.pan_delta, (50.0, 0.0));
+        assert_eq!(input.gesture_position, Some(Point::new(210.0, 100.0)));
+
+        input.begin_frame();
+        assert_eq!(input.pinch_zoom, 1.0);
+        assert_eq!(input.pan_delta, (0.0, 0.0));
+        assert_eq!(input.touches.len(), 2);
+
+        input.touch_end(1);
+        input.touch_move(2, Point::new(0.0, 0.0));
+        assert_eq!(input.pinch_zoom, 1.0);
+        assert!(input.touches.len() == 1);
+    }
 }
@@ -4,15 +4,24 @@
 //! states and manages style transitions for all nodes in the UI tree.
 //! It is backend-agnostic and works with any rendering backend.
 
-use crate::transition::lerp_style;
+use crate::transition::lerp_style_in;
 use crate::{InteractionState, Node, NodeId, Style, Transition};
 use std::collections::HashMap;
 use std::time::Instant;
 
+/// Prefix of the IDs `assign_auto_ids` gives to unidentified interactive nodes
+pub(crate) const AUTO_ID_PREFIX: &str = "__auto_path_";
+
 /// Check if two styles differ in any animatable property
 fn styles_differ(a: &Style, b: &Style) -> bool {
     a.fill_color != b.fill_color
+        || a.gradient != b.gradient
+        || a.linear_gradient != b.linear_gradient
+        || a.nine_slice != b.nine_slice
         || a.stroke != b.stroke
+        || a.shadow != b.shadow
+        || a.inset_shadow != b.inset_shadow
+        || a.tint != b.tint
         || a.corner_shape != b.corner_shape
         || a.opacity != b.opacity
         || a.text_color != b.text_color
@@ -20,6 +29,11 @@ fn styles_differ(a: &Style, b: &Style) -> bool {
         || a.translation_y != b.translation_y
         || a.width_override != b.width_override
         || a.height_override != b.height_override
+        || a.animations.len() != b.animations.len()
+        || a.animations
+            .iter()
+            .zip(&b.animations)
+            .any(|(a, b)| !a.same_as(b))
 }
 
 /// Transition state for a single node
@@ -63,6 +77,10 @@ pub struct InteractiveStateManager {
     states: HashMap<NodeId, NodeTransitionState>,
     /// Current frame time
     current_time: Instant,
+    /// Frame clock in seconds that animations are sampled at
+    clock: f64,
+    /// Whether any node was animated in the last `update_transitions`
+    animating: bool,
 }
 
 impl InteractiveStateManager {
@@ -71,12 +89,20 @@ impl InteractiveStateManager {
         Self {
             states: HashMap::new(),
             current_time: Instant::now(),
+            clock: 0.0,
+            animating: false,
         }
     }
 
     /// Call at start of each frame to update the current time
     pub fn begin_frame(&mut self) {
         self.current_time = Instant::now();
+        self.animating = false;
+    }
+
+    /// Set the frame clock (in seconds) that style animations are sampled at
+    pub fn set_clock(&mut self, seconds: f64) {
+        self.clock = seconds;
     }
 
     /// Assign auto-generated IDs to nodes that need them for interactivity
@@ -92,9 +118,17 @@ impl InteractiveStateManager {
     fn assign_auto_ids_recursive(node: &mut Node, path: &mut Vec<usize>) {
         // Check if node needs an auto-ID for interactivity
         let needs_auto_id = node.id().is_none()
-            && (node.hover_style().is_some()
+            && (node.is_focusable()
+                || node.has_event_handlers()
+                || node.is_focus_scope()
+                || node.hover_style().is_some()
                 || node.active_style().is_some()
-                || node.disabled_style().is_some());
+                || node.focused_style().is_some()
+                || node.selected_style().is_some()
+                || node.disabled_style().is_some()
+                || node
+                    .base_style()
+                    .is_some_and(|style| !style.animations.is_empty()));
 
         if needs_auto_id {
             // Generate a stable auto-ID based on tree path
@@ -103,7 +137,7 @@ impl InteractiveStateManager {
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>()
                 .join("_");
-            let auto_id = NodeId::new(format!("__auto_path_{}", path_str));
+            let auto_id = NodeId::new(format!("{AUTO_ID_PREFIX}{path_str}"));
             node.set_auto_id(auto_id);
         }
 
@@ -278,12 +312,14 @@ impl InteractiveStateManager {
                 // Interpolate
                 let progress = elapsed / trans.duration;
                 let eased = (trans.easing)(progress);
-                let interpolated = lerp_style(from, to, eased);
+                let interpolated = lerp_style_in(from, to, eased, trans.color_space);
                 entry.current_style = Some(interpolated);
             }
         } else {
-            // No transition, use target directly
+            // No transition, use target directly (and don't report the style
+            // change as an active transition)
             entry.current_style = Some(target_style);
+            entry.transition_start = None;
             // Update last known dimensions when not transitioning
             entry.last_width = current_width;
             entry.last_height = current_height;
@@ -300,7 +336,7 @@ impl InteractiveStateManager {
     /// Returns true if any node is mid-transition, indicating that
     /// continuous redraws are needed for smooth animation.
     pub fn has_active_transitions(&self) -> bool {
-        self.states.values().any(|s| s.transition_start.is_some())
+        self.animating || self.states.values().any(|s| s.transition_start.is_some())
     }
 
     /// Inject dimension overrides from transition state BEFORE layout
@@ -336,10 +372,15 @@ impl InteractiveStateManager {
     /// Captures current dimensions from layout for use in NEXT frame's transitions.
     /// Applies non-dimension styles (colors, opacity) immediately for instant visual feedback.
     /// Dimension overrides are stored but not applied (used next frame by inject_dimension_overrides).
+    ///
+    /// Selected nodes layer their selected style onto the base style, and
+    /// `focused` is the node holding focus; its focused style is layered on top of that, so
+    /// gaining or losing focus transitions like any other style change.
     pub fn update_transitions(
         &mut self,
         node: &mut Node,
         interaction_states: &HashMap<NodeId, InteractionState>,
+        focused: Option<&NodeId>,
     ) {
         // Apply styles if node has an ID and base style
         let node_id = node.id().cloned();
@@ -363,11 +404,28 @@ impl InteractiveStateManager {
                         .unwrap_or(InteractionState::Idle)
                 };
 
+                // Selection, then focus, sit between base and hover/active (and
+                // never apply when disabled)
+                let enabled = state != InteractionState::Disabled;
+                let selected_base = match node.selected_style() {
+                    Some(selected_style) if node.is_selected() && enabled => {
+                        Some(base_style.merge(selected_style))
+                    }
+                    _ => None,
+                };
+                let base_style = selected_base.as_ref().unwrap_or(base_style);
+                let focused_base = match node.focused_style() {
+                    Some(focused_style) if focused == Some(&node_id) && enabled => {
+                        Some(base_style.merge(focused_style))
+                    }
+                    _ => None,
+                };
+
                 // Compute the target style for NEXT frame
                 let computed_style = self.update_state(
                     &node_id,
                     state,
-                    base_style,
+                    focused_base.as_ref().unwrap_or(base_style),
                     node.hover_style(),
                     node.active_style(),
                     node.disabled_style(),
@@ -381,13 +439,17 @@ impl InteractiveStateManager {
                 let mut immediate_style = computed_style.clone();
                 immediate_style.width_override = None;
                 immediate_style.height_override = None;
+                for animation in &computed_style.animations {
+                    animation.apply(&mut immediate_style, self.clock);
+                }
+                self.animating |= !computed_style.animations.is_empty();
                 immediate_style.apply_to_node(node);
             }
         }
 
         // Recursively update transitions for children
         for child in node.children_mut() {
-            self.update_transitions(child, interaction_states);
+            self.update_transitions(child, interaction_states, focused);
         }
     }
 }
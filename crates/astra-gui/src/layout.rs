@@ -1,7 +1,18 @@
-use crate::primitives::Rect;
+//! Sizing, spacing, placement and transforms
+//!
+//! Besides the types defined here, this module re-exports the other types
+//! needed to describe layout, so `astra_gui::layout::*` is enough to build a
+//! layout without reaching into the crate root.
+
+use crate::transition::EasingFn;
+
+pub use crate::constraint::{Anchor, Constraint, Relation};
+pub use crate::node::Place;
+pub use crate::primitives::{Orientation, Point, Rect};
 
 /// Size specification that can be fixed, relative to parent, or derived from content.
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Size {
     /// Fixed size in logical pixels (scales with zoom)
     Logical(f32),
@@ -45,6 +56,75 @@ impl Default for ScrollDirection {
     }
 }
 
+/// How an `Overflow::Scroll` container animates its scroll offset toward the scroll target
+#[derive(Clone, Copy, Debug)]
+pub enum ScrollSmoothing {
+    /// Jump straight to the target (deterministic, useful for tests)
+    Instant,
+    /// Exponential ease-out toward the target
+    ///
+    /// The value is the smoothness rate: higher = snappier, lower = smoother.
+    Exponential(f32),
+    /// Travel from the current offset to the target over a fixed duration
+    ///
+    /// The animation restarts from the current offset whenever the target changes.
+    Eased {
+        /// Duration in seconds
+        duration: f32,
+        /// Easing function to apply
+        easing: EasingFn,
+    },
+}
+
+impl ScrollSmoothing {
+    /// Eased scrolling with the given duration (seconds) and easing function
+    pub fn eased(duration: f32, easing: EasingFn) -> Self {
+        Self::Eased { duration, easing }
+    }
+}
+
+impl Default for ScrollSmoothing {
+    fn default() -> Self {
+        Self::Exponential(10.0)
+    }
+}
+
+/// Kinetic scrolling for an `Overflow::Scroll` container
+///
+/// When a trackpad flick ends, the container keeps scrolling with the speed
+/// it had and slows down by `friction`. See `Node::with_scroll_momentum`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollMomentum {
+    /// How quickly a flick slows down, per second: the speed decays by
+    /// `exp(-friction * seconds)`
+    pub friction: f32,
+    /// How far past either end a flick may carry the content, in pixels,
+    /// before it springs back (0 = stop at the ends)
+    pub overscroll: f32,
+}
+
+impl ScrollMomentum {
+    /// Momentum with the given friction that stops at the ends
+    pub fn new(friction: f32) -> Self {
+        Self {
+            friction,
+            overscroll: 0.0,
+        }
+    }
+
+    /// Let flicks rubber-band up to `overscroll` pixels past the ends
+    pub fn with_overscroll(mut self, overscroll: f32) -> Self {
+        self.overscroll = overscroll.max(0.0);
+        self
+    }
+}
+
+impl Default for ScrollMomentum {
+    fn default() -> Self {
+        Self::new(4.0)
+    }
+}
+
 impl Size {
     /// Create a fixed size in logical pixels (scales with zoom)
     pub const fn lpx(pixels: f32) -> Self {
@@ -196,6 +276,7 @@ impl Default for Layout {
 
 /// 2D translation offset
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Translation {
     pub x: Size,
     pub y: Size,
@@ -261,6 +342,7 @@ impl Vector2 {
 
 /// Transform origin for rotation (CSS-like percentage + pixel offset)
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransformOrigin {
     /// X position as percentage of width (0.0 = left, 0.5 = center, 1.0 = right)
     pub x_percent: f32,
@@ -465,6 +547,8 @@ pub struct ComputedLayout {
     pub rect: Rect,
     /// Maximum scroll offset for scrollable containers (cached during layout)
     pub max_scroll: (f32, f32),
+    /// The size was derived from a provisional content measurement
+    pub provisional: bool,
 }
 
 impl ComputedLayout {
@@ -472,16 +556,22 @@ impl ComputedLayout {
         Self {
             rect,
             max_scroll: (0.0, 0.0),
+            provisional: false,
         }
     }
 
     pub fn with_max_scroll(rect: Rect, max_scroll: (f32, f32)) -> Self {
-        Self { rect, max_scroll }
+        Self {
+            rect,
+            max_scroll,
+            provisional: false,
+        }
     }
 }
 
 /// Spacing/padding around content
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Spacing {
     pub top: Size,
     pub right: Size,
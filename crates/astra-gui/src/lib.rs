@@ -18,6 +18,7 @@
 //! - [`EventDispatcher`] - Generates interaction events from input
 //! - [`InteractionEvent`] - Types of UI interactions (click, hover, drag, etc.)
 //! - [`TargetedEvent`] - An event targeted at a specific node
+//! - [`InputRecording`] - A per-frame input stream for deterministic playback
 //!
 //! ## State Management
 //!
@@ -29,44 +30,159 @@
 //! - [`Style`] - Visual styling properties
 //! - [`Transition`] - Animation configuration for style changes
 //! - [`ContentMeasurer`] - Trait for text measurement
+//!
+//! ## Public API
+//!
+//! Every public type is available from the crate root. For imports that stay
+//! stable across internal reorganizations, prefer:
+//!
+//! - [`prelude`] - The types most apps and widgets need
+//! - [`layout`], [`style`], [`events`], [`text`] - Everything for one area,
+//!   including the related types defined elsewhere in the crate
+//! - [`transition`] - Easing functions and interpolation helpers
+//!
+//! Enums that are expected to grow (events, shapes, cursor icons, ...) are
+//! `#[non_exhaustive]`, so match them with a wildcard arm. Paths that moved
+//! keep working through deprecated forwarding items for one release.
 
+mod animation;
+mod camera;
+mod canvas;
+mod clipboard;
 mod color;
 mod component;
+mod constraint;
 mod content;
 mod context;
+#[cfg(feature = "css")]
+mod css_parser;
+mod cursor;
 mod debug;
-mod events;
+mod deprecated;
+mod drag;
+mod drag_ghost;
+pub mod events;
+mod filter;
+mod heatmap;
+mod highlight;
 mod hit_test;
 mod input;
 mod interactive_state;
-mod layout;
+pub mod layout;
+mod macro_support;
 mod measure;
 mod memory;
+mod nine_slice;
 mod node;
 mod output;
+mod palette;
+mod platform;
+pub mod prelude;
 mod primitives;
-mod style;
+mod recording;
+#[cfg(feature = "remote")]
+mod remote;
+mod retained;
+pub mod style;
+mod stylesheet;
+pub mod text;
+mod text_edit;
+mod theme;
+mod tokens;
+mod trace;
 pub mod transition;
 
 // Core types
-pub use color::*;
-pub use component::*;
-pub use content::*;
-pub use context::*;
-pub use debug::*;
-pub use hit_test::*;
-pub use layout::*;
-pub use measure::*;
-pub use node::*;
-pub use output::*;
-pub use primitives::*;
-pub use style::*;
-pub use transition::*;
+pub use animation::{AnimatedProperty, Animation, Repeat};
+pub use camera::CameraController;
+pub use canvas::Painter;
+pub use clipboard::Clipboard;
+pub use color::{catppuccin, css, dracula, gruvbox, nord, Color, ColorSpace};
+pub use component::{Component, ComponentExt};
+pub use constraint::{Anchor, Constraint, Relation};
+pub use content::{
+    CodeContent, Content, FontFeature, FontStyle, FontWeight, FontWidth, HorizontalAlign,
+    TextContent, TextInherit, TextOutline, TextOverflow, TextSpan, VerticalAlign, Wrap,
+    DEFAULT_CODE_FAMILY,
+};
+pub use context::UiContext;
+#[cfg(feature = "css")]
+pub use css_parser::CssError;
+pub use cursor::CursorIcon;
+pub use debug::DebugOptions;
+pub use drag::{DragAxis, DragConfig};
+pub use drag_ghost::DragGhost;
+pub use filter::{ColorMatrix, Filter};
+pub use heatmap::{HeatCell, InteractionHeatmap};
+pub use highlight::Highlighter;
+#[cfg(feature = "syntect")]
+pub use highlight::SyntectHighlighter;
+pub use hit_test::{hit_test_deepest, hit_test_point, HitTestResult};
+pub use layout::{
+    ComputedLayout, Layout, Overflow, ScrollDirection, ScrollMomentum, ScrollSmoothing, Size,
+    Spacing, Transform2D, TransformOrigin, Translation, Vector2, ZIndex,
+};
+pub use measure::{CacheStats, ContentMeasurer, IntrinsicSize, MeasureCache, MeasureTextRequest};
+pub use nine_slice::{NinePatch, NineSlice, NineSliceImage, TextureId};
+pub use node::{Node, NodeId, Place};
+pub use output::FullOutput;
+pub use palette::{
+    builtin_palettes, CatppuccinFrappe, CatppuccinLatte, CatppuccinMacchiato, CatppuccinMocha,
+    Dracula, GruvboxDark, GruvboxLight, Nord, Palette,
+};
+pub use platform::{PlatformOutput, PlatformRequest, ResizeDirection, WindowAction};
+pub use primitives::{
+    AntiAliasing, ClippedShape, ConicGradient, Corner, CornerKind, CornerShape, Corners, Fill,
+    LinearGradient, Orientation, Outline, Point, Rect, Shadow, Shape, Stroke, StrokeAlignment,
+    StyledRect, StyledTriangle, TextShape, TriangleSpec,
+};
+pub use recording::{InputPlayback, InputRecording, RecordedFrame};
+#[cfg(feature = "remote")]
+pub use remote::{OutputDecodeError, FULL_OUTPUT_MAGIC, FULL_OUTPUT_SCHEMA_VERSION};
+pub use retained::{LayoutPass, RetainedTree};
+pub use style::Style;
+pub use stylesheet::{StyleRule, StyleSheet};
+pub use text_edit::{
+    matching_bracket, LineRect, Motion, Selection, TextBuffer, TextEditDecorations,
+    TextEditResponse, TextEditStyle, TextEditor,
+};
+pub use theme::{ColorScheme, Theme, ThemeColor, ThemePair};
+pub use tokens::{var, StyleProperty, TokenRef, TokenTable, TokenValue, VarBinding, VarState};
+pub use trace::{FramePhase, FrameTrace, TraceNode};
+pub use transition::{EasingFn, Transition};
 
 // Input & Events
-pub use events::*;
-pub use input::*;
+pub use events::{
+    CustomEvent, EventDispatcher, InteractionEvent, InteractionState, ScrollMetrics, TargetedEvent,
+    DEFAULT_HOVER_INTENT_DELAY, DEFAULT_LONG_PRESS_DURATION, DEFAULT_MULTI_CLICK_INTERVAL,
+};
+pub use input::{InputState, Key, KeyEvent, MouseButton, NamedKey};
 
 // State Management
-pub use interactive_state::*;
-pub use memory::*;
+pub use interactive_state::InteractiveStateManager;
+pub use memory::{
+    CodeEditorState, CollapsibleState, DragValueState, GaugeState, GradientEditorState,
+    InspectorState, ListViewState, MenuState, ModalState, NumberInputState, PlotState,
+    RangeSliderState, ReorderState, ResizeState, ScrollbarState, SearchInputState, SliderState,
+    SwipeState, TextAreaState, TextInputState, TimelineState, ToggleState, WidgetMemory,
+    WidgetStateId, WindowStackState, WindowState,
+};
+
+// Deprecated paths
+#[allow(deprecated)]
+pub use deprecated::{
+    ease_in, ease_in_cubic, ease_in_out, ease_in_out_cubic, ease_out, ease_out_cubic, lerp_color,
+    lerp_corner_kind, lerp_corner_shape, lerp_f32, lerp_outline, lerp_size, lerp_stroke,
+    lerp_stroke_alignment, lerp_style, linear,
+};
+
+// Style construction macro
+pub use astra_gui_macros::style;
+
+// `style!` expands to `::astra_gui::...` paths, which must also resolve here
+extern crate self as astra_gui;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macro_support::*;
+}
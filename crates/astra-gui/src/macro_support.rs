@@ -0,0 +1,141 @@
+//! Support code for the `style!` macro
+//!
+//! Not part of the public API: the macro expands to calls into this module
+//! (re-exported as `astra_gui::__private`) so numbers can be written where a
+//! `Size` is expected, and `padding: all(8)` reads like the `Spacing`
+//! constructors without spelling out `Size::lpx`.
+
+use crate::layout::{Size, Spacing};
+use crate::primitives::CornerShape;
+
+/// Values usable as a size in `style!`; plain numbers are logical pixels
+pub trait IntoSize {
+    fn into_size(self) -> Size;
+}
+
+impl IntoSize for Size {
+    fn into_size(self) -> Size {
+        self
+    }
+}
+
+impl IntoSize for f32 {
+    fn into_size(self) -> Size {
+        Size::lpx(self)
+    }
+}
+
+impl IntoSize for f64 {
+    fn into_size(self) -> Size {
+        Size::lpx(self as f32)
+    }
+}
+
+impl IntoSize for i32 {
+    fn into_size(self) -> Size {
+        Size::lpx(self as f32)
+    }
+}
+
+impl IntoSize for u32 {
+    fn into_size(self) -> Size {
+        Size::lpx(self as f32)
+    }
+}
+
+/// Values usable as padding in `style!`; sizes pad all sides equally
+pub trait IntoSpacing {
+    fn into_spacing(self) -> Spacing;
+}
+
+impl IntoSpacing for Spacing {
+    fn into_spacing(self) -> Spacing {
+        self
+    }
+}
+
+impl<T: IntoSize> IntoSpacing for T {
+    fn into_spacing(self) -> Spacing {
+        Spacing::all(self.into_size())
+    }
+}
+
+/// Values usable as `radius` in `style!`; sizes give round corners
+pub trait IntoCornerShape {
+    fn into_corner_shape(self) -> CornerShape;
+}
+
+impl IntoCornerShape for CornerShape {
+    fn into_corner_shape(self) -> CornerShape {
+        self
+    }
+}
+
+impl<T: IntoSize> IntoCornerShape for T {
+    fn into_corner_shape(self) -> CornerShape {
+        CornerShape::Round(self.into_size())
+    }
+}
+
+pub fn size(value: impl IntoSize) -> Size {
+    value.into_size()
+}
+
+pub fn spacing(value: impl IntoSpacing) -> Spacing {
+    value.into_spacing()
+}
+
+pub fn corner_shape(value: impl IntoCornerShape) -> CornerShape {
+    value.into_corner_shape()
+}
+
+/// Spacing constructors taking numbers, in scope inside `padding: ...`
+pub mod padding {
+    use super::{IntoSize, Spacing};
+
+    pub fn all(value: impl IntoSize) -> Spacing {
+        Spacing::all(value.into_size())
+    }
+
+    pub fn symmetric(horizontal: impl IntoSize, vertical: impl IntoSize) -> Spacing {
+        Spacing::symmetric(horizontal.into_size(), vertical.into_size())
+    }
+
+    pub fn trbl(
+        top: impl IntoSize,
+        right: impl IntoSize,
+        bottom: impl IntoSize,
+        left: impl IntoSize,
+    ) -> Spacing {
+        Spacing::trbl(
+            top.into_size(),
+            right.into_size(),
+            bottom.into_size(),
+            left.into_size(),
+        )
+    }
+
+    pub fn horizontal(value: impl IntoSize) -> Spacing {
+        Spacing::horizontal(value.into_size())
+    }
+
+    pub fn vertical(value: impl IntoSize) -> Spacing {
+        Spacing::vertical(value.into_size())
+    }
+
+    pub fn top(value: impl IntoSize) -> Spacing {
+        Spacing::top(value.into_size())
+    }
+
+    pub fn right(value: impl IntoSize) -> Spacing {
+        Spacing::right(value.into_size())
+    }
+
+    pub fn bottom(value: impl IntoSize) -> Spacing {
+        Spacing::bottom(value.into_size())
+    }
+
+    pub fn left(value: impl IntoSize) -> Spacing {
+        Spacing::left(value.into_size())
+    }
+}
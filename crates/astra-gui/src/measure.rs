@@ -3,13 +3,27 @@
 //! This module provides a backend-agnostic trait for measuring intrinsic content size
 //! (e.g., text metrics) during layout. It enables `Size::FitContent` to resolve to
 //! actual dimensions rather than falling back to parent size.
+//!
+//! Measurers that cannot produce real metrics yet (huge documents, fonts still
+//! loading) may return an [`IntrinsicSize::provisional`] estimate instead of
+//! blocking the frame. Nodes laid out from such estimates are flagged in their
+//! `ComputedLayout`, and `Node::relayout_provisional` re-runs layout for just
+//! the affected subtrees once the measurer reports the real metrics.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
-use crate::content::{FontStyle, FontWeight, HorizontalAlign, TextContent, VerticalAlign, Wrap};
+use crate::content::{
+    FontFeature, FontStyle, FontWeight, FontWidth, HorizontalAlign, TextContent, TextSpan,
+    VerticalAlign, Wrap,
+};
 
 /// Request to measure the intrinsic size of text (single or multi-line).
 #[derive(Debug, Clone)]
 pub struct MeasureTextRequest<'a> {
     pub text: &'a str,
+    /// Styled runs making up `text`; empty for plain text
+    pub spans: &'a [TextSpan],
     pub font_size: f32,
     pub h_align: HorizontalAlign,
     pub v_align: VerticalAlign,
@@ -19,12 +33,20 @@ pub struct MeasureTextRequest<'a> {
     pub max_width: Option<f32>,
     /// Text wrapping mode
     pub wrap: Wrap,
+    /// Maximum number of lines measured (None = no limit)
+    pub max_lines: Option<usize>,
     /// Line height as a multiplier of font size
     pub line_height_multiplier: f32,
     /// Font weight
     pub font_weight: FontWeight,
     /// Font style
     pub font_style: FontStyle,
+    /// OpenType features applied when shaping
+    pub font_features: &'a [FontFeature],
+    /// Font width
+    pub font_width: FontWidth,
+    /// Distance between tab stops, in widths of a space
+    pub tab_width: u16,
 }
 
 impl<'a> MeasureTextRequest<'a> {
@@ -38,15 +60,20 @@ impl<'a> MeasureTextRequest<'a> {
 
         Self {
             text: &content.text,
+            spans: &content.spans,
             font_size,
             h_align: content.h_align,
             v_align: content.v_align,
-            family: None,
+            family: content.font_family.as_deref(),
             max_width: None,
             wrap: content.wrap,
+            max_lines: content.max_lines,
             line_height_multiplier: content.line_height_multiplier,
             font_weight: content.font_weight,
             font_style: content.font_style,
+            font_features: &content.font_features,
+            font_width: content.font_width,
+            tab_width: content.tab_width,
         }
     }
 }
@@ -56,19 +83,31 @@ impl<'a> MeasureTextRequest<'a> {
 pub struct IntrinsicSize {
     pub width: f32,
     pub height: f32,
+    /// The size is an estimate; the measurer will have real metrics later
+    pub provisional: bool,
 }
 
 impl IntrinsicSize {
     pub const fn new(width: f32, height: f32) -> Self {
-        Self { width, height }
+        Self {
+            width,
+            height,
+            provisional: false,
+        }
     }
 
-    pub const fn zero() -> Self {
+    /// An estimated size to lay out with until real metrics are available
+    pub const fn provisional(width: f32, height: f32) -> Self {
         Self {
-            width: 0.0,
-            height: 0.0,
+            width,
+            height,
+            provisional: true,
         }
     }
+
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
 }
 
 /// Backend-agnostic content measurement.
@@ -85,4 +124,365 @@ pub trait ContentMeasurer {
     /// This should return the minimum bounding box that fits the shaped text,
     /// excluding any padding or margins (those are handled by layout).
     fn measure_text(&mut self, request: MeasureTextRequest<'_>) -> IntrinsicSize;
+
+    /// Hit/miss counters of the measurer's internal cache, if it has one.
+    ///
+    /// Used by frame trace exports; measurers without a cache return `None`.
+    fn cache_stats(&self) -> Option<CacheStats> {
+        None
+    }
+
+    /// Whether provisional sizes returned earlier are still awaiting real metrics.
+    ///
+    /// Apps keep redrawing (or call `Node::relayout_provisional`) while this is
+    /// true; measurers that never return provisional sizes keep the default.
+    fn has_pending_measurements(&self) -> bool {
+        false
+    }
+}
+
+/// Hit/miss counters for a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub const fn new(hits: u64, misses: u64) -> Self {
+        Self { hits, misses }
+    }
+
+    /// Fraction of lookups served from the cache (0.0 when there were none)
+    pub fn hit_rate(&self) -> f32 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f32 / total as f32
+        }
+    }
+}
+
+/// Entries a [`MeasureCache`] holds before it starts over
+const MEASURE_CACHE_CAPACITY: usize = 4096;
+
+/// Caches another measurer's results by text and layout constraints
+///
+/// Layout measures the same content several times per frame (once for the
+/// node's intrinsic size, again from each `FitContent` ancestor), and again
+/// every frame. Wrapping the measurer in a `MeasureCache` that lives across
+/// frames answers repeats from a hash map instead of re-shaping:
+///
+/// ```ignore
+/// let mut measurer = MeasureCache::new(text_engine);
+/// // every frame
+/// root.compute_layout_with_measurer(window_rect, &mut measurer);
+/// ```
+///
+/// Provisional sizes are passed through but never cached, so real metrics
+/// replace them as soon as the inner measurer has them.
+pub struct MeasureCache<M> {
+    measurer: M,
+    entries: HashMap<u64, IntrinsicSize>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<M: ContentMeasurer> MeasureCache<M> {
+    pub fn new(measurer: M) -> Self {
+        Self {
+            measurer,
+            entries: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The wrapped measurer
+    pub fn inner(&self) -> &M {
+        &self.measurer
+    }
+
+    /// The wrapped measurer; call [`Self::clear`] after changing how it measures
+    /// (e.g. loading fonts)
+    pub fn inner_mut(&mut self) -> &mut M {
+        &mut self.measurer
+    }
+
+    pub fn into_inner(self) -> M {
+        self.measurer
+    }
+
+    /// Forget all cached sizes
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached sizes
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hash of everything in `request` that affects the measured size
+    fn key(request: &MeasureTextRequest<'_>) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        request.text.hash(&mut hasher);
+        for span in request.spans {
+            span.text.len().hash(&mut hasher);
+            span.font_weight.hash(&mut hasher);
+            span.font_style.hash(&mut hasher);
+            span.font_scale.map(f32::to_bits).hash(&mut hasher);
+        }
+        request.font_size.to_bits().hash(&mut hasher);
+        request.family.hash(&mut hasher);
+        request.max_width.map(f32::to_bits).hash(&mut hasher);
+        request.wrap.hash(&mut hasher);
+        request.max_lines.hash(&mut hasher);
+        request.line_height_multiplier.to_bits().hash(&mut hasher);
+        request.font_weight.hash(&mut hasher);
+        request.font_style.hash(&mut hasher);
+        request.font_features.hash(&mut hasher);
+        request.font_width.hash(&mut hasher);
+        request.tab_width.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<M: ContentMeasurer> ContentMeasurer for MeasureCache<M> {
+    fn measure_text(&mut self, request: MeasureTextRequest<'_>) -> IntrinsicSize {
+        let key = Self::key(&request);
+        if let Some(&size) = self.entries.get(&key) {
+            self.hits += 1;
+            return size;
+        }
+        self.misses += 1;
+
+        let size = self.measurer.measure_text(request);
+        if !size.provisional {
+            if self.entries.len() >= MEASURE_CACHE_CAPACITY {
+                self.entries.clear();
+            }
+            self.entries.insert(key, size);
+        }
+        size
+    }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        Some(CacheStats::new(self.hits, self.misses))
+    }
+
+    fn has_pending_measurements(&self) -> bool {
+        self.measurer.has_pending_measurements()
+    }
+}
+
+impl<M: std::fmt::Debug> std::fmt::Debug for MeasureCache<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MeasureCache")
+            .field("measurer", &self.measurer)
+            .field("entries", &self.entries.len())
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Content, Layout, Node, Rect, Size};
+
+    /// Measurer whose metrics only become available after `ready` is set
+    struct LoadingMeasurer {
+        ready: bool,
+    }
+
+    impl ContentMeasurer for LoadingMeasurer {
+        fn measure_text(&mut self, _request: MeasureTextRequest<'_>) -> IntrinsicSize {
+            if self.ready {
+                IntrinsicSize::new(40.0, 20.0)
+            } else {
+                IntrinsicSize::provisional(10.0, 10.0)
+            }
+        }
+
+        fn has_pending_measurements(&self) -> bool {
+            !self.ready
+        }
+    }
+
+    #[test]
+    fn test_provisional_subtree_is_relaid_out() {
+        let mut root = Node::new()
+            .with_width(Size::lpx(200.0))
+            .with_height(Size::lpx(200.0))
+            .with_layout_direction(Layout::Vertical)
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(100.0))
+                    .with_height(Size::lpx(100.0))
+                    .with_child(
+                        Node::new().with_content(Content::Text(TextContent::new("loading"))),
+                    ),
+            )
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(50.0))
+                    .with_height(Size::lpx(50.0)),
+            );
+
+        let mut measurer = LoadingMeasurer { ready: false };
+        root.compute_layout_with_measurer(Rect::new([0.0, 0.0], [800.0, 600.0]), &mut measurer);
+        assert!(root.has_provisional_layout());
+        assert!(!root.children()[0].computed_layout().unwrap().provisional);
+        let sibling = root.children()[1].computed_layout().unwrap().rect;
+
+        measurer.ready = true;
+        assert!(root.relayout_provisional(&mut measurer, 1.0));
+        assert!(!root.has_provisional_layout());
+        let text = root.children()[0].children()[0]
+            .computed_layout()
+            .unwrap()
+            .rect;
+        assert_eq!((text.width(), text.height()), (40.0, 20.0));
+        assert_eq!(root.children()[1].computed_layout().unwrap().rect, sibling);
+
+        assert!(!root.relayout_provisional(&mut measurer, 1.0));
+    }
+
+    #[test]
+    fn test_measure_request_carries_spans() {
+        let content = TextContent::new("Hello ").with_span(TextSpan::new("world").bold());
+        let request = MeasureTextRequest::from_text_content(&content);
+
+        assert_eq!(request.text, "Hello world");
+        assert_eq!(request.spans.len(), 2);
+        assert_eq!(request.spans[0].text, "Hello ");
+        assert_eq!(request.spans[1].font_weight, Some(FontWeight::Bold));
+    }
+
+    #[test]
+    fn test_measure_request_carries_font_family() {
+        let plain = TextContent::new("Hello");
+        assert_eq!(MeasureTextRequest::from_text_content(&plain).family, None);
+
+        let content = TextContent::new("こんにちは").with_font_family("Noto Sans CJK JP");
+        let request = MeasureTextRequest::from_text_content(&content);
+        assert_eq!(request.family, Some("Noto Sans CJK JP"));
+    }
+
+    #[test]
+    fn test_measure_request_carries_max_lines() {
+        let plain = TextContent::new("Hello");
+        assert_eq!(
+            MeasureTextRequest::from_text_content(&plain).max_lines,
+            None
+        );
+
+        let clamped = TextContent::new("Hello").with_max_lines(0);
+        assert_eq!(
+            MeasureTextRequest::from_text_content(&clamped).max_lines,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_measure_request_carries_font_features() {
+        let plain = TextContent::new("12:00");
+        let request = MeasureTextRequest::from_text_content(&plain);
+        assert!(request.font_features.is_empty());
+        assert_eq!(request.font_width, FontWidth::Normal);
+
+        let clock = TextContent::new("12:00")
+            .tabular_numbers()
+            .without_ligatures()
+            .with_font_width(FontWidth::Condensed);
+        let request = MeasureTextRequest::from_text_content(&clock);
+        assert_eq!(
+            request.font_features,
+            [
+                FontFeature::new(*b"tnum", 1),
+                FontFeature::new(*b"liga", 0),
+                FontFeature::new(*b"clig", 0),
+            ]
+        );
+        assert_eq!(request.font_width, FontWidth::Condensed);
+    }
+
+    /// Measurer that counts how often it is asked
+    #[derive(Default)]
+    struct CountingMeasurer {
+        calls: usize,
+    }
+
+    impl ContentMeasurer for CountingMeasurer {
+        fn measure_text(&mut self, request: MeasureTextRequest<'_>) -> IntrinsicSize {
+            self.calls += 1;
+            IntrinsicSize::new(request.text.len() as f32 * 8.0, request.font_size)
+        }
+    }
+
+    #[test]
+    fn test_measure_cache_answers_repeats_across_layouts() {
+        let build = || {
+            Node::new()
+                .with_layout_direction(Layout::Vertical)
+                .with_child(Node::new().with_content(Content::Text(TextContent::new("cached"))))
+        };
+        let window = Rect::new([0.0, 0.0], [200.0, 200.0]);
+        let mut measurer = MeasureCache::new(CountingMeasurer::default());
+
+        let mut root = build();
+        root.compute_layout_with_measurer(window, &mut measurer);
+        let calls = measurer.inner().calls;
+        assert_eq!(measurer.len(), calls);
+
+        let mut root = build();
+        root.compute_layout_with_measurer(window, &mut measurer);
+        assert_eq!(measurer.inner().calls, calls);
+        assert_eq!(
+            root.children()[0].computed_layout().unwrap().rect.width(),
+            48.0
+        );
+
+        let stats = measurer.cache_stats().unwrap();
+        assert_eq!(stats.misses, calls as u64);
+        assert!(stats.hits > 0);
+    }
+
+    #[test]
+    fn test_measure_cache_keys_on_tab_width() {
+        let mut measurer = MeasureCache::new(CountingMeasurer::default());
+        let eight = TextContent::new("a\tb");
+        let four = TextContent::new("a\tb").with_tab_width(4);
+        assert_eq!(eight.tab_width, TextContent::DEFAULT_TAB_WIDTH);
+        assert_eq!(TextContent::new("").with_tab_width(0).tab_width, 1);
+
+        measurer.measure_text(MeasureTextRequest::from_text_content(&eight));
+        measurer.measure_text(MeasureTextRequest::from_text_content(&four));
+        measurer.measure_text(MeasureTextRequest::from_text_content(&four));
+        assert_eq!(measurer.inner().calls, 2);
+    }
+
+    #[test]
+    fn test_measure_cache_skips_provisional_sizes() {
+        let mut measurer = MeasureCache::new(LoadingMeasurer { ready: false });
+        let content = TextContent::new("loading");
+
+        let size = measurer.measure_text(MeasureTextRequest::from_text_content(&content));
+        assert!(size.provisional);
+        assert!(measurer.is_empty());
+        assert!(measurer.has_pending_measurements());
+
+        measurer.inner_mut().ready = true;
+        let size = measurer.measure_text(MeasureTextRequest::from_text_content(&content));
+        assert_eq!((size.width, size.provisional), (40.0, false));
+        assert_eq!(measurer.len(), 1);
+    }
 }
@@ -5,12 +5,15 @@
 //! text inputs, sliders, and drag values to maintain their state without
 //! requiring the user to manually manage it.
 
-use std::any::Any;
+use crate::node::NodeId;
+use crate::text_edit::TextEditor;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
 /// Unique identifier for widget state storage
 ///
-/// This is typically derived from the widget's ID and type.
+/// This is typically the widget's generated ID or `NodeId`. The state type is
+/// part of the storage key, so the ID only needs to be unique per type.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct WidgetStateId(String);
 
@@ -34,6 +37,12 @@ impl<S: Into<String>> From<S> for WidgetStateId {
     }
 }
 
+impl From<&NodeId> for WidgetStateId {
+    fn from(id: &NodeId) -> Self {
+        Self::new(id.as_str())
+    }
+}
+
 /// State for a text input widget
 #[derive(Debug, Clone, Default)]
 pub struct TextInputState {
@@ -45,6 +54,8 @@ pub struct TextInputState {
     pub selection: Option<(usize, usize)>,
     /// Whether the widget is focused
     pub focused: bool,
+    /// Whether a password field currently shows its text
+    pub revealed: bool,
 }
 
 impl TextInputState {
@@ -57,6 +68,7 @@ impl TextInputState {
             cursor_pos,
             selection: None,
             focused: false,
+            revealed: false,
         }
     }
 
@@ -138,6 +150,16 @@ pub struct SliderState {
     pub dragging: bool,
 }
 
+/// State for a range slider widget
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeSliderState {
+    /// Handle being dragged, 0 for the low end and 1 for the high end
+    pub active: Option<usize>,
+    /// Whether the drag started on stacked handles, so the direction of the
+    /// first movement picks the handle to drag
+    pub stacked: bool,
+}
+
 /// State for a collapsible widget
 #[derive(Debug, Clone)]
 pub struct CollapsibleState {
@@ -182,14 +204,369 @@ impl ToggleState {
     }
 }
 
+/// State for a swipe-to-reveal list row
+#[derive(Debug, Clone, Default)]
+pub struct SwipeState {
+    /// Horizontal offset of the row content in logical pixels
+    ///
+    /// Positive values reveal leading actions, negative values trailing actions.
+    pub offset: f32,
+    /// Whether the row is currently being dragged
+    pub dragging: bool,
+}
+
+impl SwipeState {
+    /// Check whether any actions are revealed
+    pub fn is_open(&self) -> bool {
+        self.offset != 0.0
+    }
+
+    /// Snap the row back to its closed position
+    pub fn close(&mut self) {
+        self.offset = 0.0;
+        self.dragging = false;
+    }
+}
+
+/// State for a resizable node's drag handles
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResizeState {
+    /// Size in logical pixels when the current drag started
+    pub start_size: (f32, f32),
+    /// Pointer movement in logical pixels since the drag started
+    pub drag_offset: (f32, f32),
+    /// Whether a handle is currently being dragged
+    pub dragging: bool,
+}
+
+/// State for a property inspector's color picker
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InspectorState {
+    /// Index of the property whose color picker is open
+    pub open_picker: Option<usize>,
+    /// Hue (degrees), saturation, lightness and alpha edited in the open picker,
+    /// kept so the hue survives while saturation or lightness is zero
+    pub picker_hsla: [f32; 4],
+}
+
+/// State for a gradient editor's selected stop and stop drags
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GradientEditorState {
+    /// Index of the selected stop, whose color is being edited
+    pub selected: usize,
+    /// Hue (degrees), saturation, lightness and alpha of the selected stop,
+    /// kept so the hue survives while saturation or lightness is zero
+    pub picker_hsla: [f32; 4],
+    /// Index of the stop being dragged, if any. Follows the stop as dragging
+    /// reorders the stops.
+    pub dragging: Option<usize>,
+    /// Position of the dragged stop when the drag started
+    pub drag_origin: f32,
+    /// Distance the dragged stop has moved so far, as a fraction of the bar
+    pub drag_offset: f32,
+}
+
+/// State for a floating window's placement and title bar/edge drags
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowState {
+    /// Top-left corner relative to the containing stack, in logical pixels
+    pub position: (f32, f32),
+    /// Size of the expanded window in logical pixels
+    pub size: (f32, f32),
+    /// Whether only the title bar is shown
+    pub collapsed: bool,
+    /// Position and size when the current move or resize drag started
+    pub drag_start: ((f32, f32), (f32, f32)),
+    /// Pointer movement in logical pixels since the drag started
+    pub drag_offset: (f32, f32),
+    /// Whether the title bar or a resize handle is being dragged
+    pub dragging: bool,
+}
+
+impl WindowState {
+    /// Create state for a window first shown at `position` with `size`
+    pub fn new(position: (f32, f32), size: (f32, f32)) -> Self {
+        Self {
+            position,
+            size,
+            ..Default::default()
+        }
+    }
+}
+
+/// Stacking order of all floating windows, shared between them
+#[derive(Debug, Clone, Default)]
+pub struct WindowStackState {
+    /// Window IDs from bottom to top
+    pub order: Vec<String>,
+}
+
+impl WindowStackState {
+    /// Key the shared stack is stored under
+    pub const ID: &'static str = "__window_stack";
+
+    /// Position of a window in the stack, adding new windows on top
+    pub fn depth(&mut self, id: &str) -> usize {
+        match self.order.iter().position(|w| w == id) {
+            Some(depth) => depth,
+            None => {
+                self.order.push(id.to_string());
+                self.order.len() - 1
+            }
+        }
+    }
+
+    /// Move a window to the top of the stack
+    pub fn raise(&mut self, id: &str) {
+        self.order.retain(|w| w != id);
+        self.order.push(id.to_string());
+    }
+}
+
+/// Animation state for a gauge's displayed value
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GaugeState {
+    /// Displayed value when the current animation started
+    pub from: f32,
+    /// Value the gauge is animating toward
+    pub to: f32,
+    /// Frame clock time (see `UiContext::time`) the animation started
+    pub start_time: f64,
+    /// Whether the gauge has been shown before (the first value isn't animated)
+    pub initialized: bool,
+}
+
+impl GaugeState {
+    /// The displayed value at `now` for an animation lasting `duration` seconds
+    pub fn value_at(&self, now: f64, duration: f32) -> f32 {
+        if duration <= 0.0 {
+            return self.to;
+        }
+        let t = ((now - self.start_time) as f32 / duration).clamp(0.0, 1.0);
+        crate::transition::lerp_f32(self.from, self.to, crate::transition::ease_out_cubic(t))
+    }
+
+    /// Start animating toward `value` if it differs from the current target
+    ///
+    /// A new target mid-animation starts from the value shown right now, so
+    /// the needle never jumps.
+    pub fn retarget(&mut self, value: f32, now: f64, duration: f32) {
+        if !self.initialized {
+            *self = Self {
+                from: value,
+                to: value,
+                start_time: now,
+                initialized: true,
+            };
+        } else if value != self.to {
+            self.from = self.value_at(now, duration);
+            self.to = value;
+            self.start_time = now;
+        }
+    }
+}
+
+/// State for a scrollbar attached to a scroll container
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrollbarState {
+    /// Scroll offset seen last frame, to notice scrolling from any source
+    pub last_offset: f32,
+    /// Frame clock time (see `UiContext::time`) of the last scroll, hover or drag
+    pub last_activity: f64,
+    /// Whether the thumb is currently being dragged
+    pub dragging: bool,
+}
+
+/// State for the open menus of a menu bar or context menu
+#[derive(Debug, Clone, Default)]
+pub struct MenuState {
+    /// Open menus, outermost first: the open menu's index in the bar, then the
+    /// item index of each open submenu
+    pub open_path: Vec<usize>,
+    /// Highlighted item in the innermost open menu
+    pub highlighted: Option<usize>,
+    /// ID of the item hovered last frame, so the highlight only follows the
+    /// pointer when it moves onto another item
+    pub hovered_item: Option<String>,
+    /// Where an open context menu sits relative to its target's top-left
+    /// corner, in physical pixels
+    pub offset: (f32, f32),
+}
+
+impl MenuState {
+    /// Check whether any menu is open
+    pub fn is_open(&self) -> bool {
+        !self.open_path.is_empty()
+    }
+
+    /// Close all open menus
+    pub fn close(&mut self) {
+        self.open_path.clear();
+        self.highlighted = None;
+        self.hovered_item = None;
+    }
+}
+
+/// State for a modal dialog's focus trap
+#[derive(Debug, Clone, Default)]
+pub struct ModalState {
+    /// Whether the modal was open last frame, to notice it opening and closing
+    pub was_open: bool,
+    /// Widget that was focused before the modal opened, refocused when it closes
+    pub restore_focus: Option<NodeId>,
+}
+
+/// State for a virtualized list's row measurements and scroll anchor
+#[derive(Debug, Clone, Default)]
+pub struct ListViewState {
+    /// Measured row heights in layout pixels, by item key
+    pub heights: HashMap<u64, f32>,
+    /// Keys of the rows built last frame, in order
+    pub built: Vec<u64>,
+    /// Top edge of the first built row last frame, in layout pixels
+    pub built_top: f32,
+    /// Scroll offset the rows were built for last frame
+    pub offset: f32,
+}
+
+/// State for a plot's view
+#[derive(Debug, Clone, Default)]
+pub struct PlotState {
+    /// Visible x range after panning or zooming; `None` follows the data
+    pub x_range: Option<(f32, f32)>,
+    /// Visible y range after panning or zooming; `None` follows the data
+    pub y_range: Option<(f32, f32)>,
+}
+
+/// State for a timeline's view, selection and drags
+#[derive(Debug, Clone, Default)]
+pub struct TimelineState {
+    /// Visible time range after panning or zooming; `None` shows everything
+    pub view: Option<(f32, f32)>,
+    /// Selected keyframes as (track, keyframe) indices
+    pub selected: Vec<(usize, usize)>,
+    /// Times of the dragged keyframes when the drag started
+    pub drag_origin: Vec<(usize, usize, f32)>,
+    /// Time the dragged keyframes have moved by so far
+    pub drag_offset: f32,
+    /// Corners of the selection box being dragged, in logical pixels local
+    /// to the tracks
+    pub selection_box: Option<([f32; 2], [f32; 2])>,
+}
+
+impl TimelineState {
+    /// Whether a keyframe is selected
+    pub fn is_selected(&self, track: usize, key: usize) -> bool {
+        self.selected.contains(&(track, key))
+    }
+}
+
+/// State for a drag-to-reorder list's drag in progress
+#[derive(Debug, Clone, Default)]
+pub struct ReorderState {
+    /// Index of the item being dragged, if any
+    pub dragging: Option<usize>,
+    /// Index the dragged item would be dropped at, in the reordered list
+    pub target: usize,
+    /// Top edge and height of each row when the drag started, in layout
+    /// pixels relative to the first row
+    pub rows: Vec<(f32, f32)>,
+    /// Width of the dragged row in layout pixels, for its ghost
+    pub width: f32,
+    /// Pointer position within the dragged row when the drag started, in
+    /// layout pixels
+    pub grab_y: f32,
+    /// How far the placeholder is open in each slot around the other rows,
+    /// from 0 to 1
+    pub openness: Vec<f32>,
+    /// Frame clock time (see `UiContext::time`) the openness was last
+    /// advanced at
+    pub last_time: f64,
+}
+
+/// State for a search input's suggestion popover
+#[derive(Debug, Clone, Default)]
+pub struct SearchInputState {
+    /// Whether the suggestions are shown
+    pub open: bool,
+    /// Suggestion highlighted with the arrow keys or the pointer
+    pub highlighted: Option<usize>,
+    /// Suggestion hovered last frame, so the highlight only follows the
+    /// pointer when it moves onto another suggestion
+    pub hovered: Option<usize>,
+    /// Whether the query changed since the filter callback last ran
+    pub pending: bool,
+    /// Frame clock time (see `UiContext::time`) of the last edit
+    pub last_edit: f64,
+}
+
+/// State for a numeric stepper's text field
+#[derive(Debug, Clone, Default)]
+pub struct NumberInputState {
+    /// Editor for the typed value while the field is focused
+    pub editor: TextEditor,
+    /// Whether the field is being edited; the value is shown formatted otherwise
+    pub editing: bool,
+    /// Whether the click that focused the field is yet to arrive; it keeps the
+    /// value selected instead of placing the caret
+    pub focus_click: bool,
+}
+
+/// State for a multi-line text area's editor
+#[derive(Debug, Clone, Default)]
+pub struct TextAreaState {
+    /// Editor holding the text, caret, selection and undo history
+    pub editor: TextEditor,
+    /// Value the editor was last synced with, to notice outside changes
+    pub synced: String,
+    /// Scroll offset when the current drag selection started, in layout
+    /// pixels; `None` when not drag selecting
+    pub drag_scroll: Option<f32>,
+}
+
+/// State for a code editor
+#[derive(Debug, Clone, Default)]
+pub struct CodeEditorState {
+    /// Editor holding the code, caret, selection and undo history
+    pub editor: TextEditor,
+    /// Value the editor was last synced with, to notice outside changes
+    pub synced: String,
+    /// Scroll offsets when the current drag selection started, in layout
+    /// pixels; `None` when not drag selecting
+    pub drag_scroll: Option<(f32, f32)>,
+}
+
+/// Storage key: the state type plus the widget's ID
+///
+/// Including the type means two widgets that happen to share an ID (or one
+/// widget storing several kinds of state) can never stomp each other.
+type StateKey = (TypeId, WidgetStateId);
+
+/// Cleanup callback registered for a state type, called with the type-erased state
+type CleanupFn = Box<dyn FnMut(&WidgetStateId, &mut dyn Any)>;
+
 /// Widget memory - stores internal state for all widgets
 ///
 /// This is a type-erased storage that allows widgets to store arbitrary
-/// state that persists across frames. Each widget type should use a
-/// consistent state type (e.g., `TextInputState` for text inputs).
+/// state that persists across frames. Entries are namespaced by state type,
+/// so `memory.get_or_insert_default::<SliderState>(id)` and
+/// `memory.get_or_insert_default::<ToggleState>(id)` refer to different entries even
+/// for the same ID.
+///
+/// # Example
+///
+/// ```ignore
+/// let state = ctx.memory().get_or_insert_default::<SliderState>(&node_id);
+/// state.dragging = true;
+///
+/// // Release resources held by a state type whenever its entries are dropped
+/// ctx.memory().on_cleanup::<MyEditorState>(|id, state| state.release());
+/// ```
 pub struct WidgetMemory {
     /// Type-erased storage for widget states
-    states: HashMap<WidgetStateId, Box<dyn Any>>,
+    states: HashMap<StateKey, Box<dyn Any>>,
+    /// Per-type callbacks run when entries are removed
+    cleanups: HashMap<TypeId, CleanupFn>,
 }
 
 impl WidgetMemory {
@@ -197,10 +574,15 @@ impl WidgetMemory {
     pub fn new() -> Self {
         Self {
             states: HashMap::new(),
+            cleanups: HashMap::new(),
         }
     }
 
-    /// Get or create state for a widget
+    fn key<T: 'static>(id: impl Into<WidgetStateId>) -> StateKey {
+        (TypeId::of::<T>(), id.into())
+    }
+
+    /// Get or create state of type `T` for a widget
     ///
     /// If state doesn't exist for this ID, creates it using the provided default.
     pub fn get_or_insert<T: 'static>(
@@ -208,44 +590,135 @@ impl WidgetMemory {
         id: impl Into<WidgetStateId>,
         default: T,
     ) -> &mut T {
-        let id = id.into();
-        self.states
-            .entry(id)
-            .or_insert_with(|| Box::new(default))
-            .downcast_mut::<T>()
-            .expect("Widget state type mismatch")
+        self.get_or_insert_with(id, || default)
+    }
+
+    /// Get or create state of type `T` for a widget, using `T::default()`
+    pub fn get_or_insert_default<T: Default + 'static>(
+        &mut self,
+        id: impl Into<WidgetStateId>,
+    ) -> &mut T {
+        self.get_or_insert_with(id, T::default)
     }
 
     /// Get or create state for a widget using Default
+    #[deprecated(since = "0.8.0", note = "use `WidgetMemory::get_or_insert_default`")]
     pub fn get_or_default<T: Default + 'static>(&mut self, id: impl Into<WidgetStateId>) -> &mut T {
-        self.get_or_insert(id, T::default())
+        self.get_or_insert_default(id)
     }
 
-    /// Get state for a widget, if it exists
+    /// Get or create state of type `T` for a widget
+    ///
+    /// If state doesn't exist for this ID, creates it using the provided function.
+    pub fn get_or_insert_with<T: 'static>(
+        &mut self,
+        id: impl Into<WidgetStateId>,
+        default: impl FnOnce() -> T,
+    ) -> &mut T {
+        self.states
+            .entry(Self::key::<T>(id))
+            .or_insert_with(|| Box::new(default()))
+            .downcast_mut::<T>()
+            .expect("state stored under a type key always has that type")
+    }
+
+    /// Get state of type `T` for a widget, if it exists
     pub fn get<T: 'static>(&self, id: impl Into<WidgetStateId>) -> Option<&T> {
-        let id = id.into();
-        self.states.get(&id).and_then(|s| s.downcast_ref::<T>())
+        self.states
+            .get(&Self::key::<T>(id))
+            .and_then(|s| s.downcast_ref::<T>())
     }
 
-    /// Get mutable state for a widget, if it exists
+    /// Get mutable state of type `T` for a widget, if it exists
     pub fn get_mut<T: 'static>(&mut self, id: impl Into<WidgetStateId>) -> Option<&mut T> {
-        let id = id.into();
-        self.states.get_mut(&id).and_then(|s| s.downcast_mut::<T>())
+        self.states
+            .get_mut(&Self::key::<T>(id))
+            .and_then(|s| s.downcast_mut::<T>())
     }
 
-    /// Check if state exists for a widget
+    /// Check if state of any type exists for a widget
     pub fn contains(&self, id: impl Into<WidgetStateId>) -> bool {
-        self.states.contains_key(&id.into())
+        let id = id.into();
+        self.states.keys().any(|(_, key_id)| *key_id == id)
+    }
+
+    /// Check if state of type `T` exists for a widget
+    pub fn contains_type<T: 'static>(&self, id: impl Into<WidgetStateId>) -> bool {
+        self.states.contains_key(&Self::key::<T>(id))
+    }
+
+    /// Register a callback run whenever state of type `T` is removed
+    ///
+    /// Called by `remove`, `remove_type`, `retain` and `clear`. Replaces any
+    /// previously registered callback for `T`.
+    pub fn on_cleanup<T: 'static>(&mut self, mut f: impl FnMut(&WidgetStateId, &mut T) + 'static) {
+        self.cleanups.insert(
+            TypeId::of::<T>(),
+            Box::new(move |id, state| {
+                if let Some(state) = state.downcast_mut::<T>() {
+                    f(id, state);
+                }
+            }),
+        );
+    }
+
+    fn cleanup(&mut self, key: &StateKey, state: &mut Box<dyn Any>) {
+        if let Some(cleanup) = self.cleanups.get_mut(&key.0) {
+            cleanup(&key.1, state.as_mut());
+        }
+    }
+
+    /// Remove state of type `T` for a widget
+    pub fn remove_type<T: 'static>(&mut self, id: impl Into<WidgetStateId>) -> bool {
+        let key = Self::key::<T>(id);
+        match self.states.remove(&key) {
+            Some(mut state) => {
+                self.cleanup(&key, &mut state);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Remove state for a widget
+    /// Remove state of all types for a widget
     pub fn remove(&mut self, id: impl Into<WidgetStateId>) -> bool {
-        self.states.remove(&id.into()).is_some()
+        let id = id.into();
+        self.remove_where(|key| key.1 == id) > 0
+    }
+
+    /// Keep only the states of type `T` for which `f` returns true
+    pub fn retain<T: 'static>(&mut self, mut f: impl FnMut(&WidgetStateId, &mut T) -> bool) {
+        let type_id = TypeId::of::<T>();
+        let removed: Vec<StateKey> = self
+            .states
+            .iter_mut()
+            .filter(|(key, _)| key.0 == type_id)
+            .filter_map(|(key, state)| {
+                let state = state.downcast_mut::<T>()?;
+                (!f(&key.1, state)).then(|| key.clone())
+            })
+            .collect();
+        self.remove_where(|key| removed.contains(key));
+    }
+
+    fn remove_where(&mut self, mut predicate: impl FnMut(&StateKey) -> bool) -> usize {
+        let keys: Vec<StateKey> = self
+            .states
+            .keys()
+            .filter(|k| predicate(k))
+            .cloned()
+            .collect();
+        for key in &keys {
+            if let Some(mut state) = self.states.remove(key) {
+                self.cleanup(key, &mut state);
+            }
+        }
+        keys.len()
     }
 
     /// Clear all widget state
     pub fn clear(&mut self) {
-        self.states.clear();
+        self.remove_where(|_| true);
     }
 
     /// Get the number of stored states
@@ -262,7 +735,7 @@ impl WidgetMemory {
 
     /// Get or create text input state
     pub fn text_input(&mut self, id: impl Into<WidgetStateId>) -> &mut TextInputState {
-        self.get_or_default(id)
+        self.get_or_insert_default(id)
     }
 
     /// Get or create text input state with initial text
@@ -271,7 +744,7 @@ impl WidgetMemory {
         id: impl Into<WidgetStateId>,
         initial_text: impl Into<String>,
     ) -> &mut TextInputState {
-        self.get_or_insert(id, TextInputState::new(initial_text))
+        self.get_or_insert_with(id, || TextInputState::new(initial_text))
     }
 
     /// Get or create drag value state
@@ -280,12 +753,17 @@ impl WidgetMemory {
         id: impl Into<WidgetStateId>,
         initial_value: f32,
     ) -> &mut DragValueState {
-        self.get_or_insert(id, DragValueState::new(initial_value))
+        self.get_or_insert_with(id, || DragValueState::new(initial_value))
     }
 
     /// Get or create slider state
     pub fn slider(&mut self, id: impl Into<WidgetStateId>) -> &mut SliderState {
-        self.get_or_default(id)
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create range slider state
+    pub fn range_slider(&mut self, id: impl Into<WidgetStateId>) -> &mut RangeSliderState {
+        self.get_or_insert_default(id)
     }
 
     /// Get or create collapsible state
@@ -294,7 +772,102 @@ impl WidgetMemory {
         id: impl Into<WidgetStateId>,
         initial_expanded: bool,
     ) -> &mut CollapsibleState {
-        self.get_or_insert(id, CollapsibleState::new(initial_expanded))
+        self.get_or_insert_with(id, || CollapsibleState::new(initial_expanded))
+    }
+
+    /// Get or create swipe row state
+    pub fn swipe(&mut self, id: impl Into<WidgetStateId>) -> &mut SwipeState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create menu bar state
+    pub fn menu(&mut self, id: impl Into<WidgetStateId>) -> &mut MenuState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create list view state
+    pub fn list_view(&mut self, id: impl Into<WidgetStateId>) -> &mut ListViewState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create number input state
+    pub fn number_input(&mut self, id: impl Into<WidgetStateId>) -> &mut NumberInputState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create reorderable list state
+    pub fn reorder(&mut self, id: impl Into<WidgetStateId>) -> &mut ReorderState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create search input state
+    pub fn search_input(&mut self, id: impl Into<WidgetStateId>) -> &mut SearchInputState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create plot state
+    pub fn plot(&mut self, id: impl Into<WidgetStateId>) -> &mut PlotState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create timeline state
+    pub fn timeline(&mut self, id: impl Into<WidgetStateId>) -> &mut TimelineState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create text area state
+    pub fn text_area(&mut self, id: impl Into<WidgetStateId>) -> &mut TextAreaState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create code editor state
+    pub fn code_editor(&mut self, id: impl Into<WidgetStateId>) -> &mut CodeEditorState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create modal state
+    pub fn modal(&mut self, id: impl Into<WidgetStateId>) -> &mut ModalState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create resize handle state
+    pub fn resize(&mut self, id: impl Into<WidgetStateId>) -> &mut ResizeState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create property inspector state
+    pub fn inspector(&mut self, id: impl Into<WidgetStateId>) -> &mut InspectorState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create gradient editor state
+    pub fn gradient_editor(&mut self, id: impl Into<WidgetStateId>) -> &mut GradientEditorState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create floating window state
+    pub fn window(
+        &mut self,
+        id: impl Into<WidgetStateId>,
+        position: (f32, f32),
+        size: (f32, f32),
+    ) -> &mut WindowState {
+        self.get_or_insert_with(id, || WindowState::new(position, size))
+    }
+
+    /// Get the stacking order shared by all floating windows
+    pub fn window_stack(&mut self) -> &mut WindowStackState {
+        self.get_or_insert_default(WindowStackState::ID)
+    }
+
+    /// Get or create gauge state
+    pub fn gauge(&mut self, id: impl Into<WidgetStateId>) -> &mut GaugeState {
+        self.get_or_insert_default(id)
+    }
+
+    /// Get or create scrollbar state
+    pub fn scrollbar(&mut self, id: impl Into<WidgetStateId>) -> &mut ScrollbarState {
+        self.get_or_insert_default(id)
     }
 
     /// Get or create toggle state
@@ -303,7 +876,7 @@ impl WidgetMemory {
         id: impl Into<WidgetStateId>,
         initial_checked: bool,
     ) -> &mut ToggleState {
-        self.get_or_insert(id, ToggleState::new(initial_checked))
+        self.get_or_insert_with(id, || ToggleState::new(initial_checked))
     }
 }
 
@@ -317,6 +890,7 @@ impl std::fmt::Debug for WidgetMemory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WidgetMemory")
             .field("num_states", &self.states.len())
+            .field("num_cleanups", &self.cleanups.len())
             .finish()
     }
 }
@@ -384,6 +958,75 @@ mod tests {
         assert!(memory.get::<TextInputState>("widget2").is_none());
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_widget_memory_inserts_only_missing_state() {
+        let mut memory = WidgetMemory::new();
+
+        memory.get_or_insert("slider", SliderState { dragging: true });
+        assert!(
+            memory
+                .get_or_insert("slider", SliderState::default())
+                .dragging
+        );
+        assert!(
+            memory
+                .get_or_insert_default::<SliderState>("slider")
+                .dragging
+        );
+        assert!(memory.get_or_default::<SliderState>("slider").dragging);
+        assert_eq!(memory.len(), 1);
+    }
+
+    #[test]
+    fn test_widget_memory_namespaces_by_type() {
+        let mut memory = WidgetMemory::new();
+        let id = NodeId::new("shared");
+
+        memory.get_or_insert_default::<SliderState>(&id).dragging = true;
+        memory.get_or_insert_default::<ToggleState>(&id).checked = true;
+
+        assert_eq!(memory.len(), 2);
+        assert!(memory.get::<SliderState>(&id).unwrap().dragging);
+        assert!(memory.get::<ToggleState>(&id).unwrap().checked);
+        assert!(memory.contains_type::<SliderState>("shared"));
+        assert!(!memory.contains_type::<CollapsibleState>("shared"));
+
+        assert!(memory.remove_type::<SliderState>(&id));
+        assert!(memory.contains(&id));
+        assert!(memory.remove(&id));
+        assert!(memory.is_empty());
+    }
+
+    #[test]
+    fn test_widget_memory_cleanup_callbacks() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let cleaned = Rc::new(RefCell::new(Vec::new()));
+        let mut memory = WidgetMemory::new();
+        let log = cleaned.clone();
+        memory.on_cleanup::<TextInputState>(move |id, state| {
+            log.borrow_mut().push((id.clone(), state.text.clone()));
+        });
+
+        memory.text_input_with_text("a", "first");
+        memory.text_input_with_text("b", "second");
+        memory.text_input_with_text("c", "third");
+        memory.slider("a");
+
+        memory.retain::<TextInputState>(|_, state| state.text != "second");
+        assert_eq!(
+            *cleaned.borrow(),
+            vec![(WidgetStateId::new("b"), "second".to_string())]
+        );
+
+        memory.remove("a");
+        memory.clear();
+        assert_eq!(cleaned.borrow().len(), 3);
+        assert!(memory.is_empty());
+    }
+
     #[test]
     fn test_collapsible_state() {
         let mut state = CollapsibleState::new(false);
@@ -404,4 +1047,36 @@ mod tests {
         state.toggle();
         assert!(state.checked);
     }
+
+    #[test]
+    fn test_window_stack_raises_to_top() {
+        let mut stack = WindowStackState::default();
+        assert_eq!(stack.depth("a"), 0);
+        assert_eq!(stack.depth("b"), 1);
+        assert_eq!(stack.depth("a"), 0);
+
+        stack.raise("a");
+        assert_eq!(stack.depth("b"), 0);
+        assert_eq!(stack.depth("a"), 1);
+        assert_eq!(stack.depth("c"), 2);
+    }
+
+    #[test]
+    fn test_gauge_animates_from_displayed_value() {
+        let mut gauge = GaugeState::default();
+        gauge.retarget(10.0, 0.0, 1.0);
+        assert_eq!(gauge.value_at(0.0, 1.0), 10.0);
+
+        gauge.retarget(20.0, 1.0, 1.0);
+        assert_eq!(gauge.value_at(1.0, 1.0), 10.0);
+        let halfway = gauge.value_at(1.5, 1.0);
+        assert!(halfway > 15.0 && halfway < 20.0);
+        assert_eq!(gauge.value_at(2.0, 1.0), 20.0);
+
+        // Retargeting mid-animation continues from where the needle is
+        gauge.retarget(0.0, 1.5, 1.0);
+        assert_eq!(gauge.value_at(1.5, 1.0), halfway);
+        assert_eq!(gauge.value_at(3.0, 1.0), 0.0);
+        assert_eq!(gauge.value_at(1.5, 0.0), 0.0);
+    }
 }
@@ -0,0 +1,218 @@
+//! Nine-slice geometry for skinned backgrounds
+//!
+//! A nine-slice texture is split by four insets into corners, edges and a
+//! center. When drawn into a rect of any size the corners keep their size,
+//! the edges stretch along one axis and the center stretches along both, so
+//! one small frame texture can skin panels and buttons of every size.
+//!
+//! `Style::nine_slice` paints a node's background with a [`NineSliceImage`].
+//! Textures are registered with the backend, which hands out the
+//! [`TextureId`] the image refers to (`Renderer::register_texture` in
+//! `astra-gui-wgpu`), and each of the nine patches is drawn as one textured
+//! quad.
+
+use crate::color::Color;
+use crate::primitives::Rect;
+
+/// Handle of a texture registered with the rendering backend
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextureId(pub u64);
+
+/// Slice insets of a nine-slice texture, in texels
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NineSlice {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+    /// Destination pixels per texel for the corners and edge thickness
+    /// (logical pixels in a `Style`, physical once shapes are collected)
+    pub scale: f32,
+}
+
+/// Nine-slice background: a texture drawn with fixed corners and stretched
+/// edges and center
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NineSliceImage {
+    /// Texture to draw
+    pub texture: TextureId,
+    /// Size of the texture in texels
+    pub texture_size: [f32; 2],
+    /// Slice insets of the texture
+    pub slice: NineSlice,
+    /// Color multiplied with every texel (white to draw the texture as is)
+    pub tint: Color,
+}
+
+/// One patch of a nine-slice: where it is drawn and which texture region it samples
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NinePatch {
+    /// Destination rect
+    pub rect: Rect,
+    /// Texture coordinates, normalized to 0.0-1.0
+    pub uv: Rect,
+}
+
+impl NineSlice {
+    /// Create slice insets for each side
+    pub const fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+            scale: 1.0,
+        }
+    }
+
+    /// Create slice insets that are equal on all sides
+    pub const fn uniform(inset: f32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+
+    /// Set how many destination pixels a texel of the corners covers
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Split `dest` into nine patches for a texture of `texture_size` texels
+    ///
+    /// Patches are in row-major order (top-left, top, top-right, left, center, ...).
+    /// If `dest` is too small for two opposite corners, both shrink
+    /// proportionally (like CSS `border-image`), so patches never overlap.
+    pub fn patches(&self, dest: Rect, texture_size: [f32; 2]) -> [NinePatch; 9] {
+        let fit = |start: f32, end: f32, available: f32| {
+            let total = start + end;
+            if total > available && total > 0.0 {
+                let shrink = available.max(0.0) / total;
+                (start * shrink, end * shrink)
+            } else {
+                (start, end)
+            }
+        };
+        let (left, right) = fit(
+            self.left * self.scale,
+            self.right * self.scale,
+            dest.width(),
+        );
+        let (top, bottom) = fit(
+            self.top * self.scale,
+            self.bottom * self.scale,
+            dest.height(),
+        );
+
+        let xs = [
+            dest.min[0],
+            dest.min[0] + left,
+            dest.max[0] - right,
+            dest.max[0],
+        ];
+        let ys = [
+            dest.min[1],
+            dest.min[1] + top,
+            dest.max[1] - bottom,
+            dest.max[1],
+        ];
+
+        // An empty texture has no texels to slice; sample it whole instead of
+        // dividing by zero
+        let fraction = |inset: f32, size: f32| {
+            if size > 0.0 {
+                (inset / size).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+        let [texture_w, texture_h] = texture_size;
+        let us = [
+            0.0,
+            fraction(self.left, texture_w),
+            1.0 - fraction(self.right, texture_w),
+            1.0,
+        ];
+        let vs = [
+            0.0,
+            fraction(self.top, texture_h),
+            1.0 - fraction(self.bottom, texture_h),
+            1.0,
+        ];
+
+        std::array::from_fn(|i| {
+            let (col, row) = (i % 3, i / 3);
+            NinePatch {
+                rect: Rect::new([xs[col], ys[row]], [xs[col + 1], ys[row + 1]]),
+                uv: Rect::new([us[col], vs[row]], [us[col + 1], vs[row + 1]]),
+            }
+        })
+    }
+}
+
+impl NineSliceImage {
+    /// Create a nine-slice image of a `texture_size` texture, drawn untinted
+    pub const fn new(texture: TextureId, texture_size: [f32; 2], slice: NineSlice) -> Self {
+        Self {
+            texture,
+            texture_size,
+            slice,
+            tint: Color::rgba(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Set the color multiplied with every texel
+    pub const fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Patches of this image drawn into `dest`
+    pub fn patches(&self, dest: Rect) -> [NinePatch; 9] {
+        self.slice.patches(dest, self.texture_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corners_keep_size_and_center_stretches() {
+        let slice = NineSlice::uniform(8.0).with_scale(2.0);
+        let patches = slice.patches(Rect::new([10.0, 20.0], [110.0, 70.0]), [32.0, 32.0]);
+
+        assert_eq!(patches[0].rect, Rect::new([10.0, 20.0], [26.0, 36.0]));
+        assert_eq!(patches[0].uv, Rect::new([0.0, 0.0], [0.25, 0.25]));
+        assert_eq!(patches[4].rect, Rect::new([26.0, 36.0], [94.0, 54.0]));
+        assert_eq!(patches[4].uv, Rect::new([0.25, 0.25], [0.75, 0.75]));
+        assert_eq!(patches[8].rect, Rect::new([94.0, 54.0], [110.0, 70.0]));
+    }
+
+    #[test]
+    fn test_corners_shrink_when_rect_is_too_small() {
+        let slice = NineSlice::new(0.0, 30.0, 0.0, 10.0);
+        let patches = slice.patches(Rect::new([0.0, 0.0], [20.0, 10.0]), [64.0, 64.0]);
+
+        assert_eq!(patches[3].rect.width(), 5.0);
+        assert_eq!(patches[4].rect.width(), 0.0);
+        assert_eq!(patches[5].rect.width(), 15.0);
+    }
+
+    #[test]
+    fn test_empty_texture_samples_whole_range() {
+        let slice = NineSlice::uniform(4.0);
+        let patches = slice.patches(Rect::new([0.0, 0.0], [40.0, 40.0]), [0.0, 0.0]);
+
+        for patch in &patches {
+            assert!(patch
+                .uv
+                .min
+                .iter()
+                .chain(&patch.uv.max)
+                .all(|v| v.is_finite()));
+        }
+        assert_eq!(patches[4].uv, Rect::new([0.0, 0.0], [1.0, 1.0]));
+    }
+}
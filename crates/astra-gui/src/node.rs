@@ -1,12 +1,52 @@
+use crate::canvas::Painter;
+use crate::color::Color;
+use crate::constraint::{self, Constraint};
 use crate::content::{Content, HorizontalAlign, VerticalAlign};
+use crate::cursor::CursorIcon;
+use crate::drag::DragConfig;
+use crate::filter::{ColorMatrix, Filter};
+use crate::interactive_state::AUTO_ID_PREFIX;
 use crate::layout::{
-    ComputedLayout, Layout, Overflow, ScrollDirection, Size, Spacing, TransformOrigin, Translation,
-    ZIndex,
+    ComputedLayout, Layout, Overflow, ScrollDirection, ScrollMomentum, ScrollSmoothing, Size,
+    Spacing, TransformOrigin, Translation, ZIndex,
 };
 use crate::measure::{ContentMeasurer, IntrinsicSize, MeasureTextRequest};
-use crate::primitives::{Rect, Shape};
+use crate::output::{ShapeCache, ShapeCacheCell};
+use crate::platform::WindowAction;
+use crate::primitives::{Outline, Point, Rect, Shadow, Shape};
 use crate::style::Style;
+use crate::stylesheet::StyleRule;
+use crate::text_edit::TextEditDecorations;
+use crate::tokens::{StyleProperty, TokenRef, VarBinding, VarState};
 use crate::transition::Transition;
+use crate::UiContext;
+
+/// Speed below which a flick stops, in pixels per second
+const MIN_FLICK_VELOCITY: f32 = 10.0;
+
+/// Stiffness of the spring pulling overscrolled content back, per second squared
+const OVERSCROLL_STIFFNESS: f32 = 150.0;
+
+/// Extra damping applied while overscrolled, per second
+const OVERSCROLL_DAMPING: f32 = 20.0;
+
+/// Progress of an eased scroll animation and of scroll momentum, persisted
+/// across UI rebuilds
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct ScrollAnimation {
+    /// Scroll offset when the animation started
+    pub from: (f32, f32),
+    /// Scroll target the animation is heading to
+    pub to: (f32, f32),
+    /// Seconds since the animation started
+    pub elapsed: f32,
+    /// Precise scroll applied this frame, for measuring the flick speed
+    pub flick_input: (f32, f32),
+    /// Scroll speed in pixels per second, while tracking a flick or coasting
+    pub velocity: (f32, f32),
+    /// Whether precise scrolling arrived last frame, i.e. a flick is underway
+    pub tracking: bool,
+}
 
 /// Determines how a node should be placed within its parent.
 ///
@@ -78,6 +118,37 @@ impl From<String> for NodeId {
     }
 }
 
+/// Paint function drawing a node's content with a `Painter`
+pub(crate) type PaintFn = Box<dyn Fn(&mut Painter)>;
+
+/// Closure run when a node is clicked
+pub(crate) type ClickHandler = Box<dyn FnMut(&mut UiContext)>;
+
+/// Closure run with the pointer movement while a node is dragged
+pub(crate) type DragHandler = Box<dyn FnMut(&mut UiContext, Point)>;
+
+/// Closure run with the scroll delta while a node is scrolled
+pub(crate) type ScrollHandler = Box<dyn FnMut(&mut UiContext, (f32, f32))>;
+
+/// Closures a node runs for its events, set with `Node::on_click`,
+/// `Node::on_drag` and `Node::on_scroll`
+#[derive(Default)]
+pub(crate) struct EventHandlers {
+    pub(crate) click: Option<ClickHandler>,
+    pub(crate) drag: Option<DragHandler>,
+    pub(crate) scroll: Option<ScrollHandler>,
+}
+
+/// The settings that decide the space a node takes in its parent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Footprint {
+    width: Size,
+    height: Size,
+    margin: Spacing,
+    place: Option<Place>,
+    zoom: Option<f32>,
+}
+
 /// A UI node that can contain a shape, content, and/or children
 ///
 /// Nodes can be either:
@@ -108,6 +179,11 @@ pub struct Node {
     /// Zoom level for browser-style zoom (scales logical pixels to physical pixels)
     /// None means inherit from parent. 1.0 = 100%, 2.0 = 200%, etc.
     zoom: Option<f32>,
+    /// Multiplier for font sizes only, leaving paddings and layout alone
+    /// None means inherit from parent. 1.0 = 100%, 1.5 = 150%, etc.
+    text_scale: Option<f32>,
+    /// Text scale after inheritance, resolved at the start of layout
+    resolved_text_scale: f32,
     /// Pan offset for camera-style zoom (typically applied at root node)
     pan_offset: Translation,
     /// Transform origin for rotation and scale
@@ -156,14 +232,36 @@ pub struct Node {
     ///
     /// Default: ScrollDirection::Inverted (natural scrolling)
     scroll_direction: ScrollDirection,
+    /// How the scroll offset animates toward the scroll target
+    ///
+    /// Default: ScrollSmoothing::Exponential(10.0)
+    scroll_smoothing: ScrollSmoothing,
+    /// Kinetic scrolling after trackpad flicks, if enabled
+    scroll_momentum: Option<ScrollMomentum>,
+    /// Progress of an in-flight `ScrollSmoothing::Eased` animation
+    scroll_animation: ScrollAnimation,
     /// Opacity of this node and all its children (0.0 = transparent, 1.0 = opaque).
     ///
     /// Default: 1.0 (fully opaque).
     opacity: f32,
+    /// Color filter of this node and all its children (composed `with_filter` calls)
+    filter: Option<ColorMatrix>,
     /// Optional shape to render for this node (background)
     shape: Option<Shape>,
+    /// Optional outline drawn outside the node rect (set via `Style::outline`)
+    outline: Option<Outline>,
+    /// Optional drop shadow behind the node's shape (set via `Style::shadow`)
+    shadow: Option<Shadow>,
+    /// Optional color composited over the shape's fill (set via `Style::tint`)
+    tint: Option<Color>,
     /// Optional content (text, inputs, etc.) - content nodes cannot have children
     content: Option<Content>,
+    /// Caret and selection drawn with the text content of an editor
+    text_edit: Option<TextEditDecorations>,
+    /// Paint function drawing into the content rect once layout is known
+    painter: Option<PaintFn>,
+    /// Closures run for this node's events (`on_click`, `on_drag`, `on_scroll`)
+    handlers: EventHandlers,
     /// Child nodes (not allowed if content is Some)
     children: Vec<Node>,
     /// Computed layout (filled during layout pass)
@@ -174,12 +272,30 @@ pub struct Node {
     hover_style: Option<Style>,
     /// Style to apply when active/pressed (merged with base + hover)
     active_style: Option<Style>,
+    /// Style to apply when focused (merged with base, below hover/active)
+    focused_style: Option<Style>,
+    /// Style to apply when selected/checked (merged with base, below focus/hover/active)
+    selected_style: Option<Style>,
     /// Style to apply when disabled (overrides all other styles)
     disabled_style: Option<Style>,
     /// Whether this node is disabled (cannot be interacted with)
     disabled: bool,
+    /// Whether this node is selected/checked (e.g. the current tab or an enabled toggle)
+    selected: bool,
     /// Transition configuration for style changes
     transition: Option<Transition>,
+    /// Stylesheet classes, resolved against the `StyleSheet` on the `UiContext`
+    ///
+    /// Later classes take precedence over earlier ones; the node's own styles
+    /// take precedence over all classes.
+    classes: Vec<String>,
+    /// Style properties bound to design tokens, resolved against the
+    /// `TokenTable` on the `UiContext`
+    vars: Vec<VarBinding>,
+    /// Text color inherited by descendant text content (CSS-like cascade)
+    text_color: Option<Color>,
+    /// Font size inherited by descendant text content (CSS-like cascade)
+    font_size: Option<Size>,
     /// Z-index for controlling rendering order (None = inherit from parent)
     ///
     /// Higher values render on top. Default: None (inherits parent's z-index or 0)
@@ -192,6 +308,32 @@ pub struct Node {
     ///
     /// When set, bypasses normal Size resolution during layout
     height_override: Option<f32>,
+    /// Hover delay before a `PeekRequested` event fires for this node
+    ///
+    /// Default: None (node is not a peek anchor)
+    peek_delay: Option<std::time::Duration>,
+    /// Cursor icon shown while the pointer is over this node (None = inherit from parent)
+    cursor: Option<CursorIcon>,
+    /// Threshold, axis lock and snapping used when dragging this node
+    drag: DragConfig,
+    /// What pressing this node does to the window (move or resize it)
+    window_action: Option<WindowAction>,
+    /// Whether this node blocks pointer input to everything drawn below it
+    input_barrier: bool,
+    /// Whether this node confines focus, keys and pointer input to its subtree
+    focus_scope: bool,
+    /// Whether this node takes keyboard focus and is visited by Tab
+    focusable: bool,
+    /// Whether drags carrying a payload can be dropped on this node
+    drop_target: bool,
+    /// Whether this node's laid-out size is recorded for `UiContext::measured_size`
+    measure_size: bool,
+    /// Constraints relating this node's edges to other nodes, solved after flow layout
+    constraints: Vec<Constraint>,
+    /// Version of this node's own settings, for retained-mode diffing
+    revision: Option<u64>,
+    /// Shapes collected for this subtree by `RetainedTree::output`
+    shape_cache: ShapeCacheCell,
 }
 
 impl Node {
@@ -206,6 +348,8 @@ impl Node {
             rotation: 0.0,
             scale: 1.0,
             zoom: None,
+            text_scale: None,
+            resolved_text_scale: 1.0,
             pan_offset: Translation::ZERO,
             transform_origin: TransformOrigin::center(),
             padding: Spacing::ZERO,
@@ -219,20 +363,49 @@ impl Node {
             scroll_target: (0.0, 0.0),
             scroll_speed: 3.0,
             scroll_direction: ScrollDirection::default(),
+            scroll_smoothing: ScrollSmoothing::default(),
+            scroll_momentum: None,
+            scroll_animation: ScrollAnimation::default(),
             opacity: 1.0,
+            filter: None,
             shape: None,
+            outline: None,
+            shadow: None,
+            tint: None,
             content: None,
+            text_edit: None,
+            painter: None,
+            handlers: EventHandlers::default(),
             children: Vec::new(),
             computed: None,
             base_style: None,
             hover_style: None,
             active_style: None,
+            focused_style: None,
+            selected_style: None,
             disabled_style: None,
             disabled: false,
+            selected: false,
             transition: None,
+            classes: Vec::new(),
+            vars: Vec::new(),
+            text_color: None,
+            font_size: None,
             z_index: None,
             width_override: None,
             height_override: None,
+            peek_delay: None,
+            cursor: None,
+            drag: DragConfig::new(),
+            window_action: None,
+            input_barrier: false,
+            focus_scope: false,
+            focusable: false,
+            drop_target: false,
+            measure_size: false,
+            constraints: Vec::new(),
+            revision: None,
+            shape_cache: ShapeCacheCell::default(),
         }
     }
 
@@ -252,6 +425,23 @@ impl Node {
         self.id.as_ref()
     }
 
+    /// Set the revision of this node's own settings, for `RetainedTree`
+    ///
+    /// While a rebuilt node keeps its ID (or position, without one) and
+    /// revision, the retained tree keeps the previous node with its layout
+    /// and only looks at its children. Bump the revision whenever anything
+    /// set on the node itself changes, including its content, styles and
+    /// event closures. Nodes without a revision are always taken as changed.
+    pub fn with_revision(mut self, revision: u64) -> Self {
+        self.revision = Some(revision);
+        self
+    }
+
+    /// Get the revision of this node's own settings, if set
+    pub fn revision(&self) -> Option<u64> {
+        self.revision
+    }
+
     /// Set an auto-generated ID (internal use only, for interactive styles)
     #[doc(hidden)]
     pub fn set_auto_id(&mut self, id: NodeId) {
@@ -260,6 +450,18 @@ impl Node {
         }
     }
 
+    /// Remove an auto-generated ID, so the next frame assigns one for the
+    /// node's current place in the tree
+    pub(crate) fn clear_auto_id(&mut self) {
+        if self
+            .id
+            .as_ref()
+            .is_some_and(|id| id.as_str().starts_with(AUTO_ID_PREFIX))
+        {
+            self.id = None;
+        }
+    }
+
     /// Set the width
     pub fn with_width(mut self, width: Size) -> Self {
         self.width = width;
@@ -315,6 +517,16 @@ impl Node {
         self
     }
 
+    /// Set the text scale, which multiplies font sizes but not layout
+    /// 1.0 = 100%, 1.5 = 150%, etc.
+    /// If set, overrides parent's text scale. If None, inherits from parent.
+    /// `UiContext::inject_dimension_overrides` sets the root's from
+    /// `UiContext::set_text_scale`.
+    pub fn with_text_scale(mut self, text_scale: f32) -> Self {
+        self.text_scale = Some(text_scale);
+        self
+    }
+
     /// Set the pan offset for camera-style zoom (typically used on root node)
     pub fn with_pan_offset(mut self, pan_offset: Translation) -> Self {
         self.pan_offset = pan_offset;
@@ -394,12 +606,51 @@ impl Node {
         self
     }
 
+    /// Set how scrolling animates toward its target (default: `ScrollSmoothing::Exponential(10.0)`)
+    ///
+    /// Use `ScrollSmoothing::Instant` for deterministic scrolling, e.g. in tests.
+    pub fn with_scroll_smoothing(mut self, smoothing: ScrollSmoothing) -> Self {
+        self.scroll_smoothing = smoothing;
+        self
+    }
+
+    /// Keep scrolling after trackpad flicks, slowing down with the given
+    /// momentum (default: no momentum)
+    ///
+    /// The flick speed is measured from precise scroll deltas; when they
+    /// stop, the container coasts on and, with `ScrollMomentum::overscroll`,
+    /// rubber-bands past the ends before springing back. Wheel steps and
+    /// programmatic scrolls stop it.
+    pub fn with_scroll_momentum(mut self, momentum: ScrollMomentum) -> Self {
+        self.scroll_momentum = Some(momentum);
+        self
+    }
+
     /// Set the opacity of this node and all its children (0.0 = transparent, 1.0 = opaque).
     pub fn with_opacity(mut self, opacity: f32) -> Self {
         self.opacity = opacity.clamp(0.0, 1.0);
         self
     }
 
+    /// Apply a color filter to this node and all its children
+    ///
+    /// Calling this again stacks filters, applied in call order. The subtree is
+    /// drawn into an offscreen layer that is filtered as a whole (see the
+    /// `filter` module docs).
+    ///
+    /// ```ignore
+    /// // Disabled panel
+    /// panel.with_filter(Filter::Grayscale(0.8)).with_opacity(0.6)
+    /// ```
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        let matrix = filter.matrix();
+        self.filter = Some(match self.filter {
+            Some(previous) => previous.then(&matrix),
+            None => matrix,
+        });
+        self
+    }
+
     /// Set the shape
     pub fn with_shape(mut self, shape: Shape) -> Self {
         self.shape = Some(shape);
@@ -416,6 +667,57 @@ impl Node {
         self
     }
 
+    /// Draw an editor's caret and selection with this node's text content
+    ///
+    /// The selection goes below the text and the caret above it, aligned
+    /// like the text within the content rect.
+    pub fn with_text_edit(mut self, decorations: TextEditDecorations) -> Self {
+        self.text_edit = Some(decorations);
+        self
+    }
+
+    /// Draw into this node with a [`Painter`]
+    ///
+    /// The function runs when the output is collected, after layout, with
+    /// coordinates local to the content rect in logical pixels. The drawing
+    /// goes above the node's background and content and below its children.
+    pub fn with_painter(mut self, paint: impl Fn(&mut Painter) + 'static) -> Self {
+        self.painter = Some(Box::new(paint));
+        self
+    }
+
+    /// Run `f` when this node is clicked
+    ///
+    /// Closures run in `UiContext::end_frame`, right after the frame's events
+    /// are dispatched, so simple apps can react to a node without giving it
+    /// an ID and matching events by ID. A node with closures gets an automatic
+    /// ID from its position in the tree if it has none.
+    ///
+    /// Events bubble: after the target's closure, the closures of its
+    /// ancestors run too, until one calls `UiContext::stop_propagation`.
+    pub fn on_click(mut self, f: impl FnMut(&mut UiContext) + 'static) -> Self {
+        self.handlers.click = Some(Box::new(f));
+        self
+    }
+
+    /// Run `f` with the pointer movement each frame this node is dragged
+    ///
+    /// See [`Node::on_click`] for when closures run.
+    pub fn on_drag(mut self, f: impl FnMut(&mut UiContext, Point) + 'static) -> Self {
+        self.handlers.drag = Some(Box::new(f));
+        self
+    }
+
+    /// Run `f` with the wheel delta (horizontal, vertical) each frame the wheel
+    /// moves over this node
+    ///
+    /// The wheel starts at the topmost node under the pointer and bubbles up
+    /// from there. See [`Node::on_click`] for when closures run.
+    pub fn on_scroll(mut self, f: impl FnMut(&mut UiContext, (f32, f32)) + 'static) -> Self {
+        self.handlers.scroll = Some(Box::new(f));
+        self
+    }
+
     /// Set the base style (always applied)
     pub fn with_style(mut self, style: Style) -> Self {
         // Default shape to rect if not set
@@ -441,6 +743,24 @@ impl Node {
         self
     }
 
+    /// Set the focused style (merged with base while the node has focus)
+    ///
+    /// Applied underneath hover and active styles, so a focus ring stays
+    /// visible while the focused node is hovered or pressed.
+    pub fn with_focused_style(mut self, style: Style) -> Self {
+        self.focused_style = Some(style);
+        self
+    }
+
+    /// Set the selected style (merged with base while the node is selected)
+    ///
+    /// Applied underneath focus, hover and active styles, so a selected tab
+    /// still reacts to the pointer. See [`Node::with_selected`].
+    pub fn with_selected_style(mut self, style: Style) -> Self {
+        self.selected_style = Some(style);
+        self
+    }
+
     /// Set the disabled style (used when node is disabled, overrides other styles)
     pub fn with_disabled_style(mut self, style: Style) -> Self {
         self.disabled_style = Some(style);
@@ -453,12 +773,206 @@ impl Node {
         self
     }
 
+    /// Set whether this node is selected/checked
+    ///
+    /// Selection is app state (the current tab, an enabled toggle), so it is
+    /// set while building the tree. Toggling it transitions to or from the
+    /// selected style like any other style change.
+    pub fn with_selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
     /// Set the transition configuration for style changes
     pub fn with_transition(mut self, transition: Transition) -> Self {
         self.transition = Some(transition);
         self
     }
 
+    /// Apply a style rule inline, e.g. one built with `style!`
+    ///
+    /// Sets the rule's base, state styles, transition and padding at once.
+    /// Styles already set on the node win, as with classes.
+    pub fn with_style_rule(mut self, rule: StyleRule) -> Self {
+        self.apply_style_rule(&rule);
+        if let Some(padding) = rule.padding {
+            self.padding = padding;
+        }
+        self
+    }
+
+    /// Add a stylesheet class to this node
+    ///
+    /// Class styles are resolved from the `StyleSheet` registered on the
+    /// `UiContext` in `end_frame`. Styles set directly on the node win.
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
+    /// Bind a base style property to a design token, e.g. `var("accent")`
+    ///
+    /// Tokens are resolved from the `TokenTable` on the `UiContext` every
+    /// frame, so overriding a token animates the node via its transition.
+    pub fn with_var(self, property: StyleProperty, token: TokenRef) -> Self {
+        self.with_state_var(VarState::Base, property, token)
+    }
+
+    /// Bind a hover style property to a design token
+    pub fn with_hover_var(self, property: StyleProperty, token: TokenRef) -> Self {
+        self.with_state_var(VarState::Hover, property, token)
+    }
+
+    /// Bind an active style property to a design token
+    pub fn with_active_var(self, property: StyleProperty, token: TokenRef) -> Self {
+        self.with_state_var(VarState::Active, property, token)
+    }
+
+    /// Bind a focused style property to a design token
+    pub fn with_focused_var(self, property: StyleProperty, token: TokenRef) -> Self {
+        self.with_state_var(VarState::Focused, property, token)
+    }
+
+    /// Bind a selected style property to a design token
+    pub fn with_selected_var(self, property: StyleProperty, token: TokenRef) -> Self {
+        self.with_state_var(VarState::Selected, property, token)
+    }
+
+    /// Bind a disabled style property to a design token
+    pub fn with_disabled_var(self, property: StyleProperty, token: TokenRef) -> Self {
+        self.with_state_var(VarState::Disabled, property, token)
+    }
+
+    fn with_state_var(mut self, state: VarState, property: StyleProperty, token: TokenRef) -> Self {
+        self.vars.push(VarBinding {
+            state,
+            property,
+            token,
+        });
+        self
+    }
+
+    /// Set the text color inherited by descendant text content
+    ///
+    /// Applies to text whose `TextContent::inherit` includes the color, i.e.
+    /// text that did not set its own color. The nearest ancestor wins.
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Set the font size inherited by descendant text content
+    ///
+    /// Applies to text whose `TextContent::inherit` includes the font size,
+    /// i.e. text that did not set its own size. The nearest ancestor wins.
+    pub fn with_font_size(mut self, size: Size) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    /// Make this node a peek anchor.
+    ///
+    /// After the cursor has rested on the node for `delay`, the dispatcher emits
+    /// `InteractionEvent::PeekRequested` and reports the peek as open until the
+    /// cursor leaves the node or a mouse button is pressed. Requires an ID.
+    pub fn with_peek_delay(mut self, delay: std::time::Duration) -> Self {
+        self.peek_delay = Some(delay);
+        self
+    }
+
+    /// Set the cursor icon shown while the pointer is over this node
+    ///
+    /// Descendants without a cursor of their own show this one too. See
+    /// `UiContext::cursor_icon`.
+    pub fn with_cursor(mut self, cursor: CursorIcon) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Set how this node is dragged: threshold, axis lock and grid snapping
+    ///
+    /// The dispatcher applies it to the positions and deltas of this node's
+    /// drag events. See [`DragConfig`].
+    pub fn with_drag(mut self, drag: DragConfig) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Make pressing this node move or resize the window
+    ///
+    /// For custom title bars and resize borders of undecorated windows. A
+    /// left press requests the action from the platform layer (see
+    /// `UiContext::take_platform_output`), unless an identified descendant
+    /// such as a close button is under the pointer.
+    pub fn with_window_action(mut self, action: WindowAction) -> Self {
+        self.window_action = Some(action);
+        self
+    }
+
+    /// Set whether this node blocks pointer input to everything drawn below it
+    ///
+    /// Hit-testing drops every node under a barrier in drawing order, so the
+    /// barrier and the nodes drawn on top of it (its children, higher z-indices)
+    /// are the only ones hovered, clicked or scrolled. Used for modal backdrops.
+    pub fn with_input_barrier(mut self, input_barrier: bool) -> Self {
+        self.input_barrier = input_barrier;
+        self
+    }
+
+    /// Set whether this node is a focus scope, e.g. a modal dialog or menu layer
+    ///
+    /// While the scope is in the tree, Tab and Shift+Tab cycle through its
+    /// focusable descendants only, keys reach only a focused node inside it,
+    /// and hit-testing ignores every node outside it. Focus moves to its first
+    /// focusable descendant when it appears and back to the previously focused
+    /// node when it is removed. The most recently opened scope wins when
+    /// several are open. Nodes without an ID get an auto-ID.
+    pub fn with_focus_scope(mut self, focus_scope: bool) -> Self {
+        self.focus_scope = focus_scope;
+        self
+    }
+
+    /// Set whether this node can take keyboard focus
+    ///
+    /// Focusable nodes are focused when clicked and visited in layout order by
+    /// Tab and Shift+Tab; disabled ones are skipped. Keys pressed while a node
+    /// is focused are sent to it as `KeyDown` events. Nodes without an ID get
+    /// an auto-ID.
+    pub fn with_focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    /// Set whether drags carrying a payload can be dropped on this node
+    ///
+    /// The topmost drop target under the pointer receives `DragEnter`,
+    /// `DragOver`, `DragLeave` and `Drop` events while a drag with a payload
+    /// (see `UiContext::set_drag_payload`) passes over it. Drop targets need
+    /// an ID to receive events.
+    pub fn with_drop_target(mut self, drop_target: bool) -> Self {
+        self.drop_target = drop_target;
+        self
+    }
+
+    /// Set whether this node's laid-out size is recorded each frame
+    ///
+    /// The size is readable in the next frame with `UiContext::measured_size`,
+    /// so content-sized nodes can be measured before deciding what to build.
+    /// The node needs an ID.
+    pub fn with_measure_size(mut self, measure_size: bool) -> Self {
+        self.measure_size = measure_size;
+        self
+    }
+
+    /// Add a layout constraint relating an anchor of this node to another node
+    ///
+    /// Constraints are solved after the flow layout and override the position
+    /// or size it computed. Referenced nodes are looked up by ID.
+    pub fn with_constraint(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
     /// Add a child node
     pub fn with_child(mut self, child: Node) -> Self {
         assert!(
@@ -491,6 +1005,11 @@ impl Node {
         self.opacity
     }
 
+    /// Get the color filter, if any
+    pub(crate) fn filter(&self) -> Option<ColorMatrix> {
+        self.filter
+    }
+
     /// Set the opacity value (used by style system)
     pub(crate) fn set_opacity(&mut self, opacity: f32) {
         self.opacity = opacity;
@@ -531,6 +1050,16 @@ impl Node {
         self.zoom
     }
 
+    /// Set the text scale (see [`Node::with_text_scale`])
+    pub(crate) fn set_text_scale(&mut self, text_scale: f32) {
+        self.text_scale = Some(text_scale);
+    }
+
+    /// Get the text scale this node's font sizes are multiplied with
+    pub(crate) fn resolved_text_scale(&self) -> f32 {
+        self.resolved_text_scale
+    }
+
     /// Get the transform origin
     pub(crate) fn transform_origin(&self) -> TransformOrigin {
         self.transform_origin
@@ -561,6 +1090,56 @@ impl Node {
         self.z_index
     }
 
+    /// Get the peek hover delay, if this node is a peek anchor
+    pub fn peek_delay(&self) -> Option<std::time::Duration> {
+        self.peek_delay
+    }
+
+    /// Get the cursor icon requested by this node
+    pub fn cursor(&self) -> Option<CursorIcon> {
+        self.cursor
+    }
+
+    /// Get the drag configuration of this node
+    pub fn drag_config(&self) -> DragConfig {
+        self.drag
+    }
+
+    /// Get what pressing this node does to the window
+    pub fn window_action(&self) -> Option<WindowAction> {
+        self.window_action
+    }
+
+    /// Check if this node blocks pointer input to everything drawn below it
+    pub fn is_input_barrier(&self) -> bool {
+        self.input_barrier
+    }
+
+    /// Check if this node is a focus scope
+    pub fn is_focus_scope(&self) -> bool {
+        self.focus_scope
+    }
+
+    /// Check if this node can take keyboard focus
+    pub fn is_focusable(&self) -> bool {
+        self.focusable
+    }
+
+    /// Check if drags carrying a payload can be dropped on this node
+    pub fn is_drop_target(&self) -> bool {
+        self.drop_target
+    }
+
+    /// Check if this node's laid-out size is recorded each frame
+    pub fn measures_size(&self) -> bool {
+        self.measure_size
+    }
+
+    /// Get the layout constraints declared on this node
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
     /// Get the scroll offset (horizontal, vertical)
     pub fn scroll_offset(&self) -> (f32, f32) {
         self.scroll_offset
@@ -598,6 +1177,24 @@ impl Node {
         self.scroll_direction
     }
 
+    /// Get the scroll smoothing behavior
+    pub fn scroll_smoothing(&self) -> ScrollSmoothing {
+        self.scroll_smoothing
+    }
+
+    /// Get the scroll momentum, if kinetic scrolling is enabled
+    pub fn scroll_momentum(&self) -> Option<ScrollMomentum> {
+        self.scroll_momentum
+    }
+
+    pub(crate) fn scroll_animation(&self) -> ScrollAnimation {
+        self.scroll_animation
+    }
+
+    pub(crate) fn set_scroll_animation(&mut self, animation: ScrollAnimation) {
+        self.scroll_animation = animation;
+    }
+
     /// Update smooth scrolling animation
     ///
     /// This should be called once per frame with the delta time in seconds.
@@ -605,16 +1202,54 @@ impl Node {
     ///
     /// Returns true if scrolling is in progress (not yet at target).
     pub fn update_scroll_animation(&mut self, dt: f32) -> bool {
-        const SCROLL_SMOOTHNESS: f32 = 10.0; // Higher = faster, lower = smoother
+        if let Some(momentum) = self.scroll_momentum {
+            if self.update_scroll_momentum(momentum, dt) {
+                return true;
+            }
+        }
 
         if self.scroll_offset == self.scroll_target {
             return false; // Already at target
         }
 
-        let t = 1.0 - (-SCROLL_SMOOTHNESS * dt).exp(); // Exponential ease-out
+        match self.scroll_smoothing {
+            ScrollSmoothing::Instant => {
+                self.scroll_offset = self.scroll_target;
+                return false;
+            }
+            ScrollSmoothing::Exponential(smoothness) => {
+                let t = 1.0 - (-smoothness * dt).exp(); // Exponential ease-out
 
-        self.scroll_offset.0 += (self.scroll_target.0 - self.scroll_offset.0) * t;
-        self.scroll_offset.1 += (self.scroll_target.1 - self.scroll_offset.1) * t;
+                self.scroll_offset.0 += (self.scroll_target.0 - self.scroll_offset.0) * t;
+                self.scroll_offset.1 += (self.scroll_target.1 - self.scroll_offset.1) * t;
+            }
+            ScrollSmoothing::Eased { duration, easing } => {
+                // Restart from the current offset whenever the target moves
+                if self.scroll_animation.to != self.scroll_target {
+                    self.scroll_animation = ScrollAnimation {
+                        from: self.scroll_offset,
+                        to: self.scroll_target,
+                        elapsed: 0.0,
+                        ..self.scroll_animation
+                    };
+                }
+
+                self.scroll_animation.elapsed += dt;
+                let progress = if duration > 0.0 {
+                    (self.scroll_animation.elapsed / duration).min(1.0)
+                } else {
+                    1.0
+                };
+                let t = easing(progress);
+                let ScrollAnimation { from, to, .. } = self.scroll_animation;
+
+                self.scroll_offset.0 = from.0 + (to.0 - from.0) * t;
+                self.scroll_offset.1 = from.1 + (to.1 - from.1) * t;
+                if progress >= 1.0 {
+                    self.scroll_offset = to;
+                }
+            }
+        }
 
         // Snap to target if very close (within 0.1 pixels)
         if (self.scroll_target.0 - self.scroll_offset.0).abs() < 0.1 {
@@ -627,6 +1262,86 @@ impl Node {
         true // Still animating
     }
 
+    /// Measure the flick speed from this frame's precise scrolling, or coast
+    /// with it once the flick ends
+    ///
+    /// Returns true while the flick is tracked or the content is coasting.
+    fn update_scroll_momentum(&mut self, momentum: ScrollMomentum, dt: f32) -> bool {
+        let max_scroll = self
+            .computed_layout()
+            .map(|layout| layout.max_scroll)
+            .unwrap_or((0.0, 0.0));
+        let animation = &mut self.scroll_animation;
+        let input = std::mem::take(&mut animation.flick_input);
+        if input != (0.0, 0.0) {
+            if dt > 0.0 {
+                let sample = (input.0 / dt, input.1 / dt);
+                animation.velocity = if animation.tracking {
+                    (
+                        (animation.velocity.0 + sample.0) * 0.5,
+                        (animation.velocity.1 + sample.1) * 0.5,
+                    )
+                } else {
+                    sample
+                };
+            }
+            animation.tracking = true;
+            return true;
+        }
+        animation.tracking = false;
+
+        let step = |offset: &mut f32, velocity: &mut f32, max: f32| {
+            let overshoot = if *offset < 0.0 {
+                *offset
+            } else if *offset > max {
+                *offset - max
+            } else {
+                0.0
+            };
+            if *velocity == 0.0 && overshoot == 0.0 {
+                return false;
+            }
+
+            if overshoot == 0.0 {
+                *velocity *= (-momentum.friction * dt).exp();
+            } else {
+                // Past an end: brake hard and spring back
+                *velocity *= (-(momentum.friction + OVERSCROLL_DAMPING) * dt).exp();
+                *velocity -= overshoot * OVERSCROLL_STIFFNESS * dt;
+            }
+            *offset += *velocity * dt;
+
+            let limit = momentum.overscroll;
+            if *offset < -limit || *offset > max + limit {
+                *offset = offset.clamp(-limit, max + limit);
+                *velocity = 0.0;
+            }
+            let settled = velocity.abs() < MIN_FLICK_VELOCITY;
+            if settled && (0.0..=max).contains(offset) {
+                *velocity = 0.0;
+                return false;
+            }
+            if settled && (*offset - offset.clamp(0.0, max)).abs() < 0.5 {
+                *offset = offset.clamp(0.0, max);
+                *velocity = 0.0;
+                return false;
+            }
+            true
+        };
+
+        let mut offset = self.scroll_offset;
+        let mut velocity = animation.velocity;
+        let moving_x = step(&mut offset.0, &mut velocity.0, max_scroll.0);
+        let moving_y = step(&mut offset.1, &mut velocity.1, max_scroll.1);
+        animation.velocity = velocity;
+        if !moving_x && !moving_y && velocity == (0.0, 0.0) && offset == self.scroll_offset {
+            return false;
+        }
+        self.scroll_offset = offset;
+        self.scroll_target = offset;
+        moving_x || moving_y
+    }
+
     /// Recursively update scroll animations for this node and all children
     ///
     /// Returns true if any node is still animating.
@@ -652,11 +1367,62 @@ impl Node {
         self.shape.as_mut()
     }
 
+    /// Get the outline, if any
+    pub(crate) fn outline(&self) -> Option<Outline> {
+        self.outline
+    }
+
+    /// Set the outline (used by style system)
+    pub(crate) fn set_outline(&mut self, outline: Outline) {
+        self.outline = Some(outline);
+    }
+
+    /// Get the drop shadow, if any
+    pub(crate) fn shadow(&self) -> Option<Shadow> {
+        self.shadow
+    }
+
+    /// Set the drop shadow (used by style system)
+    pub(crate) fn set_shadow(&mut self, shadow: Shadow) {
+        self.shadow = Some(shadow);
+    }
+
+    /// Get the fill tint, if any
+    pub(crate) fn tint(&self) -> Option<Color> {
+        self.tint
+    }
+
+    /// Set the fill tint (used by style system)
+    pub(crate) fn set_tint(&mut self, tint: Color) {
+        self.tint = Some(tint);
+    }
+
     /// Get the content, if any
     pub(crate) fn content(&self) -> Option<&Content> {
         self.content.as_ref()
     }
 
+    /// Get the caret and selection of edited text content, if any
+    pub(crate) fn text_edit(&self) -> Option<&TextEditDecorations> {
+        self.text_edit.as_ref()
+    }
+
+    /// Check if this node has any `on_click`, `on_drag` or `on_scroll` closures
+    pub(crate) fn has_event_handlers(&self) -> bool {
+        let handlers = &self.handlers;
+        handlers.click.is_some() || handlers.drag.is_some() || handlers.scroll.is_some()
+    }
+
+    /// Get mutable access to this node's event closures
+    pub(crate) fn event_handlers_mut(&mut self) -> &mut EventHandlers {
+        &mut self.handlers
+    }
+
+    /// Get the paint function, if any
+    pub(crate) fn painter(&self) -> Option<&dyn Fn(&mut Painter)> {
+        self.painter.as_deref()
+    }
+
     /// Get mutable reference to the content (used by style system)
     pub(crate) fn content_mut(&mut self) -> Option<&mut Content> {
         self.content.as_mut()
@@ -667,6 +1433,16 @@ impl Node {
         self.padding
     }
 
+    /// Set the padding (used by style system)
+    pub(crate) fn set_padding(&mut self, padding: Spacing) {
+        self.padding = padding;
+    }
+
+    /// Set the gap between children (used by style system)
+    pub(crate) fn set_gap(&mut self, gap: Size) {
+        self.gap = gap;
+    }
+
     /// Get the margin
     pub(crate) fn margin(&self) -> Spacing {
         self.margin
@@ -700,6 +1476,16 @@ impl Node {
         &mut self.children
     }
 
+    /// Take the children out, leaving none
+    pub(crate) fn take_children(&mut self) -> Vec<Node> {
+        std::mem::take(&mut self.children)
+    }
+
+    /// Replace the children
+    pub(crate) fn set_children(&mut self, children: Vec<Node>) {
+        self.children = children;
+    }
+
     /// Get the base style
     pub fn base_style(&self) -> Option<&Style> {
         self.base_style.as_ref()
@@ -715,6 +1501,16 @@ impl Node {
         self.active_style.as_ref()
     }
 
+    /// Get the focused style
+    pub fn focused_style(&self) -> Option<&Style> {
+        self.focused_style.as_ref()
+    }
+
+    /// Get the selected style
+    pub fn selected_style(&self) -> Option<&Style> {
+        self.selected_style.as_ref()
+    }
+
     /// Get the disabled style
     pub fn disabled_style(&self) -> Option<&Style> {
         self.disabled_style.as_ref()
@@ -725,11 +1521,95 @@ impl Node {
         self.disabled
     }
 
+    /// Check if this node is selected/checked
+    pub fn is_selected(&self) -> bool {
+        self.selected
+    }
+
     /// Get the transition configuration
     pub fn transition(&self) -> Option<&Transition> {
         self.transition.as_ref()
     }
 
+    /// Get the stylesheet classes
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Get the design-token bindings
+    pub fn vars(&self) -> &[VarBinding] {
+        &self.vars
+    }
+
+    /// Get the text color inherited by descendant text content
+    pub fn text_color(&self) -> Option<Color> {
+        self.text_color
+    }
+
+    /// Get the font size inherited by descendant text content
+    pub fn font_size(&self) -> Option<Size> {
+        self.font_size
+    }
+
+    /// Get the style for an interaction state (used by style system)
+    pub(crate) fn state_style(&self, state: VarState) -> Option<&Style> {
+        match state {
+            VarState::Base => self.base_style.as_ref(),
+            VarState::Hover => self.hover_style.as_ref(),
+            VarState::Active => self.active_style.as_ref(),
+            VarState::Focused => self.focused_style.as_ref(),
+            VarState::Selected => self.selected_style.as_ref(),
+            VarState::Disabled => self.disabled_style.as_ref(),
+        }
+    }
+
+    /// Replace the style for an interaction state (used by style system)
+    pub(crate) fn set_state_style(&mut self, state: VarState, style: Style) {
+        match state {
+            VarState::Base => {
+                if self.shape.is_none() {
+                    self.shape = Some(Shape::rect());
+                }
+                style.apply_to_node(self);
+                self.base_style = Some(style);
+            }
+            VarState::Hover => self.hover_style = Some(style),
+            VarState::Active => self.active_style = Some(style),
+            VarState::Focused => self.focused_style = Some(style),
+            VarState::Selected => self.selected_style = Some(style),
+            VarState::Disabled => self.disabled_style = Some(style),
+        }
+    }
+
+    /// Layer a resolved stylesheet rule underneath this node's own styles (used by style system)
+    pub(crate) fn apply_style_rule(&mut self, rule: &StyleRule) {
+        let own = StyleRule {
+            style: self.base_style.take(),
+            hover_style: self.hover_style.take(),
+            active_style: self.active_style.take(),
+            focused_style: self.focused_style.take(),
+            selected_style: self.selected_style.take(),
+            disabled_style: self.disabled_style.take(),
+            transition: self.transition.take(),
+            padding: None,
+        };
+
+        let merged = rule.merge(&own);
+        self.hover_style = merged.hover_style;
+        self.active_style = merged.active_style;
+        self.focused_style = merged.focused_style;
+        self.selected_style = merged.selected_style;
+        self.disabled_style = merged.disabled_style;
+        self.transition = merged.transition;
+        if let Some(style) = merged.style {
+            if self.shape.is_none() {
+                self.shape = Some(Shape::rect());
+            }
+            style.apply_to_node(self);
+            self.base_style = Some(style);
+        }
+    }
+
     /// Measure the intrinsic size of this node (content + padding, excluding margins).
     ///
     /// This recursively measures children and applies the same margin/gap collapsing
@@ -751,6 +1631,9 @@ impl Node {
             return IntrinsicSize::new(w * scale_factor, h * scale_factor);
         }
 
+        // Whether any measurement below was a provisional estimate
+        let mut provisional = false;
+
         // Measure width - check override first, then FitContent measures children
         let width = if let Some(w_override) = self.width_override {
             w_override
@@ -760,17 +1643,17 @@ impl Node {
                 Size::Physical(w) => w,
                 Size::FitContent => {
                     let content_width = if let Some(content) = &self.content {
-                        match content {
-                            Content::Text(text_content) => {
-                                let mut request =
-                                    MeasureTextRequest::from_text_content(text_content);
-                                request.font_size *= scale_factor;
-                                // Note: measure_node doesn't have width constraints - use None for max_width
-                                measurer.measure_text(request).width
-                            }
-                        }
+                        let text_content = content.text();
+                        let mut request = MeasureTextRequest::from_text_content(text_content);
+                        request.font_size *= scale_factor * self.resolved_text_scale;
+                        // Note: measure_node doesn't have width constraints - use None for max_width
+                        let size = measurer.measure_text(request);
+                        provisional |= size.provisional;
+                        size.width
                     } else if !self.children.is_empty() {
-                        self.measure_children(measurer, scale_factor).width
+                        let size = self.measure_children(measurer, scale_factor);
+                        provisional |= size.provisional;
+                        size.width
                     } else {
                         0.0
                     };
@@ -802,49 +1685,49 @@ impl Node {
                 Size::Physical(h) => h,
                 Size::FitContent => {
                     let content_height = if let Some(content) = &self.content {
-                        match content {
-                            Content::Text(text_content) => {
-                                let mut request =
-                                    MeasureTextRequest::from_text_content(text_content);
-                                request.font_size *= scale_factor;
-
-                                // If this node has an absolute width, use it as a constraint for text wrapping
-                                request.max_width = match self.width {
-                                    Size::Logical(w) => {
-                                        let width_px = w * scale_factor;
-                                        let padding_left = self
-                                            .padding
-                                            .left
-                                            .try_resolve_with_scale(width_px, scale_factor)
-                                            .unwrap_or(0.0);
-                                        let padding_right = self
-                                            .padding
-                                            .right
-                                            .try_resolve_with_scale(width_px, scale_factor)
-                                            .unwrap_or(0.0);
-                                        Some((width_px - padding_left - padding_right).max(0.0))
-                                    }
-                                    Size::Physical(w) => {
-                                        let padding_left = self
-                                            .padding
-                                            .left
-                                            .try_resolve_with_scale(w, scale_factor)
-                                            .unwrap_or(0.0);
-                                        let padding_right = self
-                                            .padding
-                                            .right
-                                            .try_resolve_with_scale(w, scale_factor)
-                                            .unwrap_or(0.0);
-                                        Some((w - padding_left - padding_right).max(0.0))
-                                    }
-                                    _ => None, // FitContent/Fill/Relative: no width constraint known yet
-                                };
-
-                                measurer.measure_text(request).height
+                        let text_content = content.text();
+                        let mut request = MeasureTextRequest::from_text_content(text_content);
+                        request.font_size *= scale_factor * self.resolved_text_scale;
+
+                        // If this node has an absolute width, use it as a constraint for text wrapping
+                        request.max_width = match self.width {
+                            Size::Logical(w) => {
+                                let width_px = w * scale_factor;
+                                let padding_left = self
+                                    .padding
+                                    .left
+                                    .try_resolve_with_scale(width_px, scale_factor)
+                                    .unwrap_or(0.0);
+                                let padding_right = self
+                                    .padding
+                                    .right
+                                    .try_resolve_with_scale(width_px, scale_factor)
+                                    .unwrap_or(0.0);
+                                Some((width_px - padding_left - padding_right).max(0.0))
                             }
-                        }
+                            Size::Physical(w) => {
+                                let padding_left = self
+                                    .padding
+                                    .left
+                                    .try_resolve_with_scale(w, scale_factor)
+                                    .unwrap_or(0.0);
+                                let padding_right = self
+                                    .padding
+                                    .right
+                                    .try_resolve_with_scale(w, scale_factor)
+                                    .unwrap_or(0.0);
+                                Some((w - padding_left - padding_right).max(0.0))
+                            }
+                            _ => None, // FitContent/Fill/Relative: no width constraint known yet
+                        };
+
+                        let size = measurer.measure_text(request);
+                        provisional |= size.provisional;
+                        size.height
                     } else if !self.children.is_empty() {
-                        self.measure_children(measurer, scale_factor).height
+                        let size = self.measure_children(measurer, scale_factor);
+                        provisional |= size.provisional;
+                        size.height
                     } else {
                         0.0
                     };
@@ -867,7 +1750,11 @@ impl Node {
             }
         };
 
-        IntrinsicSize::new(width, height)
+        IntrinsicSize {
+            width,
+            height,
+            provisional,
+        }
     }
 
     /// Measure the intrinsic content size of a container based on its children.
@@ -980,41 +1867,59 @@ impl Node {
                 // Height: max of child heights (cross axis)
                 let mut total_width = 0.0f32;
                 let mut max_height = 0.0f32;
+                let mut provisional = false;
 
                 for child in &self.children {
                     let size = child.measure_node(measurer, scale_factor);
                     total_width += size.width;
                     max_height = max_height.max(size.height);
+                    provisional |= size.provisional;
                 }
 
-                IntrinsicSize::new(total_width + total_horizontal_spacing, max_height)
+                IntrinsicSize {
+                    width: total_width + total_horizontal_spacing,
+                    height: max_height,
+                    provisional,
+                }
             }
             Layout::Vertical => {
                 // Height: sum of child heights + spacing (main axis)
                 // Width: max of child widths (cross axis)
                 let mut total_height = 0.0f32;
                 let mut max_width = 0.0f32;
+                let mut provisional = false;
 
                 for child in &self.children {
                     let size = child.measure_node(measurer, scale_factor);
                     total_height += size.height;
                     max_width = max_width.max(size.width);
+                    provisional |= size.provisional;
                 }
 
-                IntrinsicSize::new(max_width, total_height + total_vertical_spacing)
+                IntrinsicSize {
+                    width: max_width,
+                    height: total_height + total_vertical_spacing,
+                    provisional,
+                }
             }
             Layout::Stack => {
                 // Stack: max of all child sizes (children overlap in Z)
                 let mut max_width = 0.0f32;
                 let mut max_height = 0.0f32;
+                let mut provisional = false;
 
                 for child in &self.children {
                     let size = child.measure_node(measurer, scale_factor);
                     max_width = max_width.max(size.width);
                     max_height = max_height.max(size.height);
+                    provisional |= size.provisional;
                 }
 
-                IntrinsicSize::new(max_width, max_height)
+                IntrinsicSize {
+                    width: max_width,
+                    height: max_height,
+                    provisional,
+                }
             }
         }
     }
@@ -1030,12 +1935,15 @@ impl Node {
     ///
     /// `scale_factor` is multiplied with all Fixed sizes, padding, margins, gaps, and font sizes
     pub fn compute_layout_with_scale_factor(&mut self, available_rect: Rect, scale_factor: f32) {
-        self.compute_layout_with_parent_size(
-            available_rect,
-            available_rect.width(),
-            available_rect.height(),
-            scale_factor,
-        );
+        let relayout = |node: &mut Node| {
+            node.compute_layout_with_parent_size(
+                available_rect,
+                available_rect.width(),
+                available_rect.height(),
+                scale_factor,
+            );
+        };
+        self.layout_with_constraints(scale_factor, relayout);
     }
 
     /// Compute layout with a measurer for resolving `Size::FitContent`.
@@ -1056,14 +1964,310 @@ impl Node {
         measurer: &mut dyn ContentMeasurer,
         scale_factor: f32,
     ) {
+        let relayout = |node: &mut Node| {
+            node.compute_layout_with_parent_size_and_measurer(
+                available_rect,
+                available_rect.width(),
+                available_rect.height(),
+                measurer,
+                Overflow::Visible, // Root has no parent, assume Visible
+                scale_factor,
+            );
+        };
+        self.layout_with_constraints(scale_factor, relayout);
+    }
+
+    /// Check whether any node in this subtree was laid out from a provisional measurement
+    ///
+    /// See [`IntrinsicSize::provisional`](crate::IntrinsicSize::provisional).
+    pub fn has_provisional_layout(&self) -> bool {
+        self.computed.is_some_and(|c| c.provisional)
+            || self.children.iter().any(Node::has_provisional_layout)
+    }
+
+    /// Re-run layout for the subtrees that were laid out from provisional measurements
+    ///
+    /// Each affected node is re-laid out from its nearest ancestor whose size does
+    /// not depend on its content (neither dimension is `FitContent`). That
+    /// ancestor keeps its rect, so the rest of the tree is left untouched. Call
+    /// this once the measurer has real metrics (see
+    /// `ContentMeasurer::has_pending_measurements`) instead of laying out the
+    /// whole tree again. A provisional root needs a full `compute_layout`.
+    ///
+    /// Returns whether any subtree was re-laid out.
+    pub fn relayout_provisional(
+        &mut self,
+        measurer: &mut dyn ContentMeasurer,
+        scale_factor: f32,
+    ) -> bool {
+        if self.computed.is_none() {
+            return false;
+        }
+        self.resolve_inherited_text(None, 1.0);
+        self.relayout_provisional_subtrees(measurer, scale_factor)
+    }
+
+    fn relayout_provisional_subtrees(
+        &mut self,
+        measurer: &mut dyn ContentMeasurer,
+        scale_factor: f32,
+    ) -> bool {
+        let effective_scale_factor = self.zoom.unwrap_or(scale_factor);
+        let mut relaid = false;
+        for child in &mut self.children {
+            relaid |= child.relayout_provisional_subtrees(measurer, effective_scale_factor);
+        }
+
+        // A provisional child's size feeds into this node's layout. If this
+        // node's own size depends on its content, it is provisional too and
+        // the parent takes care of it.
+        let child_provisional = self
+            .children
+            .iter()
+            .any(|child| child.computed.is_some_and(|c| c.provisional));
+        let is_boundary = !self.width.is_fit_content() && !self.height.is_fit_content();
+        if !child_provisional || !is_boundary {
+            return relaid;
+        }
+        let Some(rect) = self.computed.map(|c| c.rect) else {
+            return relaid;
+        };
+
+        let saved = (self.width_override, self.height_override);
+        self.width_override = Some(rect.width());
+        self.height_override = Some(rect.height());
         self.compute_layout_with_parent_size_and_measurer(
-            available_rect,
-            available_rect.width(),
-            available_rect.height(),
+            rect,
+            rect.width(),
+            rect.height(),
             measurer,
-            Overflow::Visible, // Root has no parent, assume Visible
+            Overflow::Visible,
             scale_factor,
         );
+        (self.width_override, self.height_override) = saved;
+        true
+    }
+
+    /// Run the flow layout, then solve layout constraints on top of it
+    ///
+    /// Constraints that resize nodes are fed back through the width/height
+    /// overrides and the flow layout runs a second time, so children and
+    /// siblings adapt to the new size. Moved nodes are offset with their subtree.
+    fn layout_with_constraints(&mut self, scale_factor: f32, mut relayout: impl FnMut(&mut Node)) {
+        // Inherited font sizes and text scales must be resolved before text is measured
+        self.resolve_inherited_text(None, 1.0);
+        relayout(self);
+
+        let Some(mut solved) = constraint::solve(self, scale_factor) else {
+            return;
+        };
+
+        if solved.iter().any(|s| s.resized) {
+            let mut saved = Vec::new();
+            for s in solved.iter().filter(|s| s.resized) {
+                let node = self.descendant_mut(&s.path);
+                saved.push((s.path.clone(), node.width_override, node.height_override));
+                node.width_override = Some(s.rect.width());
+                node.height_override = Some(s.rect.height());
+            }
+
+            relayout(self);
+
+            for (path, width_override, height_override) in saved {
+                let node = self.descendant_mut(&path);
+                node.width_override = width_override;
+                node.height_override = height_override;
+            }
+
+            solved = constraint::solve(self, scale_factor).unwrap_or_default();
+        }
+
+        // Entries are in pre-order, so ancestors move before their descendants
+        for s in solved {
+            let node = self.descendant_mut(&s.path);
+            if let Some(current) = node.computed.as_ref().map(|c| c.rect) {
+                node.offset_layout_recursive(
+                    s.rect.min[0] - current.min[0],
+                    s.rect.min[1] - current.min[1],
+                );
+            }
+        }
+    }
+
+    /// Write the nearest ancestor's font size into inheriting text content,
+    /// and resolve each node's text scale
+    pub(crate) fn resolve_inherited_text(&mut self, inherited: Option<Size>, inherited_scale: f32) {
+        let inherited = self.font_size.or(inherited);
+        self.resolved_text_scale = self.text_scale.unwrap_or(inherited_scale);
+        if let (Some(size), Some(content)) = (inherited, self.content.as_mut()) {
+            let text = content.text_mut();
+            if text.inherit.font_size {
+                text.font_size = size;
+            }
+        }
+        for child in &mut self.children {
+            child.resolve_inherited_text(inherited, self.resolved_text_scale);
+        }
+    }
+
+    /// Get a descendant by its path of child indices
+    fn descendant_mut(&mut self, path: &[usize]) -> &mut Node {
+        path.iter()
+            .fold(self, |node, &index| &mut node.children[index])
+    }
+
+    /// Get the shapes cached for this subtree
+    pub(crate) fn shape_cache(&self) -> &ShapeCacheCell {
+        &self.shape_cache
+    }
+
+    /// Drop the shapes cached for this subtree, keeping those of descendants
+    pub(crate) fn clear_shape_cache(&mut self) {
+        *self.shape_cache.get_mut() = ShapeCache::Empty;
+    }
+
+    /// Drop the shapes cached for this subtree and all its descendants
+    pub(crate) fn clear_shape_caches(&mut self) {
+        self.clear_shape_cache();
+        for child in &mut self.children {
+            child.clear_shape_caches();
+        }
+    }
+
+    /// Check if nothing in this subtree changes how it looks between frames
+    /// once built, i.e. no styles, stylesheet classes, tokens, scrolling or
+    /// custom painting are applied to it in place
+    pub(crate) fn has_static_visuals(&self) -> bool {
+        self.base_style.is_none()
+            && self.classes.is_empty()
+            && self.vars.is_empty()
+            && self.painter.is_none()
+            && !matches!(self.overflow, Overflow::Scroll)
+            && self.children.iter().all(Node::has_static_visuals)
+    }
+
+    /// Take over another node's layout, e.g. when replacing it in a kept tree
+    pub(crate) fn copy_layout_from(&mut self, other: &Node) {
+        self.computed = other.computed;
+    }
+
+    /// Check if this node's size and margins are fixed, so its rect doesn't
+    /// depend on its content or children
+    pub(crate) fn has_fixed_size(&self) -> bool {
+        let fixed = |size: Size| matches!(size, Size::Logical(_) | Size::Physical(_));
+        fixed(self.width)
+            && fixed(self.height)
+            && [
+                self.margin.top,
+                self.margin.right,
+                self.margin.bottom,
+                self.margin.left,
+            ]
+            .into_iter()
+            .all(fixed)
+            && self.width_override.is_none()
+            && self.height_override.is_none()
+    }
+
+    /// Get the settings that decide the space this node takes in its parent
+    pub(crate) fn footprint(&self) -> Footprint {
+        Footprint {
+            width: self.width,
+            height: self.height,
+            margin: self.margin,
+            place: self.place,
+            zoom: self.zoom,
+        }
+    }
+
+    /// Check if this node lays out like `other`, apart from its children
+    ///
+    /// Content is only compared through the space it takes, so nodes sized to
+    /// their content never lay out the same.
+    pub(crate) fn lays_out_like(&self, other: &Node) -> bool {
+        let measures = |node: &Node| {
+            node.content.is_some() && (node.width.is_fit_content() || node.height.is_fit_content())
+        };
+        !measures(self)
+            && !measures(other)
+            && self.footprint() == other.footprint()
+            && self.padding == other.padding
+            && self.gap == other.gap
+            && self.layout_direction == other.layout_direction
+            && self.h_align == other.h_align
+            && self.v_align == other.v_align
+            && self.overflow == other.overflow
+            && self.text_scale == other.text_scale
+            && self.font_size == other.font_size
+            && self.width_override == other.width_override
+            && self.height_override == other.height_override
+            && self.measure_size == other.measure_size
+            && self.constraints == other.constraints
+    }
+
+    /// Check if this node or a descendant has layout constraints
+    pub(crate) fn uses_constraints(&self) -> bool {
+        !self.constraints.is_empty() || self.children.iter().any(Node::uses_constraints)
+    }
+
+    /// Lay out the descendant at `path` again inside the rect it already
+    /// occupies (plus its margins), with the scale factor, inherited text settings and overflow
+    /// its ancestors give it
+    ///
+    /// The descendant must have been laid out before.
+    pub(crate) fn relayout_descendant(
+        &mut self,
+        path: &[usize],
+        measurer: Option<&mut dyn ContentMeasurer>,
+        scale_factor: f32,
+    ) {
+        let mut node = self;
+        let mut scale_factor = scale_factor;
+        let mut font_size = None;
+        let mut text_scale = 1.0;
+        let mut overflow = Overflow::Visible;
+        for &index in path {
+            scale_factor = node.zoom.unwrap_or(scale_factor);
+            font_size = node.font_size.or(font_size);
+            text_scale = node.resolved_text_scale;
+            overflow = node.overflow;
+            node = &mut node.children[index];
+        }
+
+        let Some(rect) = node.computed.as_ref().map(|computed| computed.rect) else {
+            return;
+        };
+
+        // Layout takes the space including margins, which are fixed here
+        let scale = node.zoom.unwrap_or(scale_factor);
+        let margin = |size: Size| size.try_resolve_with_scale(0.0, scale).unwrap_or(0.0);
+        let rect = Rect::new(
+            [
+                rect.min[0] - margin(node.margin.left),
+                rect.min[1] - margin(node.margin.top),
+            ],
+            [
+                rect.max[0] + margin(node.margin.right),
+                rect.max[1] + margin(node.margin.bottom),
+            ],
+        );
+        node.resolve_inherited_text(font_size, text_scale);
+        match measurer {
+            Some(measurer) => node.compute_layout_with_parent_size_and_measurer(
+                rect,
+                rect.width(),
+                rect.height(),
+                measurer,
+                overflow,
+                scale_factor,
+            ),
+            None => node.compute_layout_with_parent_size(
+                rect,
+                rect.width(),
+                rect.height(),
+                scale_factor,
+            ),
+        }
     }
 
     /// Recursively offset this node and all its descendants by the given delta
@@ -1128,7 +2332,7 @@ impl Node {
         // - Both constrained: No measurement needed
         let measured_size = if self.width.is_fit_content() || self.height.is_fit_content() {
             // For text content, handle width constraints correctly
-            if let Some(Content::Text(text_content)) = &self.content {
+            if let Some(text_content) = self.content.as_ref().map(Content::text) {
                 // Determine max_width constraint based on width mode
                 // IMPORTANT: Only use width constraint for absolute sizes (Logical/Physical).
                 // Fill/Relative depend on parent layout and can't be used for measurement.
@@ -1167,7 +2371,7 @@ impl Node {
                 };
 
                 let mut request = MeasureTextRequest::from_text_content(text_content);
-                request.font_size *= effective_scale_factor;
+                request.font_size *= effective_scale_factor * self.resolved_text_scale;
                 request.max_width = max_width;
 
                 let size = measurer.measure_text(request);
@@ -1194,10 +2398,11 @@ impl Node {
                     .try_resolve_with_scale(size.width, effective_scale_factor)
                     .unwrap_or(0.0);
 
-                Some(IntrinsicSize::new(
-                    size.width + padding_left + padding_right,
-                    size.height + padding_top + padding_bottom,
-                ))
+                Some(IntrinsicSize {
+                    width: size.width + padding_left + padding_right,
+                    height: size.height + padding_top + padding_bottom,
+                    provisional: size.provisional,
+                })
             } else {
                 Some(self.measure_node(measurer, effective_scale_factor))
             }
@@ -1276,10 +2481,12 @@ impl Node {
         let content_height = height - padding_top - padding_bottom;
 
         // Store computed layout for this node (untransformed - translation applied during rendering)
-        self.computed = Some(ComputedLayout::new(Rect::new(
+        let mut computed = ComputedLayout::new(Rect::new(
             [outer_x, outer_y],
             [outer_x + width, outer_y + height],
-        )));
+        ));
+        computed.provisional = measured_size.is_some_and(|size| size.provisional);
+        self.computed = Some(computed);
 
         // Layout children (same as original, but passing measurer through)
         let mut current_x = content_x;
@@ -1813,14 +3020,17 @@ impl Node {
         // Resolve width and height from available space (after margins)
         // NOTE: Without a measurer, FitContent falls back to available size
         // Apply effective_scale_factor to Fixed sizes (logical -> physical pixels)
-        let width = self
-            .width
-            .try_resolve_with_scale(available_width, effective_scale_factor)
-            .unwrap_or(available_width);
-        let height = self
-            .height
-            .try_resolve_with_scale(available_height, effective_scale_factor)
-            .unwrap_or(available_height);
+        // Overrides (transitions, layout constraints) are already in physical pixels
+        let width = self.width_override.unwrap_or_else(|| {
+            self.width
+                .try_resolve_with_scale(available_width, effective_scale_factor)
+                .unwrap_or(available_width)
+        });
+        let height = self.height_override.unwrap_or_else(|| {
+            self.height
+                .try_resolve_with_scale(available_height, effective_scale_factor)
+                .unwrap_or(available_height)
+        });
 
         // Position is already adjusted for margins by parent, don't add them again
         let outer_x = available_rect.min[0];
@@ -2198,50 +3408,46 @@ impl Node {
 
             // Add content shape if this is a content node
             if let Some(content) = &self.content {
-                match content {
-                    crate::content::Content::Text(text_content) => {
-                        // Calculate content area (after padding)
-                        // NOTE: During rendering, we use scale_factor=1.0 because padding was already
-                        // resolved during layout. We just need the logical pixel values here.
-                        let width = layout.rect.max[0] - layout.rect.min[0];
-                        let height = layout.rect.max[1] - layout.rect.min[1];
-                        let padding_left = self
-                            .padding
-                            .left
-                            .try_resolve_with_scale(width, 1.0)
-                            .unwrap_or(0.0);
-                        let padding_right = self
-                            .padding
-                            .right
-                            .try_resolve_with_scale(width, 1.0)
-                            .unwrap_or(0.0);
-                        let padding_top = self
-                            .padding
-                            .top
-                            .try_resolve_with_scale(height, 1.0)
-                            .unwrap_or(0.0);
-                        let padding_bottom = self
-                            .padding
-                            .bottom
-                            .try_resolve_with_scale(height, 1.0)
-                            .unwrap_or(0.0);
+                let text_content = content.text();
+                // Calculate content area (after padding)
+                // NOTE: During rendering, we use scale_factor=1.0 because padding was already
+                // resolved during layout. We just need the logical pixel values here.
+                let width = layout.rect.max[0] - layout.rect.min[0];
+                let height = layout.rect.max[1] - layout.rect.min[1];
+                let padding_left = self
+                    .padding
+                    .left
+                    .try_resolve_with_scale(width, 1.0)
+                    .unwrap_or(0.0);
+                let padding_right = self
+                    .padding
+                    .right
+                    .try_resolve_with_scale(width, 1.0)
+                    .unwrap_or(0.0);
+                let padding_top = self
+                    .padding
+                    .top
+                    .try_resolve_with_scale(height, 1.0)
+                    .unwrap_or(0.0);
+                let padding_bottom = self
+                    .padding
+                    .bottom
+                    .try_resolve_with_scale(height, 1.0)
+                    .unwrap_or(0.0);
 
-                        let content_rect = Rect::new(
-                            [
-                                layout.rect.min[0] + padding_left,
-                                layout.rect.min[1] + padding_top,
-                            ],
-                            [
-                                layout.rect.max[0] - padding_right,
-                                layout.rect.max[1] - padding_bottom,
-                            ],
-                        );
-                        let mut text_shape =
-                            crate::primitives::TextShape::new(content_rect, text_content);
-                        text_shape.apply_opacity(combined_opacity);
-                        shapes.push((layout.rect, Shape::Text(text_shape)));
-                    }
-                }
+                let content_rect = Rect::new(
+                    [
+                        layout.rect.min[0] + padding_left,
+                        layout.rect.min[1] + padding_top,
+                    ],
+                    [
+                        layout.rect.max[0] - padding_right,
+                        layout.rect.max[1] - padding_bottom,
+                    ],
+                );
+                let mut text_shape = crate::primitives::TextShape::new(content_rect, text_content);
+                text_shape.apply_opacity(combined_opacity);
+                shapes.push((layout.rect, Shape::Text(text_shape)));
             }
         }
 
@@ -1,13 +1,22 @@
+use crate::canvas::Painter;
+use crate::color::Color;
+use crate::content::{HorizontalAlign, VerticalAlign};
+use crate::filter::ColorMatrix;
 use crate::layout::{Overflow, Size, Transform2D};
 use crate::measure::ContentMeasurer;
 use crate::node::Node;
-use crate::primitives::{AntiAliasing, ClippedShape, Rect, Shape, Stroke};
+use crate::primitives::{AntiAliasing, ClippedShape, Rect, Shadow, Shape, Stroke, StyledRect};
+use crate::text_edit::LineRect;
+use std::cell::RefCell;
 
 /// Output from the UI system containing all shapes to render
 #[derive(Clone, Debug, Default)]
 pub struct FullOutput {
     pub shapes: Vec<ClippedShape>,
     pub debug_options: Option<crate::debug::DebugOptions>,
+    /// Cursor and window requests for the platform layer, from
+    /// `UiContext::take_platform_output` (not serialized by `to_bytes`)
+    pub platform: crate::platform::PlatformOutput,
 }
 
 impl FullOutput {
@@ -18,7 +27,7 @@ impl FullOutput {
     pub fn with_shapes(shapes: Vec<ClippedShape>) -> Self {
         Self {
             shapes,
-            debug_options: None,
+            ..Self::default()
         }
     }
 
@@ -136,6 +145,40 @@ impl FullOutput {
         root: Node,
         window_size: (f32, f32),
         debug_options: Option<crate::debug::DebugOptions>,
+    ) -> Self {
+        Self::from_laid_out_tree(&root, window_size, debug_options)
+    }
+
+    /// Create output from an already-laid-out node tree without taking it,
+    /// such as the tree kept by `RetainedTree`
+    ///
+    /// `window_size` is the (width, height) of the window
+    /// `debug_options` configures which debug visualizations to show
+    pub fn from_laid_out_tree(
+        root: &Node,
+        window_size: (f32, f32),
+        debug_options: Option<crate::debug::DebugOptions>,
+    ) -> Self {
+        Self::from_tree(root, window_size, debug_options, false)
+    }
+
+    /// Create output from the tree kept by `RetainedTree`, reusing the shapes
+    /// cached for its unchanged revisioned subtrees
+    pub(crate) fn from_retained_tree(
+        root: &Node,
+        window_size: (f32, f32),
+        debug_options: Option<crate::debug::DebugOptions>,
+    ) -> Self {
+        // Debug overlays aren't cached
+        let cache_shapes = !debug_options.is_some_and(|options| options.is_enabled());
+        Self::from_tree(root, window_size, debug_options, cache_shapes)
+    }
+
+    fn from_tree(
+        root: &Node,
+        window_size: (f32, f32),
+        debug_options: Option<crate::debug::DebugOptions>,
+        cache_shapes: bool,
     ) -> Self {
         // Get the effective scale factor from the root node
         let effective_scale_factor = root.zoom().unwrap_or(1.0);
@@ -164,7 +207,7 @@ impl FullOutput {
         let mut raw_shapes = Vec::new();
         let mut tree_index = 0;
         collect_clipped_shapes(
-            &root,
+            root,
             window_rect,
             window_rect,
             initial_transform, // Start with pan offset applied
@@ -173,17 +216,18 @@ impl FullOutput {
             crate::layout::ZIndex::DEFAULT, // Initial z_index
             &mut tree_index,                // Track tree order
             effective_scale_factor,
+            cache_shapes,
         );
 
         // Sort shapes by (z_index, tree_index) for correct layering
         // Lower z_index renders first (bottom), higher z_index renders last (top)
         // Within same z_index, tree order is preserved (stable sort)
-        raw_shapes.sort_by_key(|(_, _, _, _, _, z_index, tree_idx)| (*z_index, *tree_idx));
+        raw_shapes.sort_by_key(|(_, _, _, _, _, z_index, tree_idx, _)| (*z_index, *tree_idx));
 
-        let shapes = raw_shapes
+        let mut shapes: Vec<ClippedShape> = raw_shapes
             .into_iter()
             .map(
-                |(rect, clip_rect, shape, transform, opacity, z_index, tree_idx)| {
+                |(rect, clip_rect, shape, transform, opacity, z_index, tree_idx, filter)| {
                     // Apply the rect to the shape if it's a StyledRect.
                     // Text already carries its own bounding rect internally (TextShape::rect).
                     let shape_with_rect = match shape {
@@ -203,18 +247,97 @@ impl FullOutput {
                             .with_opacity(opacity);
                     clipped.z_index = z_index;
                     clipped.tree_index = tree_idx;
+                    clipped.filter = filter;
                     clipped
                 },
             )
             .collect();
 
+        // The overdraw view shows every shape, hidden or not
+        if !debug_options.is_some_and(|options| options.show_overdraw) {
+            skip_occluded_shapes(&mut shapes, window_rect);
+        }
+
         Self {
             shapes,
             debug_options,
+            ..Self::default()
+        }
+    }
+
+    /// Draw another output on top of this one
+    ///
+    /// Used for layers that live outside the UI tree, such as the drag ghost
+    /// from `UiContext::drag_ghost_layer`.
+    pub fn add_layer(&mut self, layer: FullOutput) {
+        self.shapes.extend(layer.shapes);
+    }
+
+    /// Append the interaction heatmap overlay if `DebugOptions::show_heatmap` is enabled
+    ///
+    /// `window_size` is the (width, height) of the window
+    pub fn add_heatmap_overlay(
+        &mut self,
+        heatmap: &crate::heatmap::InteractionHeatmap,
+        window_size: (f32, f32),
+    ) {
+        if self.debug_options.is_some_and(|o| o.show_heatmap) {
+            let window_rect = Rect::new([0.0, 0.0], [window_size.0, window_size.1]);
+            self.shapes.extend(heatmap.overlay_shapes(window_rect));
         }
     }
 }
 
+/// Drop every shape drawn before the topmost opaque shape covering the whole window
+///
+/// `shapes` must be in draw order. An opaque full-window layer (a modal scrim, a full-screen
+/// page) hides everything below it, so those shapes are never tessellated, uploaded or drawn.
+fn skip_occluded_shapes(shapes: &mut Vec<ClippedShape>, window_rect: Rect) {
+    if let Some(cover) = shapes
+        .iter()
+        .rposition(|shape| covers_opaquely(shape, &window_rect))
+    {
+        shapes.drain(..cover);
+    }
+}
+
+fn covers_opaquely(shape: &ClippedShape, window_rect: &Rect) -> bool {
+    let Shape::Rect(styled_rect) = &shape.shape else {
+        return false;
+    };
+
+    // Rotated or scaled shapes are rare as backdrops; don't bother with their bounds
+    let transform = &shape.transform;
+    if transform.rotation != 0.0 || transform.scale != 1.0 {
+        return false;
+    }
+
+    let rect = Rect::new(
+        [
+            styled_rect.rect.min[0] + transform.translation.x,
+            styled_rect.rect.min[1] + transform.translation.y,
+        ],
+        [
+            styled_rect.rect.max[0] + transform.translation.x,
+            styled_rect.rect.max[1] + transform.translation.y,
+        ],
+    );
+
+    shape.opacity >= 1.0
+        && styled_rect.fill.a >= 1.0
+        && styled_rect.conic_gradient.is_none()
+        && styled_rect.linear_gradient.is_none()
+        && styled_rect.nine_slice.is_none()
+        && styled_rect.blur.resolve_physical_or_zero(1.0) <= 0.0
+        && styled_rect
+            .corner_shape
+            .extent()
+            .resolve_physical_or_zero(1.0)
+            <= 0.0
+        && shape.clip_rect.contains_rect(window_rect)
+        && rect.contains_rect(window_rect)
+}
+
 // Recursively walk the node tree to associate a clip rect with each collected shape.
 fn collect_clipped_shapes(
     node: &Node,
@@ -222,18 +345,11 @@ fn collect_clipped_shapes(
     inherited_clip_rect: Rect,
     parent_transform: Transform2D,
     debug_options: Option<crate::debug::DebugOptions>,
-    out: &mut Vec<(
-        Rect,
-        Rect,
-        Shape,
-        Transform2D,
-        f32,
-        crate::layout::ZIndex,
-        usize,
-    )>,
+    out: &mut Vec<RawShape>,
     parent_z_index: crate::layout::ZIndex,
     tree_index: &mut usize,
     scale_factor: f32,
+    cache_shapes: bool,
 ) {
     collect_clipped_shapes_with_opacity(
         node,
@@ -242,35 +358,147 @@ fn collect_clipped_shapes(
         parent_transform,
         debug_options,
         out,
-        1.0,
+        Inherited::ROOT,
         parent_z_index,
         tree_index,
         scale_factor,
+        cache_shapes,
     );
 }
 
-// Recursively walk the node tree with cumulative opacity.
+/// A collected shape before sorting: rect, clip rect, shape, transform,
+/// opacity, z-index, tree index and layer filter
+type RawShape = (
+    Rect,
+    Rect,
+    Shape,
+    Transform2D,
+    f32,
+    crate::layout::ZIndex,
+    usize,
+    Option<ColorMatrix>,
+);
+
+/// Shapes a revisioned subtree of a `RetainedTree` collected last time
+///
+/// Reused while the subtree is unchanged and its ancestors pass down the
+/// same clip, transform and cascaded values. The retained tree clears it when
+/// the subtree changes or is laid out again.
+#[derive(Default)]
+pub(crate) enum ShapeCache {
+    #[default]
+    Empty,
+    /// The subtree's visuals also change in place each frame (style
+    /// transitions, scrolling, ...), so it is collected every time
+    Volatile,
+    Shapes {
+        context: CacheContext,
+        /// Shapes with tree indices counted from the subtree's first
+        shapes: Vec<RawShape>,
+        /// Tree indices the subtree took
+        tree_indices: usize,
+    },
+}
+
+/// What a subtree's shapes depend on besides the subtree itself
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct CacheContext {
+    window_rect: Rect,
+    clip_rect: Rect,
+    transform: Transform2D,
+    inherited: Inherited,
+    z_index: crate::layout::ZIndex,
+    scale_factor: f32,
+}
+
+/// Shape cache slot kept on every node
+pub(crate) type ShapeCacheCell = RefCell<ShapeCache>;
+
+/// Values cascaded from ancestors while collecting shapes
+#[derive(Clone, Copy, PartialEq)]
+struct Inherited {
+    /// Product of all ancestor opacities
+    opacity: f32,
+    /// Nearest ancestor `Node::text_color`
+    text_color: Option<Color>,
+    /// Nearest ancestor `Node::font_size`
+    font_size: Option<Size>,
+    /// Composition of all ancestor `Node::filter`s (innermost first)
+    filter: Option<ColorMatrix>,
+}
+
+impl Inherited {
+    const ROOT: Self = Self {
+        opacity: 1.0,
+        text_color: None,
+        font_size: None,
+        filter: None,
+    };
+}
+
+// Recursively walk the node tree with cumulative opacity and inherited text properties.
 fn collect_clipped_shapes_with_opacity(
     node: &Node,
     window_rect: Rect,
     inherited_clip_rect: Rect,
     parent_transform: Transform2D,
     debug_options: Option<crate::debug::DebugOptions>,
-    out: &mut Vec<(
-        Rect,
-        Rect,
-        Shape,
-        Transform2D,
-        f32,
-        crate::layout::ZIndex,
-        usize,
-    )>,
-    parent_opacity: f32,
+    out: &mut Vec<RawShape>,
+    parent: Inherited,
     parent_z_index: crate::layout::ZIndex,
     tree_index: &mut usize,
     scale_factor: f32,
+    cache_shapes: bool,
 ) {
-    let combined_opacity = parent_opacity * node.opacity();
+    // Unchanged revisioned subtrees of a retained tree reuse their shapes
+    let cache_context = (cache_shapes && node.revision().is_some()).then_some(CacheContext {
+        window_rect,
+        clip_rect: inherited_clip_rect,
+        transform: parent_transform,
+        inherited: parent,
+        z_index: parent_z_index,
+        scale_factor,
+    });
+    if let Some(context) = &cache_context {
+        if let ShapeCache::Shapes {
+            context: cached,
+            shapes,
+            tree_indices,
+        } = &*node.shape_cache().borrow()
+        {
+            if cached == context {
+                let first = *tree_index;
+                out.extend(shapes.iter().map(
+                    |(rect, clip, shape, transform, opacity, z, index, filter)| {
+                        (
+                            *rect,
+                            *clip,
+                            shape.clone(),
+                            *transform,
+                            *opacity,
+                            *z,
+                            first + index,
+                            *filter,
+                        )
+                    },
+                ));
+                *tree_index += tree_indices;
+                return;
+            }
+        }
+    }
+    let (shapes_start, first_tree_index) = (out.len(), *tree_index);
+
+    let combined_opacity = parent.opacity * node.opacity();
+    let inherited = Inherited {
+        opacity: combined_opacity,
+        text_color: node.text_color().or(parent.text_color),
+        font_size: node.font_size().or(parent.font_size),
+        filter: match (node.filter(), parent.filter) {
+            (Some(own), Some(outer)) => Some(own.then(&outer)),
+            (own, outer) => own.or(outer),
+        },
+    };
 
     // Determine this node's z_index (inherit from parent if not set)
     let current_z_index = node.z_index().unwrap_or(parent_z_index);
@@ -293,15 +521,7 @@ fn collect_clipped_shapes_with_opacity(
     ];
 
     // Build local transform from node properties
-    let local_transform = Transform2D {
-        translation: node
-            .translation()
-            .resolve(rect_size[0], rect_size[1], scale_factor),
-        rotation: node.rotation(),
-        scale: node.scale(),
-        origin: node.transform_origin(),
-        absolute_origin: None, // Will be set during composition if needed
-    };
+    let local_transform = local_transform(node, rect_size, scale_factor);
 
     // Accumulate transforms: parent → local
     let mut world_transform = parent_transform.then(&local_transform, rect_size);
@@ -329,6 +549,13 @@ fn collect_clipped_shapes_with_opacity(
         return;
     }
 
+    // Shapes the node draws itself start here (drawn into its filter layer
+    // below, debug shapes are not)
+    let own_shapes_start = out.len();
+
+    // Resolved corner shape of the background rect, followed by the outline
+    let mut resolved_corner_shape = crate::CornerShape::None;
+
     // Background shape (if any)
     // The node's own shape uses the inherited clip rect (from parent), not effective_clip_rect.
     // This ensures the container's border/background is not clipped by its own overflow policy.
@@ -382,8 +609,106 @@ fn collect_clipped_shapes_with_opacity(
                             smoothness,
                         }
                     }
+                    crate::CornerShape::PerCorner(corners) => {
+                        crate::CornerShape::PerCorner(corners.map(|corner| {
+                            corner.map_size(|size| {
+                                Size::ppx(
+                                    size.try_resolve_with_scale(min_dim, scale_factor)
+                                        .unwrap_or(0.0),
+                                )
+                            })
+                        }))
+                    }
                     crate::CornerShape::None => crate::CornerShape::None,
                 };
+                resolved_corner_shape = scaled_rect.corner_shape;
+
+                scaled_rect.blur = Size::ppx(
+                    scaled_rect
+                        .blur
+                        .try_resolve_with_scale(min_dim, scale_factor)
+                        .unwrap_or(0.0),
+                );
+
+                if let Some(tint) = node.tint() {
+                    scaled_rect.fill = tint_fill(scaled_rect.fill, tint);
+                    if let Some(gradient) = &mut scaled_rect.conic_gradient {
+                        gradient.map_colors(|color| tint_fill(color, tint));
+                    }
+                    if let Some(gradient) = &mut scaled_rect.linear_gradient {
+                        gradient.map_colors(|color| tint_fill(color, tint));
+                    }
+                    if let Some(image) = &mut scaled_rect.nine_slice {
+                        image.tint = tint_fill(image.tint, tint);
+                    }
+                }
+
+                if let Some(image) = &mut scaled_rect.nine_slice {
+                    image.slice.scale *= scale_factor;
+                }
+
+                // Inset shadow sizes resolve against the node like the drop shadow's
+                scaled_rect.inset_shadow = scaled_rect
+                    .inset_shadow
+                    .filter(|shadow| shadow.color.a > 0.0)
+                    .map(|shadow| {
+                        let resolve = |size: Size| {
+                            Size::ppx(
+                                size.try_resolve_with_scale(width, scale_factor)
+                                    .unwrap_or(0.0),
+                            )
+                        };
+                        Shadow::new(
+                            resolve(shadow.offset_x),
+                            resolve(shadow.offset_y),
+                            resolve(shadow.blur),
+                            shadow.color,
+                        )
+                        .with_spread(resolve(shadow.spread))
+                    });
+
+                // Drop shadow (if any): a blurred copy of the rect pushed just before it,
+                // so it is drawn behind the background
+                if let Some(shadow) = node.shadow().filter(|shadow| shadow.color.a > 0.0) {
+                    let resolve = |size: Size| {
+                        size.try_resolve_with_scale(width, scale_factor)
+                            .unwrap_or(0.0)
+                    };
+                    let [dx, dy] = [resolve(shadow.offset_x), resolve(shadow.offset_y)];
+                    let spread = resolve(shadow.spread);
+                    let shadow_rect = Rect::new(
+                        [
+                            node_rect.min[0] + dx - spread,
+                            node_rect.min[1] + dy - spread,
+                        ],
+                        [
+                            node_rect.max[0] + dx + spread,
+                            node_rect.max[1] + dy + spread,
+                        ],
+                    );
+
+                    if !is_empty_rect(shadow_rect) {
+                        out.push((
+                            shadow_rect,
+                            inherited_clip_rect,
+                            Shape::Rect(
+                                StyledRect::new(shadow_rect, shadow.color)
+                                    .with_corner_shape(expand_corner_shape(
+                                        resolved_corner_shape,
+                                        spread,
+                                    ))
+                                    .with_blur(Size::ppx(resolve(shadow.blur).max(0.0))),
+                            ),
+                            // Keep rotation/scale pivoting around the node
+                            pin_transform_origin(world_transform, node_rect),
+                            combined_opacity,
+                            current_z_index,
+                            *tree_index,
+                            None,
+                        ));
+                        *tree_index += 1;
+                    }
+                }
 
                 Shape::Rect(scaled_rect)
             }
@@ -416,71 +741,181 @@ fn collect_clipped_shapes_with_opacity(
             combined_opacity,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
     }
 
+    // Outline (if any): a separate instance around the node rect, so it never affects layout
+    if let Some(outline) = node.outline() {
+        let width = node_rect.max[0] - node_rect.min[0];
+        let outline_width = outline
+            .width
+            .try_resolve_with_scale(width, scale_factor)
+            .unwrap_or(0.0);
+        let offset = outline
+            .offset
+            .try_resolve_with_scale(width, scale_factor)
+            .unwrap_or(0.0);
+
+        if outline_width > 0.0 && outline.color.a > 0.0 {
+            let expand = offset + outline_width;
+            let outline_rect = Rect::new(
+                [node_rect.min[0] - expand, node_rect.min[1] - expand],
+                [node_rect.max[0] + expand, node_rect.max[1] + expand],
+            );
+
+            // Keep rotation/scale pivoting around the node, not the larger outline rect
+            let outline_transform = pin_transform_origin(world_transform, node_rect);
+
+            out.push((
+                outline_rect,
+                inherited_clip_rect,
+                Shape::Rect(
+                    StyledRect::new(outline_rect, Color::transparent())
+                        .with_corner_shape(expand_corner_shape(resolved_corner_shape, expand))
+                        .with_stroke(Stroke::new(Size::ppx(outline_width), outline.color)),
+                ),
+                outline_transform,
+                combined_opacity,
+                current_z_index,
+                *tree_index,
+                None,
+            ));
+            *tree_index += 1;
+        }
+    }
+
     // Content (if any)
     if let Some(content) = node.content() {
-        match content {
-            crate::content::Content::Text(text_content) => {
-                // Content uses the node's content rect (after padding) as its bounding box,
-                // but still inherits the node/ancestor clip rect.
-                let padding = node.padding();
-                let width = node_rect.max[0] - node_rect.min[0];
-                let height = node_rect.max[1] - node_rect.min[1];
-                let padding_left = padding
-                    .left
-                    .try_resolve_with_scale(width, scale_factor)
-                    .unwrap_or(0.0);
-                let padding_right = padding
-                    .right
-                    .try_resolve_with_scale(width, scale_factor)
-                    .unwrap_or(0.0);
-                let padding_top = padding
-                    .top
-                    .try_resolve_with_scale(height, scale_factor)
-                    .unwrap_or(0.0);
-                let padding_bottom = padding
-                    .bottom
-                    .try_resolve_with_scale(height, scale_factor)
-                    .unwrap_or(0.0);
-
-                let content_rect = Rect::new(
-                    [
-                        node_rect.min[0] + padding_left,
-                        node_rect.min[1] + padding_top,
-                    ],
-                    [
-                        node_rect.max[0] - padding_right,
-                        node_rect.max[1] - padding_bottom,
-                    ],
+        let text_content = content.text();
+        // Content uses the node's content rect (after padding) as its bounding box,
+        // but still inherits the node/ancestor clip rect.
+        let content_rect = padded_rect(node, node_rect, scale_factor);
+        let width = node_rect.max[0] - node_rect.min[0];
+        let mut text_shape = crate::primitives::TextShape::new(content_rect, text_content);
+        if text_content.inherit.color {
+            if let Some(color) = inherited.text_color {
+                text_shape.color = color;
+            }
+        }
+        let font_size = match inherited.font_size {
+            Some(size) if text_content.inherit.font_size => size,
+            _ => text_content.font_size,
+        };
+        // Scale font size by scale_factor for zoom, and by the text scale
+        let text_scale = scale_factor * node.resolved_text_scale();
+        let scaled_font_size = font_size
+            .try_resolve_with_scale(width, text_scale)
+            .unwrap_or(16.0);
+        text_shape.font_size = Size::lpx(scaled_font_size);
+        // The outline grows with the glyphs it surrounds
+        if let Some(outline) = &mut text_shape.outline {
+            outline.width = Size::lpx(
+                outline
+                    .width
+                    .try_resolve_with_scale(width, text_scale)
+                    .unwrap_or(0.0),
+            );
+        }
+        text_shape.wrap = text_content.wrap;
+        text_shape.line_height_multiplier = text_content.line_height_multiplier;
+
+        // Editor selection below the text, caret above it
+        let text_edit = node.text_edit();
+        let decoration_transform = pin_transform_origin(world_transform, node_rect);
+        let push_decoration =
+            |out: &mut Vec<_>, tree_index: &mut usize, line_rect: &LineRect, color: Color| {
+                let rect = text_edit_rect(
+                    line_rect,
+                    content_rect,
+                    text_edit.map_or(0.0, |edit| edit.block_height),
+                    text_content.h_align,
+                    text_content.v_align,
+                    scale_factor,
                 );
-                let mut text_shape = crate::primitives::TextShape::new(content_rect, text_content);
-                // Scale font size by scale_factor for zoom
-                let scaled_font_size = text_content
-                    .font_size
-                    .try_resolve_with_scale(width, scale_factor)
-                    .unwrap_or(16.0);
-                text_shape.font_size = Size::lpx(scaled_font_size);
-                text_shape.wrap = text_content.wrap;
-                text_shape.line_height_multiplier = text_content.line_height_multiplier;
-                // OPTIMIZATION: Store opacity instead of applying it to shape
                 out.push((
-                    node_rect,
+                    rect,
                     effective_clip_rect,
-                    Shape::Text(text_shape),
-                    world_transform,
+                    Shape::Rect(StyledRect::new(rect, color)),
+                    decoration_transform,
                     combined_opacity,
                     current_z_index,
                     *tree_index,
+                    None,
                 ));
                 *tree_index += 1;
+            };
+        if let Some(edit) = text_edit {
+            for line_rect in &edit.selection {
+                push_decoration(out, tree_index, line_rect, edit.selection_color);
             }
         }
+
+        // OPTIMIZATION: Store opacity instead of applying it to shape
+        out.push((
+            node_rect,
+            effective_clip_rect,
+            Shape::Text(text_shape),
+            world_transform,
+            combined_opacity,
+            current_z_index,
+            *tree_index,
+            None,
+        ));
+        *tree_index += 1;
+
+        if let Some(edit) = text_edit {
+            if let Some(caret) = &edit.caret {
+                push_decoration(out, tree_index, caret, edit.caret_color);
+            }
+        }
+    }
+
+    // Painter drawing (if any), in the content rect like content
+    if let Some(paint) = node.painter() {
+        let mut painter = Painter::new(
+            padded_rect(node, node_rect, scale_factor),
+            scale_factor,
+            node.resolved_text_scale(),
+        );
+        paint(&mut painter);
+        let painter_transform = pin_transform_origin(world_transform, node_rect);
+        for painted in painter.into_shapes() {
+            out.push((
+                painted.rect,
+                effective_clip_rect,
+                painted.shape,
+                rotate_about_center(painter_transform, painted.rect, painted.rotation),
+                combined_opacity,
+                current_z_index,
+                *tree_index,
+                None,
+            ));
+            *tree_index += 1;
+        }
+    }
+
+    // Apply scroll offset to children if this is a scroll container
+    let child_transform = if node.overflow() == Overflow::Scroll {
+        let scroll_offset = node.scroll_offset();
+        let mut scrolled_transform = world_transform;
+        scrolled_transform.translation.x -= scroll_offset.0;
+        scrolled_transform.translation.y -= scroll_offset.1;
+        scrolled_transform
+    } else {
+        world_transform
+    };
+
+    if inherited.filter.is_some() {
+        for (.., filter) in &mut out[own_shapes_start..] {
+            *filter = inherited.filter;
+        }
     }
 
     // Debug overlays (if enabled) must also be overflow-clipped consistently.
+    // They pivot around the node like its own shape, so they line up with it
+    // under rotation and scale.
     if let Some(options) = debug_options {
         if options.is_enabled() {
             collect_debug_shapes_clipped(
@@ -488,7 +923,7 @@ fn collect_clipped_shapes_with_opacity(
                 node_rect,
                 effective_clip_rect,
                 &options,
-                &world_transform,
+                &pin_transform_origin(world_transform, node_rect),
                 out,
                 scale_factor,
                 current_z_index,
@@ -497,14 +932,14 @@ fn collect_clipped_shapes_with_opacity(
         }
     }
 
-    // Collect gap debug shapes between children
+    // Collect gap debug shapes between children, in the children's (scrolled) space
     if let Some(options) = debug_options {
         if options.show_gaps && node.gap().is_non_zero() {
             collect_gap_debug_shapes(
                 node,
                 effective_clip_rect,
                 &options,
-                &world_transform,
+                &pin_transform_origin(child_transform, node_rect),
                 out,
                 scale_factor,
                 current_z_index,
@@ -513,17 +948,6 @@ fn collect_clipped_shapes_with_opacity(
         }
     }
 
-    // Apply scroll offset to children if this is a scroll container
-    let child_transform = if node.overflow() == Overflow::Scroll {
-        let scroll_offset = node.scroll_offset();
-        let mut scrolled_transform = world_transform;
-        scrolled_transform.translation.x -= scroll_offset.0;
-        scrolled_transform.translation.y -= scroll_offset.1;
-        scrolled_transform
-    } else {
-        world_transform
-    };
-
     for child in node.children() {
         collect_clipped_shapes_with_opacity(
             child,
@@ -532,12 +956,238 @@ fn collect_clipped_shapes_with_opacity(
             child_transform, // Pass accumulated transform with scroll offset
             debug_options,
             out,
-            combined_opacity,
+            inherited,
             current_z_index, // Pass down current z_index
             tree_index,      // Pass through tree_index counter
             scale_factor,
+            cache_shapes,
+        );
+    }
+
+    if let Some(context) = cache_context {
+        let mut cache = node.shape_cache().borrow_mut();
+        if !matches!(*cache, ShapeCache::Volatile) {
+            *cache = if node.has_static_visuals() {
+                ShapeCache::Shapes {
+                    context,
+                    shapes: out[shapes_start..]
+                        .iter()
+                        .map(
+                            |(rect, clip, shape, transform, opacity, z, index, filter)| {
+                                let index = index - first_tree_index;
+                                let shape = shape.clone();
+                                (
+                                    *rect, *clip, shape, *transform, *opacity, *z, index, *filter,
+                                )
+                            },
+                        )
+                        .collect(),
+                    tree_indices: *tree_index - first_tree_index,
+                }
+            } else {
+                ShapeCache::Volatile
+            };
+        }
+    }
+}
+
+/// The node's content rect: its rect inset by the resolved padding
+fn padded_rect(node: &Node, node_rect: Rect, scale_factor: f32) -> Rect {
+    let padding = node.padding();
+    let width = node_rect.max[0] - node_rect.min[0];
+    let height = node_rect.max[1] - node_rect.min[1];
+    let padding_left = padding
+        .left
+        .try_resolve_with_scale(width, scale_factor)
+        .unwrap_or(0.0);
+    let padding_right = padding
+        .right
+        .try_resolve_with_scale(width, scale_factor)
+        .unwrap_or(0.0);
+    let padding_top = padding
+        .top
+        .try_resolve_with_scale(height, scale_factor)
+        .unwrap_or(0.0);
+    let padding_bottom = padding
+        .bottom
+        .try_resolve_with_scale(height, scale_factor)
+        .unwrap_or(0.0);
+
+    Rect::new(
+        [
+            node_rect.min[0] + padding_left,
+            node_rect.min[1] + padding_top,
+        ],
+        [
+            node_rect.max[0] - padding_right,
+            node_rect.max[1] - padding_bottom,
+        ],
+    )
+}
+
+/// Compose a rotation of a shape around its own center with the transform of
+/// its node
+///
+/// The renderer rotates each shape around a single origin, so the combined
+/// rotation pivots around the shape's center, with the translation moving
+/// that center to where the node transform puts it.
+fn rotate_about_center(transform: Transform2D, rect: Rect, rotation: f32) -> Transform2D {
+    if rotation == 0.0 || transform.scale.abs() <= f32::EPSILON {
+        return transform;
+    }
+    let rotate = |[x, y]: [f32; 2], angle: f32| {
+        let (sin, cos) = angle.sin_cos();
+        [x * cos + y * sin, -x * sin + y * cos]
+    };
+    let center = [
+        (rect.min[0] + rect.max[0]) * 0.5,
+        (rect.min[1] + rect.max[1]) * 0.5,
+    ];
+    let origin = transform.absolute_origin.unwrap_or(center);
+
+    // Where the node transform puts the shape's center
+    let offset = rotate(
+        [
+            (center[0] + transform.translation.x - origin[0]) * transform.scale,
+            (center[1] + transform.translation.y - origin[1]) * transform.scale,
+        ],
+        transform.rotation,
+    );
+    let target = [origin[0] + offset[0], origin[1] + offset[1]];
+
+    // Pivoting around the center, the translation is applied before the
+    // combined scale and rotation, so undo those on the remaining offset
+    let rotation = transform.rotation + rotation;
+    let translation = rotate(
+        [
+            (target[0] - center[0]) / transform.scale,
+            (target[1] - center[1]) / transform.scale,
+        ],
+        -rotation,
+    );
+    Transform2D {
+        translation: crate::layout::Vector2 {
+            x: translation[0],
+            y: translation[1],
+        },
+        rotation,
+        scale: transform.scale,
+        origin: transform.origin,
+        absolute_origin: Some(center),
+    }
+}
+
+/// Transform a node applies to itself and its subtree, relative to its parent
+fn local_transform(node: &Node, rect_size: [f32; 2], scale_factor: f32) -> Transform2D {
+    Transform2D {
+        translation: node
+            .translation()
+            .resolve(rect_size[0], rect_size[1], scale_factor),
+        rotation: node.rotation(),
+        scale: node.scale(),
+        origin: node.transform_origin(),
+        absolute_origin: None, // Will be set during composition if needed
+    }
+}
+
+/// Bounds of `rect` after its node's own transform, in the parent's layout space
+fn locally_transformed_rect(node: &Node, rect: Rect, scale_factor: f32) -> Rect {
+    let size = [rect.max[0] - rect.min[0], rect.max[1] - rect.min[1]];
+    // `Transform2D::apply` resolves the origin relative to the rect, so
+    // transform the rect at the origin and move the bounds back
+    let bounds = compute_transformed_aabb(
+        Rect::new([0.0, 0.0], size),
+        &local_transform(node, size, scale_factor),
+    );
+    Rect::new(
+        [rect.min[0] + bounds.min[0], rect.min[1] + bounds.min[1]],
+        [rect.min[0] + bounds.max[0], rect.min[1] + bounds.max[1]],
+    )
+}
+
+/// Pin the pivot of `transform` to the transform origin of `node_rect`
+///
+/// Without an absolute origin the renderer resolves the origin against each
+/// shape's own rect, so extra shapes drawn for a node (outlines, debug
+/// overlays) would rotate and scale around themselves instead of the node.
+/// Place a caret or selection rect (logical pixels, relative to its line) in
+/// the content rect, aligned like the text block it belongs to
+fn text_edit_rect(
+    line_rect: &LineRect,
+    content_rect: Rect,
+    block_height: f32,
+    h_align: HorizontalAlign,
+    v_align: VerticalAlign,
+    scale_factor: f32,
+) -> Rect {
+    let block_height = block_height * scale_factor;
+    let line_width = line_rect.line_width * scale_factor;
+    let top = match v_align {
+        VerticalAlign::Top => content_rect.min[1],
+        VerticalAlign::Center => content_rect.min[1] + (content_rect.height() - block_height) * 0.5,
+        VerticalAlign::Bottom => content_rect.max[1] - block_height,
+    };
+    let left = match h_align {
+        HorizontalAlign::Left => content_rect.min[0],
+        HorizontalAlign::Center => content_rect.min[0] + (content_rect.width() - line_width) * 0.5,
+        HorizontalAlign::Right => content_rect.max[0] - line_width,
+    };
+    let min = [
+        left + line_rect.x * scale_factor,
+        top + line_rect.y * scale_factor,
+    ];
+    Rect::new(
+        min,
+        [
+            min[0] + line_rect.width * scale_factor,
+            min[1] + line_rect.height * scale_factor,
+        ],
+    )
+}
+
+fn pin_transform_origin(mut transform: Transform2D, node_rect: Rect) -> Transform2D {
+    if transform.absolute_origin.is_none() {
+        let (origin_x, origin_y) = transform.origin.resolve(
+            node_rect.max[0] - node_rect.min[0],
+            node_rect.max[1] - node_rect.min[1],
         );
+        transform.absolute_origin =
+            Some([node_rect.min[0] + origin_x, node_rect.min[1] + origin_y]);
     }
+    transform
+}
+
+/// Grow a resolved (physical pixel) corner shape so it stays concentric when
+/// the rect is expanded by `amount` on every side
+fn expand_corner_shape(shape: crate::CornerShape, amount: f32) -> crate::CornerShape {
+    let grow = |size: Size| match size {
+        Size::Physical(px) if px > 0.0 => Size::ppx((px + amount).max(0.0)),
+        other => other,
+    };
+    match shape {
+        crate::CornerShape::None => crate::CornerShape::None,
+        crate::CornerShape::Round(r) => crate::CornerShape::Round(grow(r)),
+        crate::CornerShape::Cut(d) => crate::CornerShape::Cut(grow(d)),
+        crate::CornerShape::InverseRound(r) => crate::CornerShape::InverseRound(grow(r)),
+        crate::CornerShape::Squircle { radius, smoothness } => crate::CornerShape::Squircle {
+            radius: grow(radius),
+            smoothness,
+        },
+        crate::CornerShape::PerCorner(corners) => {
+            crate::CornerShape::PerCorner(corners.map(|corner| corner.map_size(grow)))
+        }
+    }
+}
+
+/// Mix a tint over a fill color, keeping the fill's alpha so invisible fills stay invisible
+fn tint_fill(fill: Color, tint: Color) -> Color {
+    let mix = |f: f32, t: f32| f + (t - f) * tint.a;
+    Color::rgba(
+        mix(fill.r, tint.r),
+        mix(fill.g, tint.g),
+        mix(fill.b, tint.b),
+        fill.a,
+    )
 }
 
 fn intersect_rect(a: Rect, b: Rect) -> Rect {
@@ -586,15 +1236,7 @@ fn collect_debug_shapes_clipped(
     clip_rect: Rect,
     options: &crate::debug::DebugOptions,
     transform: &Transform2D,
-    out: &mut Vec<(
-        Rect,
-        Rect,
-        Shape,
-        Transform2D,
-        f32,
-        crate::layout::ZIndex,
-        usize,
-    )>,
+    out: &mut Vec<RawShape>,
     scale_factor: f32,
     current_z_index: crate::layout::ZIndex,
     tree_index: &mut usize,
@@ -652,6 +1294,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -671,6 +1314,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -693,6 +1337,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -712,6 +1357,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -763,6 +1409,7 @@ fn collect_debug_shapes_clipped(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
     }
@@ -790,6 +1437,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -812,6 +1460,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -831,6 +1480,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -853,6 +1503,7 @@ fn collect_debug_shapes_clipped(
                 1.0,
                 current_z_index,
                 *tree_index,
+                None,
             ));
             *tree_index += 1;
         }
@@ -872,6 +1523,7 @@ fn collect_debug_shapes_clipped(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
     }
@@ -891,6 +1543,7 @@ fn collect_debug_shapes_clipped(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
     }
@@ -927,6 +1580,7 @@ fn collect_debug_shapes_clipped(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
 
@@ -951,6 +1605,7 @@ fn collect_debug_shapes_clipped(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
 
@@ -980,6 +1635,7 @@ fn collect_debug_shapes_clipped(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
     }
@@ -990,16 +1646,8 @@ fn collect_gap_debug_shapes(
     clip_rect: Rect,
     _options: &crate::debug::DebugOptions,
     transform: &Transform2D,
-    out: &mut Vec<(
-        Rect,
-        Rect,
-        Shape,
-        Transform2D,
-        f32,
-        crate::layout::ZIndex,
-        usize,
-    )>,
-    _scale_factor: f32,
+    out: &mut Vec<RawShape>,
+    scale_factor: f32,
     current_z_index: crate::layout::ZIndex,
     tree_index: &mut usize,
 ) {
@@ -1027,8 +1675,11 @@ fn collect_gap_debug_shapes(
             continue;
         };
 
-        let current_rect = current_layout.rect;
-        let next_rect = next_layout.rect;
+        // Where the children actually end up, including their own translation,
+        // rotation and scale
+        let current_rect =
+            locally_transformed_rect(current_child, current_layout.rect, scale_factor);
+        let next_rect = locally_transformed_rect(next_child, next_layout.rect, scale_factor);
 
         // Calculate gap rect based on layout direction
         let gap_rect = match layout_direction {
@@ -1052,6 +1703,11 @@ fn collect_gap_debug_shapes(
             }
         };
 
+        // Transformed children can touch or overlap, leaving no visible gap
+        if is_empty_rect(gap_rect) {
+            continue;
+        }
+
         // Draw purple semi-transparent rectangle for gap
         out.push((
             gap_rect,
@@ -1064,7 +1720,576 @@ fn collect_gap_debug_shapes(
             1.0,
             current_z_index,
             *tree_index,
+            None,
         ));
         *tree_index += 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Content, Layout, Spacing, Style, TextContent, Translation, ZIndex};
+
+    fn panel(color: Color) -> Node {
+        Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_style(Style {
+                fill_color: Some(color),
+                ..Default::default()
+            })
+    }
+
+    fn build(overlay: Color) -> FullOutput {
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Stack)
+            .with_child(panel(Color::rgb(0.2, 0.2, 0.2)))
+            .with_child(panel(overlay).with_z_index(ZIndex::OVERLAY))
+            .with_child(panel(Color::rgb(0.5, 0.5, 0.5)));
+        FullOutput::from_node(root, (100.0, 100.0))
+    }
+
+    fn fills(output: &FullOutput) -> Vec<Color> {
+        output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::Rect(rect) => Some(rect.fill),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_opaque_full_window_layer_hides_lower_layers() {
+        let overlay = Color::rgb(1.0, 0.0, 0.0);
+        assert_eq!(fills(&build(overlay)), vec![overlay]);
+    }
+
+    #[test]
+    fn test_translucent_layer_keeps_lower_layers() {
+        // The translucent overlay reveals the opaque panel below it, which in
+        // turn still hides the first panel
+        let overlay = Color::rgba(1.0, 0.0, 0.0, 0.5);
+        assert_eq!(
+            fills(&build(overlay)),
+            vec![Color::rgb(0.5, 0.5, 0.5), overlay]
+        );
+    }
+
+    #[test]
+    fn test_blurred_full_window_rect_keeps_lower_layers() {
+        let window_rect = Rect::new([0.0, 0.0], [100.0, 100.0]);
+        let mut rect = StyledRect::new(window_rect, Color::rgb(0.0, 0.0, 0.0));
+        assert!(covers_opaquely(
+            &ClippedShape::new(window_rect, Shape::Rect(rect.clone())),
+            &window_rect
+        ));
+
+        // Soft edges let what's below show through
+        rect.blur = Size::lpx(8.0);
+        assert!(!covers_opaquely(
+            &ClippedShape::new(window_rect, Shape::Rect(rect)),
+            &window_rect
+        ));
+    }
+
+    #[test]
+    fn test_overdraw_view_keeps_occluded_layers() {
+        let overlay = Color::rgb(1.0, 0.0, 0.0);
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Stack)
+            .with_child(panel(Color::rgb(0.2, 0.2, 0.2)))
+            .with_child(panel(overlay));
+        let debug = crate::debug::DebugOptions::none().with_overdraw(true);
+        let output = FullOutput::from_node_with_debug(root, (100.0, 100.0), Some(debug));
+        assert_eq!(fills(&output), vec![Color::rgb(0.2, 0.2, 0.2), overlay]);
+    }
+
+    #[test]
+    fn test_text_properties_cascade_from_ancestors() {
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let blue = Color::rgb(0.0, 0.0, 1.0);
+        let green = Color::rgb(0.0, 1.0, 0.0);
+        let text = |content: TextContent| {
+            Node::new()
+                .with_width(Size::lpx(50.0))
+                .with_height(Size::lpx(20.0))
+                .with_content(Content::Text(content))
+        };
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_text_color(red)
+            .with_font_size(Size::lpx(24.0))
+            .with_child(text(TextContent::new("inherits")))
+            .with_child(text(TextContent::new("own color").with_color(blue)))
+            .with_child(
+                Node::new()
+                    .with_text_color(green)
+                    .with_child(text(TextContent::new("nearest"))),
+            );
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        let texts: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::Text(text) => Some((text.color, text.font_size)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (red, Size::lpx(24.0)),
+                (blue, Size::lpx(24.0)),
+                (green, Size::lpx(24.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text_scale_multiplies_font_sizes_only() {
+        let text = |label: &str| {
+            Node::new()
+                .with_width(Size::lpx(50.0))
+                .with_height(Size::lpx(20.0))
+                .with_padding(Spacing::all(Size::lpx(4.0)))
+                .with_content(Content::Text(
+                    TextContent::new(label).with_font_size(Size::lpx(10.0)),
+                ))
+        };
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_text_scale(2.0)
+            .with_child(text("scaled"))
+            .with_child(text("opted out").with_text_scale(1.0));
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        let texts: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::Text(text) => Some((text.font_size, text.rect.width())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            vec![(Size::lpx(20.0), 42.0), (Size::lpx(10.0), 42.0)]
+        );
+    }
+
+    #[test]
+    fn test_filter_applies_to_node_and_children() {
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(40.0))
+                    .with_height(Size::lpx(40.0))
+                    .with_style(Style::fill(red))
+                    .with_filter(crate::Filter::Grayscale(1.0))
+                    .with_filter(crate::Filter::Brightness(0.5))
+                    .with_child(
+                        Node::new()
+                            .with_width(Size::lpx(10.0))
+                            .with_height(Size::lpx(10.0))
+                            .with_style(Style::fill(red)),
+                    ),
+            )
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(10.0))
+                    .with_height(Size::lpx(10.0))
+                    .with_style(Style::fill(red)),
+            );
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+        assert_eq!(output.shapes.len(), 3);
+
+        // The filtered node and its child keep their colors and go into a
+        // layer turning them a dimmed gray, the sibling stays unfiltered
+        let gray = 0.2126 * 0.5;
+        for shape in &output.shapes[..2] {
+            let Shape::Rect(rect) = &shape.shape else {
+                panic!("expected rect shape");
+            };
+            assert_eq!(rect.fill, red);
+            let filtered = shape.filter.unwrap().apply(red);
+            assert!((filtered.r - gray).abs() < 1e-5 && (filtered.g - gray).abs() < 1e-5);
+        }
+        assert_eq!(output.shapes[2].filter, None);
+    }
+
+    #[test]
+    fn test_nested_filters_compose() {
+        let root = Node::new()
+            .with_filter(crate::Filter::Brightness(0.5))
+            .with_child(
+                Node::new()
+                    .with_filter(crate::Filter::Grayscale(1.0))
+                    .with_content(Content::Text(TextContent::new("🙂"))),
+            );
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        // The emoji is drawn into one layer with both filters
+        let expected = crate::Filter::Grayscale(1.0)
+            .matrix()
+            .then(&crate::Filter::Brightness(0.5).matrix());
+        let matrix = output.shapes[0].filter.unwrap();
+        for (row, expected_row) in matrix.0.iter().zip(expected.0) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert!((value - expected_value).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nine_slice_scales_with_zoom() {
+        let image = crate::NineSliceImage::new(
+            crate::TextureId(7),
+            [16.0, 16.0],
+            crate::NineSlice::uniform(4.0).with_scale(2.0),
+        );
+        let root = Node::new()
+            .with_width(Size::lpx(40.0))
+            .with_height(Size::lpx(40.0))
+            .with_style(Style::nine_slice(image))
+            .with_zoom(1.5);
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        let Shape::Rect(rect) = &output.shapes[0].shape else {
+            panic!("expected rect shape");
+        };
+        let image = rect.nine_slice.as_ref().unwrap();
+        assert_eq!(image.texture, crate::TextureId(7));
+        assert_eq!(image.slice.scale, 3.0);
+    }
+
+    #[test]
+    fn test_linear_gradient_spans_rect_at_any_angle() {
+        let gradient = crate::LinearGradient::new([
+            (0.0, Color::rgb(1.0, 0.0, 0.0)),
+            (1.0, Color::rgb(0.0, 0.0, 1.0)),
+        ]);
+        let root = Node::new()
+            .with_width(Size::lpx(80.0))
+            .with_height(Size::lpx(40.0))
+            .with_style(Style::linear_gradient(gradient.clone()));
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+        let Shape::Rect(rect) = &output.shapes[0].shape else {
+            panic!("expected rect shape");
+        };
+        assert_eq!(rect.linear_gradient.as_ref(), Some(&gradient));
+
+        // Left to right: the left and right edges are the ends
+        assert_eq!(gradient.position_of([-40.0, 5.0], 80.0, 40.0), 0.0);
+        assert_eq!(gradient.position_of([40.0, -5.0], 80.0, 40.0), 1.0);
+
+        // Diagonal: the ends touch opposite corners
+        let diagonal = gradient.with_angle(std::f32::consts::FRAC_PI_4);
+        assert!(diagonal.position_of([-40.0, 20.0], 80.0, 40.0).abs() < 1e-5);
+        assert!((diagonal.position_of([40.0, -20.0], 80.0, 40.0) - 1.0).abs() < 1e-5);
+        assert!(diagonal.position_of([40.0, 20.0], 80.0, 40.0) < 1.0);
+    }
+
+    #[test]
+    fn test_elevation_adds_shadow_behind_tinted_fill() {
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(40.0))
+                    .with_height(Size::lpx(20.0))
+                    .with_style(Style::fill(Color::rgb(0.0, 0.0, 0.0)).merge(&Style::elevation(3))),
+            );
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        let rects: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::Rect(rect) => Some(rect.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rects.len(), 2);
+
+        // The shadow comes first (drawn behind), offset down and blurred
+        let shadow = &rects[0];
+        assert_eq!(shadow.rect, Rect::new([0.0, 4.0], [40.0, 24.0]));
+        assert_eq!(shadow.blur, Size::ppx(10.0));
+        assert_eq!(shadow.fill.a, 0.24);
+
+        // The background keeps its rect and gets the light overlay tint
+        let background = &rects[1];
+        assert_eq!(background.rect, Rect::new([0.0, 0.0], [40.0, 20.0]));
+        assert_eq!(background.blur, Size::ppx(0.0));
+        assert!(background.fill.r > 0.0 && background.fill.a == 1.0);
+
+        // Level 0 is flat: no shadow shape at all
+        let flat = FullOutput::from_node(
+            Node::new()
+                .with_width(Size::lpx(40.0))
+                .with_height(Size::lpx(20.0))
+                .with_style(Style::fill(Color::rgb(0.0, 0.0, 0.0)).merge(&Style::elevation(0))),
+            (100.0, 100.0),
+        );
+        assert_eq!(flat.shapes.len(), 1);
+    }
+
+    #[test]
+    fn test_inset_shadow_resolves_on_rect() {
+        let style = Style {
+            fill_color: Some(Color::rgb(1.0, 1.0, 1.0)),
+            inset_shadow: Some(
+                Shadow::new(
+                    Size::lpx(0.0),
+                    Size::lpx(2.0),
+                    Size::lpx(4.0),
+                    Color::rgba(0.0, 0.0, 0.0, 0.3),
+                )
+                .with_spread(Size::lpx(1.0)),
+            ),
+            ..Default::default()
+        };
+        let root = Node::new()
+            .with_width(Size::lpx(40.0))
+            .with_height(Size::lpx(20.0))
+            .with_style(style);
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        // Drawn as part of the rect itself, not as an extra shape
+        assert_eq!(output.shapes.len(), 1);
+        let Shape::Rect(rect) = &output.shapes[0].shape else {
+            panic!("expected rect shape");
+        };
+        let shadow = rect.inset_shadow.unwrap();
+        assert_eq!(shadow.offset_y, Size::ppx(2.0));
+        assert_eq!(shadow.blur, Size::ppx(4.0));
+        assert_eq!(shadow.spread, Size::ppx(1.0));
+    }
+
+    #[test]
+    fn test_outline_is_separate_shape_outside_rect() {
+        let outline_color = Color::rgb(0.0, 0.0, 1.0);
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(40.0))
+                    .with_height(Size::lpx(20.0))
+                    .with_style(Style {
+                        fill_color: Some(Color::rgb(1.0, 0.0, 0.0)),
+                        corner_shape: Some(crate::CornerShape::Round(Size::lpx(4.0))),
+                        outline: Some(
+                            crate::Outline::new(Size::lpx(2.0), outline_color)
+                                .with_offset(Size::lpx(3.0)),
+                        ),
+                        ..Default::default()
+                    }),
+            )
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(10.0))
+                    .with_height(Size::lpx(10.0)),
+            );
+        let output = FullOutput::from_node(root, (100.0, 100.0));
+
+        let rects: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::Rect(rect) => Some(rect.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rects.len(), 2);
+
+        // The background keeps its rect; the outline is expanded by offset + width
+        assert_eq!(rects[0].rect, Rect::new([0.0, 0.0], [40.0, 20.0]));
+        let outline = &rects[1];
+        assert_eq!(outline.rect, Rect::new([-5.0, -5.0], [45.0, 25.0]));
+        assert_eq!(outline.fill, Color::transparent());
+        assert_eq!(outline.stroke.map(|s| s.color), Some(outline_color));
+        assert_eq!(
+            outline.corner_shape,
+            crate::CornerShape::Round(Size::ppx(9.0))
+        );
+    }
+
+    #[test]
+    fn test_text_edit_draws_selection_below_and_caret_above_text() {
+        let selection_color = Color::rgb(0.0, 0.0, 1.0);
+        let caret_color = Color::rgb(1.0, 0.0, 0.0);
+        let line = |x: f32, width: f32| crate::LineRect {
+            x,
+            y: 0.0,
+            width,
+            height: 20.0,
+            line_width: 40.0,
+        };
+        let root = Node::new()
+            .with_width(Size::lpx(100.0))
+            .with_height(Size::lpx(40.0))
+            .with_padding(crate::Spacing::all(Size::lpx(5.0)))
+            .with_content(Content::Text(
+                TextContent::new("text")
+                    .with_h_align(HorizontalAlign::Center)
+                    .with_v_align(VerticalAlign::Center),
+            ))
+            .with_text_edit(crate::TextEditDecorations {
+                caret: Some(line(30.0, 2.0)),
+                selection: vec![line(10.0, 20.0)],
+                block_height: 20.0,
+                caret_color,
+                selection_color,
+            });
+        let output = FullOutput::from_node(root, (200.0, 200.0));
+
+        let kinds: Vec<_> = output
+            .shapes
+            .iter()
+            .map(|shape| match &shape.shape {
+                Shape::Rect(rect) => Some(rect.fill),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kinds, vec![Some(selection_color), None, Some(caret_color)]);
+
+        // The 40px wide line is centered in the 90x30 content rect
+        let Shape::Rect(selection) = &output.shapes[0].shape else {
+            unreachable!()
+        };
+        assert_eq!(selection.rect, Rect::new([40.0, 10.0], [60.0, 30.0]));
+        let Shape::Rect(caret) = &output.shapes[2].shape else {
+            unreachable!()
+        };
+        assert_eq!(caret.rect, Rect::new([60.0, 10.0], [62.0, 30.0]));
+    }
+
+    fn debug_output(root: Node, options: crate::DebugOptions) -> FullOutput {
+        FullOutput::from_node_with_debug(root, (200.0, 200.0), Some(options))
+    }
+
+    #[test]
+    fn test_debug_overlays_pivot_around_their_node() {
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_child(
+                Node::new()
+                    .with_width(Size::lpx(40.0))
+                    .with_height(Size::lpx(20.0))
+                    .with_margin(Spacing::all(Size::lpx(10.0)))
+                    .with_scale(2.0),
+            );
+        let output = debug_output(root, crate::DebugOptions::none().with_margins(true));
+
+        // Every margin strip scales around the node's center, not its own.
+        // The top strip starts one margin above and left of the node.
+        assert_eq!(output.shapes.len(), 4);
+        let top = output.shapes[0].node_rect;
+        let center = [top.min[0] + 10.0 + 20.0, top.min[1] + 10.0 + 10.0];
+        for shape in &output.shapes {
+            assert_eq!(shape.transform.absolute_origin, Some(center));
+        }
+    }
+
+    #[test]
+    fn test_gap_overlay_follows_translated_children() {
+        let child = || {
+            Node::new()
+                .with_width(Size::lpx(20.0))
+                .with_height(Size::lpx(20.0))
+        };
+        let root = Node::new()
+            .with_width(Size::Fill)
+            .with_height(Size::Fill)
+            .with_layout_direction(Layout::Horizontal)
+            .with_gap(Size::lpx(10.0))
+            .with_child(child())
+            .with_child(child().with_translation(Translation::new(Size::lpx(5.0), Size::lpx(0.0))))
+            .with_child(
+                child().with_translation(Translation::new(Size::lpx(-25.0), Size::lpx(0.0))),
+            );
+        let output = debug_output(root, crate::DebugOptions::none().with_gaps(true));
+
+        // The first gap widens with the translation; the second child overlaps
+        // the third, so there is no second gap to show
+        let gaps: Vec<_> = output.shapes.iter().map(|shape| shape.node_rect).collect();
+        assert_eq!(gaps, vec![Rect::new([20.0, 0.0], [35.0, 20.0])]);
+    }
+
+    /// Where the renderer draws `point` of a shape with `transform`
+    fn rendered_point(transform: &Transform2D, point: [f32; 2]) -> [f32; 2] {
+        let origin = transform.absolute_origin.unwrap();
+        let x = (point[0] + transform.translation.x - origin[0]) * transform.scale;
+        let y = (point[1] + transform.translation.y - origin[1]) * transform.scale;
+        let (sin, cos) = transform.rotation.sin_cos();
+        [origin[0] + x * cos + y * sin, origin[1] - x * sin + y * cos]
+    }
+
+    #[test]
+    fn test_painter_lines_follow_the_node_transform() {
+        let canvas = Node::new()
+            .with_width(Size::lpx(100.0))
+            .with_height(Size::lpx(100.0))
+            .with_padding(Spacing::all(Size::lpx(10.0)))
+            .with_translation(Translation::new(Size::lpx(5.0), Size::lpx(0.0)))
+            .with_rotation(std::f32::consts::FRAC_PI_2)
+            .with_scale(2.0)
+            .with_painter(|painter| {
+                assert_eq!(painter.size(), (80.0, 80.0));
+                painter.line([0.0, 0.0], [20.0, 10.0], 2.0, Color::rgb(1.0, 1.0, 1.0));
+            });
+        let output = FullOutput::from_node(canvas, (200.0, 200.0));
+        assert_eq!(output.shapes.len(), 1);
+        let line = &output.shapes[0];
+
+        // The capsule's ends (inside its round caps) land where the node
+        // transform puts the line's end points
+        let node_transform = Transform2D {
+            translation: crate::layout::Vector2 { x: 5.0, y: 0.0 },
+            rotation: std::f32::consts::FRAC_PI_2,
+            scale: 2.0,
+            origin: crate::layout::TransformOrigin::center(),
+            absolute_origin: Some([50.0, 50.0]),
+        };
+        let rect = line.node_rect;
+        let center = [
+            (rect.min[0] + rect.max[0]) * 0.5,
+            (rect.min[1] + rect.max[1]) * 0.5,
+        ];
+        let half_length = (rect.width() - rect.height()) * 0.5;
+        let ends = [
+            rendered_point(&line.transform, [center[0] - half_length, center[1]]),
+            rendered_point(&line.transform, [center[0] + half_length, center[1]]),
+        ];
+        let expected = [
+            rendered_point(&node_transform, [10.0, 10.0]),
+            rendered_point(&node_transform, [30.0, 20.0]),
+        ];
+        for (end, expected) in ends.iter().zip(expected) {
+            assert!(
+                (end[0] - expected[0]).abs() < 1e-3,
+                "{end:?} != {expected:?}"
+            );
+            assert!(
+                (end[1] - expected[1]).abs() < 1e-3,
+                "{end:?} != {expected:?}"
+            );
+        }
+    }
+}
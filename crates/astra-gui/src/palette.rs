@@ -0,0 +1,309 @@
+//! Color palettes that themes are built from
+//!
+//! A [`Palette`] names the colors of a scheme by role (backgrounds, surfaces,
+//! text, accents). [`Theme::from_palette`](crate::Theme::from_palette) maps
+//! those roles onto the semantic theme tokens, so every built-in palette (and
+//! any custom one) yields a complete theme:
+//!
+//! ```ignore
+//! // A palette picker
+//! for palette in builtin_palettes() {
+//!     if button(palette.name()).clicked(ctx) {
+//!         ctx.set_theme(Theme::from_palette(*palette));
+//!     }
+//! }
+//! ```
+
+use crate::color::{catppuccin, dracula, gruvbox, nord, Color};
+use crate::theme::ColorScheme;
+
+/// Colors of a palette by role
+///
+/// Backgrounds go from `crust` (most recessed) over `mantle` to `base`, and
+/// surfaces (inputs, tracks) from `surface0` to the more emphasized
+/// `surface2`; in light palettes "recessed" means darker too.
+pub trait Palette {
+    /// Display name, e.g. for a palette picker
+    fn name(&self) -> &'static str;
+    /// Whether the palette is light or dark
+    fn scheme(&self) -> ColorScheme;
+
+    /// Main background
+    fn base(&self) -> Color;
+    /// Background one step recessed from `base`
+    fn mantle(&self) -> Color;
+    /// Most recessed background
+    fn crust(&self) -> Color;
+    fn surface0(&self) -> Color;
+    fn surface1(&self) -> Color;
+    fn surface2(&self) -> Color;
+
+    /// Regular text
+    fn text(&self) -> Color;
+    /// Secondary text
+    fn subtext0(&self) -> Color;
+    /// Text between `text` and `subtext0` in emphasis
+    fn subtext1(&self) -> Color;
+
+    /// Main accent
+    fn accent(&self) -> Color;
+    /// Secondary accent
+    fn accent_secondary(&self) -> Color;
+    fn red(&self) -> Color;
+    fn yellow(&self) -> Color;
+    fn green(&self) -> Color;
+}
+
+macro_rules! catppuccin_palette {
+    ($name:ident, $flavor:ident, $display:literal, $scheme:ident) => {
+        #[doc = concat!("Catppuccin ", $display)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name;
+
+        impl Palette for $name {
+            fn name(&self) -> &'static str {
+                concat!("Catppuccin ", $display)
+            }
+            fn scheme(&self) -> ColorScheme {
+                ColorScheme::$scheme
+            }
+            fn base(&self) -> Color {
+                catppuccin::$flavor::BASE
+            }
+            fn mantle(&self) -> Color {
+                catppuccin::$flavor::MANTLE
+            }
+            fn crust(&self) -> Color {
+                catppuccin::$flavor::CRUST
+            }
+            fn surface0(&self) -> Color {
+                catppuccin::$flavor::SURFACE0
+            }
+            fn surface1(&self) -> Color {
+                catppuccin::$flavor::SURFACE1
+            }
+            fn surface2(&self) -> Color {
+                catppuccin::$flavor::SURFACE2
+            }
+            fn text(&self) -> Color {
+                catppuccin::$flavor::TEXT
+            }
+            fn subtext0(&self) -> Color {
+                catppuccin::$flavor::SUBTEXT0
+            }
+            fn subtext1(&self) -> Color {
+                catppuccin::$flavor::SUBTEXT1
+            }
+            fn accent(&self) -> Color {
+                catppuccin::$flavor::LAVENDER
+            }
+            fn accent_secondary(&self) -> Color {
+                catppuccin::$flavor::MAUVE
+            }
+            fn red(&self) -> Color {
+                catppuccin::$flavor::RED
+            }
+            fn yellow(&self) -> Color {
+                catppuccin::$flavor::YELLOW
+            }
+            fn green(&self) -> Color {
+                catppuccin::$flavor::GREEN
+            }
+        }
+    };
+}
+
+catppuccin_palette!(CatppuccinMocha, mocha, "Mocha", Dark);
+catppuccin_palette!(CatppuccinMacchiato, macchiato, "Macchiato", Dark);
+catppuccin_palette!(CatppuccinFrappe, frappe, "Frappé", Dark);
+catppuccin_palette!(CatppuccinLatte, latte, "Latte", Light);
+
+/// Nord (Polar Night backgrounds, Frost accents)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Nord;
+
+impl Palette for Nord {
+    fn name(&self) -> &'static str {
+        "Nord"
+    }
+    fn scheme(&self) -> ColorScheme {
+        ColorScheme::Dark
+    }
+    fn base(&self) -> Color {
+        nord::NORD0
+    }
+    // Nord has no background darker than nord0; these are the darker
+    // Polar Night shades common Nord ports use for sidebars and title bars
+    fn mantle(&self) -> Color {
+        Color::srgba(41, 46, 57, 255)
+    }
+    fn crust(&self) -> Color {
+        Color::srgba(36, 41, 51, 255)
+    }
+    fn surface0(&self) -> Color {
+        nord::NORD1
+    }
+    fn surface1(&self) -> Color {
+        nord::NORD2
+    }
+    fn surface2(&self) -> Color {
+        nord::NORD3
+    }
+    fn text(&self) -> Color {
+        nord::NORD6
+    }
+    fn subtext0(&self) -> Color {
+        nord::NORD4
+    }
+    fn subtext1(&self) -> Color {
+        nord::NORD5
+    }
+    fn accent(&self) -> Color {
+        nord::NORD8
+    }
+    fn accent_secondary(&self) -> Color {
+        nord::NORD15
+    }
+    fn red(&self) -> Color {
+        nord::NORD11
+    }
+    fn yellow(&self) -> Color {
+        nord::NORD13
+    }
+    fn green(&self) -> Color {
+        nord::NORD14
+    }
+}
+
+macro_rules! gruvbox_palette {
+    ($name:ident, $variant:ident, $display:literal, $scheme:ident) => {
+        #[doc = concat!("Gruvbox ", $display)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name;
+
+        impl Palette for $name {
+            fn name(&self) -> &'static str {
+                concat!("Gruvbox ", $display)
+            }
+            fn scheme(&self) -> ColorScheme {
+                ColorScheme::$scheme
+            }
+            fn base(&self) -> Color {
+                gruvbox::$variant::BG0
+            }
+            fn mantle(&self) -> Color {
+                gruvbox::$variant::BG0_S
+            }
+            fn crust(&self) -> Color {
+                gruvbox::$variant::BG0_H
+            }
+            fn surface0(&self) -> Color {
+                gruvbox::$variant::BG1
+            }
+            fn surface1(&self) -> Color {
+                gruvbox::$variant::BG2
+            }
+            fn surface2(&self) -> Color {
+                gruvbox::$variant::BG3
+            }
+            fn text(&self) -> Color {
+                gruvbox::$variant::FG
+            }
+            fn subtext0(&self) -> Color {
+                gruvbox::$variant::FG4
+            }
+            fn subtext1(&self) -> Color {
+                gruvbox::$variant::FG3
+            }
+            fn accent(&self) -> Color {
+                gruvbox::$variant::YELLOW
+            }
+            fn accent_secondary(&self) -> Color {
+                gruvbox::$variant::ORANGE
+            }
+            fn red(&self) -> Color {
+                gruvbox::$variant::RED
+            }
+            fn yellow(&self) -> Color {
+                gruvbox::$variant::YELLOW
+            }
+            fn green(&self) -> Color {
+                gruvbox::$variant::GREEN
+            }
+        }
+    };
+}
+
+gruvbox_palette!(GruvboxDark, dark, "Dark", Dark);
+gruvbox_palette!(GruvboxLight, light, "Light", Light);
+
+/// Dracula
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dracula;
+
+impl Palette for Dracula {
+    fn name(&self) -> &'static str {
+        "Dracula"
+    }
+    fn scheme(&self) -> ColorScheme {
+        ColorScheme::Dark
+    }
+    fn base(&self) -> Color {
+        dracula::BACKGROUND
+    }
+    fn mantle(&self) -> Color {
+        dracula::BACKGROUND_DARK
+    }
+    fn crust(&self) -> Color {
+        dracula::BACKGROUND_DARKER
+    }
+    fn surface0(&self) -> Color {
+        dracula::CURRENT_LINE
+    }
+    // Dracula has no shades between these three, so fill in the steps
+    // between the current line and comment colors
+    fn surface1(&self) -> Color {
+        Color::srgba(83, 89, 127, 255)
+    }
+    fn surface2(&self) -> Color {
+        dracula::COMMENT
+    }
+    fn text(&self) -> Color {
+        dracula::FOREGROUND
+    }
+    fn subtext0(&self) -> Color {
+        Color::srgba(166, 172, 205, 255)
+    }
+    fn subtext1(&self) -> Color {
+        Color::srgba(207, 209, 224, 255)
+    }
+    fn accent(&self) -> Color {
+        dracula::PURPLE
+    }
+    fn accent_secondary(&self) -> Color {
+        dracula::PINK
+    }
+    fn red(&self) -> Color {
+        dracula::RED
+    }
+    fn yellow(&self) -> Color {
+        dracula::YELLOW
+    }
+    fn green(&self) -> Color {
+        dracula::GREEN
+    }
+}
+
+/// All built-in palettes, for palette pickers
+pub fn builtin_palettes() -> &'static [&'static dyn Palette] {
+    &[
+        &CatppuccinMocha,
+        &CatppuccinMacchiato,
+        &CatppuccinFrappe,
+        &CatppuccinLatte,
+        &Nord,
+        &GruvboxDark,
+        &GruvboxLight,
+        &Dracula,
+    ]
+}
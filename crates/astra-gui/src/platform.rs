@@ -0,0 +1,61 @@
+//! Requests for the platform layer
+//!
+//! Some interactions need the windowing backend to act: showing a cursor,
+//! moving or resizing a borderless window from a custom title bar, opening a
+//! link in the browser. `UiContext` gathers them while it dispatches events
+//! and hands them over with `UiContext::take_platform_output`; apps attach
+//! them to the frame's `FullOutput` and the backend executes them (see
+//! `astra_gui_wgpu::apply_platform_output`).
+//!
+//! Nodes ask for window moves and resizes with `Node::with_window_action`.
+//! The request is made when the left button is pressed on the node, since
+//! platforms only start a window drag while the button is held. URLs are
+//! opened with `UiContext::open_url`.
+//!
+//! Platform output describes the producing process's window, so it is not
+//! part of the `FullOutput` byte format used for remote rendering.
+
+use crate::cursor::CursorIcon;
+
+/// The window edge or corner a resize is dragged from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeDirection {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+/// What pressing a node does to the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowAction {
+    /// Move the window, for custom title bars
+    Drag,
+    /// Resize the window from an edge or corner
+    Resize(ResizeDirection),
+}
+
+/// A request for the windowing backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlatformRequest {
+    /// Start moving the window with the pointer
+    DragWindow,
+    /// Start resizing the window with the pointer
+    ResizeWindow(ResizeDirection),
+    /// Open a URL with the system's default handler
+    OpenUrl(String),
+}
+
+/// Everything the platform layer should do after a frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlatformOutput {
+    /// The cursor icon to show
+    pub cursor_icon: CursorIcon,
+    /// Requests gathered during the frame, oldest first
+    pub requests: Vec<PlatformRequest>,
+}
@@ -0,0 +1,21 @@
+//! Commonly used types
+//!
+//! `use astra_gui::prelude::*;` brings in what most apps and widgets need to
+//! build a UI tree. Less common types live in the [`layout`](crate::layout),
+//! [`style`](crate::style), [`events`](crate::events) and [`text`](crate::text)
+//! modules.
+
+pub use crate::color::Color;
+pub use crate::component::{Component, ComponentExt};
+pub use crate::content::{
+    Content, FontStyle, FontWeight, HorizontalAlign, TextContent, VerticalAlign, Wrap,
+};
+pub use crate::context::UiContext;
+pub use crate::cursor::CursorIcon;
+pub use crate::events::{CustomEvent, InteractionEvent, TargetedEvent};
+pub use crate::layout::{Layout, Overflow, Size, Spacing, Translation, ZIndex};
+pub use crate::node::{Node, NodeId, Place};
+pub use crate::primitives::{CornerShape, Outline, Stroke};
+pub use crate::style::Style;
+pub use crate::theme::Theme;
+pub use crate::transition::Transition;
@@ -1,9 +1,15 @@
 use crate::color::Color;
-use crate::content::{FontStyle, FontWeight, HorizontalAlign, TextContent, VerticalAlign, Wrap};
+use crate::content::{
+    FontFeature, FontStyle, FontWeight, FontWidth, HorizontalAlign, TextContent, TextOutline,
+    TextOverflow, TextSpan, VerticalAlign, Wrap,
+};
+use crate::filter::ColorMatrix;
 use crate::layout::{Size, Transform2D, ZIndex};
+use crate::nine_slice::NineSliceImage;
 
 /// A 2D point in screen space
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -38,6 +44,7 @@ impl From<Point> for [f32; 2] {
 
 /// Defines how a stroke is positioned relative to the shape boundary
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StrokeAlignment {
     /// Stroke rendered entirely inside the shape (current behavior)
     Inset,
@@ -89,6 +96,7 @@ impl Default for StrokeAlignment {
 
 /// Stroke definition with width and color
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stroke {
     pub width: Size,
     pub color: Color,
@@ -110,6 +118,261 @@ impl Stroke {
     }
 }
 
+/// Outline drawn outside a node's rect without affecting layout
+///
+/// Unlike a stroke, the outline is rendered as its own shape, so it can sit
+/// `offset` away from the node's edge (like CSS `outline-offset`). Useful for
+/// focus rings and debug highlights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outline {
+    pub width: Size,
+    pub color: Color,
+    /// Gap between the node's edge and the inner edge of the outline
+    pub offset: Size,
+}
+
+impl Outline {
+    pub const fn new(width: Size, color: Color) -> Self {
+        Self {
+            width,
+            color,
+            offset: Size::Logical(0.0),
+        }
+    }
+
+    pub const fn with_offset(mut self, offset: Size) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Shadow cast by a node, like CSS `box-shadow`
+///
+/// As a drop shadow (`Style::shadow`) it is rendered as a blurred copy of the
+/// node's shape behind it. As an inset shadow (`Style::inset_shadow`) it is
+/// cast inward from the shape's edges, as if the shape were a hole the size of
+/// the shape shrunk by `spread` and moved by the offset. Neither affects layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shadow {
+    /// Horizontal offset of the shadow from the node
+    pub offset_x: Size,
+    /// Vertical offset of the shadow from the node
+    pub offset_y: Size,
+    /// Width of the soft edge; 0 gives a hard shadow
+    pub blur: Size,
+    /// How far the shadow grows (or shrinks, if negative) before blurring
+    pub spread: Size,
+    pub color: Color,
+}
+
+impl Shadow {
+    pub const fn new(offset_x: Size, offset_y: Size, blur: Size, color: Color) -> Self {
+        Self {
+            offset_x,
+            offset_y,
+            blur,
+            spread: Size::Logical(0.0),
+            color,
+        }
+    }
+
+    pub const fn with_spread(mut self, spread: Size) -> Self {
+        self.spread = spread;
+        self
+    }
+}
+
+/// Conic (angular) gradient sweeping colors around a center point, like CSS
+/// `conic-gradient`
+///
+/// Stop positions are fractions of a full turn, measured clockwise from
+/// `start_angle`. Before the first stop the first color is used, after the last
+/// stop the last color, so the gradient always covers the whole shape. Two
+/// stops at the same position give a hard edge (pie charts, progress rings).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConicGradient {
+    /// Center as a fraction of the rect size ([0.5, 0.5] = middle)
+    pub center: [f32; 2],
+    /// Angle of position 0.0 in radians, clockwise from the top
+    pub start_angle: f32,
+    /// Color stops as (position, color), sorted by position
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl ConicGradient {
+    /// Create a gradient centered in the rect, starting at the top
+    ///
+    /// Stops are sorted by position and positions clamped to [0, 1].
+    pub fn new(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        Self {
+            center: [0.5, 0.5],
+            start_angle: 0.0,
+            stops: sorted_stops(stops),
+        }
+    }
+
+    /// Full hue wheel, as used by color pickers
+    pub fn color_wheel() -> Self {
+        Self::new((0..=6).map(|i| {
+            let position = i as f32 / 6.0;
+            (position, Color::hsl(position * 360.0, 1.0, 0.5))
+        }))
+    }
+
+    /// Pie-style progress: `color` up to `fraction` of a turn, `track` after it
+    pub fn progress(fraction: f32, color: Color, track: Color) -> Self {
+        let fraction = fraction.clamp(0.0, 1.0);
+        Self::new([
+            (0.0, color),
+            (fraction, color),
+            (fraction, track),
+            (1.0, track),
+        ])
+    }
+
+    pub fn with_center(mut self, x: f32, y: f32) -> Self {
+        self.center = [x, y];
+        self
+    }
+
+    pub fn with_start_angle(mut self, start_angle: f32) -> Self {
+        self.start_angle = start_angle;
+        self
+    }
+
+    /// Color at a position (fraction of a turn from `start_angle`)
+    pub fn color_at(&self, position: f32) -> Color {
+        stops_color_at(&self.stops, position)
+    }
+
+    /// Apply `f` to every stop color
+    pub fn map_colors(&mut self, mut f: impl FnMut(Color) -> Color) {
+        for (_, color) in &mut self.stops {
+            *color = f(*color);
+        }
+    }
+}
+
+/// Linear gradient blending colors along a straight line, like CSS
+/// `linear-gradient`
+///
+/// The gradient runs toward `angle`, in radians clockwise from the top
+/// (0 = to top, π/2 = to right). As in CSS, the gradient line is just long
+/// enough for positions 0 and 1 to touch opposite corners, so the stops span
+/// the whole shape at any angle. Colors extend past the first and last stops.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearGradient {
+    /// Direction the gradient runs toward, in radians clockwise from the top
+    pub angle: f32,
+    /// Color stops as (position, color), sorted by position
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl LinearGradient {
+    /// Create a gradient running from left to right
+    ///
+    /// Stops are sorted by position and positions clamped to [0, 1].
+    pub fn new(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        Self {
+            angle: std::f32::consts::FRAC_PI_2,
+            stops: sorted_stops(stops),
+        }
+    }
+
+    /// Set the direction the gradient runs toward (radians clockwise from the top)
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Color at a position along the gradient line (0 at the start, 1 at the end)
+    pub fn color_at(&self, position: f32) -> Color {
+        stops_color_at(&self.stops, position)
+    }
+
+    /// Position along the gradient line of a point in a `width` × `height`
+    /// rect, relative to the rect's center
+    pub fn position_of(&self, point: [f32; 2], width: f32, height: f32) -> f32 {
+        let direction = [self.angle.sin(), -self.angle.cos()];
+        let length = (width * direction[0]).abs() + (height * direction[1]).abs();
+        if length <= f32::EPSILON {
+            return 0.5;
+        }
+        (point[0] * direction[0] + point[1] * direction[1]) / length + 0.5
+    }
+
+    /// Sort the stops by position after editing them in place
+    pub fn sort_stops(&mut self) {
+        self.stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    /// Apply `f` to every stop color
+    pub fn map_colors(&mut self, mut f: impl FnMut(Color) -> Color) {
+        for (_, color) in &mut self.stops {
+            *color = f(*color);
+        }
+    }
+}
+
+/// Stops with positions clamped to [0, 1], sorted by position
+fn sorted_stops(stops: impl IntoIterator<Item = (f32, Color)>) -> Vec<(f32, Color)> {
+    let mut stops: Vec<(f32, Color)> = stops
+        .into_iter()
+        .map(|(position, color)| (position.clamp(0.0, 1.0), color))
+        .collect();
+    stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    stops
+}
+
+/// Color of sorted gradient stops at a position, extending the end colors
+fn stops_color_at(stops: &[(f32, Color)], position: f32) -> Color {
+    let Some(&(first_position, first_color)) = stops.first() else {
+        return Color::transparent();
+    };
+    if position <= first_position {
+        return first_color;
+    }
+    for pair in stops.windows(2) {
+        let ((p0, c0), (p1, c1)) = (pair[0], pair[1]);
+        if position < p1 {
+            let t = (position - p0) / (p1 - p0);
+            return crate::transition::lerp_color(c0, c1, t);
+        }
+    }
+    stops[stops.len() - 1].1
+}
+
+/// How the inside of a rect is painted
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Fill {
+    Solid(Color),
+    ConicGradient(ConicGradient),
+    LinearGradient(LinearGradient),
+}
+
+impl From<Color> for Fill {
+    fn from(color: Color) -> Self {
+        Fill::Solid(color)
+    }
+}
+
+impl From<ConicGradient> for Fill {
+    fn from(gradient: ConicGradient) -> Self {
+        Fill::ConicGradient(gradient)
+    }
+}
+
+impl From<LinearGradient> for Fill {
+    fn from(gradient: LinearGradient) -> Self {
+        Fill::LinearGradient(gradient)
+    }
+}
+
 /// Axis-aligned rectangle defined by min and max corners
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Rect {
@@ -145,6 +408,14 @@ impl Rect {
             && point.y <= self.max[1]
     }
 
+    /// Check if another rectangle lies entirely inside this one
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.min[0] >= self.min[0]
+            && other.min[1] >= self.min[1]
+            && other.max[0] <= self.max[0]
+            && other.max[1] <= self.max[1]
+    }
+
     /// Get the intersection of this rect with another
     pub fn intersect(&self, other: &Rect) -> Option<Rect> {
         let min_x = self.min[0].max(other.min[0]);
@@ -233,6 +504,7 @@ pub enum TriangleSpec {
 
 /// Corner shape for rectangles
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CornerShape {
     /// No corner modification (sharp 90-degree corners)
     None,
@@ -245,6 +517,8 @@ pub enum CornerShape {
     /// Squircle (superellipse) with specified radius and smoothness factor
     /// smoothness: 1.0 = circle, higher values = more square-like
     Squircle { radius: Size, smoothness: f32 },
+    /// Independent shape for each corner (e.g. round top and square bottom for tab headers)
+    PerCorner(Corners),
 }
 
 impl CornerShape {
@@ -256,6 +530,167 @@ impl CornerShape {
             CornerShape::Cut(d) => *d,
             CornerShape::InverseRound(r) => *r,
             CornerShape::Squircle { radius, .. } => *radius,
+            CornerShape::PerCorner(corners) => corners
+                .as_array()
+                .into_iter()
+                .map(|corner| corner.extent())
+                .fold(Size::Logical(0.0), |a, b| {
+                    if b.resolve_physical_or_zero(1.0) > a.resolve_physical_or_zero(1.0) {
+                        b
+                    } else {
+                        a
+                    }
+                }),
+        }
+    }
+
+    /// Expand this shape into one shape per corner
+    pub fn corners(&self) -> Corners {
+        match *self {
+            CornerShape::None => Corners::all(CornerKind::Square),
+            CornerShape::Round(r) => Corners::all(CornerKind::Round(r)),
+            CornerShape::Cut(d) => Corners::all(CornerKind::Cut(d)),
+            CornerShape::InverseRound(r) => Corners::all(CornerKind::InverseRound(r)),
+            CornerShape::Squircle { radius, smoothness } => {
+                Corners::all(CornerKind::Squircle { radius, smoothness })
+            }
+            CornerShape::PerCorner(corners) => corners,
+        }
+    }
+}
+
+/// Shape of a single corner, used by `CornerShape::PerCorner`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CornerKind {
+    /// Sharp 90-degree corner
+    #[default]
+    Square,
+    /// Circular arc rounding with specified radius
+    Round(Size),
+    /// Diagonal cut with specified distance from corner
+    Cut(Size),
+    /// Inverse circular arc (concave, like a ticket punch)
+    InverseRound(Size),
+    /// Squircle (superellipse) with specified radius and smoothness factor
+    ///
+    /// Renderers may share a single smoothness between all squircle corners of a rect.
+    Squircle { radius: Size, smoothness: f32 },
+}
+
+impl CornerKind {
+    /// Get the distance this corner extends from the corner point
+    pub fn extent(&self) -> Size {
+        match self {
+            CornerKind::Square => Size::Logical(0.0),
+            CornerKind::Round(r) => *r,
+            CornerKind::Cut(d) => *d,
+            CornerKind::InverseRound(r) => *r,
+            CornerKind::Squircle { radius, .. } => *radius,
+        }
+    }
+
+    /// Apply `f` to the size of this corner
+    pub fn map_size(self, f: impl FnOnce(Size) -> Size) -> Self {
+        match self {
+            CornerKind::Square => CornerKind::Square,
+            CornerKind::Round(r) => CornerKind::Round(f(r)),
+            CornerKind::Cut(d) => CornerKind::Cut(f(d)),
+            CornerKind::InverseRound(r) => CornerKind::InverseRound(f(r)),
+            CornerKind::Squircle { radius, smoothness } => CornerKind::Squircle {
+                radius: f(radius),
+                smoothness,
+            },
+        }
+    }
+}
+
+/// One corner shape for each corner of a rectangle
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Corners {
+    pub top_left: CornerKind,
+    pub top_right: CornerKind,
+    pub bottom_right: CornerKind,
+    pub bottom_left: CornerKind,
+}
+
+impl Corners {
+    /// Use the same shape for all corners
+    pub const fn all(corner: CornerKind) -> Self {
+        Self {
+            top_left: corner,
+            top_right: corner,
+            bottom_right: corner,
+            bottom_left: corner,
+        }
+    }
+
+    /// Shape the top corners, leaving the bottom corners square
+    pub const fn top(corner: CornerKind) -> Self {
+        Self::all(CornerKind::Square)
+            .with_top_left(corner)
+            .with_top_right(corner)
+    }
+
+    /// Shape the bottom corners, leaving the top corners square
+    pub const fn bottom(corner: CornerKind) -> Self {
+        Self::all(CornerKind::Square)
+            .with_bottom_left(corner)
+            .with_bottom_right(corner)
+    }
+
+    /// Shape the left corners, leaving the right corners square
+    pub const fn left(corner: CornerKind) -> Self {
+        Self::all(CornerKind::Square)
+            .with_top_left(corner)
+            .with_bottom_left(corner)
+    }
+
+    /// Shape the right corners, leaving the left corners square
+    pub const fn right(corner: CornerKind) -> Self {
+        Self::all(CornerKind::Square)
+            .with_top_right(corner)
+            .with_bottom_right(corner)
+    }
+
+    pub const fn with_top_left(mut self, corner: CornerKind) -> Self {
+        self.top_left = corner;
+        self
+    }
+
+    pub const fn with_top_right(mut self, corner: CornerKind) -> Self {
+        self.top_right = corner;
+        self
+    }
+
+    pub const fn with_bottom_right(mut self, corner: CornerKind) -> Self {
+        self.bottom_right = corner;
+        self
+    }
+
+    pub const fn with_bottom_left(mut self, corner: CornerKind) -> Self {
+        self.bottom_left = corner;
+        self
+    }
+
+    /// Corners in clockwise order starting at the top left
+    pub const fn as_array(&self) -> [CornerKind; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+
+    /// Apply `f` to every corner
+    pub fn map(self, mut f: impl FnMut(CornerKind) -> CornerKind) -> Self {
+        Self {
+            top_left: f(self.top_left),
+            top_right: f(self.top_right),
+            bottom_right: f(self.bottom_right),
+            bottom_left: f(self.bottom_left),
         }
     }
 }
@@ -268,6 +703,20 @@ pub struct StyledRect {
     pub fill: Color,
     pub stroke: Option<Stroke>,
     pub anti_aliasing: AntiAliasing,
+    /// Width of a soft edge fading the fill out across the shape boundary
+    /// (used for shadows); zero for a crisp edge
+    pub blur: Size,
+    /// Conic gradient painted instead of `fill` when set
+    pub conic_gradient: Option<ConicGradient>,
+    /// Linear gradient painted instead of `fill` when set (and no conic
+    /// gradient is)
+    pub linear_gradient: Option<LinearGradient>,
+    /// Shadow cast inward from the edges, over the fill and under the stroke
+    /// (for pressed or sunken looks)
+    pub inset_shadow: Option<Shadow>,
+    /// Nine-slice texture painted instead of `fill` and any gradient when set
+    /// (its patches have square corners; the texture shapes them)
+    pub nine_slice: Option<NineSliceImage>,
 }
 
 impl StyledRect {
@@ -278,7 +727,24 @@ impl StyledRect {
             fill,
             stroke: None,
             anti_aliasing: AntiAliasing::default(),
+            blur: Size::Logical(0.0),
+            conic_gradient: None,
+            linear_gradient: None,
+            inset_shadow: None,
+            nine_slice: None,
+        }
+    }
+
+    /// Set the fill to a solid color or a gradient
+    pub fn with_fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.conic_gradient = None;
+        self.linear_gradient = None;
+        match fill.into() {
+            Fill::Solid(color) => self.fill = color,
+            Fill::ConicGradient(gradient) => self.conic_gradient = Some(gradient),
+            Fill::LinearGradient(gradient) => self.linear_gradient = Some(gradient),
         }
+        self
     }
 
     pub fn with_corner_shape(mut self, corner_shape: CornerShape) -> Self {
@@ -296,9 +762,34 @@ impl StyledRect {
         self
     }
 
+    pub fn with_blur(mut self, blur: Size) -> Self {
+        self.blur = blur;
+        self
+    }
+
+    pub fn with_inset_shadow(mut self, shadow: Shadow) -> Self {
+        self.inset_shadow = Some(shadow);
+        self
+    }
+
+    /// Paint a nine-slice texture instead of the fill
+    pub fn with_nine_slice(mut self, image: NineSliceImage) -> Self {
+        self.nine_slice = Some(image);
+        self
+    }
+
     /// Apply opacity by multiplying fill and stroke alpha values
     pub fn apply_opacity(&mut self, opacity: f32) {
         self.fill.a *= opacity;
+        if let Some(gradient) = &mut self.conic_gradient {
+            gradient.map_colors(|color| color.with_alpha(color.a * opacity));
+        }
+        if let Some(shadow) = &mut self.inset_shadow {
+            shadow.color.a *= opacity;
+        }
+        if let Some(image) = &mut self.nine_slice {
+            image.tint.a *= opacity;
+        }
         if let Some(stroke) = &mut self.stroke {
             stroke.color.a *= opacity;
         }
@@ -521,6 +1012,8 @@ pub struct TextShape {
     pub rect: Rect,
     /// The text content to render
     pub text: String,
+    /// Styled runs making up `text`; empty for plain text
+    pub spans: Vec<TextSpan>,
     /// Font size in logical pixels
     pub font_size: Size,
     /// Text color
@@ -531,12 +1024,26 @@ pub struct TextShape {
     pub v_align: VerticalAlign,
     /// Text wrapping mode
     pub wrap: Wrap,
+    /// Handling of lines wider than `rect`
+    pub overflow: TextOverflow,
+    /// Maximum number of lines drawn (`None` for no limit)
+    pub max_lines: Option<usize>,
     /// Line height as a multiplier of font size
     pub line_height_multiplier: f32,
     /// Font weight
     pub font_weight: FontWeight,
     /// Font style
     pub font_style: FontStyle,
+    /// Preferred font family (`None` for the backend's default font)
+    pub font_family: Option<String>,
+    /// OpenType features applied when shaping
+    pub font_features: Vec<FontFeature>,
+    /// Font width
+    pub font_width: FontWidth,
+    /// Distance between tab stops, in widths of a space
+    pub tab_width: u16,
+    /// Outline drawn behind the glyphs
+    pub outline: Option<TextOutline>,
 }
 
 impl TextShape {
@@ -545,25 +1052,48 @@ impl TextShape {
         Self {
             rect,
             text: content.text.clone(),
+            spans: content.spans.clone(),
             font_size: content.font_size,
             color: content.color,
             h_align: content.h_align,
             v_align: content.v_align,
             wrap: content.wrap,
+            overflow: content.overflow,
+            max_lines: content.max_lines,
             line_height_multiplier: content.line_height_multiplier,
             font_weight: content.font_weight,
             font_style: content.font_style,
+            font_family: content.font_family.clone(),
+            font_features: content.font_features.clone(),
+            font_width: content.font_width,
+            tab_width: content.tab_width,
+            outline: content.outline,
         }
     }
 
     /// Apply opacity by multiplying text color alpha
     pub fn apply_opacity(&mut self, opacity: f32) {
         self.color.a *= opacity;
+        if let Some(outline) = &mut self.outline {
+            outline.color.a *= opacity;
+        }
+        for color in self.spans.iter_mut().filter_map(|span| span.color.as_mut()) {
+            color.a *= opacity;
+        }
+    }
+
+    /// Color of a span, falling back to the text color
+    pub fn span_color(&self, span: usize) -> Color {
+        self.spans
+            .get(span)
+            .and_then(|span| span.color)
+            .unwrap_or(self.color)
     }
 }
 
 /// Shapes that can be rendered
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Shape {
     Rect(StyledRect),
     Text(TextShape),
@@ -583,6 +1113,11 @@ impl Shape {
             fill: Color::transparent(),
             stroke: None,
             anti_aliasing: AntiAliasing::default(),
+            blur: Size::Logical(0.0),
+            conic_gradient: None,
+            linear_gradient: None,
+            inset_shadow: None,
+            nine_slice: None,
         })
     }
 
@@ -630,6 +1165,7 @@ pub struct ClippedShape {
     pub opacity: f32,           // Combined opacity from node hierarchy
     pub z_index: ZIndex,        // Z-index for layering (higher = on top)
     pub tree_index: usize,      // Position in tree traversal (for stable sort)
+    pub filter: Option<ColorMatrix>, // Filter of the layer it's drawn into (`Node::with_filter`)
 }
 
 impl ClippedShape {
@@ -649,6 +1185,7 @@ impl ClippedShape {
             opacity: 1.0,
             z_index: ZIndex::DEFAULT,
             tree_index: 0,
+            filter: None,
         }
     }
 
@@ -666,6 +1203,7 @@ impl ClippedShape {
             opacity: 1.0,
             z_index: ZIndex::DEFAULT,
             tree_index: 0,
+            filter: None,
         }
     }
 
@@ -0,0 +1,133 @@
+//! Input recording and playback
+//!
+//! `UiContext::start_input_recording` captures the input each frame is built
+//! and dispatched with, along with the frame's duration, into an
+//! [`InputRecording`]. `UiContext::play_input` later replays it in place of
+//! live input, frame by frame, with the recorded durations driving
+//! transitions and scroll animations, so a scripted demo or a bug report's
+//! interaction plays out the same way again. With the `serde` feature the
+//! recording can be saved to and loaded from any serde format.
+//!
+//! Timers measured in wall time, such as long presses and hover intent, fire
+//! as recorded when the recording is replayed at its original pace.
+
+use crate::input::InputState;
+
+/// The input of one recorded frame
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedFrame {
+    /// Time since the previous frame in seconds (0 for the first frame)
+    pub dt: f32,
+    /// The input the frame was built and dispatched with
+    pub input: InputState,
+}
+
+/// A per-frame input stream captured by `UiContext::start_input_recording`
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputRecording {
+    frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    /// Create an empty recording
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a frame
+    pub fn push(&mut self, frame: RecordedFrame) {
+        self.frames.push(frame);
+    }
+
+    /// Get the recorded frames, oldest first
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// Get the number of recorded frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if no frames were recorded
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Get the total recorded time in seconds
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.dt).sum()
+    }
+
+    /// Set the duration of the most recent frame, once it is known
+    pub(crate) fn set_last_dt(&mut self, dt: f32) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.dt = dt;
+        }
+    }
+}
+
+/// Replays an [`InputRecording`] one frame at a time
+#[derive(Debug, Clone)]
+pub struct InputPlayback {
+    recording: InputRecording,
+    next: usize,
+}
+
+impl InputPlayback {
+    /// Start replaying a recording from its first frame
+    pub fn new(recording: InputRecording) -> Self {
+        Self { recording, next: 0 }
+    }
+
+    /// Replace `input` with the next recorded frame's input
+    ///
+    /// Returns the frame's duration in seconds, or `None` once every frame
+    /// has been replayed.
+    pub fn next_frame(&mut self, input: &mut InputState) -> Option<f32> {
+        let frame = self.recording.frames.get(self.next)?;
+        self.next += 1;
+        *input = frame.input.clone();
+        Some(frame.dt)
+    }
+
+    /// Get the number of frames replayed so far
+    pub fn position(&self) -> usize {
+        self.next
+    }
+
+    /// Check if every frame has been replayed
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.len()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{Key, MouseButton, NamedKey, Point};
+
+    #[test]
+    fn test_recording_serde_round_trip() {
+        let mut input = InputState::new();
+        input.cursor_position = Some(Point::new(12.0, 34.0));
+        input.press_button(MouseButton::Left);
+        input.press_key(Key::Named(NamedKey::ArrowLeft), false, true);
+        input.type_character('x');
+
+        let mut recording = InputRecording::new();
+        recording.push(RecordedFrame { dt: 0.016, input });
+        let json = serde_json::to_string(&recording).unwrap();
+        let loaded: InputRecording = serde_json::from_str(&json).unwrap();
+
+        let mut replayed = InputState::new();
+        let mut playback = InputPlayback::new(loaded);
+        assert_eq!(playback.next_frame(&mut replayed), Some(0.016));
+        assert!(playback.is_finished());
+        assert_eq!(replayed.cursor_position, Some(Point::new(12.0, 34.0)));
+        assert!(replayed.is_button_just_pressed(MouseButton::Left));
+        assert_eq!(replayed.text_input(), "x");
+    }
+}
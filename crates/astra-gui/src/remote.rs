@@ -0,0 +1,1270 @@
+//! Versioned binary serialization of `FullOutput` for remote rendering
+//!
+//! Lets a UI be built and laid out in one process (or machine) and drawn by a
+//! thin renderer elsewhere: `FullOutput::to_bytes` on the producer side,
+//! `FullOutput::from_bytes` (or `Renderer::render_serialized` in
+//! `astra-gui-wgpu`) on the consumer side.
+//!
+//! # Format
+//!
+//! All values are little-endian. A payload starts with the magic bytes
+//! `ASTR` followed by a `u16` schema version, the optional debug options and
+//! the shape list. Each shape stores its node rect, clip rect, transform,
+//! opacity, z-index, tree index and the shape itself (rect, text run or
+//! triangle) behind a one-byte tag.
+//!
+//! The schema is stable: a released version is never changed. New versions
+//! may only append data, and decoders accept every version up to
+//! [`FULL_OUTPUT_SCHEMA_VERSION`], so newer renderers keep reading older producers.
+
+use crate::color::Color;
+use crate::content::{
+    FontFeature, FontStyle, FontWeight, FontWidth, HorizontalAlign, TextContent, TextOutline,
+    TextOverflow, TextSpan, VerticalAlign, Wrap,
+};
+use crate::debug::DebugOptions;
+use crate::filter::ColorMatrix;
+use crate::layout::{Size, Transform2D, TransformOrigin, Vector2, ZIndex};
+use crate::nine_slice::{NineSlice, NineSliceImage, TextureId};
+use crate::output::FullOutput;
+use crate::primitives::{
+    AntiAliasing, ClippedShape, ConicGradient, Corner, CornerKind, CornerShape, Corners,
+    LinearGradient, Orientation, Rect, Shadow, Shape, Stroke, StrokeAlignment, StyledRect,
+    StyledTriangle, TextShape, TriangleSpec,
+};
+
+/// Magic bytes at the start of every serialized `FullOutput`
+pub const FULL_OUTPUT_MAGIC: [u8; 4] = *b"ASTR";
+
+/// Current schema version written by `FullOutput::to_bytes`
+///
+/// - 1: initial schema
+/// - 2: rects end with their blur width
+/// - 3: rects end with an optional conic gradient (after the blur width)
+/// - 4: rects end with an optional inset shadow (after the gradient)
+/// - 5: text runs end with their rich text spans
+/// - 6: text runs end with an optional font family (after the spans)
+/// - 7: text runs end with their overflow mode (after the font family)
+/// - 8: text runs end with their line limit, 0 for none (after the overflow mode)
+/// - 9: text runs end with an optional outline (after the line limit)
+/// - 10: text runs end with their font features and width (after the outline)
+/// - 11: text runs end with their tab width (after the font width)
+/// - 12: rects end with an optional linear gradient (after the inset shadow)
+/// - 13: shapes end with the optional color filter of their layer
+/// - 14: rects end with an optional nine-slice image (after the linear gradient)
+pub const FULL_OUTPUT_SCHEMA_VERSION: u16 = 14;
+
+/// Error returned when a serialized `FullOutput` cannot be decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OutputDecodeError {
+    /// The payload does not start with [`FULL_OUTPUT_MAGIC`]
+    BadMagic,
+    /// The payload was written by a newer schema than this decoder supports
+    UnsupportedVersion(u16),
+    /// The payload ended in the middle of a value
+    UnexpectedEof,
+    /// An enum tag had no known meaning
+    InvalidTag { kind: &'static str, tag: u8 },
+    /// A text run was not valid UTF-8
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for OutputDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputDecodeError::BadMagic => write!(f, "not a serialized FullOutput (bad magic)"),
+            OutputDecodeError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {version} (newest supported is {FULL_OUTPUT_SCHEMA_VERSION})"
+            ),
+            OutputDecodeError::UnexpectedEof => write!(f, "unexpected end of data"),
+            OutputDecodeError::InvalidTag { kind, tag } => write!(f, "invalid {kind} tag {tag}"),
+            OutputDecodeError::InvalidUtf8 => write!(f, "text is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for OutputDecodeError {}
+
+impl FullOutput {
+    /// Serialize this output with the current schema version
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.bytes(&FULL_OUTPUT_MAGIC);
+        w.u16(FULL_OUTPUT_SCHEMA_VERSION);
+        match &self.debug_options {
+            Some(options) => {
+                w.u8(1);
+                w.u16(debug_bits(options));
+            }
+            None => w.u8(0),
+        }
+        w.u32(self.shapes.len() as u32);
+        for shape in &self.shapes {
+            w.clipped_shape(shape);
+        }
+        w.buf
+    }
+
+    /// Deserialize an output written by `to_bytes` (any schema version up to the current one)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, OutputDecodeError> {
+        let mut r = Reader {
+            bytes,
+            pos: 0,
+            version: 0,
+        };
+        if r.take(4)? != FULL_OUTPUT_MAGIC {
+            return Err(OutputDecodeError::BadMagic);
+        }
+        let version = r.u16()?;
+        if version == 0 || version > FULL_OUTPUT_SCHEMA_VERSION {
+            return Err(OutputDecodeError::UnsupportedVersion(version));
+        }
+        r.version = version;
+
+        let debug_options = match r.u8()? {
+            0 => None,
+            1 => Some(debug_from_bits(r.u16()?)),
+            tag => return Err(OutputDecodeError::InvalidTag { kind: "debug", tag }),
+        };
+        let count = r.u32()? as usize;
+        // Cap the preallocation so a corrupt count can't request huge buffers
+        let mut shapes = Vec::with_capacity(count.min(r.remaining() / 64 + 1));
+        for _ in 0..count {
+            shapes.push(r.clipped_shape()?);
+        }
+
+        Ok(Self {
+            shapes,
+            debug_options,
+            ..Self::default()
+        })
+    }
+}
+
+fn debug_bits(options: &DebugOptions) -> u16 {
+    [
+        options.show_margins,
+        options.show_padding,
+        options.show_borders,
+        options.show_content_area,
+        options.show_clip_rects,
+        options.show_gaps,
+        options.show_transform_origins,
+        options.show_text_bounds,
+        options.show_heatmap,
+        options.show_overdraw,
+    ]
+    .iter()
+    .enumerate()
+    .fold(0, |bits, (i, on)| bits | ((*on as u16) << i))
+}
+
+fn debug_from_bits(bits: u16) -> DebugOptions {
+    let bit = |i: u16| bits & (1 << i) != 0;
+    DebugOptions {
+        show_margins: bit(0),
+        show_padding: bit(1),
+        show_borders: bit(2),
+        show_content_area: bit(3),
+        show_clip_rects: bit(4),
+        show_gaps: bit(5),
+        show_transform_origins: bit(6),
+        show_text_bounds: bit(7),
+        show_heatmap: bit(8),
+        show_overdraw: bit(9),
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.bytes(value.as_bytes());
+    }
+
+    fn point(&mut self, p: [f32; 2]) {
+        self.f32(p[0]);
+        self.f32(p[1]);
+    }
+
+    fn rect(&mut self, rect: Rect) {
+        self.point(rect.min);
+        self.point(rect.max);
+    }
+
+    fn color(&mut self, color: Color) {
+        for c in [color.r, color.g, color.b, color.a] {
+            self.f32(c);
+        }
+    }
+
+    fn size(&mut self, size: Size) {
+        let (tag, value) = match size {
+            Size::Logical(v) => (0, v),
+            Size::Physical(v) => (1, v),
+            Size::Relative(v) => (2, v),
+            Size::Fractional(v) => (3, v),
+            Size::Fill => (4, 0.0),
+            Size::FitContent => (5, 0.0),
+        };
+        self.u8(tag);
+        self.f32(value);
+    }
+
+    fn conic_gradient(&mut self, gradient: Option<&ConicGradient>) {
+        let Some(gradient) = gradient else {
+            self.u8(0);
+            return;
+        };
+        self.u8(1);
+        self.f32(gradient.center[0]);
+        self.f32(gradient.center[1]);
+        self.f32(gradient.start_angle);
+        self.u32(gradient.stops.len() as u32);
+        for &(position, color) in &gradient.stops {
+            self.f32(position);
+            self.color(color);
+        }
+    }
+
+    fn linear_gradient(&mut self, gradient: Option<&LinearGradient>) {
+        let Some(gradient) = gradient else {
+            self.u8(0);
+            return;
+        };
+        self.u8(1);
+        self.f32(gradient.angle);
+        self.u32(gradient.stops.len() as u32);
+        for &(position, color) in &gradient.stops {
+            self.f32(position);
+            self.color(color);
+        }
+    }
+
+    fn shadow(&mut self, shadow: Option<Shadow>) {
+        let Some(shadow) = shadow else {
+            self.u8(0);
+            return;
+        };
+        self.u8(1);
+        self.size(shadow.offset_x);
+        self.size(shadow.offset_y);
+        self.size(shadow.blur);
+        self.size(shadow.spread);
+        self.color(shadow.color);
+    }
+
+    fn stroke(&mut self, stroke: Option<Stroke>) {
+        let Some(stroke) = stroke else {
+            self.u8(0);
+            return;
+        };
+        self.u8(1);
+        self.size(stroke.width);
+        self.color(stroke.color);
+        let (tag, offset) = match stroke.alignment {
+            StrokeAlignment::Inset => (0, 0.0),
+            StrokeAlignment::Centered => (1, 0.0),
+            StrokeAlignment::Outset => (2, 0.0),
+            StrokeAlignment::Custom(offset) => (3, offset),
+        };
+        self.u8(tag);
+        self.f32(offset);
+    }
+
+    fn anti_aliasing(&mut self, aa: AntiAliasing) {
+        self.u8(match aa {
+            AntiAliasing::None => 0,
+            AntiAliasing::Analytical => 1,
+        });
+    }
+
+    fn corner_kind(&mut self, kind: CornerKind) {
+        let (tag, size) = match kind {
+            CornerKind::Square => (0, Size::Logical(0.0)),
+            CornerKind::Round(s) => (1, s),
+            CornerKind::Cut(s) => (2, s),
+            CornerKind::InverseRound(s) => (3, s),
+            CornerKind::Squircle { radius, smoothness } => {
+                self.u8(4);
+                self.size(radius);
+                self.f32(smoothness);
+                return;
+            }
+        };
+        self.u8(tag);
+        self.size(size);
+    }
+
+    fn corner_shape(&mut self, shape: CornerShape) {
+        match shape {
+            CornerShape::None => self.u8(0),
+            CornerShape::Round(s) => {
+                self.u8(1);
+                self.size(s);
+            }
+            CornerShape::Cut(s) => {
+                self.u8(2);
+                self.size(s);
+            }
+            CornerShape::InverseRound(s) => {
+                self.u8(3);
+                self.size(s);
+            }
+            CornerShape::Squircle { radius, smoothness } => {
+                self.u8(4);
+                self.size(radius);
+                self.f32(smoothness);
+            }
+            CornerShape::PerCorner(corners) => {
+                self.u8(5);
+                for kind in corners.as_array() {
+                    self.corner_kind(kind);
+                }
+            }
+        }
+    }
+
+    fn orientation(&mut self, orientation: Orientation) {
+        self.u8(match orientation {
+            Orientation::Up => 0,
+            Orientation::Down => 1,
+            Orientation::Left => 2,
+            Orientation::Right => 3,
+        });
+    }
+
+    fn triangle_spec(&mut self, spec: TriangleSpec) {
+        match spec {
+            TriangleSpec::Isosceles { orientation } => {
+                self.u8(0);
+                self.orientation(orientation);
+            }
+            TriangleSpec::Equilateral { orientation } => {
+                self.u8(1);
+                self.orientation(orientation);
+            }
+            TriangleSpec::RightAngled {
+                corner,
+                orientation,
+            } => {
+                self.u8(2);
+                self.u8(match corner {
+                    Corner::TopLeft => 0,
+                    Corner::TopRight => 1,
+                    Corner::BottomLeft => 2,
+                    Corner::BottomRight => 3,
+                });
+                self.orientation(orientation);
+            }
+            TriangleSpec::Points { p1, p2, p3 } => {
+                self.u8(3);
+                self.point(p1);
+                self.point(p2);
+                self.point(p3);
+            }
+            TriangleSpec::ApexAngle {
+                angle_degrees,
+                orientation,
+            } => {
+                self.u8(4);
+                self.f32(angle_degrees);
+                self.orientation(orientation);
+            }
+        }
+    }
+
+    fn text(&mut self, text: &TextShape) {
+        self.rect(text.rect);
+        self.str(&text.text);
+        self.size(text.font_size);
+        self.color(text.color);
+        self.u8(match text.h_align {
+            HorizontalAlign::Left => 0,
+            HorizontalAlign::Center => 1,
+            HorizontalAlign::Right => 2,
+        });
+        self.u8(match text.v_align {
+            VerticalAlign::Top => 0,
+            VerticalAlign::Center => 1,
+            VerticalAlign::Bottom => 2,
+        });
+        self.u8(match text.wrap {
+            Wrap::None => 0,
+            Wrap::Word => 1,
+            Wrap::Glyph => 2,
+            Wrap::WordOrGlyph => 3,
+        });
+        self.f32(text.line_height_multiplier);
+        self.u16(text.font_weight.to_weight());
+        self.u8(match text.font_style {
+            FontStyle::Normal => 0,
+            FontStyle::Italic => 1,
+        });
+        self.u32(text.spans.len() as u32);
+        for span in &text.spans {
+            self.span(span);
+        }
+        match &text.font_family {
+            Some(family) => {
+                self.u8(1);
+                self.str(family);
+            }
+            None => self.u8(0),
+        }
+        self.u8(match text.overflow {
+            TextOverflow::Clip => 0,
+            TextOverflow::Ellipsis => 1,
+            TextOverflow::Fade => 2,
+        });
+        self.u32(text.max_lines.map_or(0, |lines| lines as u32));
+        match text.outline {
+            Some(outline) => {
+                self.u8(1);
+                self.size(outline.width);
+                self.color(outline.color);
+            }
+            None => self.u8(0),
+        }
+        self.u32(text.font_features.len() as u32);
+        for feature in &text.font_features {
+            self.bytes(&feature.tag);
+            self.u32(feature.value);
+        }
+        self.u8(match text.font_width {
+            FontWidth::UltraCondensed => 0,
+            FontWidth::ExtraCondensed => 1,
+            FontWidth::Condensed => 2,
+            FontWidth::SemiCondensed => 3,
+            FontWidth::Normal => 4,
+            FontWidth::SemiExpanded => 5,
+            FontWidth::Expanded => 6,
+            FontWidth::ExtraExpanded => 7,
+            FontWidth::UltraExpanded => 8,
+        });
+        self.u16(text.tab_width);
+    }
+
+    fn color_filter(&mut self, filter: Option<ColorMatrix>) {
+        let Some(matrix) = filter else {
+            self.u8(0);
+            return;
+        };
+        self.u8(1);
+        for value in matrix.0.into_iter().flatten() {
+            self.f32(value);
+        }
+    }
+
+    fn nine_slice(&mut self, image: Option<&NineSliceImage>) {
+        let Some(image) = image else {
+            self.u8(0);
+            return;
+        };
+        self.u8(1);
+        self.u64(image.texture.0);
+        self.point(image.texture_size);
+        let slice = image.slice;
+        for v in [
+            slice.top,
+            slice.right,
+            slice.bottom,
+            slice.left,
+            slice.scale,
+        ] {
+            self.f32(v);
+        }
+        self.color(image.tint);
+    }
+
+    fn span(&mut self, span: &TextSpan) {
+        self.str(&span.text);
+        match span.color {
+            Some(color) => {
+                self.u8(1);
+                self.color(color);
+            }
+            None => self.u8(0),
+        }
+        // Weight 0 and style 0 mean "use the text's value"
+        self.u16(span.font_weight.map_or(0, FontWeight::to_weight));
+        self.u8(match span.font_style {
+            None => 0,
+            Some(FontStyle::Normal) => 1,
+            Some(FontStyle::Italic) => 2,
+        });
+        match span.font_scale {
+            Some(scale) => {
+                self.u8(1);
+                self.f32(scale);
+            }
+            None => self.u8(0),
+        }
+        self.u8(u8::from(span.underline));
+    }
+
+    fn transform(&mut self, transform: &Transform2D) {
+        self.f32(transform.translation.x);
+        self.f32(transform.translation.y);
+        self.f32(transform.rotation);
+        self.f32(transform.scale);
+        let origin = transform.origin;
+        for v in [
+            origin.x_percent,
+            origin.y_percent,
+            origin.x_offset,
+            origin.y_offset,
+        ] {
+            self.f32(v);
+        }
+        match transform.absolute_origin {
+            Some(p) => {
+                self.u8(1);
+                self.point(p);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    fn clipped_shape(&mut self, shape: &ClippedShape) {
+        self.rect(shape.node_rect);
+        self.rect(shape.clip_rect);
+        self.transform(&shape.transform);
+        self.f32(shape.opacity);
+        self.i32(shape.z_index.0);
+        self.u32(shape.tree_index as u32);
+        match &shape.shape {
+            Shape::Rect(rect) => {
+                self.u8(0);
+                self.rect(rect.rect);
+                self.corner_shape(rect.corner_shape);
+                self.color(rect.fill);
+                self.stroke(rect.stroke);
+                self.anti_aliasing(rect.anti_aliasing);
+                self.size(rect.blur);
+                self.conic_gradient(rect.conic_gradient.as_ref());
+                self.shadow(rect.inset_shadow);
+                self.linear_gradient(rect.linear_gradient.as_ref());
+                self.nine_slice(rect.nine_slice.as_ref());
+            }
+            Shape::Text(text) => {
+                self.u8(1);
+                self.text(text);
+            }
+            Shape::Triangle(tri) => {
+                self.u8(2);
+                self.rect(tri.rect);
+                self.triangle_spec(tri.spec);
+                self.color(tri.fill);
+                self.stroke(tri.stroke);
+                self.anti_aliasing(tri.anti_aliasing);
+            }
+        }
+        self.color_filter(shape.filter);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Schema version of the payload, for fields added in later versions
+    version: u16,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], OutputDecodeError> {
+        if self.remaining() < len {
+            return Err(OutputDecodeError::UnexpectedEof);
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], OutputDecodeError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, OutputDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, OutputDecodeError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, OutputDecodeError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, OutputDecodeError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, OutputDecodeError> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, OutputDecodeError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn str(&mut self) -> Result<String, OutputDecodeError> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| OutputDecodeError::InvalidUtf8)
+    }
+
+    fn point(&mut self) -> Result<[f32; 2], OutputDecodeError> {
+        Ok([self.f32()?, self.f32()?])
+    }
+
+    fn rect(&mut self) -> Result<Rect, OutputDecodeError> {
+        Ok(Rect::new(self.point()?, self.point()?))
+    }
+
+    fn color(&mut self) -> Result<Color, OutputDecodeError> {
+        Ok(Color::rgba(
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+        ))
+    }
+
+    fn tag(&mut self, kind: &'static str, max: u8) -> Result<u8, OutputDecodeError> {
+        let tag = self.u8()?;
+        if tag > max {
+            return Err(OutputDecodeError::InvalidTag { kind, tag });
+        }
+        Ok(tag)
+    }
+
+    fn size(&mut self) -> Result<Size, OutputDecodeError> {
+        let tag = self.tag("size", 5)?;
+        let value = self.f32()?;
+        Ok(match tag {
+            0 => Size::Logical(value),
+            1 => Size::Physical(value),
+            2 => Size::Relative(value),
+            3 => Size::Fractional(value),
+            4 => Size::Fill,
+            _ => Size::FitContent,
+        })
+    }
+
+    fn conic_gradient(&mut self) -> Result<Option<ConicGradient>, OutputDecodeError> {
+        if self.tag("gradient", 1)? == 0 {
+            return Ok(None);
+        }
+        let center = [self.f32()?, self.f32()?];
+        let start_angle = self.f32()?;
+        let count = self.u32()? as usize;
+        let mut stops = Vec::with_capacity(count.min(self.remaining() / 8));
+        for _ in 0..count {
+            stops.push((self.f32()?, self.color()?));
+        }
+        Ok(Some(ConicGradient {
+            center,
+            start_angle,
+            stops,
+        }))
+    }
+
+    fn linear_gradient(&mut self) -> Result<Option<LinearGradient>, OutputDecodeError> {
+        if self.tag("linear gradient", 1)? == 0 {
+            return Ok(None);
+        }
+        let angle = self.f32()?;
+        let count = self.u32()? as usize;
+        let mut stops = Vec::with_capacity(count.min(self.remaining() / 8));
+        for _ in 0..count {
+            stops.push((self.f32()?, self.color()?));
+        }
+        Ok(Some(LinearGradient { angle, stops }))
+    }
+
+    fn shadow(&mut self) -> Result<Option<Shadow>, OutputDecodeError> {
+        if self.tag("shadow", 1)? == 0 {
+            return Ok(None);
+        }
+        let (offset_x, offset_y, blur, spread) =
+            (self.size()?, self.size()?, self.size()?, self.size()?);
+        Ok(Some(
+            Shadow::new(offset_x, offset_y, blur, self.color()?).with_spread(spread),
+        ))
+    }
+
+    fn stroke(&mut self) -> Result<Option<Stroke>, OutputDecodeError> {
+        if self.tag("stroke", 1)? == 0 {
+            return Ok(None);
+        }
+        let width = self.size()?;
+        let color = self.color()?;
+        let tag = self.tag("stroke alignment", 3)?;
+        let offset = self.f32()?;
+        let alignment = match tag {
+            0 => StrokeAlignment::Inset,
+            1 => StrokeAlignment::Centered,
+            2 => StrokeAlignment::Outset,
+            _ => StrokeAlignment::Custom(offset),
+        };
+        Ok(Some(Stroke::new(width, color).with_alignment(alignment)))
+    }
+
+    fn anti_aliasing(&mut self) -> Result<AntiAliasing, OutputDecodeError> {
+        Ok(match self.tag("anti-aliasing", 1)? {
+            0 => AntiAliasing::None,
+            _ => AntiAliasing::Analytical,
+        })
+    }
+
+    fn corner_kind(&mut self) -> Result<CornerKind, OutputDecodeError> {
+        let tag = self.tag("corner kind", 4)?;
+        let size = self.size()?;
+        Ok(match tag {
+            0 => CornerKind::Square,
+            1 => CornerKind::Round(size),
+            2 => CornerKind::Cut(size),
+            3 => CornerKind::InverseRound(size),
+            _ => CornerKind::Squircle {
+                radius: size,
+                smoothness: self.f32()?,
+            },
+        })
+    }
+
+    fn corner_shape(&mut self) -> Result<CornerShape, OutputDecodeError> {
+        Ok(match self.tag("corner shape", 5)? {
+            0 => CornerShape::None,
+            1 => CornerShape::Round(self.size()?),
+            2 => CornerShape::Cut(self.size()?),
+            3 => CornerShape::InverseRound(self.size()?),
+            4 => CornerShape::Squircle {
+                radius: self.size()?,
+                smoothness: self.f32()?,
+            },
+            _ => CornerShape::PerCorner(Corners {
+                top_left: self.corner_kind()?,
+                top_right: self.corner_kind()?,
+                bottom_right: self.corner_kind()?,
+                bottom_left: self.corner_kind()?,
+            }),
+        })
+    }
+
+    fn orientation(&mut self) -> Result<Orientation, OutputDecodeError> {
+        Ok(match self.tag("orientation", 3)? {
+            0 => Orientation::Up,
+            1 => Orientation::Down,
+            2 => Orientation::Left,
+            _ => Orientation::Right,
+        })
+    }
+
+    fn triangle_spec(&mut self) -> Result<TriangleSpec, OutputDecodeError> {
+        Ok(match self.tag("triangle", 4)? {
+            0 => TriangleSpec::Isosceles {
+                orientation: self.orientation()?,
+            },
+            1 => TriangleSpec::Equilateral {
+                orientation: self.orientation()?,
+            },
+            2 => TriangleSpec::RightAngled {
+                corner: match self.tag("corner", 3)? {
+                    0 => Corner::TopLeft,
+                    1 => Corner::TopRight,
+                    2 => Corner::BottomLeft,
+                    _ => Corner::BottomRight,
+                },
+                orientation: self.orientation()?,
+            },
+            3 => TriangleSpec::Points {
+                p1: self.point()?,
+                p2: self.point()?,
+                p3: self.point()?,
+            },
+            _ => TriangleSpec::ApexAngle {
+                angle_degrees: self.f32()?,
+                orientation: self.orientation()?,
+            },
+        })
+    }
+
+    fn text(&mut self) -> Result<TextShape, OutputDecodeError> {
+        Ok(TextShape {
+            rect: self.rect()?,
+            text: self.str()?,
+            font_size: self.size()?,
+            color: self.color()?,
+            h_align: match self.tag("horizontal align", 2)? {
+                0 => HorizontalAlign::Left,
+                1 => HorizontalAlign::Center,
+                _ => HorizontalAlign::Right,
+            },
+            v_align: match self.tag("vertical align", 2)? {
+                0 => VerticalAlign::Top,
+                1 => VerticalAlign::Center,
+                _ => VerticalAlign::Bottom,
+            },
+            wrap: match self.tag("wrap", 3)? {
+                0 => Wrap::None,
+                1 => Wrap::Word,
+                2 => Wrap::Glyph,
+                _ => Wrap::WordOrGlyph,
+            },
+            line_height_multiplier: self.f32()?,
+            font_weight: FontWeight::from_weight(self.u16()?),
+            font_style: match self.tag("font style", 1)? {
+                0 => FontStyle::Normal,
+                _ => FontStyle::Italic,
+            },
+            spans: if self.version >= 5 {
+                self.spans()?
+            } else {
+                Vec::new()
+            },
+            font_family: if self.version >= 6 && self.tag("font family", 1)? == 1 {
+                Some(self.str()?)
+            } else {
+                None
+            },
+            overflow: if self.version >= 7 {
+                match self.tag("text overflow", 2)? {
+                    0 => TextOverflow::Clip,
+                    1 => TextOverflow::Ellipsis,
+                    _ => TextOverflow::Fade,
+                }
+            } else {
+                TextOverflow::Clip
+            },
+            max_lines: if self.version >= 8 {
+                match self.u32()? {
+                    0 => None,
+                    lines => Some(lines as usize),
+                }
+            } else {
+                None
+            },
+            outline: if self.version >= 9 && self.tag("text outline", 1)? == 1 {
+                Some(TextOutline::new(self.size()?, self.color()?))
+            } else {
+                None
+            },
+            font_features: if self.version >= 10 {
+                self.font_features()?
+            } else {
+                Vec::new()
+            },
+            font_width: if self.version >= 10 {
+                match self.tag("font width", 8)? {
+                    0 => FontWidth::UltraCondensed,
+                    1 => FontWidth::ExtraCondensed,
+                    2 => FontWidth::Condensed,
+                    3 => FontWidth::SemiCondensed,
+                    4 => FontWidth::Normal,
+                    5 => FontWidth::SemiExpanded,
+                    6 => FontWidth::Expanded,
+                    7 => FontWidth::ExtraExpanded,
+                    _ => FontWidth::UltraExpanded,
+                }
+            } else {
+                FontWidth::Normal
+            },
+            tab_width: if self.version >= 11 {
+                self.u16()?.max(1)
+            } else {
+                TextContent::DEFAULT_TAB_WIDTH
+            },
+        })
+    }
+
+    fn color_filter(&mut self) -> Result<Option<ColorMatrix>, OutputDecodeError> {
+        if self.tag("color filter", 1)? == 0 {
+            return Ok(None);
+        }
+        let mut matrix = ColorMatrix::IDENTITY;
+        for value in matrix.0.iter_mut().flatten() {
+            *value = self.f32()?;
+        }
+        Ok(Some(matrix))
+    }
+
+    fn nine_slice(&mut self) -> Result<Option<NineSliceImage>, OutputDecodeError> {
+        if self.tag("nine slice", 1)? == 0 {
+            return Ok(None);
+        }
+        let texture = TextureId(self.u64()?);
+        let texture_size = self.point()?;
+        let slice = NineSlice::new(self.f32()?, self.f32()?, self.f32()?, self.f32()?)
+            .with_scale(self.f32()?);
+        Ok(Some(NineSliceImage {
+            tint: self.color()?,
+            ..NineSliceImage::new(texture, texture_size, slice)
+        }))
+    }
+
+    fn font_features(&mut self) -> Result<Vec<FontFeature>, OutputDecodeError> {
+        let count = self.u32()? as usize;
+        let mut features = Vec::with_capacity(count.min(self.remaining() / 8));
+        for _ in 0..count {
+            let tag = self.take(4)?;
+            features.push(FontFeature::new(
+                [tag[0], tag[1], tag[2], tag[3]],
+                self.u32()?,
+            ));
+        }
+        Ok(features)
+    }
+
+    fn spans(&mut self) -> Result<Vec<TextSpan>, OutputDecodeError> {
+        let count = self.u32()? as usize;
+        let mut spans = Vec::with_capacity(count.min(self.remaining() / 10));
+        for _ in 0..count {
+            let text = self.str()?;
+            let color = match self.tag("span color", 1)? {
+                0 => None,
+                _ => Some(self.color()?),
+            };
+            let font_weight = match self.u16()? {
+                0 => None,
+                weight => Some(FontWeight::from_weight(weight)),
+            };
+            let font_style = match self.tag("span font style", 2)? {
+                0 => None,
+                1 => Some(FontStyle::Normal),
+                _ => Some(FontStyle::Italic),
+            };
+            let font_scale = match self.tag("span font scale", 1)? {
+                0 => None,
+                _ => Some(self.f32()?),
+            };
+            spans.push(TextSpan {
+                text,
+                color,
+                font_weight,
+                font_style,
+                font_scale,
+                underline: self.tag("span underline", 1)? == 1,
+            });
+        }
+        Ok(spans)
+    }
+
+    fn transform(&mut self) -> Result<Transform2D, OutputDecodeError> {
+        Ok(Transform2D {
+            translation: Vector2 {
+                x: self.f32()?,
+                y: self.f32()?,
+            },
+            rotation: self.f32()?,
+            scale: self.f32()?,
+            origin: TransformOrigin {
+                x_percent: self.f32()?,
+                y_percent: self.f32()?,
+                x_offset: self.f32()?,
+                y_offset: self.f32()?,
+            },
+            absolute_origin: match self.tag("absolute origin", 1)? {
+                0 => None,
+                _ => Some(self.point()?),
+            },
+        })
+    }
+
+    fn clipped_shape(&mut self) -> Result<ClippedShape, OutputDecodeError> {
+        let node_rect = self.rect()?;
+        let clip_rect = self.rect()?;
+        let transform = self.transform()?;
+        let opacity = self.f32()?;
+        let z_index = ZIndex(self.i32()?);
+        let tree_index = self.u32()? as usize;
+        let shape = match self.tag("shape", 2)? {
+            0 => Shape::Rect(StyledRect {
+                rect: self.rect()?,
+                corner_shape: self.corner_shape()?,
+                fill: self.color()?,
+                stroke: self.stroke()?,
+                anti_aliasing: self.anti_aliasing()?,
+                blur: if self.version >= 2 {
+                    self.size()?
+                } else {
+                    Size::ppx(0.0)
+                },
+                conic_gradient: if self.version >= 3 {
+                    self.conic_gradient()?
+                } else {
+                    None
+                },
+                inset_shadow: if self.version >= 4 {
+                    self.shadow()?
+                } else {
+                    None
+                },
+                linear_gradient: if self.version >= 12 {
+                    self.linear_gradient()?
+                } else {
+                    None
+                },
+                nine_slice: if self.version >= 14 {
+                    self.nine_slice()?
+                } else {
+                    None
+                },
+            }),
+            1 => Shape::Text(self.text()?),
+            _ => Shape::Triangle(StyledTriangle {
+                rect: self.rect()?,
+                spec: self.triangle_spec()?,
+                fill: self.color()?,
+                stroke: self.stroke()?,
+                anti_aliasing: self.anti_aliasing()?,
+            }),
+        };
+
+        let filter = if self.version >= 13 {
+            self.color_filter()?
+        } else {
+            None
+        };
+
+        Ok(ClippedShape {
+            node_rect,
+            clip_rect,
+            shape,
+            transform,
+            opacity,
+            z_index,
+            tree_index,
+            filter,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_output() -> FullOutput {
+        let rect = Rect::new([10.0, 20.0], [110.0, 70.0]);
+        let styled = StyledRect::new(rect, Color::rgba(0.1, 0.2, 0.3, 0.4))
+            .with_corner_shape(CornerShape::PerCorner(
+                Corners::all(CornerKind::Round(Size::lpx(4.0)))
+                    .with_top_left(CornerKind::Cut(Size::ppx(2.0))),
+            ))
+            .with_stroke(
+                Stroke::new(Size::lpx(1.5), Color::rgb(1.0, 0.0, 0.0))
+                    .with_alignment(StrokeAlignment::Custom(0.5)),
+            )
+            .with_blur(Size::ppx(6.0))
+            .with_fill(ConicGradient::progress(
+                0.3,
+                Color::rgb(0.0, 0.5, 1.0),
+                Color::rgba(1.0, 1.0, 1.0, 0.2),
+            ))
+            .with_inset_shadow(
+                Shadow::new(
+                    Size::lpx(0.0),
+                    Size::lpx(2.0),
+                    Size::lpx(4.0),
+                    Color::rgba(0.0, 0.0, 0.0, 0.5),
+                )
+                .with_spread(Size::ppx(1.0)),
+            );
+        let text = TextShape::new(
+            rect,
+            &TextContent::new("héllo\n")
+                .with_span(TextSpan::new("world").bold().underline())
+                .with_font_size(Size::lpx(14.0))
+                .with_font_weight(FontWeight::SemiBold)
+                .with_font_family("Noto Sans")
+                .with_overflow(TextOverflow::Ellipsis)
+                .with_max_lines(2)
+                .with_outline(Size::lpx(2.0), Color::rgb(0.0, 0.0, 0.0))
+                .with_font_feature(FontFeature::stylistic_set(3).off())
+                .with_font_width(FontWidth::Condensed)
+                .with_tab_width(4),
+        );
+        let triangle = StyledTriangle::new(
+            rect,
+            TriangleSpec::RightAngled {
+                corner: Corner::BottomRight,
+                orientation: Orientation::Left,
+            },
+            Color::rgb(0.0, 1.0, 0.0),
+        );
+
+        let linear = StyledRect::new(rect, Color::rgb(1.0, 1.0, 1.0))
+            .with_fill(
+                LinearGradient::new([
+                    (0.0, Color::rgb(1.0, 0.5, 0.0)),
+                    (0.7, Color::rgba(0.2, 0.0, 1.0, 0.8)),
+                ])
+                .with_angle(2.5),
+            )
+            .with_nine_slice(
+                NineSliceImage::new(
+                    TextureId(9),
+                    [32.0, 16.0],
+                    NineSlice::new(1.0, 2.0, 3.0, 4.0).with_scale(2.0),
+                )
+                .with_tint(Color::rgba(1.0, 0.5, 0.5, 0.8)),
+            );
+
+        let mut shapes = vec![
+            ClippedShape::new(rect, Shape::Rect(styled)),
+            ClippedShape::new(rect, Shape::Text(text)),
+            ClippedShape::new(rect, Shape::Triangle(triangle)),
+            ClippedShape::new(rect, Shape::Rect(linear)),
+        ];
+        shapes[0].transform.rotation = 0.25;
+        shapes[0].transform.absolute_origin = Some([60.0, 45.0]);
+        shapes[1].opacity = 0.5;
+        shapes[1].filter = Some(crate::Filter::Grayscale(1.0).matrix());
+        shapes[2].z_index = ZIndex(-3);
+        shapes[2].tree_index = 7;
+
+        FullOutput {
+            shapes,
+            debug_options: Some(DebugOptions {
+                show_padding: true,
+                show_overdraw: true,
+                ..DebugOptions::none()
+            }),
+            ..FullOutput::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_preserves_output() {
+        let output = sample_output();
+        let bytes = output.to_bytes();
+        assert_eq!(&bytes[..4], b"ASTR");
+        assert_eq!(
+            u16::from_le_bytes([bytes[4], bytes[5]]),
+            FULL_OUTPUT_SCHEMA_VERSION
+        );
+
+        let decoded = FullOutput::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.shapes.len(), output.shapes.len());
+        // Re-encoding the decoded output must be byte-identical
+        assert_eq!(decoded.to_bytes(), bytes);
+
+        let debug = decoded.debug_options.unwrap();
+        assert!(debug.show_padding && debug.show_overdraw && !debug.show_margins);
+        let Shape::Text(text) = &decoded.shapes[1].shape else {
+            panic!("expected text shape");
+        };
+        assert_eq!(text.text, "héllo\nworld");
+        assert_eq!(text.spans[1], TextSpan::new("world").bold().underline());
+        assert_eq!(text.font_family.as_deref(), Some("Noto Sans"));
+        assert_eq!(text.overflow, TextOverflow::Ellipsis);
+        assert_eq!(text.max_lines, Some(2));
+        assert_eq!(text.font_features, [FontFeature::new(*b"ss03", 0)]);
+        assert_eq!(text.font_width, FontWidth::Condensed);
+        assert_eq!(text.tab_width, 4);
+        assert_eq!(
+            text.outline,
+            Some(TextOutline::new(Size::lpx(2.0), Color::rgb(0.0, 0.0, 0.0)))
+        );
+        assert_eq!(
+            decoded.shapes[1].filter,
+            Some(crate::Filter::Grayscale(1.0).matrix())
+        );
+        assert_eq!(decoded.shapes[2].z_index, ZIndex(-3));
+        let Shape::Rect(linear) = &decoded.shapes[3].shape else {
+            panic!("expected rect shape");
+        };
+        let gradient = linear.linear_gradient.as_ref().unwrap();
+        assert_eq!(gradient.angle, 2.5);
+        assert_eq!(gradient.stops.len(), 2);
+        let Shape::Rect(original) = &output.shapes[3].shape else {
+            panic!("expected rect shape");
+        };
+        assert_eq!(linear.nine_slice, original.nine_slice);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let bytes = sample_output().to_bytes();
+
+        assert_eq!(
+            FullOutput::from_bytes(b"nope").unwrap_err(),
+            OutputDecodeError::BadMagic
+        );
+        assert_eq!(
+            FullOutput::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            OutputDecodeError::UnexpectedEof
+        );
+
+        let mut future = bytes.clone();
+        future[4..6].copy_from_slice(&(FULL_OUTPUT_SCHEMA_VERSION + 1).to_le_bytes());
+        assert_eq!(
+            FullOutput::from_bytes(&future).unwrap_err(),
+            OutputDecodeError::UnsupportedVersion(FULL_OUTPUT_SCHEMA_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn test_decodes_version_1_rects_without_blur() {
+        let rect = Rect::new([0.0, 0.0], [10.0, 10.0]);
+        let output = FullOutput {
+            shapes: vec![ClippedShape::new(
+                rect,
+                Shape::Rect(StyledRect::new(rect, Color::rgb(1.0, 1.0, 1.0))),
+            )],
+            ..FullOutput::default()
+        };
+
+        // A version 1 rect is the same record without the trailing blur size,
+        // gradient flags and inset shadow flag
+        let mut bytes = output.to_bytes();
+        bytes.truncate(bytes.len() - 8);
+        bytes[4..6].copy_from_slice(&1u16.to_le_bytes());
+
+        let decoded = FullOutput::from_bytes(&bytes).unwrap();
+        let Shape::Rect(decoded_rect) = &decoded.shapes[0].shape else {
+            panic!("expected rect shape");
+        };
+        assert_eq!(decoded_rect.blur, Size::ppx(0.0));
+    }
+}
@@ -0,0 +1,338 @@
+//! Retained-mode UI tree
+//!
+//! Immediate mode rebuilds and lays out the whole tree every frame. For
+//! mostly-static UIs a [`RetainedTree`] keeps the laid-out tree between
+//! frames instead, and updates it in one of two ways:
+//!
+//! - `update` diffs a newly built tree against the kept one. Nodes are
+//!   matched by ID (or position among unidentified siblings); a matched node
+//!   with the same `Node::with_revision` keeps its previous settings and
+//!   layout, and the diff moves on to its children. Other matched nodes take
+//!   the new settings, and only count as changed if they lay out differently
+//!   (size, spacing, alignment, ...) or their children did.
+//! - `mutate` changes one node in place by its ID, without building anything.
+//!
+//! Either way the tree remembers which subtrees changed, and `layout` lays out
+//! only those. A changed subtree whose node has a fixed size, and the same
+//! size, margins and placement as before, is laid out again in the rect it
+//! already occupies. Anything else could move its siblings, so the change
+//! moves up to the parent, up to the whole tree. Trees using layout
+//! constraints are always laid out as a whole.
+//!
+//! `output` collects the shapes to render. Unchanged subtrees whose node has a
+//! revision reuse the shapes collected last time, as long as they weren't
+//! laid out again and their ancestors still pass down the same clip,
+//! transform, opacity and inherited text and filter settings. Subtrees whose
+//! looks also change in place each frame, through styles, stylesheet classes,
+//! tokens, scrolling or custom painting, are always collected again.
+//!
+//! `UiContext::retained` holds the tree and `UiContext::end_retained_frame`
+//! dispatches events against it.
+
+use crate::debug::DebugOptions;
+use crate::measure::ContentMeasurer;
+use crate::node::{Node, NodeId};
+use crate::output::FullOutput;
+use crate::primitives::Rect;
+
+/// What `RetainedTree::layout` laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutPass {
+    /// Nothing changed, the previous layout was kept
+    Skipped,
+    /// The given number of changed subtrees were laid out in place
+    Subtrees(usize),
+    /// The whole tree was laid out
+    Full,
+}
+
+/// A changed subtree, by its path of child indices from the root
+#[derive(Debug, Clone)]
+struct Change {
+    path: Vec<usize>,
+    /// Whether the subtree can be laid out again in its previous rect
+    in_place: bool,
+}
+
+/// A UI tree kept between frames and updated by diffing or targeted mutations
+#[derive(Default)]
+pub struct RetainedTree {
+    root: Option<Node>,
+    changes: Vec<Change>,
+    /// The whole tree needs layout
+    full_layout: bool,
+    /// The rect the tree was last laid out in
+    layout_rect: Option<Rect>,
+}
+
+impl RetainedTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the kept tree, if one was set
+    pub fn root(&self) -> Option<&Node> {
+        self.root.as_ref()
+    }
+
+    /// Get the kept tree mutably
+    ///
+    /// Changes made through it aren't tracked; call `invalidate` after
+    /// changing layout or looks.
+    pub fn root_mut(&mut self) -> Option<&mut Node> {
+        self.root.as_mut()
+    }
+
+    /// Take the kept tree out, e.g. to end a frame with it
+    pub(crate) fn take_root(&mut self) -> Option<Node> {
+        self.root.take()
+    }
+
+    /// Put back a tree taken with `take_root`
+    pub(crate) fn set_root(&mut self, root: Node) {
+        self.root = Some(root);
+    }
+
+    /// Check if something changed since the last layout
+    pub fn needs_layout(&self) -> bool {
+        self.full_layout || !self.changes.is_empty()
+    }
+
+    /// Lay out the whole tree with the next `layout`, e.g. while size
+    /// transitions run
+    pub fn invalidate(&mut self) {
+        self.full_layout = true;
+        if let Some(root) = self.root.as_mut() {
+            root.clear_shape_caches();
+        }
+    }
+
+    /// Diff a newly built tree against the kept one and keep the result
+    ///
+    /// Matched nodes with an unchanged revision keep their previous settings
+    /// and layout; everything else comes from `root`, keeping the previous
+    /// layout where the settings lay out the same.
+    pub fn update(&mut self, root: Node) {
+        let old = self.root.take().map(|mut old| {
+            old.clear_auto_id();
+            old
+        });
+        let (mut root, _) = reconcile(old, root, &mut Vec::new(), &mut self.changes);
+        // New nodes that keep their layout still inherit text settings
+        root.resolve_inherited_text(None, 1.0);
+        self.root = Some(root);
+    }
+
+    /// Change the node with ID `id` in place
+    ///
+    /// Returns false if no node has the ID. Changing the node's size,
+    /// margins or placement lays out its parent again too.
+    pub fn mutate(&mut self, id: &str, mutate: impl FnOnce(&mut Node)) -> bool {
+        let Some(root) = self.root.as_mut() else {
+            return false;
+        };
+        let mut path = Vec::new();
+        if !find_path(root, id, &mut path) {
+            return false;
+        }
+        // The node and every subtree containing it look different now
+        for depth in 0..=path.len() {
+            descendant_mut(root, &path[..depth]).clear_shape_cache();
+        }
+        let node = descendant_mut(root, &path);
+        let before = node.footprint();
+        mutate(node);
+        let in_place = node.has_fixed_size() && node.footprint() == before;
+        self.changes.push(Change { path, in_place });
+        true
+    }
+
+    /// Lay out what changed since the last layout inside `window`
+    ///
+    /// Pass the same measurer every frame when the tree uses
+    /// `Size::FitContent` or text.
+    pub fn layout(
+        &mut self,
+        window: Rect,
+        mut measurer: Option<&mut dyn ContentMeasurer>,
+    ) -> LayoutPass {
+        let changes = std::mem::take(&mut self.changes);
+        let Some(root) = self.root.as_mut() else {
+            return LayoutPass::Skipped;
+        };
+
+        // Subtrees are laid out in place only inside an earlier layout
+        let mut full = self.full_layout
+            || self.layout_rect != Some(window)
+            || root.computed_layout().is_none()
+            || (!changes.is_empty() && root.uses_constraints());
+        let mut paths: Vec<Vec<usize>> = Vec::new();
+        if !full {
+            for change in changes {
+                // Changes that could move their siblings move up to the parent
+                let mut path = change.path;
+                let mut in_place = change.in_place;
+                while !in_place && !path.is_empty() {
+                    path.pop();
+                    in_place = !path.is_empty() && descendant_mut(root, &path).has_fixed_size();
+                }
+                if path.is_empty() || descendant_mut(root, &path).computed_layout().is_none() {
+                    full = true;
+                    break;
+                }
+                paths.push(path);
+            }
+        }
+
+        self.full_layout = false;
+        self.layout_rect = Some(window);
+        if full {
+            root.clear_shape_caches();
+            match measurer {
+                Some(measurer) => root.compute_layout_with_measurer(window, measurer),
+                None => root.compute_layout(window),
+            }
+            return LayoutPass::Full;
+        }
+        if paths.is_empty() {
+            return LayoutPass::Skipped;
+        }
+
+        // Lay out each subtree once, skipping those inside another
+        paths.sort();
+        paths.dedup_by(|inner, outer| inner.starts_with(outer));
+        for path in &paths {
+            // Everything around and inside the subtree may move
+            for depth in 0..path.len() {
+                descendant_mut(root, &path[..depth]).clear_shape_cache();
+            }
+            descendant_mut(root, path).clear_shape_caches();
+            let measurer = measurer
+                .as_mut()
+                .map(|measurer| &mut **measurer as &mut dyn ContentMeasurer);
+            root.relayout_descendant(path, measurer, 1.0);
+        }
+        LayoutPass::Subtrees(paths.len())
+    }
+
+    /// Collect the shapes to render from the laid-out tree, like
+    /// `FullOutput::from_laid_out_tree`
+    ///
+    /// Unchanged revisioned subtrees reuse their shapes from the last call.
+    pub fn output(
+        &self,
+        window_size: (f32, f32),
+        debug_options: Option<DebugOptions>,
+    ) -> FullOutput {
+        match &self.root {
+            Some(root) => FullOutput::from_retained_tree(root, window_size, debug_options),
+            None => FullOutput::default(),
+        }
+    }
+}
+
+/// Merge `new` into `old`, recording the changed subtrees
+///
+/// Also returns whether the whole subtree was kept as it was, so it may keep
+/// its cached shapes.
+fn reconcile(
+    old: Option<Node>,
+    mut new: Node,
+    path: &mut Vec<usize>,
+    changes: &mut Vec<Change>,
+) -> (Node, bool) {
+    let Some(mut old) = old else {
+        changes.push(Change {
+            path: path.clone(),
+            in_place: false,
+        });
+        return (new, false);
+    };
+
+    // Match the new children to the old ones by ID, or by position among
+    // unidentified siblings. Auto-IDs from the last frame don't count, and are
+    // assigned again for the node's current place
+    let mut old_children: Vec<Option<Node>> = old
+        .take_children()
+        .into_iter()
+        .map(|mut child| {
+            child.clear_auto_id();
+            Some(child)
+        })
+        .collect();
+    let old_ids: Vec<Option<NodeId>> = old_children
+        .iter()
+        .map(|child| child.as_ref().and_then(|child| child.id().cloned()))
+        .collect();
+    let new_children = new.take_children();
+    let same_children = new_children.len() == old_ids.len()
+        && new_children
+            .iter()
+            .zip(&old_ids)
+            .all(|(child, id)| child.id() == id.as_ref());
+
+    let mut children = Vec::with_capacity(new_children.len());
+    let mut children_kept = true;
+    for (index, child) in new_children.into_iter().enumerate() {
+        let matched = match child.id() {
+            Some(id) => old_ids.iter().position(|old| old.as_ref() == Some(id)),
+            None => (old_ids.get(index) == Some(&None)).then_some(index),
+        };
+        let previous = matched.and_then(|matched| old_children[matched].take());
+        path.push(index);
+        let (child, kept) = reconcile(previous, child, path, changes);
+        children.push(child);
+        children_kept &= kept;
+        path.pop();
+    }
+
+    let unchanged = new.revision().is_some() && new.revision() == old.revision();
+    if unchanged {
+        if !same_children {
+            changes.push(Change {
+                path: path.clone(),
+                in_place: old.has_fixed_size(),
+            });
+        }
+        let kept = same_children && children_kept;
+        if !kept {
+            old.clear_shape_cache();
+        }
+        old.set_children(children);
+        (old, kept)
+    } else {
+        // New settings that lay out the same only change the output
+        if !same_children || !new.lays_out_like(&old) {
+            changes.push(Change {
+                path: path.clone(),
+                in_place: new.has_fixed_size() && new.footprint() == old.footprint(),
+            });
+        }
+        // Keep the previous layout, to lay out in place or as it is
+        new.copy_layout_from(&old);
+        new.set_children(children);
+        (new, false)
+    }
+}
+
+/// Find the path of child indices to the node with ID `id`
+fn find_path(node: &Node, id: &str, path: &mut Vec<usize>) -> bool {
+    if node.id().is_some_and(|node_id| node_id.as_str() == id) {
+        return true;
+    }
+    for (index, child) in node.children().iter().enumerate() {
+        path.push(index);
+        if find_path(child, id, path) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
+
+/// Get the descendant at a path of child indices
+fn descendant_mut<'a>(node: &'a mut Node, path: &[usize]) -> &'a mut Node {
+    path.iter()
+        .fold(node, |node, &index| &mut node.children_mut()[index])
+}
@@ -1,21 +1,68 @@
-use crate::color::Color;
-use crate::content::Content;
-use crate::layout::{TransformOrigin, Translation};
+//! Visual styling
+//!
+//! Besides [`Style`] itself, this module re-exports the types used to fill in
+//! a style, share it through themes and stylesheets, and animate it.
+
+use crate::layout::{Size, TransformOrigin, Translation};
 use crate::node::Node;
-use crate::primitives::{CornerShape, Shape, Stroke};
+use crate::primitives::Shape;
+
+pub use crate::animation::{AnimatedProperty, Animation, Repeat};
+pub use crate::color::{Color, ColorSpace};
+#[cfg(feature = "css")]
+pub use crate::css_parser::CssError;
+pub use crate::filter::{ColorMatrix, Filter};
+pub use crate::nine_slice::{NineSlice, NineSliceImage, TextureId};
+pub use crate::palette::{
+    builtin_palettes, CatppuccinFrappe, CatppuccinLatte, CatppuccinMacchiato, CatppuccinMocha,
+    Dracula, GruvboxDark, GruvboxLight, Nord, Palette,
+};
+pub use crate::primitives::{
+    AntiAliasing, ConicGradient, Corner, CornerKind, CornerShape, Corners, Fill, LinearGradient,
+    Outline, Shadow, Stroke, StrokeAlignment,
+};
+pub use crate::stylesheet::{StyleRule, StyleSheet};
+pub use crate::theme::{ColorScheme, Theme, ThemeColor, ThemePair};
+pub use crate::tokens::{
+    var, StyleProperty, TokenRef, TokenTable, TokenValue, VarBinding, VarState,
+};
+pub use crate::transition::{EasingFn, Transition};
 
 /// Visual style properties that can be transitioned
 ///
 /// All fields are `Option<T>` to allow partial styles that only override specific properties.
 /// This enables style merging where hover/active states only specify the properties that change.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Style {
     /// Background fill color (for shapes)
     pub fill_color: Option<Color>,
 
+    /// Conic gradient painted instead of the fill color (for shapes)
+    pub gradient: Option<ConicGradient>,
+
+    /// Linear gradient painted instead of the fill color (for shapes)
+    pub linear_gradient: Option<LinearGradient>,
+
+    /// Nine-slice texture painted instead of the fill color and gradients (for shapes)
+    pub nine_slice: Option<NineSliceImage>,
+
     /// Stroke configuration (width and color)
     pub stroke: Option<Stroke>,
 
+    /// Outline drawn outside the node rect (does not affect layout)
+    pub outline: Option<Outline>,
+
+    /// Drop shadow behind the node's shape (does not affect layout)
+    pub shadow: Option<Shadow>,
+
+    /// Shadow cast inward from the edges of the node's shape (for shapes)
+    pub inset_shadow: Option<Shadow>,
+
+    /// Color composited over the fill color (e.g. the elevation overlay)
+    pub tint: Option<Color>,
+
     /// Corner shape (supports all variants: None, Round, Cut, InverseRound, Squircle)
     pub corner_shape: Option<CornerShape>,
 
@@ -56,6 +103,12 @@ pub struct Style {
     /// When set, this bypasses the normal Size→pixels resolution during layout
     /// and uses this value directly. Used for animating between different Size variants.
     pub height_override: Option<f32>,
+
+    /// Repeating animations sampled from the frame clock, applied over the
+    /// static properties above (only run through `UiContext`). Not
+    /// serialized, since easing functions can't be.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub animations: Vec<Animation>,
 }
 
 impl Style {
@@ -72,6 +125,30 @@ impl Style {
         }
     }
 
+    /// Create a style with only a fill gradient
+    pub fn gradient(gradient: ConicGradient) -> Self {
+        Self {
+            gradient: Some(gradient),
+            ..Default::default()
+        }
+    }
+
+    /// Create a style with only a linear fill gradient
+    pub fn linear_gradient(gradient: LinearGradient) -> Self {
+        Self {
+            linear_gradient: Some(gradient),
+            ..Default::default()
+        }
+    }
+
+    /// Create a style with only a nine-slice background
+    pub fn nine_slice(image: NineSliceImage) -> Self {
+        Self {
+            nine_slice: Some(image),
+            ..Default::default()
+        }
+    }
+
     /// Create a style with only text color
     pub fn text(color: Color) -> Self {
         Self {
@@ -88,6 +165,51 @@ impl Style {
         }
     }
 
+    /// Create a style with only a repeating animation
+    pub fn animated(animation: Animation) -> Self {
+        Self {
+            animations: vec![animation],
+            ..Default::default()
+        }
+    }
+
+    /// Create a style for an elevation level: a drop shadow plus a light overlay tint
+    ///
+    /// Levels go from 0 (flat) to 5; higher levels are clamped to 5. Each level
+    /// raises the shadow's offset and blur and strengthens the tint, so surfaces
+    /// at the same level look consistent across the app. Level 0 sets a
+    /// transparent shadow and tint rather than none, so transitions between
+    /// levels animate smoothly. Merge the result with the surface's own style:
+    ///
+    /// ```ignore
+    /// let card = Style::fill(mocha::SURFACE0).merge(&Style::elevation(2));
+    /// let card_hover = Style::elevation(4);
+    /// ```
+    pub fn elevation(level: u8) -> Self {
+        // (shadow offset y, shadow blur, shadow alpha, tint alpha) per level,
+        // loosely following Material's shadow and dark-theme overlay scale
+        const LEVELS: [(f32, f32, f32, f32); 6] = [
+            (0.0, 0.0, 0.0, 0.0),
+            (1.0, 3.0, 0.20, 0.05),
+            (2.0, 6.0, 0.22, 0.07),
+            (4.0, 10.0, 0.24, 0.08),
+            (6.0, 16.0, 0.26, 0.09),
+            (8.0, 24.0, 0.28, 0.11),
+        ];
+        let (offset_y, blur, shadow_alpha, tint_alpha) = LEVELS[usize::from(level).min(5)];
+
+        Self {
+            shadow: Some(Shadow::new(
+                Size::lpx(0.0),
+                Size::lpx(offset_y),
+                Size::lpx(blur),
+                Color::rgba(0.0, 0.0, 0.0, shadow_alpha),
+            )),
+            tint: Some(Color::rgba(1.0, 1.0, 1.0, tint_alpha)),
+            ..Default::default()
+        }
+    }
+
     /// Merge this style with another, preferring values from `other` when present
     ///
     /// This is used to combine base → hover → active styles, where each layer
@@ -95,7 +217,17 @@ impl Style {
     pub fn merge(&self, other: &Style) -> Style {
         Style {
             fill_color: other.fill_color.or(self.fill_color),
+            gradient: other.gradient.clone().or_else(|| self.gradient.clone()),
+            linear_gradient: other
+                .linear_gradient
+                .clone()
+                .or_else(|| self.linear_gradient.clone()),
+            nine_slice: other.nine_slice.or(self.nine_slice),
             stroke: other.stroke.or(self.stroke),
+            outline: other.outline.or(self.outline),
+            shadow: other.shadow.or(self.shadow),
+            inset_shadow: other.inset_shadow.or(self.inset_shadow),
+            tint: other.tint.or(self.tint),
             corner_shape: other.corner_shape.or(self.corner_shape),
             opacity: other.opacity.or(self.opacity),
             text_color: other.text_color.or(self.text_color),
@@ -106,6 +238,11 @@ impl Style {
             transform_origin: other.transform_origin.or(self.transform_origin),
             width_override: other.width_override.or(self.width_override),
             height_override: other.height_override.or(self.height_override),
+            animations: if other.animations.is_empty() {
+                self.animations.clone()
+            } else {
+                other.animations.clone()
+            },
         }
     }
 
@@ -118,6 +255,18 @@ impl Style {
             node.set_opacity(opacity);
         }
 
+        if let Some(outline) = self.outline {
+            node.set_outline(outline);
+        }
+
+        if let Some(shadow) = self.shadow {
+            node.set_shadow(shadow);
+        }
+
+        if let Some(tint) = self.tint {
+            node.set_tint(tint);
+        }
+
         // Apply to shape if present
         if let Some(shape) = node.shape_mut() {
             match shape {
@@ -126,6 +275,22 @@ impl Style {
                         rect.fill = color;
                     }
 
+                    if let Some(gradient) = &self.gradient {
+                        rect.conic_gradient = Some(gradient.clone());
+                    }
+
+                    if let Some(gradient) = &self.linear_gradient {
+                        rect.linear_gradient = Some(gradient.clone());
+                    }
+
+                    if let Some(image) = self.nine_slice {
+                        rect.nine_slice = Some(image);
+                    }
+
+                    if let Some(shadow) = self.inset_shadow {
+                        rect.inset_shadow = Some(shadow);
+                    }
+
                     // Apply stroke via unified field only.
                     if let Some(stroke) = self.stroke {
                         rect.stroke = Some(stroke);
@@ -154,9 +319,10 @@ impl Style {
 
         // Apply to text content if present
         if let Some(content) = node.content_mut() {
-            let Content::Text(ref mut text) = content;
+            let text = content.text_mut();
             if let Some(color) = self.text_color {
                 text.color = color;
+                text.inherit.color = false;
             }
         }
 
@@ -228,4 +394,65 @@ mod tests {
         assert_eq!(merged.fill_color, Some(Color::rgb(1.0, 0.0, 0.0)));
         assert_eq!(merged.opacity, Some(0.5));
     }
+
+    #[test]
+    fn test_style_macro_builds_style() {
+        use crate::layout::Spacing;
+        use crate::primitives::{CornerShape, Stroke};
+
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let style = crate::style! {
+            fill: red,
+            radius: 12,
+            stroke: (2, red),
+            translate_x: 4.5,
+            opacity: 0.5,
+        };
+        assert_eq!(style.fill_color, Some(red));
+        assert_eq!(
+            style.corner_shape,
+            Some(CornerShape::Round(Size::lpx(12.0)))
+        );
+        assert_eq!(style.stroke, Some(Stroke::new(Size::lpx(2.0), red)));
+        assert_eq!(style.translation_x, Some(Size::lpx(4.5)));
+        assert_eq!(style.opacity, Some(0.5));
+
+        let rule = crate::style! {
+            fill: red,
+            padding: symmetric(8, 4),
+            hover: { opacity: 0.8, elevation: 2 },
+        };
+        assert_eq!(rule.style.unwrap().fill_color, Some(red));
+        assert_eq!(
+            rule.padding,
+            Some(Spacing::symmetric(Size::lpx(8.0), Size::lpx(4.0)))
+        );
+        let hover = rule.hover_style.unwrap();
+        assert_eq!(hover.opacity, Some(0.8));
+        assert_eq!(hover.shadow, Style::elevation(2).shadow);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_and_partial_config() {
+        use crate::primitives::CornerShape;
+
+        let style = Style {
+            fill_color: Some(Color::rgb(0.2, 0.4, 0.6)),
+            corner_shape: Some(CornerShape::Round(Size::lpx(8.0))),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&style).unwrap();
+        let decoded: Style = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.fill_color, style.fill_color);
+        assert_eq!(decoded.corner_shape, style.corner_shape);
+
+        // Missing properties fall back to unset
+        let partial: Style =
+            serde_json::from_str(r#"{ "opacity": 0.5, "translation_x": { "Logical": 4.0 } }"#)
+                .unwrap();
+        assert_eq!(partial.opacity, Some(0.5));
+        assert_eq!(partial.translation_x, Some(Size::lpx(4.0)));
+        assert_eq!(partial.fill_color, None);
+    }
 }
@@ -0,0 +1,336 @@
+//! Class-based stylesheets
+//!
+//! Nodes declare classes with `Node::with_class`, and a `StyleSheet` registered
+//! on the `UiContext` maps each class to a `StyleRule`. Rules are resolved in
+//! `UiContext::end_frame`, before interaction states and transitions, so class
+//! styles get hover/active/disabled variants and animate like inline styles.
+//!
+//! Precedence (lowest to highest): rules in the order the classes were added
+//! to the node, then the node's own `with_style`/`with_hover_style`/... styles.
+//!
+//! Layout properties (padding) must be known before layout, so they are
+//! resolved separately in `UiContext::inject_dimension_overrides`.
+
+use crate::layout::Spacing;
+use crate::node::Node;
+use crate::style::Style;
+use crate::transition::Transition;
+use std::collections::HashMap;
+
+/// Styles applied to every node carrying a given class
+#[derive(Debug, Clone, Default)]
+pub struct StyleRule {
+    pub(crate) style: Option<Style>,
+    pub(crate) hover_style: Option<Style>,
+    pub(crate) active_style: Option<Style>,
+    pub(crate) focused_style: Option<Style>,
+    pub(crate) selected_style: Option<Style>,
+    pub(crate) disabled_style: Option<Style>,
+    pub(crate) transition: Option<Transition>,
+    pub(crate) padding: Option<Spacing>,
+}
+
+impl StyleRule {
+    /// Create an empty rule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the base style
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set the hover style (merged with base when hovered)
+    pub fn with_hover_style(mut self, style: Style) -> Self {
+        self.hover_style = Some(style);
+        self
+    }
+
+    /// Set the active style (merged with base + hover when pressed)
+    pub fn with_active_style(mut self, style: Style) -> Self {
+        self.active_style = Some(style);
+        self
+    }
+
+    /// Set the focused style (merged with base while focused)
+    pub fn with_focused_style(mut self, style: Style) -> Self {
+        self.focused_style = Some(style);
+        self
+    }
+
+    /// Set the selected style (merged with base while selected)
+    pub fn with_selected_style(mut self, style: Style) -> Self {
+        self.selected_style = Some(style);
+        self
+    }
+
+    /// Set the disabled style
+    pub fn with_disabled_style(mut self, style: Style) -> Self {
+        self.disabled_style = Some(style);
+        self
+    }
+
+    /// Set the transition used for style changes
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+
+    /// Set the padding, used for nodes without padding of their own
+    pub fn with_padding(mut self, padding: Spacing) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Get the base style
+    pub fn style(&self) -> Option<&Style> {
+        self.style.as_ref()
+    }
+
+    /// Get the hover style
+    pub fn hover_style(&self) -> Option<&Style> {
+        self.hover_style.as_ref()
+    }
+
+    /// Get the active style
+    pub fn active_style(&self) -> Option<&Style> {
+        self.active_style.as_ref()
+    }
+
+    /// Get the focused style
+    pub fn focused_style(&self) -> Option<&Style> {
+        self.focused_style.as_ref()
+    }
+
+    /// Get the selected style
+    pub fn selected_style(&self) -> Option<&Style> {
+        self.selected_style.as_ref()
+    }
+
+    /// Get the disabled style
+    pub fn disabled_style(&self) -> Option<&Style> {
+        self.disabled_style.as_ref()
+    }
+
+    /// Get the transition
+    pub fn transition(&self) -> Option<&Transition> {
+        self.transition.as_ref()
+    }
+
+    /// Get the padding
+    pub fn padding(&self) -> Option<Spacing> {
+        self.padding
+    }
+
+    /// Layer `other` on top of this rule, its properties taking precedence
+    pub fn merge(&self, other: &StyleRule) -> StyleRule {
+        StyleRule {
+            style: merge_layer(self.style.as_ref(), other.style.as_ref()),
+            hover_style: merge_layer(self.hover_style.as_ref(), other.hover_style.as_ref()),
+            active_style: merge_layer(self.active_style.as_ref(), other.active_style.as_ref()),
+            focused_style: merge_layer(self.focused_style.as_ref(), other.focused_style.as_ref()),
+            selected_style: merge_layer(
+                self.selected_style.as_ref(),
+                other.selected_style.as_ref(),
+            ),
+            disabled_style: merge_layer(
+                self.disabled_style.as_ref(),
+                other.disabled_style.as_ref(),
+            ),
+            transition: other.transition.or(self.transition),
+            padding: other.padding.or(self.padding),
+        }
+    }
+}
+
+fn merge_layer(below: Option<&Style>, above: Option<&Style>) -> Option<Style> {
+    match (below, above) {
+        (Some(below), Some(above)) => Some(below.merge(above)),
+        (Some(style), None) | (None, Some(style)) => Some(style.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Maps class names to style rules
+///
+/// # Example
+///
+/// ```ignore
+/// let sheet = StyleSheet::new()
+///     .with_rule("card", StyleRule::new().with_style(Style {
+///         fill_color: Some(mocha::SURFACE0),
+///         ..Default::default()
+///     }))
+///     .with_rule("danger", StyleRule::new().with_hover_style(Style {
+///         fill_color: Some(mocha::RED),
+///         ..Default::default()
+///     }));
+/// ctx.set_style_sheet(sheet);
+///
+/// Node::new().with_id(NodeId::new("delete")).with_class("card").with_class("danger")
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StyleSheet {
+    rules: HashMap<String, StyleRule>,
+}
+
+impl StyleSheet {
+    /// Create an empty stylesheet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) the rule for a class
+    pub fn with_rule(mut self, class: impl Into<String>, rule: StyleRule) -> Self {
+        self.set_rule(class, rule);
+        self
+    }
+
+    /// Add (or replace) the rule for a class
+    pub fn set_rule(&mut self, class: impl Into<String>, rule: StyleRule) {
+        self.rules.insert(class.into(), rule);
+    }
+
+    /// Remove the rule for a class, returning it if present
+    pub fn remove_rule(&mut self, class: &str) -> Option<StyleRule> {
+        self.rules.remove(class)
+    }
+
+    /// Get the rule for a class
+    pub fn rule(&self, class: &str) -> Option<&StyleRule> {
+        self.rules.get(class)
+    }
+
+    /// Check whether the stylesheet has no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Extend this stylesheet with the rules of another, replacing rules for the same class
+    pub fn extend(&mut self, other: StyleSheet) {
+        self.rules.extend(other.rules);
+    }
+
+    /// Merge the rules for a node's classes, in class order
+    fn resolve(&self, node: &Node) -> StyleRule {
+        node.classes()
+            .iter()
+            .filter_map(|class| self.rules.get(class))
+            .fold(StyleRule::default(), |acc, rule| acc.merge(rule))
+    }
+
+    /// Resolve class styles for a node tree
+    pub(crate) fn apply(&self, node: &mut Node) {
+        if !node.classes().is_empty() {
+            let resolved = self.resolve(node);
+            node.apply_style_rule(&resolved);
+        }
+
+        for child in node.children_mut() {
+            self.apply(child);
+        }
+    }
+
+    /// Resolve class layout properties for a node tree (before layout)
+    pub(crate) fn apply_layout(&self, node: &mut Node) {
+        if !node.classes().is_empty() {
+            if let Some(padding) = self.resolve(node).padding {
+                if node.padding() == Spacing::ZERO {
+                    node.set_padding(padding);
+                }
+            }
+        }
+
+        for child in node.children_mut() {
+            self.apply_layout(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, NodeId};
+
+    fn fill(color: Color) -> Style {
+        Style {
+            fill_color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_class_styles_resolve_in_order() {
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let blue = Color::rgb(0.0, 0.0, 1.0);
+        let sheet = StyleSheet::new()
+            .with_rule(
+                "card",
+                StyleRule::new().with_style(Style {
+                    fill_color: Some(red),
+                    opacity: Some(0.5),
+                    ..Default::default()
+                }),
+            )
+            .with_rule(
+                "danger",
+                StyleRule::new()
+                    .with_style(fill(blue))
+                    .with_hover_style(fill(red)),
+            );
+
+        let mut node = Node::new()
+            .with_id(NodeId::new("n"))
+            .with_class("card")
+            .with_class("danger")
+            .with_class("unknown");
+        sheet.apply(&mut node);
+
+        let base = node.base_style().unwrap();
+        assert_eq!(base.fill_color, Some(blue));
+        assert_eq!(base.opacity, Some(0.5));
+        assert_eq!(node.hover_style().unwrap().fill_color, Some(red));
+        assert!(node.shape().is_some());
+    }
+
+    #[test]
+    fn test_inline_styles_override_classes() {
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let blue = Color::rgb(0.0, 0.0, 1.0);
+        let sheet = StyleSheet::new().with_rule(
+            "card",
+            StyleRule::new().with_style(Style {
+                fill_color: Some(red),
+                opacity: Some(0.5),
+                ..Default::default()
+            }),
+        );
+
+        let mut node = Node::new().with_class("card").with_style(fill(blue));
+        sheet.apply(&mut node);
+
+        let base = node.base_style().unwrap();
+        assert_eq!(base.fill_color, Some(blue));
+        assert_eq!(base.opacity, Some(0.5));
+    }
+
+    #[test]
+    fn test_class_padding_only_fills_unset_padding() {
+        use crate::Size;
+
+        let padding = Spacing::all(Size::lpx(8.0));
+        let sheet = StyleSheet::new().with_rule("card", StyleRule::new().with_padding(padding));
+
+        let mut node = Node::new().with_class("card").with_child(
+            Node::new()
+                .with_class("card")
+                .with_padding(Spacing::all(Size::lpx(2.0))),
+        );
+        sheet.apply_layout(&mut node);
+
+        assert_eq!(node.padding(), padding);
+        assert_eq!(node.children()[0].padding(), Spacing::all(Size::lpx(2.0)));
+    }
+}
@@ -0,0 +1,23 @@
+//! Text content and measurement
+//!
+//! Re-exports the types a node's text content is built from, the
+//! [`Highlighter`] interface that colors code content, the
+//! [`ContentMeasurer`] interface text backends implement to size it, and the
+//! [`Clipboard`] text widgets copy to and paste from.
+
+pub use crate::clipboard::Clipboard;
+pub use crate::content::{
+    CodeContent, Content, FontStyle, FontWeight, HorizontalAlign, TextContent, TextInherit,
+    TextOverflow, TextSpan, VerticalAlign, Wrap, DEFAULT_CODE_FAMILY,
+};
+pub use crate::highlight::Highlighter;
+#[cfg(feature = "syntect")]
+pub use crate::highlight::SyntectHighlighter;
+pub use crate::measure::{
+    CacheStats, ContentMeasurer, IntrinsicSize, MeasureCache, MeasureTextRequest,
+};
+pub use crate::primitives::TextShape;
+pub use crate::text_edit::{
+    LineRect, Motion, Selection, TextBuffer, TextEditDecorations, TextEditResponse, TextEditStyle,
+    TextEditor,
+};
@@ -0,0 +1,1525 @@
+//! Text editing core
+//!
+//! [`TextEditor`] keeps editable text in a gap buffer together with a caret
+//! and selection, word-wise motions and an undo history. Widgets drive it
+//! with [`TextEditor::handle_input`] (or the individual editing methods), then
+//! attach the caret and selection geometry to the text node so it's emitted
+//! into the `FullOutput` along with the text:
+//!
+//! ```ignore
+//! let editor = ctx.memory().get_or_insert_default::<TextEditor>(&id);
+//! let response = editor.handle_input(&input);
+//! let decorations = editor.decorations(measurer, &edit_style, caret_visible);
+//!
+//! Node::new()
+//!     .with_content(Content::Text(TextContent::new(editor.text())))
+//!     .with_text_edit(decorations)
+//! ```
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::color::Color;
+use crate::content::{
+    FontStyle, FontWeight, FontWidth, HorizontalAlign, TextContent, VerticalAlign, Wrap,
+};
+use crate::input::{InputState, Key, NamedKey};
+use crate::measure::{ContentMeasurer, MeasureTextRequest};
+use crate::primitives::Point;
+use crate::theme::Theme;
+
+/// Smallest amount the gap grows by when it fills up
+const MIN_GAP: usize = 64;
+
+/// Undo steps kept before the oldest ones are dropped
+const UNDO_LIMIT: usize = 256;
+
+/// Text stored in a gap buffer
+///
+/// Edits close to the previous one (typing, deleting) only move the bytes
+/// between the two positions, so they stay cheap in long documents. Positions
+/// are byte offsets into the text and lie on char boundaries.
+#[derive(Debug, Clone, Default)]
+pub struct TextBuffer {
+    bytes: Vec<u8>,
+    gap: Range<usize>,
+}
+
+impl TextBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Length of the text in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len() - self.gap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of lines (an empty text has one line)
+    pub fn line_count(&self) -> usize {
+        let (front, back) = self.physical_parts();
+        1 + front.iter().chain(back).filter(|&&b| b == b'\n').count()
+    }
+
+    /// Insert text at a byte offset
+    pub fn insert(&mut self, pos: usize, text: &str) {
+        assert!(
+            self.is_char_boundary(pos),
+            "insert position {pos} is not a char boundary"
+        );
+        self.move_gap(pos);
+        self.reserve_gap(text.len());
+        self.bytes[pos..pos + text.len()].copy_from_slice(text.as_bytes());
+        self.gap.start += text.len();
+    }
+
+    /// Remove a byte range, returning the removed text
+    pub fn remove(&mut self, range: Range<usize>) -> String {
+        let removed = self.slice(range.clone()).into_owned();
+        self.move_gap(range.start);
+        self.gap.end += range.len();
+        removed
+    }
+
+    /// Text in a byte range; borrowed unless the range spans the gap
+    pub fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        assert!(
+            self.is_char_boundary(range.start) && self.is_char_boundary(range.end),
+            "slice {range:?} does not lie on char boundaries"
+        );
+        let gap_start = self.gap.start;
+        let gap_len = self.gap.len();
+        if range.end <= gap_start {
+            Cow::Borrowed(utf8(&self.bytes[range]))
+        } else if range.start >= gap_start {
+            Cow::Borrowed(utf8(
+                &self.bytes[range.start + gap_len..range.end + gap_len],
+            ))
+        } else {
+            Cow::Owned(
+                [
+                    utf8(&self.bytes[range.start..gap_start]),
+                    utf8(&self.bytes[self.gap.end..range.end + gap_len]),
+                ]
+                .concat(),
+            )
+        }
+    }
+
+    /// The whole text; borrowed when the gap sits at either end
+    pub fn text(&self) -> Cow<'_, str> {
+        self.slice(0..self.len())
+    }
+
+    /// Move the gap to the end and borrow the text as one string
+    pub fn make_contiguous(&mut self) -> &str {
+        self.move_gap(self.len());
+        utf8(&self.bytes[..self.gap.start])
+    }
+
+    pub fn is_char_boundary(&self, pos: usize) -> bool {
+        pos == 0 || pos == self.len() || (pos < self.len() && !is_continuation(self.byte(pos)))
+    }
+
+    /// The char starting at a byte offset
+    pub fn char_at(&self, pos: usize) -> Option<char> {
+        if pos >= self.len() {
+            return None;
+        }
+        let width = utf8_width(self.byte(pos)).min(self.len() - pos);
+        let mut buf = [0; 4];
+        for (i, b) in buf.iter_mut().enumerate().take(width) {
+            *b = self.byte(pos + i);
+        }
+        std::str::from_utf8(&buf[..width]).ok()?.chars().next()
+    }
+
+    /// The char ending at a byte offset
+    pub fn char_before(&self, pos: usize) -> Option<char> {
+        (pos > 0)
+            .then(|| self.prev_boundary(pos))
+            .and_then(|start| self.char_at(start))
+    }
+
+    /// Offset of the char before `pos`
+    pub fn prev_boundary(&self, pos: usize) -> usize {
+        let mut pos = pos.saturating_sub(1);
+        while pos > 0 && !self.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        pos
+    }
+
+    /// Offset of the char after the one at `pos`
+    pub fn next_boundary(&self, pos: usize) -> usize {
+        let len = self.len();
+        (pos + 1..len)
+            .find(|&pos| self.is_char_boundary(pos))
+            .unwrap_or(len)
+    }
+
+    /// Start of the line containing `pos`
+    pub fn line_start(&self, pos: usize) -> usize {
+        let mut pos = pos;
+        while pos > 0 && self.byte(pos - 1) != b'\n' {
+            pos -= 1;
+        }
+        pos
+    }
+
+    /// End of the line containing `pos` (the offset of its newline, if any)
+    pub fn line_end(&self, pos: usize) -> usize {
+        let len = self.len();
+        let mut pos = pos;
+        while pos < len && self.byte(pos) != b'\n' {
+            pos += 1;
+        }
+        pos
+    }
+
+    /// Start of the word before `pos`, skipping whitespace in between
+    ///
+    /// Words are runs of alphanumeric characters (and `_`) or runs of other
+    /// punctuation, so `foo.bar` takes two motions to cross the `.`.
+    pub fn prev_word_boundary(&self, pos: usize) -> usize {
+        let mut pos = pos;
+        while let Some(ch) = self.char_before(pos).filter(|ch| ch.is_whitespace()) {
+            pos -= ch.len_utf8();
+        }
+        if let Some(class) = self.char_before(pos).map(CharClass::of) {
+            while let Some(ch) = self
+                .char_before(pos)
+                .filter(|&ch| CharClass::of(ch) == class)
+            {
+                pos -= ch.len_utf8();
+            }
+        }
+        pos
+    }
+
+    /// End of the word at `pos` plus the whitespace after it
+    pub fn next_word_boundary(&self, pos: usize) -> usize {
+        let mut pos = pos;
+        if let Some(class) = self
+            .char_at(pos)
+            .map(CharClass::of)
+            .filter(|&class| class != CharClass::Whitespace)
+        {
+            while let Some(ch) = self.char_at(pos).filter(|&ch| CharClass::of(ch) == class) {
+                pos += ch.len_utf8();
+            }
+        }
+        while let Some(ch) = self.char_at(pos).filter(|ch| ch.is_whitespace()) {
+            pos += ch.len_utf8();
+        }
+        pos
+    }
+
+    /// Number of chars in a byte range
+    pub fn char_count(&self, range: Range<usize>) -> usize {
+        range
+            .filter(|&pos| !is_continuation(self.byte(pos)))
+            .count()
+    }
+
+    /// Byte at a text offset
+    fn byte(&self, pos: usize) -> u8 {
+        if pos < self.gap.start {
+            self.bytes[pos]
+        } else {
+            self.bytes[pos + self.gap.len()]
+        }
+    }
+
+    /// The bytes before and after the gap
+    fn physical_parts(&self) -> (&[u8], &[u8]) {
+        (&self.bytes[..self.gap.start], &self.bytes[self.gap.end..])
+    }
+
+    fn move_gap(&mut self, pos: usize) {
+        let gap_len = self.gap.len();
+        if pos < self.gap.start {
+            self.bytes.copy_within(pos..self.gap.start, pos + gap_len);
+        } else if pos > self.gap.start {
+            self.bytes
+                .copy_within(self.gap.end..pos + gap_len, self.gap.start);
+        }
+        self.gap = pos..pos + gap_len;
+    }
+
+    fn reserve_gap(&mut self, additional: usize) {
+        if self.gap.len() >= additional {
+            return;
+        }
+        let grow = (additional - self.gap.len())
+            .max(MIN_GAP)
+            .max(self.bytes.len() / 2);
+        let old_len = self.bytes.len();
+        self.bytes.resize(old_len + grow, 0);
+        self.bytes
+            .copy_within(self.gap.end..old_len, self.gap.end + grow);
+        self.gap.end += grow;
+    }
+}
+
+impl From<&str> for TextBuffer {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl From<String> for TextBuffer {
+    fn from(text: String) -> Self {
+        let len = text.len();
+        Self {
+            bytes: text.into_bytes(),
+            gap: len..len,
+        }
+    }
+}
+
+fn utf8(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).expect("the gap only splits text on char boundaries")
+}
+
+fn is_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+fn utf8_width(first_byte: u8) -> usize {
+    match first_byte {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+/// Whether typing `text` after `typed` begins a new word
+fn starts_word(typed: &str, text: &str) -> bool {
+    typed.ends_with(char::is_whitespace) && !text.starts_with(char::is_whitespace)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+impl CharClass {
+    fn of(ch: char) -> Self {
+        if ch.is_whitespace() {
+            Self::Whitespace
+        } else if ch.is_alphanumeric() || ch == '_' {
+            Self::Word
+        } else {
+            Self::Punctuation
+        }
+    }
+}
+
+/// Caret and selection as byte offsets
+///
+/// The selection spans from `anchor` to `caret`; nothing is selected when
+/// they're equal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Selection {
+    pub anchor: usize,
+    pub caret: usize,
+}
+
+impl Selection {
+    pub fn new(anchor: usize, caret: usize) -> Self {
+        Self { anchor, caret }
+    }
+
+    /// A caret without selected text
+    pub fn collapsed(pos: usize) -> Self {
+        Self::new(pos, pos)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.caret
+    }
+
+    /// The selected byte range, in text order
+    pub fn range(&self) -> Range<usize> {
+        self.anchor.min(self.caret)..self.anchor.max(self.caret)
+    }
+}
+
+/// Caret motions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    /// Previous line, keeping the column of the first vertical motion
+    Up,
+    /// Next line, keeping the column of the first vertical motion
+    Down,
+    LineStart,
+    LineEnd,
+    DocumentStart,
+    DocumentEnd,
+}
+
+/// Edits that merge into the previous undo step while they continue it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Typing,
+    Deleting,
+}
+
+/// One undo step: `removed` was replaced by `inserted` at `pos`
+#[derive(Debug, Clone)]
+struct Edit {
+    pos: usize,
+    removed: String,
+    inserted: String,
+    before: Selection,
+    after: Selection,
+}
+
+/// What `TextEditor::handle_input` did this frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextEditResponse {
+    /// The text changed
+    pub changed: bool,
+    /// Enter was pressed (Ctrl+Enter in multi-line editors)
+    pub submitted: bool,
+}
+
+/// Editable text with a caret, selection and undo history
+///
+/// Multi-line by default; single-line editors turn pasted newlines into
+/// spaces and report Enter as a submit.
+#[derive(Debug, Clone)]
+pub struct TextEditor {
+    buffer: TextBuffer,
+    selection: Selection,
+    multiline: bool,
+    /// Column (in chars) that consecutive vertical motions aim for
+    preferred_column: Option<usize>,
+    /// Horizontal position that consecutive row motions aim for
+    preferred_x: Option<f32>,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    /// Kind of the last edit while the next one may still merge into it
+    coalesce: Option<EditKind>,
+}
+
+impl Default for TextEditor {
+    fn default() -> Self {
+        Self {
+            buffer: TextBuffer::new(),
+            selection: Selection::default(),
+            multiline: true,
+            preferred_column: None,
+            preferred_x: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            coalesce: None,
+        }
+    }
+}
+
+impl TextEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the initial text, with the caret at its end
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set whether Enter inserts a newline (true) or submits (false)
+    pub fn with_multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.multiline
+    }
+
+    /// Replace the text, clearing the undo history and moving the caret to the end
+    pub fn set_text(&mut self, text: &str) {
+        let text = self.normalize(text);
+        self.selection = Selection::collapsed(text.len());
+        self.buffer = TextBuffer::from(text.into_owned());
+        self.preferred_column = None;
+        self.preferred_x = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.coalesce = None;
+    }
+
+    pub fn buffer(&self) -> &TextBuffer {
+        &self.buffer
+    }
+
+    pub fn text(&self) -> Cow<'_, str> {
+        self.buffer.text()
+    }
+
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+
+    /// Set the selection, clamped to the text and snapped to char boundaries
+    pub fn set_selection(&mut self, selection: Selection) {
+        let clamp = |pos: usize| {
+            let mut pos = pos.min(self.buffer.len());
+            while !self.buffer.is_char_boundary(pos) {
+                pos -= 1;
+            }
+            pos
+        };
+        self.selection = Selection::new(clamp(selection.anchor), clamp(selection.caret));
+        self.preferred_column = None;
+        self.preferred_x = None;
+        self.coalesce = None;
+    }
+
+    pub fn selected_text(&self) -> Cow<'_, str> {
+        self.buffer.slice(self.selection.range())
+    }
+
+    /// Zero-based line and column (in chars) of the caret
+    pub fn caret_position(&self) -> (usize, usize) {
+        let caret = self.selection.caret;
+        let line_start = self.buffer.line_start(caret);
+        let line = self.buffer.slice(0..line_start).matches('\n').count();
+        (line, self.buffer.char_count(line_start..caret))
+    }
+
+    /// Byte offset of the caret closest to `point`, e.g. to place the caret on
+    /// click; see [`TextEditStyle::caret_index_at`]
+    pub fn caret_index_at(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        style: &TextEditStyle,
+        point: Point,
+    ) -> usize {
+        style.caret_index_at(measurer, &self.buffer.text(), point)
+    }
+
+    pub fn select_all(&mut self) {
+        self.set_selection(Selection::new(0, self.buffer.len()));
+    }
+
+    /// Replace the selection with `text`
+    ///
+    /// Returns true if the text changed.
+    pub fn insert(&mut self, text: &str) -> bool {
+        let text = self.normalize(text);
+        let kind = (self.selection.is_empty() && !text.contains('\n')).then_some(EditKind::Typing);
+        self.replace(self.selection.range(), &text, kind)
+    }
+
+    /// Delete the selection or the char (or word) before the caret
+    pub fn backspace(&mut self, word: bool) -> bool {
+        let caret = self.selection.caret;
+        let range = if !self.selection.is_empty() {
+            self.selection.range()
+        } else if word {
+            self.buffer.prev_word_boundary(caret)..caret
+        } else {
+            self.buffer.prev_boundary(caret)..caret
+        };
+        self.replace(range, "", Some(EditKind::Deleting))
+    }
+
+    /// Delete the selection or the char (or word) after the caret
+    pub fn delete(&mut self, word: bool) -> bool {
+        let caret = self.selection.caret;
+        let range = if !self.selection.is_empty() {
+            self.selection.range()
+        } else if word {
+            caret..self.buffer.next_word_boundary(caret)
+        } else {
+            caret..self.buffer.next_boundary(caret)
+        };
+        self.replace(range, "", Some(EditKind::Deleting))
+    }
+
+    /// Move the caret, extending the selection from its anchor if `extend` is set
+    pub fn move_caret(&mut self, motion: Motion, extend: bool) {
+        let caret = self.selection.caret;
+        // Without shift, left/right collapse a selection to its edge
+        let collapse = !extend && !self.selection.is_empty();
+        let target = match motion {
+            Motion::Left if collapse => self.selection.range().start,
+            Motion::Right if collapse => self.selection.range().end,
+            Motion::Left => self.buffer.prev_boundary(caret),
+            Motion::Right => self.buffer.next_boundary(caret),
+            Motion::WordLeft => self.buffer.prev_word_boundary(caret),
+            Motion::WordRight => self.buffer.next_word_boundary(caret),
+            Motion::Up => self.vertical_target(caret, false),
+            Motion::Down => self.vertical_target(caret, true),
+            Motion::LineStart => self.buffer.line_start(caret),
+            Motion::LineEnd => self.buffer.line_end(caret),
+            Motion::DocumentStart => 0,
+            Motion::DocumentEnd => self.buffer.len(),
+        };
+
+        if !matches!(motion, Motion::Up | Motion::Down) {
+            self.preferred_column = None;
+        }
+        self.preferred_x = None;
+        self.selection = if extend {
+            Selection::new(self.selection.anchor, target)
+        } else {
+            Selection::collapsed(target)
+        };
+        self.coalesce = None;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Revert the last undo step; returns false if there was none
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+        self.buffer.remove(edit.pos..edit.pos + edit.inserted.len());
+        self.buffer.insert(edit.pos, &edit.removed);
+        self.selection = edit.before;
+        self.redo.push(edit);
+        self.preferred_column = None;
+        self.preferred_x = None;
+        self.coalesce = None;
+        true
+    }
+
+    /// Reapply the last undone step; returns false if there was none
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+        self.buffer.remove(edit.pos..edit.pos + edit.removed.len());
+        self.buffer.insert(edit.pos, &edit.inserted);
+        self.selection = edit.after;
+        self.undo.push(edit);
+        self.preferred_column = None;
+        self.preferred_x = None;
+        self.coalesce = None;
+        true
+    }
+
+    /// Apply this frame's typed characters and editing keys
+    ///
+    /// Handles arrows (word-wise with Ctrl, extending the selection with
+    /// Shift), Home/End (document start/end with Ctrl), Backspace/Delete,
+    /// Enter, Ctrl+A, Ctrl+Z and Ctrl+Shift+Z / Ctrl+Y.
+    pub fn handle_input(&mut self, input: &InputState) -> TextEditResponse {
+        let mut response = TextEditResponse::default();
+        let word = input.ctrl_held;
+        let extend = input.shift_held;
+
+        for ch in input.characters_typed.iter().filter(|ch| !ch.is_control()) {
+            response.changed |= self.insert(ch.encode_utf8(&mut [0; 4]));
+        }
+
+        for key in &input.keys_just_pressed {
+            match key {
+                Key::Character(ch) if input.ctrl_held => match ch.to_lowercase().as_str() {
+                    "a" => self.select_all(),
+                    "z" if input.shift_held => response.changed |= self.redo(),
+                    "z" => response.changed |= self.undo(),
+                    "y" => response.changed |= self.redo(),
+                    _ => {}
+                },
+                Key::Named(NamedKey::Enter) if self.multiline && !input.ctrl_held => {
+                    response.changed |= self.insert("\n");
+                }
+                Key::Named(NamedKey::Enter) => response.submitted = true,
+                Key::Named(NamedKey::Backspace) => response.changed |= self.backspace(word),
+                Key::Named(NamedKey::Delete) => response.changed |= self.delete(word),
+                Key::Named(NamedKey::ArrowLeft) => {
+                    let motion = if word { Motion::WordLeft } else { Motion::Left };
+                    self.move_caret(motion, extend);
+                }
+                Key::Named(NamedKey::ArrowRight) => {
+                    let motion = if word {
+                        Motion::WordRight
+                    } else {
+                        Motion::Right
+                    };
+                    self.move_caret(motion, extend);
+                }
+                Key::Named(NamedKey::ArrowUp) => self.move_caret(Motion::Up, extend),
+                Key::Named(NamedKey::ArrowDown) => self.move_caret(Motion::Down, extend),
+                Key::Named(NamedKey::Home) => {
+                    let motion = if word {
+                        Motion::DocumentStart
+                    } else {
+                        Motion::LineStart
+                    };
+                    self.move_caret(motion, extend);
+                }
+                Key::Named(NamedKey::End) => {
+                    let motion = if word {
+                        Motion::DocumentEnd
+                    } else {
+                        Motion::LineEnd
+                    };
+                    self.move_caret(motion, extend);
+                }
+                _ => {}
+            }
+        }
+
+        response
+    }
+
+    /// [`TextEditor::handle_input`] for text wrapped with
+    /// [`TextEditStyle::wrap_width`]: Up/Down move between wrapped rows
+    /// rather than lines
+    pub fn handle_input_wrapped(
+        &mut self,
+        input: &InputState,
+        measurer: &mut dyn ContentMeasurer,
+        style: &TextEditStyle,
+    ) -> TextEditResponse {
+        let is_vertical = |key: &Key| {
+            matches!(
+                key,
+                Key::Named(NamedKey::ArrowUp) | Key::Named(NamedKey::ArrowDown)
+            )
+        };
+        if style.wrap_width.is_none() || !input.keys_just_pressed.iter().any(is_vertical) {
+            return self.handle_input(input);
+        }
+
+        let mut rest = input.clone();
+        rest.keys_just_pressed.retain(|key| !is_vertical(key));
+        let response = self.handle_input(&rest);
+        for key in input
+            .keys_just_pressed
+            .iter()
+            .filter(|key| is_vertical(key))
+        {
+            let down = matches!(key, Key::Named(NamedKey::ArrowDown));
+            self.move_caret_row(measurer, style, down, input.shift_held);
+        }
+        response
+    }
+
+    /// Move the caret to the previous or next row as laid out by `style`,
+    /// keeping the horizontal position of the first row motion
+    ///
+    /// Unlike [`Motion::Up`]/[`Motion::Down`] this follows wrapped rows.
+    /// Moving past the first or last row goes to the start or end of the text.
+    pub fn move_caret_row(
+        &mut self,
+        measurer: &mut dyn ContentMeasurer,
+        style: &TextEditStyle,
+        down: bool,
+        extend: bool,
+    ) {
+        let text = self.buffer.text().into_owned();
+        let rows = style.rows(measurer, &text);
+        let caret = self.selection.caret;
+        let row = row_of(&rows, caret);
+        let x = match self.preferred_x {
+            Some(x) => x,
+            None => style.measure(measurer, &text[rows[row].range.start..caret]),
+        };
+
+        let target = if !down && row == 0 {
+            0
+        } else if down && row + 1 == rows.len() {
+            text.len()
+        } else {
+            let target = &rows[if down { row + 1 } else { row - 1 }];
+            style.index_in_row(measurer, &text, target, x)
+        };
+        self.selection = if extend {
+            Selection::new(self.selection.anchor, target)
+        } else {
+            Selection::collapsed(target)
+        };
+        self.preferred_column = None;
+        self.preferred_x = Some(x);
+        self.coalesce = None;
+    }
+
+    /// Measure the caret and selection for drawing
+    ///
+    /// Lines are measured unwrapped, matching text shown with `Wrap::None`,
+    /// unless the style sets a [`TextEditStyle::wrap_width`]; then the rows
+    /// match [`TextEditStyle::display_text`]. The caret is left out when
+    /// `caret_visible` is false (e.g. while the blink hides it).
+    pub fn decorations(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        style: &TextEditStyle,
+        caret_visible: bool,
+    ) -> TextEditDecorations {
+        let line_height = style.font_size * style.line_height_multiplier;
+        let text = self.buffer.text();
+        let rows = style.rows(measurer, &text);
+        let mut measure = |text: &str| style.measure(measurer, text);
+
+        let caret = self.selection.caret;
+        let selected = self.selection.range();
+        let mut decorations = TextEditDecorations {
+            caret: None,
+            selection: Vec::new(),
+            block_height: 0.0,
+            caret_color: style.caret_color,
+            selection_color: style.selection_color,
+        };
+
+        let caret_row = row_of(&rows, caret);
+        for (index, row) in rows.iter().enumerate() {
+            let (line_start, line_end) = (row.range.start, row.range.end);
+            let line = &text[row.range.clone()];
+            let y = index as f32 * line_height;
+            // A selection starting at a soft break begins on the next row
+            let has_caret = caret_visible && index == caret_row;
+            let has_selection = !selected.is_empty()
+                && (selected.start < line_end || (!row.soft && selected.start == line_end))
+                && selected.end > line_start;
+
+            if has_caret || has_selection {
+                let line_width = measure(line);
+
+                if has_selection {
+                    let start = selected.start.max(line_start) - line_start;
+                    let end = selected.end.min(line_end) - line_start;
+                    let x = measure(&line[..start]);
+                    let mut width = measure(&line[..end]) - x;
+                    // Selected newlines show as a sliver past the line end
+                    if selected.end > line_end && !row.soft {
+                        width += style.font_size * 0.3;
+                    }
+                    decorations.selection.push(LineRect {
+                        x,
+                        y,
+                        width,
+                        height: line_height,
+                        line_width,
+                    });
+                }
+
+                if has_caret {
+                    let x = measure(&line[..caret - line_start]);
+                    decorations.caret = Some(style.caret_line_rect(index, x, line_width));
+                }
+            }
+
+            decorations.block_height = y + line_height;
+        }
+
+        decorations
+    }
+
+    /// Apply an edit and record it for undo
+    fn replace(&mut self, range: Range<usize>, text: &str, kind: Option<EditKind>) -> bool {
+        if range.is_empty() && text.is_empty() {
+            return false;
+        }
+
+        let before = self.selection;
+        let removed = self.buffer.remove(range.clone());
+        self.buffer.insert(range.start, text);
+        let after = Selection::collapsed(range.start + text.len());
+        self.selection = after;
+        self.preferred_column = None;
+        self.preferred_x = None;
+        self.redo.clear();
+
+        let merged = kind.is_some()
+            && kind == self.coalesce
+            && self.undo.last_mut().is_some_and(|last| {
+                match kind {
+                    // Keep typing in one step until a new word starts
+                    Some(EditKind::Typing)
+                        if last.pos + last.inserted.len() == range.start
+                            && !starts_word(&last.inserted, text) =>
+                    {
+                        last.inserted.push_str(text);
+                    }
+                    // Backspacing into the previous deletion
+                    Some(EditKind::Deleting) if range.end == last.pos => {
+                        last.removed.insert_str(0, &removed);
+                        last.pos = range.start;
+                    }
+                    // Deleting forward from the previous deletion
+                    Some(EditKind::Deleting) if range.start == last.pos => {
+                        last.removed.push_str(&removed);
+                    }
+                    _ => return false,
+                }
+                last.after = after;
+                true
+            });
+
+        if !merged {
+            self.undo.push(Edit {
+                pos: range.start,
+                removed,
+                inserted: text.to_string(),
+                before,
+                after,
+            });
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.coalesce = kind;
+        true
+    }
+
+    /// Where an Up/Down motion from `caret` lands
+    fn vertical_target(&mut self, caret: usize, down: bool) -> usize {
+        let len = self.buffer.len();
+        let line_start = self.buffer.line_start(caret);
+        let column = *self
+            .preferred_column
+            .get_or_insert_with(|| self.buffer.char_count(line_start..caret));
+
+        let target_line = if down {
+            let line_end = self.buffer.line_end(caret);
+            if line_end == len {
+                return len;
+            }
+            line_end + 1
+        } else {
+            if line_start == 0 {
+                return 0;
+            }
+            self.buffer.line_start(line_start - 1)
+        };
+
+        // Walk `column` chars into the target line, stopping at its end
+        let mut pos = target_line;
+        for _ in 0..column {
+            if self.buffer.char_at(pos).is_none_or(|ch| ch == '\n') {
+                break;
+            }
+            pos = self.buffer.next_boundary(pos);
+        }
+        pos
+    }
+
+    /// Normalize line endings, and fold newlines into spaces for single-line editors
+    fn normalize<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut text = Cow::Borrowed(text);
+        if text.contains('\r') {
+            text = Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"));
+        }
+        if !self.multiline && text.contains('\n') {
+            text = Cow::Owned(text.replace('\n', " "));
+        }
+        text
+    }
+}
+
+/// How an editor's caret and selection are measured and drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextEditStyle {
+    /// Font size in logical pixels (match the text content)
+    pub font_size: f32,
+    pub line_height_multiplier: f32,
+    pub font_weight: FontWeight,
+    pub font_style: FontStyle,
+    /// Font family (match the text content); `None` for the default family
+    pub font_family: Option<&'static str>,
+    /// Distance between tab stops, in widths of a space (match the text content)
+    pub tab_width: u16,
+    /// Width of the caret in logical pixels
+    pub caret_width: f32,
+    pub caret_color: Color,
+    pub selection_color: Color,
+    /// Wrap lines at word boundaries to fit this width in logical pixels
+    /// (`None` keeps lines unwrapped)
+    ///
+    /// Show the text from [`TextEditStyle::display_text`] with `Wrap::None`,
+    /// so it breaks exactly where the caret and selection expect.
+    pub wrap_width: Option<f32>,
+}
+
+impl TextEditStyle {
+    /// Create a style resolved against the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_size: 16.0,
+            line_height_multiplier: 1.2,
+            font_weight: FontWeight::Normal,
+            font_style: FontStyle::Normal,
+            font_family: None,
+            tab_width: TextContent::DEFAULT_TAB_WIDTH,
+            caret_width: 2.0,
+            caret_color: theme.text,
+            selection_color: theme.primary.with_alpha(0.3),
+            wrap_width: None,
+        }
+    }
+
+    /// `text` as it is laid out: with a newline at every wrap point when
+    /// [`TextEditStyle::wrap_width`] is set, unchanged otherwise
+    pub fn display_text<'t>(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        text: &'t str,
+    ) -> Cow<'t, str> {
+        if self.wrap_width.is_none() {
+            return Cow::Borrowed(text);
+        }
+        let rows: Vec<&str> = self
+            .rows(measurer, text)
+            .into_iter()
+            .map(|row| &text[row.range])
+            .collect();
+        Cow::Owned(rows.join("\n"))
+    }
+
+    /// Byte offset of the caret closest to `point` in `text`
+    ///
+    /// `point` is in the space of [`LineRect`]: x from the start of its line and
+    /// y from the top of the text block. Lines are measured unwrapped, as in
+    /// [`TextEditor::decorations`]. Points past either end of a line land on
+    /// that end; the caret always sits on a char boundary.
+    pub fn caret_index_at(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        text: &str,
+        point: Point,
+    ) -> usize {
+        let line_height = self.font_size * self.line_height_multiplier;
+        let rows = self.rows(measurer, text);
+        let row = ((point.y / line_height).max(0.0) as usize).min(rows.len() - 1);
+        self.index_in_row(measurer, text, &rows[row], point.x)
+    }
+
+    /// The caret at byte offset `index` in `text`, as [`TextEditor::decorations`]
+    /// draws it
+    ///
+    /// An index inside a char gets the caret before that char.
+    pub fn caret_rect(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        text: &str,
+        index: usize,
+    ) -> LineRect {
+        let mut index = index.min(text.len());
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        let rows = self.rows(measurer, text);
+        let row = row_of(&rows, index);
+        let range = rows[row].range.clone();
+
+        let x = self.measure(measurer, &text[range.start..index]);
+        let line_width = self.measure(measurer, &text[range]);
+        self.caret_line_rect(row, x, line_width)
+    }
+
+    /// The char at byte offset `index` in `text`, spanning the height of its
+    /// line, or `None` past the end of the text or on a newline
+    pub fn char_rect(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        text: &str,
+        index: usize,
+    ) -> Option<LineRect> {
+        let ch = text.get(index..)?.chars().next().filter(|&ch| ch != '\n')?;
+        let rows = self.rows(measurer, text);
+        let row = row_of(&rows, index);
+        let start = rows[row].range.start;
+
+        let x = self.measure(measurer, &text[start..index]);
+        let end = self.measure(measurer, &text[start..index + ch.len_utf8()]);
+        Some(LineRect {
+            x,
+            y: row as f32 * self.font_size * self.line_height_multiplier,
+            width: end - x,
+            height: self.font_size * self.line_height_multiplier,
+            line_width: self.measure(measurer, &text[rows[row].range.clone()]),
+        })
+    }
+
+    /// Rows of `text` as laid out: its lines, broken further at word
+    /// boundaries when [`TextEditStyle::wrap_width`] is set
+    ///
+    /// Whitespace at a wrap point stays at the end of the row before it, like
+    /// in the text renderer. Words wider than the wrap width break between
+    /// chars.
+    fn rows(&self, measurer: &mut dyn ContentMeasurer, text: &str) -> Vec<Row> {
+        let mut rows = Vec::new();
+        let mut line_start = 0;
+        for line in text.split('\n') {
+            let line_end = line_start + line.len();
+            let mut start = line_start;
+            if let Some(width) = self.wrap_width {
+                while self.measure(measurer, &text[start..line_end]) > width {
+                    let end = self.wrap_point(measurer, text, start..line_end, width);
+                    if end == line_end {
+                        break;
+                    }
+                    rows.push(Row {
+                        range: start..end,
+                        soft: true,
+                    });
+                    start = end;
+                }
+            }
+            rows.push(Row {
+                range: start..line_end,
+                soft: false,
+            });
+            line_start = line_end + 1;
+        }
+        rows
+    }
+
+    /// Where the row starting at `line.start` wraps: after the last whitespace
+    /// that fits within `width`, or after the last char that fits if there is
+    /// none (but at least one char in)
+    fn wrap_point(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        text: &str,
+        line: Range<usize>,
+        width: f32,
+    ) -> usize {
+        let slice = &text[line.clone()];
+        let boundaries: Vec<usize> = slice
+            .char_indices()
+            .map(|(index, _)| index)
+            .skip(1)
+            .chain(std::iter::once(slice.len()))
+            .collect();
+        let fits =
+            boundaries.partition_point(|&end| self.measure(measurer, &slice[..end]) <= width);
+        let fit = boundaries[fits.saturating_sub(1)];
+
+        // Whitespace hangs past the edge instead of starting the next row
+        let hang = |pos: usize| {
+            pos + slice[pos..]
+                .find(|ch: char| !ch.is_whitespace())
+                .unwrap_or(slice.len() - pos)
+        };
+        let end = if slice[fit..].starts_with(char::is_whitespace) {
+            hang(fit)
+        } else {
+            match slice[..fit].rfind(char::is_whitespace) {
+                Some(space) => hang(space),
+                None => fit,
+            }
+        };
+        line.start + end
+    }
+
+    /// Byte offset of the caret in `row` closest to `x`
+    ///
+    /// Points past either end land on that end, except that the end of a
+    /// wrapped row is the start of the next one, so the caret stops before the
+    /// whitespace at the wrap point.
+    fn index_in_row(
+        &self,
+        measurer: &mut dyn ContentMeasurer,
+        text: &str,
+        row: &Row,
+        x: f32,
+    ) -> usize {
+        let line = &text[row.range.clone()];
+        // Prefix widths grow with the prefix, so the boundaries around `x`
+        // are found by bisection rather than measuring every one
+        let mut boundaries: Vec<usize> = line
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain(std::iter::once(line.len()))
+            .collect();
+        if row.soft && boundaries.len() > 1 {
+            boundaries.pop();
+        }
+        let after = boundaries.partition_point(|&end| self.measure(measurer, &line[..end]) < x);
+        let nearest = match after {
+            0 => 0,
+            after if after == boundaries.len() => after - 1,
+            after => {
+                let before_x = self.measure(measurer, &line[..boundaries[after - 1]]);
+                let after_x = self.measure(measurer, &line[..boundaries[after]]);
+                if x - before_x <= after_x - x {
+                    after - 1
+                } else {
+                    after
+                }
+            }
+        };
+        row.range.start + boundaries[nearest]
+    }
+
+    /// Caret rect at `x` on line `row`, centered vertically on the line
+    fn caret_line_rect(&self, row: usize, x: f32, line_width: f32) -> LineRect {
+        let line_height = self.font_size * self.line_height_multiplier;
+        LineRect {
+            x: (x - self.caret_width * 0.5).max(0.0),
+            y: row as f32 * line_height + (line_height - self.font_size) * 0.5,
+            width: self.caret_width,
+            height: self.font_size,
+            line_width,
+        }
+    }
+
+    /// Width of `text` as a single line in this style
+    fn measure(&self, measurer: &mut dyn ContentMeasurer, text: &str) -> f32 {
+        if text.is_empty() {
+            return 0.0;
+        }
+        measurer
+            .measure_text(MeasureTextRequest {
+                text,
+                spans: &[],
+                font_size: self.font_size,
+                h_align: HorizontalAlign::Left,
+                v_align: VerticalAlign::Top,
+                family: self.font_family,
+                max_width: None,
+                wrap: Wrap::None,
+                max_lines: None,
+                line_height_multiplier: self.line_height_multiplier,
+                font_weight: self.font_weight,
+                font_style: self.font_style,
+                font_features: &[],
+                font_width: FontWidth::Normal,
+                tab_width: self.tab_width,
+            })
+            .width
+    }
+}
+
+impl Default for TextEditStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// One row of laid-out text
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    /// Byte range of the row, without the newline ending it
+    range: Range<usize>,
+    /// Whether the row ends at a wrap point rather than a newline or the end
+    soft: bool,
+}
+
+/// Byte offset of the bracket matching the one at byte offset `index`
+///
+/// Pairs `()`, `[]` and `{}`, skipping nested pairs of the same kind. Returns
+/// `None` if there is no bracket at `index` or it is unbalanced. Brackets in
+/// strings and comments are counted like any other.
+pub fn matching_bracket(text: &str, index: usize) -> Option<usize> {
+    const PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+    let ch = text.get(index..)?.chars().next()?;
+    let mut depth = 0usize;
+    if let Some(&(open, close)) = PAIRS.iter().find(|(open, _)| *open == ch) {
+        for (offset, c) in text[index..].char_indices() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + offset);
+                }
+            }
+        }
+    } else if let Some(&(open, close)) = PAIRS.iter().find(|(_, close)| *close == ch) {
+        for (offset, c) in text[..=index].char_indices().rev() {
+            if c == close {
+                depth += 1;
+            } else if c == open {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(offset);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Index of the row the caret at `pos` is drawn on: a position at a wrap
+/// point belongs to the row after it
+fn row_of(rows: &[Row], pos: usize) -> usize {
+    rows.iter()
+        .position(|row| pos < row.range.end || (pos == row.range.end && !row.soft))
+        .unwrap_or(rows.len() - 1)
+}
+
+/// A rect on one line of text, in logical pixels
+///
+/// `x` is relative to the start of the line and `y` to the top of the text
+/// block, so the output can align it the same way as the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Width of the whole line, for horizontal alignment
+    pub line_width: f32,
+}
+
+/// Caret and selection of edited text, drawn with the text node via
+/// `Node::with_text_edit`
+///
+/// The selection is drawn below the text and the caret above it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEditDecorations {
+    pub caret: Option<LineRect>,
+    pub selection: Vec<LineRect>,
+    /// Height of the whole text block, for vertical alignment
+    pub block_height: f32,
+    pub caret_color: Color,
+    pub selection_color: Color,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measure::IntrinsicSize;
+
+    /// Measures every char as 10px wide
+    struct MonospaceMeasurer;
+
+    impl ContentMeasurer for MonospaceMeasurer {
+        fn measure_text(&mut self, request: MeasureTextRequest<'_>) -> IntrinsicSize {
+            IntrinsicSize::new(request.text.chars().count() as f32 * 10.0, 20.0)
+        }
+    }
+
+    #[test]
+    fn test_gap_buffer_edits_around_the_gap() {
+        let mut buffer = TextBuffer::from("hello world");
+        buffer.insert(5, ",");
+        buffer.insert(0, "¡");
+        assert_eq!(buffer.text(), "¡hello, world");
+
+        // A slice across the gap is stitched together
+        assert_eq!(buffer.slice(0..10), "¡hello, w");
+        assert_eq!(buffer.remove(9..14), "world");
+        assert_eq!(buffer.make_contiguous(), "¡hello, ");
+        assert_eq!(buffer.char_before(2), Some('¡'));
+        assert_eq!(buffer.next_boundary(0), 2);
+        assert!(!buffer.is_char_boundary(1));
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let mut editor = TextEditor::new().with_text("let foo.bar = 1;");
+        editor.move_caret(Motion::WordLeft, false);
+        assert_eq!(editor.selection().caret, 15);
+        editor.move_caret(Motion::WordLeft, false);
+        editor.move_caret(Motion::WordLeft, false);
+        assert_eq!(editor.selection().caret, 12);
+
+        editor.move_caret(Motion::LineStart, false);
+        editor.move_caret(Motion::WordRight, true);
+        assert_eq!(editor.selected_text(), "let ");
+    }
+
+    #[test]
+    fn test_vertical_motion_keeps_column() {
+        let mut editor = TextEditor::new().with_text("long line\nab\nanother");
+        editor.set_selection(Selection::collapsed(7));
+
+        editor.move_caret(Motion::Down, false);
+        assert_eq!(editor.caret_position(), (1, 2));
+        editor.move_caret(Motion::Down, false);
+        assert_eq!(editor.caret_position(), (2, 7));
+        editor.move_caret(Motion::Up, true);
+        assert_eq!(editor.selected_text(), "\nanother");
+    }
+
+    #[test]
+    fn test_undo_groups_typing_by_word() {
+        let mut editor = TextEditor::new();
+        for ch in "one two".chars() {
+            editor.insert(&ch.to_string());
+        }
+        editor.backspace(false);
+        editor.backspace(false);
+        assert_eq!(editor.text(), "one t");
+
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "one two");
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "one ");
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "");
+        assert!(!editor.undo());
+
+        assert!(editor.redo());
+        assert_eq!(editor.text(), "one ");
+        assert_eq!(editor.selection(), Selection::collapsed(4));
+    }
+
+    #[test]
+    fn test_single_line_submits_and_folds_newlines() {
+        let mut editor = TextEditor::new().with_multiline(false);
+        editor.insert("a\r\nb");
+        assert_eq!(editor.text(), "a b");
+
+        let mut input = InputState::new();
+        input.press_key(Key::Named(NamedKey::Enter), false, false);
+        let response = editor.handle_input(&input);
+        assert!(response.submitted && !response.changed);
+    }
+
+    #[test]
+    fn test_decorations_span_selected_lines() {
+        let mut editor = TextEditor::new().with_text("abc\nde\nf");
+        editor.set_selection(Selection::new(1, 5));
+        let style = TextEditStyle {
+            font_size: 20.0,
+            line_height_multiplier: 1.0,
+            ..TextEditStyle::default()
+        };
+
+        let decorations = editor.decorations(&mut MonospaceMeasurer, &style, true);
+        assert_eq!(decorations.block_height, 60.0);
+        assert_eq!(decorations.selection.len(), 2);
+        // The first line is selected past its newline
+        assert_eq!(decorations.selection[0].x, 10.0);
+        assert_eq!(decorations.selection[0].width, 26.0);
+        assert_eq!(decorations.selection[1].width, 10.0);
+
+        let caret = decorations.caret.unwrap();
+        assert_eq!((caret.x, caret.y, caret.line_width), (9.0, 20.0, 20.0));
+    }
+
+    #[test]
+    fn test_caret_hit_testing_round_trips() {
+        let style = TextEditStyle {
+            font_size: 20.0,
+            line_height_multiplier: 1.0,
+            ..TextEditStyle::default()
+        };
+        let text = "abc\nдe";
+        let at =
+            |x: f32, y: f32| style.caret_index_at(&mut MonospaceMeasurer, text, Point { x, y });
+
+        // Nearest boundary on the line under the point, clamped to its ends
+        assert_eq!(at(14.0, 5.0), 1);
+        assert_eq!(at(16.0, 5.0), 2);
+        assert_eq!(at(-5.0, 5.0), 0);
+        assert_eq!(at(100.0, 5.0), 3);
+        // Second line; "д" is two bytes wide
+        assert_eq!(at(11.0, 25.0), 6);
+        assert_eq!(at(100.0, 500.0), text.len());
+
+        let caret = style.caret_rect(&mut MonospaceMeasurer, text, 6);
+        assert_eq!((caret.x, caret.y, caret.line_width), (9.0, 20.0, 20.0));
+        // Inside "д" the caret goes before it
+        assert_eq!(style.caret_rect(&mut MonospaceMeasurer, text, 5).x, 0.0);
+
+        let editor = TextEditor::new().with_text(text);
+        assert_eq!(
+            editor.caret_index_at(&mut MonospaceMeasurer, &style, Point { x: 21.0, y: 0.0 }),
+            2
+        );
+    }
+
+    #[test]
+    fn test_wrapped_rows_break_at_words() {
+        let style = TextEditStyle {
+            font_size: 20.0,
+            line_height_multiplier: 1.0,
+            wrap_width: Some(50.0),
+            ..TextEditStyle::default()
+        };
+        let measurer = &mut MonospaceMeasurer;
+        let text = "hello world foo\nabcdefghij";
+
+        // Spaces hang at the end of the row, long words break between chars
+        assert_eq!(
+            style.display_text(measurer, text),
+            "hello \nworld \nfoo\nabcde\nfghij"
+        );
+        // The wrap point belongs to the next row, and clicks past the end of
+        // a wrapped row stop before its trailing space
+        let caret = style.caret_rect(measurer, text, 6);
+        assert_eq!((caret.x, caret.y), (0.0, 20.0));
+        assert_eq!(
+            style.caret_index_at(measurer, text, Point { x: 100.0, y: 5.0 }),
+            5
+        );
+
+        let mut editor = TextEditor::new().with_text(text);
+        editor.set_selection(Selection::new(3, 8));
+        let decorations = editor.decorations(measurer, &style, false);
+        assert_eq!(decorations.block_height, 100.0);
+        let widths: Vec<f32> = decorations
+            .selection
+            .iter()
+            .map(|rect| rect.width)
+            .collect();
+        assert_eq!(widths, [30.0, 20.0]);
+
+        // Row motions keep their horizontal position across wrapped rows
+        editor.set_selection(Selection::collapsed(2));
+        editor.move_caret_row(measurer, &style, true, false);
+        assert_eq!(editor.selection().caret, 8);
+        editor.move_caret_row(measurer, &style, true, false);
+        assert_eq!(editor.selection().caret, 14);
+        editor.move_caret_row(measurer, &style, true, true);
+        assert_eq!(editor.selected_text(), "o\nab");
+    }
+
+    #[test]
+    fn test_char_rects_follow_wrapped_rows() {
+        let style = TextEditStyle {
+            font_size: 20.0,
+            line_height_multiplier: 1.0,
+            wrap_width: Some(50.0),
+            ..TextEditStyle::default()
+        };
+        let measurer = &mut MonospaceMeasurer;
+        let text = "hello world
+дx";
+
+        let rect = style.char_rect(measurer, text, 7).unwrap();
+        assert_eq!(
+            (rect.x, rect.y, rect.width, rect.height),
+            (10.0, 20.0, 10.0, 20.0)
+        );
+        let rect = style.char_rect(measurer, text, 14).unwrap();
+        assert_eq!((rect.x, rect.y), (10.0, 40.0));
+        // Newlines, the end of the text and positions inside a char have none
+        assert!(style.char_rect(measurer, text, 11).is_none());
+        assert!(style.char_rect(measurer, text, text.len()).is_none());
+        assert!(style.char_rect(measurer, text, 13).is_none());
+    }
+
+    #[test]
+    fn test_matching_brackets_skip_nested_pairs() {
+        let text = "f(a[0], (b)) { x }";
+        assert_eq!(matching_bracket(text, 1), Some(11));
+        assert_eq!(matching_bracket(text, 11), Some(1));
+        assert_eq!(matching_bracket(text, 3), Some(5));
+        assert_eq!(matching_bracket(text, 13), Some(17));
+        assert_eq!(matching_bracket(text, 0), None);
+        assert_eq!(matching_bracket("(()", 0), None);
+        assert_eq!(matching_bracket("())", 2), None);
+    }
+}
@@ -0,0 +1,240 @@
+//! Theme with semantic color tokens
+//!
+//! Widgets resolve their default styles against the theme stored in the
+//! `UiContext` at build time, so switching palettes is a single
+//! `ctx.set_theme(..)` call instead of touching every widget style.
+
+use crate::color::Color;
+use crate::palette::{
+    CatppuccinFrappe, CatppuccinLatte, CatppuccinMacchiato, CatppuccinMocha, Palette,
+};
+
+/// Semantic color token, resolved to a concrete color with [`Theme::color`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThemeColor {
+    Surface,
+    SurfaceHover,
+    SurfaceActive,
+    Overlay,
+    Field,
+    FieldHover,
+    FieldActive,
+    Border,
+    Primary,
+    Secondary,
+    OnPrimary,
+    Text,
+    TextMuted,
+    TextDisabled,
+    Danger,
+    Warning,
+    Success,
+}
+
+/// Semantic color palette shared by all widgets
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Theme {
+    /// Background of buttons, headers and rows
+    pub surface: Color,
+    /// Surface under the cursor
+    pub surface_hover: Color,
+    /// Surface while pressed
+    pub surface_active: Color,
+    /// Background of floating content such as peeks and popups
+    pub overlay: Color,
+    /// Background of inputs, tracks and other recessed controls
+    pub field: Color,
+    /// Field under the cursor
+    pub field_hover: Color,
+    /// Field while pressed or dragged
+    pub field_active: Color,
+    /// Outlines and separators
+    pub border: Color,
+    /// Accent for focus, selection and "on" states
+    pub primary: Color,
+    /// Secondary accent, e.g. for hover highlights on accented controls
+    pub secondary: Color,
+    /// Text and icons drawn on top of `primary` (or other accent fills)
+    pub on_primary: Color,
+    /// Regular text
+    pub text: Color,
+    /// Placeholders and secondary text
+    pub text_muted: Color,
+    /// Text of disabled widgets
+    pub text_disabled: Color,
+    /// Destructive actions and errors
+    pub danger: Color,
+    /// Warnings
+    pub warning: Color,
+    /// Confirmations and success states
+    pub success: Color,
+}
+
+impl Theme {
+    /// Build a theme from a palette's colors
+    pub fn from_palette(palette: &dyn Palette) -> Self {
+        Theme {
+            surface: palette.base(),
+            surface_hover: palette.mantle(),
+            surface_active: palette.crust(),
+            overlay: palette.mantle(),
+            field: palette.surface0(),
+            field_hover: palette.surface1(),
+            field_active: palette.surface2(),
+            border: palette.surface0(),
+            primary: palette.accent(),
+            secondary: palette.accent_secondary(),
+            on_primary: palette.crust(),
+            text: palette.text(),
+            text_muted: palette.subtext0(),
+            text_disabled: palette.subtext1(),
+            danger: palette.red(),
+            warning: palette.yellow(),
+            success: palette.green(),
+        }
+    }
+
+    /// Catppuccin Mocha (the default)
+    pub fn mocha() -> Self {
+        Self::from_palette(&CatppuccinMocha)
+    }
+
+    /// Catppuccin Macchiato
+    pub fn macchiato() -> Self {
+        Self::from_palette(&CatppuccinMacchiato)
+    }
+
+    /// Catppuccin Frappé
+    pub fn frappe() -> Self {
+        Self::from_palette(&CatppuccinFrappe)
+    }
+
+    /// Catppuccin Latte
+    pub fn latte() -> Self {
+        Self::from_palette(&CatppuccinLatte)
+    }
+
+    /// Resolve a semantic token to its color in this theme
+    pub fn color(&self, token: ThemeColor) -> Color {
+        match token {
+            ThemeColor::Surface => self.surface,
+            ThemeColor::SurfaceHover => self.surface_hover,
+            ThemeColor::SurfaceActive => self.surface_active,
+            ThemeColor::Overlay => self.overlay,
+            ThemeColor::Field => self.field,
+            ThemeColor::FieldHover => self.field_hover,
+            ThemeColor::FieldActive => self.field_active,
+            ThemeColor::Border => self.border,
+            ThemeColor::Primary => self.primary,
+            ThemeColor::Secondary => self.secondary,
+            ThemeColor::OnPrimary => self.on_primary,
+            ThemeColor::Text => self.text,
+            ThemeColor::TextMuted => self.text_muted,
+            ThemeColor::TextDisabled => self.text_disabled,
+            ThemeColor::Danger => self.danger,
+            ThemeColor::Warning => self.warning,
+            ThemeColor::Success => self.success,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::mocha()
+    }
+}
+
+/// Light or dark appearance, e.g. the operating system's color scheme
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorScheme {
+    Light,
+    #[default]
+    Dark,
+}
+
+/// Light and dark variants of a theme
+///
+/// Pick the variant for the current color scheme with [`ThemePair::get`] and
+/// pass it to `ctx.set_theme(..)`. Widgets resolve their styles against the
+/// new theme on the next build, and their transitions animate the change.
+/// Backends can follow the OS scheme automatically (see `AutoTheme` in
+/// `astra-gui-wgpu`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThemePair {
+    pub light: Theme,
+    pub dark: Theme,
+}
+
+impl ThemePair {
+    pub fn new(light: Theme, dark: Theme) -> Self {
+        Self { light, dark }
+    }
+
+    /// The variant for a color scheme
+    pub fn get(&self, scheme: ColorScheme) -> Theme {
+        match scheme {
+            ColorScheme::Light => self.light,
+            ColorScheme::Dark => self.dark,
+        }
+    }
+}
+
+impl Default for ThemePair {
+    /// Catppuccin Latte and Mocha
+    fn default() -> Self {
+        Self::new(Theme::latte(), Theme::mocha())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::catppuccin;
+    use crate::palette::builtin_palettes;
+
+    #[test]
+    fn test_default_theme_is_mocha() {
+        assert_eq!(Theme::default(), Theme::mocha());
+        assert_eq!(Theme::default().surface, catppuccin::mocha::BASE);
+    }
+
+    #[test]
+    fn test_color_resolves_tokens() {
+        let theme = Theme::latte();
+        assert_eq!(theme.color(ThemeColor::Text), catppuccin::latte::TEXT);
+        assert_eq!(theme.color(ThemeColor::Primary), theme.primary);
+        assert_eq!(theme.color(ThemeColor::Danger), catppuccin::latte::RED);
+    }
+
+    #[test]
+    fn test_theme_pair_picks_variant_for_scheme() {
+        let pair = ThemePair::default();
+        assert_eq!(pair.get(ColorScheme::Light), Theme::latte());
+        assert_eq!(pair.get(ColorScheme::Dark), Theme::mocha());
+        assert_eq!(pair.get(ColorScheme::default()), Theme::default());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_theme_pair_serde_round_trip() {
+        let pair = ThemePair::default();
+        let json = serde_json::to_string(&pair).unwrap();
+        assert_eq!(serde_json::from_str::<ThemePair>(&json).unwrap(), pair);
+    }
+
+    #[test]
+    fn test_builtin_palettes_have_readable_text() {
+        for palette in builtin_palettes() {
+            let theme = Theme::from_palette(*palette);
+            assert!(
+                theme.text.contrast_ratio(&theme.surface) >= 4.5,
+                "{} text is hard to read",
+                palette.name()
+            );
+        }
+    }
+}
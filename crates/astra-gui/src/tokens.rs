@@ -0,0 +1,350 @@
+//! Design-token variables
+//!
+//! Nodes bind style properties to named tokens with `Node::with_var`, e.g.
+//! `.with_var(StyleProperty::FillColor, var("accent"))`. The values live in a
+//! `TokenTable` on the `UiContext` and are looked up every frame in
+//! `UiContext::end_frame`, after stylesheets and before interaction states and
+//! transitions. Overriding a token at runtime (`ctx.set_token("accent", ..)`)
+//! therefore changes the target style of every dependent node, which animates
+//! with the node's transition like any other style change.
+//!
+//! Bindings take precedence over inline and stylesheet values for the same
+//! property. Unknown tokens and tokens of the wrong kind leave the property
+//! untouched.
+//!
+//! Layout properties (padding, gap) must be known before layout, so they are
+//! resolved separately in `UiContext::inject_dimension_overrides` and are not
+//! animated.
+
+use crate::color::Color;
+use crate::layout::{Size, Spacing};
+use crate::node::Node;
+use crate::primitives::Stroke;
+use crate::style::Style;
+use std::collections::HashMap;
+
+/// Value stored for a design token
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenValue {
+    Color(Color),
+    Number(f32),
+    Size(Size),
+}
+
+impl From<Color> for TokenValue {
+    fn from(color: Color) -> Self {
+        TokenValue::Color(color)
+    }
+}
+
+impl From<f32> for TokenValue {
+    fn from(value: f32) -> Self {
+        TokenValue::Number(value)
+    }
+}
+
+impl From<Size> for TokenValue {
+    fn from(size: Size) -> Self {
+        TokenValue::Size(size)
+    }
+}
+
+impl TokenValue {
+    fn as_color(&self) -> Option<Color> {
+        match self {
+            TokenValue::Color(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    fn as_number(&self) -> Option<f32> {
+        match self {
+            TokenValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Sizes accept plain numbers as logical pixels
+    fn as_size(&self) -> Option<Size> {
+        match self {
+            TokenValue::Size(size) => Some(*size),
+            TokenValue::Number(value) => Some(Size::Logical(*value)),
+            TokenValue::Color(_) => None,
+        }
+    }
+}
+
+/// Reference to a named token, created with [`var`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenRef(String);
+
+impl TokenRef {
+    /// Get the token name
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Reference a design token by name, e.g. `var("accent")`
+pub fn var(name: impl Into<String>) -> TokenRef {
+    TokenRef(name.into())
+}
+
+/// Style property that can be bound to a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StyleProperty {
+    /// `Style::fill_color` (color token)
+    FillColor,
+    /// `Style::text_color` (color token)
+    TextColor,
+    /// `Style::cursor_color` (color token)
+    CursorColor,
+    /// Stroke color (color token)
+    StrokeColor,
+    /// Stroke width (size or number token)
+    StrokeWidth,
+    /// `Style::opacity` (number token)
+    Opacity,
+    /// `Style::rotation` in radians (number token)
+    Rotation,
+    /// `Style::translation_x` (size or number token)
+    TranslationX,
+    /// `Style::translation_y` (size or number token)
+    TranslationY,
+    /// Padding on all sides (size or number token, layout)
+    Padding,
+    /// Gap between children (size or number token, layout)
+    Gap,
+}
+
+impl StyleProperty {
+    fn is_layout(&self) -> bool {
+        matches!(self, StyleProperty::Padding | StyleProperty::Gap)
+    }
+
+    /// Write a token value into a style, returning whether the value applied
+    fn apply(&self, value: &TokenValue, style: &mut Style) -> bool {
+        match self {
+            StyleProperty::FillColor => value.as_color().map(|c| style.fill_color = Some(c)),
+            StyleProperty::TextColor => value.as_color().map(|c| style.text_color = Some(c)),
+            StyleProperty::CursorColor => value.as_color().map(|c| style.cursor_color = Some(c)),
+            StyleProperty::StrokeColor => value.as_color().map(|c| {
+                let stroke = style
+                    .stroke
+                    .get_or_insert(Stroke::new(Size::lpx(1.0), Color::transparent()));
+                stroke.color = c;
+            }),
+            StyleProperty::StrokeWidth => value.as_size().map(|w| {
+                let stroke = style
+                    .stroke
+                    .get_or_insert(Stroke::new(Size::lpx(1.0), Color::transparent()));
+                stroke.width = w;
+            }),
+            StyleProperty::Opacity => value.as_number().map(|v| style.opacity = Some(v)),
+            StyleProperty::Rotation => value.as_number().map(|v| style.rotation = Some(v)),
+            StyleProperty::TranslationX => value.as_size().map(|s| style.translation_x = Some(s)),
+            StyleProperty::TranslationY => value.as_size().map(|s| style.translation_y = Some(s)),
+            StyleProperty::Padding | StyleProperty::Gap => None,
+        }
+        .is_some()
+    }
+}
+
+/// Interaction state whose style a token binding targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VarState {
+    Base,
+    Hover,
+    Active,
+    Focused,
+    Selected,
+    Disabled,
+}
+
+/// A style property bound to a token on a node
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarBinding {
+    pub state: VarState,
+    pub property: StyleProperty,
+    pub token: TokenRef,
+}
+
+/// Named design-token values
+///
+/// # Example
+///
+/// ```ignore
+/// ctx.set_tokens(
+///     TokenTable::new()
+///         .with("accent", mocha::LAVENDER)
+///         .with("spacing-m", Size::lpx(12.0)),
+/// );
+///
+/// Node::new()
+///     .with_id(NodeId::new("card"))
+///     .with_var(StyleProperty::FillColor, var("accent"))
+///     .with_var(StyleProperty::Padding, var("spacing-m"))
+///     .with_transition(Transition::standard());
+///
+/// // Later: every node bound to "accent" animates to the new color
+/// ctx.set_token("accent", mocha::PEACH);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenTable {
+    values: HashMap<String, TokenValue>,
+}
+
+impl TokenTable {
+    /// Create an empty token table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a token value
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<TokenValue>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Set (or override) a token value
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<TokenValue>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Remove a token, returning its value if present
+    pub fn remove(&mut self, name: &str) -> Option<TokenValue> {
+        self.values.remove(name)
+    }
+
+    /// Get a token value
+    pub fn get(&self, name: &str) -> Option<&TokenValue> {
+        self.values.get(name)
+    }
+
+    /// Get a color token
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.get(name).and_then(TokenValue::as_color)
+    }
+
+    /// Get a number token
+    pub fn number(&self, name: &str) -> Option<f32> {
+        self.get(name).and_then(TokenValue::as_number)
+    }
+
+    /// Get a size token (numbers are treated as logical pixels)
+    pub fn size(&self, name: &str) -> Option<Size> {
+        self.get(name).and_then(TokenValue::as_size)
+    }
+
+    /// Check whether the table has no tokens
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Resolve visual token bindings for a node tree
+    pub(crate) fn apply(&self, node: &mut Node) {
+        if !node.vars().is_empty() {
+            let bindings = node.vars().to_vec();
+            for state in [
+                VarState::Base,
+                VarState::Hover,
+                VarState::Active,
+                VarState::Focused,
+                VarState::Selected,
+                VarState::Disabled,
+            ] {
+                if !bindings.iter().any(|b| b.state == state) {
+                    continue;
+                }
+                let mut style = node.state_style(state).cloned().unwrap_or_default();
+                let mut changed = false;
+                for binding in bindings.iter().filter(|b| b.state == state) {
+                    if let Some(value) = self.get(binding.token.name()) {
+                        changed |= binding.property.apply(value, &mut style);
+                    }
+                }
+                if changed {
+                    node.set_state_style(state, style);
+                }
+            }
+        }
+
+        for child in node.children_mut() {
+            self.apply(child);
+        }
+    }
+
+    /// Resolve layout token bindings for a node tree (before layout)
+    pub(crate) fn apply_layout(&self, node: &mut Node) {
+        let bindings: Vec<_> = node
+            .vars()
+            .iter()
+            .filter(|b| b.state == VarState::Base && b.property.is_layout())
+            .filter_map(|b| Some((b.property, self.size(b.token.name())?)))
+            .collect();
+        for (property, size) in bindings {
+            match property {
+                StyleProperty::Padding => node.set_padding(Spacing::all(size)),
+                StyleProperty::Gap => node.set_gap(size),
+                _ => {}
+            }
+        }
+
+        for child in node.children_mut() {
+            self.apply_layout(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeId;
+
+    #[test]
+    fn test_bindings_resolve_from_table() {
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let blue = Color::rgb(0.0, 0.0, 1.0);
+        let tokens = TokenTable::new()
+            .with("accent", red)
+            .with("hover", blue)
+            .with("faded", 0.5)
+            .with("spacing-m", Size::lpx(12.0));
+
+        let mut node = Node::new()
+            .with_id(NodeId::new("n"))
+            .with_style(Style {
+                opacity: Some(1.0),
+                text_color: Some(blue),
+                ..Default::default()
+            })
+            .with_var(StyleProperty::FillColor, var("accent"))
+            .with_var(StyleProperty::Opacity, var("faded"))
+            .with_var(StyleProperty::TextColor, var("missing"))
+            .with_var(StyleProperty::Padding, var("spacing-m"))
+            .with_hover_var(StyleProperty::FillColor, var("hover"));
+        tokens.apply(&mut node);
+        tokens.apply_layout(&mut node);
+
+        let base = node.base_style().unwrap();
+        assert_eq!(base.fill_color, Some(red));
+        assert_eq!(base.opacity, Some(0.5));
+        assert_eq!(base.text_color, Some(blue));
+        assert_eq!(node.hover_style().unwrap().fill_color, Some(blue));
+        assert_eq!(node.padding(), Spacing::all(Size::lpx(12.0)));
+        assert!(node.shape().is_some());
+    }
+
+    #[test]
+    fn test_mismatched_token_kind_is_ignored() {
+        let tokens = TokenTable::new().with("accent", Color::rgb(1.0, 0.0, 0.0));
+        let mut node = Node::new()
+            .with_var(StyleProperty::Opacity, var("accent"))
+            .with_var(StyleProperty::Gap, var("accent"));
+        tokens.apply(&mut node);
+        tokens.apply_layout(&mut node);
+
+        assert!(node.base_style().is_none());
+        assert_eq!(node.gap(), Size::Logical(0.0));
+    }
+}
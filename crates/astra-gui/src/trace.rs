@@ -0,0 +1,405 @@
+//! Frame trace export for offline analysis
+//!
+//! A `FrameTrace` snapshots a laid-out node tree together with the frame's
+//! phase timings and cache counters, and serializes it either as a Chrome
+//! trace (loadable in `chrome://tracing`, Perfetto or speedscope) or as a
+//! plain JSON tree. This complements the live profiling output of the
+//! examples: traces can be written from CI runs and compared offline.
+//!
+//! In the Chrome trace, phases are laid out back to back on a "Frame phases"
+//! track. The node tree is emitted on a separate "Layout tree" track as
+//! nested spans whose length is the subtree's node count (one microsecond per
+//! node), so the flame chart shows tree structure and subtree size rather
+//! than time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! ui.compute_layout_with_measurer(window_rect, &mut engine);
+//! let trace = FrameTrace::capture(&ui)
+//!     .with_phase("build", build_ms)
+//!     .with_phase("layout", layout_ms)
+//!     .with_cache_stats("text measurement", engine.cache_stats().unwrap_or_default());
+//! trace.write_chrome_trace("frame.trace.json")?;
+//! ```
+
+use crate::measure::CacheStats;
+use crate::node::Node;
+use crate::primitives::Rect;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+/// A named phase of a frame and how long it took
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramePhase {
+    pub name: String,
+    pub duration_ms: f32,
+}
+
+/// Snapshot of a single node in a captured tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceNode {
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    /// Computed rect, `None` if the node was never laid out
+    pub rect: Option<Rect>,
+    pub has_content: bool,
+    pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    fn capture(node: &Node) -> Self {
+        Self {
+            id: node.id().map(|id| id.as_str().to_string()),
+            classes: node.classes().to_vec(),
+            rect: node.computed_layout().map(|layout| layout.rect),
+            has_content: node.content().is_some(),
+            children: node.children().iter().map(TraceNode::capture).collect(),
+        }
+    }
+
+    /// Number of nodes in this subtree, including this node
+    pub fn subtree_len(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(TraceNode::subtree_len)
+            .sum::<usize>()
+    }
+
+    fn name(&self) -> &str {
+        self.id.as_deref().unwrap_or("<anonymous>")
+    }
+}
+
+/// Node tree, timings and cache counters of a single frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameTrace {
+    root: Option<TraceNode>,
+    phases: Vec<FramePhase>,
+    caches: Vec<(String, CacheStats)>,
+}
+
+impl FrameTrace {
+    /// Create an empty trace
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture a laid-out node tree
+    ///
+    /// Call after `compute_layout` so computed rects are available.
+    pub fn capture(root: &Node) -> Self {
+        Self {
+            root: Some(TraceNode::capture(root)),
+            ..Default::default()
+        }
+    }
+
+    /// Append a frame phase (phases are recorded in order)
+    pub fn with_phase(mut self, name: impl Into<String>, duration_ms: f32) -> Self {
+        self.add_phase(name, duration_ms);
+        self
+    }
+
+    /// Append a frame phase (phases are recorded in order)
+    pub fn add_phase(&mut self, name: impl Into<String>, duration_ms: f32) {
+        self.phases.push(FramePhase {
+            name: name.into(),
+            duration_ms,
+        });
+    }
+
+    /// Record the counters of a named cache
+    pub fn with_cache_stats(mut self, name: impl Into<String>, stats: CacheStats) -> Self {
+        self.add_cache_stats(name, stats);
+        self
+    }
+
+    /// Record the counters of a named cache
+    pub fn add_cache_stats(&mut self, name: impl Into<String>, stats: CacheStats) {
+        self.caches.push((name.into(), stats));
+    }
+
+    /// Get the captured root node
+    pub fn root(&self) -> Option<&TraceNode> {
+        self.root.as_ref()
+    }
+
+    /// Get the recorded phases
+    pub fn phases(&self) -> &[FramePhase] {
+        &self.phases
+    }
+
+    /// Get the recorded cache counters
+    pub fn caches(&self) -> &[(String, CacheStats)] {
+        &self.caches
+    }
+
+    /// Number of captured nodes
+    pub fn node_count(&self) -> usize {
+        self.root.as_ref().map_or(0, TraceNode::subtree_len)
+    }
+
+    /// Serialize as Chrome trace event JSON
+    pub fn to_chrome_trace(&self) -> String {
+        let mut events = vec![
+            r#"{"name":"thread_name","ph":"M","pid":1,"tid":1,"args":{"name":"Frame phases"}}"#
+                .to_string(),
+            r#"{"name":"thread_name","ph":"M","pid":1,"tid":2,"args":{"name":"Layout tree"}}"#
+                .to_string(),
+        ];
+
+        let mut ts = 0.0;
+        for phase in &self.phases {
+            let dur = phase.duration_ms * 1000.0;
+            events.push(format!(
+                r#"{{"name":{},"cat":"phase","ph":"X","pid":1,"tid":1,"ts":{},"dur":{}}}"#,
+                json_string(&phase.name),
+                json_number(ts),
+                json_number(dur)
+            ));
+            ts += dur;
+        }
+
+        for (name, stats) in &self.caches {
+            events.push(format!(
+                r#"{{"name":{},"cat":"cache","ph":"C","pid":1,"tid":1,"ts":0,"args":{{"hits":{},"misses":{}}}}}"#,
+                json_string(name),
+                stats.hits,
+                stats.misses
+            ));
+        }
+
+        if let Some(root) = &self.root {
+            push_node_events(root, 0, &mut events);
+        }
+
+        format!(
+            r#"{{"displayTimeUnit":"ms","traceEvents":[{}]}}"#,
+            events.join(",")
+        )
+    }
+
+    /// Serialize as a plain JSON document with the nested node tree
+    pub fn to_json(&self) -> String {
+        let phases: Vec<String> = self
+            .phases
+            .iter()
+            .map(|phase| {
+                format!(
+                    r#"{{"name":{},"duration_ms":{}}}"#,
+                    json_string(&phase.name),
+                    json_number(phase.duration_ms)
+                )
+            })
+            .collect();
+
+        let caches: Vec<String> = self
+            .caches
+            .iter()
+            .map(|(name, stats)| {
+                format!(
+                    r#"{{"name":{},"hits":{},"misses":{},"hit_rate":{}}}"#,
+                    json_string(name),
+                    stats.hits,
+                    stats.misses,
+                    json_number(stats.hit_rate())
+                )
+            })
+            .collect();
+
+        let mut root = String::new();
+        match &self.root {
+            Some(node) => write_node_json(node, &mut root),
+            None => root.push_str("null"),
+        }
+
+        format!(
+            r#"{{"phases":[{}],"caches":[{}],"node_count":{},"root":{}}}"#,
+            phases.join(","),
+            caches.join(","),
+            self.node_count(),
+            root
+        )
+    }
+
+    /// Write the Chrome trace JSON to a file
+    pub fn write_chrome_trace(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_chrome_trace())
+    }
+
+    /// Write the plain JSON tree to a file
+    pub fn write_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+fn push_node_events(node: &TraceNode, ts: usize, events: &mut Vec<String>) {
+    events.push(format!(
+        r#"{{"name":{},"cat":"node","ph":"X","pid":1,"tid":2,"ts":{},"dur":{},"args":{}}}"#,
+        json_string(node.name()),
+        ts,
+        node.subtree_len(),
+        node_args_json(node)
+    ));
+
+    let mut child_ts = ts + 1;
+    for child in &node.children {
+        push_node_events(child, child_ts, events);
+        child_ts += child.subtree_len();
+    }
+}
+
+fn write_node_json(node: &TraceNode, out: &mut String) {
+    let _ = write!(
+        out,
+        r#"{{"id":{},"classes":{},"rect":{},"has_content":{},"children":["#,
+        node.id.as_deref().map_or("null".to_string(), json_string),
+        json_string_array(&node.classes),
+        rect_json(node.rect),
+        node.has_content
+    );
+    for (i, child) in node.children.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_node_json(child, out);
+    }
+    out.push_str("]}");
+}
+
+fn node_args_json(node: &TraceNode) -> String {
+    format!(
+        r#"{{"classes":{},"rect":{},"has_content":{},"children":{}}}"#,
+        json_string_array(&node.classes),
+        rect_json(node.rect),
+        node.has_content,
+        node.children.len()
+    )
+}
+
+fn rect_json(rect: Option<Rect>) -> String {
+    match rect {
+        Some(rect) => format!(
+            r#"{{"x":{},"y":{},"width":{},"height":{}}}"#,
+            json_number(rect.min[0]),
+            json_number(rect.min[1]),
+            json_number(rect.width()),
+            json_number(rect.height())
+        ),
+        None => "null".to_string(),
+    }
+}
+
+fn json_string_array(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|value| json_string(value)).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// JSON has no representation for NaN or infinities
+fn json_number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "0".to_string()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NodeId, Size};
+
+    fn laid_out_tree() -> Node {
+        let mut root = Node::new()
+            .with_id(NodeId::new("root"))
+            .with_width(Size::lpx(100.0))
+            .with_height(Size::lpx(50.0))
+            .with_child(
+                Node::new()
+                    .with_id(NodeId::new("a \"quoted\" child"))
+                    .with_class("card")
+                    .with_width(Size::lpx(40.0))
+                    .with_height(Size::lpx(20.0))
+                    .with_child(Node::new().with_width(Size::lpx(10.0))),
+            )
+            .with_child(Node::new().with_width(Size::lpx(10.0)));
+        root.compute_layout(Rect::from_min_size([0.0, 0.0], [100.0, 50.0]));
+        root
+    }
+
+    #[test]
+    fn test_capture_records_tree_and_rects() {
+        let trace = FrameTrace::capture(&laid_out_tree());
+        let root = trace.root().unwrap();
+
+        assert_eq!(trace.node_count(), 4);
+        assert_eq!(root.id.as_deref(), Some("root"));
+        assert_eq!(root.rect.unwrap().width(), 100.0);
+        assert_eq!(root.children[0].classes, vec!["card".to_string()]);
+        assert_eq!(root.children[0].subtree_len(), 2);
+    }
+
+    #[test]
+    fn test_chrome_trace_nests_node_spans() {
+        let trace = FrameTrace::capture(&laid_out_tree())
+            .with_phase("build", 1.5)
+            .with_phase("layout", 0.5)
+            .with_cache_stats("text", CacheStats::new(3, 1));
+        let json = trace.to_chrome_trace();
+
+        assert!(json.starts_with(r#"{"displayTimeUnit":"ms","traceEvents":["#));
+        assert!(json.contains(
+            r#""name":"layout","cat":"phase","ph":"X","pid":1,"tid":1,"ts":1500,"dur":500"#
+        ));
+        assert!(json.contains(r#""args":{"hits":3,"misses":1}"#));
+        assert!(
+            json.contains(r#""name":"root","cat":"node","ph":"X","pid":1,"tid":2,"ts":0,"dur":4"#)
+        );
+        assert!(json.contains(
+            r#""name":"a \"quoted\" child","cat":"node","ph":"X","pid":1,"tid":2,"ts":1,"dur":2"#
+        ));
+        assert!(json.contains(
+            r#""name":"<anonymous>","cat":"node","ph":"X","pid":1,"tid":2,"ts":3,"dur":1"#
+        ));
+    }
+
+    #[test]
+    fn test_json_export() {
+        let trace =
+            FrameTrace::capture(&laid_out_tree()).with_cache_stats("text", CacheStats::new(3, 1));
+        let json = trace.to_json();
+
+        assert!(json.contains(r#""caches":[{"name":"text","hits":3,"misses":1,"hit_rate":0.75}]"#));
+        assert!(json.contains(r#""node_count":4"#));
+        assert!(json.contains(
+            r#""root":{"id":"root","classes":[],"rect":{"x":0,"y":0,"width":100,"height":50}"#
+        ));
+        assert_eq!(
+            FrameTrace::new().to_json(),
+            r#"{"phases":[],"caches":[],"node_count":0,"root":null}"#
+        );
+    }
+}
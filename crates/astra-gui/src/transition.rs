@@ -1,5 +1,9 @@
-use crate::color::Color;
-use crate::primitives::{CornerShape, Stroke, StrokeAlignment};
+use crate::color::{Color, ColorSpace};
+use crate::nine_slice::NineSliceImage;
+use crate::primitives::{
+    ConicGradient, CornerKind, CornerShape, Corners, LinearGradient, Outline, Shadow, Stroke,
+    StrokeAlignment,
+};
 use crate::style::Style;
 
 /// Easing function type: takes progress (0.0 to 1.0) and returns eased value (0.0 to 1.0)
@@ -55,7 +59,48 @@ pub fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
 }
 
-/// Linearly interpolate between two colors
+/// Interpolate between two colors in the given color space
+///
+/// Alpha is always interpolated linearly.
+pub fn lerp_color_in(a: Color, b: Color, t: f32, space: ColorSpace) -> Color {
+    let alpha = lerp_f32(a.a, b.a, t);
+    match space {
+        ColorSpace::LinearRgb => lerp_color(a, b, t),
+        ColorSpace::Oklab => {
+            let [l1, a1, b1] = a.to_oklab();
+            let [l2, a2, b2] = b.to_oklab();
+            Color::oklaba(
+                lerp_f32(l1, l2, t),
+                lerp_f32(a1, a2, t),
+                lerp_f32(b1, b2, t),
+                alpha,
+            )
+        }
+        ColorSpace::Oklch => {
+            // Below this chroma the hue is meaningless (grays), so take the
+            // other color's hue instead of swinging through unrelated hues
+            const ACHROMATIC: f32 = 1e-4;
+
+            let [l1, c1, mut h1] = a.to_oklch();
+            let [l2, c2, mut h2] = b.to_oklch();
+            if c1 < ACHROMATIC {
+                h1 = h2;
+            } else if c2 < ACHROMATIC {
+                h2 = h1;
+            }
+            // Take the shorter way around the hue circle
+            let delta = (h2 - h1 + 180.0).rem_euclid(360.0) - 180.0;
+            Color::oklcha(
+                lerp_f32(l1, l2, t),
+                lerp_f32(c1, c2, t),
+                h1 + delta * t,
+                alpha,
+            )
+        }
+    }
+}
+
+/// Linearly interpolate between two colors in linear RGB
 pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
     Color {
         r: lerp_f32(a.r, b.r, t),
@@ -115,6 +160,79 @@ pub fn lerp_stroke(a: Stroke, b: Stroke, t: f32) -> Stroke {
     }
 }
 
+/// Linearly interpolate between two outlines
+pub fn lerp_outline(a: Outline, b: Outline, t: f32) -> Outline {
+    Outline {
+        width: lerp_size(a.width, b.width, t),
+        color: lerp_color(a.color, b.color, t),
+        offset: lerp_size(a.offset, b.offset, t),
+    }
+}
+
+/// Interpolate between two shadows, blending colors in the given color space
+pub fn lerp_shadow(a: Shadow, b: Shadow, t: f32, space: ColorSpace) -> Shadow {
+    Shadow {
+        offset_x: lerp_size(a.offset_x, b.offset_x, t),
+        offset_y: lerp_size(a.offset_y, b.offset_y, t),
+        blur: lerp_size(a.blur, b.blur, t),
+        spread: lerp_size(a.spread, b.spread, t),
+        color: lerp_color_in(a.color, b.color, t, space),
+    }
+}
+
+/// Interpolate between two conic gradients, blending colors in the given color space
+///
+/// Gradients with the same number of stops interpolate stop by stop (so an
+/// animated progress ring sweeps smoothly). Otherwise, snaps to the target
+/// gradient at t >= 0.5.
+pub fn lerp_conic_gradient(
+    a: &ConicGradient,
+    b: &ConicGradient,
+    t: f32,
+    space: ColorSpace,
+) -> ConicGradient {
+    if a.stops.len() != b.stops.len() {
+        return if t < 0.5 { a.clone() } else { b.clone() };
+    }
+    ConicGradient {
+        center: [
+            lerp_f32(a.center[0], b.center[0], t),
+            lerp_f32(a.center[1], b.center[1], t),
+        ],
+        start_angle: lerp_f32(a.start_angle, b.start_angle, t),
+        stops: a
+            .stops
+            .iter()
+            .zip(&b.stops)
+            .map(|(&(p0, c0), &(p1, c1))| (lerp_f32(p0, p1, t), lerp_color_in(c0, c1, t, space)))
+            .collect(),
+    }
+}
+
+/// Interpolate between two linear gradients, blending colors in the given color space
+///
+/// Like [`lerp_conic_gradient`], stops interpolate one by one when both
+/// gradients have as many, and the gradient snaps at t >= 0.5 otherwise.
+pub fn lerp_linear_gradient(
+    a: &LinearGradient,
+    b: &LinearGradient,
+    t: f32,
+    space: ColorSpace,
+) -> LinearGradient {
+    if a.stops.len() != b.stops.len() {
+        return if t < 0.5 { a.clone() } else { b.clone() };
+    }
+    LinearGradient {
+        angle: lerp_f32(a.angle, b.angle, t),
+        stops: a
+            .stops
+            .iter()
+            .zip(&b.stops)
+            .map(|(&(p0, c0), &(p1, c1))| (lerp_f32(p0, p1, t), lerp_color_in(c0, c1, t, space)))
+            .collect(),
+    }
+}
+
 /// Linearly interpolate between two corner shapes
 ///
 /// Only interpolates if both shapes are the same variant with compatible parameters.
@@ -142,17 +260,84 @@ pub fn lerp_corner_shape(a: CornerShape, b: CornerShape, t: f32) -> CornerShape
             radius: lerp_size(r1, r2, t),
             smoothness: lerp_f32(s1, s2, t),
         },
+        // Per-corner shapes interpolate corner by corner
+        (a, b)
+            if matches!(a, CornerShape::PerCorner(_)) || matches!(b, CornerShape::PerCorner(_)) =>
+        {
+            let (a, b) = (a.corners(), b.corners());
+            CornerShape::PerCorner(Corners {
+                top_left: lerp_corner_kind(a.top_left, b.top_left, t),
+                top_right: lerp_corner_kind(a.top_right, b.top_right, t),
+                bottom_right: lerp_corner_kind(a.bottom_right, b.bottom_right, t),
+                bottom_left: lerp_corner_kind(a.bottom_left, b.bottom_left, t),
+            })
+        }
         // Different variants: snap at halfway point
         (_, b) if t >= 0.5 => b,
         (a, _) => a,
     }
 }
 
-/// Interpolate between two styles
+/// Zero in the same unit as `size`, so it interpolates with it
+fn zero_size_like(size: crate::layout::Size) -> crate::layout::Size {
+    use crate::layout::Size;
+    match size {
+        Size::Logical(_) => Size::Logical(0.0),
+        Size::Physical(_) => Size::Physical(0.0),
+        Size::Relative(_) => Size::Relative(0.0),
+        other => other,
+    }
+}
+
+/// Linearly interpolate between two single corners
+///
+/// Square corners interpolate with round corners as a zero radius. Other
+/// mismatched kinds snap to the target at t >= 0.5.
+pub fn lerp_corner_kind(a: CornerKind, b: CornerKind, t: f32) -> CornerKind {
+    match (a, b) {
+        (CornerKind::Square, CornerKind::Square) => CornerKind::Square,
+        (CornerKind::Round(r1), CornerKind::Round(r2)) => CornerKind::Round(lerp_size(r1, r2, t)),
+        (CornerKind::Square, CornerKind::Round(r)) => {
+            CornerKind::Round(lerp_size(zero_size_like(r), r, t))
+        }
+        (CornerKind::Round(r), CornerKind::Square) => {
+            CornerKind::Round(lerp_size(r, zero_size_like(r), t))
+        }
+        (CornerKind::Cut(d1), CornerKind::Cut(d2)) => CornerKind::Cut(lerp_size(d1, d2, t)),
+        (CornerKind::InverseRound(r1), CornerKind::InverseRound(r2)) => {
+            CornerKind::InverseRound(lerp_size(r1, r2, t))
+        }
+        (
+            CornerKind::Squircle {
+                radius: r1,
+                smoothness: s1,
+            },
+            CornerKind::Squircle {
+                radius: r2,
+                smoothness: s2,
+            },
+        ) => CornerKind::Squircle {
+            radius: lerp_size(r1, r2, t),
+            smoothness: lerp_f32(s1, s2, t),
+        },
+        (_, b) if t >= 0.5 => b,
+        (a, _) => a,
+    }
+}
+
+/// Interpolate between two styles, blending colors in linear RGB
 ///
 /// For each property, if both styles have a value, interpolate between them.
 /// Otherwise, use whichever value is present (or None if neither has a value).
 pub fn lerp_style(from: &Style, to: &Style, t: f32) -> Style {
+    lerp_style_in(from, to, t, ColorSpace::LinearRgb)
+}
+
+/// Interpolate between two styles, blending colors in the given color space
+///
+/// Used by [`Transition`], which picks the space with [`Transition::with_color_space`].
+pub fn lerp_style_in(from: &Style, to: &Style, t: f32, space: ColorSpace) -> Style {
+    let lerp_color = |a, b, t| lerp_color_in(a, b, t, space);
     Style {
         fill_color: match (from.fill_color, to.fill_color) {
             (Some(a), Some(b)) => Some(lerp_color(a, b, t)),
@@ -161,7 +346,57 @@ pub fn lerp_style(from: &Style, to: &Style, t: f32) -> Style {
             (None, None) => None,
         },
         stroke: match (from.stroke, to.stroke) {
-            (Some(a), Some(b)) => Some(lerp_stroke(a, b, t)),
+            (Some(a), Some(b)) => Some(Stroke {
+                color: lerp_color(a.color, b.color, t),
+                ..lerp_stroke(a, b, t)
+            }),
+            (None, Some(b)) => Some(b),
+            (Some(a), None) => Some(a),
+            (None, None) => None,
+        },
+        outline: match (from.outline, to.outline) {
+            (Some(a), Some(b)) => Some(Outline {
+                color: lerp_color(a.color, b.color, t),
+                ..lerp_outline(a, b, t)
+            }),
+            (None, Some(b)) => Some(b),
+            (Some(a), None) => Some(a),
+            (None, None) => None,
+        },
+        shadow: match (from.shadow, to.shadow) {
+            (Some(a), Some(b)) => Some(lerp_shadow(a, b, t, space)),
+            (None, Some(b)) => Some(b),
+            (Some(a), None) => Some(a),
+            (None, None) => None,
+        },
+        inset_shadow: match (from.inset_shadow, to.inset_shadow) {
+            (Some(a), Some(b)) => Some(lerp_shadow(a, b, t, space)),
+            (None, Some(b)) => Some(b),
+            (Some(a), None) => Some(a),
+            (None, None) => None,
+        },
+        gradient: match (&from.gradient, &to.gradient) {
+            (Some(a), Some(b)) => Some(lerp_conic_gradient(a, b, t, space)),
+            (None, Some(b)) => Some(b.clone()),
+            (Some(a), None) => Some(a.clone()),
+            (None, None) => None,
+        },
+        linear_gradient: match (&from.linear_gradient, &to.linear_gradient) {
+            (Some(a), Some(b)) => Some(lerp_linear_gradient(a, b, t, space)),
+            (None, Some(b)) => Some(b.clone()),
+            (Some(a), None) => Some(a.clone()),
+            (None, None) => None,
+        },
+        // Textures can't blend, so only the tint of the same texture does
+        nine_slice: match (from.nine_slice, to.nine_slice) {
+            (Some(a), Some(b)) if a.texture == b.texture => Some(NineSliceImage {
+                tint: lerp_color(a.tint, b.tint, t),
+                ..b
+            }),
+            (a, b) => b.or(a),
+        },
+        tint: match (from.tint, to.tint) {
+            (Some(a), Some(b)) => Some(lerp_color(a, b, t)),
             (None, Some(b)) => Some(b),
             (Some(a), None) => Some(a),
             (None, None) => None,
@@ -227,6 +462,8 @@ pub fn lerp_style(from: &Style, to: &Style, t: f32) -> Style {
             (Some(a), None) => Some(a),
             (None, None) => None,
         },
+        // Animations run on their own clock and switch over immediately
+        animations: to.animations.clone(),
     }
 }
 
@@ -240,50 +477,55 @@ pub struct Transition {
 
     /// Easing function to apply
     pub easing: EasingFn,
+
+    /// Color space that colors are blended in (default OKLab)
+    pub color_space: ColorSpace,
 }
 
 impl Transition {
     /// Create a new transition with custom duration and easing
     pub fn new(duration: f32, easing: EasingFn) -> Self {
-        Self { duration, easing }
+        Self {
+            duration,
+            easing,
+            color_space: ColorSpace::default(),
+        }
     }
 
     /// Instant transition (no animation, duration = 0)
     pub fn instant() -> Self {
-        Self {
-            duration: 0.0,
-            easing: linear,
-        }
+        Self::new(0.0, linear)
     }
 
     /// Quick transition (150ms, ease-out)
     ///
     /// Good for hover states and quick feedback
     pub fn quick() -> Self {
-        Self {
-            duration: 0.15,
-            easing: ease_out,
-        }
+        Self::new(0.15, ease_out)
     }
 
     /// Standard transition (250ms, ease-in-out)
     ///
     /// Good for most state changes
     pub fn standard() -> Self {
-        Self {
-            duration: 0.25,
-            easing: ease_in_out,
-        }
+        Self::new(0.25, ease_in_out)
     }
 
     /// Slow transition (400ms, ease-in-out)
     ///
     /// Good for emphasized state changes
     pub fn slow() -> Self {
-        Self {
-            duration: 0.4,
-            easing: ease_in_out,
-        }
+        Self::new(0.4, ease_in_out)
+    }
+
+    /// Set the color space that colors are blended in
+    ///
+    /// Defaults to [`ColorSpace::Oklab`], which avoids the gray, muddy
+    /// mid-colors of blending in RGB. Use [`ColorSpace::LinearRgb`] for the
+    /// previous behavior.
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
     }
 }
 
@@ -335,4 +577,64 @@ mod tests {
         assert_eq!(gray.g, 0.5);
         assert_eq!(gray.b, 0.5);
     }
+
+    #[test]
+    fn test_perceptual_color_lerp_keeps_mid_colors_vivid() {
+        let blue = Color::rgb(0.0, 0.0, 1.0);
+        let yellow = Color::rgb(1.0, 1.0, 0.0);
+        let [l1, ..] = blue.to_oklch();
+        let [l2, ..] = yellow.to_oklch();
+
+        // Linear RGB passes through gray
+        let rgb = lerp_color_in(blue, yellow, 0.5, ColorSpace::LinearRgb);
+        assert!(rgb.to_oklch()[1] < 0.01);
+
+        // OKLab is halfway in perceived lightness
+        let oklab = lerp_color_in(blue, yellow, 0.5, ColorSpace::Oklab);
+        assert!((oklab.to_oklch()[0] - (l1 + l2) / 2.0).abs() < 0.01);
+
+        // OKLCH keeps the mid-color saturated
+        let oklch = lerp_color_in(blue, yellow, 0.5, ColorSpace::Oklch);
+        assert!(oklch.to_oklch()[1] > 0.1);
+
+        let end = lerp_color_in(blue, yellow, 1.0, ColorSpace::Oklch);
+        assert!((end.r - 1.0).abs() < 1e-3 && (end.b - 0.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_lerp_corner_shape_per_corner() {
+        use crate::layout::Size;
+
+        let a = CornerShape::Round(Size::lpx(10.0));
+        let b = CornerShape::PerCorner(Corners::top(CornerKind::Round(Size::lpx(20.0))));
+        let mid = lerp_corner_shape(a, b, 0.5);
+
+        let CornerShape::PerCorner(corners) = mid else {
+            panic!("expected per-corner shape, got {mid:?}");
+        };
+        assert_eq!(corners.top_left, CornerKind::Round(Size::lpx(15.0)));
+        assert_eq!(corners.bottom_left, CornerKind::Round(Size::lpx(5.0)));
+    }
+
+    #[test]
+    fn test_lerp_conic_gradient_sweeps_matching_stops() {
+        let (fill, track) = (Color::rgb(0.0, 0.5, 1.0), Color::rgb(0.2, 0.2, 0.2));
+        let from = ConicGradient::progress(0.2, fill, track);
+        let to = ConicGradient::progress(0.6, fill, track);
+
+        let mid = lerp_conic_gradient(&from, &to, 0.5, ColorSpace::Oklab);
+        assert!((mid.stops[1].0 - 0.4).abs() < 1e-5);
+        assert!((mid.stops[2].0 - 0.4).abs() < 1e-5);
+
+        // Different stop counts can't be matched up and snap halfway
+        let wheel = ConicGradient::color_wheel();
+        assert_eq!(
+            lerp_conic_gradient(&from, &wheel, 0.4, ColorSpace::Oklab),
+            from
+        );
+        assert_eq!(
+            lerp_conic_gradient(&from, &wheel, 0.6, ColorSpace::Oklab),
+            wheel
+        );
+    }
 }